@@ -0,0 +1,107 @@
+//! Captures build metadata (git commit, build date, rustc, features) at compile time.
+//!
+//! The values are exposed to the crate as `TETRAD_*` environment variables and
+//! read back by `tetrad::types::build::BuildInfo`.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    let git_commit = git(&["rev-parse", "--short=12", "HEAD"]).unwrap_or_else(unknown);
+    let git_dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .map(|status| !status.is_empty())
+        .unwrap_or(false);
+    let git_commit = if git_dirty {
+        format!("{}-dirty", git_commit)
+    } else {
+        git_commit
+    };
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .filter(|version| !version.is_empty())
+        .unwrap_or_else(unknown);
+
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|feature| feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort();
+
+    println!("cargo:rustc-env=TETRAD_GIT_COMMIT={}", git_commit);
+    println!("cargo:rustc-env=TETRAD_BUILD_DATE={}", build_date());
+    println!("cargo:rustc-env=TETRAD_RUSTC_VERSION={}", rustc_version);
+    println!("cargo:rustc-env=TETRAD_FEATURES={}", features.join(","));
+    println!(
+        "cargo:rustc-env=TETRAD_TARGET={}",
+        std::env::var("TARGET").unwrap_or_else(|_| unknown())
+    );
+
+    // Re-run when the checked-out commit changes, not on every source edit
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    for git_path in ["HEAD", "index"] {
+        rerun_if_git_path_changed(git_path);
+    }
+    if let Some(reference) = git(&["symbolic-ref", "-q", "HEAD"]) {
+        rerun_if_git_path_changed(&reference);
+    }
+}
+
+fn unknown() -> String {
+    "unknown".to_string()
+}
+
+/// Runs a git command, returning its trimmed stdout on success.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|out| out.trim().to_string())
+}
+
+fn rerun_if_git_path_changed(git_path: &str) {
+    if let Some(path) = git(&["rev-parse", "--git-path", git_path]) {
+        if Path::new(&path).exists() {
+            println!("cargo:rerun-if-changed={}", path);
+        }
+    }
+}
+
+/// Build date (UTC, `YYYY-MM-DD`), honoring `SOURCE_DATE_EPOCH` for reproducible builds.
+fn build_date() -> String {
+    let secs = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        });
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -1,6 +1,7 @@
 //! Cache LRU para resultados de avaliação.
 
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
@@ -8,9 +9,13 @@ use chrono::{DateTime, Utc};
 use lru::LruCache;
 use sha2::{Digest, Sha256};
 
+use crate::types::config::{CacheConfig, CacheTtlPerType};
+use crate::types::language::Language;
 use crate::types::requests::EvaluationType;
 use crate::types::responses::EvaluationResult;
 
+use super::persist::{CacheFile, PersistedEntry};
+
 /// Resultado em cache.
 #[derive(Debug, Clone)]
 pub struct CachedResult {
@@ -19,27 +24,44 @@ pub struct CachedResult {
 
     /// Momento em que foi cacheado.
     pub cached_at: DateTime<Utc>,
+
+    /// Tempo de vida desta entrada, fixado no momento da inserção.
+    pub ttl: Duration,
 }
 
 impl CachedResult {
     /// Cria um novo resultado em cache.
-    pub fn new(result: EvaluationResult) -> Self {
+    pub fn new(result: EvaluationResult, ttl: Duration) -> Self {
+        Self::new_at(result, ttl, Utc::now())
+    }
+
+    /// Cria um resultado em cache com o momento de inserção explícito.
+    pub fn new_at(result: EvaluationResult, ttl: Duration, cached_at: DateTime<Utc>) -> Self {
         Self {
             result,
-            cached_at: Utc::now(),
+            cached_at,
+            ttl,
         }
     }
 
     /// Verifica se o cache expirou.
-    pub fn is_expired(&self, ttl: Duration) -> bool {
-        let elapsed = Utc::now()
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Utc::now())
+    }
+
+    /// Verifica se o cache estará expirado no momento `now`.
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        let elapsed = now
             .signed_duration_since(self.cached_at)
             .to_std()
             .unwrap_or(Duration::MAX);
-        elapsed >= ttl
+        elapsed >= self.ttl
     }
 }
 
+/// Relógio usado para calcular a expiração das entradas.
+pub type Clock = Box<dyn Fn() -> DateTime<Utc> + Send + Sync>;
+
 /// Estatísticas do cache.
 #[derive(Debug, Clone, Default)]
 pub struct CacheStats {
@@ -54,6 +76,12 @@ pub struct CacheStats {
 
     /// Número de erros (cache misses).
     pub misses: u64,
+
+    /// Entradas já expiradas (cada uma pelo seu próprio TTL) ainda não removidas.
+    pub expired: usize,
+
+    /// Momento de inserção da entrada mais antiga.
+    pub oldest_entry: Option<DateTime<Utc>>,
 }
 
 impl CacheStats {
@@ -72,6 +100,9 @@ impl CacheStats {
 pub struct EvaluationCache {
     cache: LruCache<String, CachedResult>,
     ttl: Duration,
+    ttl_per_type: CacheTtlPerType,
+    clock: Clock,
+    file: Option<CacheFile>,
     hits: AtomicU64,
     misses: AtomicU64,
 }
@@ -81,17 +112,97 @@ impl EvaluationCache {
     ///
     /// # Argumentos
     /// - `capacity`: Número máximo de entradas
-    /// - `ttl`: Tempo de vida das entradas
+    /// - `ttl`: Tempo de vida padrão das entradas
     pub fn new(capacity: usize, ttl: Duration) -> Self {
         let cap = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(100).unwrap());
         Self {
             cache: LruCache::new(cap),
             ttl,
+            ttl_per_type: CacheTtlPerType::default(),
+            clock: Box::new(Utc::now),
+            file: None,
             hits: AtomicU64::new(0),
             misses: AtomicU64::new(0),
         }
     }
 
+    /// Cria o cache a partir da seção `[cache]` da configuração.
+    pub fn from_config(config: &CacheConfig) -> Self {
+        let cache = Self::new(config.capacity, Duration::from_secs(config.ttl_secs))
+            .with_ttl_per_type(config.ttl_per_type.clone());
+        match &config.persist_path {
+            Some(path) => cache.with_persistence(path),
+            None => cache,
+        }
+    }
+
+    /// Persiste as entradas em `path` e carrega as que ainda são válidas.
+    ///
+    /// Entradas expiradas são descartadas e o arquivo é compactado. Falhas de
+    /// leitura são registradas e o cache começa vazio.
+    pub fn with_persistence(mut self, path: impl Into<PathBuf>) -> Self {
+        let file = CacheFile::new(path);
+        let now = (self.clock)();
+
+        match file.load() {
+            Ok(entries) => {
+                if let Some(last) = entries.last() {
+                    self.hits.store(last.hits, Ordering::Relaxed);
+                    self.misses.store(last.misses, Ordering::Relaxed);
+                }
+                for entry in entries {
+                    let ttl = Duration::from_secs(entry.ttl_secs);
+                    let cached = CachedResult::new_at(entry.result, ttl, entry.cached_at);
+                    if cached.is_expired_at(now) {
+                        // Uma versão mais nova pode ter expirado; remove a antiga também
+                        self.cache.pop(&entry.key);
+                    } else {
+                        self.cache.put(entry.key, cached);
+                    }
+                }
+                if let Err(e) = file.rewrite(&self.persisted_entries()) {
+                    tracing::warn!(error = %e, "Failed to compact cache file");
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to load cache file"),
+        }
+
+        self.file = Some(file);
+        self
+    }
+
+    /// Define o tempo de vida por tipo de avaliação.
+    ///
+    /// Só afeta entradas inseridas depois; as existentes mantêm o TTL com que
+    /// foram cacheadas.
+    pub fn with_ttl_per_type(mut self, ttl_per_type: CacheTtlPerType) -> Self {
+        self.ttl_per_type = ttl_per_type;
+        self
+    }
+
+    /// Aplica capacidade e TTLs de uma nova seção `[cache]`.
+    ///
+    /// Aumentar a capacidade preserva todas as entradas; reduzir descarta as
+    /// menos usadas. Os TTLs só afetam entradas inseridas depois. A
+    /// persistência não muda: `persist_path` só é lido na inicialização.
+    pub fn reconfigure(&mut self, config: &CacheConfig) {
+        let cap = NonZeroUsize::new(config.capacity).unwrap_or(NonZeroUsize::new(100).unwrap());
+        self.cache.resize(cap);
+        self.ttl = Duration::from_secs(config.ttl_secs);
+        self.ttl_per_type = config.ttl_per_type.clone();
+    }
+
+    /// Substitui o relógio usado para inserção e expiração (útil em testes).
+    pub fn with_clock(mut self, clock: impl Fn() -> DateTime<Utc> + Send + Sync + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Retorna o tempo de vida aplicado a um tipo de avaliação.
+    pub fn ttl_for(&self, eval_type: &EvaluationType) -> Duration {
+        self.ttl_per_type.get(eval_type).unwrap_or(self.ttl)
+    }
+
     /// Cria um cache com configuração padrão.
     pub fn default_config() -> Self {
         Self::new(100, Duration::from_secs(300)) // 5 minutos TTL
@@ -99,7 +210,8 @@ impl EvaluationCache {
 
     /// Gera uma chave de cache baseada no código.
     ///
-    /// A chave é um hash SHA256 do código normalizado + linguagem + tipo de avaliação.
+    /// A chave é um hash SHA256 do código normalizado + linguagem (id
+    /// canônico, veja [`Language`]) + tipo de avaliação.
     pub fn cache_key(code: &str, language: &str, eval_type: &EvaluationType) -> String {
         let normalized = Self::normalize_code(code);
         let eval_type_str = match eval_type {
@@ -107,11 +219,13 @@ impl EvaluationCache {
             EvaluationType::Code => "code",
             EvaluationType::Tests => "tests",
             EvaluationType::FinalCheck => "final",
+            EvaluationType::Documentation => "docs",
+            EvaluationType::Diff => "diff",
         };
 
         let mut hasher = Sha256::new();
         hasher.update(normalized.as_bytes());
-        hasher.update(language.as_bytes());
+        hasher.update(Language::canonical(language).as_bytes());
         hasher.update(eval_type_str.as_bytes());
 
         hex::encode(hasher.finalize())
@@ -131,7 +245,8 @@ impl EvaluationCache {
     /// Retorna `None` se não encontrado ou se expirado.
     pub fn get(&mut self, key: &str) -> Option<&EvaluationResult> {
         // Primeiro verifica se existe e se está expirado (usando peek para não alterar LRU)
-        let is_expired = self.cache.peek(key).map(|c| c.is_expired(self.ttl));
+        let now = (self.clock)();
+        let is_expired = self.cache.peek(key).map(|c| c.is_expired_at(now));
 
         match is_expired {
             Some(true) => {
@@ -153,6 +268,13 @@ impl EvaluationCache {
         }
     }
 
+    /// Verifica se há uma entrada válida para a chave, sem contar acerto ou
+    /// erro nem alterar a ordem LRU.
+    pub fn contains(&self, key: &str) -> bool {
+        let now = (self.clock)();
+        self.cache.peek(key).is_some_and(|c| !c.is_expired_at(now))
+    }
+
     /// Busca por código (gera a chave automaticamente).
     pub fn get_by_code(
         &mut self,
@@ -164,9 +286,25 @@ impl EvaluationCache {
         self.get(&key)
     }
 
-    /// Insere no cache.
+    /// Insere no cache com o TTL padrão.
     pub fn insert(&mut self, key: String, result: EvaluationResult) {
-        self.cache.put(key, CachedResult::new(result));
+        self.insert_with_ttl(key, result, self.ttl);
+    }
+
+    /// Insere no cache com um TTL específico.
+    ///
+    /// Com persistência, um feedback pendente é renderizado antes da gravação.
+    pub fn insert_with_ttl(&mut self, key: String, result: EvaluationResult, ttl: Duration) {
+        let result = if self.file.is_some() {
+            result.with_materialized_feedback()
+        } else {
+            result
+        };
+        let cached = CachedResult::new_at(result, ttl, (self.clock)());
+        if let Some(file) = &self.file {
+            file.append(self.persisted_entry(&key, &cached));
+        }
+        self.cache.put(key, cached);
     }
 
     /// Insere por código (gera a chave automaticamente).
@@ -178,62 +316,102 @@ impl EvaluationCache {
         result: EvaluationResult,
     ) {
         let key = Self::cache_key(code, language, eval_type);
-        self.insert(key, result);
+        let ttl = self.ttl_for(eval_type);
+        self.insert_with_ttl(key, result, ttl);
     }
 
     /// Invalida uma entrada específica.
     pub fn invalidate(&mut self, key: &str) {
-        self.cache.pop(key);
+        if self.cache.pop(key).is_some() {
+            self.persist_all();
+        }
     }
 
-    /// Limpa todo o cache.
+    /// Limpa todo o cache, incluindo o arquivo persistido.
     pub fn clear(&mut self) {
         self.cache.clear();
+        if let Some(file) = &self.file {
+            if let Err(e) = file.clear() {
+                tracing::warn!(error = %e, "Failed to remove cache file");
+            }
+        }
     }
 
     /// Retorna estatísticas do cache.
     pub fn stats(&self) -> CacheStats {
+        let now = (self.clock)();
         CacheStats {
             size: self.cache.len(),
             capacity: self.cache.cap().get(),
             hits: self.hits.load(Ordering::Relaxed),
             misses: self.misses.load(Ordering::Relaxed),
+            expired: self
+                .cache
+                .iter()
+                .filter(|(_, v)| v.is_expired_at(now))
+                .count(),
+            oldest_entry: self.cache.iter().map(|(_, v)| v.cached_at).min(),
         }
     }
 
     /// Remove entradas expiradas.
     pub fn cleanup_expired(&mut self) {
-        // Coleta chaves expiradas
+        // Coleta chaves expiradas, cada uma pelo seu próprio TTL
+        let now = (self.clock)();
         let expired_keys: Vec<String> = self
             .cache
             .iter()
-            .filter(|(_, v)| v.is_expired(self.ttl))
+            .filter(|(_, v)| v.is_expired_at(now))
             .map(|(k, _)| k.clone())
             .collect();
 
+        if expired_keys.is_empty() {
+            return;
+        }
+
         // Remove cada uma
         for key in expired_keys {
             self.cache.pop(&key);
         }
+        self.persist_all();
+    }
+
+    /// Regrava o arquivo persistido com as entradas atuais, sem bloquear.
+    fn persist_all(&self) {
+        if let Some(file) = &self.file {
+            file.rewrite_in_background(self.persisted_entries());
+        }
+    }
+
+    /// Entradas atuais no formato persistido, da menos para a mais recente.
+    fn persisted_entries(&self) -> Vec<PersistedEntry> {
+        self.cache
+            .iter()
+            .rev()
+            .map(|(key, cached)| self.persisted_entry(key, cached))
+            .collect()
+    }
+
+    fn persisted_entry(&self, key: &str, cached: &CachedResult) -> PersistedEntry {
+        PersistedEntry {
+            key: key.to_string(),
+            result: cached.result.clone(),
+            cached_at: cached.cached_at,
+            ttl_secs: cached.ttl.as_secs(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::responses::Decision;
+    use crate::test_support::ResultBuilder;
+    use std::sync::{Arc, Mutex};
 
     fn create_test_result() -> EvaluationResult {
-        EvaluationResult {
-            request_id: "test-123".to_string(),
-            decision: Decision::Pass,
-            score: 85,
-            consensus_achieved: true,
-            votes: std::collections::HashMap::new(),
-            findings: vec![],
-            feedback: "Test feedback".to_string(),
-            timestamp: Utc::now(),
-        }
+        ResultBuilder::pass(85).build()
     }
 
     #[test]
@@ -258,6 +436,43 @@ mod tests {
         assert_eq!(key1, key2);
     }
 
+    #[test]
+    fn test_cache_key_language_aliases() {
+        let key = EvaluationCache::cache_key("fn main() {}", "rust", &EvaluationType::Code);
+
+        // Grafias da mesma linguagem compartilham a entrada
+        for alias in ["Rust", "rs", " RUST "] {
+            assert_eq!(
+                EvaluationCache::cache_key("fn main() {}", alias, &EvaluationType::Code),
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn test_cache_key_documentation() {
+        let code_key = EvaluationCache::cache_key("# Usage", "markdown", &EvaluationType::Code);
+        let docs_key =
+            EvaluationCache::cache_key("# Usage", "markdown", &EvaluationType::Documentation);
+
+        // Mesmo texto revisado como docs = chave diferente
+        assert_ne!(code_key, docs_key);
+    }
+
+    #[test]
+    fn test_cache_key_diff() {
+        let diff_a = "@@ -1 +1 @@\n-let x = 1;\n+let x = 2;";
+        let diff_b = "@@ -1 +1 @@\n-let x = 1;\n+let x = 3;";
+
+        let key_a = EvaluationCache::cache_key(diff_a, "rust", &EvaluationType::Diff);
+        let key_b = EvaluationCache::cache_key(diff_b, "rust", &EvaluationType::Diff);
+        let code_key = EvaluationCache::cache_key(diff_a, "rust", &EvaluationType::Code);
+
+        // O conteúdo do diff faz parte da chave
+        assert_ne!(key_a, key_b);
+        assert_ne!(key_a, code_key);
+    }
+
     #[test]
     fn test_cache_hit() {
         let mut cache = EvaluationCache::new(10, Duration::from_secs(60));
@@ -359,6 +574,38 @@ mod tests {
         assert!((stats.hit_rate() - 0.666).abs() < 0.01);
     }
 
+    #[test]
+    fn test_reconfigure_keeps_entries_when_growing() {
+        let mut cache = EvaluationCache::new(2, Duration::from_secs(60));
+        cache.insert("key1".to_string(), create_test_result());
+        cache.insert("key2".to_string(), create_test_result());
+
+        let config = CacheConfig {
+            capacity: 10,
+            ttl_secs: 600,
+            ..CacheConfig::default()
+        };
+        cache.reconfigure(&config);
+        cache.insert("key3".to_string(), create_test_result());
+
+        let stats = cache.stats();
+        assert_eq!(stats.capacity, 10);
+        assert_eq!(stats.size, 3);
+        assert!(cache.contains("key1"));
+        assert_eq!(
+            cache.ttl_for(&EvaluationType::Code),
+            Duration::from_secs(600)
+        );
+
+        // Reduzir descarta as menos usadas
+        cache.reconfigure(&CacheConfig {
+            capacity: 1,
+            ..config
+        });
+        assert_eq!(cache.stats().size, 1);
+        assert!(cache.contains("key3"));
+    }
+
     #[test]
     fn test_insert_by_code() {
         let mut cache = EvaluationCache::new(10, Duration::from_secs(60));
@@ -373,12 +620,209 @@ mod tests {
     #[test]
     fn test_cached_result_is_expired() {
         let result = create_test_result();
-        let cached = CachedResult::new(result);
 
         // Com TTL de 1 hora, não deve estar expirado
-        assert!(!cached.is_expired(Duration::from_secs(3600)));
+        let cached = CachedResult::new(result.clone(), Duration::from_secs(3600));
+        assert!(!cached.is_expired());
 
         // Com TTL de 0, deve estar expirado
-        assert!(cached.is_expired(Duration::from_secs(0)));
+        let cached = CachedResult::new(result, Duration::from_secs(0));
+        assert!(cached.is_expired());
+    }
+
+    /// Relógio controlável: avança apenas quando o teste manda.
+    fn manual_clock() -> (Arc<Mutex<DateTime<Utc>>>, impl Fn() -> DateTime<Utc>) {
+        let now = Arc::new(Mutex::new(Utc::now()));
+        let clock = now.clone();
+        (now, move || *clock.lock().unwrap())
+    }
+
+    fn advance(now: &Mutex<DateTime<Utc>>, secs: i64) {
+        *now.lock().unwrap() += chrono::Duration::seconds(secs);
+    }
+
+    fn ttl_per_type() -> CacheTtlPerType {
+        CacheTtlPerType {
+            plan: Some(60),
+            final_check: Some(3600),
+            ..CacheTtlPerType::default()
+        }
+    }
+
+    #[test]
+    fn test_ttl_per_type_expires_entries_independently() {
+        let (now, clock) = manual_clock();
+        let mut cache = EvaluationCache::new(10, Duration::from_secs(300))
+            .with_ttl_per_type(ttl_per_type())
+            .with_clock(clock);
+        let result = create_test_result();
+
+        for eval_type in [
+            EvaluationType::Plan,
+            EvaluationType::Code,
+            EvaluationType::FinalCheck,
+        ] {
+            cache.insert_by_code("fn main() {}", "rust", &eval_type, result.clone());
+        }
+        assert_eq!(
+            cache.ttl_for(&EvaluationType::Code),
+            Duration::from_secs(300)
+        );
+
+        // Plano expira em 60s
+        advance(&now, 61);
+        assert!(cache
+            .get_by_code("fn main() {}", "rust", &EvaluationType::Plan)
+            .is_none());
+        assert!(cache
+            .get_by_code("fn main() {}", "rust", &EvaluationType::Code)
+            .is_some());
+
+        // Código usa o TTL padrão de 300s
+        advance(&now, 240);
+        assert!(cache
+            .get_by_code("fn main() {}", "rust", &EvaluationType::Code)
+            .is_none());
+        assert!(cache
+            .get_by_code("fn main() {}", "rust", &EvaluationType::FinalCheck)
+            .is_some());
+
+        // Final check dura 1 hora
+        advance(&now, 3300);
+        assert!(cache
+            .get_by_code("fn main() {}", "rust", &EvaluationType::FinalCheck)
+            .is_none());
+    }
+
+    #[test]
+    fn test_cleanup_and_stats_use_entry_ttl() {
+        let (now, clock) = manual_clock();
+        let mut cache = EvaluationCache::new(10, Duration::from_secs(300))
+            .with_ttl_per_type(ttl_per_type())
+            .with_clock(clock);
+        let result = create_test_result();
+
+        cache.insert_by_code("plan", "text", &EvaluationType::Plan, result.clone());
+        cache.insert_by_code("code", "rust", &EvaluationType::Code, result);
+
+        advance(&now, 120);
+        let stats = cache.stats();
+        assert_eq!(stats.size, 2);
+        assert_eq!(stats.expired, 1);
+
+        cache.cleanup_expired();
+        let stats = cache.stats();
+        assert_eq!(stats.size, 1);
+        assert_eq!(stats.expired, 0);
+        assert!(cache
+            .get_by_code("code", "rust", &EvaluationType::Code)
+            .is_some());
+    }
+
+    #[test]
+    fn test_config_change_does_not_affect_existing_entries() {
+        let (now, clock) = manual_clock();
+        let mut cache = EvaluationCache::new(10, Duration::from_secs(300)).with_clock(clock);
+
+        cache.insert_by_code("plan", "text", &EvaluationType::Plan, create_test_result());
+
+        // TTL menor configurado depois da inserção não vale retroativamente
+        cache = cache.with_ttl_per_type(ttl_per_type());
+        advance(&now, 120);
+        assert!(cache
+            .get_by_code("plan", "text", &EvaluationType::Plan)
+            .is_some());
+    }
+
+    #[test]
+    fn test_persistence_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.jsonl");
+
+        let mut cache = EvaluationCache::new(10, Duration::from_secs(300)).with_persistence(&path);
+        cache.insert_by_code(
+            "fn main() {}",
+            "rust",
+            &EvaluationType::Code,
+            create_test_result(),
+        );
+        cache.get_by_code("fn main() {}", "rust", &EvaluationType::Code);
+        cache.insert("other".to_string(), create_test_result());
+
+        let mut reloaded =
+            EvaluationCache::new(10, Duration::from_secs(300)).with_persistence(&path);
+        let stats = reloaded.stats();
+        assert_eq!(stats.size, 2);
+        assert_eq!(stats.hits, 1);
+        assert!(stats.oldest_entry.is_some());
+
+        let cached = reloaded.get_by_code("fn main() {}", "rust", &EvaluationType::Code);
+        assert_eq!(cached.unwrap().request_id, "test-123");
+    }
+
+    #[test]
+    fn test_persistence_skips_expired_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.jsonl");
+        let (now, clock) = manual_clock();
+
+        let mut cache = EvaluationCache::new(10, Duration::from_secs(300))
+            .with_ttl_per_type(ttl_per_type())
+            .with_persistence(&path);
+        cache.insert_by_code("plan", "text", &EvaluationType::Plan, create_test_result());
+        cache.insert_by_code("code", "rust", &EvaluationType::Code, create_test_result());
+
+        // Plano (60s) expirou; código (300s) continua válido
+        advance(&now, 120);
+        let mut reloaded = EvaluationCache::new(10, Duration::from_secs(300))
+            .with_clock(clock)
+            .with_persistence(&path);
+        assert_eq!(reloaded.stats().size, 1);
+        assert!(reloaded
+            .get_by_code("code", "rust", &EvaluationType::Code)
+            .is_some());
+
+        // O arquivo foi compactado
+        let lines = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(lines.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_persistence_materializes_pending_feedback() {
+        use crate::types::responses::PendingFeedback;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.jsonl");
+        let mut result = create_test_result();
+        result.feedback = "\nnota".to_string();
+        result.pending_feedback = Some(PendingFeedback::new(|r| format!("score {}", r.score)));
+
+        // Em memória, o feedback continua pendente
+        let mut memory = EvaluationCache::new(10, Duration::from_secs(300));
+        memory.insert("key".to_string(), result.clone());
+        assert!(memory.get("key").unwrap().pending_feedback.is_some());
+
+        let mut cache = EvaluationCache::new(10, Duration::from_secs(300)).with_persistence(&path);
+        cache.insert("key".to_string(), result);
+
+        let mut reloaded =
+            EvaluationCache::new(10, Duration::from_secs(300)).with_persistence(&path);
+        assert_eq!(reloaded.get("key").unwrap().feedback, "score 85\nnota");
+    }
+
+    #[test]
+    fn test_clear_removes_persisted_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("cache.jsonl");
+
+        let mut cache = EvaluationCache::new(10, Duration::from_secs(300)).with_persistence(&path);
+        cache.insert("key1".to_string(), create_test_result());
+        assert!(path.exists());
+
+        cache.clear();
+        assert!(!path.exists());
+
+        let reloaded = EvaluationCache::new(10, Duration::from_secs(300)).with_persistence(&path);
+        assert_eq!(reloaded.stats().size, 0);
     }
 }
@@ -3,7 +3,17 @@
 //! Este módulo implementa um cache Least Recently Used (LRU) para
 //! armazenar resultados de avaliações recentes, evitando reavaliações
 //! desnecessárias do mesmo código.
+//!
+//! Com `cache.persist_path` configurado, as entradas também são gravadas em
+//! disco e sobrevivem a reinícios do servidor.
+//!
+//! Com `[cache.votes]` habilitado, os votos de cada executor também são
+//! guardados pela chave (executor, prompt), evitando chamar de novo os
+//! executores cujo prompt não mudou.
 
 mod lru;
+mod persist;
+mod votes;
 
 pub use lru::{CacheStats, CachedResult, EvaluationCache};
+pub use votes::{CachedVoteExecutor, VoteCache};
@@ -0,0 +1,157 @@
+//! Persistência do cache em disco.
+//!
+//! As entradas são gravadas em JSON Lines: cada inserção acrescenta uma linha
+//! ao arquivo, fora do caminho crítico da avaliação. Ao carregar, a última
+//! linha de cada chave vence, entradas expiradas são descartadas e o arquivo
+//! é compactado.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::responses::EvaluationResult;
+
+/// Entrada do cache como gravada em disco.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct PersistedEntry {
+    /// Chave do cache.
+    pub key: String,
+
+    /// Resultado da avaliação.
+    pub result: EvaluationResult,
+
+    /// Momento em que foi cacheado.
+    pub cached_at: DateTime<Utc>,
+
+    /// Tempo de vida da entrada, em segundos.
+    pub ttl_secs: u64,
+
+    /// Acertos acumulados no momento da gravação.
+    #[serde(default)]
+    pub hits: u64,
+
+    /// Erros acumulados no momento da gravação.
+    #[serde(default)]
+    pub misses: u64,
+}
+
+/// Arquivo JSON Lines com as entradas do cache.
+#[derive(Debug, Clone)]
+pub(super) struct CacheFile {
+    path: PathBuf,
+}
+
+impl CacheFile {
+    /// Cria o acesso ao arquivo (não toca o disco).
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Lê todas as entradas, na ordem do arquivo.
+    ///
+    /// Arquivo inexistente resulta em lista vazia; linhas inválidas são ignoradas.
+    pub fn load(&self) -> io::Result<Vec<PersistedEntry>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(entry) => entries.push(entry),
+                Err(e) => tracing::warn!(
+                    path = %self.path.display(),
+                    error = %e,
+                    "Skipping invalid cache entry"
+                ),
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Reescreve o arquivo apenas com as entradas dadas.
+    ///
+    /// Grava num arquivo temporário e renomeia, para não deixar o cache
+    /// truncado se o processo for interrompido.
+    pub fn rewrite(&self, entries: &[PersistedEntry]) -> io::Result<()> {
+        rewrite_file(&self.path, entries)
+    }
+
+    /// Acrescenta uma entrada sem bloquear o chamador.
+    ///
+    /// Dentro de um runtime tokio a escrita roda numa tarefa bloqueante;
+    /// fora dele, é feita imediatamente.
+    pub fn append(&self, entry: PersistedEntry) {
+        let path = self.path.clone();
+        self.run_in_background(move || append_line(&path, &entry));
+    }
+
+    /// Reescreve o arquivo sem bloquear o chamador.
+    pub fn rewrite_in_background(&self, entries: Vec<PersistedEntry>) {
+        let path = self.path.clone();
+        self.run_in_background(move || rewrite_file(&path, &entries));
+    }
+
+    /// Remove o arquivo.
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn run_in_background(&self, write: impl FnOnce() -> io::Result<()> + Send + 'static) {
+        let path = self.path.clone();
+        let task = move || {
+            if let Err(e) = write() {
+                tracing::warn!(path = %path.display(), error = %e, "Failed to persist cache");
+            }
+        };
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn_blocking(task);
+            }
+            Err(_) => task(),
+        }
+    }
+}
+
+fn create_parent(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => fs::create_dir_all(parent),
+        _ => Ok(()),
+    }
+}
+
+fn append_line(path: &Path, entry: &PersistedEntry) -> io::Result<()> {
+    create_parent(path)?;
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())
+}
+
+fn rewrite_file(path: &Path, entries: &[PersistedEntry]) -> io::Result<()> {
+    create_parent(path)?;
+    let tmp = path.with_extension("tmp");
+    {
+        let mut file = io::BufWriter::new(fs::File::create(&tmp)?);
+        for entry in entries {
+            serde_json::to_writer(&mut file, entry)?;
+            file.write_all(b"\n")?;
+        }
+        file.flush()?;
+    }
+    fs::rename(&tmp, path)
+}
@@ -0,0 +1,340 @@
+//! Cache de votos individuais por executor.
+//!
+//! Complementa o cache de resultados: cada voto é guardado pela chave
+//! (executor, hash do prompt), de modo que numa nova revisão só os
+//! executores cujo prompt mudou são chamados de novo. O consenso é sempre
+//! recalculado a partir dos votos, em cache ou não.
+
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use lru::LruCache;
+use sha2::{Digest, Sha256};
+
+use crate::executors::{CliExecutor, ExecutorHealth, PromptTemplates};
+use crate::types::config::VoteCacheConfig;
+use crate::types::requests::EvaluationRequest;
+use crate::types::responses::ModelVote;
+use crate::TetradResult;
+
+use super::lru::Clock;
+use super::CacheStats;
+
+/// Voto em cache.
+#[derive(Debug, Clone)]
+struct CachedVote {
+    vote: ModelVote,
+    cached_at: DateTime<Utc>,
+}
+
+/// Cache LRU de votos por (executor, prompt).
+///
+/// Usa um `Mutex` interno para poder ser consultado pelos executores
+/// enquanto os assentos votam em paralelo.
+pub struct VoteCache {
+    cache: Mutex<LruCache<String, CachedVote>>,
+    ttl: Duration,
+    clock: Clock,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl VoteCache {
+    /// Cria um novo cache de votos.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        let cap = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(100).unwrap());
+        Self {
+            cache: Mutex::new(LruCache::new(cap)),
+            ttl,
+            clock: Box::new(Utc::now),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Cria o cache a partir da seção `[cache.votes]`, ou `None` se desabilitado.
+    pub fn from_config(config: &VoteCacheConfig) -> Option<Self> {
+        config
+            .enabled
+            .then(|| Self::new(config.capacity, Duration::from_secs(config.ttl_secs)))
+    }
+
+    /// Substitui o relógio usado para inserção e expiração (útil em testes).
+    pub fn with_clock(mut self, clock: impl Fn() -> DateTime<Utc> + Send + Sync + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Gera a chave de um voto: hash SHA256 do nome do executor + prompt.
+    pub fn key(executor: &str, prompt: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(executor.as_bytes());
+        hasher.update([0]);
+        hasher.update(prompt.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Busca um voto; `None` se não encontrado ou expirado.
+    pub fn get(&self, key: &str) -> Option<ModelVote> {
+        let now = (self.clock)();
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+
+        let expired = cache.peek(key).map(|c| self.is_expired(c, now));
+        match expired {
+            Some(false) => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                cache.get(key).map(|c| c.vote.clone())
+            }
+            Some(true) => {
+                cache.pop(key);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Verifica se há um voto válido para a chave, sem contar acerto ou erro.
+    pub fn contains(&self, key: &str) -> bool {
+        let now = (self.clock)();
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.peek(key).is_some_and(|c| !self.is_expired(c, now))
+    }
+
+    /// Guarda um voto.
+    pub fn insert(&self, key: String, vote: ModelVote) {
+        let cached = CachedVote {
+            vote,
+            cached_at: (self.clock)(),
+        };
+        self.cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .put(key, cached);
+    }
+
+    /// Remove todos os votos.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    /// Retorna estatísticas do cache de votos.
+    pub fn stats(&self) -> CacheStats {
+        let now = (self.clock)();
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        CacheStats {
+            size: cache.len(),
+            capacity: cache.cap().get(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            expired: cache
+                .iter()
+                .filter(|(_, c)| self.is_expired(c, now))
+                .count(),
+            oldest_entry: cache.iter().map(|(_, c)| c.cached_at).min(),
+        }
+    }
+
+    fn is_expired(&self, cached: &CachedVote, now: DateTime<Utc>) -> bool {
+        let elapsed = now
+            .signed_duration_since(cached.cached_at)
+            .to_std()
+            .unwrap_or(Duration::MAX);
+        elapsed >= self.ttl
+    }
+}
+
+/// Executor que consulta o [`VoteCache`] antes de chamar a CLI.
+///
+/// Sem cache, apenas repassa as chamadas ao executor original. Só votos
+/// bem-sucedidos são guardados; erros sempre chamam a CLI de novo. Com
+/// [`Self::with_health`], a duração das chamadas reais (não das respostas do
+/// cache) alimenta a média de latência do executor.
+pub struct CachedVoteExecutor<'a> {
+    inner: &'a dyn CliExecutor,
+    cache: Option<&'a VoteCache>,
+    health: Option<&'a ExecutorHealth>,
+}
+
+impl<'a> CachedVoteExecutor<'a> {
+    /// Envolve `inner` com o cache de votos.
+    pub fn new(inner: &'a dyn CliExecutor, cache: Option<&'a VoteCache>) -> Self {
+        Self {
+            inner,
+            cache,
+            health: None,
+        }
+    }
+
+    /// Registra a latência das avaliações bem-sucedidas em `health`.
+    pub fn with_health(mut self, health: &'a ExecutorHealth) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    async fn evaluate_timed(
+        &self,
+        request: &EvaluationRequest,
+        timeout: Option<Duration>,
+    ) -> TetradResult<ModelVote> {
+        let started = Instant::now();
+        let vote = match timeout {
+            Some(timeout) => self.inner.evaluate_with_timeout(request, timeout).await?,
+            None => self.inner.evaluate(request).await?,
+        };
+        if let Some(health) = self.health {
+            health.record_latency(self.inner.name(), started.elapsed());
+        }
+        Ok(vote)
+    }
+
+    async fn evaluate_cached(
+        &self,
+        request: &EvaluationRequest,
+        timeout: Option<Duration>,
+    ) -> TetradResult<ModelVote> {
+        let Some(cache) = self.cache else {
+            return self.evaluate_timed(request, timeout).await;
+        };
+
+        let key = VoteCache::key(self.inner.name(), &self.inner.build_prompt(request));
+        if let Some(vote) = cache.get(&key) {
+            tracing::debug!(executor = self.inner.name(), "Vote cache hit");
+            return Ok(vote);
+        }
+
+        // Um voto neutro no lugar de uma CLI ausente não vale reaproveitar
+        let vote = self.evaluate_timed(request, timeout).await?;
+        if !vote.synthetic {
+            cache.insert(key, vote.clone());
+        }
+        Ok(vote)
+    }
+}
+
+#[async_trait]
+impl CliExecutor for CachedVoteExecutor<'_> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn command(&self) -> &str {
+        self.inner.command()
+    }
+
+    async fn is_available(&self) -> bool {
+        self.inner.is_available().await
+    }
+
+    async fn version(&self) -> TetradResult<String> {
+        self.inner.version().await
+    }
+
+    async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
+        self.evaluate_cached(request, None).await
+    }
+
+    async fn evaluate_with_timeout(
+        &self,
+        request: &EvaluationRequest,
+        timeout: Duration,
+    ) -> TetradResult<ModelVote> {
+        self.evaluate_cached(request, Some(timeout)).await
+    }
+
+    fn specialization(&self) -> &str {
+        self.inner.specialization()
+    }
+
+    fn prompt_templates(&self) -> Option<&PromptTemplates> {
+        self.inner.prompt_templates()
+    }
+
+    fn build_prompt(&self, request: &EvaluationRequest) -> String {
+        self.inner.build_prompt(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::responses::Vote;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    struct CountingExecutor {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CliExecutor for CountingExecutor {
+        fn name(&self) -> &str {
+            "Codex"
+        }
+
+        fn command(&self) -> &str {
+            "mock"
+        }
+
+        async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ModelVote::new("Codex", Vote::Pass, 90))
+        }
+
+        fn specialization(&self) -> &str {
+            "test"
+        }
+    }
+
+    #[test]
+    fn test_key_depends_on_executor_and_prompt() {
+        let key = VoteCache::key("Codex", "prompt");
+        assert_eq!(key, VoteCache::key("Codex", "prompt"));
+        assert_ne!(key, VoteCache::key("Gemini", "prompt"));
+        assert_ne!(key, VoteCache::key("Codex", "other prompt"));
+    }
+
+    #[test]
+    fn test_vote_expires_after_ttl() {
+        let now = Arc::new(Mutex::new(Utc::now()));
+        let clock = now.clone();
+        let cache =
+            VoteCache::new(10, Duration::from_secs(60)).with_clock(move || *clock.lock().unwrap());
+
+        cache.insert("k".to_string(), ModelVote::new("Codex", Vote::Pass, 90));
+        assert!(cache.get("k").is_some());
+
+        *now.lock().unwrap() += chrono::Duration::seconds(61);
+        assert!(cache.get("k").is_none());
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.size, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cached_executor_skips_cli_for_same_prompt() {
+        let inner = CountingExecutor {
+            calls: AtomicUsize::new(0),
+        };
+        let cache = VoteCache::new(10, Duration::from_secs(60));
+        let executor = CachedVoteExecutor::new(&inner, Some(&cache));
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        executor.evaluate(&request).await.unwrap();
+        executor.evaluate(&request).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        let changed = EvaluationRequest::new("fn main() { run(); }", "rust");
+        executor.evaluate(&changed).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -2,12 +2,62 @@
 
 use std::path::{Path, PathBuf};
 
-use crate::executors::{CliExecutor, CodexExecutor, GeminiExecutor, QwenExecutor};
-use crate::types::config::Config;
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use super::{
+    style, CacheAction, ClaudeScope, HookAction, OutputFormat, PatternsAction, ReviewType,
+    TrendBucket, TrustAction,
+};
+use crate::consensus::{FeedbackTemplate, VoteWeights};
+use crate::executors::{
+    build_executors, run_canary, run_seat, scratch_root, seat_count, seat_weights,
+    slots as executor_slots, sweep_orphans, CanaryOutcome, CliExecutor, ConfiguredExecutor,
+    QuarantineList, ORPHAN_MAX_AGE, QUARANTINE_FILE_NAME,
+};
+use crate::reasoning::{
+    ExportOptions, PatternMatch, PeriodStats, ReasoningBank, StatsBucket, DIGEST_FILE_NAME,
+};
+use crate::types::build::BuildInfo;
+use crate::types::config::{Config, ConsensusRule, ExecutorConfig, PROJECT_DATA_DIR};
+use crate::types::language::Language;
+use crate::types::responses::{Decision, EvaluationResult, SuggestedFix};
 use crate::TetradResult;
 
+/// Prints progress to stdout in text mode, or to stderr when stdout carries JSON.
+macro_rules! progress {
+    ($json:expr, $($arg:tt)*) => {
+        if $json {
+            eprintln!($($arg)*);
+        } else {
+            outln!($($arg)*);
+        }
+    };
+}
+
+/// Machine-readable report emitted by `tetrad evaluate --format json`.
+#[derive(Debug, Serialize)]
+pub struct EvaluationReport<'a> {
+    /// Evaluation result (request_id, decision, score, votes, findings...).
+    #[serde(flatten)]
+    pub result: &'a EvaluationResult,
+
+    /// Evaluated language.
+    pub language: &'a str,
+
+    /// Source file, when evaluating with `@path`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<&'a str>,
+
+    /// ReasoningBank patterns that matched the code.
+    pub matched_patterns: &'a [PatternMatch],
+}
+
 /// Initializes configuration in the specified directory.
-pub async fn init(path: Option<PathBuf>) -> TetradResult<()> {
+///
+/// With `claude`, also registers `tetrad serve` as an MCP server in the
+/// Claude Code settings for that scope.
+pub async fn init(path: Option<PathBuf>, claude: Option<ClaudeScope>) -> TetradResult<()> {
     let target_dir = path.unwrap_or_else(|| PathBuf::from("."));
 
     // Create directory if it doesn't exist
@@ -16,84 +66,318 @@ pub async fn init(path: Option<PathBuf>) -> TetradResult<()> {
         tracing::info!("Directory created: {}", target_dir.display());
     }
 
-    let config_path = target_dir.join("tetrad.toml");
-
-    if config_path.exists() {
-        println!("Configuration already exists at: {}", config_path.display());
-        println!("Use 'tetrad config' to modify.");
-        return Ok(());
-    }
-
-    // Create .tetrad/ directory for the database
+    // Create .tetrad/ directory for the database and the init lock
     let tetrad_dir = target_dir.join(".tetrad");
     if !tetrad_dir.exists() {
         std::fs::create_dir_all(&tetrad_dir)?;
         tracing::info!(".tetrad/ directory created");
     }
 
+    // Concurrent inits (an npm postinstall and a user, say) run one at a time
+    let _lock = lock_init(&tetrad_dir)?;
+
     // Update .gitignore to ignore .tetrad/
     update_gitignore(&target_dir)?;
 
-    // Create default configuration
+    let config_path = target_dir.join("tetrad.toml");
+
+    if config_path.exists() {
+        outln!("Configuration already exists at: {}", config_path.display());
+        outln!("Use 'tetrad config' to modify.");
+        if let Some(scope) = claude {
+            register_with_claude(scope, &target_dir)?;
+        }
+        return Ok(());
+    }
+
+    // Create default configuration, checking it reads back before reporting success
     let config = Config::default_config();
     config.save(&config_path)?;
+    Config::load(&config_path).map_err(|e| {
+        crate::TetradError::config(format!(
+            "Configuration written to {} does not parse back: {}",
+            config_path.display(),
+            e
+        ))
+    })?;
+
+    outln!("Tetrad initialized successfully!");
+    outln!("Configuration created at: {}", config_path.display());
+    outln!("Data directory: .tetrad/");
+    if let Some(scope) = claude {
+        register_with_claude(scope, &target_dir)?;
+    }
+    outln!();
+    outln!("Next steps:");
+    outln!("  1. Check if CLIs are installed: tetrad status");
+    outln!("  2. Configure options: tetrad config");
+    if claude.is_none() {
+        outln!("  3. Add to Claude Code: tetrad init --claude");
+    }
+
+    Ok(())
+}
 
-    println!("Tetrad initialized successfully!");
-    println!("Configuration created at: {}", config_path.display());
-    println!("Data directory: .tetrad/");
-    println!();
-    println!("Next steps:");
-    println!("  1. Check if CLIs are installed: tetrad status");
-    println!("  2. Configure options: tetrad config");
-    println!("  3. Add to Claude Code: claude mcp add tetrad -- tetrad serve");
+/// Name of the MCP server entry written by `tetrad init --claude`.
+const CLAUDE_SERVER_NAME: &str = "tetrad";
+
+/// What `tetrad init --claude` did to the settings file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ClaudeRegistration {
+    /// The settings file did not exist and was created.
+    Created,
+    /// The entry was added to an existing file.
+    Added,
+    /// An existing entry pointed elsewhere and was replaced.
+    Updated,
+    /// The entry was already up to date; the file was not touched.
+    Unchanged,
+}
 
+/// Registers the running binary with Claude Code and reports the outcome.
+fn register_with_claude(scope: ClaudeScope, target_dir: &Path) -> TetradResult<()> {
+    let settings_path = claude_settings_path(scope, target_dir)?;
+    let exe = std::env::current_exe()?;
+    let exe = std::fs::canonicalize(&exe).unwrap_or(exe);
+
+    let outcome = register_claude_server(&settings_path, &exe)?;
+    let path = settings_path.display();
+    match outcome {
+        ClaudeRegistration::Created => outln!("Claude Code settings created at: {}", path),
+        ClaudeRegistration::Added => outln!("Tetrad registered in Claude Code settings: {}", path),
+        ClaudeRegistration::Updated => {
+            outln!("Tetrad entry updated in Claude Code settings: {}", path)
+        }
+        ClaudeRegistration::Unchanged => {
+            outln!(
+                "Tetrad is already registered in Claude Code settings: {}",
+                path
+            )
+        }
+    }
     Ok(())
 }
 
+/// Claude Code file holding MCP servers for `scope`.
+///
+/// Project servers live in `.mcp.json` at the project root; user servers in
+/// `~/.claude.json`, on every OS.
+fn claude_settings_path(scope: ClaudeScope, target_dir: &Path) -> TetradResult<PathBuf> {
+    match scope {
+        ClaudeScope::Project => Ok(target_dir.join(".mcp.json")),
+        ClaudeScope::User => dirs::home_dir()
+            .map(|home| home.join(".claude.json"))
+            .ok_or_else(|| crate::TetradError::config("Could not determine the home directory")),
+    }
+}
+
+/// Inserts or updates the `tetrad` entry under `mcpServers` in `settings_path`.
+///
+/// Other keys keep their order and the file keeps its indentation. A file
+/// that is not a JSON object is copied to `<file>.bak` and left untouched.
+fn register_claude_server(settings_path: &Path, exe: &Path) -> TetradResult<ClaudeRegistration> {
+    let entry = serde_json::json!({
+        "type": "stdio",
+        "command": exe.to_string_lossy(),
+        "args": ["serve"]
+    });
+
+    let existing = match std::fs::read_to_string(settings_path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let (mut settings, indent) = match &existing {
+        Some(content) => match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(settings @ serde_json::Value::Object(_))
+                if settings
+                    .get("mcpServers")
+                    .is_none_or(serde_json::Value::is_object) =>
+            {
+                (settings, json_indent(content))
+            }
+            parsed => {
+                let backup = backup_path(settings_path);
+                std::fs::copy(settings_path, &backup)?;
+                let reason = match parsed {
+                    Err(e) => e.to_string(),
+                    Ok(_) => "expected an object with an \"mcpServers\" object".to_string(),
+                };
+                return Err(crate::TetradError::config(format!(
+                    "Cannot register Tetrad: {} is not valid Claude Code settings ({}); \
+                     a copy was saved to {}",
+                    settings_path.display(),
+                    reason,
+                    backup.display()
+                )));
+            }
+        },
+        None => (serde_json::json!({}), "  ".to_string()),
+    };
+
+    let servers = settings
+        .as_object_mut()
+        .expect("settings checked to be an object")
+        .entry("mcpServers")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .expect("mcpServers checked to be an object");
+    let outcome = match servers.get(CLAUDE_SERVER_NAME) {
+        Some(current) if *current == entry => return Ok(ClaudeRegistration::Unchanged),
+        Some(_) => ClaudeRegistration::Updated,
+        None if existing.is_none() => ClaudeRegistration::Created,
+        None => ClaudeRegistration::Added,
+    };
+    servers.insert(CLAUDE_SERVER_NAME.to_string(), entry);
+
+    let mut content = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut content, formatter);
+    settings.serialize(&mut serializer)?;
+    if existing.as_deref().is_none_or(|c| c.ends_with('\n')) {
+        content.push(b'\n');
+    }
+
+    if let Some(parent) = settings_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    crate::types::config::write_atomic(settings_path, &content)?;
+    Ok(outcome)
+}
+
+/// Indentation of the first indented line in `content`, or two spaces.
+fn json_indent(content: &str) -> String {
+    content
+        .lines()
+        .skip(1)
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ")
+        .to_string()
+}
+
+/// `<path>.bak`, keeping the original extension.
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".bak");
+    path.with_file_name(name)
+}
+
+/// Takes an exclusive advisory lock on `.tetrad/.lock`, released when the
+/// returned file is dropped.
+///
+/// On platforms without file locking, init proceeds unlocked.
+fn lock_init(tetrad_dir: &Path) -> TetradResult<std::fs::File> {
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(tetrad_dir.join(".lock"))?;
+
+    match file.lock() {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::Unsupported => {
+            tracing::debug!("File locking unsupported, initializing without a lock");
+        }
+        Err(e) => return Err(e.into()),
+    }
+    Ok(file)
+}
+
+/// Comment written above the `.tetrad/` entry in `.gitignore`.
+const GITIGNORE_COMMENT: &str = "# Tetrad - local database and cache";
+
 /// Updates or creates .gitignore to include .tetrad/
 fn update_gitignore(target_dir: &Path) -> TetradResult<()> {
     let gitignore_path = target_dir.join(".gitignore");
-    let tetrad_entry = ".tetrad/";
-    let tetrad_comment = "# Tetrad - local database and cache";
+    let existed = gitignore_path.exists();
+    let content = if existed {
+        std::fs::read_to_string(&gitignore_path)?
+    } else {
+        String::new()
+    };
 
-    if gitignore_path.exists() {
-        // Read existing content
-        let content = std::fs::read_to_string(&gitignore_path)?;
+    let Some(new_content) = gitignore_with_tetrad(&content) else {
+        tracing::debug!(".gitignore already contains .tetrad/");
+        return Ok(());
+    };
 
-        // Check if it already contains .tetrad/
-        if content
-            .lines()
-            .any(|line| line.trim() == tetrad_entry || line.trim() == ".tetrad")
-        {
-            tracing::debug!(".gitignore already contains .tetrad/");
-            return Ok(());
-        }
+    crate::types::config::write_atomic(&gitignore_path, new_content.as_bytes())?;
+    if existed {
+        eprintln!(".gitignore updated with .tetrad/");
+    } else {
+        eprintln!(".gitignore created with .tetrad/");
+    }
+
+    Ok(())
+}
+
+/// Returns `content` with exactly one `.tetrad/` entry, or `None` if it
+/// already has exactly one.
+///
+/// Entries and the Tetrad comment are matched case-insensitively; repeats
+/// (left by concurrent inits of older versions, for instance) are dropped.
+fn gitignore_with_tetrad(content: &str) -> Option<String> {
+    let is_entry = |line: &str| {
+        let line = line.trim();
+        line.eq_ignore_ascii_case(".tetrad/") || line.eq_ignore_ascii_case(".tetrad")
+    };
+    let is_comment = |line: &str| line.trim().eq_ignore_ascii_case(GITIGNORE_COMMENT);
+
+    let entries = content.lines().filter(|line| is_entry(line)).count();
+    let comments = content.lines().filter(|line| is_comment(line)).count();
 
+    if entries == 0 {
         // Append to end of file
         let mut new_content = content.trim_end().to_string();
         if !new_content.is_empty() {
             new_content.push_str("\n\n");
         }
-        new_content.push_str(tetrad_comment);
-        new_content.push('\n');
-        new_content.push_str(tetrad_entry);
-        new_content.push('\n');
+        new_content.push_str(GITIGNORE_COMMENT);
+        new_content.push_str("\n.tetrad/\n");
+        return Some(new_content);
+    }
+    if entries == 1 && comments <= 1 {
+        return None;
+    }
 
-        std::fs::write(&gitignore_path, new_content)?;
-        println!(".gitignore updated with .tetrad/");
-    } else {
-        // Create new .gitignore
-        let content = format!("{}\n{}\n", tetrad_comment, tetrad_entry);
-        std::fs::write(&gitignore_path, content)?;
-        println!(".gitignore created with .tetrad/");
+    // Keep the first entry and comment; a dropped block leaves no double blank line
+    let mut lines: Vec<&str> = Vec::new();
+    let (mut seen_entry, mut seen_comment) = (false, false);
+    for line in content.lines() {
+        if is_entry(line) {
+            if std::mem::replace(&mut seen_entry, true) {
+                continue;
+            }
+        } else if is_comment(line) {
+            if std::mem::replace(&mut seen_comment, true) {
+                continue;
+            }
+        } else if line.trim().is_empty() && lines.last().is_some_and(|l| l.trim().is_empty()) {
+            continue;
+        }
+        lines.push(line);
     }
 
-    Ok(())
+    let mut new_content = lines.join("\n").trim_end().to_string();
+    new_content.push('\n');
+    Some(new_content)
 }
 
 /// Starts the MCP server.
-pub async fn serve(port: Option<u16>, config: &Config) -> TetradResult<()> {
-    use crate::mcp::McpServer;
+///
+/// With `watch_config`, changes to `config_path` are applied while the
+/// server runs; an invalid file is logged and the current configuration kept.
+/// With `offline`, reloaded configurations keep the mock executors.
+pub async fn serve(
+    port: Option<u16>,
+    watch_config: bool,
+    offline: bool,
+    config_path: &Path,
+    config: &Config,
+) -> TetradResult<()> {
+    use crate::mcp::{ConfigWatcher, McpServer};
 
     tracing::debug!(
         "Configuration loaded: timeout={}s, consensus={:?}",
@@ -111,53 +395,110 @@ pub async fn serve(port: Option<u16>, config: &Config) -> TetradResult<()> {
     // Start MCP server via stdio
     tracing::info!("Starting Tetrad MCP server via stdio...");
 
+    // Prompt files and selftest directories left behind by a crashed process
+    let swept = sweep_orphans(&scratch_root(), ORPHAN_MAX_AGE);
+    if swept > 0 {
+        tracing::info!(removed = swept, "Removed orphaned scratch artifacts");
+    }
+
     let mut server = McpServer::new(config.clone())?;
-    server.run().await
+    let watcher = watch_config.then(|| {
+        let watcher = ConfigWatcher::new(config_path).with_offline(offline);
+        tokio::spawn(watcher.run(server.reloader()))
+    });
+
+    let result = server.run().await;
+    if let Some(watcher) = watcher {
+        watcher.abort();
+    }
+    result
+}
+
+/// Serves the HTTP evaluation API configured in `[mcp.api]`.
+///
+/// `port` replaces the port of `mcp.api.bind`.
+pub async fn serve_api(
+    port: Option<u16>,
+    watch_config: bool,
+    offline: bool,
+    config_path: &Path,
+    config: &Config,
+) -> TetradResult<()> {
+    use crate::mcp::{ApiServer, ConfigWatcher, ToolHandler, EVALUATE_PATH};
+    use crate::TetradError;
+
+    let Some(mut api) = config.mcp.api.clone() else {
+        return Err(TetradError::config(
+            "The evaluation API is not configured; add an [mcp.api] section with a bearer_token",
+        ));
+    };
+    if let Some(port) = port {
+        let mut addr: std::net::SocketAddr = api
+            .bind
+            .parse()
+            .map_err(|_| TetradError::config(format!("Invalid mcp.api.bind: {}", api.bind)))?;
+        addr.set_port(port);
+        api.bind = addr.to_string();
+    }
+
+    let tools = ToolHandler::new(config.clone())?;
+    let watcher = watch_config.then(|| {
+        let watcher = ConfigWatcher::new(config_path).with_offline(offline);
+        tokio::spawn(watcher.run(tools.reloader()))
+    });
+
+    outln!(
+        "Evaluation API listening on http://{}{}",
+        api.bind,
+        EVALUATE_PATH
+    );
+    let result = ApiServer::new(tools, api)?.serve().await;
+    if let Some(watcher) = watcher {
+        watcher.abort();
+    }
+    result
 }
 
 /// Shows CLI status.
 pub async fn status(config: &Config) -> TetradResult<()> {
-    println!("Checking executor status...\n");
+    outln!("Checking executor status...\n");
 
     // Create executors with TOML configuration
-    let executors: Vec<(Box<dyn CliExecutor>, bool)> = vec![
-        (
-            Box::new(CodexExecutor::from_config(&config.executors.codex)),
-            config.executors.codex.enabled,
-        ),
-        (
-            Box::new(GeminiExecutor::from_config(&config.executors.gemini)),
-            config.executors.gemini.enabled,
-        ),
-        (
-            Box::new(QwenExecutor::from_config(&config.executors.qwen)),
-            config.executors.qwen.enabled,
-        ),
-    ];
+    let executors = build_executors(&config.executors, &config.prompts)?;
+    let quarantine = QuarantineList::load(&config.data_dir().join(QUARANTINE_FILE_NAME));
+    let style = style::current();
 
-    for (executor, enabled) in executors {
+    for (executor, executor_config) in &executors {
         let name = executor.name();
 
-        if !enabled {
-            println!("  ○ {} - disabled", name);
+        if !executor_config.enabled {
+            outln!("  {} {} - disabled", style.off(), name);
             continue;
         }
 
         let available = executor.is_available().await;
-        let status_icon = if available { "✓" } else { "✗" };
+        let status_icon = if available { style.ok() } else { style.fail() };
         let status_text = if available { "available" } else { "not found" };
 
-        println!("  {} {} - {}", status_icon, name, status_text);
+        outln!("  {} {} - {}", status_icon, name, status_text);
 
         if available {
             if let Ok(version) = executor.version().await {
-                println!("      version: {}", version);
+                outln!("      version: {}", version);
             }
         }
+        if let Some(entry) = quarantine.get(name) {
+            outln!(
+                "      {} quarantined, retry after {}: {}",
+                style.warn(),
+                entry.retry_after.format("%Y-%m-%d %H:%M:%S UTC"),
+                entry.reason
+            );
+        }
     }
 
-    println!();
-    println!("Tip: Install missing CLIs to enable full consensus.");
+    outln!();
+    outln!("Tip: Install missing CLIs to enable full consensus.");
 
     Ok(())
 }
@@ -176,40 +517,97 @@ pub async fn config_cmd(config_path: &Path) -> TetradResult<()> {
     run_interactive_config(config_path)
 }
 
+/// Prints the effective configuration as TOML.
+///
+/// With `consensus.strictness`, a header lists the settings the dial covers
+/// and whether each value came from the dial or from the file.
+pub fn effective_config(config_path: &Path, config: &Config) -> TetradResult<()> {
+    outln!("{}", effective_config_text(config_path, config)?);
+    Ok(())
+}
+
+fn effective_config_text(config_path: &Path, config: &Config) -> TetradResult<String> {
+    use crate::types::config::SettingSource;
+    use std::fmt::Write as _;
+
+    let mut text = String::new();
+    if config_path.exists() {
+        let _ = writeln!(
+            text,
+            "# Effective configuration of {}",
+            config_path.display()
+        );
+    } else {
+        let _ = writeln!(text, "# Effective configuration (built-in defaults)");
+    }
+
+    if let Some(trace) = &config.consensus.strictness_trace {
+        let _ = writeln!(text, "# consensus.strictness = {}:", trace.level);
+        for setting in &trace.settings {
+            let source = match setting.source {
+                SettingSource::Strictness => format!("from strictness {}", trace.level),
+                SettingSource::Explicit => format!("explicit, overrides {}", setting.dial_value),
+            };
+            let _ = writeln!(
+                text,
+                "#   {:<20} = {:<10} ({})",
+                setting.key,
+                setting.value.to_string(),
+                source
+            );
+        }
+    }
+
+    text.push('\n');
+    text.push_str(&toml::to_string_pretty(config).map_err(|e| {
+        crate::TetradError::config(format!("could not serialize the configuration: {}", e))
+    })?);
+    Ok(text)
+}
+
 /// Diagnoses configuration issues.
-pub async fn doctor(config: &Config) -> TetradResult<()> {
-    println!("Diagnosing Tetrad configuration...\n");
+///
+/// With `deep`, every enabled and available executor also runs a canary
+/// evaluation, and output that does not parse as the expected JSON is
+/// reported with an excerpt; a passing canary takes the executor out of
+/// quarantine. `clear_quarantine` empties the quarantine first. Returns
+/// `true` when issues (not warnings) were found.
+pub async fn doctor(config: &Config, deep: bool, clear_quarantine: bool) -> TetradResult<bool> {
+    outln!("Diagnosing Tetrad configuration...\n");
 
     let mut issues: Vec<String> = Vec::new();
     let mut warnings: Vec<String> = Vec::new();
+    let style = style::current();
 
-    println!("✓ Configuration loaded");
+    outln!("{} Configuration loaded", style.ok());
+    outln!(
+        "{} Data directory: {}",
+        style.ok(),
+        config.data_dir().display()
+    );
+    check_stray_data_dir(config, &mut warnings)?;
+
+    let mut quarantine = QuarantineList::load(&config.data_dir().join(QUARANTINE_FILE_NAME));
+    if clear_quarantine {
+        let cleared = quarantine.clear()?;
+        outln!(
+            "{} Quarantine cleared ({} executor(s) released)",
+            style.ok(),
+            cleared
+        );
+    }
 
     // Create executors with TOML configuration
-    let executors: Vec<(Box<dyn CliExecutor>, bool, &str)> = vec![
-        (
-            Box::new(CodexExecutor::from_config(&config.executors.codex)),
-            config.executors.codex.enabled,
-            "Codex",
-        ),
-        (
-            Box::new(GeminiExecutor::from_config(&config.executors.gemini)),
-            config.executors.gemini.enabled,
-            "Gemini",
-        ),
-        (
-            Box::new(QwenExecutor::from_config(&config.executors.qwen)),
-            config.executors.qwen.enabled,
-            "Qwen",
-        ),
-    ];
+    let executors = build_executors(&config.executors, &config.prompts)?;
 
     let mut available_count = 0;
     let mut enabled_count = 0;
 
-    for (executor, enabled, name) in executors {
-        if !enabled {
-            println!("○ {} is disabled in config", name);
+    for (executor, executor_config) in &executors {
+        let name = executor.name();
+
+        if !executor_config.enabled {
+            outln!("{} {} is disabled in config", style.off(), name);
             continue;
         }
 
@@ -217,7 +615,35 @@ pub async fn doctor(config: &Config) -> TetradResult<()> {
 
         if executor.is_available().await {
             available_count += 1;
-            println!("✓ {} is available (command: {})", name, executor.command());
+            outln!(
+                "{} {} is available (command: {})",
+                style.ok(),
+                name,
+                executor.command()
+            );
+            if deep {
+                match run_canary(executor.as_ref(), executor_config).await {
+                    CanaryOutcome::Parsed { vote, score } => {
+                        outln!(
+                            "{} {} answered the canary evaluation ({}, score {})",
+                            style.ok(),
+                            name,
+                            vote,
+                            score
+                        );
+                        if quarantine.remove(name)? {
+                            outln!("{} {} released from quarantine", style.ok(), name);
+                        }
+                    }
+                    CanaryOutcome::Unparseable { error, excerpt } => issues.push(format!(
+                        "{} output could not be parsed ({}); check its args. Output: {}",
+                        name, error, excerpt
+                    )),
+                    CanaryOutcome::Failed(error) => {
+                        issues.push(format!("{} canary evaluation failed: {}", name, error))
+                    }
+                }
+            }
         } else {
             warnings.push(format!(
                 "{} is not installed (expected command: {})",
@@ -227,6 +653,66 @@ pub async fn doctor(config: &Config) -> TetradResult<()> {
         }
     }
 
+    let now = chrono::Utc::now();
+    for entry in quarantine.entries() {
+        let until = if entry.retry_after > now {
+            format!(
+                "until {}",
+                entry.retry_after.format("%Y-%m-%d %H:%M:%S UTC")
+            )
+        } else {
+            "until its next evaluation succeeds".to_string()
+        };
+        warnings.push(format!(
+            "{} is quarantined {} (since {}): {}. Run 'tetrad doctor --clear-quarantine' to release it",
+            entry.executor,
+            until,
+            entry.opened_at.format("%Y-%m-%d %H:%M:%S UTC"),
+            entry.reason
+        ));
+    }
+
+    // Validate custom executor names and fallback chains
+    let names: Vec<&str> = executors.iter().map(|(e, _)| e.name()).collect();
+    for (index, name) in names.iter().enumerate() {
+        if names[..index].iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            issues.push(format!("Executor name '{}' is used more than once", name));
+        }
+    }
+
+    for (executor, executor_config) in &executors {
+        let name = executor.name();
+        if let Some(primary) = &executor_config.fallback_for {
+            if primary.eq_ignore_ascii_case(name) {
+                issues.push(format!("{} is configured as its own fallback", name));
+            } else if !names
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(primary))
+            {
+                warnings.push(format!(
+                    "{} is a fallback for unknown executor '{}' and will never run",
+                    name, primary
+                ));
+            } else {
+                outln!("{} {} is a fallback for {}", style.ok(), name, primary);
+            }
+        }
+    }
+
+    // Validate the feedback template up front; a broken one aborts evaluations
+    if let Some(path) = &config.consensus.feedback_template {
+        match FeedbackTemplate::load(path) {
+            Ok(_) => outln!(
+                "{} Feedback template loaded ({})",
+                style.ok(),
+                path.display()
+            ),
+            Err(e) => issues.push(e.to_string()),
+        }
+    }
+
+    check_config_values(config, &executors, &mut issues, &mut warnings);
+
     if enabled_count == 0 {
         issues.push("No executor enabled in config - consensus is not possible".to_string());
     } else if available_count == 0 {
@@ -239,44 +725,294 @@ pub async fn doctor(config: &Config) -> TetradResult<()> {
     }
 
     // Summary
-    println!();
+    outln!();
+    let has_issues = !issues.is_empty();
     if issues.is_empty() && warnings.is_empty() {
-        println!("✓ All OK! Tetrad is ready to use.");
+        outln!("{} All OK! Tetrad is ready to use.", style.ok());
     } else {
         if !warnings.is_empty() {
-            println!("Warnings:");
+            outln!("Warnings:");
             for warning in warnings {
-                println!("  ⚠ {}", warning);
+                outln!("  {} {}", style.warn(), warning);
             }
         }
         if !issues.is_empty() {
-            println!("Issues:");
+            outln!("Issues:");
             for issue in issues {
-                println!("  ✗ {}", issue);
+                outln!("  {} {}", style.fail(), issue);
+            }
+        }
+    }
+
+    Ok(has_issues)
+}
+
+/// Reports the problems found by [`Config::validate`], plus the ones that
+/// need the filesystem or the executor list.
+fn check_config_values(
+    config: &Config,
+    executors: &[ConfiguredExecutor],
+    issues: &mut Vec<String>,
+    warnings: &mut Vec<String>,
+) {
+    let mut db_path_ok = true;
+    if let Err(crate::TetradError::InvalidConfig(problems)) = config.validate() {
+        db_path_ok = !problems.iter().any(|p| p.starts_with("reasoning.db_path"));
+        issues.extend(problems);
+    }
+
+    if config.reasoning.enabled && db_path_ok {
+        if let Err(e) = check_writable_parent(&config.reasoning.db_path) {
+            issues.push(format!(
+                "ReasoningBank database {} is not writable: {}",
+                config.reasoning.db_path.display(),
+                e
+            ));
+        }
+    }
+
+    // Unanimity with a single voter is not a consensus
+    let voters = executors
+        .iter()
+        .filter(|(_, c)| c.enabled && c.fallback_for.is_none())
+        .count();
+    if voters == 1
+        && matches!(
+            config.consensus.default_rule,
+            ConsensusRule::Golden | ConsensusRule::Strong
+        )
+    {
+        warnings.push(format!(
+            "Only one executor is enabled but the consensus rule is {:?}; its vote alone decides",
+            config.consensus.default_rule
+        ));
+    }
+}
+
+/// Checks that the directory that will hold `path` can be written to.
+///
+/// Missing directories are created on first use, so the nearest existing
+/// ancestor is probed instead.
+fn check_writable_parent(path: &Path) -> std::io::Result<()> {
+    let mut dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    while !dir.exists() {
+        match dir.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => dir = parent,
+            _ => {
+                dir = Path::new(".");
+                break;
             }
         }
     }
 
+    let probe = dir.join(format!(".tetrad-doctor-{}", std::process::id()));
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)?;
+    std::fs::remove_file(&probe)
+}
+
+/// Offers to move a `.tetrad/` left in the current directory by older
+/// versions into [`Config::data_dir`].
+///
+/// Outside a terminal, or when both directories hold a database, only a
+/// warning is recorded.
+fn check_stray_data_dir(config: &Config, warnings: &mut Vec<String>) -> TetradResult<()> {
+    use std::io::IsTerminal;
+
+    let data_dir = config.data_dir();
+    let Some(stray) = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| stray_data_dir(&cwd, &data_dir))
+    else {
+        return Ok(());
+    };
+
+    if data_dir.join("tetrad.db").exists() {
+        warnings.push(format!(
+            "{} holds data outside the data directory {}; both have a database, merge them manually",
+            stray.display(),
+            data_dir.display()
+        ));
+        return Ok(());
+    }
+
+    let interactive = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    let prompt = format!("Move {} to {}?", stray.display(), data_dir.display());
+    if interactive && super::interactive::confirm(&prompt, true)? {
+        let moved = migrate_data_dir(&stray, &data_dir)?;
+        outln!(
+            "{} Moved {} entries to {}",
+            style::current().ok(),
+            moved,
+            data_dir.display()
+        );
+    } else {
+        warnings.push(format!(
+            "{} holds data outside the data directory {}; run `tetrad doctor` in a terminal to move it",
+            stray.display(),
+            data_dir.display()
+        ));
+    }
     Ok(())
 }
 
+/// `.tetrad/` in `dir` holding data, when it is not `data_dir` itself.
+fn stray_data_dir(dir: &Path, data_dir: &Path) -> Option<PathBuf> {
+    let candidate = dir.join(PROJECT_DATA_DIR);
+    let has_data = std::fs::read_dir(&candidate)
+        .map(|mut entries| {
+            entries.any(|entry| entry.is_ok_and(|entry| entry.file_name() != ".lock"))
+        })
+        .unwrap_or(false);
+    if !has_data {
+        return None;
+    }
+
+    let same = match (
+        std::fs::canonicalize(&candidate),
+        std::fs::canonicalize(data_dir),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => candidate == data_dir,
+    };
+    (!same).then_some(candidate)
+}
+
+/// Moves the entries of `from` into `to`, then removes `from` if it ended up
+/// empty.
+///
+/// Entries that already exist in `to` are left in place. Files are copied
+/// when a rename is not possible (e.g. across file systems). Returns how
+/// many entries were moved.
+fn migrate_data_dir(from: &Path, to: &Path) -> TetradResult<usize> {
+    std::fs::create_dir_all(to)?;
+
+    let mut moved = 0;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if target.exists() {
+            continue;
+        }
+        if std::fs::rename(entry.path(), &target).is_err() {
+            if !entry.file_type()?.is_file() {
+                return Err(crate::TetradError::other(format!(
+                    "Could not move {} to {}",
+                    entry.path().display(),
+                    target.display()
+                )));
+            }
+            std::fs::copy(entry.path(), &target)?;
+            std::fs::remove_file(entry.path())?;
+        }
+        moved += 1;
+    }
+
+    // Fails harmlessly when something was left behind
+    let _ = std::fs::remove_dir(from);
+    Ok(moved)
+}
+
+/// Runs the in-process MCP smoke test and prints one line per stage.
+///
+/// Returns whether every stage passed.
+pub async fn selftest(live: bool, config: &Config) -> TetradResult<bool> {
+    use super::selftest::{run_selftest, StageStatus};
+
+    let style = style::current();
+    outln!(
+        "Running Tetrad self-test with {} executors...\n",
+        if live { "configured" } else { "mock" }
+    );
+
+    let reports = run_selftest(config, live).await?;
+
+    for report in &reports {
+        let icon = match report.status {
+            StageStatus::Pass => style.ok(),
+            StageStatus::Fail => style.fail(),
+            StageStatus::Skip => style.off(),
+        };
+        outln!(
+            "{} {} {:<15} {:>6} ms  {}",
+            icon,
+            report.status,
+            report.name,
+            report.duration.as_millis(),
+            report.detail
+        );
+    }
+
+    let passed = reports.iter().all(|r| r.status == StageStatus::Pass);
+    if passed {
+        outln!("\n{} Self-test passed.", style.ok());
+    } else {
+        outln!("\n{} Self-test failed.", style.fail());
+    }
+
+    Ok(passed)
+}
+
 /// Shows version.
 pub fn version() {
-    println!("tetrad {}", env!("CARGO_PKG_VERSION"));
-    println!();
-    println!("Quadruple Consensus MCP for Claude Code");
-    println!("https://github.com/SamoraDC/tetrad");
+    let build = BuildInfo::current();
+
+    outln!("tetrad {}", build.version);
+    outln!("commit:   {} ({})", build.git_commit, build.build_date);
+    outln!("rustc:    {}", build.rustc);
+    outln!("features: {}", build.features.join(", "));
+    outln!("target:   {}", build.target);
+    outln!();
+    outln!("Quadruple Consensus MCP for Claude Code");
+    outln!("https://github.com/SamoraDC/tetrad");
 }
 
 /// Evaluates code manually (without MCP).
-pub async fn evaluate(code: &str, language: &str, config: &Config) -> TetradResult<()> {
+///
+/// With [`OutputFormat::Json`], stdout carries a single JSON document and all
+/// progress messages go to stderr. With `show_fixes`, suggested fixes are
+/// rendered as diff previews; they are never applied. With [`ReviewType::Docs`],
+/// `code` is documentation and `related` the code it describes. With
+/// `override_budget`, an exhausted monthly budget under the
+/// `require_override` action does not stop the evaluation.
+///
+/// Returns the decision, or `None` when no evaluator voted; see
+/// [`decision_exit_code`] for the exit status it maps to. Without a vote the
+/// JSON document is `{"decision": null, "reason": "no_evaluator"}`.
+#[allow(clippy::too_many_arguments)]
+pub async fn evaluate(
+    code: &str,
+    language: &str,
+    format: OutputFormat,
+    show_fixes: bool,
+    review_type: ReviewType,
+    related: Option<&str>,
+    override_budget: bool,
+    config: &Config,
+) -> TetradResult<Option<Decision>> {
     use crate::consensus::ConsensusEngine;
-    use crate::reasoning::{PatternMatcher, ReasoningBank};
+    use crate::hooks::HookResult;
+    use crate::reasoning::{
+        known_antipatterns, prompt_context, score_adjustment, ArtifactKind, PatternMatcher,
+    };
     use crate::types::requests::{EvaluationRequest, EvaluationType};
     use crate::types::responses::ModelVote;
     use std::collections::HashMap;
 
-    println!("Evaluating code...\n");
+    let json = format == OutputFormat::Json;
+    let evaluation_type = review_type.evaluation_type();
+    let is_docs = evaluation_type == EvaluationType::Documentation;
+
+    if is_docs {
+        eprintln!("Evaluating documentation...\n");
+    } else {
+        eprintln!("Evaluating code...\n");
+    }
 
     // Load code from file if starts with @
     let (code_content, file_path_opt) = if let Some(file_path) = code.strip_prefix('@') {
@@ -288,13 +1024,32 @@ pub async fn evaluate(code: &str, language: &str, config: &Config) -> TetradResu
         (code.to_string(), None)
     };
 
-    // Detect language if "auto"
-    let detected_language = if language == "auto" {
-        PatternMatcher::detect_language(&code_content)
+    // Related code may also come from a file
+    let related_code = match related {
+        Some(related) => Some(match related.strip_prefix('@') {
+            Some(file_path) => std::fs::read_to_string(file_path)?,
+            None => related.to_string(),
+        }),
+        None => None,
+    };
+
+    // Detect language if "auto"; for docs, it is the language of the related code
+    let detected_language = if language != "auto" {
+        Language::parse(language)
+    } else if is_docs {
+        related_code
+            .as_deref()
+            .map(|code| Language::parse(&PatternMatcher::detect_language(code)))
+            .unwrap_or_else(|| Language::parse("text"))
     } else {
-        language.to_string()
+        let (language, confidence) = PatternMatcher::detect_language_with_confidence(
+            &code_content,
+            file_path_opt.as_deref(),
+        );
+        tracing::debug!(%language, confidence, "Detected language");
+        Language::parse(&language)
     };
-    println!("Language: {}", detected_language);
+    progress!(json, "Language: {}", detected_language);
 
     // Use ReasoningBank configuration
     let db_path = &config.reasoning.db_path;
@@ -312,94 +1067,179 @@ pub async fn evaluate(code: &str, language: &str, config: &Config) -> TetradResu
     };
 
     // RETRIEVE - Search for similar patterns
-    if let Some(ref b) = bank {
-        let matches = b.retrieve(&code_content, &detected_language);
-        if !matches.is_empty() {
-            println!("\nPatterns found in ReasoningBank:");
-            for m in &matches {
-                let icon = match m.pattern.pattern_type {
-                    crate::reasoning::PatternType::AntiPattern => "⚠",
-                    crate::reasoning::PatternType::GoodPattern => "✓",
-                    crate::reasoning::PatternType::Ambiguous => "?",
-                };
-                println!(
-                    "  {} {} - {} (confidence: {:.0}%)",
-                    icon,
-                    m.pattern.issue_category,
-                    m.pattern.description,
-                    m.pattern.confidence * 100.0
-                );
-            }
+    let matches = bank
+        .as_ref()
+        .map(|b| {
+            b.retrieve_artifact(
+                &code_content,
+                detected_language.as_str(),
+                ArtifactKind::from(evaluation_type),
+            )
+        })
+        .unwrap_or_default();
+
+    if !matches.is_empty() {
+        progress!(json, "\nPatterns found in ReasoningBank:");
+        let style = style::current();
+        for m in &matches {
+            let icon = match m.pattern.pattern_type {
+                crate::reasoning::PatternType::AntiPattern => style.warn(),
+                crate::reasoning::PatternType::GoodPattern => style.ok(),
+                crate::reasoning::PatternType::Ambiguous => "?".to_string(),
+            };
+            progress!(
+                json,
+                "  {} {} - {} (confidence: {:.0}%)",
+                icon,
+                m.pattern.issue_category,
+                m.pattern.description,
+                m.pattern.confidence * 100.0
+            );
         }
     }
 
     // Cria executores e coleta votos
-    let executors: Vec<Box<dyn CliExecutor>> = vec![
-        Box::new(CodexExecutor::from_config(&config.executors.codex)),
-        Box::new(GeminiExecutor::from_config(&config.executors.gemini)),
-        Box::new(QwenExecutor::from_config(&config.executors.qwen)),
-    ];
+    let mut executors = build_executors(&config.executors, &config.prompts)?;
+    let slots = executor_slots(&executors);
+    let voters = seat_count(&slots);
+    let engine = ConsensusEngine::from_config(config.consensus.clone(), voters)?
+        .with_weights(VoteWeights::new(seat_weights(&slots)));
 
     let mut votes: HashMap<String, ModelVote> = HashMap::new();
     let request_id = format!("eval-{}", chrono::Utc::now().timestamp());
 
+    // Known anti-patterns go to the executors as prompt context
+    let antipatterns = known_antipatterns(&matches, config.reasoning.pattern_min_confidence);
+    let context = if config.reasoning.apply_to_prompt {
+        prompt_context(&antipatterns)
+    } else {
+        None
+    };
+
     // Cria requisição de avaliação
-    let request = EvaluationRequest {
+    let mut request = EvaluationRequest {
         request_id: request_id.clone(),
         code: code_content.clone(),
         language: detected_language.clone(),
-        evaluation_type: EvaluationType::Code,
-        context: None,
-        file_path: file_path_opt,
+        evaluation_type,
+        context,
+        file_path: file_path_opt.clone(),
+        related_code,
+        doc_type: None,
+        related_files: Vec::new(),
+        executors: None,
     };
 
-    println!("\nRunning evaluators...");
+    // Secrets never reach the executors; a blocked review is not charged
+    match redaction_hook(config)?.map(|hook| hook.check(&request)) {
+        Some(HookResult::Skip { reason, decision }) => {
+            let result = EvaluationResult::skipped(
+                &request_id,
+                reason,
+                decision.unwrap_or(Decision::Revise),
+            );
+            let report = EvaluationReport {
+                result: &result,
+                language: detected_language.as_str(),
+                file_path: file_path_opt.as_deref(),
+                matched_patterns: &matches,
+            };
+            print_evaluation(&report, json, show_fixes, &code_content)?;
+            return Ok(Some(result.decision));
+        }
+        Some(HookResult::ModifyRequest(redacted)) => request = redacted,
+        _ => {}
+    }
+
+    // Count the evaluation against the monthly budget before any executor runs
+    charge_budget(bank.as_mut(), config, 1, voters, override_budget)?;
 
-    for executor in executors {
-        let name = executor.name();
-        if !executor.is_available().await {
-            println!("  {} - not available, skipping", name);
-            continue;
+    progress!(json, "\nRunning evaluators...");
+
+    // Unavailable executors are treated as disabled so a fallback can take their seat
+    for (executor, executor_config) in executors.iter_mut() {
+        if executor_config.enabled && !executor.is_available().await {
+            progress!(json, "  {} - not available, skipping", executor.name());
+            executor_config.enabled = false;
         }
+    }
 
-        print!("  {} - evaluating... ", name);
+    let slots = executor_slots(&executors);
+
+    for index in 0..slots.len() {
+        let Some(seat) = run_seat(&slots, index, &request).await else {
+            continue;
+        };
 
-        match executor.evaluate(&request).await {
+        match seat.result {
             Ok(vote) => {
-                println!("{:?} (score: {})", vote.vote, vote.score);
-                votes.insert(name.to_string(), vote);
+                progress!(
+                    json,
+                    "  {} - {:?} (score: {})",
+                    seat.name,
+                    vote.vote,
+                    vote.score
+                );
+                votes.insert(seat.name, vote);
             }
             Err(e) => {
-                println!("error: {}", e);
+                progress!(json, "  {} - error: {}", seat.name, e);
             }
         }
     }
 
     if votes.is_empty() {
-        println!("\nNo evaluator available. Install at least one CLI.");
-        return Ok(());
+        progress!(json, "\nNo evaluator available. Install at least one CLI.");
+        if json {
+            let report = serde_json::json!({ "decision": null, "reason": "no_evaluator" });
+            outln!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        return Ok(None);
     }
 
     // Aplica consenso
-    let engine = ConsensusEngine::new(config.consensus.clone());
-    let result = engine.evaluate(votes, &request_id);
+    let mut result = engine.evaluate(votes, &request_id);
+
+    // Penalize the score for known anti-patterns
+    if let Some(adjustment) = score_adjustment(
+        &antipatterns,
+        result.score,
+        config.reasoning.score_penalty_max,
+    ) {
+        engine.apply_pattern_adjustment(&mut result, adjustment);
+    }
+
+    // Flag flaky results before registering this one
+    if let Some(ref b) = bank {
+        match b.find_divergent(
+            &request.code,
+            result.decision,
+            config.reasoning.flaky_window_secs,
+        ) {
+            Ok(Some((previous_id, previous_decision))) => {
+                result.mark_flaky(&previous_id, previous_decision);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Error checking flaky evaluations: {}", e),
+        }
+    }
 
     // JUDGE - Register result in ReasoningBank
     if let Some(ref mut b) = bank {
         let loops_to_consensus = 1; // CLI runs only 1 loop
-        match b.judge(
-            &request_id,
-            &code_content,
-            &detected_language,
+        match b.judge_request(
+            &request,
             &result,
             loops_to_consensus,
             config.consensus.max_loops,
         ) {
             Ok(judgment) => {
                 if judgment.new_patterns_created > 0 || judgment.patterns_updated > 0 {
-                    println!(
+                    progress!(
+                        json,
                         "\nReasoningBank: {} new patterns, {} updated",
-                        judgment.new_patterns_created, judgment.patterns_updated
+                        judgment.new_patterns_created,
+                        judgment.patterns_updated
                     );
                 }
             }
@@ -409,26 +1249,57 @@ pub async fn evaluate(code: &str, language: &str, config: &Config) -> TetradResu
         }
 
         // CONSOLIDATE - Check if it's time to consolidate
-        if let Ok(eval_count) = b.count_trajectories() {
-            if eval_count > 0 && eval_count % config.reasoning.consolidation_interval == 0 {
-                if let Ok(consolidation) = b.consolidate() {
-                    if consolidation.patterns_merged > 0 || consolidation.patterns_pruned > 0 {
-                        println!(
-                            "ReasoningBank consolidated: {} merged, {} pruned",
-                            consolidation.patterns_merged, consolidation.patterns_pruned
-                        );
-                    }
+        if b.consolidation_due() {
+            if let Ok(consolidation) = b.consolidate() {
+                if consolidation.patterns_merged > 0
+                    || consolidation.patterns_pruned > 0
+                    || consolidation.good_patterns_collapsed > 0
+                    || consolidation.trajectories_pruned > 0
+                {
+                    progress!(
+                        json,
+                        "ReasoningBank consolidated: {} merged, {} pruned, {} one-off good patterns folded, {} old trajectories removed",
+                        consolidation.patterns_merged,
+                        consolidation.patterns_pruned,
+                        consolidation.good_patterns_collapsed,
+                        consolidation.trajectories_pruned
+                    );
                 }
+                refresh_digest(b, config);
             }
         }
     }
 
-    // Show result
-    println!("\n{}", "=".repeat(50));
-    println!("{}", result.feedback);
+    let report = EvaluationReport {
+        result: &result,
+        language: detected_language.as_str(),
+        file_path: file_path_opt.as_deref(),
+        matched_patterns: &matches,
+    };
+    print_evaluation(&report, json, show_fixes, &code_content)?;
+
+    Ok(Some(result.decision))
+}
+
+/// Prints the result of `tetrad evaluate`: the JSON report, or the feedback
+/// and, with `show_fixes`, a preview of each suggested fix to `code`.
+fn print_evaluation(
+    report: &EvaluationReport<'_>,
+    json: bool,
+    show_fixes: bool,
+    code: &str,
+) -> TetradResult<()> {
+    if json {
+        outln!("{}", serde_json::to_string_pretty(report)?);
+        return Ok(());
+    }
+
+    let result = report.result;
+    outln!("\n{}", "=".repeat(50));
+    outln!("{}", result.feedback);
 
-    println!("Final score: {}", result.score);
-    println!(
+    outln!("Final score: {}", result.score);
+    outln!(
         "Consensus: {}",
         if result.consensus_achieved {
             "YES"
@@ -436,42 +1307,795 @@ pub async fn evaluate(code: &str, language: &str, config: &Config) -> TetradResu
             "NO"
         }
     );
+    outln!("Confidence: {:.0}%", result.confidence * 100.0);
 
-    Ok(())
-}
+    if show_fixes {
+        let label = report.file_path.unwrap_or("<input>");
+        let mut any_fix = false;
 
-/// Shows evaluation history from ReasoningBank.
-pub async fn history(limit: usize, config: &Config) -> TetradResult<()> {
-    use crate::reasoning::ReasoningBank;
+        for finding in &result.findings {
+            for fix in finding.fixes() {
+                if !any_fix {
+                    outln!("\nSuggested fixes (not applied):");
+                    any_fix = true;
+                }
+                outln!("\n# {}", finding.issue);
+                out!("{}", render_fix_preview(code, fix, label));
+            }
+        }
 
-    if !config.reasoning.enabled {
-        println!("ReasoningBank is disabled in configuration.");
+        if !any_fix {
+            outln!("\nNo suggested fixes.");
+        }
+    }
+
+    Ok(())
+}
+
+/// Exit status of `tetrad evaluate` when nothing was reviewed: no evaluator
+/// voted or the review failed with an error.
+pub const EVALUATE_FAILED_EXIT_CODE: i32 = 3;
+
+/// Exit status of `tetrad evaluate` for a decision: 0 for PASS, 1 for
+/// REVISE and 2 for BLOCK, so scripts can branch on it; see
+/// [`EVALUATE_FAILED_EXIT_CODE`] for a review that did not happen.
+pub fn decision_exit_code(decision: Decision) -> i32 {
+    match decision {
+        Decision::Pass => 0,
+        Decision::Revise => 1,
+        Decision::Block => 2,
+    }
+}
+
+/// Renders a suggested fix as a unified-diff style preview.
+/// Counts `evaluations` evaluations against the monthly budget.
+///
+/// Each evaluation is estimated at one call per executor seat. Without a
+/// ReasoningBank there is nowhere to keep the count and nothing is enforced.
+fn charge_budget(
+    bank: Option<&mut ReasoningBank>,
+    config: &Config,
+    evaluations: u64,
+    seats: usize,
+    override_budget: bool,
+) -> TetradResult<()> {
+    use crate::reasoning::BudgetCharge;
+    use crate::types::config::BudgetAction;
+
+    let Some(bank) = bank else {
+        return Ok(());
+    };
+    let budget = &config.general.budget;
+    let cost = evaluations as f64 * seats as f64 * budget.cost_per_call;
+
+    match bank.charge_usage(
+        budget,
+        evaluations,
+        cost,
+        override_budget,
+        chrono::Utc::now(),
+    )? {
+        BudgetCharge::Charged(_) => Ok(()),
+        BudgetCharge::OverBudget(status) => {
+            eprintln!(
+                "{} Monthly budget exhausted ({}), evaluating anyway",
+                style::current().warn(),
+                status.summary()
+            );
+            Ok(())
+        }
+        BudgetCharge::Refused(status) => {
+            let hint = match budget.action {
+                BudgetAction::RequireOverride => " (use --override-budget to evaluate anyway)",
+                _ => "",
+            };
+            Err(crate::TetradError::BudgetExceeded(format!(
+                "{}{}",
+                status.summary(),
+                hint
+            )))
+        }
+    }
+}
+
+fn render_fix_preview(code: &str, fix: &SuggestedFix, label: &str) -> String {
+    let lines: Vec<&str> = code.lines().collect();
+    let start = (fix.start_line.max(1) as usize).min(lines.len() + 1);
+    let end = (fix.end_line as usize).clamp(start - 1, lines.len());
+    let removed = &lines[start - 1..end];
+    let added: Vec<&str> = fix.replacement.lines().collect();
+
+    let mut preview = format!("--- {}\n", label);
+    preview.push_str(&format!(
+        "+++ {} (fix from {})\n",
+        label,
+        fix.sources.join(", ")
+    ));
+    preview.push_str(&format!(
+        "@@ -{},{} +{},{} @@\n",
+        start,
+        removed.len(),
+        start,
+        added.len()
+    ));
+    for line in removed {
+        preview.push_str(&format!("-{}\n", line));
+    }
+    for line in &added {
+        preview.push_str(&format!("+{}\n", line));
+    }
+    preview
+}
+
+/// Redaction hook from `[hooks.redaction]`, for evaluations run by the CLI
+/// itself; `tetrad serve` registers it in its `HookSystem` instead.
+fn redaction_hook(config: &Config) -> TetradResult<Option<crate::hooks::RedactionHook>> {
+    match &config.hooks.redaction {
+        Some(redaction) => crate::hooks::RedactionHook::from_config(redaction),
+        None => Ok(None),
+    }
+}
+
+/// Executors and consensus engine shared by a batch of evaluations.
+struct BatchPipeline {
+    executors: Vec<(std::sync::Arc<dyn CliExecutor>, ExecutorConfig)>,
+    engine: std::sync::Arc<crate::consensus::ConsensusEngine>,
+    options: super::batch::BatchOptions,
+    available: usize,
+    seats: usize,
+}
+
+/// Checks the executors once for a batch of `evaluations` and charges the
+/// batch against the monthly budget.
+///
+/// Unavailable executors are kept as disabled so a fallback can take their
+/// seat. Returns `None`, without charging, when no evaluator is available.
+async fn prepare_batch(
+    evaluations: usize,
+    override_budget: bool,
+    config: &Config,
+) -> TetradResult<Option<BatchPipeline>> {
+    use crate::consensus::ConsensusEngine;
+    use std::sync::Arc;
+
+    let configured = build_executors(&config.executors, &config.prompts)?;
+    let slots = executor_slots(&configured);
+    let voters = seat_count(&slots);
+    let engine = Arc::new(
+        ConsensusEngine::from_config(config.consensus.clone(), voters)?
+            .with_weights(VoteWeights::new(seat_weights(&slots))),
+    );
+
+    let mut executors: Vec<(Arc<dyn CliExecutor>, ExecutorConfig)> = Vec::new();
+    let mut unavailable = Vec::new();
+    for (executor, mut executor_config) in configured {
+        if executor_config.enabled && !executor.is_available().await {
+            eprintln!("  {} - not available, skipping", executor.name());
+            executor_config.enabled = false;
+            if executor_config.fallback_for.is_none() {
+                unavailable.push(executor.name().to_string());
+            }
+        }
+        executors.push((Arc::from(executor), executor_config));
+    }
+
+    let available = executors.iter().filter(|(_, c)| c.enabled).count();
+    if available == 0 {
+        return Ok(None);
+    }
+
+    // The whole batch is counted against the monthly budget up front
+    if config.reasoning.enabled {
+        if let Some(parent) = config.reasoning.db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut bank =
+            ReasoningBank::new_with_config(&config.reasoning.db_path, &config.reasoning)?;
+        charge_budget(
+            Some(&mut bank),
+            config,
+            evaluations as u64,
+            voters,
+            override_budget,
+        )?;
+    }
+
+    Ok(Some(BatchPipeline {
+        executors,
+        engine,
+        options: super::batch::BatchOptions {
+            unavailable,
+            degraded_ttl: std::time::Duration::from_secs(config.cache.degraded_ttl_secs),
+            redaction: redaction_hook(config)?,
+        },
+        available,
+        seats: voters,
+    }))
+}
+
+/// Evaluates every source file under a directory.
+///
+/// Returns `true` if any file was blocked, so the caller can exit non-zero.
+pub async fn evaluate_dir(
+    dir: &Path,
+    language: &str,
+    max_files: usize,
+    jobs: usize,
+    override_budget: bool,
+    config: &Config,
+) -> TetradResult<bool> {
+    use super::batch::{collect_files, evaluate_files};
+    use crate::cache::EvaluationCache;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    if !dir.is_dir() {
+        return Err(crate::TetradError::config(format!(
+            "Not a directory: {}",
+            dir.display()
+        )));
+    }
+
+    let forced_language = (language != "auto").then(|| Language::parse(language));
+    let files = collect_files(dir, forced_language.as_ref(), max_files)?;
+
+    if files.is_empty() {
+        outln!("No source files found in {}", dir.display());
+        return Ok(false);
+    }
+
+    let Some(pipeline) = prepare_batch(files.len(), override_budget, config).await? else {
+        outln!("No evaluator available. Install at least one CLI.");
+        return Ok(false);
+    };
+
+    eprintln!(
+        "Evaluating {} files with {} evaluators ({} at a time)...\n",
+        files.len(),
+        pipeline.available,
+        jobs.max(1)
+    );
+
+    let cache = Arc::new(Mutex::new(EvaluationCache::from_config(&config.cache)));
+
+    let outcomes = evaluate_files(
+        files,
+        pipeline.executors,
+        pipeline.engine,
+        cache,
+        pipeline.options,
+        jobs,
+    )
+    .await;
+
+    // Summary table
+    let width = outcomes
+        .iter()
+        .map(|o| o.file.path.display().to_string().len())
+        .max()
+        .unwrap_or(4)
+        .max(4);
+
+    outln!("{:<width$}  {:<8}  {:>5}", "FILE", "DECISION", "SCORE");
+    outln!("{}", "-".repeat(width + 17));
+
+    let (mut passed, mut revised, mut blocked, mut failed) = (0, 0, 0, 0);
+
+    for outcome in &outcomes {
+        let file = outcome.file.path.display().to_string();
+        match &outcome.result {
+            Ok(result) => {
+                match result.decision {
+                    Decision::Pass => passed += 1,
+                    Decision::Revise => revised += 1,
+                    Decision::Block => blocked += 1,
+                }
+                outln!(
+                    "{:<width$}  {:<8}  {:>5}{}",
+                    file,
+                    result.decision.to_string(),
+                    result.score,
+                    if outcome.cached { " (cached)" } else { "" }
+                );
+                if let Some(reason) = &result.skip_reason {
+                    outln!("{:<width$}    {}", "", reason);
+                }
+            }
+            Err(e) => {
+                failed += 1;
+                outln!("{:<width$}  {:<8}  {:>5}  {}", file, "ERROR", "-", e);
+            }
+        }
+    }
+
+    outln!();
+    outln!(
+        "{} files: {} PASS, {} REVISE, {} BLOCK, {} errors",
+        outcomes.len(),
+        passed,
+        revised,
+        blocked,
+        failed
+    );
+
+    Ok(blocked > 0)
+}
+
+/// Watches a directory and evaluates source files as they change.
+///
+/// A file is evaluated once it has been quiet for `debounce` after a change;
+/// files already present are not evaluated at startup. Content seen before
+/// is answered by the cache and not charged against the budget, fresh
+/// results are recorded in the ReasoningBank. Runs until Ctrl-C.
+pub async fn watch(
+    dir: &Path,
+    language: &str,
+    debounce: std::time::Duration,
+    max_files: usize,
+    jobs: usize,
+    override_budget: bool,
+    config: &Config,
+) -> TetradResult<()> {
+    use super::watch::{evaluate_changes, is_cached, Watcher};
+    use crate::cache::EvaluationCache;
+    use std::sync::Arc;
+    use std::time::Instant;
+    use tokio::sync::Mutex;
+
+    /// How often the tree is rescanned.
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(200);
+
+    if !dir.is_dir() {
+        return Err(crate::TetradError::config(format!(
+            "Not a directory: {}",
+            dir.display()
+        )));
+    }
+
+    let forced_language = (language != "auto").then(|| Language::parse(language));
+    let mut watcher = Watcher::new(dir, forced_language, max_files, debounce)?;
+
+    let Some(pipeline) = prepare_batch(0, override_budget, config).await? else {
+        outln!("No evaluator available. Install at least one CLI.");
+        return Ok(());
+    };
+    let mut bank = if config.reasoning.enabled {
+        Some(ReasoningBank::new_with_config(
+            &config.reasoning.db_path,
+            &config.reasoning,
+        )?)
+    } else {
+        None
+    };
+    let cache = Arc::new(Mutex::new(EvaluationCache::from_config(&config.cache)));
+
+    eprintln!(
+        "Watching {} with {} evaluators (Ctrl-C to stop)...\n",
+        dir.display(),
+        pipeline.available
+    );
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            _ = tokio::time::sleep(POLL_INTERVAL) => {}
+        }
+
+        let files = watcher.poll(Instant::now())?;
+        if files.is_empty() {
+            continue;
+        }
+
+        // Only content the cache cannot answer counts against the budget
+        let fresh = {
+            let cache = cache.lock().await;
+            files.iter().filter(|f| !is_cached(&cache, f)).count()
+        };
+        if fresh > 0 {
+            if let Err(e) = charge_budget(
+                bank.as_mut(),
+                config,
+                fresh as u64,
+                pipeline.seats,
+                override_budget,
+            ) {
+                eprintln!("{} {}", style::current().warn(), e);
+                continue;
+            }
+        }
+
+        let outcomes = tokio::select! {
+            _ = &mut ctrl_c => break,
+            outcomes = evaluate_changes(
+                files,
+                &pipeline.executors,
+                pipeline.engine.clone(),
+                cache.clone(),
+                &pipeline.options,
+                bank.as_mut(),
+                config.consensus.max_loops,
+                jobs,
+            ) => outcomes,
+        };
+
+        let time = chrono::Local::now().format("%H:%M:%S");
+        for outcome in &outcomes {
+            let file = outcome
+                .file
+                .path
+                .strip_prefix(dir)
+                .unwrap_or(&outcome.file.path)
+                .display();
+            match &outcome.result {
+                Ok(result) => {
+                    outln!(
+                        "[{}] {}  {}  {}{}",
+                        time,
+                        file,
+                        result.decision,
+                        result.score,
+                        if outcome.cached { " (cached)" } else { "" }
+                    );
+                    if let Some(reason) = &result.skip_reason {
+                        outln!("    {}", reason);
+                    }
+                    if result.decision != Decision::Pass {
+                        for finding in &result.findings {
+                            outln!("    [{}] {}", finding.severity, finding.issue);
+                        }
+                    }
+                }
+                Err(e) => outln!("[{}] {}  ERROR  {}", time, file, e),
+            }
+        }
+
+        if let Some(ref mut b) = bank {
+            if b.consolidation_due() && b.consolidate().is_ok() {
+                refresh_digest(b, config);
+            }
+        }
+    }
+
+    eprintln!("\nStopped watching {}.", dir.display());
+    Ok(())
+}
+
+/// One file of `tetrad evaluate --staged`.
+#[derive(Debug, Serialize)]
+pub struct StagedFileReport {
+    /// Path of the staged file.
+    pub path: String,
+
+    /// Language the diff was reviewed as.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Decision for the file's changes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision: Option<crate::types::responses::Decision>,
+
+    /// Score for the file's changes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<u8>,
+
+    /// Findings for the file's changes.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub findings: Vec<crate::types::responses::Finding>,
+
+    /// Why the file was not reviewed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+
+    /// Why the review failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Machine-readable report emitted by `tetrad evaluate --staged --format json`.
+#[derive(Debug, Serialize)]
+pub struct StagedReport {
+    /// Every staged file, reviewed or skipped, in diff order.
+    pub files: Vec<StagedFileReport>,
+
+    /// Average score of the reviewed files; `None` when none was reviewed.
+    pub score: Option<u8>,
+
+    /// `consensus.min_score` the average is held to.
+    pub min_score: u8,
+
+    /// Whether the commit should be rejected: a file was blocked or the
+    /// average score is below `min_score`.
+    pub failed: bool,
+}
+
+/// Why a staged file is not reviewed, if it is not.
+fn staged_skip_reason(
+    file: &super::git::FileDiff,
+    language: Option<&Language>,
+    max_file_bytes: usize,
+) -> Option<String> {
+    if file.deleted {
+        Some("deleted".to_string())
+    } else if file.binary {
+        Some("binary file".to_string())
+    } else if file.added_lines() == 0 {
+        Some("no added lines".to_string())
+    } else if file.text.len() > max_file_bytes {
+        Some(format!(
+            "diff is {} bytes, over the {}-byte limit",
+            file.text.len(),
+            max_file_bytes
+        ))
+    } else if language.is_none() {
+        Some("unknown language".to_string())
+    } else {
+        None
+    }
+}
+
+/// Reviews the staged changes (`git diff --cached`), one file at a time.
+///
+/// Each file's hunks are reviewed as a diff in the language its path
+/// suggests. Deleted, binary and oversized files, and files in an unknown
+/// language, are skipped with a notice. Returns `true` when the commit
+/// should be rejected: a file was blocked or the average score of the
+/// reviewed files is below `consensus.min_score`.
+pub async fn evaluate_staged(
+    language: &str,
+    max_file_bytes: usize,
+    jobs: usize,
+    format: OutputFormat,
+    override_budget: bool,
+    config: &Config,
+) -> TetradResult<bool> {
+    use super::batch::{evaluate_files, BatchFile};
+    use super::git::{parse_diff, staged_diff};
+    use crate::cache::EvaluationCache;
+    use crate::reasoning::PatternMatcher;
+    use crate::types::responses::Decision;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    let json = format == OutputFormat::Json;
+    let forced_language = (language != "auto").then(|| Language::parse(language));
+
+    let mut reports = Vec::new();
+    let mut batch = Vec::new();
+    for file in parse_diff(&staged_diff(Path::new("."))?) {
+        let language = forced_language.clone().or_else(|| {
+            PatternMatcher::language_from_path(Path::new(&file.path)).map(Language::from)
+        });
+        let skipped = staged_skip_reason(&file, language.as_ref(), max_file_bytes);
+        let report = StagedFileReport {
+            path: file.path.clone(),
+            language: language.as_ref().map(|l| l.as_str().to_string()),
+            decision: None,
+            score: None,
+            findings: Vec::new(),
+            skipped,
+            error: None,
+        };
+
+        match (&report.skipped, language) {
+            (None, Some(language)) => batch.push((
+                reports.len(),
+                BatchFile {
+                    path: PathBuf::from(&file.path),
+                    language,
+                    diff: Some(file.text),
+                },
+            )),
+            (reason, _) => progress!(
+                json,
+                "Skipping {}: {}",
+                file.path,
+                reason.as_deref().unwrap_or_default()
+            ),
+        }
+        reports.push(report);
+    }
+
+    if reports.is_empty() {
+        progress!(json, "No staged changes.");
+    }
+
+    if !batch.is_empty() {
+        let Some(pipeline) = prepare_batch(batch.len(), override_budget, config).await? else {
+            progress!(json, "No evaluator available. Install at least one CLI.");
+            return Ok(false);
+        };
+        eprintln!(
+            "Reviewing {} staged files with {} evaluators...\n",
+            batch.len(),
+            pipeline.available
+        );
+
+        let cache = Arc::new(Mutex::new(EvaluationCache::from_config(&config.cache)));
+        let (indices, files): (Vec<usize>, Vec<BatchFile>) = batch.into_iter().unzip();
+        let outcomes = evaluate_files(
+            files,
+            pipeline.executors,
+            pipeline.engine,
+            cache,
+            pipeline.options,
+            jobs,
+        )
+        .await;
+        for (index, outcome) in indices.into_iter().zip(outcomes) {
+            let report = &mut reports[index];
+            match outcome.result {
+                Ok(result) => {
+                    report.decision = Some(result.decision);
+                    report.score = Some(result.score);
+                    report.findings = result.findings;
+                    report.skipped = result.skip_reason;
+                }
+                Err(e) => report.error = Some(e.to_string()),
+            }
+        }
+    }
+
+    let scores: Vec<u32> = reports
+        .iter()
+        .filter_map(|r| r.score.map(u32::from))
+        .collect();
+    let score =
+        (!scores.is_empty()).then(|| (scores.iter().sum::<u32>() / scores.len() as u32) as u8);
+    let min_score = config.consensus.min_score;
+    let blocked: Vec<&str> = reports
+        .iter()
+        .filter(|r| r.decision == Some(Decision::Block))
+        .map(|r| r.path.as_str())
+        .collect();
+    let low_score = score.is_some_and(|score| score < min_score);
+    let failed = !blocked.is_empty() || low_score;
+
+    if json {
+        let report = StagedReport {
+            files: reports,
+            score,
+            min_score,
+            failed,
+        };
+        outln!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(failed);
+    }
+    if reports.is_empty() {
+        return Ok(false);
+    }
+
+    let width = reports
+        .iter()
+        .map(|r| r.path.len())
+        .max()
+        .unwrap_or(4)
+        .max(4);
+    outln!("{:<width$}  {:<8}  {:>5}", "FILE", "DECISION", "SCORE");
+    outln!("{}", "-".repeat(width + 17));
+    for report in &reports {
+        match (&report.decision, &report.skipped, &report.error) {
+            (Some(decision), _, _) => {
+                outln!(
+                    "{:<width$}  {:<8}  {:>5}",
+                    report.path,
+                    decision.to_string(),
+                    report.score.unwrap_or_default()
+                );
+                if let Some(reason) = &report.skipped {
+                    outln!("{:<width$}    {}", "", reason);
+                }
+                if *decision != Decision::Pass {
+                    for finding in &report.findings {
+                        outln!("{:<width$}    [{}] {}", "", finding.severity, finding.issue);
+                    }
+                }
+            }
+            (None, Some(reason), _) => {
+                outln!(
+                    "{:<width$}  {:<8}  {:>5}  {}",
+                    report.path,
+                    "SKIPPED",
+                    "-",
+                    reason
+                )
+            }
+            (None, None, error) => outln!(
+                "{:<width$}  {:<8}  {:>5}  {}",
+                report.path,
+                "ERROR",
+                "-",
+                error.as_deref().unwrap_or_default()
+            ),
+        }
+    }
+
+    outln!();
+    match score {
+        Some(score) => outln!("Average score: {} (min_score {})", score, min_score),
+        None => outln!("No staged file was reviewed."),
+    }
+    if !blocked.is_empty() {
+        outln!("Commit rejected: blocked in {}.", blocked.join(", "));
+    } else if low_score {
+        outln!("Commit rejected: average score below min_score.");
+    }
+
+    Ok(failed)
+}
+
+/// Installs the pre-commit hook that runs `tetrad evaluate --staged`.
+pub fn hook(action: HookAction) -> TetradResult<()> {
+    match action {
+        HookAction::Install { force } => {
+            let path = super::git::install_pre_commit_hook(Path::new("."), force)?;
+            outln!("Pre-commit hook installed at {}", path.display());
+            outln!("Staged changes are now reviewed before each commit (skip with --no-verify).");
+        }
+    }
+    Ok(())
+}
+
+/// Shows evaluation history from ReasoningBank.
+///
+/// With `executors`, shows how each executor voted instead: its vote
+/// distribution, mean score and how often it agreed with the final decision.
+pub async fn history(executors: bool, limit: usize, config: &Config) -> TetradResult<()> {
+    if !config.reasoning.enabled {
+        outln!("ReasoningBank is disabled in configuration.");
         return Ok(());
     }
 
     let db_path = &config.reasoning.db_path;
 
     if !db_path.exists() {
-        println!("ReasoningBank has not been created yet.");
-        println!("Run 'tetrad evaluate' to start collecting data.");
+        outln!("ReasoningBank has not been created yet.");
+        outln!("Run 'tetrad evaluate' to start collecting data.");
         return Ok(());
     }
 
     let bank = ReasoningBank::new_with_config(db_path, &config.reasoning)?;
+
+    if executors {
+        let mut stats: Vec<_> = bank.executor_accuracy_stats()?.into_iter().collect();
+        if stats.is_empty() {
+            outln!("No executor votes recorded yet.");
+            return Ok(());
+        }
+        stats.sort_by(|a, b| b.1.votes.cmp(&a.1.votes).then(a.0.cmp(&b.0)));
+
+        outln!("ReasoningBank - Executor Votes\n");
+        for (executor, stats) in stats.iter().take(limit) {
+            outln!(
+                "  {}: {} votes (PASS {}, WARN {}, FAIL {}), avg score {:.1}, {:.0}% agreement with decision",
+                executor,
+                stats.votes,
+                stats.pass,
+                stats.warn,
+                stats.fail,
+                stats.mean_score,
+                stats.agreement_rate * 100.0
+            );
+        }
+        return Ok(());
+    }
+
     let knowledge = bank.distill();
 
-    println!("ReasoningBank - Distilled Knowledge\n");
-    println!("Total patterns: {}", knowledge.total_patterns);
-    println!("Total trajectories: {}", knowledge.total_trajectories);
-    println!(
+    outln!("ReasoningBank - Distilled Knowledge\n");
+    outln!("Total patterns: {}", knowledge.total_patterns);
+    outln!("Total trajectories: {}", knowledge.total_trajectories);
+    outln!(
         "Average loops to consensus: {:.2}",
         knowledge.avg_loops_to_consensus
     );
 
     if !knowledge.top_antipatterns.is_empty() {
-        println!("\nTop Anti-patterns:");
+        outln!("\nTop Anti-patterns:");
         for (i, pattern) in knowledge.top_antipatterns.iter().take(limit).enumerate() {
-            println!(
+            outln!(
                 "  {}. {} ({}) - {} failures, {:.0}% confidence",
                 i + 1,
                 pattern.issue_category,
@@ -483,9 +2107,9 @@ pub async fn history(limit: usize, config: &Config) -> TetradResult<()> {
     }
 
     if !knowledge.top_good_patterns.is_empty() {
-        println!("\nTop Good Patterns:");
+        outln!("\nTop Good Patterns:");
         for (i, pattern) in knowledge.top_good_patterns.iter().take(limit).enumerate() {
-            println!(
+            outln!(
                 "  {}. {} ({}) - {} successes, {:.0}% confidence",
                 i + 1,
                 pattern.issue_category,
@@ -497,9 +2121,9 @@ pub async fn history(limit: usize, config: &Config) -> TetradResult<()> {
     }
 
     if !knowledge.language_stats.is_empty() {
-        println!("\nStatistics by language:");
+        outln!("\nStatistics by language:");
         for (lang, stats) in &knowledge.language_stats {
-            println!(
+            outln!(
                 "  {}: {} evaluations, {:.0}% success, avg score {:.1}",
                 lang,
                 stats.total_evaluations,
@@ -512,45 +2136,449 @@ pub async fn history(limit: usize, config: &Config) -> TetradResult<()> {
     Ok(())
 }
 
-/// Exports patterns from ReasoningBank.
-pub async fn export_patterns(output: &std::path::Path, config: &Config) -> TetradResult<()> {
-    use crate::reasoning::ReasoningBank;
+/// Shows ReasoningBank statistics and the monthly budget.
+///
+/// With `flakiness`, lists the code signatures whose decisions flipped most
+/// often between evaluations.
+pub async fn stats(flakiness: bool, limit: usize, config: &Config) -> TetradResult<()> {
+    if !config.reasoning.enabled {
+        outln!("ReasoningBank is disabled in configuration.");
+        return Ok(());
+    }
+
+    let db_path = &config.reasoning.db_path;
+
+    if !db_path.exists() {
+        outln!("ReasoningBank has not been created yet.");
+        outln!("Run 'tetrad evaluate' to start collecting data.");
+        return Ok(());
+    }
+
+    let bank = ReasoningBank::new_with_config(db_path, &config.reasoning)?;
+
+    if !flakiness {
+        outln!("ReasoningBank - Statistics\n");
+        let knowledge = bank.distill();
+        outln!("Total patterns: {}", knowledge.total_patterns);
+        outln!("Total evaluations: {}", knowledge.total_trajectories);
+        let legacy = bank.count_legacy_patterns()?;
+        if legacy > 0 {
+            outln!(
+                "Legacy patterns: {} (recorded before structural signatures; matched by keyword only)",
+                legacy
+            );
+        }
+
+        let budget = bank.budget_status(&config.general.budget, chrono::Utc::now())?;
+        outln!("\nBudget ({}):", budget.usage.month);
+        match budget.max_evaluations {
+            Some(max) => outln!(
+                "  Evaluations: {}/{} ({} remaining)",
+                budget.usage.evaluations,
+                max,
+                budget.remaining_evaluations.unwrap_or(0)
+            ),
+            None => outln!("  Evaluations: {} (no limit)", budget.usage.evaluations),
+        }
+        if budget.usage.estimated_cost > 0.0 || budget.max_estimated_cost.is_some() {
+            match budget.max_estimated_cost {
+                Some(max) => outln!(
+                    "  Estimated cost: {:.2}/{:.2} ({:.2} remaining)",
+                    budget.usage.estimated_cost,
+                    max,
+                    budget.remaining_cost.unwrap_or(0.0)
+                ),
+                None => outln!("  Estimated cost: {:.2}", budget.usage.estimated_cost),
+            }
+        }
+
+        outln!("\nUse 'tetrad stats --flakiness' to list flaky signatures.");
+        outln!("Use 'tetrad stats --bucket week' to see trends over time.");
+        return Ok(());
+    }
+
+    let report = bank.flakiness_report(limit)?;
+
+    if report.is_empty() {
+        outln!("No flaky evaluations recorded.");
+        return Ok(());
+    }
+
+    outln!("Flaky signatures (same code, different decisions)\n");
+    for (i, signature) in report.iter().enumerate() {
+        outln!(
+            "  {}. {} - {} evaluations, {} flips, last: {} ({})",
+            i + 1,
+            &signature.code_hash[..signature.code_hash.len().min(12)],
+            signature.evaluations,
+            signature.flips,
+            signature.last_request_id,
+            signature.last_seen.format("%Y-%m-%d %H:%M")
+        );
+    }
+
+    Ok(())
+}
+
+/// Shows the evaluation metrics the MCP server recorded in the ReasoningBank.
+///
+/// These are the counters `tetrad_status` reports under `metrics`, summed
+/// over every server run instead of since startup. With
+/// [`OutputFormat::Json`], prints the same JSON object, with zero counters
+/// when the ReasoningBank is disabled or empty.
+pub async fn metrics(format: OutputFormat, config: &Config) -> TetradResult<()> {
+    use crate::hooks::Metrics;
+
+    let db_path = &config.reasoning.db_path;
+    let available = config.reasoning.enabled && db_path.exists();
+    let counters = if available {
+        ReasoningBank::new_with_config(db_path, &config.reasoning)?.metric_counters()?
+    } else {
+        Vec::new()
+    };
+    let metrics = Metrics::from_counters(&counters);
+
+    if format == OutputFormat::Json {
+        outln!("{}", serde_json::to_string_pretty(&metrics)?);
+        return Ok(());
+    }
+
+    if !config.reasoning.enabled {
+        outln!("ReasoningBank is disabled in configuration.");
+        return Ok(());
+    }
+    if metrics.total_evaluations == 0 && metrics.recertifications == 0 {
+        outln!("No evaluations recorded yet.");
+        outln!("Metrics are collected by the MCP server ('tetrad serve').");
+        return Ok(());
+    }
+
+    outln!("Evaluation metrics (all server runs)\n");
+    outln!("Evaluations: {}", metrics.total_evaluations);
+    outln!(
+        "  PASS: {}  REVISE: {}  BLOCK: {}",
+        metrics.passes,
+        metrics.revises,
+        metrics.blocks
+    );
+    outln!("Success rate: {:.1}%", metrics.success_rate * 100.0);
+    outln!("Average score: {:.1}", metrics.average_score);
+    outln!("Re-certified final checks: {}", metrics.recertifications);
+
+    if !metrics.by_type.is_empty() {
+        outln!(
+            "\n{:<14}  {:>6}  {:>6}  {:>6}  {:>6}",
+            "TYPE",
+            "EVALS",
+            "PASS",
+            "REVISE",
+            "BLOCK"
+        );
+        for (evaluation_type, counts) in &metrics.by_type {
+            outln!(
+                "{:<14}  {:>6}  {:>6}  {:>6}  {:>6}",
+                evaluation_type,
+                counts.evaluations,
+                counts.passes,
+                counts.revises,
+                counts.blocks
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Trends printed by `tetrad stats --bucket --format json`.
+#[derive(Serialize)]
+struct TrendsReport {
+    bucket: StatsBucket,
+    since: Option<NaiveDate>,
+    periods: Vec<PeriodStats>,
+}
+
+/// Shows ReasoningBank trends: evaluations, success rate, average score,
+/// average loops and block rate per day or week, oldest first.
+///
+/// With `since`, only evaluations from that day on are counted. With
+/// [`OutputFormat::Json`], prints a single JSON document, with no periods
+/// when the ReasoningBank is disabled or empty.
+pub async fn stats_trends(
+    bucket: TrendBucket,
+    since: Option<NaiveDate>,
+    format: OutputFormat,
+    config: &Config,
+) -> TetradResult<()> {
+    let db_path = &config.reasoning.db_path;
+    let available = config.reasoning.enabled && db_path.exists();
+    let mut report = TrendsReport {
+        bucket: bucket.stats_bucket(),
+        since,
+        periods: Vec::new(),
+    };
+    if available {
+        let bank = ReasoningBank::new_with_config(db_path, &config.reasoning)?;
+        report.periods = bank.stats_by_period(since, report.bucket)?;
+    }
+
+    if format == OutputFormat::Json {
+        outln!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if !config.reasoning.enabled {
+        outln!("ReasoningBank is disabled in configuration.");
+        return Ok(());
+    }
+    if !available {
+        outln!("ReasoningBank has not been created yet.");
+        outln!("Run 'tetrad evaluate' to start collecting data.");
+        return Ok(());
+    }
+
+    match since {
+        Some(since) => outln!("Trends by {} since {}\n", report.bucket, since),
+        None => outln!("Trends by {}\n", report.bucket),
+    }
+    if report.periods.is_empty() {
+        outln!("No evaluations recorded in this period.");
+        return Ok(());
+    }
+
+    outln!(
+        "{:<10}  {:>6}  {:>7}  {:>9}  {:>9}  {:>10}",
+        "PERIOD",
+        "EVALS",
+        "SUCCESS",
+        "AVG SCORE",
+        "AVG LOOPS",
+        "BLOCK RATE"
+    );
+    for period in &report.periods {
+        outln!(
+            "{:<10}  {:>6}  {:>6.1}%  {:>9.1}  {:>9.2}  {:>9.1}%",
+            period.period,
+            period.evaluations,
+            period.success_rate * 100.0,
+            period.avg_score,
+            period.avg_loops,
+            period.block_rate * 100.0
+        );
+    }
+
+    if let [first, .., last] = report.periods.as_slice() {
+        let change = (last.block_rate - first.block_rate) * 100.0;
+        let direction = if change < 0.0 { "down" } else { "up" };
+        outln!(
+            "\nBlock rate: {:.1}% in the first {}, {:.1}% in the last ({} {:.1} points).",
+            first.block_rate * 100.0,
+            report.bucket,
+            last.block_rate * 100.0,
+            direction,
+            change.abs()
+        );
+    }
+
+    Ok(())
+}
+
+/// Applies the trajectory retention settings to the ReasoningBank and
+/// reports row counts per table and, for SQLite, the database size before
+/// and after.
+///
+/// With `vacuum`, the space freed by deleted rows is returned to the file
+/// system; without it the file keeps its size and reuses the space.
+pub async fn maintenance(vacuum: bool, config: &Config) -> TetradResult<()> {
+    use crate::types::config::StorageBackend;
+
+    if !config.reasoning.enabled {
+        outln!("ReasoningBank is disabled in configuration.");
+        return Ok(());
+    }
+
+    let db_path = &config.reasoning.db_path;
+    let sqlite = config.reasoning.backend == StorageBackend::Sqlite;
+
+    if sqlite && !db_path.exists() {
+        outln!("ReasoningBank has not been created yet.");
+        return Ok(());
+    }
+
+    let mut bank = ReasoningBank::new_with_config(db_path, &config.reasoning)?;
+    // Flush the journal first so both sizes are measured the same way
+    bank.checkpoint()?;
+    let size_before = sqlite.then(|| database_size(db_path));
+    let counts_before = bank.table_counts()?;
+
+    let pruned = bank.prune_trajectories(vacuum)?;
+    bank.checkpoint()?;
+    let size_after = sqlite.then(|| database_size(db_path));
+    let counts_after = bank.table_counts()?;
+
+    outln!("ReasoningBank maintenance\n");
+    let max = match config.reasoning.max_trajectories {
+        0 => "no limit".to_string(),
+        max => max.to_string(),
+    };
+    let retention = match config.reasoning.trajectory_retention_days {
+        0 => "forever".to_string(),
+        days => format!("{} days", days),
+    };
+    outln!(
+        "Retention: at most {} trajectories, kept {}",
+        max,
+        retention
+    );
+    outln!("Trajectories pruned: {}\n", pruned);
+
+    outln!("  {:<20} {:>10} {:>10}", "Table", "Before", "After");
+    for ((table, before), (_, after)) in counts_before.iter().zip(&counts_after) {
+        outln!("  {:<20} {:>10} {:>10}", table, before, after);
+    }
+
+    let digest_path = config.data_dir().join(DIGEST_FILE_NAME);
+    let digest_state = if bank.write_digest(&digest_path)? {
+        "updated"
+    } else {
+        "unchanged"
+    };
+    outln!("\nDigest: {} ({})", digest_path.display(), digest_state);
+
+    if let (Some(before), Some(after)) = (size_before, size_after) {
+        outln!(
+            "\nDatabase size: {} -> {}",
+            format_size(before),
+            format_size(after)
+        );
+        if pruned > 0 && !vacuum {
+            outln!("Run with --vacuum to return the freed space to the file system.");
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrites `digest.md` in the data directory after a consolidation.
+///
+/// Failures are only logged: the evaluation already succeeded.
+fn refresh_digest(bank: &ReasoningBank, config: &Config) {
+    let path = config.data_dir().join(DIGEST_FILE_NAME);
+    match bank.write_digest(&path) {
+        Ok(true) => tracing::debug!("Digest updated at {}", path.display()),
+        Ok(false) => {}
+        Err(e) => tracing::warn!("Could not write digest to {}: {}", path.display(), e),
+    }
+}
+
+/// Size in bytes of a SQLite database, including its WAL journal.
+fn database_size(db_path: &Path) -> u64 {
+    let mut wal = db_path.as_os_str().to_owned();
+    wal.push("-wal");
+    [db_path, Path::new(&wal)]
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Formats a byte count for display (`512 B`, `1.5 KiB`, `12.0 MiB`).
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 3] = ["KiB", "MiB", "GiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
 
+/// Exports patterns from ReasoningBank.
+///
+/// Without `output`, writes `tetrad-patterns.<ext>` for the chosen format.
+pub async fn export_patterns(
+    output: Option<&Path>,
+    options: &ExportOptions,
+    config: &Config,
+) -> TetradResult<()> {
     if !config.reasoning.enabled {
-        println!("ReasoningBank is disabled in configuration.");
+        outln!("ReasoningBank is disabled in configuration.");
         return Ok(());
     }
 
     let db_path = &config.reasoning.db_path;
 
     if !db_path.exists() {
-        println!("ReasoningBank has not been created yet.");
-        println!("No patterns to export.");
+        outln!("ReasoningBank has not been created yet.");
+        outln!("No patterns to export.");
         return Ok(());
     }
 
+    let output = output.map_or_else(
+        || PathBuf::from(format!("tetrad-patterns.{}", options.format.extension())),
+        Path::to_path_buf,
+    );
+
     let bank = ReasoningBank::new_with_config(db_path, &config.reasoning)?;
-    bank.export(output)?;
+    let exported = bank.export(&output, options)?;
 
-    println!("Patterns exported to: {}", output.display());
+    outln!("{} patterns exported to: {}", exported, output.display());
 
     Ok(())
 }
 
 /// Imports patterns into ReasoningBank.
-pub async fn import_patterns(input: &std::path::Path, config: &Config) -> TetradResult<()> {
-    use crate::reasoning::ReasoningBank;
+///
+/// `input` may be a local path, a `file://` URL or an `http(s)://` URL. The
+/// pack is size-capped and, when `sha256` is given, verified before anything
+/// is written to the database. Its signature is checked against the keys in
+/// `trusted_keys` (default: `trusted-keys` in the data directory); with
+/// `require_signature` a pack without a valid trusted signature is refused.
+pub async fn import_patterns(
+    input: &str,
+    sha256: Option<&str>,
+    require_signature: bool,
+    trusted_keys: Option<&Path>,
+    config: &Config,
+) -> TetradResult<()> {
+    use crate::reasoning::{
+        fetch_pack, FetchOptions, PackSource, SignaturePolicy, TrustedKeys, TRUSTED_KEYS_DIR_NAME,
+    };
 
     if !config.reasoning.enabled {
-        println!("ReasoningBank is disabled in configuration.");
+        outln!("ReasoningBank is disabled in configuration.");
         return Ok(());
     }
 
-    if !input.exists() {
-        println!("File not found: {}", input.display());
-        return Ok(());
+    let source = PackSource::parse(input)?;
+    if let PackSource::File(path) = &source {
+        if !path.exists() {
+            outln!("File not found: {}", path.display());
+            return Ok(());
+        }
     }
 
+    let mut options = FetchOptions::default();
+    if let Some(sha256) = sha256 {
+        options = options.with_sha256(sha256);
+    }
+    let pack = fetch_pack(&source, &options).await?;
+
+    let default_keys = config.data_dir().join(TRUSTED_KEYS_DIR_NAME);
+    let trusted = match trusted_keys {
+        Some(dir) => TrustedKeys::load_dir(dir)?,
+        None if default_keys.is_dir() => TrustedKeys::load_dir(&default_keys)?,
+        None => TrustedKeys::default(),
+    };
+    let policy = SignaturePolicy {
+        require: require_signature,
+        trusted,
+    };
+
     let db_path = &config.reasoning.db_path;
 
     // Create directory if it doesn't exist
@@ -559,16 +2587,408 @@ pub async fn import_patterns(input: &std::path::Path, config: &Config) -> Tetrad
     }
 
     let mut bank = ReasoningBank::new_with_config(db_path, &config.reasoning)?;
-    let result = bank.import(input)?;
+    let result = bank.import_bytes_with_policy(&pack, &source.to_string(), &policy)?;
+
+    if !result.signature.is_verified() {
+        outln!("Warning: {}", result.signature);
+    }
+    outln!("Import completed:");
+    outln!("  Patterns imported: {}", result.imported);
+    outln!("  Patterns skipped (already exist): {}", result.skipped);
+    outln!("  Patterns merged: {}", result.merged);
+    if result.signature.is_verified() {
+        outln!("  Signature: {}", result.signature);
+    }
+
+    Ok(())
+}
+
+/// Generates a key pair for signing pattern exports in the data directory.
+pub fn keygen(force: bool, config: &Config) -> TetradResult<()> {
+    use crate::reasoning::{fingerprint, generate_signing_key, write_keypair};
+
+    let key = generate_signing_key()?;
+    let (private, public) = write_keypair(&config.data_dir(), &key, force)?;
+
+    outln!("Private key: {}", private.display());
+    outln!("Public key:  {}", public.display());
+    outln!("Fingerprint: {}", fingerprint(&key.verifying_key()));
+    outln!();
+    outln!(
+        "Sign exports with: tetrad export --sign-key {}",
+        private.display()
+    );
+    outln!("Importers trust the key by copying the public key into their trusted-keys directory.");
+
+    Ok(())
+}
+
+/// Shows statistics for, or clears, the persisted evaluation cache.
+///
+/// The MCP server keeps its cache in memory; only entries written to
+/// `cache.persist_path` are visible from the CLI.
+pub async fn cache(action: CacheAction, config: &Config) -> TetradResult<()> {
+    use crate::cache::EvaluationCache;
+
+    let Some(path) = &config.cache.persist_path else {
+        outln!("Cache persistence is disabled; the cache lives inside the MCP server process.");
+        outln!("Set cache.persist_path in tetrad.toml to inspect it from the CLI.");
+        return Ok(());
+    };
+
+    let mut cache = EvaluationCache::from_config(&config.cache);
+
+    match action {
+        CacheAction::Stats => {
+            let stats = cache.stats();
+            outln!("Evaluation cache ({})\n", path.display());
+            outln!("Entries: {} / {}", stats.size, stats.capacity);
+            outln!(
+                "Hit rate: {:.1}% ({} hits, {} misses)",
+                stats.hit_rate() * 100.0,
+                stats.hits,
+                stats.misses
+            );
+            match stats.oldest_entry {
+                Some(oldest) => outln!("Oldest entry: {}", oldest.format("%Y-%m-%d %H:%M:%S UTC")),
+                None => outln!("Oldest entry: -"),
+            }
+        }
+        CacheAction::Clear => {
+            let removed = cache.stats().size;
+            cache.clear();
+            outln!("Cache cleared ({} entries removed).", removed);
+        }
+    }
+
+    Ok(())
+}
+
+/// Refuses to use a configuration file that defines commands (custom
+/// executors, changed executor commands) until the user approves it.
+///
+/// An approved file is used silently until its contents change. With
+/// `trust`, the file is approved without asking; otherwise the user is asked
+/// in a terminal, and outside one the command fails with
+/// [`TetradError::UntrustedConfig`](crate::TetradError::UntrustedConfig).
+pub fn ensure_trusted(config_path: &Path, config: &Config, trust: bool) -> TetradResult<()> {
+    use crate::types::trust::{executable_surface, TrustStatus, TrustStore};
+    use std::io::IsTerminal;
+
+    let surface = executable_surface(config);
+    if surface.is_empty() {
+        return Ok(());
+    }
+    let Ok(contents) = std::fs::read(config_path) else {
+        // Defaults are used when the file cannot be read
+        return Ok(());
+    };
+
+    let mut store = TrustStore::open_default()?;
+    let status = store.status(config_path, &contents);
+    if status == TrustStatus::Trusted {
+        return Ok(());
+    }
+
+    let reason = match status {
+        TrustStatus::Modified => "changed since it was trusted",
+        _ => "is not trusted yet",
+    };
+    let interactive = std::io::stdin().is_terminal() && std::io::stderr().is_terminal();
+    let approved = if trust {
+        true
+    } else if interactive {
+        // stderr, since stdout may belong to the MCP transport
+        eprintln!("{} {}; it runs:", config_path.display(), reason);
+        for command in &surface {
+            eprintln!("  {}", command);
+        }
+        super::interactive::confirm("Trust this configuration?", false)?
+    } else {
+        false
+    };
+
+    if !approved {
+        return Err(crate::TetradError::UntrustedConfig {
+            path: config_path.display().to_string(),
+            reason: reason.to_string(),
+            surface,
+        });
+    }
+
+    store.approve(config_path, &contents);
+    store.save()?;
+    tracing::info!(config = %config_path.display(), "Configuration trusted");
+    Ok(())
+}
+
+/// Lists or revokes approved configuration files.
+pub async fn trust(action: TrustAction, config_path: &Path) -> TetradResult<()> {
+    use crate::types::trust::TrustStore;
+
+    let mut store = TrustStore::open_default()?;
+    match action {
+        TrustAction::List => {
+            if store.entries().next().is_none() {
+                outln!("No trusted configuration files.");
+                return Ok(());
+            }
+            outln!("Trusted configuration files ({})\n", store.path().display());
+            for (path, entry) in store.entries() {
+                outln!(
+                    "  {}  (trusted {})",
+                    path.display(),
+                    entry.approved_at.format("%Y-%m-%d %H:%M:%S UTC")
+                );
+            }
+        }
+        TrustAction::Revoke { path } => {
+            let path = path.as_deref().unwrap_or(config_path);
+            if store.revoke(path) {
+                store.save()?;
+                outln!("Revoked trust in {}.", path.display());
+            } else {
+                outln!("{} was not trusted.", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Estimates latency and cost of reviewing `code` without calling any evaluator.
+///
+/// The persisted cache (`cache.persist_path`) is checked for an exact hit.
+pub async fn estimate(
+    code: &str,
+    language: &str,
+    review_type: ReviewType,
+    format: OutputFormat,
+    config: &Config,
+) -> TetradResult<()> {
+    use crate::mcp::ToolHandler;
+    use crate::reasoning::PatternMatcher;
+    use crate::types::requests::EvaluationRequest;
+
+    let (code_content, file_path) = match code.strip_prefix('@') {
+        Some(file_path) => (std::fs::read_to_string(file_path)?, Some(file_path)),
+        None => (code.to_string(), None),
+    };
+    let language = if language == "auto" {
+        PatternMatcher::detect_language_with_confidence(&code_content, file_path).0
+    } else {
+        language.to_string()
+    };
+
+    let mut request =
+        EvaluationRequest::new(&code_content, &language).with_type(review_type.evaluation_type());
+    if let Some(file_path) = file_path {
+        request = request.with_file_path(file_path);
+    }
+
+    // Estimates need neither the ReasoningBank nor the result store
+    let mut config = config.clone();
+    config.reasoning.enabled = false;
+    config.mcp.results_db_path = None;
+    let handler = ToolHandler::new(config)?;
+    let estimate = handler.estimate(&request).await;
+
+    if format == OutputFormat::Json {
+        outln!("{}", serde_json::to_string_pretty(&estimate)?);
+        return Ok(());
+    }
+
+    outln!(
+        "Estimate ({}, {} tokens)\n",
+        request.language,
+        estimate.prompt_tokens
+    );
+    for executor in &estimate.executors {
+        let status = if executor.skipped {
+            format!("skipped ({})", executor.circuit)
+        } else if executor.vote_cached {
+            "vote cached".to_string()
+        } else {
+            format!(
+                "~{:.1}s ({}), {:.4} cost",
+                executor.latency_ms as f64 / 1000.0,
+                executor.latency_source,
+                executor.cost
+            )
+        };
+        outln!("  {:<10} {}", executor.name, status);
+    }
+    outln!();
+    if estimate.cache_hit_probability >= 1.0 {
+        outln!("Cached: the review would be answered from the cache.");
+    } else {
+        outln!(
+            "Expected: ~{:.1}s, {:.4} cost",
+            estimate.expected_latency_ms as f64 / 1000.0,
+            estimate.expected_cost
+        );
+    }
+    Ok(())
+}
+
+/// Lists, shows, deletes or manually adds ReasoningBank patterns.
+///
+/// With `--format json`, stdout carries a single JSON document: an array for
+/// `list`, the full pattern for `show` and `add`.
+pub async fn patterns(action: PatternsAction, config: &Config) -> TetradResult<()> {
+    use crate::reasoning::{PatternFilter, ReasoningBank};
+    use crate::TetradError;
+
+    if !config.reasoning.enabled {
+        outln!("ReasoningBank is disabled in configuration.");
+        return Ok(());
+    }
+
+    let db_path = &config.reasoning.db_path;
+    let is_add = matches!(action, PatternsAction::Add { .. });
+
+    if !db_path.exists() && !is_add {
+        if let PatternsAction::List {
+            format: OutputFormat::Json,
+            ..
+        } = action
+        {
+            outln!("[]");
+            return Ok(());
+        }
+        outln!("ReasoningBank has not been created yet.");
+        outln!("Run 'tetrad evaluate' to start collecting data.");
+        return Ok(());
+    }
+
+    let mut bank = ReasoningBank::new_with_config(db_path, &config.reasoning)?;
+    let not_found = |id: i64| TetradError::ReasoningBank(format!("pattern {} not found", id));
+
+    match action {
+        PatternsAction::List {
+            pattern_type,
+            language,
+            category,
+            sort,
+            limit,
+            format,
+        } => {
+            let filter = PatternFilter {
+                pattern_type: pattern_type.map(|kind| kind.pattern_type()),
+                language,
+                category,
+                min_confidence: None,
+                sort: sort.sort(),
+                limit: Some(limit),
+            };
+            let patterns = bank.list_patterns(&filter)?;
+
+            if format == OutputFormat::Json {
+                outln!("{}", serde_json::to_string_pretty(&patterns)?);
+                return Ok(());
+            }
+
+            if patterns.is_empty() {
+                outln!("No patterns found.");
+                return Ok(());
+            }
+
+            outln!(
+                "{:>6}  {:<12}  {:<10}  {:<14}  {:>5}  {:>7}  DESCRIPTION",
+                "ID",
+                "TYPE",
+                "LANGUAGE",
+                "CATEGORY",
+                "CONF",
+                "SEEN"
+            );
+            for pattern in &patterns {
+                outln!(
+                    "{:>6}  {:<12}  {:<10}  {:<14}  {:>4.0}%  {:>7}  {}",
+                    pattern.id,
+                    pattern.pattern_type,
+                    pattern.language,
+                    pattern.issue_category,
+                    pattern.confidence * 100.0,
+                    pattern.success_count + pattern.failure_count,
+                    first_line(&pattern.description, 60)
+                );
+            }
+        }
+        PatternsAction::Show { id, format } => {
+            let pattern = bank.get_pattern(id)?.ok_or_else(|| not_found(id))?;
+            print_pattern(&pattern, format)?;
+        }
+        PatternsAction::Delete { id } => {
+            if !bank.delete_pattern(id)? {
+                return Err(not_found(id));
+            }
+            outln!("Pattern {} deleted.", id);
+        }
+        PatternsAction::Add {
+            description,
+            category,
+            language,
+            solution,
+            format,
+        } => {
+            let id = bank.insert_manual_pattern(
+                &language,
+                &category,
+                &description,
+                solution.as_deref(),
+            )?;
+            let pattern = bank.get_pattern(id)?.ok_or_else(|| not_found(id))?;
+            if format == OutputFormat::Text {
+                outln!("Pattern {} added.\n", id);
+            }
+            print_pattern(&pattern, format)?;
+        }
+    }
 
-    println!("Import completed:");
-    println!("  Patterns imported: {}", result.imported);
-    println!("  Patterns skipped (already exist): {}", result.skipped);
-    println!("  Patterns merged: {}", result.merged);
+    Ok(())
+}
 
+/// Prints a pattern as text or JSON.
+fn print_pattern(pattern: &crate::reasoning::Pattern, format: OutputFormat) -> TetradResult<()> {
+    if format == OutputFormat::Json {
+        outln!("{}", serde_json::to_string_pretty(pattern)?);
+        return Ok(());
+    }
+
+    outln!("Pattern {}", pattern.id);
+    outln!("  Type: {}", pattern.pattern_type);
+    outln!("  Artifact: {}", pattern.artifact_kind);
+    outln!("  Language: {}", pattern.language);
+    outln!("  Category: {}", pattern.issue_category);
+    outln!("  Signature: {}", pattern.code_signature);
+    outln!(
+        "  Confidence: {:.0}% ({} successes, {} failures)",
+        pattern.confidence * 100.0,
+        pattern.success_count,
+        pattern.failure_count
+    );
+    outln!(
+        "  First seen: {}",
+        pattern.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    outln!(
+        "  Last seen: {}",
+        pattern.last_seen.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    outln!("  Description: {}", pattern.description);
+    outln!("  Solution: {}", pattern.solution.as_deref().unwrap_or("-"));
     Ok(())
 }
 
+/// First line of `text`, cut to `max_chars` characters.
+fn first_line(text: &str, max_chars: usize) -> String {
+    let line = text.lines().next().unwrap_or("");
+    match line.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -579,6 +2999,210 @@ mod tests {
         version();
     }
 
+    #[test]
+    fn test_decision_exit_code() {
+        assert_eq!(decision_exit_code(Decision::Pass), 0);
+        assert_eq!(decision_exit_code(Decision::Revise), 1);
+        assert_eq!(decision_exit_code(Decision::Block), 2);
+    }
+
+    #[test]
+    fn test_gitignore_with_tetrad() {
+        assert_eq!(
+            gitignore_with_tetrad("").unwrap(),
+            "# Tetrad - local database and cache\n.tetrad/\n"
+        );
+        assert_eq!(
+            gitignore_with_tetrad("target/\n").unwrap(),
+            "target/\n\n# Tetrad - local database and cache\n.tetrad/\n"
+        );
+
+        // Already present, in any case
+        assert!(gitignore_with_tetrad("target/\n.tetrad/\n").is_none());
+        assert!(gitignore_with_tetrad(".Tetrad\n").is_none());
+    }
+
+    #[test]
+    fn test_gitignore_with_tetrad_dedupes_entries() {
+        let racy = "target/\n\n# Tetrad - local database and cache\n.tetrad/\n\n\
+                    # Tetrad - local database and cache\n.TETRAD/\nnode_modules/\n";
+
+        assert_eq!(
+            gitignore_with_tetrad(racy).unwrap(),
+            "target/\n\n# Tetrad - local database and cache\n.tetrad/\n\nnode_modules/\n"
+        );
+    }
+
+    #[test]
+    fn test_project_config_is_discovered_from_subdirectories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = std::fs::canonicalize(dir.path()).unwrap();
+        std::fs::write(root.join("tetrad.toml"), "").unwrap();
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+
+        let found = Config::discover(&root.join("src/nested")).unwrap();
+        assert_eq!(found, root.join("tetrad.toml"));
+
+        let config = Config::load(found).unwrap().with_resolved_paths();
+        assert_eq!(config.project_root.as_deref(), Some(root.as_path()));
+        if std::env::var_os(crate::types::config::DATA_DIR_ENV).is_none() {
+            assert_eq!(config.data_dir(), root.join(".tetrad"));
+            assert_eq!(config.reasoning.db_path, root.join(".tetrad/tetrad.db"));
+        }
+    }
+
+    #[test]
+    fn test_stray_data_dir_is_migrated() {
+        let dir = tempfile::tempdir().unwrap();
+        let stray = dir.path().join("sub/.tetrad");
+        let data_dir = dir.path().join(".tetrad");
+        std::fs::create_dir_all(&stray).unwrap();
+
+        // Only the init lock: nothing to migrate
+        std::fs::write(stray.join(".lock"), "").unwrap();
+        assert!(stray_data_dir(&dir.path().join("sub"), &data_dir).is_none());
+
+        std::fs::write(stray.join("tetrad.db"), "bank").unwrap();
+        assert_eq!(
+            stray_data_dir(&dir.path().join("sub"), &data_dir),
+            Some(stray.clone())
+        );
+        // The data directory itself is never stray
+        assert!(stray_data_dir(&dir.path().join("sub"), &stray).is_none());
+
+        std::fs::create_dir_all(&data_dir).unwrap();
+        std::fs::write(data_dir.join(".lock"), "").unwrap();
+        assert_eq!(migrate_data_dir(&stray, &data_dir).unwrap(), 1);
+        assert_eq!(
+            std::fs::read_to_string(data_dir.join("tetrad.db")).unwrap(),
+            "bank"
+        );
+        // The stray lock was kept, so the directory stays
+        assert!(stray.join(".lock").exists());
+        assert!(!stray.join("tetrad.db").exists());
+    }
+
+    #[tokio::test]
+    async fn test_init_is_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_path_buf();
+
+        let (first, second) = tokio::join!(
+            init(Some(path.clone()), None),
+            init(Some(path.clone()), None)
+        );
+        first.unwrap();
+        second.unwrap();
+        init(Some(path.clone()), None).await.unwrap();
+
+        Config::load(path.join("tetrad.toml")).unwrap();
+        let gitignore = std::fs::read_to_string(path.join(".gitignore")).unwrap();
+        assert_eq!(gitignore.matches(".tetrad/").count(), 1);
+        // No temporary file is left behind
+        let leftovers: Vec<_> = std::fs::read_dir(&path)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name.to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty(), "{:?}", leftovers);
+    }
+
+    #[test]
+    fn test_register_claude_server_creates_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = claude_settings_path(ClaudeScope::Project, dir.path()).unwrap();
+        assert_eq!(settings, dir.path().join(".mcp.json"));
+
+        let exe = Path::new("/opt/tetrad/bin/tetrad");
+        assert_eq!(
+            register_claude_server(&settings, exe).unwrap(),
+            ClaudeRegistration::Created
+        );
+
+        let content = std::fs::read_to_string(&settings).unwrap();
+        assert!(content.ends_with("}\n"));
+        let parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(
+            parsed["mcpServers"]["tetrad"],
+            serde_json::json!({
+                "type": "stdio",
+                "command": "/opt/tetrad/bin/tetrad",
+                "args": ["serve"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_register_claude_server_preserves_other_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let settings = dir.path().join(".mcp.json");
+        let original = "{\n    \"zeta\": true,\n    \"mcpServers\": {\n        \"other\": {\n            \"command\": \"other-server\"\n        }\n    },\n    \"alpha\": 1\n}\n";
+        std::fs::write(&settings, original).unwrap();
+        let exe = Path::new("/usr/local/bin/tetrad");
+
+        assert_eq!(
+            register_claude_server(&settings, exe).unwrap(),
+            ClaudeRegistration::Added
+        );
+        let content = std::fs::read_to_string(&settings).unwrap();
+        // Key order and the 4-space indentation are kept
+        assert!(
+            content.starts_with("{\n    \"zeta\": true,\n    \"mcpServers\": {\n        \"other\"")
+        );
+        assert!(content.ends_with("    \"alpha\": 1\n}\n"));
+        let parsed: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(parsed["mcpServers"]["other"]["command"], "other-server");
+        assert_eq!(
+            parsed["mcpServers"]["tetrad"]["command"],
+            "/usr/local/bin/tetrad"
+        );
+
+        // A second run leaves the file as it is
+        assert_eq!(
+            register_claude_server(&settings, exe).unwrap(),
+            ClaudeRegistration::Unchanged
+        );
+        assert_eq!(std::fs::read_to_string(&settings).unwrap(), content);
+
+        // A new binary location replaces the entry
+        assert_eq!(
+            register_claude_server(&settings, Path::new("/home/dev/.cargo/bin/tetrad")).unwrap(),
+            ClaudeRegistration::Updated
+        );
+        let parsed: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&settings).unwrap()).unwrap();
+        assert_eq!(
+            parsed["mcpServers"]["tetrad"]["command"],
+            "/home/dev/.cargo/bin/tetrad"
+        );
+    }
+
+    #[test]
+    fn test_register_claude_server_backs_up_malformed_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = Path::new("/usr/local/bin/tetrad");
+
+        for (name, original) in [
+            (".mcp.json", "{ \"mcpServers\": { \"other\": "),
+            ("servers-array.json", "{ \"mcpServers\": [] }"),
+        ] {
+            let settings = dir.path().join(name);
+            std::fs::write(&settings, original).unwrap();
+
+            let err = register_claude_server(&settings, exe).unwrap_err();
+            assert!(
+                err.to_string().contains("not valid Claude Code settings"),
+                "{}",
+                err
+            );
+
+            // The original is untouched and a copy sits next to it
+            assert_eq!(std::fs::read_to_string(&settings).unwrap(), original);
+            let backup = dir.path().join(format!("{}.bak", name));
+            assert_eq!(std::fs::read_to_string(&backup).unwrap(), original);
+        }
+    }
+
     #[tokio::test]
     async fn test_status() {
         // Verify status runs without errors
@@ -591,7 +3215,83 @@ mod tests {
     async fn test_doctor() {
         // Verify doctor runs without errors
         let config = Config::default_config();
-        let result = doctor(&config).await;
+        let result = doctor(&config, false, false).await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_check_config_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = Config::default_config();
+        config.consensus.min_score = 120;
+        config.consensus.max_loops = 0;
+        config.consensus.finding_similarity = 1.5;
+        config.cache.capacity = 0;
+        config.consensus.default_rule = ConsensusRule::Golden;
+        config.reasoning.db_path = dir.path().join("missing/nested/patterns.db");
+        config.executors.gemini.enabled = false;
+        config.executors.qwen.enabled = false;
+
+        let executors = build_executors(&config.executors, &config.prompts).unwrap();
+        let (mut issues, mut warnings) = (Vec::new(), Vec::new());
+        check_config_values(&config, &executors, &mut issues, &mut warnings);
+
+        assert_eq!(issues.len(), 4, "{:?}", issues);
+        assert!(issues[0].contains("min_score is 120"));
+        assert!(issues[1].contains("finding_similarity"));
+        assert!(issues[2].contains("max_loops"));
+        assert!(issues[3].contains("cache.capacity"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("Golden"));
+    }
+
+    #[test]
+    fn test_check_writable_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(check_writable_parent(&dir.path().join("a/b/patterns.db")).is_ok());
+        assert!(check_writable_parent(Path::new("patterns.db")).is_ok());
+
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(check_writable_parent(&file.join("patterns.db")).is_err());
+    }
+
+    #[test]
+    fn test_evaluation_report_json() {
+        let result = EvaluationResult::success("eval-1", 92, "Looks good");
+        let report = EvaluationReport {
+            result: &result,
+            language: "rust",
+            file_path: None,
+            matched_patterns: &[],
+        };
+
+        let json: serde_json::Value = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["request_id"], "eval-1");
+        assert_eq!(json["score"], 92);
+        assert_eq!(json["consensus_achieved"], true);
+        assert_eq!(json["language"], "rust");
+        assert!(json["votes"].is_object());
+        assert!(json["findings"].is_array());
+        assert!(json["matched_patterns"].as_array().unwrap().is_empty());
+        assert!(json.get("file_path").is_none());
+    }
+
+    #[test]
+    fn test_render_fix_preview() {
+        let code = "fn main() {\n    let x = y.unwrap();\n}\n";
+        let mut fix = SuggestedFix::new(2, 2, "    let x = y?;");
+        fix.sources = vec!["Codex".to_string(), "Qwen".to_string()];
+
+        let preview = render_fix_preview(code, &fix, "main.rs");
+
+        assert_eq!(
+            preview,
+            "--- main.rs\n\
+             +++ main.rs (fix from Codex, Qwen)\n\
+             @@ -2,1 +2,1 @@\n\
+             -    let x = y.unwrap();\n\
+             +    let x = y?;\n"
+        );
+    }
 }
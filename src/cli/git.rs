@@ -0,0 +1,328 @@
+//! Git integration: the staged diff and the pre-commit hook.
+//!
+//! `tetrad evaluate --staged` reviews exactly what is about to be committed:
+//! [`staged_diff`] asks git for it and [`parse_diff`] splits it per file.
+//! `tetrad hook install` writes a pre-commit hook that runs that review.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::{TetradError, TetradResult};
+
+/// Marker written in hooks installed by Tetrad.
+pub const HOOK_MARKER: &str = "# Installed by tetrad hook install";
+
+/// Pre-commit hook installed by `tetrad hook install`.
+const PRE_COMMIT_HOOK: &str = "#!/bin/sh
+# Installed by tetrad hook install
+# Reviews the staged changes with Tetrad and blocks the commit when they fail.
+# Skip it once with `git commit --no-verify`.
+exec tetrad evaluate --staged
+";
+
+/// Changes to one file in a unified diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiff {
+    /// Path after the change (before it, for deleted files).
+    pub path: String,
+
+    /// Whether git reported the file as binary.
+    pub binary: bool,
+
+    /// Whether the change deletes the file.
+    pub deleted: bool,
+
+    /// This file's part of the diff, from its `diff --git` header on.
+    pub text: String,
+}
+
+impl FileDiff {
+    /// Number of added lines.
+    pub fn added_lines(&self) -> usize {
+        self.text
+            .lines()
+            .filter(|line| line.starts_with('+') && !line.starts_with("+++"))
+            .count()
+    }
+}
+
+/// Runs `git diff --cached` in `dir` and returns the staged diff.
+///
+/// Paths are not escaped for non-ASCII characters; other unusual paths come
+/// quoted and are unquoted by [`parse_diff`].
+pub fn staged_diff(dir: &Path) -> TetradResult<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args([
+            "-c",
+            "core.quotepath=off",
+            "diff",
+            "--cached",
+            "--unified=3",
+            "--no-color",
+            "--no-ext-diff",
+        ])
+        .output()
+        .map_err(|e| TetradError::Other(format!("Could not run git: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(TetradError::Other(format!(
+            "git diff --cached failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Splits a `git diff` output into one [`FileDiff`] per file, in order.
+///
+/// The path comes from the `+++` line, falling back to `rename to` and then
+/// to the `diff --git` header for changes without content (pure renames,
+/// mode changes, binary files).
+pub fn parse_diff(diff: &str) -> Vec<FileDiff> {
+    let mut files = Vec::new();
+    let mut current: Option<(Vec<&str>, String)> = None;
+
+    for line in diff.lines() {
+        if let Some(header) = line.strip_prefix("diff --git ") {
+            if let Some((lines, header_path)) = current.take() {
+                files.push(file_diff(&lines, header_path));
+            }
+            current = Some((vec![line], header_new_path(header)));
+        } else if let Some((lines, _)) = current.as_mut() {
+            lines.push(line);
+        }
+    }
+    if let Some((lines, header_path)) = current {
+        files.push(file_diff(&lines, header_path));
+    }
+
+    files
+}
+
+fn file_diff(lines: &[&str], header_path: String) -> FileDiff {
+    let mut new_path = None;
+    let mut old_path = None;
+    let mut renamed_to = None;
+    let mut binary = false;
+
+    // Only the extended header comes before the first hunk
+    for line in lines
+        .iter()
+        .skip(1)
+        .take_while(|line| !line.starts_with("@@"))
+    {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            new_path = Some(path);
+        } else if let Some(path) = line.strip_prefix("--- ") {
+            old_path = Some(path);
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            renamed_to = Some(unquote(path));
+        } else if line.starts_with("Binary files ") || *line == "GIT binary patch" {
+            binary = true;
+        }
+    }
+
+    let deleted = new_path == Some("/dev/null");
+    let side = if deleted { old_path } else { new_path };
+    let path = side
+        .map(|path| strip_side(&unquote(path.trim_end_matches('\t'))))
+        .or(renamed_to)
+        .unwrap_or(header_path);
+
+    let mut text = lines.join("\n");
+    text.push('\n');
+
+    FileDiff {
+        path,
+        binary,
+        deleted,
+        text,
+    }
+}
+
+/// New path from a `diff --git a/<old> b/<new>` header.
+///
+/// Without quotes the header is ambiguous when paths contain " b/". Both
+/// sides are the same path unless the file was renamed, so that split is
+/// tried first.
+fn header_new_path(header: &str) -> String {
+    if let Some(start) = header.strip_suffix('"').and_then(|rest| rest.rfind(" \"")) {
+        return strip_side(&unquote(&header[start + 1..]));
+    }
+
+    let same = header.len().saturating_sub(5) / 2;
+    if let (Some(old), Some(new)) = (header.get(2..2 + same), header.get(5 + same..)) {
+        if old == new && header[2 + same..].starts_with(" b/") {
+            return new.to_string();
+        }
+    }
+    match header.rfind(" b/") {
+        Some(at) => header[at + 3..].to_string(),
+        None => header.to_string(),
+    }
+}
+
+/// Removes the `a/` or `b/` prefix git puts on diff paths.
+fn strip_side(path: &str) -> String {
+    path.strip_prefix("a/")
+        .or_else(|| path.strip_prefix("b/"))
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Undoes git's C-style path quoting (`"caf\303\251.py"`).
+///
+/// Unquoted paths are returned as they are.
+fn unquote(path: &str) -> String {
+    let Some(inner) = path
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        return path.to_string();
+    };
+
+    let mut bytes = Vec::with_capacity(inner.len());
+    let mut chars = inner.bytes().peekable();
+    while let Some(byte) = chars.next() {
+        if byte != b'\\' {
+            bytes.push(byte);
+            continue;
+        }
+        match chars.next() {
+            Some(b'n') => bytes.push(b'\n'),
+            Some(b't') => bytes.push(b'\t'),
+            Some(digit @ b'0'..=b'7') => {
+                let mut value = u32::from(digit - b'0');
+                for _ in 0..2 {
+                    match chars.peek() {
+                        Some(next @ b'0'..=b'7') => {
+                            value = value * 8 + u32::from(next - b'0');
+                            chars.next();
+                        }
+                        _ => break,
+                    }
+                }
+                bytes.push(value as u8);
+            }
+            Some(other) => bytes.push(other),
+            None => bytes.push(b'\\'),
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Writes the pre-commit hook into the hooks directory of the repository at `dir`.
+///
+/// The directory is asked to git, so `core.hooksPath` and worktrees are
+/// honoured. An existing hook that Tetrad did not install is only replaced
+/// with `force`. Returns the path of the hook.
+pub fn install_pre_commit_hook(dir: &Path, force: bool) -> TetradResult<PathBuf> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(["rev-parse", "--git-path", "hooks"])
+        .output()
+        .map_err(|e| TetradError::Other(format!("Could not run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(TetradError::Other(format!(
+            "Not a git repository: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let hooks_dir = dir.join(String::from_utf8_lossy(&output.stdout).trim());
+    let hook = hooks_dir.join("pre-commit");
+    if let Ok(existing) = std::fs::read_to_string(&hook) {
+        if !force && !existing.contains(HOOK_MARKER) {
+            return Err(TetradError::Other(format!(
+                "{} already exists; use --force to replace it",
+                hook.display()
+            )));
+        }
+    }
+
+    std::fs::create_dir_all(&hooks_dir)?;
+    std::fs::write(&hook, PRE_COMMIT_HOOK)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(hook)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAGED: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/diffs/staged.diff"
+    ));
+    const QUOTED_PATHS: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/diffs/quoted-paths.diff"
+    ));
+
+    #[test]
+    fn test_parse_diff_splits_files() {
+        let files = parse_diff(STAGED);
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "NOTES",
+                "app.js",
+                "lib/keep.py",
+                "logo.png",
+                "main.rs",
+                "old.py"
+            ]
+        );
+
+        let main = &files[4];
+        assert!(!main.binary && !main.deleted);
+        assert_eq!(main.added_lines(), 1);
+        assert!(main.text.starts_with("diff --git a/main.rs b/main.rs\n"));
+        assert!(main.text.contains("+    let password = \"hunter2\";\n"));
+        assert!(!main.text.contains("old.py"));
+    }
+
+    #[test]
+    fn test_parse_diff_flags_binary_deleted_and_renamed() {
+        let files = parse_diff(STAGED);
+
+        let logo = &files[3];
+        assert!(logo.binary);
+        assert_eq!(logo.added_lines(), 0);
+
+        let old = &files[5];
+        assert!(old.deleted);
+        assert_eq!(old.added_lines(), 0);
+
+        // Renomeação pura: sem hunks, o caminho vem de "rename to"
+        let renamed = &files[2];
+        assert!(!renamed.binary && !renamed.deleted);
+        assert_eq!(renamed.added_lines(), 0);
+    }
+
+    #[test]
+    fn test_parse_diff_unquotes_paths() {
+        let files = parse_diff(QUOTED_PATHS);
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        // O "+++" de caminhos com espaço termina em tab
+        assert_eq!(paths, vec!["café.py", "my file.py"]);
+        assert_eq!(files[1].added_lines(), 1);
+    }
+
+    #[test]
+    fn test_parse_diff_header_only() {
+        assert!(parse_diff("").is_empty());
+
+        let files =
+            parse_diff("diff --git a/bin/run b/bin/run\nold mode 100644\nnew mode 100755\n");
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "bin/run");
+        assert_eq!(files[0].added_lines(), 0);
+    }
+}
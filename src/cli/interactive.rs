@@ -4,16 +4,38 @@
 
 use std::path::{Path, PathBuf};
 
-use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use dialoguer::theme::{ColorfulTheme, SimpleTheme, Theme};
+use dialoguer::{Confirm, Input, Select};
 
-use crate::types::config::{Config, ConsensusRule};
+use super::style::{self, Style};
+
+use crate::types::config::{Config, ConsensusRule, MAX_WEIGHT, MIN_WEIGHT};
 use crate::TetradResult;
 
+/// Prompt theme matching the current output style.
+fn theme() -> Box<dyn Theme> {
+    if style::current().is_styled() {
+        Box::new(ColorfulTheme::default())
+    } else {
+        Box::new(SimpleTheme)
+    }
+}
+
+/// Asks a yes/no question.
+pub fn confirm(prompt: &str, default: bool) -> TetradResult<bool> {
+    Ok(Confirm::with_theme(theme().as_ref())
+        .with_prompt(prompt)
+        .default(default)
+        .interact()?)
+}
+
 /// Runs interactive configuration.
 pub fn run_interactive_config(config_path: &Path) -> TetradResult<()> {
-    let theme = ColorfulTheme::default();
+    let style = style::current();
+    let theme = theme();
+    let theme = theme.as_ref();
 
-    println!("\n🔧 Tetrad Interactive Configuration\n");
+    println!("\n{}Tetrad Interactive Configuration\n", style.emoji("🔧"));
 
     // Load existing config or create new one
     let mut config = if config_path.exists() {
@@ -35,32 +57,34 @@ pub fn run_interactive_config(config_path: &Path) -> TetradResult<()> {
             "Exit without Saving",
         ];
 
-        let selection = Select::with_theme(&theme)
+        let selection = Select::with_theme(theme)
             .with_prompt("What would you like to configure?")
             .items(&options)
             .default(0)
             .interact()?;
 
         match selection {
-            0 => configure_general(&theme, &mut config)?,
-            1 => configure_executors(&theme, &mut config)?,
-            2 => configure_consensus(&theme, &mut config)?,
-            3 => configure_reasoning(&theme, &mut config)?,
-            4 => configure_cache(&theme, &mut config)?,
+            0 => configure_general(theme, &mut config)?,
+            1 => configure_executors(theme, &mut config)?,
+            2 => configure_consensus(theme, &mut config)?,
+            3 => configure_reasoning(theme, &mut config)?,
+            4 => configure_cache(theme, &mut config)?,
             5 => {
                 config.save(config_path)?;
-                println!("\n✓ Configuration saved to: {}\n", config_path.display());
+                println!(
+                    "\n{} Configuration saved to: {}\n",
+                    style.ok(),
+                    config_path.display()
+                );
                 break;
             }
-            6 => {
-                if Confirm::with_theme(&theme)
-                    .with_prompt("Are you sure you want to exit without saving?")
-                    .default(false)
-                    .interact()?
-                {
-                    println!("\nExiting without saving.\n");
-                    break;
-                }
+            6 if Confirm::with_theme(theme)
+                .with_prompt("Are you sure you want to exit without saving?")
+                .default(false)
+                .interact()? =>
+            {
+                println!("\nExiting without saving.\n");
+                break;
             }
             _ => {}
         }
@@ -70,8 +94,8 @@ pub fn run_interactive_config(config_path: &Path) -> TetradResult<()> {
 }
 
 /// Configures general options.
-fn configure_general(theme: &ColorfulTheme, config: &mut Config) -> TetradResult<()> {
-    println!("\n📋 General Settings\n");
+fn configure_general(theme: &dyn Theme, config: &mut Config) -> TetradResult<()> {
+    println!("\n{}General Settings\n", style::current().emoji("📋"));
 
     // Log level
     let log_levels = vec!["error", "warn", "info", "debug", "trace"];
@@ -111,13 +135,13 @@ fn configure_general(theme: &ColorfulTheme, config: &mut Config) -> TetradResult
 
     config.general.timeout_secs = timeout;
 
-    println!("\n✓ General settings updated.\n");
+    println!("\n{} General settings updated.\n", style::current().ok());
     Ok(())
 }
 
 /// Configures executors.
-fn configure_executors(theme: &ColorfulTheme, config: &mut Config) -> TetradResult<()> {
-    println!("\n🤖 Executor Configuration\n");
+fn configure_executors(theme: &dyn Theme, config: &mut Config) -> TetradResult<()> {
+    println!("\n{}Executor Configuration\n", style::current().emoji("🤖"));
 
     let executors = vec!["Codex", "Gemini", "Qwen", "Back"];
 
@@ -142,11 +166,11 @@ fn configure_executors(theme: &ColorfulTheme, config: &mut Config) -> TetradResu
 
 /// Configures a specific executor.
 fn configure_single_executor(
-    theme: &ColorfulTheme,
+    theme: &dyn Theme,
     name: &str,
     executor: &mut crate::types::config::ExecutorConfig,
 ) -> TetradResult<()> {
-    println!("\n⚙️  Configuring {}\n", name);
+    println!("\n{}Configuring {}\n", style::current().emoji("⚙️"), name);
 
     // Enabled
     executor.enabled = Confirm::with_theme(theme)
@@ -189,27 +213,36 @@ fn configure_single_executor(
         .default(executor.weight)
         .interact_text()?;
 
-    executor.weight = weight.clamp(1, 10);
+    executor.weight = weight.clamp(MIN_WEIGHT, MAX_WEIGHT);
 
-    println!("\n✓ {} configured.\n", name);
+    println!("\n{} {} configured.\n", style::current().ok(), name);
     Ok(())
 }
 
 /// Configures consensus.
-fn configure_consensus(theme: &ColorfulTheme, config: &mut Config) -> TetradResult<()> {
-    println!("\n🤝 Consensus Configuration\n");
+fn configure_consensus(theme: &dyn Theme, config: &mut Config) -> TetradResult<()> {
+    println!(
+        "\n{}Consensus Configuration\n",
+        style::current().emoji("🤝")
+    );
 
     // Default rule
-    let rules = vec![
+    let mut rules = vec![
         "Golden (unanimity)",
         "Strong (3/3 or 2/3 with high confidence)",
         "Weak (simple majority)",
     ];
+    // Custom expressions are only written by hand in tetrad.toml
+    if config.consensus.custom.is_some() {
+        rules.push("Custom (expressions in [consensus.custom])");
+    }
 
     let current_idx = match config.consensus.default_rule {
         ConsensusRule::Golden => 0,
         ConsensusRule::Strong => 1,
         ConsensusRule::Weak => 2,
+        ConsensusRule::Custom if config.consensus.custom.is_some() => 3,
+        ConsensusRule::Custom => 1,
     };
 
     let rule_idx = Select::with_theme(theme)
@@ -221,7 +254,8 @@ fn configure_consensus(theme: &ColorfulTheme, config: &mut Config) -> TetradResu
     config.consensus.default_rule = match rule_idx {
         0 => ConsensusRule::Golden,
         1 => ConsensusRule::Strong,
-        _ => ConsensusRule::Weak,
+        2 => ConsensusRule::Weak,
+        _ => ConsensusRule::Custom,
     };
 
     // Minimum score
@@ -240,13 +274,16 @@ fn configure_consensus(theme: &ColorfulTheme, config: &mut Config) -> TetradResu
 
     config.consensus.max_loops = max_loops;
 
-    println!("\n✓ Consensus configured.\n");
+    println!("\n{} Consensus configured.\n", style::current().ok());
     Ok(())
 }
 
 /// Configures ReasoningBank.
-fn configure_reasoning(theme: &ColorfulTheme, config: &mut Config) -> TetradResult<()> {
-    println!("\n🧠 ReasoningBank Configuration\n");
+fn configure_reasoning(theme: &dyn Theme, config: &mut Config) -> TetradResult<()> {
+    println!(
+        "\n{}ReasoningBank Configuration\n",
+        style::current().emoji("🧠")
+    );
 
     // Enabled
     config.reasoning.enabled = Confirm::with_theme(theme)
@@ -283,13 +320,13 @@ fn configure_reasoning(theme: &ColorfulTheme, config: &mut Config) -> TetradResu
 
     config.reasoning.consolidation_interval = consolidation_interval;
 
-    println!("\n✓ ReasoningBank configured.\n");
+    println!("\n{} ReasoningBank configured.\n", style::current().ok());
     Ok(())
 }
 
 /// Configures cache.
-fn configure_cache(theme: &ColorfulTheme, config: &mut Config) -> TetradResult<()> {
-    println!("\n💾 Cache Configuration\n");
+fn configure_cache(theme: &dyn Theme, config: &mut Config) -> TetradResult<()> {
+    println!("\n{}Cache Configuration\n", style::current().emoji("💾"));
 
     // Enabled
     config.cache.enabled = Confirm::with_theme(theme)
@@ -318,87 +355,107 @@ fn configure_cache(theme: &ColorfulTheme, config: &mut Config) -> TetradResult<(
 
     config.cache.ttl_secs = ttl;
 
-    println!("\n✓ Cache configured.\n");
+    println!("\n{} Cache configured.\n", style::current().ok());
     Ok(())
 }
 
 /// Shows configuration summary.
 pub fn show_config_summary(config: &Config) {
-    println!("\n📊 Configuration Summary\n");
-    println!("┌─────────────────────────────────────────┐");
-    println!("│ General                                 │");
-    println!("├─────────────────────────────────────────┤");
-    println!("│ Log level: {:<28} │", config.general.log_level);
-    println!("│ Timeout: {:<29}s │", config.general.timeout_secs);
-    println!("├─────────────────────────────────────────┤");
-    println!("│ Executors                               │");
-    println!("├─────────────────────────────────────────┤");
-    println!(
+    let style = style::current();
+    let line = |text: &str| summary_line(style, text);
+    let enabled_icon = |enabled: bool| if enabled { style.ok() } else { style.fail() };
+
+    outln!("\n{}Configuration Summary\n", style.emoji("📊"));
+    line("┌─────────────────────────────────────────┐");
+    line("│ General                                 │");
+    line("├─────────────────────────────────────────┤");
+    line(&format!("│ Log level: {:<28} │", config.general.log_level));
+    line(&format!(
+        "│ Timeout: {:<29}s │",
+        config.general.timeout_secs
+    ));
+    line("├─────────────────────────────────────────┤");
+    line("│ Executors                               │");
+    line("├─────────────────────────────────────────┤");
+    line(&format!(
         "│ Codex:  {} ({:<26}) │",
-        if config.executors.codex.enabled {
-            "✓"
-        } else {
-            "✗"
-        },
+        enabled_icon(config.executors.codex.enabled),
         config.executors.codex.command
-    );
-    println!(
+    ));
+    line(&format!(
         "│ Gemini: {} ({:<26}) │",
-        if config.executors.gemini.enabled {
-            "✓"
-        } else {
-            "✗"
-        },
+        enabled_icon(config.executors.gemini.enabled),
         config.executors.gemini.command
-    );
-    println!(
+    ));
+    line(&format!(
         "│ Qwen:   {} ({:<26}) │",
-        if config.executors.qwen.enabled {
-            "✓"
-        } else {
-            "✗"
-        },
+        enabled_icon(config.executors.qwen.enabled),
         config.executors.qwen.command
-    );
-    println!("├─────────────────────────────────────────┤");
-    println!("│ Consensus                               │");
-    println!("├─────────────────────────────────────────┤");
-    println!(
+    ));
+    line("├─────────────────────────────────────────┤");
+    line("│ Consensus                               │");
+    line("├─────────────────────────────────────────┤");
+    line(&format!(
         "│ Rule: {:<33} │",
         format!("{:?}", config.consensus.default_rule)
-    );
-    println!("│ Min score: {:<28} │", config.consensus.min_score);
-    println!("│ Max loops: {:<28} │", config.consensus.max_loops);
-    println!("├─────────────────────────────────────────┤");
-    println!("│ ReasoningBank                           │");
-    println!("├─────────────────────────────────────────┤");
-    println!(
+    ));
+    line(&format!(
+        "│ Min score: {:<28} │",
+        config.consensus.min_score
+    ));
+    line(&format!(
+        "│ Max loops: {:<28} │",
+        config.consensus.max_loops
+    ));
+    line("├─────────────────────────────────────────┤");
+    line("│ ReasoningBank                           │");
+    line("├─────────────────────────────────────────┤");
+    line(&format!(
         "│ Enabled: {:<30} │",
         if config.reasoning.enabled {
             "Yes"
         } else {
             "No"
         }
-    );
+    ));
     if config.reasoning.enabled {
-        println!(
+        line(&format!(
             "│ Consolidation: every {:<17} │",
             format!("{} evaluations", config.reasoning.consolidation_interval)
-        );
+        ));
     }
-    println!("├─────────────────────────────────────────┤");
-    println!("│ Cache                                   │");
-    println!("├─────────────────────────────────────────┤");
-    println!(
+    line("├─────────────────────────────────────────┤");
+    line("│ Cache                                   │");
+    line("├─────────────────────────────────────────┤");
+    line(&format!(
         "│ Enabled: {:<30} │",
         if config.cache.enabled { "Yes" } else { "No" }
-    );
+    ));
     if config.cache.enabled {
-        println!("│ Capacity: {:<29} │", config.cache.capacity);
-        println!("│ TTL: {:<33}s │", config.cache.ttl_secs);
+        line(&format!("│ Capacity: {:<29} │", config.cache.capacity));
+        line(&format!("│ TTL: {:<33}s │", config.cache.ttl_secs));
     }
-    println!("└─────────────────────────────────────────┘");
-    println!();
+    line("└─────────────────────────────────────────┘");
+    outln!();
+}
+
+/// Prints a summary line, replacing box-drawing characters in plain mode.
+fn summary_line(style: Style, text: &str) {
+    if style.is_styled() {
+        outln!("{}", text);
+        return;
+    }
+
+    let plain: String = text
+        .chars()
+        .map(|c| match c {
+            '┌' | '┐' | '└' | '┘' | '├' | '┤' => '+',
+            '─' => '-',
+            '│' => '|',
+            c => c,
+        })
+        .collect();
+    outln!("{}", plain);
 }
 
 #[cfg(test)]
@@ -1,11 +1,69 @@
 //! Command line interface for Tetrad.
 
+/// Prints command output to stdout, without a trailing newline.
+///
+/// See [`write_stdout`] for the behavior while the MCP transport owns stdout.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::cli::write_stdout(format_args!($($arg)*), false)
+    };
+}
+
+/// Prints a line of command output to stdout.
+///
+/// See [`write_stdout`] for the behavior while the MCP transport owns stdout.
+macro_rules! outln {
+    () => {
+        $crate::cli::write_stdout(format_args!(""), true)
+    };
+    ($($arg:tt)*) => {
+        $crate::cli::write_stdout(format_args!($($arg)*), true)
+    };
+}
+
+pub mod batch;
 pub mod commands;
+pub mod git;
 pub mod interactive;
+pub mod selftest;
+pub mod style;
+pub mod watch;
 
-use clap::{Parser, Subcommand};
+use chrono::{NaiveDate, Utc};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::io::Write;
 use std::path::PathBuf;
 
+use crate::reasoning::{ExportFormat, PatternSort, PatternType, StatsBucket};
+use crate::types::requests::EvaluationType;
+
+/// Writes explicitly-requested command output to stdout.
+///
+/// While the MCP stdio transport is running, stdout carries only JSON-RPC
+/// messages. Any output reaching this function in that state is redirected
+/// to stderr, and panics under `cfg(test)` so regressions are caught early.
+pub fn write_stdout(args: std::fmt::Arguments<'_>, newline: bool) {
+    if crate::mcp::stdout_reserved() {
+        if cfg!(test) {
+            panic!("stdout write while MCP stdio transport is active: {}", args);
+        }
+        tracing::warn!("Redirected stdout write to stderr while serving MCP");
+        if newline {
+            eprintln!("{}", args);
+        } else {
+            eprint!("{}", args);
+        }
+        return;
+    }
+
+    let mut stdout = std::io::stdout().lock();
+    let _ = if newline {
+        writeln!(stdout, "{}", args)
+    } else {
+        write!(stdout, "{}", args).and_then(|_| stdout.flush())
+    };
+}
+
 /// Tetrad - Quadruple Consensus CLI for Claude Code.
 #[derive(Parser, Debug)]
 #[command(name = "tetrad")]
@@ -23,11 +81,68 @@ pub struct Cli {
     #[arg(short, long)]
     pub quiet: bool,
 
+    /// When to use colors and unicode icons (also honours NO_COLOR).
+    #[arg(long, value_enum, default_value = "auto", global = true)]
+    pub color: style::ColorChoice,
+
+    /// Trust the commands defined in the configuration file and remember
+    /// the approval (required outside a terminal).
+    #[arg(long, global = true)]
+    pub trust: bool,
+
+    /// Replace every executor with the deterministic mock, so no CLI is
+    /// called (demos and CI).
+    #[arg(long, global = true)]
+    pub offline: bool,
+
     /// Command to execute.
     #[command(subcommand)]
     pub command: Commands,
 }
 
+/// Output format for commands that produce a result.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+
+    /// A single JSON document on stdout.
+    Json,
+}
+
+/// Which Claude Code settings `tetrad init --claude` writes to.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClaudeScope {
+    /// The user's `~/.claude.json`, for every project.
+    User,
+
+    /// The project's `.mcp.json`, shared through version control.
+    #[default]
+    Project,
+}
+
+/// What `tetrad evaluate` reviews.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReviewType {
+    /// Source code.
+    #[default]
+    Code,
+
+    /// Documentation (docstrings, README), checked against `--related` code.
+    Docs,
+}
+
+impl ReviewType {
+    /// Evaluation type sent to the executors.
+    pub fn evaluation_type(self) -> EvaluationType {
+        match self {
+            ReviewType::Code => EvaluationType::Code,
+            ReviewType::Docs => EvaluationType::Documentation,
+        }
+    }
+}
+
 /// Available commands.
 #[derive(Subcommand, Debug)]
 pub enum Commands {
@@ -36,55 +151,569 @@ pub enum Commands {
         /// Target directory (default: current directory).
         #[arg(short, long)]
         path: Option<PathBuf>,
+
+        /// Register the MCP server with Claude Code.
+        #[arg(long)]
+        claude: bool,
+
+        /// Where to register it with `--claude`.
+        #[arg(long, value_enum, default_value = "project", requires = "claude")]
+        scope: ClaudeScope,
     },
 
     /// Start the MCP server.
     Serve {
-        /// Port for the server (if using HTTP transport).
+        /// Port for the server (if using HTTP transport). With `--api`,
+        /// overrides the port of `mcp.api.bind`.
         #[arg(short, long)]
         port: Option<u16>,
+
+        /// Serve the HTTP evaluation API for CI (`POST /api/evaluate`,
+        /// configured in `[mcp.api]`) instead of MCP over stdio.
+        #[arg(long)]
+        api: bool,
+
+        /// Reload the configuration file when it changes.
+        #[arg(long)]
+        watch_config: bool,
     },
 
     /// Show CLI status (codex, gemini, qwen).
     Status,
 
     /// Configure options interactively.
-    Config,
+    Config {
+        /// Print the effective configuration instead, noting which
+        /// consensus settings come from `consensus.strictness`.
+        #[arg(long)]
+        effective: bool,
+    },
 
     /// Diagnose configuration issues.
-    Doctor,
+    ///
+    /// Exits with status 1 when issues (not warnings) are found.
+    Doctor {
+        /// Also run a canary evaluation on each executor and check its output parses.
+        ///
+        /// An executor that passes leaves the quarantine.
+        #[arg(long)]
+        deep: bool,
+
+        /// Take every executor out of quarantine.
+        #[arg(long)]
+        clear_quarantine: bool,
+    },
+
+    /// Run an in-process smoke test of the MCP server.
+    ///
+    /// Exits with status 1 when any stage fails.
+    Selftest {
+        /// Use the configured executors instead of mock ones.
+        #[arg(long)]
+        live: bool,
+    },
 
     /// Show version.
     Version,
 
     /// Evaluate code manually (without MCP).
+    ///
+    /// With `--code`, exits with status 0 for PASS, 1 for REVISE and 2 for BLOCK.
     Evaluate {
         /// Code to evaluate (or file path with @).
+        #[arg(
+            short = 'c',
+            long,
+            required_unless_present_any = ["dir", "staged"],
+            conflicts_with_all = ["dir", "staged"]
+        )]
+        code: Option<String>,
+
+        /// Evaluate every source file under this directory (respects .gitignore).
+        #[arg(short, long, conflicts_with = "staged")]
+        dir: Option<PathBuf>,
+
+        /// Review the staged changes (`git diff --cached`) file by file.
+        ///
+        /// Exits with status 1 when a file is blocked or the average score
+        /// is below `consensus.min_score`.
+        #[arg(long)]
+        staged: bool,
+
+        /// Skip staged files whose diff is larger than this, in bytes.
+        #[arg(long, default_value = "65536", requires = "staged")]
+        max_file_bytes: usize,
+
+        /// Code language.
+        #[arg(short, long, default_value = "auto")]
+        language: String,
+
+        /// Maximum number of files evaluated in --dir mode.
+        #[arg(long, default_value = "200")]
+        max_files: usize,
+
+        /// Number of files evaluated concurrently in --dir and --staged mode.
+        #[arg(short, long, default_value = "4")]
+        jobs: usize,
+
+        /// Output format. With `json`, progress messages go to stderr.
+        #[arg(long, value_enum, default_value = "text", conflicts_with = "dir")]
+        format: OutputFormat,
+
+        /// Show suggested fixes as unified-diff previews (never applied).
+        #[arg(long, conflicts_with_all = ["dir", "staged"])]
+        show_fixes: bool,
+
+        /// What to review.
+        #[arg(
+            long = "type",
+            value_enum,
+            default_value = "code",
+            conflicts_with_all = ["dir", "staged"]
+        )]
+        review_type: ReviewType,
+
+        /// Code the documentation describes, for `--type docs` (or file path with @).
+        #[arg(long, conflicts_with_all = ["dir", "staged"])]
+        related: Option<String>,
+
+        /// Evaluate even if the monthly budget is exhausted
+        /// (`general.budget.action = "require_override"`).
+        #[arg(long)]
+        override_budget: bool,
+    },
+
+    /// Watch a directory and evaluate source files as they change.
+    ///
+    /// Prints one verdict per changed file; press Ctrl-C to stop.
+    Watch {
+        /// Directory to watch (respects .gitignore).
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Code language.
+        #[arg(short, long, default_value = "auto")]
+        language: String,
+
+        /// How long a file must be quiet after a change before it is evaluated.
+        #[arg(long, default_value = "500")]
+        debounce_ms: u64,
+
+        /// Maximum number of files watched.
+        #[arg(long, default_value = "200")]
+        max_files: usize,
+
+        /// Number of files evaluated concurrently.
+        #[arg(short, long, default_value = "4")]
+        jobs: usize,
+
+        /// Evaluate even if the monthly budget is exhausted
+        /// (`general.budget.action = "require_override"`).
+        #[arg(long)]
+        override_budget: bool,
+    },
+
+    /// Estimate how long a review would take and what it would cost.
+    ///
+    /// No evaluator is called. Without latency history (kept by the MCP
+    /// server), latencies come from a prompt-size heuristic.
+    Estimate {
+        /// Code to estimate (or file path with @).
         #[arg(short = 'c', long)]
         code: String,
 
         /// Code language.
         #[arg(short, long, default_value = "auto")]
         language: String,
+
+        /// What to review.
+        #[arg(long = "type", value_enum, default_value = "code")]
+        review_type: ReviewType,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
     },
 
     /// Show evaluation history from ReasoningBank.
     History {
+        /// Show how each executor voted and how often it agreed with the decision.
+        #[arg(long)]
+        executors: bool,
+
         /// Limit of entries to show.
         #[arg(short, long, default_value = "20")]
         limit: usize,
     },
 
+    /// Show ReasoningBank statistics.
+    Stats {
+        /// List code signatures whose decisions flip between evaluations.
+        #[arg(long)]
+        flakiness: bool,
+
+        /// Limit of entries to show.
+        #[arg(short, long, default_value = "10")]
+        limit: usize,
+
+        /// Show trends: evaluations, success rate, average score, average
+        /// loops and block rate per day or week.
+        #[arg(long, value_enum, conflicts_with = "flakiness")]
+        bucket: Option<TrendBucket>,
+
+        /// Only count evaluations from this day on: a date (YYYY-MM-DD) or a
+        /// period back from today (e.g. 30d, 12w). Implies weekly trends
+        /// unless --bucket is given.
+        #[arg(long, value_parser = parse_since, conflicts_with = "flakiness")]
+        since: Option<NaiveDate>,
+
+        /// Output format for trends.
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Show evaluation metrics recorded by the MCP server across runs.
+    Metrics {
+        /// Output format.
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Prune old trajectories and report the ReasoningBank size.
+    ///
+    /// Applies `reasoning.max_trajectories` and
+    /// `reasoning.trajectory_retention_days`.
+    Maintenance {
+        /// Return the freed space to the file system (rewrites the database).
+        #[arg(long)]
+        vacuum: bool,
+    },
+
     /// Export patterns from ReasoningBank.
     Export {
-        /// Output file.
-        #[arg(short, long, default_value = "tetrad-patterns.json")]
-        output: PathBuf,
+        /// Output file [default: tetrad-patterns.json, .json.gz or .md, by format].
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// File format.
+        #[arg(long, value_enum, default_value = "json")]
+        format: ExportFileFormat,
+
+        /// Only export patterns for this language.
+        #[arg(short, long)]
+        language: Option<String>,
+
+        /// Only export patterns of this type.
+        #[arg(long, value_enum)]
+        pattern_type: Option<PatternKind>,
+
+        /// Only export patterns with at least this confidence (0.0-1.0).
+        #[arg(long, value_parser = parse_confidence)]
+        min_confidence: Option<f64>,
+
+        /// Sign the export with this private key (see `tetrad keygen`).
+        #[arg(long, value_name = "PATH")]
+        sign_key: Option<PathBuf>,
     },
 
     /// Import patterns into ReasoningBank.
     Import {
-        /// Input file.
-        input: PathBuf,
+        /// Pattern pack to import: a path, a file:// URL or an http(s):// URL.
+        input: String,
+
+        /// Expected SHA-256 of the pack (hex); the import aborts on mismatch.
+        #[arg(long)]
+        sha256: Option<String>,
+
+        /// Refuse packs without a valid signature from a trusted key
+        /// (otherwise they are imported with a warning).
+        #[arg(long)]
+        require_signature: bool,
+
+        /// Directory of trusted public keys (*.pub) [default: trusted-keys in the data directory].
+        #[arg(long, value_name = "DIR")]
+        trusted_keys: Option<PathBuf>,
+    },
+
+    /// Generate a key pair for signing pattern exports.
+    Keygen {
+        /// Replace an existing key pair.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Inspect or clear the persisted evaluation cache.
+    Cache {
+        /// Action to perform.
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+
+    /// List, inspect, delete or manually add ReasoningBank patterns.
+    Patterns {
+        /// Action to perform.
+        #[command(subcommand)]
+        action: PatternsAction,
+    },
+
+    /// List or revoke approved configuration files.
+    Trust {
+        /// Action to perform.
+        #[command(subcommand)]
+        action: TrustAction,
+    },
+
+    /// Manage the git pre-commit hook.
+    Hook {
+        /// Action to perform.
+        #[command(subcommand)]
+        action: HookAction,
+    },
+}
+
+impl Commands {
+    /// Whether the command may run executor commands, and so needs a
+    /// trusted configuration file.
+    pub fn runs_executors(&self) -> bool {
+        match self {
+            Commands::Serve { .. }
+            | Commands::Status
+            | Commands::Doctor { .. }
+            | Commands::Evaluate { .. }
+            | Commands::Watch { .. } => true,
+            Commands::Selftest { live } => *live,
+            _ => false,
+        }
+    }
+}
+
+/// Actions for `tetrad trust`.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum TrustAction {
+    /// List approved configuration files.
+    List,
+
+    /// Forget the approval of a configuration file.
+    Revoke {
+        /// Configuration file [default: the one in use].
+        path: Option<PathBuf>,
+    },
+}
+
+/// Actions for `tetrad hook`.
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookAction {
+    /// Write a pre-commit hook that runs `tetrad evaluate --staged`.
+    Install {
+        /// Replace an existing pre-commit hook not installed by Tetrad.
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+/// Actions for `tetrad cache`.
+#[derive(Subcommand, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheAction {
+    /// Show size, capacity, hit rate and oldest entry.
+    Stats,
+
+    /// Remove every cached evaluation.
+    Clear,
+}
+
+/// Actions for `tetrad patterns`.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum PatternsAction {
+    /// List patterns, optionally filtered.
+    List {
+        /// Only patterns of this type.
+        #[arg(long = "type", value_enum)]
+        pattern_type: Option<PatternKind>,
+
+        /// Only patterns for this language.
+        #[arg(short, long)]
+        language: Option<String>,
+
+        /// Only patterns in this issue category.
+        #[arg(long)]
+        category: Option<String>,
+
+        /// Sort order.
+        #[arg(long, value_enum, default_value = "confidence")]
+        sort: PatternOrder,
+
+        /// Limit of entries to show.
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Show a pattern, including its signature and solution.
+    Show {
+        /// Pattern id (as shown by `tetrad patterns list`).
+        id: i64,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Delete a pattern; trajectories that referenced it are kept.
+    Delete {
+        /// Pattern id.
+        id: i64,
+    },
+
+    /// Manually register an anti-pattern.
+    Add {
+        /// What is wrong with code that matches this pattern.
+        #[arg(short, long)]
+        description: String,
+
+        /// Issue category (e.g. security, logic, performance).
+        #[arg(long)]
+        category: String,
+
+        /// Language the pattern applies to (`any` matches every language).
+        #[arg(short, long, default_value = "any")]
+        language: String,
+
+        /// How to fix it.
+        #[arg(short, long)]
+        solution: Option<String>,
+
+        /// Output format.
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
     },
 }
+
+/// File formats accepted by `tetrad export --format`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFileFormat {
+    /// Pretty-printed JSON.
+    #[default]
+    Json,
+
+    /// Gzip-compressed JSON; `tetrad import` detects it automatically.
+    JsonGz,
+
+    /// Human-readable Markdown report (cannot be imported).
+    Markdown,
+}
+
+impl ExportFileFormat {
+    /// Export format used by the ReasoningBank.
+    pub fn export_format(self) -> ExportFormat {
+        match self {
+            ExportFileFormat::Json => ExportFormat::Json,
+            ExportFileFormat::JsonGz => ExportFormat::JsonGz,
+            ExportFileFormat::Markdown => ExportFormat::Markdown,
+        }
+    }
+}
+
+/// Period sizes accepted by `tetrad stats --bucket`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TrendBucket {
+    /// One row per day (UTC).
+    Day,
+
+    /// One row per week, starting on Monday (UTC).
+    #[default]
+    Week,
+}
+
+impl TrendBucket {
+    /// Bucket used by the ReasoningBank.
+    pub fn stats_bucket(self) -> StatsBucket {
+        match self {
+            TrendBucket::Day => StatsBucket::Day,
+            TrendBucket::Week => StatsBucket::Week,
+        }
+    }
+}
+
+/// Parses `tetrad stats --since`: a date, or days/weeks back from today (UTC).
+fn parse_since(value: &str) -> Result<NaiveDate, String> {
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    let invalid = || {
+        format!(
+            "'{}' is not a date (YYYY-MM-DD) or a period like 30d or 12w",
+            value
+        )
+    };
+    let (count, unit) = value.split_at(value.len().saturating_sub(1));
+    let count: u64 = count.parse().map_err(|_| invalid())?;
+    let days = match unit {
+        "d" => count,
+        "w" => count.saturating_mul(7),
+        _ => return Err(invalid()),
+    };
+    Utc::now()
+        .date_naive()
+        .checked_sub_days(chrono::Days::new(days))
+        .ok_or_else(invalid)
+}
+
+/// Parses a confidence between 0.0 and 1.0.
+fn parse_confidence(value: &str) -> Result<f64, String> {
+    let confidence: f64 = value
+        .parse()
+        .map_err(|_| format!("'{}' is not a number", value))?;
+    if !(0.0..=1.0).contains(&confidence) {
+        return Err(format!("{} is not between 0.0 and 1.0", confidence));
+    }
+    Ok(confidence)
+}
+
+/// Pattern types accepted by `tetrad patterns list --type` and `tetrad export --pattern-type`.
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternKind {
+    /// Code that keeps failing.
+    Anti,
+
+    /// Code that passes.
+    Good,
+
+    /// Mixed results.
+    Ambiguous,
+}
+
+impl PatternKind {
+    /// Pattern type stored in the ReasoningBank.
+    pub fn pattern_type(self) -> PatternType {
+        match self {
+            PatternKind::Anti => PatternType::AntiPattern,
+            PatternKind::Good => PatternType::GoodPattern,
+            PatternKind::Ambiguous => PatternType::Ambiguous,
+        }
+    }
+}
+
+/// Sort orders accepted by `tetrad patterns list --sort`.
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PatternOrder {
+    /// Highest confidence first.
+    #[default]
+    Confidence,
+
+    /// Most evaluations (successes + failures) first.
+    Usage,
+}
+
+impl PatternOrder {
+    /// Sort order used by the ReasoningBank query.
+    pub fn sort(self) -> PatternSort {
+        match self {
+            PatternOrder::Confidence => PatternSort::Confidence,
+            PatternOrder::Usage => PatternSort::Usage,
+        }
+    }
+}
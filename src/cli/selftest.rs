@@ -0,0 +1,397 @@
+//! Self-contained smoke test behind `tetrad selftest`.
+//!
+//! Runs the MCP server in-process over a [`LoopbackTransport`] and drives it
+//! the way Claude Code would: `initialize`, `tools/list` and a
+//! `tetrad_review_code` call. It then checks that the ReasoningBank recorded
+//! the evaluation and that repeating the call is answered from the cache.
+//!
+//! Mock executors are used unless `live` is set, so the test passes without
+//! any CLI installed. The ReasoningBank lives in a temporary directory and
+//! is removed afterwards; the user's database is never touched.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use crate::executors::{build_executors, CliExecutor, ConfiguredExecutor, Scratch};
+use crate::mcp::{LoopbackClient, LoopbackTransport, McpServer, ToolHandler};
+use crate::reasoning::ReasoningBank;
+use crate::types::config::{CacheConfig, Config, ExecutorConfig, ReasoningConfig};
+use crate::types::requests::EvaluationRequest;
+use crate::types::responses::{ModelVote, Vote};
+use crate::TetradResult;
+
+/// Code sent in the review stages.
+const SAMPLE_CODE: &str = "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}";
+
+/// Outcome of a selftest stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageStatus {
+    /// The stage succeeded.
+    Pass,
+    /// The stage failed.
+    Fail,
+    /// Not run because an earlier stage failed.
+    Skip,
+}
+
+impl std::fmt::Display for StageStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StageStatus::Pass => write!(f, "PASS"),
+            StageStatus::Fail => write!(f, "FAIL"),
+            StageStatus::Skip => write!(f, "SKIP"),
+        }
+    }
+}
+
+/// Report of a single stage.
+#[derive(Debug, Clone)]
+pub struct StageReport {
+    /// Stage name.
+    pub name: &'static str,
+    /// Outcome.
+    pub status: StageStatus,
+    /// Time spent in the stage.
+    pub duration: Duration,
+    /// Summary on success, reason on failure.
+    pub detail: String,
+}
+
+/// Runs stages in order, timing each one.
+///
+/// Stages depend on the ones before them, so after a failure the remaining
+/// stages are skipped without being run.
+#[derive(Debug, Default)]
+pub struct StageRunner {
+    reports: Vec<StageReport>,
+}
+
+impl StageRunner {
+    /// Creates an empty runner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs a stage and records its outcome.
+    ///
+    /// The stage resolves to a value and a summary, or to the failure reason.
+    /// Returns the value when the stage passed.
+    pub async fn run<T>(
+        &mut self,
+        name: &'static str,
+        stage: impl Future<Output = Result<(T, String), String>>,
+    ) -> Option<T> {
+        if !self.passed() {
+            self.reports.push(StageReport {
+                name,
+                status: StageStatus::Skip,
+                duration: Duration::ZERO,
+                detail: "an earlier stage failed".to_string(),
+            });
+            return None;
+        }
+
+        let started = Instant::now();
+        let outcome = stage.await;
+        let duration = started.elapsed();
+
+        let (status, detail, value) = match outcome {
+            Ok((value, detail)) => (StageStatus::Pass, detail, Some(value)),
+            Err(reason) => (StageStatus::Fail, reason, None),
+        };
+        self.reports.push(StageReport {
+            name,
+            status,
+            duration,
+            detail,
+        });
+        value
+    }
+
+    /// Whether no stage has failed so far.
+    pub fn passed(&self) -> bool {
+        self.reports.iter().all(|r| r.status == StageStatus::Pass)
+    }
+
+    /// Reports of the stages run so far.
+    pub fn reports(&self) -> &[StageReport] {
+        &self.reports
+    }
+
+    /// Consumes the runner, returning its reports.
+    pub fn into_reports(self) -> Vec<StageReport> {
+        self.reports
+    }
+}
+
+/// Runs the selftest against an in-process MCP server.
+///
+/// With `live`, the executors from `config` are used; otherwise three mock
+/// executors that always approve. Errors building the server (an invalid
+/// feedback template, for instance) are returned as `Err`; failures of the
+/// stages themselves are in the reports.
+pub async fn run_selftest(config: &Config, live: bool) -> TetradResult<Vec<StageReport>> {
+    // The scratch directory goes away with the guard, even if the run is cancelled
+    let mut scratch = Scratch::new();
+    let dir = scratch.dir("tetrad-selftest")?;
+
+    let mut config = config.clone();
+    config.reasoning.enabled = true;
+    config.reasoning.db_path = dir.join("selftest.db");
+    config.cache = CacheConfig::default();
+    config.general.warm_up = false;
+
+    let executors = if live {
+        build_executors(&config.executors, &config.prompts)?
+    } else {
+        mock_executors()
+    };
+
+    serve_and_drive(config, executors).await
+}
+
+/// Serves the MCP loop and drives it through the stages concurrently.
+async fn serve_and_drive(
+    config: Config,
+    executors: Vec<ConfiguredExecutor>,
+) -> TetradResult<Vec<StageReport>> {
+    let tools = ToolHandler::with_executors(config.clone(), executors)?;
+    let (transport, client) = LoopbackTransport::pair();
+    let mut server = McpServer::with_transport(tools, transport);
+
+    // The client is dropped when the stages end, which stops the server
+    let (served, runner) = tokio::join!(server.run(), drive(client, &config.reasoning));
+    served?;
+    Ok(runner.into_reports())
+}
+
+/// Drives the server through every stage.
+async fn drive(mut client: LoopbackClient, reasoning: &ReasoningConfig) -> StageRunner {
+    let mut runner = StageRunner::new();
+
+    runner
+        .run("initialize", async {
+            let result = call(
+                &mut client,
+                "initialize",
+                json!({
+                    "protocolVersion": "2024-11-05",
+                    "capabilities": {},
+                    "clientInfo": {"name": "tetrad-selftest", "version": env!("CARGO_PKG_VERSION")}
+                }),
+            )
+            .await?;
+            let name = result["serverInfo"]["name"].as_str().unwrap_or_default();
+            if name != "tetrad" {
+                return Err(format!("unexpected server name '{}'", name));
+            }
+            client
+                .notify("notifications/initialized", None)
+                .map_err(|e| e.to_string())?;
+            Ok((
+                (),
+                format!(
+                    "protocol {}",
+                    result["protocolVersion"].as_str().unwrap_or("?")
+                ),
+            ))
+        })
+        .await;
+
+    runner
+        .run("tools/list", async {
+            let result = call(&mut client, "tools/list", json!({})).await?;
+            let tools = result["tools"].as_array().cloned().unwrap_or_default();
+            if !tools.iter().any(|t| t["name"] == "tetrad_review_code") {
+                return Err("tetrad_review_code is not listed".to_string());
+            }
+            Ok(((), format!("{} tools", tools.len())))
+        })
+        .await;
+
+    let first_id = runner
+        .run("review", async {
+            let review = review_code(&mut client).await?;
+            if review["votes"]
+                .as_array()
+                .is_none_or(|votes| votes.is_empty())
+            {
+                return Err("no executor voted; run 'tetrad doctor'".to_string());
+            }
+            let request_id = review["request_id"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string();
+            let summary = format!(
+                "{}, score {}",
+                review["decision"].as_str().unwrap_or("?"),
+                review["score"]
+            );
+            Ok((request_id, summary))
+        })
+        .await;
+
+    runner
+        .run("reasoning bank", async {
+            let bank = ReasoningBank::new_with_config(&reasoning.db_path, reasoning)
+                .map_err(|e| e.to_string())?;
+            match bank.count_trajectories().map_err(|e| e.to_string())? {
+                0 => Err("no trajectory was recorded".to_string()),
+                n => Ok(((), format!("{} trajectory recorded", n))),
+            }
+        })
+        .await;
+
+    runner
+        .run("cache", async {
+            let review = review_code(&mut client).await?;
+            let request_id = review["request_id"].as_str().unwrap_or_default();
+            let first_id = first_id.as_deref().unwrap_or_default();
+            if request_id != first_id {
+                return Err(format!(
+                    "repeated call was evaluated again ({} then {})",
+                    first_id, request_id
+                ));
+            }
+            Ok(((), "repeated call served from cache".to_string()))
+        })
+        .await;
+
+    runner
+}
+
+/// Sends a request, returning its `result` or the JSON-RPC error message.
+async fn call(client: &mut LoopbackClient, method: &str, params: Value) -> Result<Value, String> {
+    let response = client
+        .request(method, Some(params))
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(error) = response.get("error") {
+        return Err(format!(
+            "{} failed: {}",
+            method,
+            error["message"].as_str().unwrap_or("unknown error")
+        ));
+    }
+    Ok(response["result"].clone())
+}
+
+/// Calls `tetrad_review_code` on the sample and parses the JSON it returns.
+async fn review_code(client: &mut LoopbackClient) -> Result<Value, String> {
+    let result = call(
+        client,
+        "tools/call",
+        json!({
+            "name": "tetrad_review_code",
+            "arguments": {"code": SAMPLE_CODE, "language": "rust"}
+        }),
+    )
+    .await?;
+
+    let text = result["content"][0]["text"].as_str().unwrap_or_default();
+    if result["isError"] == true {
+        return Err(text.to_string());
+    }
+    serde_json::from_str(text).map_err(|e| format!("review result is not JSON: {}", e))
+}
+
+/// Executor that approves everything without running a CLI.
+struct MockExecutor {
+    name: &'static str,
+}
+
+#[async_trait]
+impl CliExecutor for MockExecutor {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn command(&self) -> &str {
+        "selftest"
+    }
+
+    async fn is_available(&self) -> bool {
+        true
+    }
+
+    async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+        Ok(ModelVote::new(self.name, Vote::Pass, 95).with_reasoning("Selftest mock vote."))
+    }
+
+    fn specialization(&self) -> &str {
+        "selftest"
+    }
+}
+
+fn mock_executors() -> Vec<ConfiguredExecutor> {
+    ["MockCodex", "MockGemini", "MockQwen"]
+        .into_iter()
+        .map(|name| {
+            let executor: Box<dyn CliExecutor> = Box::new(MockExecutor { name });
+            (executor, ExecutorConfig::new("selftest", &[]))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_runner_skips_after_failure() {
+        let mut runner = StageRunner::new();
+
+        assert_eq!(
+            runner
+                .run("first", async { Ok((1, "ok".to_string())) })
+                .await,
+            Some(1)
+        );
+        assert_eq!(
+            runner
+                .run::<()>("second", async { Err("broken".to_string()) })
+                .await,
+            None
+        );
+        let mut ran = false;
+        let third = runner
+            .run("third", async {
+                ran = true;
+                Ok(((), String::new()))
+            })
+            .await;
+        assert!(third.is_none());
+        assert!(!ran, "skipped stages are not run");
+
+        let statuses: Vec<_> = runner.reports().iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            [StageStatus::Pass, StageStatus::Fail, StageStatus::Skip]
+        );
+        assert_eq!(runner.reports()[1].detail, "broken");
+        assert!(!runner.passed());
+    }
+
+    #[tokio::test]
+    async fn test_selftest_with_mock_executors() {
+        let reports = run_selftest(&Config::default(), false).await.unwrap();
+
+        let names: Vec<_> = reports.iter().map(|r| r.name).collect();
+        assert_eq!(
+            names,
+            [
+                "initialize",
+                "tools/list",
+                "review",
+                "reasoning bank",
+                "cache"
+            ]
+        );
+        for report in &reports {
+            assert_eq!(report.status, StageStatus::Pass, "{:?}", report);
+        }
+    }
+}
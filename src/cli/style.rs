@@ -0,0 +1,154 @@
+//! Output styling for CLI commands.
+//!
+//! Styled output uses ANSI colors and unicode icons. Plain output uses
+//! neither, so logs stay readable in CI and on dumb terminals. The style is
+//! resolved once from `--color`, `NO_COLOR`, `TERM` and TTY detection.
+
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+use clap::ValueEnum;
+
+/// When to use colors and unicode icons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    /// Style output only when stdout is a terminal and `NO_COLOR` is unset.
+    #[default]
+    Auto,
+    /// Always style output.
+    Always,
+    /// Never style output.
+    Never,
+}
+
+/// Resolved output style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    styled: bool,
+}
+
+static STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Sets the process-wide style from the `--color` flag.
+///
+/// Only the first call has an effect; later calls return the style already set.
+pub fn init(choice: ColorChoice) -> Style {
+    *STYLE.get_or_init(|| Style::detect(choice))
+}
+
+/// Returns the process-wide style, detecting it if [`init`] was never called.
+pub fn current() -> Style {
+    *STYLE.get_or_init(|| Style::detect(ColorChoice::Auto))
+}
+
+impl Style {
+    /// Colors and unicode icons.
+    pub const STYLED: Style = Style { styled: true };
+
+    /// No colors, ASCII icons.
+    pub const PLAIN: Style = Style { styled: false };
+
+    /// Resolves the style for the current environment.
+    pub fn detect(choice: ColorChoice) -> Self {
+        Self::resolve(
+            choice,
+            std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()),
+            std::env::var("TERM").is_ok_and(|term| term == "dumb"),
+            std::io::stdout().is_terminal(),
+        )
+    }
+
+    /// Resolves the style from explicit inputs.
+    ///
+    /// `--color always` and `--color never` win over the environment.
+    pub fn resolve(choice: ColorChoice, no_color: bool, dumb_term: bool, is_tty: bool) -> Self {
+        let styled = match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => !no_color && !dumb_term && is_tty,
+        };
+        Self { styled }
+    }
+
+    /// Whether output uses colors and unicode.
+    pub fn is_styled(&self) -> bool {
+        self.styled
+    }
+
+    /// Success icon.
+    pub fn ok(&self) -> String {
+        self.icon("✓", "+", GREEN)
+    }
+
+    /// Failure icon.
+    pub fn fail(&self) -> String {
+        self.icon("✗", "x", RED)
+    }
+
+    /// Disabled/skipped icon.
+    pub fn off(&self) -> String {
+        self.icon("○", "-", DIM)
+    }
+
+    /// Warning icon.
+    pub fn warn(&self) -> String {
+        self.icon("⚠", "!", YELLOW)
+    }
+
+    /// Emoji followed by a space, or nothing in plain mode.
+    pub fn emoji(&self, emoji: &str) -> String {
+        if self.styled {
+            format!("{} ", emoji)
+        } else {
+            String::new()
+        }
+    }
+
+    fn icon(&self, unicode: &str, ascii: &str, color: &str) -> String {
+        if self.styled {
+            format!("{}{}{}", color, unicode, RESET)
+        } else {
+            ascii.to_string()
+        }
+    }
+}
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_auto() {
+        assert!(Style::resolve(ColorChoice::Auto, false, false, true).is_styled());
+        // NO_COLOR, dumb terminals and pipes all disable styling
+        assert!(!Style::resolve(ColorChoice::Auto, true, false, true).is_styled());
+        assert!(!Style::resolve(ColorChoice::Auto, false, true, true).is_styled());
+        assert!(!Style::resolve(ColorChoice::Auto, false, false, false).is_styled());
+    }
+
+    #[test]
+    fn test_flag_overrides_environment() {
+        assert!(Style::resolve(ColorChoice::Always, true, true, false).is_styled());
+        assert!(!Style::resolve(ColorChoice::Never, false, false, true).is_styled());
+    }
+
+    #[test]
+    fn test_icons() {
+        assert_eq!(Style::STYLED.ok(), "\x1b[32m✓\x1b[0m");
+        assert_eq!(Style::STYLED.warn(), "\x1b[33m⚠\x1b[0m");
+        assert_eq!(Style::STYLED.emoji("📊"), "📊 ");
+
+        let plain = Style::PLAIN;
+        assert_eq!(
+            [plain.ok(), plain.fail(), plain.off(), plain.warn()],
+            ["+", "x", "-", "!"]
+        );
+        assert_eq!(plain.emoji("📊"), "");
+    }
+}
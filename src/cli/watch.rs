@@ -0,0 +1,337 @@
+//! File-watching evaluation (`tetrad watch`).
+//!
+//! Polls a directory tree (respecting `.gitignore`, like `evaluate --dir`),
+//! waits until a changed file has been quiet for the debounce interval and
+//! evaluates it through the batch pipeline. Content already evaluated is
+//! answered by the shared `EvaluationCache`; fresh results are recorded in
+//! the ReasoningBank.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use tokio::sync::Mutex;
+
+use super::batch::{collect_files, evaluate_files, BatchFile, BatchOptions, BatchOutcome};
+use crate::cache::EvaluationCache;
+use crate::consensus::ConsensusEngine;
+use crate::executors::CliExecutor;
+use crate::reasoning::ReasoningBank;
+use crate::types::config::ExecutorConfig;
+use crate::types::language::Language;
+use crate::types::requests::EvaluationType;
+use crate::TetradResult;
+
+/// Size and modification time of a watched file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl Stamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+}
+
+/// Polls a directory tree for changed source files.
+#[derive(Debug)]
+pub struct Watcher {
+    root: PathBuf,
+    language: Option<Language>,
+    max_files: usize,
+    debounce: Duration,
+    stamps: HashMap<PathBuf, Stamp>,
+    pending: HashMap<PathBuf, (BatchFile, Instant)>,
+}
+
+impl Watcher {
+    /// Starts watching `root`.
+    ///
+    /// Files already present are only reported once they change.
+    pub fn new(
+        root: &Path,
+        language: Option<Language>,
+        max_files: usize,
+        debounce: Duration,
+    ) -> TetradResult<Self> {
+        let mut watcher = Self {
+            root: root.to_path_buf(),
+            language,
+            max_files,
+            debounce,
+            stamps: HashMap::new(),
+            pending: HashMap::new(),
+        };
+        watcher.stamps = watcher
+            .scan()?
+            .into_iter()
+            .map(|(file, stamp)| (file.path, stamp))
+            .collect();
+        Ok(watcher)
+    }
+
+    fn scan(&self) -> TetradResult<Vec<(BatchFile, Stamp)>> {
+        Ok(
+            collect_files(&self.root, self.language.as_ref(), self.max_files)?
+                .into_iter()
+                .filter_map(|file| Stamp::of(&file.path).map(|stamp| (file, stamp)))
+                .collect(),
+        )
+    }
+
+    /// Rescans the tree and returns the files that changed and have then
+    /// been quiet for the debounce interval, sorted by path.
+    ///
+    /// Every new write to a file restarts its interval; deleted files are
+    /// dropped.
+    pub fn poll(&mut self, now: Instant) -> TetradResult<Vec<BatchFile>> {
+        let mut stamps = HashMap::new();
+        for (file, stamp) in self.scan()? {
+            if self.stamps.get(&file.path) != Some(&stamp) {
+                self.pending.insert(file.path.clone(), (file.clone(), now));
+            }
+            stamps.insert(file.path, stamp);
+        }
+        self.pending.retain(|path, _| stamps.contains_key(path));
+        self.stamps = stamps;
+
+        let debounce = self.debounce;
+        let mut ready = Vec::new();
+        self.pending.retain(|_, (file, changed)| {
+            if now.saturating_duration_since(*changed) >= debounce {
+                ready.push(file.clone());
+                false
+            } else {
+                true
+            }
+        });
+        ready.sort_by(|a: &BatchFile, b| a.path.cmp(&b.path));
+        Ok(ready)
+    }
+}
+
+/// Whether the cache already holds a result for the file's current content.
+pub fn is_cached(cache: &EvaluationCache, file: &BatchFile) -> bool {
+    std::fs::read_to_string(&file.path).is_ok_and(|code| {
+        cache.contains(&EvaluationCache::cache_key(
+            &code,
+            file.language.as_str(),
+            &EvaluationType::Code,
+        ))
+    })
+}
+
+/// Evaluates changed files and records the fresh results in the ReasoningBank.
+///
+/// Results answered by the cache are not recorded again: they were recorded
+/// when that content was first evaluated.
+#[allow(clippy::too_many_arguments)]
+pub async fn evaluate_changes(
+    files: Vec<BatchFile>,
+    executors: &[(Arc<dyn CliExecutor>, ExecutorConfig)],
+    engine: Arc<ConsensusEngine>,
+    cache: Arc<Mutex<EvaluationCache>>,
+    options: &BatchOptions,
+    bank: Option<&mut ReasoningBank>,
+    max_loops: u8,
+    jobs: usize,
+) -> Vec<BatchOutcome> {
+    let outcomes = evaluate_files(
+        files,
+        executors.to_vec(),
+        engine,
+        cache,
+        options.clone(),
+        jobs,
+    )
+    .await;
+
+    if let Some(bank) = bank {
+        for outcome in &outcomes {
+            if let (Some(request), Ok(result)) = (&outcome.request, &outcome.result) {
+                // A watch round is a single loop, like `tetrad evaluate`
+                if let Err(e) = bank.judge_request(request, result, 1, max_loops) {
+                    tracing::warn!(file = %outcome.file.path.display(), error = %e, "Error registering in ReasoningBank");
+                }
+            }
+        }
+    }
+
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executors::MockExecutor;
+    use crate::types::config::ConsensusConfig;
+    use crate::types::requests::EvaluationRequest;
+    use crate::types::responses::ModelVote;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::tempdir;
+
+    const DEBOUNCE: Duration = Duration::from_millis(500);
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn names(files: &[BatchFile]) -> Vec<String> {
+        files
+            .iter()
+            .map(|f| f.path.file_name().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_poll_debounces_successive_writes() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "fn main() {}").unwrap();
+
+        let mut watcher = Watcher::new(dir.path(), None, 100, DEBOUNCE).unwrap();
+        let start = Instant::now();
+
+        // Arquivos existentes não são reportados sem mudança
+        assert!(watcher.poll(start).unwrap().is_empty());
+
+        std::fs::write(&file, "fn main() { run(); }").unwrap();
+        assert!(watcher.poll(start).unwrap().is_empty());
+
+        // Uma nova escrita reinicia o intervalo
+        std::fs::write(&file, "fn main() { run(); stop(); }").unwrap();
+        assert!(watcher.poll(start + ms(300)).unwrap().is_empty());
+        assert!(watcher.poll(start + ms(600)).unwrap().is_empty());
+
+        assert_eq!(names(&watcher.poll(start + ms(800)).unwrap()), ["main.rs"]);
+        assert!(watcher.poll(start + ms(2000)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_poll_reports_new_files_and_respects_ignores() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join(".gitignore"), "*.gen.rs\n").unwrap();
+        std::fs::create_dir_all(root.join(".tetrad")).unwrap();
+
+        let mut watcher = Watcher::new(root, None, 100, DEBOUNCE).unwrap();
+        let start = Instant::now();
+
+        std::fs::write(root.join("lib.rs"), "pub fn lib() {}").unwrap();
+        std::fs::write(root.join("api.gen.rs"), "pub fn gen() {}").unwrap();
+        std::fs::write(root.join(".tetrad/cache.rs"), "pub fn c() {}").unwrap();
+        std::fs::write(root.join("notes.txt"), "notes").unwrap();
+        std::fs::write(root.join("gone.py"), "x = 1").unwrap();
+        assert!(watcher.poll(start).unwrap().is_empty());
+
+        // Arquivos removidos antes do fim do intervalo são descartados
+        std::fs::remove_file(root.join("gone.py")).unwrap();
+        assert_eq!(names(&watcher.poll(start + DEBOUNCE).unwrap()), ["lib.rs"]);
+    }
+
+    /// Mock que conta as chamadas ao executor.
+    struct CountingExecutor {
+        inner: MockExecutor,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl CliExecutor for CountingExecutor {
+        fn name(&self) -> &str {
+            "Codex"
+        }
+
+        fn command(&self) -> &str {
+            "mock"
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.inner.evaluate(request).await
+        }
+
+        fn specialization(&self) -> &str {
+            "syntax"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_changes_reuses_cache_and_feeds_bank() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        let file = root.join("auth.rs");
+        std::fs::write(&file, "let password = \"hunter2\";").unwrap();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let executor: Arc<dyn CliExecutor> = Arc::new(CountingExecutor {
+            inner: MockExecutor::new("Codex", "syntax"),
+            calls: calls.clone(),
+        });
+        let executors = vec![(executor, ExecutorConfig::new("mock", &[]))];
+        let engine = Arc::new(ConsensusEngine::from_config(ConsensusConfig::default(), 1).unwrap());
+        let cache = Arc::new(Mutex::new(EvaluationCache::new(100, ms(60_000))));
+        let mut bank = ReasoningBank::new(&root.join("patterns.db")).unwrap();
+
+        let files = collect_files(root, None, 100).unwrap();
+        let outcomes = evaluate_changes(
+            files.clone(),
+            &executors,
+            engine.clone(),
+            cache.clone(),
+            &BatchOptions::default(),
+            Some(&mut bank),
+            3,
+            1,
+        )
+        .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(!outcomes[0].cached);
+        assert_eq!(bank.count_trajectories().unwrap(), 1);
+
+        // Mesmo conteúdo: responde pelo cache, sem votar nem registrar de novo
+        assert!(is_cached(&*cache.lock().await, &files[0]));
+        let outcomes = evaluate_changes(
+            files.clone(),
+            &executors,
+            engine.clone(),
+            cache.clone(),
+            &BatchOptions::default(),
+            Some(&mut bank),
+            3,
+            1,
+        )
+        .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(outcomes[0].cached);
+        assert_eq!(bank.count_trajectories().unwrap(), 1);
+
+        // Conteúdo novo é avaliado
+        std::fs::write(&file, "let v = x.unwrap();").unwrap();
+        assert!(!is_cached(&*cache.lock().await, &files[0]));
+        evaluate_changes(
+            files,
+            &executors,
+            engine,
+            cache,
+            &BatchOptions::default(),
+            Some(&mut bank),
+            3,
+            1,
+        )
+        .await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(bank.count_trajectories().unwrap(), 2);
+    }
+}
@@ -5,50 +5,309 @@
 //! - Extrair issues comuns (consenso em problemas)
 //! - Consolidar feedback em mensagem coerente
 //! - Calcular score agregado
+//!
+//! A decisão ([`DecisionData`]) e a renderização do feedback são etapas
+//! separadas, para que caminhos que só precisam da decisão não paguem pelo
+//! markdown.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
-use std::collections::HashMap;
+use crate::types::config::SeverityGate;
+use crate::types::responses::{
+    Decision, EvaluationResult, Finding, ModelVote, PatternAdjustment, PendingFeedback,
+    RuleExplanation, Severity, SuggestedFix, Vote,
+};
 
-use crate::types::responses::{Decision, EvaluationResult, Finding, ModelVote, Severity, Vote};
+use super::feedback::{FeedbackContext, FeedbackTemplate};
+use super::rules::{ConsensusRule, VoteWeights};
 
-use super::rules::ConsensusRule;
+/// Similaridade mínima padrão para agrupar issues de executores diferentes.
+pub const DEFAULT_FINDING_SIMILARITY: f64 = 0.6;
 
 /// Agregador de votos.
 pub struct VoteAggregator;
 
+/// Resultado estruturado da agregação, sem o feedback renderizado.
+///
+/// Caminhos que só precisam da decisão (resumo do batch, recomputação de
+/// métricas) usam este valor sem pagar a renderização do markdown.
+#[derive(Debug, Clone)]
+pub struct DecisionData {
+    /// Votos dos executores.
+    pub votes: HashMap<String, ModelVote>,
+
+    /// Decisão final (após severity gating).
+    pub decision: Decision,
+
+    /// Score agregado.
+    pub score: u8,
+
+    /// Se os votantes concordaram.
+    pub consensus_achieved: bool,
+
+    /// Findings consolidados, por severidade.
+    pub findings: Vec<Finding>,
+
+    /// Gate de severidade aplicado à decisão.
+    pub block_on_severity: SeverityGate,
+
+    /// Nome da regra de consenso aplicada.
+    pub rule: String,
+
+    /// Confiança do consenso (0.0-1.0); veja [`VoteAggregator::calculate_confidence`].
+    pub confidence: f64,
+
+    /// Condição da regra que produziu a decisão.
+    pub explanation: RuleExplanation,
+}
+
+impl DecisionData {
+    /// Converte em resultado com o feedback já renderizado.
+    pub fn into_result(self, request_id: &str, feedback: String) -> EvaluationResult {
+        EvaluationResult {
+            request_id: request_id.to_string(),
+            decision: self.decision,
+            score: self.score,
+            votes: self.votes,
+            findings: self.findings,
+            feedback,
+            consensus_achieved: self.consensus_achieved,
+            confidence: self.confidence,
+            timestamp: chrono::Utc::now(),
+            flaky_with: None,
+            pattern_adjustment: None,
+            budget_exceeded: false,
+            cut_off_executors: Vec::new(),
+            degraded: false,
+            degraded_executors: Vec::new(),
+            short_circuited_executors: Vec::new(),
+            executor_timeouts: HashMap::new(),
+            executor_selection: None,
+            skip_reason: None,
+            effective_rule: Some(self.rule),
+            strictness: None,
+            low_confidence: false,
+            explanation: Some(self.explanation),
+            matched_patterns: Vec::new(),
+            from_cache: false,
+            chunks: Vec::new(),
+            refinement_loop: None,
+            loop_limit_exceeded: false,
+            pending_feedback: None,
+        }
+    }
+
+    /// Converte em resultado com o feedback pendente.
+    ///
+    /// O feedback é renderizado com `template` (ou o formato padrão) por
+    /// [`EvaluationResult::materialize_feedback`].
+    pub fn into_deferred_result(
+        self,
+        request_id: &str,
+        template: Option<Arc<FeedbackTemplate>>,
+    ) -> EvaluationResult {
+        let gate = self.block_on_severity;
+        let mut result = self.into_result(request_id, String::new());
+        result.pending_feedback = Some(PendingFeedback::new(move |result| {
+            VoteAggregator::render(
+                &result.votes,
+                result.decision,
+                result.score,
+                result.consensus_achieved,
+                &result.findings,
+                gate,
+                template.as_deref(),
+            )
+        }));
+        result
+    }
+}
+
 impl VoteAggregator {
     /// Agrega votos e retorna o resultado da avaliação.
+    ///
+    /// Findings com severidade igual ou acima de `block_on_severity` forçam a
+    /// decisão para `Block` (Critical) ou `Revise` (Error), mesmo que os votos
+    /// aprovem. `consensus_achieved` continua refletindo apenas os votos.
+    ///
+    /// Issues parecidos (similaridade >= `similarity`) viram um só finding;
+    /// veja [`VoteAggregator::extract_findings`].
+    ///
+    /// Sem `template`, o feedback usa o formato padrão.
     pub fn aggregate(
         votes: HashMap<String, ModelVote>,
         rule: &dyn ConsensusRule,
         min_score: u8,
+        block_on_severity: SeverityGate,
+        similarity: f64,
+        template: Option<&FeedbackTemplate>,
         request_id: &str,
     ) -> EvaluationResult {
-        let decision = rule.evaluate(&votes, min_score);
+        let data = Self::aggregate_decision(votes, rule, min_score, block_on_severity, similarity);
+        let feedback = Self::render_feedback(&data, template);
+        data.into_result(request_id, feedback)
+    }
+
+    /// Agrega votos sem renderizar o feedback.
+    pub fn aggregate_decision(
+        votes: HashMap<String, ModelVote>,
+        rule: &dyn ConsensusRule,
+        min_score: u8,
+        block_on_severity: SeverityGate,
+        similarity: f64,
+    ) -> DecisionData {
+        let mut explanation = rule.explain(&votes, min_score);
         let consensus_achieved = rule.is_consensus_achieved(&votes, min_score);
-        let score = Self::calculate_score(&votes);
-        let findings = Self::extract_findings(&votes);
-        let feedback = Self::consolidate_feedback(&votes, &decision);
+        let score = match rule.weights() {
+            Some(weights) => Self::calculate_weighted_score(&votes, weights),
+            None => Self::calculate_score(&votes),
+        };
+        let findings = Self::extract_findings(&votes, similarity);
 
-        EvaluationResult {
-            request_id: request_id.to_string(),
+        let confidence = Self::calculate_confidence(&votes, score, min_score, consensus_achieved);
+
+        let gated = Self::gated_findings(&findings, block_on_severity);
+        let decision = Self::apply_severity_gate(explanation.decision, &gated);
+        if decision != explanation.decision {
+            explanation.severity_gate = Some(
+                match block_on_severity {
+                    SeverityGate::Off => "off",
+                    SeverityGate::Error => "error",
+                    SeverityGate::Critical => "critical",
+                }
+                .to_string(),
+            );
+        }
+
+        DecisionData {
+            votes,
             decision,
             score,
-            votes,
+            consensus_achieved,
             findings,
-            feedback,
+            block_on_severity,
+            rule: rule.name().to_string(),
+            confidence,
+            explanation,
+        }
+    }
+
+    /// Renderiza o feedback de uma decisão agregada.
+    ///
+    /// Sem `template`, usa o formato padrão.
+    pub fn render_feedback(data: &DecisionData, template: Option<&FeedbackTemplate>) -> String {
+        Self::render(
+            &data.votes,
+            data.decision,
+            data.score,
+            data.consensus_achieved,
+            &data.findings,
+            data.block_on_severity,
+            template,
+        )
+    }
+
+    fn render(
+        votes: &HashMap<String, ModelVote>,
+        decision: Decision,
+        score: u8,
+        consensus_achieved: bool,
+        findings: &[Finding],
+        block_on_severity: SeverityGate,
+        template: Option<&FeedbackTemplate>,
+    ) -> String {
+        let gated = Self::gated_findings(findings, block_on_severity);
+        let context = FeedbackContext {
+            votes,
+            decision,
+            score,
             consensus_achieved,
-            timestamp: chrono::Utc::now(),
+            findings,
+            gated: &gated,
+        };
+        match template {
+            Some(template) => template.render(&context),
+            None => context.render_default(),
         }
     }
 
     /// Calcula o score agregado (média dos scores).
     pub fn calculate_score(votes: &HashMap<String, ModelVote>) -> u8 {
+        Self::calculate_weighted_score(votes, &VoteWeights::default())
+    }
+
+    /// Calcula o score agregado ponderado pelo peso de cada executor.
+    pub fn calculate_weighted_score(
+        votes: &HashMap<String, ModelVote>,
+        weights: &VoteWeights,
+    ) -> u8 {
+        weights.average_score(votes)
+    }
+
+    /// Calcula a confiança do consenso (0.0-1.0).
+    ///
+    /// Soma três fatores:
+    /// - fração de votos PASS (até 0.4)
+    /// - quanto o score passa de `min_score` (até 0.3)
+    /// - consenso alcançado (0.3)
+    pub fn calculate_confidence(
+        votes: &HashMap<String, ModelVote>,
+        score: u8,
+        min_score: u8,
+        consensus_achieved: bool,
+    ) -> f64 {
         if votes.is_empty() {
-            return 0;
+            return 0.0;
+        }
+
+        let mut confidence = 0.0;
+
+        let pass_count = votes.values().filter(|v| v.vote == Vote::Pass).count();
+        confidence += pass_count as f64 / votes.len() as f64 * 0.4;
+
+        if score >= min_score {
+            let headroom = (100 - min_score).max(1) as f64;
+            confidence += (score - min_score) as f64 / headroom * 0.3;
+        }
+
+        if consensus_achieved {
+            confidence += 0.3;
+        }
+
+        confidence.min(1.0)
+    }
+
+    /// Rebaixa para Revise um Pass com confiança abaixo de `min_confidence`.
+    ///
+    /// Os votos aprovaram, mas discordam demais entre si (ex: scores 95/55/72);
+    /// uma nota no feedback mostra a divergência.
+    pub fn apply_confidence_gate(result: &mut EvaluationResult, min_confidence: f64) {
+        if result.decision != Decision::Pass || result.confidence >= min_confidence {
+            return;
         }
 
-        let total: u32 = votes.values().map(|v| v.score as u32).sum();
-        (total / votes.len() as u32) as u8
+        let mut scores: Vec<(&String, u8)> = result
+            .votes
+            .iter()
+            .map(|(name, vote)| (name, vote.score))
+            .collect();
+        scores.sort();
+        let scores: Vec<String> = scores
+            .iter()
+            .map(|(name, score)| format!("{} {}", name, score))
+            .collect();
+
+        result.feedback.push_str(&format!(
+            "\n### Baixa Confiança\n\n\
+             Os executores aprovaram, mas com confiança de {:.0}%, abaixo do mínimo de {:.0}% \
+             (scores: {}). A decisão foi rebaixada para REVISE; reveja as avaliações divergentes.\n",
+            result.confidence * 100.0,
+            min_confidence * 100.0,
+            scores.join(", ")
+        ));
+        result.decision = Decision::Revise;
+        result.low_confidence = true;
     }
 
     /// Calcula o score mínimo entre os votos.
@@ -57,139 +316,176 @@ impl VoteAggregator {
     }
 
     /// Extrai findings dos votos, consolidando issues comuns.
-    pub fn extract_findings(votes: &HashMap<String, ModelVote>) -> Vec<Finding> {
+    ///
+    /// Issues cuja similaridade de tokens atinge `similarity` viram um único
+    /// finding: a descrição mais longa é o issue canônico, os executores de
+    /// todos os membros contam para o `consensus_strength` e a severidade é a
+    /// maior entre os membros. A ordem é determinística (por executor e por
+    /// ordem de reporte, depois por severidade).
+    pub fn extract_findings(votes: &HashMap<String, ModelVote>, similarity: f64) -> Vec<Finding> {
         let mut findings: Vec<Finding> = Vec::new();
-        let mut issue_counts: HashMap<String, (Vec<String>, Severity)> = HashMap::new();
-
-        // Conta quantos executores reportaram cada issue
-        for (executor, vote) in votes {
-            for issue in &vote.issues {
-                let key = Self::normalize_issue(issue);
-                let entry = issue_counts
-                    .entry(key.clone())
-                    .or_insert_with(|| (Vec::new(), Self::infer_severity(issue)));
-                entry.0.push(executor.clone());
-            }
-        }
 
-        // Cria findings para issues reportados por múltiplos executores (consenso)
-        for (issue, (executors, severity)) in &issue_counts {
-            let consensus_strength = if executors.len() >= 3 {
+        for cluster in Self::cluster_issues(votes, similarity) {
+            let consensus_strength = if cluster.executors.len() >= 3 {
                 "forte"
-            } else if executors.len() >= 2 {
+            } else if cluster.executors.len() >= 2 {
                 "moderado"
             } else {
                 "fraco"
             };
 
+            // Severidade e categoria vêm do membro mais grave
+            let (severity, category) = cluster.most_severe();
+
             // Busca sugestão correspondente
-            let suggestion = Self::find_suggestion_for_issue(votes, issue);
+            let suggestion = Self::find_suggestion_for_issue(votes, &cluster.members);
 
-            // Infere categoria do issue
-            let category = Self::infer_category(issue);
+            // Agrupa correções sugeridas; conflitos viram alternativas
+            let mut fixes = Self::collect_fixes_for_issue(votes, &cluster.members).into_iter();
+            let fix = fixes.next();
+            let alternative_fixes = fixes.collect();
+
+            // Linhas reportadas pelos executores têm prioridade sobre o range da correção
+            let lines = Self::collect_lines_for_issue(votes, &cluster.members)
+                .or_else(|| fix.as_ref().map(|f| (f.start_line..=f.end_line).collect()));
 
             findings.push(Finding {
-                issue: issue.clone(),
-                severity: *severity,
+                issue: cluster.canonical().to_string(),
+                severity,
                 category,
-                lines: None,
+                lines,
                 suggestion,
-                source: executors.join(", "),
+                source: cluster.executors.join(", "),
                 consensus_strength: consensus_strength.to_string(),
+                fix,
+                alternative_fixes,
+                file: cluster.file,
+                chunk: None,
             });
         }
 
-        // Ordena por severidade (Critical > Error > Warning > Info)
-        findings.sort_by(|a, b| {
-            let severity_order = |s: &Severity| match s {
-                Severity::Critical => 0,
-                Severity::Error => 1,
-                Severity::Warning => 2,
-                Severity::Info => 3,
-            };
-            severity_order(&a.severity).cmp(&severity_order(&b.severity))
-        });
+        // Ordena por severidade (Critical > Error > Warning > Info); a ordenação
+        // é estável, então empates mantêm a ordem dos clusters
+        findings.sort_by_key(|f| std::cmp::Reverse(f.severity));
 
         findings
     }
 
-    /// Consolida feedback de todos os executores.
-    pub fn consolidate_feedback(votes: &HashMap<String, ModelVote>, decision: &Decision) -> String {
-        let mut feedback = String::new();
+    /// Agrupa os issues dos votos por similaridade.
+    ///
+    /// Cada issue entra no cluster com o membro mais parecido (o primeiro, em
+    /// caso de empate), se a similaridade atingir `similarity`; senão abre um
+    /// cluster novo. Executores são percorridos em ordem alfabética.
+    fn cluster_issues(votes: &HashMap<String, ModelVote>, similarity: f64) -> Vec<IssueCluster> {
+        let mut executors: Vec<&String> = votes.keys().collect();
+        executors.sort();
 
-        // Cabeçalho baseado na decisão
-        let header = match decision {
-            Decision::Pass => "## Avaliação Aprovada",
-            Decision::Revise => "## Revisão Necessária",
-            Decision::Block => "## Avaliação Bloqueada",
-        };
-        feedback.push_str(header);
-        feedback.push_str("\n\n");
-
-        // Resumo dos votos
-        let pass_count = votes.values().filter(|v| v.vote == Vote::Pass).count();
-        let warn_count = votes.values().filter(|v| v.vote == Vote::Warn).count();
-        let fail_count = votes.values().filter(|v| v.vote == Vote::Fail).count();
+        let mut clusters: Vec<IssueCluster> = Vec::new();
+        for executor in executors {
+            let vote = &votes[executor];
+            for issue in &vote.issues {
+                let normalized = Self::normalize_issue(issue);
+                let tokens = Self::issue_tokens(&normalized);
+                let file = vote.issue_files.get(issue).map(String::as_str);
 
-        feedback.push_str(&format!(
-            "**Votos:** {} PASS | {} WARN | {} FAIL\n\n",
-            pass_count, warn_count, fail_count
-        ));
+                let mut best: Option<(usize, f64)> = None;
+                for (i, cluster) in clusters.iter().enumerate() {
+                    // Issues de arquivos diferentes nunca se fundem
+                    if !cluster.same_file(file) {
+                        continue;
+                    }
+                    let score = cluster.similarity(&normalized, &tokens);
+                    if score >= similarity && best.is_none_or(|(_, b)| score > b) {
+                        best = Some((i, score));
+                    }
+                }
 
-        // Feedback individual de cada executor
-        feedback.push_str("### Feedback dos Avaliadores\n\n");
+                match best {
+                    Some((i, _)) => clusters[i].add(normalized, issue, tokens, executor, file),
+                    None => {
+                        clusters.push(IssueCluster::new(normalized, issue, tokens, executor, file))
+                    }
+                }
+            }
+        }
 
-        for (executor, vote) in votes {
-            let icon = match vote.vote {
-                Vote::Pass => "✓",
-                Vote::Warn => "⚠",
-                Vote::Fail => "✗",
-            };
+        clusters
+    }
 
-            feedback.push_str(&format!(
-                "**{} {}** (score: {})\n",
-                icon, executor, vote.score
-            ));
+    /// Retorna os findings que atingem o limite de severidade configurado.
+    pub fn gated_findings(findings: &[Finding], block_on_severity: SeverityGate) -> Vec<&Finding> {
+        match block_on_severity.min_severity() {
+            Some(threshold) => findings
+                .iter()
+                .filter(|f| f.severity >= threshold)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
 
-            if !vote.reasoning.is_empty() {
-                feedback.push_str(&format!("> {}\n", vote.reasoning));
-            }
+    /// Rebaixa a decisão conforme os findings que atingiram o limite.
+    ///
+    /// Nunca torna a decisão mais branda: um `Block` dos votos permanece `Block`.
+    pub fn apply_severity_gate(decision: Decision, gated: &[&Finding]) -> Decision {
+        let forced = if gated.iter().any(|f| f.severity == Severity::Critical) {
+            Decision::Block
+        } else if gated.is_empty() {
+            return decision;
+        } else {
+            Decision::Revise
+        };
 
-            if !vote.issues.is_empty() {
-                feedback.push_str("\nIssues:\n");
-                for issue in &vote.issues {
-                    feedback.push_str(&format!("- {}\n", issue));
-                }
-            }
+        match (decision, forced) {
+            (Decision::Block, _) | (Decision::Revise, Decision::Revise) => decision,
+            _ => forced,
+        }
+    }
 
-            if !vote.suggestions.is_empty() {
-                feedback.push_str("\nSugestões:\n");
-                for suggestion in &vote.suggestions {
-                    feedback.push_str(&format!("- {}\n", suggestion));
-                }
-            }
+    /// Aplica a penalidade de anti-patterns conhecidos ao resultado.
+    ///
+    /// Se o score cair abaixo de `min_score`, um `Pass` vira `Revise`; a
+    /// decisão nunca fica mais branda.
+    pub fn apply_pattern_adjustment(
+        result: &mut EvaluationResult,
+        adjustment: PatternAdjustment,
+        min_score: u8,
+    ) {
+        let adjusted = result.score.saturating_sub(adjustment.penalty);
 
-            feedback.push('\n');
+        result.feedback.push_str(&format!(
+            "\n### Ajuste por Patterns Conhecidos\n\n\
+             Score reduzido de {} para {} por anti-patterns de avaliações anteriores:\n",
+            result.score, adjusted
+        ));
+        for pattern in &adjustment.patterns {
+            result.feedback.push_str(&format!(
+                "- [{}] {} (confiança: {:.0}%)\n",
+                pattern.issue_category,
+                pattern.description,
+                pattern.confidence * 100.0
+            ));
         }
 
-        // Ações recomendadas
-        feedback.push_str("### Ações Recomendadas\n\n");
-        match decision {
-            Decision::Pass => {
-                feedback.push_str("O código foi aprovado por todos os avaliadores. ");
-                feedback.push_str("Você pode prosseguir com a implementação.\n");
-            }
-            Decision::Revise => {
-                feedback.push_str("O código precisa de ajustes antes de ser aprovado. ");
-                feedback.push_str("Revise os issues acima e submeta novamente.\n");
-            }
-            Decision::Block => {
-                feedback.push_str("O código foi bloqueado devido a problemas críticos. ");
-                feedback.push_str("Corrija TODOS os issues marcados como Critical ou Error antes de prosseguir.\n");
-            }
+        result.score = adjusted;
+        if result.decision == Decision::Pass && adjusted < min_score {
+            result.decision = Decision::Revise;
         }
+        result.pattern_adjustment = Some(adjustment);
+    }
 
-        feedback
+    /// Consolida feedback de todos os executores no formato padrão.
+    pub fn consolidate_feedback(votes: &HashMap<String, ModelVote>, decision: &Decision) -> String {
+        let findings = Self::extract_findings(votes, DEFAULT_FINDING_SIMILARITY);
+
+        FeedbackContext {
+            votes,
+            decision: *decision,
+            score: Self::calculate_score(votes),
+            consensus_achieved: false,
+            findings: &findings,
+            gated: &[],
+        }
+        .render_default()
     }
 
     /// Normaliza um issue para comparação (lowercase, trim).
@@ -197,6 +493,42 @@ impl VoteAggregator {
         issue.to_lowercase().trim().to_string()
     }
 
+    /// Tokens significativos de um issue normalizado.
+    ///
+    /// Separa por caracteres não alfanuméricos, reduz plurais simples e
+    /// descarta palavras funcionais e qualificadores ("possible", "risk") que
+    /// não identificam o problema.
+    fn issue_tokens(issue: &str) -> HashSet<String> {
+        issue
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|token| token.len() > 1)
+            .map(Self::singular)
+            .filter(|token| !ISSUE_STOPWORDS.contains(&token.as_str()))
+            .collect()
+    }
+
+    /// Reduz plurais simples ("queries" → "query", "panics" → "panic").
+    fn singular(token: &str) -> String {
+        if let Some(stem) = token.strip_suffix("ies") {
+            if stem.len() >= 2 {
+                return format!("{}y", stem);
+            }
+        }
+        match token.strip_suffix('s') {
+            Some(stem) if stem.len() >= 3 && !stem.ends_with('s') => stem.to_string(),
+            _ => token.to_string(),
+        }
+    }
+
+    /// Similaridade de cosseno entre dois conjuntos de tokens (0.0 a 1.0).
+    fn token_similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+        if a.is_empty() || b.is_empty() {
+            return 0.0;
+        }
+        let shared = a.intersection(b).count() as f64;
+        shared / ((a.len() * b.len()) as f64).sqrt()
+    }
+
     /// Infere a severidade de um issue baseado em keywords.
     fn infer_severity(issue: &str) -> Severity {
         let issue_lower = issue.to_lowercase();
@@ -235,6 +567,23 @@ impl VoteAggregator {
             || issue_lower.contains("credential")
         {
             "security".to_string()
+        } else if issue_lower.starts_with("accuracy")
+            || issue_lower.contains("inaccurate")
+            || issue_lower.contains("outdated")
+            || issue_lower.contains("does not match the code")
+        {
+            "accuracy".to_string()
+        } else if issue_lower.starts_with("completeness")
+            || issue_lower.contains("undocumented")
+            || issue_lower.contains("not documented")
+        {
+            "completeness".to_string()
+        } else if issue_lower.starts_with("documentation")
+            || issue_lower.contains("broken link")
+            || issue_lower.contains("inconsistent tone")
+            || issue_lower.contains("unclear wording")
+        {
+            "documentation".to_string()
         } else if issue_lower.contains("performance")
             || issue_lower.contains("slow")
             || issue_lower.contains("memory")
@@ -264,16 +613,98 @@ impl VoteAggregator {
         }
     }
 
-    /// Busca uma sugestão correspondente a um issue.
+    /// Coleta as linhas reportadas pelos executores para um cluster de issues.
+    ///
+    /// Retorna a união ordenada das linhas, ou `None` se nenhum executor as
+    /// reportou.
+    fn collect_lines_for_issue(
+        votes: &HashMap<String, ModelVote>,
+        members: &[String],
+    ) -> Option<Vec<u32>> {
+        let mut lines: Vec<u32> = votes
+            .values()
+            .flat_map(|vote| &vote.lines)
+            .filter(|(vote_issue, _)| members.contains(&Self::normalize_issue(vote_issue)))
+            .flat_map(|(_, lines)| lines.iter().copied())
+            .collect();
+
+        if lines.is_empty() {
+            return None;
+        }
+        lines.sort_unstable();
+        lines.dedup();
+        Some(lines)
+    }
+
+    /// Coleta as correções sugeridas para um cluster de issues.
+    ///
+    /// Correções idênticas de executores diferentes são unificadas (com todas
+    /// as fontes). O resultado é ordenado pelo número de executores que
+    /// propuseram cada correção.
+    fn collect_fixes_for_issue(
+        votes: &HashMap<String, ModelVote>,
+        members: &[String],
+    ) -> Vec<SuggestedFix> {
+        let mut fixes: Vec<SuggestedFix> = Vec::new();
+
+        // Ordena por executor para um resultado determinístico
+        let mut executors: Vec<&String> = votes.keys().collect();
+        executors.sort();
+
+        for executor in executors {
+            let vote = &votes[executor];
+            let mut vote_fixes: Vec<(&String, &SuggestedFix)> = vote
+                .fixes
+                .iter()
+                .filter(|(vote_issue, _)| members.contains(&Self::normalize_issue(vote_issue)))
+                .collect();
+            vote_fixes.sort_by(|a, b| a.0.cmp(b.0));
+
+            for (_, fix) in vote_fixes {
+                match fixes.iter_mut().find(|f| f.same_change(fix)) {
+                    Some(existing) => {
+                        if !existing.sources.contains(executor) {
+                            existing.sources.push(executor.clone());
+                        }
+                    }
+                    None => {
+                        let mut fix = fix.clone();
+                        fix.sources = vec![executor.clone()];
+                        fixes.push(fix);
+                    }
+                }
+            }
+        }
+
+        fixes.sort_by_key(|f| std::cmp::Reverse(f.sources.len()));
+        fixes
+    }
+
+    /// Busca uma sugestão correspondente a um cluster de issues.
     fn find_suggestion_for_issue(
         votes: &HashMap<String, ModelVote>,
-        issue: &str,
+        members: &[String],
     ) -> Option<String> {
-        let issue_normalized = Self::normalize_issue(issue);
+        // Ordena por executor para um resultado determinístico
+        let mut executors: Vec<&String> = votes.keys().collect();
+        executors.sort();
 
-        for vote in votes.values() {
+        // Sugestões anexadas ao próprio issue têm prioridade sobre as gerais
+        for executor in &executors {
+            let vote = &votes[*executor];
+            for vote_issue in &vote.issues {
+                if members.contains(&Self::normalize_issue(vote_issue)) {
+                    if let Some(suggestion) = vote.issue_suggestions.get(vote_issue) {
+                        return Some(suggestion.clone());
+                    }
+                }
+            }
+        }
+
+        for executor in executors {
+            let vote = &votes[executor];
             for (i, vote_issue) in vote.issues.iter().enumerate() {
-                if Self::normalize_issue(vote_issue) == issue_normalized {
+                if members.contains(&Self::normalize_issue(vote_issue)) {
                     if let Some(suggestion) = vote.suggestions.get(i) {
                         return Some(suggestion.clone());
                     }
@@ -281,9 +712,9 @@ impl VoteAggregator {
             }
 
             // Se não encontrou por índice, tenta a primeira sugestão disponível
-            if !vote.suggestions.is_empty() {
+            for issue in members {
                 // Usa chars() para slice seguro em UTF-8 (evita panic em caracteres não-ASCII)
-                let issue_prefix: String = issue_normalized.chars().take(20).collect();
+                let issue_prefix: String = issue.chars().take(20).collect();
                 for suggestion in &vote.suggestions {
                     if suggestion.to_lowercase().contains(&issue_prefix) {
                         return Some(suggestion.clone());
@@ -296,10 +727,170 @@ impl VoteAggregator {
     }
 }
 
+/// Palavras ignoradas na comparação de issues.
+const ISSUE_STOPWORDS: &[&str] = &[
+    // Palavras funcionais
+    "an",
+    "the",
+    "in",
+    "on",
+    "of",
+    "to",
+    "for",
+    "with",
+    "at",
+    "by",
+    "from",
+    "into",
+    "and",
+    "or",
+    "is",
+    "are",
+    "be",
+    "been",
+    "it",
+    "its",
+    "this",
+    "that",
+    "there",
+    "may",
+    "might",
+    "can",
+    "could",
+    "should",
+    "would",
+    "will",
+    // Qualificadores que não identificam o problema
+    "possible",
+    "potential",
+    "potentially",
+    "likely",
+    "risk",
+    "issue",
+    "problem",
+    "vulnerability",
+    "vulnerable",
+    "found",
+    "detected",
+];
+
+/// Issues parecidos reportados por um ou mais executores.
+struct IssueCluster {
+    /// Issues normalizados, na ordem em que apareceram.
+    members: Vec<String>,
+
+    /// Texto original de cada membro, como o primeiro executor o escreveu
+    /// (mesma ordem de `members`).
+    texts: Vec<String>,
+
+    /// Tokens de cada membro (mesma ordem de `members`).
+    tokens: Vec<HashSet<String>>,
+
+    /// Executores que reportaram algum membro, sem repetição.
+    executors: Vec<String>,
+
+    /// Arquivo do primeiro membro que o informou.
+    file: Option<String>,
+}
+
+impl IssueCluster {
+    fn new(
+        issue: String,
+        text: &str,
+        tokens: HashSet<String>,
+        executor: &str,
+        file: Option<&str>,
+    ) -> Self {
+        Self {
+            members: vec![issue],
+            texts: vec![text.trim().to_string()],
+            tokens: vec![tokens],
+            executors: vec![executor.to_string()],
+            file: file.map(str::to_string),
+        }
+    }
+
+    fn add(
+        &mut self,
+        issue: String,
+        text: &str,
+        tokens: HashSet<String>,
+        executor: &str,
+        file: Option<&str>,
+    ) {
+        if self.file.is_none() {
+            self.file = file.map(str::to_string);
+        }
+        if !self.members.contains(&issue) {
+            self.members.push(issue);
+            self.texts.push(text.trim().to_string());
+            self.tokens.push(tokens);
+        }
+        if !self.executors.iter().any(|e| e == executor) {
+            self.executors.push(executor.to_string());
+        }
+    }
+
+    /// Se um issue do arquivo `file` pode entrar no cluster.
+    ///
+    /// Issues sem arquivo combinam com qualquer cluster.
+    fn same_file(&self, file: Option<&str>) -> bool {
+        match (self.file.as_deref(), file) {
+            (Some(own), Some(other)) => own == other,
+            _ => true,
+        }
+    }
+
+    /// Maior similaridade entre o issue e os membros do cluster.
+    fn similarity(&self, issue: &str, tokens: &HashSet<String>) -> f64 {
+        self.members
+            .iter()
+            .zip(&self.tokens)
+            .map(|(member, member_tokens)| {
+                if member == issue {
+                    1.0
+                } else {
+                    VoteAggregator::token_similarity(tokens, member_tokens)
+                }
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /// Texto original do membro mais longo (o primeiro, em caso de empate).
+    fn canonical(&self) -> &str {
+        let mut canonical = 0;
+        for (i, member) in self.members.iter().enumerate().skip(1) {
+            if member.chars().count() > self.members[canonical].chars().count() {
+                canonical = i;
+            }
+        }
+        &self.texts[canonical]
+    }
+
+    /// Maior severidade entre os membros e a categoria desse membro.
+    ///
+    /// Em caso de empate, prefere o issue canônico.
+    fn most_severe(&self) -> (Severity, String) {
+        let canonical = self.canonical();
+        let mut worst = canonical;
+        let mut severity = VoteAggregator::infer_severity(canonical);
+        for member in &self.members {
+            let member_severity = VoteAggregator::infer_severity(member);
+            if member_severity > severity {
+                worst = member;
+                severity = member_severity;
+            }
+        }
+        (severity, VoteAggregator::infer_category(worst))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::consensus::rules::StrongRule;
+    use crate::test_support::{vote_map, VoteBuilder};
+    use crate::types::responses::{FileFindings, Vote};
 
     fn create_vote(name: &str, vote: Vote, score: u8) -> (String, ModelVote) {
         (name.to_string(), ModelVote::new(name, vote, score))
@@ -312,36 +903,190 @@ mod tests {
         issues: Vec<&str>,
         suggestions: Vec<&str>,
     ) -> (String, ModelVote) {
-        let mut mv = ModelVote::new(name, vote, score);
-        mv.issues = issues.into_iter().map(String::from).collect();
-        mv.suggestions = suggestions.into_iter().map(String::from).collect();
-        (name.to_string(), mv)
+        let builder = issues
+            .into_iter()
+            .fold(VoteBuilder::new(name, vote, score), VoteBuilder::issue);
+        let builder = suggestions
+            .into_iter()
+            .fold(builder, VoteBuilder::suggestion);
+        (name.to_string(), builder.build())
     }
 
     #[test]
     fn test_calculate_score() {
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Pass, 80),
-            create_vote("Gemini", Vote::Pass, 90),
-            create_vote("Qwen", Vote::Pass, 85),
-        ]
-        .into_iter()
-        .collect();
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 80),
+            ("Gemini", Vote::Pass, 90),
+            ("Qwen", Vote::Pass, 85),
+        ]);
 
         assert_eq!(VoteAggregator::calculate_score(&votes), 85);
     }
 
+    #[test]
+    fn test_calculate_weighted_score() {
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 90),
+            ("Gemini", Vote::Pass, 60),
+            ("Qwen", Vote::Pass, 60),
+        ]);
+
+        // Codex pesa 4: (90*4 + 60 + 60) / 6 = 80
+        let weights = VoteWeights::new([("codex", 4), ("gemini", 1), ("qwen", 1)]);
+        assert_eq!(
+            VoteAggregator::calculate_weighted_score(&votes, &weights),
+            80
+        );
+
+        // Pesos iguais dão a média simples
+        let uniform = VoteWeights::new([("codex", 3), ("gemini", 3), ("qwen", 3)]);
+        assert_eq!(
+            VoteAggregator::calculate_weighted_score(&votes, &uniform),
+            VoteAggregator::calculate_score(&votes)
+        );
+    }
+
     #[test]
     fn test_calculate_min_score() {
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Pass, 80),
-            create_vote("Gemini", Vote::Pass, 90),
-            create_vote("Qwen", Vote::Warn, 60),
-        ]
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 80),
+            ("Gemini", Vote::Pass, 90),
+            ("Qwen", Vote::Warn, 60),
+        ]);
+
+        assert_eq!(VoteAggregator::calculate_min_score(&votes), 60);
+    }
+
+    #[test]
+    fn test_extract_findings_groups_fixes() {
+        let issue = "unwrap may panic";
+        let agreed = SuggestedFix::new(3, 3, "let x = y?;");
+        let conflicting = SuggestedFix::new(3, 4, "let x = y.unwrap_or_default();");
+
+        let mut votes: HashMap<String, ModelVote> = HashMap::new();
+        for (name, fix) in [
+            ("Codex", agreed.clone()),
+            ("Gemini", agreed.clone()),
+            ("Qwen", conflicting.clone()),
+        ] {
+            let (name, mut mv) = create_vote_with_issues(name, Vote::Warn, 60, vec![issue], vec![]);
+            mv.fixes.insert(issue.to_string(), fix);
+            votes.insert(name, mv);
+        }
+
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
+        let finding = findings.iter().find(|f| f.issue == issue).unwrap();
+
+        let fix = finding.fix.as_ref().unwrap();
+        assert!(fix.same_change(&agreed));
+        assert_eq!(fix.sources, vec!["Codex", "Gemini"]);
+        assert_eq!(finding.lines, Some(vec![3]));
+
+        assert_eq!(finding.alternative_fixes.len(), 1);
+        assert!(finding.alternative_fixes[0].same_change(&conflicting));
+        assert_eq!(finding.alternative_fixes[0].sources, vec!["Qwen"]);
+    }
+
+    #[test]
+    fn test_extract_findings_reported_lines() {
+        let issue = "missing bounds check";
+        let mut votes: HashMap<String, ModelVote> = HashMap::new();
+        for (name, lines) in [("Codex", vec![12, 13]), ("Gemini", vec![13, 14])] {
+            let (name, mut mv) = create_vote_with_issues(name, Vote::Warn, 60, vec![issue], vec![]);
+            mv.lines.insert(issue.to_string(), lines);
+            votes.insert(name, mv);
+        }
+        let (name, mut mv) = create_vote_with_issues("Qwen", Vote::Warn, 60, vec![issue], vec![]);
+        mv.fixes
+            .insert(issue.to_string(), SuggestedFix::new(1, 1, "x"));
+        votes.insert(name, mv);
+
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
+        let finding = findings.iter().find(|f| f.issue == issue).unwrap();
+
+        // Linhas reportadas vencem o range da correção
+        assert_eq!(finding.lines, Some(vec![12, 13, 14]));
+    }
+
+    #[test]
+    fn test_extract_findings_two_files_disjoint() {
+        let issue = "sql injection in query builder";
+        let mut votes: HashMap<String, ModelVote> = HashMap::new();
+        for (name, file) in [("Codex", "src/db.rs"), ("Gemini", "src/api.rs")] {
+            let (name, mut mv) = create_vote_with_issues(name, Vote::Fail, 30, vec![issue], vec![]);
+            mv.issue_files.insert(issue.to_string(), file.to_string());
+            votes.insert(name, mv);
+        }
+        let (name, mut mv) =
+            create_vote_with_issues("Qwen", Vote::Warn, 60, vec!["unused variable"], vec![]);
+        mv.issue_files
+            .insert("unused variable".to_string(), "src/api.rs".to_string());
+        votes.insert(name, mv);
+
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
+
+        // O mesmo texto em arquivos diferentes não se funde
+        assert_eq!(findings.len(), 3);
+        let injections: Vec<&Finding> = findings.iter().filter(|f| f.issue == issue).collect();
+        assert_eq!(injections.len(), 2);
+        assert!(injections.iter().all(|f| f.consensus_strength == "fraco"));
+
+        let groups = FileFindings::group(&findings);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].file.as_deref(), Some("src/api.rs"));
+        assert_eq!(groups[0].findings.len(), 2);
+        assert_eq!(
+            groups[0].verdict,
+            groups[0].findings.iter().map(|f| f.severity).max().unwrap()
+        );
+        assert_eq!(groups[1].file.as_deref(), Some("src/db.rs"));
+        assert_eq!(groups[1].findings.len(), 1);
+        assert_eq!(groups[1].findings[0].source, "Codex");
+    }
+
+    #[test]
+    fn test_extract_findings_single_file_not_grouped() {
+        let votes: HashMap<String, ModelVote> = [create_vote_with_issues(
+            "Codex",
+            Vote::Warn,
+            60,
+            vec!["unused variable"],
+            vec![],
+        )]
         .into_iter()
         .collect();
 
-        assert_eq!(VoteAggregator::calculate_min_score(&votes), 60);
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
+        assert!(findings[0].file.is_none());
+        assert!(FileFindings::group(&findings).is_empty());
+    }
+
+    #[test]
+    fn test_extract_findings_prefers_issue_suggestion() {
+        let issue = "unwrap may panic";
+        let (codex, mut codex_vote) = create_vote_with_issues(
+            "Codex",
+            Vote::Warn,
+            60,
+            vec![issue],
+            vec!["Adicionar testes"],
+        );
+        codex_vote.lines.insert(issue.to_string(), vec![7]);
+        let (gemini, mut gemini_vote) =
+            create_vote_with_issues("Gemini", Vote::Warn, 60, vec![issue], vec![]);
+        gemini_vote
+            .issue_suggestions
+            .insert(issue.to_string(), "Propagar o erro com ?".to_string());
+        let votes: HashMap<String, ModelVote> = [(codex, codex_vote), (gemini, gemini_vote)]
+            .into_iter()
+            .collect();
+
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
+        let finding = findings.iter().find(|f| f.issue == issue).unwrap();
+
+        // A sugestão do próprio issue vence a sugestão geral por índice
+        assert_eq!(finding.suggestion.as_deref(), Some("Propagar o erro com ?"));
+        assert_eq!(finding.lines, Some(vec![7]));
     }
 
     #[test]
@@ -366,40 +1111,399 @@ mod tests {
         .into_iter()
         .collect();
 
-        let findings = VoteAggregator::extract_findings(&votes);
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
         assert!(!findings.is_empty());
 
         // Deve haver um finding para SQL injection
-        let sql_finding = findings.iter().find(|f| f.issue.contains("sql injection"));
+        let sql_finding = findings.iter().find(|f| f.issue.contains("SQL injection"));
         assert!(sql_finding.is_some());
     }
 
+    fn paraphrased_votes(issues: [&str; 3]) -> HashMap<String, ModelVote> {
+        ["Codex", "Gemini", "Qwen"]
+            .into_iter()
+            .zip(issues)
+            .map(|(name, issue)| create_vote_with_issues(name, Vote::Warn, 60, vec![issue], vec![]))
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_findings_clusters_paraphrases() {
+        let sets = [
+            [
+                "SQL injection risk in query builder",
+                "Possible SQL injection vulnerability",
+                "Potential SQL injection in the query builder",
+            ],
+            [
+                "unwrap() on user input may panic",
+                "Calling unwrap on user input can panic",
+                "Unwrap of user input panics on invalid data",
+            ],
+            [
+                "Missing bounds check on buffer index",
+                "Buffer index is missing a bounds check",
+                "Bounds check missing on buffer index access",
+            ],
+        ];
+
+        for issues in sets {
+            let findings = VoteAggregator::extract_findings(
+                &paraphrased_votes(issues),
+                DEFAULT_FINDING_SIMILARITY,
+            );
+
+            assert_eq!(findings.len(), 1, "{:?}", findings);
+            assert_eq!(findings[0].consensus_strength, "forte");
+            assert_eq!(findings[0].source, "Codex, Gemini, Qwen");
+        }
+    }
+
+    #[test]
+    fn test_extract_findings_cluster_canonical_and_severity() {
+        let votes = paraphrased_votes([
+            "SQL injection risk in query builder",
+            "Possible SQL injection vulnerability",
+            "User input reaches the database unescaped",
+        ]);
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
+
+        // O terceiro issue não é paráfrase suficiente
+        assert_eq!(findings.len(), 2, "{:?}", findings);
+
+        let sql = &findings[0];
+        assert_eq!(sql.issue, "Possible SQL injection vulnerability");
+        assert_eq!(sql.severity, Severity::Critical);
+        assert_eq!(sql.category, "security");
+        assert_eq!(sql.consensus_strength, "moderado");
+
+        // A severidade do cluster é a maior entre os membros
+        let votes = paraphrased_votes([
+            "Style: unclear naming in the query builder module",
+            "Unclear naming in query builder (security)",
+            "Unclear naming in the query builder",
+        ]);
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
+        assert_eq!(findings.len(), 1, "{:?}", findings);
+        assert_eq!(findings[0].severity, Severity::Critical);
+        assert_eq!(findings[0].category, "security");
+        assert_eq!(
+            findings[0].issue,
+            "Style: unclear naming in the query builder module"
+        );
+    }
+
+    #[test]
+    fn test_extract_findings_keeps_distinct_issues_apart() {
+        let votes = paraphrased_votes([
+            "SQL injection in query builder",
+            "Missing error handling in query builder",
+            "Unused import of serde",
+        ]);
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
+
+        assert_eq!(findings.len(), 3, "{:?}", findings);
+        assert!(findings.iter().all(|f| f.consensus_strength == "fraco"));
+    }
+
+    #[test]
+    fn test_extract_findings_similarity_threshold() {
+        let issues = [
+            "SQL injection risk in query builder",
+            "Possible SQL injection vulnerability",
+            "Potential SQL injection in the query builder",
+        ];
+
+        // Com 1.0, só issues com os mesmos tokens se juntam
+        let findings = VoteAggregator::extract_findings(&paraphrased_votes(issues), 1.0);
+        assert_eq!(findings.len(), 2, "{:?}", findings);
+        assert_eq!(findings[0].source, "Codex, Qwen");
+        assert_eq!(findings[1].source, "Gemini");
+    }
+
+    #[test]
+    fn test_extract_findings_is_deterministic() {
+        let issues = [
+            "Missing error handling in query builder",
+            "SQL injection risk in query builder",
+            "Possible SQL injection vulnerability",
+        ];
+        let first = VoteAggregator::extract_findings(
+            &paraphrased_votes(issues),
+            DEFAULT_FINDING_SIMILARITY,
+        );
+
+        for _ in 0..10 {
+            let again = VoteAggregator::extract_findings(
+                &paraphrased_votes(issues),
+                DEFAULT_FINDING_SIMILARITY,
+            );
+            let summary = |findings: &[Finding]| -> Vec<(String, String)> {
+                findings
+                    .iter()
+                    .map(|f| (f.issue.clone(), f.source.clone()))
+                    .collect()
+            };
+            assert_eq!(summary(&again), summary(&first));
+        }
+    }
+
+    #[test]
+    fn test_extract_findings_cluster_merges_fixes() {
+        let fix = SuggestedFix::new(7, 7, "let q = query.bind(input);");
+        let mut votes = paraphrased_votes([
+            "SQL injection risk in query builder",
+            "Possible SQL injection vulnerability",
+            "Potential SQL injection in the query builder",
+        ]);
+        for vote in votes.values_mut() {
+            let issue = vote.issues[0].clone();
+            vote.fixes.insert(issue, fix.clone());
+        }
+
+        let findings = VoteAggregator::extract_findings(&votes, DEFAULT_FINDING_SIMILARITY);
+        assert_eq!(findings.len(), 1);
+        let merged = findings[0].fix.as_ref().unwrap();
+        assert_eq!(merged.sources, vec!["Codex", "Gemini", "Qwen"]);
+        assert!(findings[0].alternative_fixes.is_empty());
+    }
+
     #[test]
     fn test_aggregate_pass() {
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Pass, 85),
-            create_vote("Gemini", Vote::Pass, 90),
-            create_vote("Qwen", Vote::Pass, 88),
-        ]
-        .into_iter()
-        .collect();
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Pass, 90),
+            ("Qwen", Vote::Pass, 88),
+        ]);
 
-        let rule = StrongRule;
-        let result = VoteAggregator::aggregate(votes, &rule, 70, "test-123");
+        let rule = StrongRule::default();
+        let result = VoteAggregator::aggregate(
+            votes,
+            &rule,
+            70,
+            SeverityGate::Critical,
+            DEFAULT_FINDING_SIMILARITY,
+            None,
+            "test-123",
+        );
 
         assert_eq!(result.decision, Decision::Pass);
         assert!(result.consensus_achieved);
         assert_eq!(result.score, 87); // (85+90+88)/3
     }
 
-    #[test]
-    fn test_consolidate_feedback_pass() {
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Pass, 85),
+    fn unanimous_pass_with_issue(issue: &str) -> HashMap<String, ModelVote> {
+        vec![
+            create_vote_with_issues("Codex", Vote::Pass, 90, vec![issue], vec![]),
             create_vote("Gemini", Vote::Pass, 90),
+            create_vote("Qwen", Vote::Pass, 90),
         ]
         .into_iter()
-        .collect();
+        .collect()
+    }
+
+    #[test]
+    fn test_aggregate_severity_gate_blocks_on_critical() {
+        let votes = unanimous_pass_with_issue("SQL injection in query builder");
+
+        let rule = StrongRule::default();
+        let result = VoteAggregator::aggregate(
+            votes,
+            &rule,
+            70,
+            SeverityGate::Critical,
+            DEFAULT_FINDING_SIMILARITY,
+            None,
+            "gate-1",
+        );
+
+        assert_eq!(result.decision, Decision::Block);
+        assert!(result.consensus_achieved);
+
+        let notice = result.feedback.find("severity gating").unwrap();
+        let voters = result.feedback.find("Feedback dos Avaliadores").unwrap();
+        assert!(notice < voters);
+        assert!(result
+            .feedback
+            .contains("- [CRITICAL] SQL injection in query builder (Codex)"));
+    }
+
+    #[test]
+    fn test_aggregate_severity_gate_revises_on_error() {
+        let rule = StrongRule::default();
+
+        let votes = unanimous_pass_with_issue("Off-by-one bug in loop");
+        let result = VoteAggregator::aggregate(
+            votes,
+            &rule,
+            70,
+            SeverityGate::Error,
+            DEFAULT_FINDING_SIMILARITY,
+            None,
+            "gate-2",
+        );
+        assert_eq!(result.decision, Decision::Revise);
+        assert!(result.consensus_achieved);
+
+        // Com o limite padrão, Error não força a decisão
+        let votes = unanimous_pass_with_issue("Off-by-one bug in loop");
+        let result = VoteAggregator::aggregate(
+            votes,
+            &rule,
+            70,
+            SeverityGate::Critical,
+            DEFAULT_FINDING_SIMILARITY,
+            None,
+            "gate-3",
+        );
+        assert_eq!(result.decision, Decision::Pass);
+        assert!(!result.feedback.contains("severity gating"));
+    }
+
+    #[test]
+    fn test_aggregate_severity_gate_off() {
+        let votes = unanimous_pass_with_issue("SQL injection in query builder");
+
+        let rule = StrongRule::default();
+        let result = VoteAggregator::aggregate(
+            votes,
+            &rule,
+            70,
+            SeverityGate::Off,
+            DEFAULT_FINDING_SIMILARITY,
+            None,
+            "gate-4",
+        );
+
+        assert_eq!(result.decision, Decision::Pass);
+        assert!(!result.feedback.contains("severity gating"));
+    }
+
+    #[test]
+    fn test_aggregate_decision_skips_feedback() {
+        let rule = StrongRule::default();
+        let data = VoteAggregator::aggregate_decision(
+            unanimous_pass_with_issue("SQL injection in query builder"),
+            &rule,
+            70,
+            SeverityGate::Critical,
+            DEFAULT_FINDING_SIMILARITY,
+        );
+        assert_eq!(data.decision, Decision::Block);
+
+        let eager = VoteAggregator::aggregate(
+            unanimous_pass_with_issue("SQL injection in query builder"),
+            &rule,
+            70,
+            SeverityGate::Critical,
+            DEFAULT_FINDING_SIMILARITY,
+            None,
+            "eager",
+        );
+        assert_eq!(VoteAggregator::render_feedback(&data, None), eager.feedback);
+    }
+
+    #[test]
+    fn test_deferred_result_materializes_before_notes() {
+        let rule = StrongRule::default();
+        let eager = VoteAggregator::aggregate(
+            unanimous_pass_with_issue("SQL injection in query builder"),
+            &rule,
+            70,
+            SeverityGate::Critical,
+            DEFAULT_FINDING_SIMILARITY,
+            None,
+            "eager",
+        );
+
+        let mut deferred = VoteAggregator::aggregate_decision(
+            unanimous_pass_with_issue("SQL injection in query builder"),
+            &rule,
+            70,
+            SeverityGate::Critical,
+            DEFAULT_FINDING_SIMILARITY,
+        )
+        .into_deferred_result("deferred", None);
+        assert!(deferred.feedback.is_empty());
+        assert!(deferred.pending_feedback.is_some());
+
+        // Notas acrescentadas antes da materialização ficam depois do feedback
+        deferred.mark_flaky("eval-1", Decision::Pass);
+        deferred.materialize_feedback();
+
+        assert!(deferred.pending_feedback.is_none());
+        assert!(deferred.feedback.starts_with(&eager.feedback));
+        assert!(deferred.feedback[eager.feedback.len()..].contains("Resultado Instável"));
+
+        // Materializar de novo não duplica o feedback
+        let rendered = deferred.feedback.clone();
+        deferred.materialize_feedback();
+        assert_eq!(deferred.feedback, rendered);
+    }
+
+    #[test]
+    fn test_apply_severity_gate_never_relaxes() {
+        let finding = Finding::new(Severity::Error, "bug", "off-by-one bug");
+        let gated = vec![&finding];
+
+        assert_eq!(
+            VoteAggregator::apply_severity_gate(Decision::Block, &gated),
+            Decision::Block
+        );
+        assert_eq!(
+            VoteAggregator::apply_severity_gate(Decision::Pass, &gated),
+            Decision::Revise
+        );
+        assert_eq!(
+            VoteAggregator::apply_severity_gate(Decision::Pass, &[]),
+            Decision::Pass
+        );
+    }
+
+    fn create_adjustment(penalty: u8) -> PatternAdjustment {
+        PatternAdjustment {
+            original_score: 85,
+            penalty,
+            patterns: vec![crate::types::responses::AppliedPattern {
+                issue_category: "security".to_string(),
+                description: "SQL injection".to_string(),
+                confidence: 0.9,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_apply_pattern_adjustment_lowers_score() {
+        let mut result = EvaluationResult::success("test", 85, "ok");
+
+        VoteAggregator::apply_pattern_adjustment(&mut result, create_adjustment(5), 70);
+
+        assert_eq!(result.score, 80);
+        assert_eq!(result.decision, Decision::Pass);
+        assert_eq!(result.pattern_adjustment.as_ref().unwrap().penalty, 5);
+        assert!(result.feedback.contains("Score reduzido de 85 para 80"));
+        assert!(result.feedback.contains("[security] SQL injection"));
+
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["pattern_adjustment"]["original_score"], 85);
+    }
+
+    #[test]
+    fn test_apply_pattern_adjustment_below_min_score_revises() {
+        let mut result = EvaluationResult::success("test", 75, "ok");
+        VoteAggregator::apply_pattern_adjustment(&mut result, create_adjustment(10), 70);
+        assert_eq!(result.score, 65);
+        assert_eq!(result.decision, Decision::Revise);
+
+        let mut blocked = EvaluationResult::failure("test", 75, "bad");
+        VoteAggregator::apply_pattern_adjustment(&mut blocked, create_adjustment(10), 70);
+        assert_eq!(blocked.decision, Decision::Block);
+    }
+
+    #[test]
+    fn test_consolidate_feedback_pass() {
+        let votes: HashMap<String, ModelVote> =
+            vote_map(&[("Codex", Vote::Pass, 85), ("Gemini", Vote::Pass, 90)]);
 
         let feedback = VoteAggregator::consolidate_feedback(&votes, &Decision::Pass);
 
@@ -409,12 +1513,8 @@ mod tests {
 
     #[test]
     fn test_consolidate_feedback_block() {
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Fail, 30),
-            create_vote("Gemini", Vote::Fail, 25),
-        ]
-        .into_iter()
-        .collect();
+        let votes: HashMap<String, ModelVote> =
+            vote_map(&[("Codex", Vote::Fail, 30), ("Gemini", Vote::Fail, 25)]);
 
         let feedback = VoteAggregator::consolidate_feedback(&votes, &Decision::Block);
 
@@ -441,4 +1541,28 @@ mod tests {
             Severity::Info
         );
     }
+
+    #[test]
+    fn test_infer_category_documentation() {
+        assert_eq!(
+            VoteAggregator::infer_category("accuracy: example calls a removed function"),
+            "accuracy"
+        );
+        assert_eq!(
+            VoteAggregator::infer_category("completeness: error cases are not documented"),
+            "completeness"
+        );
+        assert_eq!(
+            VoteAggregator::infer_category("documentation: tone switches between sections"),
+            "documentation"
+        );
+        assert_eq!(
+            VoteAggregator::infer_category("Broken link to the install guide"),
+            "documentation"
+        );
+        assert_eq!(
+            VoteAggregator::infer_category("Performance: slow loop"),
+            "performance"
+        );
+    }
 }
@@ -4,12 +4,25 @@
 //! de consenso e gerenciando loops de refinamento.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
-use crate::types::config::ConsensusConfig;
-use crate::types::responses::{Decision, EvaluationResult, ModelVote};
+use serde::Serialize;
 
-use super::aggregator::VoteAggregator;
-use super::rules::{create_rule, ConsensusRule};
+use crate::types::config::{ConsensusConfig, ConsensusRule as ConsensusRuleConfig, SeverityGate};
+use crate::types::responses::{Decision, EvaluationResult, ModelVote, PatternAdjustment, Vote};
+use crate::TetradResult;
+
+use super::aggregator::{DecisionData, VoteAggregator};
+use super::feedback::FeedbackTemplate;
+use super::rules::{
+    adaptive_rule, create_rule, ConsensusRule, CustomRule, VoteWeights, DEFAULT_VOTERS,
+};
+
+/// Máximo de votos pendentes para verificar se a decisão já está definida.
+///
+/// Cada voto pendente tem 5 desfechos possíveis; acima disso a coleta
+/// simplesmente espera os votos.
+pub const MAX_SETTLE_PENDING: usize = 5;
 
 /// Motor de consenso.
 ///
@@ -20,22 +33,462 @@ use super::rules::{create_rule, ConsensusRule};
 pub struct ConsensusEngine {
     config: ConsensusConfig,
     rule: Box<dyn ConsensusRule>,
+    /// Expressões de `[consensus.custom]`, já interpretadas.
+    custom: Option<CustomRule>,
+    voters: usize,
+    /// Peso de cada executor na votação.
+    weights: VoteWeights,
+    template: Option<Arc<FeedbackTemplate>>,
+}
+
+/// Configurações efetivas do modo estrito da verificação final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StrictSettings {
+    /// Regra de consenso aplicada (sempre Golden).
+    pub rule: ConsensusRuleConfig,
+
+    /// Score mínimo efetivo: `max(min_score, strict_min_score)`.
+    pub min_score: u8,
+
+    /// Gate de severidade: findings Error revisam e Critical bloqueiam.
+    pub block_on_severity: SeverityGate,
+}
+
+/// Comparecimento de uma votação, informado por quem coletou os votos.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Turnout {
+    /// Assentos esperados na votação.
+    pub expected: usize,
+
+    /// Assentos sem voto próprio (executor indisponível ou com erro).
+    ///
+    /// Com `consensus.adaptive`, seus votos neutros são descartados e a
+    /// regra é reduzida aos executores que votaram.
+    pub absent: Vec<String>,
+
+    /// Votantes convocados quando a requisição restringe os executores.
+    ///
+    /// A regra é dimensionada para eles em vez de todos os habilitados, com
+    /// ou sem `consensus.adaptive`; `None` usa os habilitados.
+    pub voters: Option<usize>,
+}
+
+impl Turnout {
+    /// Cria o comparecimento para `expected` assentos, todos presentes.
+    pub fn new(expected: usize) -> Self {
+        Self {
+            expected,
+            absent: Vec::new(),
+            voters: None,
+        }
+    }
+
+    /// Define os assentos que ficaram sem voto próprio.
+    pub fn with_absent(mut self, absent: Vec<String>) -> Self {
+        self.absent = absent;
+        self
+    }
+
+    /// Define quantos votantes a requisição convocou.
+    pub fn with_voters(mut self, voters: usize) -> Self {
+        self.voters = Some(voters);
+        self
+    }
 }
 
 impl ConsensusEngine {
-    /// Cria um novo motor de consenso.
+    /// Cria um novo motor de consenso para os 3 executores padrão.
     pub fn new(config: ConsensusConfig) -> Self {
-        let rule = create_rule(&config.default_rule);
-        Self { config, rule }
+        Self::with_voters(config, DEFAULT_VOTERS)
+    }
+
+    /// Cria um motor de consenso para `voters` executores habilitados.
+    ///
+    /// Usa o feedback padrão; para aplicar `feedback_template`, use
+    /// [`ConsensusEngine::from_config`]. Expressões inválidas em
+    /// `[consensus.custom]` são registradas e a regra custom cai para Strong.
+    pub fn with_voters(config: ConsensusConfig, voters: usize) -> Self {
+        let custom = custom_rule(&config).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring [consensus.custom]: {}", e);
+            None
+        });
+        Self::build(config, custom, voters)
+    }
+
+    fn build(config: ConsensusConfig, custom: Option<CustomRule>, voters: usize) -> Self {
+        let weights = VoteWeights::default();
+        let rule = create_rule(&config.default_rule, custom.as_ref(), voters, &weights);
+        Self {
+            config,
+            rule,
+            custom,
+            voters,
+            weights,
+            template: None,
+        }
+    }
+
+    /// Pondera os votos com o `weight` de cada executor.
+    ///
+    /// Pesos iguais decidem como a votação sem pesos.
+    pub fn with_weights(mut self, weights: VoteWeights) -> Self {
+        self.rule = create_rule(
+            &self.config.default_rule,
+            self.custom.as_ref(),
+            self.voters,
+            &weights,
+        );
+        self.weights = weights;
+        self
+    }
+
+    /// Cria um motor de consenso carregando o template de feedback configurado.
+    ///
+    /// Falha se o template não puder ser lido ou contiver erros, ou se as
+    /// expressões de `[consensus.custom]` forem inválidas.
+    pub fn from_config(config: ConsensusConfig, voters: usize) -> TetradResult<Self> {
+        let template = FeedbackTemplate::from_config(&config)?;
+        let custom = custom_rule(&config)?;
+        let engine = Self::build(config, custom, voters);
+        Ok(match template {
+            Some(template) => engine.with_template(template),
+            None => engine,
+        })
+    }
+
+    /// Define o template usado para renderizar o feedback.
+    pub fn with_template(mut self, template: FeedbackTemplate) -> Self {
+        self.template = Some(Arc::new(template));
+        self
     }
 
     /// Avalia os votos e retorna o resultado.
+    ///
+    /// Espera um voto por executor habilitado; com `consensus.adaptive`,
+    /// menos votos reduzem a regra (veja [`ConsensusEngine::evaluate_with_turnout`]).
     pub fn evaluate(
         &self,
         votes: HashMap<String, ModelVote>,
         request_id: &str,
     ) -> EvaluationResult {
-        VoteAggregator::aggregate(votes, self.rule.as_ref(), self.config.min_score, request_id)
+        self.evaluate_with_turnout(votes, request_id, &Turnout::new(self.voters))
+    }
+
+    /// Avalia os votos considerando quantos executores eram esperados e
+    /// quais ficaram sem votar.
+    ///
+    /// Com `consensus.adaptive`, a regra é escolhida pelos votos recebidos:
+    /// 1 votante decide sozinho, 2 precisam concordar e 3 ou mais usam a
+    /// regra configurada. O nome da regra aplicada fica em `effective_rule`
+    /// e a origem das configurações do dial de rigor em `strictness`.
+    pub fn evaluate_with_turnout(
+        &self,
+        votes: HashMap<String, ModelVote>,
+        request_id: &str,
+        turnout: &Turnout,
+    ) -> EvaluationResult {
+        let (votes, adapted) = self.adapt(votes, turnout);
+        let mut result = VoteAggregator::aggregate(
+            votes,
+            adapted.as_deref().unwrap_or(self.rule.as_ref()),
+            self.config.min_score,
+            self.config.block_on_severity,
+            self.config.finding_similarity,
+            self.template.as_deref(),
+            request_id,
+        );
+        result.strictness = self.config.strictness_trace.clone();
+        self.apply_confidence_gate(&mut result);
+        result
+    }
+
+    /// Agrega os votos sem renderizar o feedback.
+    ///
+    /// Um Pass abaixo de `consensus.min_confidence` já sai como Revise.
+    pub fn decide(&self, votes: HashMap<String, ModelVote>) -> DecisionData {
+        let (votes, adapted) = self.adapt(votes, &Turnout::new(self.voters));
+        let mut data = VoteAggregator::aggregate_decision(
+            votes,
+            adapted.as_deref().unwrap_or(self.rule.as_ref()),
+            self.config.min_score,
+            self.config.block_on_severity,
+            self.config.finding_similarity,
+        );
+        if data.decision == Decision::Pass
+            && self
+                .config
+                .min_confidence
+                .is_some_and(|min| data.confidence < min)
+        {
+            data.decision = Decision::Revise;
+        }
+        data
+    }
+
+    /// Decisão que os votos pendentes não podem mais mudar.
+    ///
+    /// `votes` e `turnout.absent` descrevem os assentos que já terminaram;
+    /// `pending` são os que ainda não votaram. Cada pendente pode aprovar com
+    /// score 0 ou 100, alertar, reprovar ou ficar ausente, e pode reportar
+    /// findings de qualquer severidade para o gate. Retorna a decisão e se há
+    /// consenso quando todos os desfechos coincidem, ou `None` caso
+    /// contrário (ou com mais de [`MAX_SETTLE_PENDING`] pendentes).
+    ///
+    /// A regra custom nunca é dada como definida: suas expressões podem
+    /// comparar scores intermediários, que os desfechos acima não cobrem.
+    pub fn settled_decision(
+        &self,
+        votes: &HashMap<String, ModelVote>,
+        turnout: &Turnout,
+        pending: &[&str],
+    ) -> Option<(Decision, bool)> {
+        if pending.is_empty()
+            || pending.len() > MAX_SETTLE_PENDING
+            || self.config.default_rule == ConsensusRuleConfig::Custom
+        {
+            return None;
+        }
+
+        let findings = VoteAggregator::extract_findings(votes, self.config.finding_similarity);
+        let gated = VoteAggregator::gated_findings(&findings, self.config.block_on_severity);
+        // Findings ainda não reportados podem acionar o gate
+        let forced: &[Decision] = match self.config.block_on_severity {
+            SeverityGate::Off => &[],
+            SeverityGate::Critical => &[Decision::Block],
+            SeverityGate::Error => &[Decision::Revise, Decision::Block],
+        };
+
+        let mut settled: Option<(Decision, bool)> = None;
+        let outcomes = PENDING_OUTCOMES.len().pow(pending.len() as u32);
+        for combination in 0..outcomes {
+            let mut votes = votes.clone();
+            let mut turnout = turnout.clone();
+            let mut rest = combination;
+            for name in pending {
+                let (vote, score, absent) = PENDING_OUTCOMES[rest % PENDING_OUTCOMES.len()];
+                rest /= PENDING_OUTCOMES.len();
+                votes.insert(name.to_string(), ModelVote::new(*name, vote, score));
+                if absent {
+                    turnout.absent.push(name.to_string());
+                }
+            }
+
+            let (votes, adapted) = self.adapt(votes, &turnout);
+            let rule = adapted.as_deref().unwrap_or(self.rule.as_ref());
+            let decision = rule.evaluate(&votes, self.config.min_score);
+            let consensus = rule.is_consensus_achieved(&votes, self.config.min_score);
+            let decision = VoteAggregator::apply_severity_gate(decision, &gated);
+
+            let finals = std::iter::once(decision)
+                .chain(forced.iter().map(|forced| harsher(decision, *forced)));
+            for decision in finals {
+                match settled {
+                    None => settled = Some((decision, consensus)),
+                    Some(fixed) if fixed == (decision, consensus) => {}
+                    Some(_) => return None,
+                }
+            }
+        }
+        // A confiança de um Pass depende dos scores que ainda não chegaram
+        if self.config.min_confidence.is_some()
+            && settled.is_some_and(|(decision, _)| decision == Decision::Pass)
+        {
+            return None;
+        }
+        settled
+    }
+
+    /// Avalia uma votação encerrada antes do fim com a decisão já definida
+    /// por [`ConsensusEngine::settled_decision`].
+    ///
+    /// O score e os findings vêm só dos votos recebidos.
+    pub fn evaluate_settled(
+        &self,
+        votes: HashMap<String, ModelVote>,
+        request_id: &str,
+        turnout: &Turnout,
+        (decision, consensus_achieved): (Decision, bool),
+    ) -> EvaluationResult {
+        let votes = if self.config.adaptive {
+            votes
+                .into_iter()
+                .filter(|(name, _)| !turnout.absent.contains(name))
+                .collect()
+        } else {
+            votes
+        };
+        let resized = self.resized(turnout);
+        let mut data = VoteAggregator::aggregate_decision(
+            votes,
+            resized.as_deref().unwrap_or(self.rule.as_ref()),
+            self.config.min_score,
+            self.config.block_on_severity,
+            self.config.finding_similarity,
+        );
+        if data.decision != decision {
+            // A regra viu só os votos recebidos; quem decidiu foi o conjunto
+            // de desfechos possíveis dos pendentes
+            let detail = format!(
+                "decision settled before every executor voted: no outcome of the {} pending votes could change it",
+                turnout.expected.saturating_sub(data.votes.len())
+            );
+            data.explanation = data.explanation.decided(decision, "settled_early", detail);
+        }
+        data.decision = decision;
+        data.consensus_achieved = consensus_achieved;
+        data.confidence = VoteAggregator::calculate_confidence(
+            &data.votes,
+            data.score,
+            self.config.min_score,
+            consensus_achieved,
+        );
+        let feedback = self.render_feedback(&data);
+        data.into_result(request_id, feedback)
+    }
+
+    /// Escolhe a regra adaptativa para o comparecimento, descartando os
+    /// votos neutros dos ausentes.
+    ///
+    /// Retorna `None` quando vale a regra configurada; nesse caso os votos
+    /// seguem intactos.
+    fn adapt(
+        &self,
+        votes: HashMap<String, ModelVote>,
+        turnout: &Turnout,
+    ) -> (HashMap<String, ModelVote>, Option<Box<dyn ConsensusRule>>) {
+        if !self.config.adaptive {
+            return (votes, self.resized(turnout));
+        }
+
+        let voted = votes
+            .keys()
+            .filter(|name| !turnout.absent.contains(name))
+            .count();
+        let expected = turnout.expected.max(self.voters_for(turnout));
+        match adaptive_rule(
+            &self.config.default_rule,
+            self.custom.as_ref(),
+            expected,
+            voted,
+            &self.weights,
+        ) {
+            Some(rule) => {
+                let present = votes
+                    .into_iter()
+                    .filter(|(name, _)| !turnout.absent.contains(name))
+                    .collect();
+                (present, Some(rule))
+            }
+            None => (votes, self.resized(turnout)),
+        }
+    }
+
+    /// Votantes esperados: os convocados pela requisição ou os habilitados.
+    fn voters_for(&self, turnout: &Turnout) -> usize {
+        turnout.voters.unwrap_or(self.voters)
+    }
+
+    /// Regra configurada dimensionada para os votantes convocados, quando
+    /// a requisição restringiu os executores.
+    fn resized(&self, turnout: &Turnout) -> Option<Box<dyn ConsensusRule>> {
+        let voters = self.voters_for(turnout);
+        (voters != self.voters).then(|| {
+            create_rule(
+                &self.config.default_rule,
+                self.custom.as_ref(),
+                voters,
+                &self.weights,
+            )
+        })
+    }
+
+    /// Renderiza o feedback de uma decisão com o template configurado.
+    pub fn render_feedback(&self, data: &DecisionData) -> String {
+        VoteAggregator::render_feedback(data, self.template.as_deref())
+    }
+
+    /// Avalia os votos adiando a renderização do feedback.
+    ///
+    /// O resultado tem a mesma decisão de [`ConsensusEngine::evaluate`]; o
+    /// feedback só é gerado por `EvaluationResult::materialize_feedback`.
+    pub fn evaluate_deferred(
+        &self,
+        votes: HashMap<String, ModelVote>,
+        request_id: &str,
+    ) -> EvaluationResult {
+        let (votes, adapted) = self.adapt(votes, &Turnout::new(self.voters));
+        let data = VoteAggregator::aggregate_decision(
+            votes,
+            adapted.as_deref().unwrap_or(self.rule.as_ref()),
+            self.config.min_score,
+            self.config.block_on_severity,
+            self.config.finding_similarity,
+        );
+        let mut result = data.into_deferred_result(request_id, self.template.clone());
+        self.apply_confidence_gate(&mut result);
+        result
+    }
+
+    /// Resolve o modo estrito para uma requisição.
+    ///
+    /// O parâmetro explícito da requisição vence `strict_final_check`;
+    /// retorna `None` quando o modo estrito não se aplica.
+    pub fn strict_settings(&self, requested: Option<bool>) -> Option<StrictSettings> {
+        if !requested.unwrap_or(self.config.strict_final_check) {
+            return None;
+        }
+
+        Some(StrictSettings {
+            rule: ConsensusRuleConfig::Golden,
+            min_score: self.config.min_score.max(self.config.strict_min_score),
+            block_on_severity: SeverityGate::Error,
+        })
+    }
+
+    /// Avalia os votos com as configurações do modo estrito.
+    ///
+    /// Ignora a regra, o score mínimo e o gate de severidade configurados.
+    pub fn evaluate_strict(
+        &self,
+        votes: HashMap<String, ModelVote>,
+        request_id: &str,
+        strict: &StrictSettings,
+    ) -> EvaluationResult {
+        self.evaluate_strict_with_turnout(votes, request_id, strict, &Turnout::new(self.voters))
+    }
+
+    /// Como [`ConsensusEngine::evaluate_strict`], com a regra dimensionada
+    /// para os votantes convocados em `turnout`.
+    ///
+    /// O modo estrito nunca é adaptado aos ausentes.
+    pub fn evaluate_strict_with_turnout(
+        &self,
+        votes: HashMap<String, ModelVote>,
+        request_id: &str,
+        strict: &StrictSettings,
+        turnout: &Turnout,
+    ) -> EvaluationResult {
+        let rule = create_rule(&strict.rule, None, self.voters_for(turnout), &self.weights);
+        let mut result = VoteAggregator::aggregate(
+            votes,
+            rule.as_ref(),
+            strict.min_score,
+            strict.block_on_severity,
+            self.config.finding_similarity,
+            self.template.as_deref(),
+            request_id,
+        );
+        self.apply_confidence_gate(&mut result);
+        result
+    }
+
+    /// Aplica ao resultado a penalidade de anti-patterns conhecidos.
+    pub fn apply_pattern_adjustment(
+        &self,
+        result: &mut EvaluationResult,
+        adjustment: PatternAdjustment,
+    ) {
+        VoteAggregator::apply_pattern_adjustment(result, adjustment, self.config.min_score);
     }
 
     /// Verifica se o consenso foi alcançado.
@@ -89,37 +542,51 @@ impl ConsensusEngine {
     /// Baseado em:
     /// - Unanimidade dos votos
     /// - Score médio vs min_score
-    /// - Consistência dos reasonings
+    /// - Consenso alcançado
     pub fn calculate_confidence(&self, result: &EvaluationResult) -> f64 {
-        if result.votes.is_empty() {
-            return 0.0;
-        }
-
-        let mut confidence = 0.0;
+        VoteAggregator::calculate_confidence(
+            &result.votes,
+            result.score,
+            self.config.min_score,
+            result.consensus_achieved,
+        )
+    }
 
-        // Fator 1: Unanimidade (até 0.4)
-        let pass_count = result
-            .votes
-            .values()
-            .filter(|v| v.vote == crate::types::responses::Vote::Pass)
-            .count();
-        let unanimity = pass_count as f64 / result.votes.len() as f64;
-        confidence += unanimity * 0.4;
+    /// Rebaixa para Revise um Pass abaixo de `consensus.min_confidence`.
+    fn apply_confidence_gate(&self, result: &mut EvaluationResult) {
+        if let Some(min_confidence) = self.config.min_confidence {
+            VoteAggregator::apply_confidence_gate(result, min_confidence);
+        }
+    }
+}
 
-        // Fator 2: Score vs min_score (até 0.3)
-        let score_factor = if result.score >= self.config.min_score {
-            (result.score - self.config.min_score) as f64 / (100 - self.config.min_score) as f64
-        } else {
-            0.0
-        };
-        confidence += score_factor * 0.3;
+/// Desfechos possíveis de um voto pendente: voto, score e se fica ausente.
+///
+/// Os extremos de score bastam, já que as regras comparam médias com
+/// `min_score`.
+const PENDING_OUTCOMES: [(Vote, u8, bool); 5] = [
+    (Vote::Pass, 100, false),
+    (Vote::Pass, 0, false),
+    (Vote::Warn, 50, false),
+    (Vote::Fail, 0, false),
+    (Vote::Warn, 50, true),
+];
 
-        // Fator 3: Consenso alcançado (até 0.3)
-        if result.consensus_achieved {
-            confidence += 0.3;
-        }
+/// Interpreta as expressões de `[consensus.custom]`, se houver.
+fn custom_rule(config: &ConsensusConfig) -> TetradResult<Option<CustomRule>> {
+    config
+        .custom
+        .as_ref()
+        .map(CustomRule::from_config)
+        .transpose()
+}
 
-        confidence.min(1.0)
+/// A mais severa de duas decisões.
+fn harsher(a: Decision, b: Decision) -> Decision {
+    match (a, b) {
+        (Decision::Block, _) | (_, Decision::Block) => Decision::Block,
+        (Decision::Revise, _) | (_, Decision::Revise) => Decision::Revise,
+        _ => Decision::Pass,
     }
 }
 
@@ -132,7 +599,8 @@ impl Default for ConsensusEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::config::ConsensusRule as ConsensusRuleConfig;
+    use crate::test_support::vote_map;
+    use crate::types::config::{ConsensusRule as ConsensusRuleConfig, CustomRuleConfig};
     use crate::types::responses::Vote;
 
     fn create_vote(name: &str, vote: Vote, score: u8) -> (String, ModelVote) {
@@ -144,6 +612,7 @@ mod tests {
             default_rule: rule,
             min_score,
             max_loops,
+            ..Default::default()
         }
     }
 
@@ -162,13 +631,11 @@ mod tests {
         let config = create_config(ConsensusRuleConfig::Strong, 70, 3);
         let engine = ConsensusEngine::new(config);
 
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Pass, 85),
-            create_vote("Gemini", Vote::Pass, 90),
-            create_vote("Qwen", Vote::Pass, 88),
-        ]
-        .into_iter()
-        .collect();
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Pass, 90),
+            ("Qwen", Vote::Pass, 88),
+        ]);
 
         let result = engine.evaluate(votes, "test-123");
 
@@ -181,13 +648,11 @@ mod tests {
         let config = create_config(ConsensusRuleConfig::Strong, 70, 3);
         let engine = ConsensusEngine::new(config);
 
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Fail, 30),
-            create_vote("Gemini", Vote::Fail, 25),
-            create_vote("Qwen", Vote::Fail, 20),
-        ]
-        .into_iter()
-        .collect();
+        let votes = vote_map(&[
+            ("Codex", Vote::Fail, 30),
+            ("Gemini", Vote::Fail, 25),
+            ("Qwen", Vote::Fail, 20),
+        ]);
 
         let result = engine.evaluate(votes, "test-123");
 
@@ -211,13 +676,11 @@ mod tests {
         let config = create_config(ConsensusRuleConfig::Strong, 70, 3);
         let engine = ConsensusEngine::new(config);
 
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Pass, 95),
-            create_vote("Gemini", Vote::Pass, 98),
-            create_vote("Qwen", Vote::Pass, 97),
-        ]
-        .into_iter()
-        .collect();
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 95),
+            ("Gemini", Vote::Pass, 98),
+            ("Qwen", Vote::Pass, 97),
+        ]);
 
         let result = engine.evaluate(votes, "test-123");
         let confidence = engine.calculate_confidence(&result);
@@ -230,13 +693,11 @@ mod tests {
         let config = create_config(ConsensusRuleConfig::Strong, 70, 3);
         let engine = ConsensusEngine::new(config);
 
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Pass, 72),
-            create_vote("Gemini", Vote::Warn, 65),
-            create_vote("Qwen", Vote::Fail, 40),
-        ]
-        .into_iter()
-        .collect();
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 72),
+            ("Gemini", Vote::Warn, 65),
+            ("Qwen", Vote::Fail, 40),
+        ]);
 
         let result = engine.evaluate(votes, "test-123");
         let confidence = engine.calculate_confidence(&result);
@@ -252,13 +713,11 @@ mod tests {
         assert_eq!(engine.rule_name(), "golden");
 
         // Com Golden rule, mesmo um WARN bloqueia
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Pass, 85),
-            create_vote("Gemini", Vote::Warn, 75),
-            create_vote("Qwen", Vote::Pass, 88),
-        ]
-        .into_iter()
-        .collect();
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Warn, 75),
+            ("Qwen", Vote::Pass, 88),
+        ]);
 
         let result = engine.evaluate(votes, "test-123");
         assert_eq!(result.decision, Decision::Revise);
@@ -272,15 +731,529 @@ mod tests {
         assert_eq!(engine.rule_name(), "weak");
 
         // Com Weak rule, 2 PASS são suficientes
-        let votes: HashMap<String, ModelVote> = vec![
-            create_vote("Codex", Vote::Pass, 85),
-            create_vote("Gemini", Vote::Pass, 80),
-            create_vote("Qwen", Vote::Fail, 30),
-        ]
-        .into_iter()
-        .collect();
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Pass, 80),
+            ("Qwen", Vote::Fail, 30),
+        ]);
 
         let result = engine.evaluate(votes, "test-123");
         assert_eq!(result.decision, Decision::Pass);
     }
+
+    #[test]
+    fn test_strict_settings_precedence() {
+        let weak = create_config(ConsensusRuleConfig::Weak, 70, 3);
+        let strict_by_default = ConsensusConfig {
+            strict_final_check: true,
+            ..weak.clone()
+        };
+
+        // Sem parâmetro explícito, vale a configuração
+        assert!(ConsensusEngine::new(weak.clone())
+            .strict_settings(None)
+            .is_none());
+        assert!(ConsensusEngine::new(strict_by_default.clone())
+            .strict_settings(None)
+            .is_some());
+
+        // O parâmetro explícito da requisição sempre vence
+        assert!(ConsensusEngine::new(strict_by_default)
+            .strict_settings(Some(false))
+            .is_none());
+        let strict = ConsensusEngine::new(weak)
+            .strict_settings(Some(true))
+            .unwrap();
+        assert_eq!(strict.rule, ConsensusRuleConfig::Golden);
+        assert_eq!(strict.min_score, 85);
+        assert_eq!(strict.block_on_severity, SeverityGate::Error);
+    }
+
+    #[test]
+    fn test_strict_min_score_never_lowers_config() {
+        let engine = ConsensusEngine::new(create_config(ConsensusRuleConfig::Weak, 90, 3));
+        assert_eq!(engine.strict_settings(Some(true)).unwrap().min_score, 90);
+
+        let engine = ConsensusEngine::new(ConsensusConfig {
+            strict_min_score: 95,
+            ..create_config(ConsensusRuleConfig::Weak, 70, 3)
+        });
+        assert_eq!(engine.strict_settings(Some(true)).unwrap().min_score, 95);
+    }
+
+    #[test]
+    fn test_result_traces_strictness_sources() {
+        let mut config = ConsensusConfig {
+            strictness: Some(1),
+            ..Default::default()
+        };
+        config.resolve_strictness(&["min_score"]);
+        let engine = ConsensusEngine::new(config);
+
+        let votes = vote_map(&[("Codex", Vote::Pass, 90)]);
+        let result = engine.evaluate(votes, "strictness-1");
+
+        let trace = result.strictness.expect("strictness trace");
+        assert_eq!(trace.level, 1);
+        assert_eq!(trace.settings[0].value, serde_json::json!("weak"));
+        assert_eq!(
+            trace.settings[1].source,
+            crate::types::config::SettingSource::Explicit
+        );
+
+        // Sem o dial, o resultado não traz o trace
+        let plain = ConsensusEngine::new(ConsensusConfig::default());
+        let votes = vote_map(&[("Codex", Vote::Pass, 90)]);
+        assert!(plain.evaluate(votes, "plain").strictness.is_none());
+    }
+
+    #[test]
+    fn test_evaluate_strict_overrides_rule_and_severity() {
+        let engine = ConsensusEngine::new(ConsensusConfig {
+            block_on_severity: SeverityGate::Off,
+            ..create_config(ConsensusRuleConfig::Weak, 70, 3)
+        });
+        let strict = engine.strict_settings(Some(true)).unwrap();
+
+        // 2 de 3 passam na Weak, mas não na Golden
+        let split = || -> HashMap<String, ModelVote> {
+            vote_map(&[
+                ("Codex", Vote::Pass, 90),
+                ("Gemini", Vote::Pass, 90),
+                ("Qwen", Vote::Warn, 80),
+            ])
+        };
+        assert_eq!(engine.evaluate(split(), "weak").decision, Decision::Pass);
+        assert_ne!(
+            engine.evaluate_strict(split(), "strict", &strict).decision,
+            Decision::Pass
+        );
+
+        // Unanimidade com score abaixo do mínimo estrito não passa
+        let unanimous: HashMap<String, ModelVote> = vote_map(&[
+            ("Codex", Vote::Pass, 80),
+            ("Gemini", Vote::Pass, 80),
+            ("Qwen", Vote::Pass, 80),
+        ]);
+        assert_ne!(
+            engine
+                .evaluate_strict(unanimous, "strict-score", &strict)
+                .decision,
+            Decision::Pass
+        );
+
+        // Findings Error forçam revisão mesmo com o gate desligado na config
+        let with_error: HashMap<String, ModelVote> = ["Codex", "Gemini", "Qwen"]
+            .into_iter()
+            .map(|name| {
+                let (name, vote) = create_vote(name, Vote::Pass, 95);
+                (
+                    name,
+                    vote.with_issues(vec!["Error: unchecked index may panic".to_string()]),
+                )
+            })
+            .collect();
+        let result = engine.evaluate_strict(with_error, "strict-gate", &strict);
+        assert_eq!(result.decision, Decision::Revise);
+    }
+
+    #[test]
+    fn test_adaptive_single_voter_under_each_rule() {
+        for rule in [
+            ConsensusRuleConfig::Golden,
+            ConsensusRuleConfig::Strong,
+            ConsensusRuleConfig::Weak,
+        ] {
+            let engine = ConsensusEngine::new(create_config(rule, 70, 3));
+
+            let result = engine.evaluate(vote_map(&[("Codex", Vote::Pass, 85)]), "single");
+            assert_eq!(result.decision, Decision::Pass, "{:?}", rule);
+            assert!(result.consensus_achieved);
+            assert_eq!(result.effective_rule.as_deref(), Some("single-evaluator"));
+
+            // O gate de score mínimo continua valendo
+            let result = engine.evaluate(vote_map(&[("Codex", Vote::Pass, 60)]), "low");
+            assert_eq!(result.decision, Decision::Revise, "{:?}", rule);
+
+            let result = engine.evaluate(vote_map(&[("Codex", Vote::Fail, 30)]), "fail");
+            assert_eq!(result.decision, Decision::Block, "{:?}", rule);
+        }
+    }
+
+    #[test]
+    fn test_adaptive_two_voters_under_each_rule() {
+        for rule in [
+            ConsensusRuleConfig::Golden,
+            ConsensusRuleConfig::Strong,
+            ConsensusRuleConfig::Weak,
+        ] {
+            let engine = ConsensusEngine::new(create_config(rule, 70, 3));
+
+            let agree = vote_map(&[("Codex", Vote::Pass, 85), ("Gemini", Vote::Pass, 80)]);
+            let result = engine.evaluate(agree, "agree");
+            assert_eq!(result.decision, Decision::Pass, "{:?}", rule);
+            assert_eq!(result.effective_rule.as_deref(), Some("dual-evaluator"));
+
+            let disagree = vote_map(&[("Codex", Vote::Pass, 85), ("Gemini", Vote::Fail, 30)]);
+            let result = engine.evaluate(disagree, "disagree");
+            assert_eq!(result.decision, Decision::Revise, "{:?}", rule);
+            assert!(!result.consensus_achieved);
+        }
+    }
+
+    #[test]
+    fn test_adaptive_three_voters_keep_configured_rule() {
+        let split = || {
+            vote_map(&[
+                ("Codex", Vote::Pass, 85),
+                ("Gemini", Vote::Pass, 80),
+                ("Qwen", Vote::Warn, 65),
+            ])
+        };
+        for (rule, expected) in [
+            (ConsensusRuleConfig::Golden, Decision::Revise),
+            (ConsensusRuleConfig::Strong, Decision::Revise),
+            (ConsensusRuleConfig::Weak, Decision::Pass),
+        ] {
+            let engine = ConsensusEngine::new(create_config(rule, 70, 3));
+            let result = engine.evaluate(split(), "three");
+            assert_eq!(result.decision, expected, "{:?}", rule);
+            assert_eq!(result.effective_rule.as_deref(), Some(engine.rule_name()));
+        }
+    }
+
+    #[test]
+    fn test_adaptive_drops_absent_neutral_votes() {
+        let engine = ConsensusEngine::new(create_config(ConsensusRuleConfig::Strong, 70, 3));
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 90),
+            ("Gemini", Vote::Warn, 50),
+            ("Qwen", Vote::Warn, 50),
+        ]);
+        let turnout = Turnout::new(3).with_absent(vec!["Gemini".to_string(), "Qwen".to_string()]);
+
+        let result = engine.evaluate_with_turnout(votes, "absent", &turnout);
+        assert_eq!(result.decision, Decision::Pass);
+        assert_eq!(result.effective_rule.as_deref(), Some("single-evaluator"));
+        assert_eq!(result.votes.len(), 1);
+        assert_eq!(result.score, 90);
+    }
+
+    #[test]
+    fn test_adaptive_disabled_keeps_configured_rule() {
+        let engine = ConsensusEngine::new(ConsensusConfig {
+            adaptive: false,
+            ..create_config(ConsensusRuleConfig::Strong, 70, 3)
+        });
+
+        let result = engine.evaluate(vote_map(&[("Codex", Vote::Pass, 90)]), "strict-count");
+        assert_eq!(result.decision, Decision::Revise);
+        assert_eq!(result.effective_rule.as_deref(), Some("strong"));
+
+        // Votos neutros de ausentes continuam contando
+        let votes = vote_map(&[("Codex", Vote::Pass, 90), ("Gemini", Vote::Warn, 50)]);
+        let turnout = Turnout::new(2).with_absent(vec!["Gemini".to_string()]);
+        let result = engine.evaluate_with_turnout(votes, "neutral", &turnout);
+        assert_eq!(result.votes.len(), 2);
+    }
+
+    fn settle_config(rule: ConsensusRuleConfig, gate: SeverityGate) -> ConsensusConfig {
+        ConsensusConfig {
+            block_on_severity: gate,
+            adaptive: false,
+            ..create_config(rule, 70, 3)
+        }
+    }
+
+    fn custom_config(pass: &str, block: &str) -> ConsensusConfig {
+        ConsensusConfig {
+            custom: Some(CustomRuleConfig {
+                pass: pass.to_string(),
+                block: block.to_string(),
+            }),
+            ..settle_config(ConsensusRuleConfig::Custom, SeverityGate::Off)
+        }
+    }
+
+    #[test]
+    fn test_custom_rule_decides() {
+        let engine = ConsensusEngine::from_config(
+            custom_config("pass_count >= 2 && avg_score >= 80", "fail_count >= 1"),
+            3,
+        )
+        .unwrap();
+
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 90),
+            ("Gemini", Vote::Pass, 85),
+            ("Qwen", Vote::Warn, 70),
+        ]);
+        let result = engine.evaluate(votes, "custom-pass");
+        assert_eq!(result.decision, Decision::Pass);
+        assert!(result.consensus_achieved);
+
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 95),
+            ("Gemini", Vote::Pass, 95),
+            ("Qwen", Vote::Fail, 85),
+        ]);
+        assert_eq!(
+            engine.evaluate(votes, "custom-block").decision,
+            Decision::Block
+        );
+    }
+
+    #[test]
+    fn test_custom_rule_invalid_expression() {
+        let config = custom_config("pass_count >= ", "");
+        assert!(ConsensusEngine::from_config(config.clone(), 3).is_err());
+
+        // Sem from_config, a regra cai para Strong
+        let engine = ConsensusEngine::with_voters(config, 3);
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 90),
+            ("Gemini", Vote::Pass, 85),
+            ("Qwen", Vote::Warn, 70),
+        ]);
+        assert_eq!(
+            engine.evaluate(votes, "fallback").decision,
+            Decision::Revise
+        );
+    }
+
+    #[test]
+    fn test_settled_decision_never_settles_custom_rule() {
+        let engine = ConsensusEngine::new(custom_config("avg_score == 80", "fail_count >= 1"));
+        let votes = vote_map(&[("Codex", Vote::Fail, 20)]);
+
+        assert_eq!(
+            engine.settled_decision(&votes, &Turnout::new(3), &["Gemini", "Qwen"]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_settled_decision_golden_fail() {
+        let engine = ConsensusEngine::new(settle_config(
+            ConsensusRuleConfig::Golden,
+            SeverityGate::Critical,
+        ));
+        let votes = vote_map(&[("Codex", Vote::Fail, 20)]);
+
+        assert_eq!(
+            engine.settled_decision(&votes, &Turnout::new(3), &["Gemini", "Qwen"]),
+            Some((Decision::Block, false))
+        );
+    }
+
+    #[test]
+    fn test_settled_decision_open_while_votes_matter() {
+        let engine = ConsensusEngine::new(settle_config(
+            ConsensusRuleConfig::Golden,
+            SeverityGate::Off,
+        ));
+
+        // Dois PASS ainda dependem do terceiro voto
+        let votes = vote_map(&[("Codex", Vote::Pass, 90), ("Gemini", Vote::Pass, 90)]);
+        assert_eq!(
+            engine.settled_decision(&votes, &Turnout::new(3), &["Qwen"]),
+            None
+        );
+
+        // Sem votos pendentes não há o que antecipar
+        assert_eq!(engine.settled_decision(&votes, &Turnout::new(2), &[]), None);
+    }
+
+    #[test]
+    fn test_settled_decision_considers_severity_gate() {
+        let votes = vote_map(&[("Codex", Vote::Pass, 90), ("Gemini", Vote::Fail, 30)]);
+        let pending = ["Qwen"];
+
+        // REVISE só fica definido se nenhum finding pendente puder bloquear
+        let engine = ConsensusEngine::new(settle_config(
+            ConsensusRuleConfig::Strong,
+            SeverityGate::Off,
+        ));
+        assert_eq!(
+            engine.settled_decision(&votes, &Turnout::new(3), &pending),
+            Some((Decision::Revise, false))
+        );
+
+        let engine = ConsensusEngine::new(settle_config(
+            ConsensusRuleConfig::Strong,
+            SeverityGate::Critical,
+        ));
+        assert_eq!(
+            engine.settled_decision(&votes, &Turnout::new(3), &pending),
+            None
+        );
+    }
+
+    #[test]
+    fn test_settled_decision_considers_absent_seats() {
+        let votes = vote_map(&[("Codex", Vote::Fail, 20)]);
+        let pending = ["Gemini", "Qwen"];
+
+        // Com a regra adaptativa, um ausente reduz a votação a dual-evaluator
+        let engine = ConsensusEngine::new(ConsensusConfig {
+            adaptive: true,
+            ..settle_config(ConsensusRuleConfig::Golden, SeverityGate::Critical)
+        });
+        assert_eq!(
+            engine.settled_decision(&votes, &Turnout::new(3), &pending),
+            None
+        );
+    }
+
+    #[test]
+    fn test_settled_decision_limits_pending_votes() {
+        let engine = ConsensusEngine::with_voters(
+            settle_config(ConsensusRuleConfig::Golden, SeverityGate::Critical),
+            MAX_SETTLE_PENDING + 2,
+        );
+        let votes = vote_map(&[("A", Vote::Fail, 20)]);
+        let names: Vec<String> = (0..=MAX_SETTLE_PENDING)
+            .map(|i| format!("E{}", i))
+            .collect();
+        let pending: Vec<&str> = names.iter().map(String::as_str).collect();
+
+        assert_eq!(
+            engine.settled_decision(&votes, &Turnout::new(MAX_SETTLE_PENDING + 2), &pending),
+            None
+        );
+    }
+
+    #[test]
+    fn test_evaluate_settled_scores_received_votes() {
+        let engine = ConsensusEngine::new(settle_config(
+            ConsensusRuleConfig::Golden,
+            SeverityGate::Critical,
+        ));
+        let votes = vote_map(&[("Codex", Vote::Fail, 20)]);
+
+        let result =
+            engine.evaluate_settled(votes, "settled", &Turnout::new(3), (Decision::Block, false));
+        assert_eq!(result.decision, Decision::Block);
+        assert!(!result.consensus_achieved);
+        assert_eq!(result.score, 20);
+        assert_eq!(result.votes.len(), 1);
+        assert_eq!(result.effective_rule.as_deref(), Some("golden"));
+    }
+
+    #[test]
+    fn test_requested_voters_size_the_rule() {
+        let engine = ConsensusEngine::new(ConsensusConfig {
+            adaptive: false,
+            ..create_config(ConsensusRuleConfig::Strong, 70, 3)
+        });
+        let votes = || vote_map(&[("Codex", Vote::Pass, 90), ("Qwen", Vote::Pass, 90)]);
+
+        // Sem a contagem pedida, a regra Strong espera os 3 habilitados
+        let result = engine.evaluate_with_turnout(votes(), "all", &Turnout::new(2));
+        assert_eq!(result.decision, Decision::Revise);
+
+        let turnout = Turnout::new(2).with_voters(2);
+        let result = engine.evaluate_with_turnout(votes(), "two", &turnout);
+        assert_eq!(result.decision, Decision::Pass);
+        assert!(result.consensus_achieved);
+        assert_eq!(result.effective_rule.as_deref(), Some("strong"));
+
+        let strict = engine.strict_settings(Some(true)).unwrap();
+        let result = engine.evaluate_strict_with_turnout(votes(), "strict", &strict, &turnout);
+        assert_eq!(result.decision, Decision::Pass);
+    }
+
+    #[test]
+    fn test_low_confidence_pass_is_downgraded() {
+        let engine = ConsensusEngine::new(ConsensusConfig {
+            min_confidence: Some(0.8),
+            ..create_config(ConsensusRuleConfig::Strong, 70, 3)
+        });
+        let spread = || {
+            vote_map(&[
+                ("Codex", Vote::Pass, 95),
+                ("Gemini", Vote::Pass, 55),
+                ("Qwen", Vote::Pass, 72),
+            ])
+        };
+
+        let result = engine.evaluate(spread(), "spread");
+        assert!(result.consensus_achieved);
+        assert!(result.confidence < 0.8);
+        assert_eq!(result.decision, Decision::Revise);
+        assert!(result.low_confidence);
+        assert!(result.feedback.contains("Baixa Confiança"));
+        assert!(result.feedback.contains("Gemini 55"));
+
+        // O caminho sem feedback renderizado decide igual
+        assert_eq!(engine.decide(spread()).decision, Decision::Revise);
+        let deferred = engine
+            .evaluate_deferred(spread(), "deferred")
+            .with_materialized_feedback();
+        assert_eq!(deferred.decision, Decision::Revise);
+        assert!(deferred.feedback.contains("Baixa Confiança"));
+
+        // Votos próximos e altos passam
+        let result = engine.evaluate(
+            vote_map(&[
+                ("Codex", Vote::Pass, 95),
+                ("Gemini", Vote::Pass, 98),
+                ("Qwen", Vote::Pass, 97),
+            ]),
+            "close",
+        );
+        assert!(result.confidence >= 0.8);
+        assert_eq!(result.decision, Decision::Pass);
+        assert!(!result.low_confidence);
+    }
+
+    #[test]
+    fn test_confidence_is_reported_without_min_confidence() {
+        let engine = ConsensusEngine::new(create_config(ConsensusRuleConfig::Strong, 70, 3));
+        let result = engine.evaluate(
+            vote_map(&[
+                ("Codex", Vote::Pass, 95),
+                ("Gemini", Vote::Pass, 55),
+                ("Qwen", Vote::Pass, 72),
+            ]),
+            "spread",
+        );
+
+        assert_eq!(result.decision, Decision::Pass);
+        assert!(!result.low_confidence);
+        assert_eq!(result.confidence, engine.calculate_confidence(&result));
+        assert!(result.confidence > 0.0 && result.confidence < 0.8);
+    }
+
+    #[test]
+    fn test_min_confidence_does_not_settle_pass_early() {
+        let config = ConsensusConfig {
+            min_score: 50,
+            min_confidence: Some(0.8),
+            ..settle_config(ConsensusRuleConfig::Weak, SeverityGate::Off)
+        };
+        let votes = vote_map(&[("Codex", Vote::Pass, 100), ("Gemini", Vote::Pass, 100)]);
+
+        let without = ConsensusEngine::new(ConsensusConfig {
+            min_confidence: None,
+            ..config.clone()
+        });
+        let with = ConsensusEngine::new(config);
+        let settled = without.settled_decision(&votes, &Turnout::new(3), &["Qwen"]);
+        assert_eq!(settled.map(|(decision, _)| decision), Some(Decision::Pass));
+        assert_eq!(
+            with.settled_decision(&votes, &Turnout::new(3), &["Qwen"]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_results_without_confidence_still_deserialize() {
+        let mut json = serde_json::to_value(EvaluationResult::success("old", 90, "ok")).unwrap();
+        json.as_object_mut().unwrap().remove("confidence");
+
+        let result: EvaluationResult = serde_json::from_value(json).unwrap();
+        assert_eq!(result.confidence, 0.0);
+        assert!(!result.low_confidence);
+    }
 }
@@ -0,0 +1,630 @@
+//! Renderização do feedback consolidado.
+//!
+//! O feedback padrão é montado a partir de seções fixas (cabeçalho, resumo
+//! dos votos, feedback por avaliador e ações recomendadas). Um template
+//! configurado em `consensus.feedback_template` pode reordenar, omitir ou
+//! complementar essas seções usando placeholders `{{ variavel }}`.
+//!
+//! ## Variáveis
+//!
+//! | Variável             | Conteúdo                                        |
+//! | -------------------- | ----------------------------------------------- |
+//! | `decision`           | `PASS`, `REVISE` ou `BLOCK`                     |
+//! | `score`              | Score agregado (0-100)                          |
+//! | `consensus_achieved` | `true` ou `false`                               |
+//! | `header`             | Cabeçalho markdown da decisão                   |
+//! | `gating`             | Findings que forçaram a decisão (severity gate) |
+//! | `vote_summary`       | Contagem de PASS/WARN/FAIL                      |
+//! | `votes`              | Feedback individual de cada avaliador           |
+//! | `findings`           | Findings consolidados, por severidade           |
+//! | `files`              | Findings por arquivo, com o pior de cada um     |
+//! | `fix_plan`           | Correções sugeridas, por finding                |
+//! | `disagreement`       | Avaliadores que divergiram da maioria           |
+//! | `actions`            | Ações recomendadas para a decisão               |
+//!
+//! Seções sem conteúdo (ex.: `gating` sem findings bloqueantes) são vazias.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::types::config::ConsensusConfig;
+use crate::types::responses::{Decision, FileFindings, Finding, ModelVote, Vote};
+use crate::{TetradError, TetradResult};
+
+/// Variáveis disponíveis nos templates de feedback.
+pub const FEEDBACK_VARIABLES: &[&str] = &[
+    "decision",
+    "score",
+    "consensus_achieved",
+    "header",
+    "gating",
+    "vote_summary",
+    "votes",
+    "findings",
+    "files",
+    "fix_plan",
+    "disagreement",
+    "actions",
+];
+
+/// Dados disponíveis para renderizar o feedback.
+pub struct FeedbackContext<'a> {
+    /// Votos dos executores.
+    pub votes: &'a HashMap<String, ModelVote>,
+
+    /// Decisão final (após severity gating).
+    pub decision: Decision,
+
+    /// Score agregado.
+    pub score: u8,
+
+    /// Se os votantes concordaram.
+    pub consensus_achieved: bool,
+
+    /// Findings consolidados.
+    pub findings: &'a [Finding],
+
+    /// Findings que forçaram a decisão.
+    pub gated: &'a [&'a Finding],
+}
+
+impl FeedbackContext<'_> {
+    /// Renderiza o feedback no formato padrão do Tetrad.
+    pub fn render_default(&self) -> String {
+        let mut feedback = String::new();
+        feedback.push_str(self.header());
+        feedback.push_str("\n\n");
+        // Findings que atingiram o limite de severidade vêm antes dos votos
+        feedback.push_str(&self.gating());
+        feedback.push_str(&self.vote_summary());
+        feedback.push_str(&self.votes());
+        // Requests com vários arquivos ganham uma seção por arquivo
+        feedback.push_str(&self.files());
+        feedback.push_str(&self.actions());
+        feedback
+    }
+
+    /// Retorna o valor de uma variável de template.
+    fn variable(&self, name: &str) -> String {
+        match name {
+            "decision" => self.decision.to_string(),
+            "score" => self.score.to_string(),
+            "consensus_achieved" => self.consensus_achieved.to_string(),
+            "header" => self.header().to_string(),
+            "gating" => self.gating(),
+            "vote_summary" => self.vote_summary(),
+            "votes" => self.votes(),
+            "findings" => self.findings(),
+            "files" => self.files(),
+            "fix_plan" => self.fix_plan(),
+            "disagreement" => self.disagreement(),
+            "actions" => self.actions(),
+            _ => String::new(),
+        }
+    }
+
+    /// Executores ordenados por nome, para saída determinística.
+    fn sorted_votes(&self) -> Vec<(&String, &ModelVote)> {
+        let mut votes: Vec<_> = self.votes.iter().collect();
+        votes.sort_by(|a, b| a.0.cmp(b.0));
+        votes
+    }
+
+    fn header(&self) -> &'static str {
+        match self.decision {
+            Decision::Pass => "## Avaliação Aprovada",
+            Decision::Revise => "## Revisão Necessária",
+            Decision::Block => "## Avaliação Bloqueada",
+        }
+    }
+
+    fn gating(&self) -> String {
+        if self.gated.is_empty() {
+            return String::new();
+        }
+
+        let mut section = format!(
+            "**Decisão forçada por severity gating** (block_on_severity): {} finding(s) no limite ou acima\n\n",
+            self.gated.len()
+        );
+        for finding in self.gated {
+            section.push_str(&format!(
+                "- [{}] {} ({})\n",
+                finding.severity, finding.issue, finding.source
+            ));
+        }
+        section.push('\n');
+        section
+    }
+
+    fn vote_summary(&self) -> String {
+        let count = |vote: Vote| self.votes.values().filter(|v| v.vote == vote).count();
+
+        format!(
+            "**Votos:** {} PASS | {} WARN | {} FAIL\n\n",
+            count(Vote::Pass),
+            count(Vote::Warn),
+            count(Vote::Fail)
+        )
+    }
+
+    fn votes(&self) -> String {
+        let mut section = String::from("### Feedback dos Avaliadores\n\n");
+
+        for (executor, vote) in self.sorted_votes() {
+            let icon = match vote.vote {
+                Vote::Pass => "✓",
+                Vote::Warn => "⚠",
+                Vote::Fail => "✗",
+            };
+
+            section.push_str(&format!(
+                "**{} {}** (score: {})\n",
+                icon, executor, vote.score
+            ));
+
+            if !vote.reasoning.is_empty() {
+                section.push_str(&format!("> {}\n", vote.reasoning));
+            }
+
+            if !vote.issues.is_empty() {
+                section.push_str("\nIssues:\n");
+                for issue in &vote.issues {
+                    section.push_str(&format!("- {}\n", issue));
+                }
+            }
+
+            if !vote.suggestions.is_empty() {
+                section.push_str("\nSugestões:\n");
+                for suggestion in &vote.suggestions {
+                    section.push_str(&format!("- {}\n", suggestion));
+                }
+            }
+
+            section.push('\n');
+        }
+
+        section
+    }
+
+    fn findings(&self) -> String {
+        if self.findings.is_empty() {
+            return String::new();
+        }
+
+        let mut section = String::from("### Findings\n\n");
+        for finding in self.findings {
+            section.push_str(&format!(
+                "- [{}] {} ({})\n",
+                finding.severity, finding.issue, finding.source
+            ));
+            if let Some(suggestion) = &finding.suggestion {
+                section.push_str(&format!("  Sugestão: {}\n", suggestion));
+            }
+        }
+        section.push('\n');
+        section
+    }
+
+    fn files(&self) -> String {
+        let groups = FileFindings::group(self.findings);
+        if groups.is_empty() {
+            return String::new();
+        }
+
+        let mut section = String::from("### Findings por Arquivo\n\n");
+        for group in &groups {
+            section.push_str(&format!(
+                "**{}** — pior severidade: {} ({} finding(s))\n",
+                group.file.as_deref().unwrap_or("(sem arquivo)"),
+                group.verdict,
+                group.findings.len()
+            ));
+            for finding in &group.findings {
+                let lines = finding
+                    .lines
+                    .as_ref()
+                    .map(|lines| {
+                        let lines: Vec<String> = lines.iter().map(u32::to_string).collect();
+                        format!(" [linhas {}]", lines.join(", "))
+                    })
+                    .unwrap_or_default();
+                section.push_str(&format!(
+                    "- [{}] {}{} ({})\n",
+                    finding.severity, finding.issue, lines, finding.source
+                ));
+            }
+            section.push('\n');
+        }
+        section
+    }
+
+    fn fix_plan(&self) -> String {
+        let fixes: Vec<_> = self
+            .findings
+            .iter()
+            .filter_map(|finding| finding.fix.as_ref().map(|fix| (finding, fix)))
+            .collect();
+        if fixes.is_empty() {
+            return String::new();
+        }
+
+        let mut section = String::from("### Plano de Correções\n\n");
+        for (index, (finding, fix)) in fixes.iter().enumerate() {
+            section.push_str(&format!(
+                "{}. Linhas {}-{}: {}\n```\n{}\n```\n",
+                index + 1,
+                fix.start_line,
+                fix.end_line,
+                finding.issue,
+                fix.replacement
+            ));
+        }
+        section.push('\n');
+        section
+    }
+
+    fn disagreement(&self) -> String {
+        let count = |vote: Vote| self.votes.values().filter(|v| v.vote == vote).count();
+        let counts = [
+            (Vote::Pass, count(Vote::Pass)),
+            (Vote::Warn, count(Vote::Warn)),
+            (Vote::Fail, count(Vote::Fail)),
+        ];
+
+        let top = counts.iter().map(|(_, n)| *n).max().unwrap_or(0);
+        if top == self.votes.len() {
+            return String::new();
+        }
+
+        // Sem maioria clara (empate), todos os votos são listados
+        let leaders: Vec<_> = counts.iter().filter(|(_, n)| *n == top).collect();
+        let majority = match leaders.as_slice() {
+            [(vote, _)] => Some(*vote),
+            _ => None,
+        };
+
+        let mut section = String::from("### Divergências\n\n");
+        for (executor, vote) in self.sorted_votes() {
+            if Some(vote.vote) != majority {
+                section.push_str(&format!(
+                    "- **{}** votou {} (score: {})\n",
+                    executor, vote.vote, vote.score
+                ));
+            }
+        }
+        section.push('\n');
+        section
+    }
+
+    fn actions(&self) -> String {
+        let mut section = String::from("### Ações Recomendadas\n\n");
+        match self.decision {
+            Decision::Pass => {
+                section.push_str("O código foi aprovado por todos os avaliadores. ");
+                section.push_str("Você pode prosseguir com a implementação.\n");
+            }
+            Decision::Revise => {
+                section.push_str("O código precisa de ajustes antes de ser aprovado. ");
+                section.push_str("Revise os issues acima e submeta novamente.\n");
+            }
+            Decision::Block => {
+                section.push_str("O código foi bloqueado devido a problemas críticos. ");
+                section.push_str("Corrija TODOS os issues marcados como Critical ou Error antes de prosseguir.\n");
+            }
+        }
+        section
+    }
+}
+
+/// Trecho de um template de feedback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Variable(&'static str),
+}
+
+/// Template de feedback com placeholders `{{ variavel }}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedbackTemplate {
+    segments: Vec<Segment>,
+}
+
+impl FeedbackTemplate {
+    /// Interpreta um template, validando todos os placeholders.
+    pub fn parse(source: &str) -> TetradResult<Self> {
+        let segments = Self::parse_segments(source).map_err(TetradError::config)?;
+        Ok(Self { segments })
+    }
+
+    fn parse_segments(source: &str) -> Result<Vec<Segment>, String> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Text(rest[..start].to_string()));
+            }
+
+            let offset = source.len() - rest.len() + start;
+            let line = source[..offset].matches('\n').count() + 1;
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| format!("unclosed '{{{{' on line {}", line))?;
+
+            let name = after[..end].trim();
+            let variable = FEEDBACK_VARIABLES
+                .iter()
+                .find(|v| **v == name)
+                .copied()
+                .ok_or_else(|| {
+                    format!(
+                        "unknown variable '{}' on line {} (available: {})",
+                        name,
+                        line,
+                        FEEDBACK_VARIABLES.join(", ")
+                    )
+                })?;
+            segments.push(Segment::Variable(variable));
+
+            rest = &after[end + 2..];
+        }
+
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_string()));
+        }
+
+        Ok(segments)
+    }
+
+    /// Carrega e valida um template a partir de um arquivo.
+    pub fn load<P: AsRef<Path>>(path: P) -> TetradResult<Self> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|e| {
+            TetradError::config(format!(
+                "cannot read feedback template '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        let segments = Self::parse_segments(&source).map_err(|e| {
+            TetradError::config(format!(
+                "invalid feedback template '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Ok(Self { segments })
+    }
+
+    /// Carrega o template configurado em `consensus.feedback_template`, se houver.
+    pub fn from_config(config: &ConsensusConfig) -> TetradResult<Option<Self>> {
+        config
+            .feedback_template
+            .as_ref()
+            .map(Self::load)
+            .transpose()
+    }
+
+    /// Renderiza o template com os dados da avaliação.
+    pub fn render(&self, context: &FeedbackContext<'_>) -> String {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Variable(name) => context.variable(name),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::responses::{Severity, SuggestedFix};
+
+    fn votes() -> HashMap<String, ModelVote> {
+        let mut codex = ModelVote::new("Codex", Vote::Pass, 90);
+        codex.reasoning = "Código limpo".to_string();
+
+        let mut gemini = ModelVote::new("Gemini", Vote::Fail, 40);
+        gemini.issues = vec!["unwrap may panic".to_string()];
+        gemini.suggestions = vec!["Use ?".to_string()];
+
+        let qwen = ModelVote::new("Qwen", Vote::Pass, 85);
+
+        [("Codex", codex), ("Gemini", gemini), ("Qwen", qwen)]
+            .into_iter()
+            .map(|(name, vote)| (name.to_string(), vote))
+            .collect()
+    }
+
+    fn findings() -> Vec<Finding> {
+        let mut finding = Finding::new(Severity::Error, "logic", "unwrap may panic");
+        finding.source = "Gemini".to_string();
+        finding.suggestion = Some("Use ?".to_string());
+        finding.fix = Some(SuggestedFix::new(3, 3, "let x = y?;"));
+        vec![finding]
+    }
+
+    fn context<'a>(
+        votes: &'a HashMap<String, ModelVote>,
+        findings: &'a [Finding],
+    ) -> FeedbackContext<'a> {
+        FeedbackContext {
+            votes,
+            decision: Decision::Revise,
+            score: 71,
+            consensus_achieved: false,
+            findings,
+            gated: &[],
+        }
+    }
+
+    #[test]
+    fn test_render_default_golden() {
+        let votes = votes();
+        let findings = findings();
+
+        let expected = "\
+## Revisão Necessária
+
+**Votos:** 2 PASS | 0 WARN | 1 FAIL
+
+### Feedback dos Avaliadores
+
+**✓ Codex** (score: 90)
+> Código limpo
+
+**✗ Gemini** (score: 40)
+
+Issues:
+- unwrap may panic
+
+Sugestões:
+- Use ?
+
+**✓ Qwen** (score: 85)
+
+### Ações Recomendadas
+
+O código precisa de ajustes antes de ser aprovado. Revise os issues acima e submeta novamente.
+";
+
+        assert_eq!(context(&votes, &findings).render_default(), expected);
+    }
+
+    #[test]
+    fn test_render_custom_template_golden() {
+        let votes = votes();
+        let findings = findings();
+        let template = FeedbackTemplate::parse(
+            "# {{ decision }} ({{score}}/100)\n\n{{ disagreement }}{{ findings }}{{ fix_plan }}---\nVeja https://wiki.example/review\n",
+        )
+        .unwrap();
+
+        let expected = "\
+# REVISE (71/100)
+
+### Divergências
+
+- **Gemini** votou FAIL (score: 40)
+
+### Findings
+
+- [ERROR] unwrap may panic (Gemini)
+  Sugestão: Use ?
+
+### Plano de Correções
+
+1. Linhas 3-3: unwrap may panic
+```
+let x = y?;
+```
+
+---
+Veja https://wiki.example/review
+";
+
+        assert_eq!(template.render(&context(&votes, &findings)), expected);
+    }
+
+    #[test]
+    fn test_empty_sections_render_as_empty() {
+        let votes: HashMap<String, ModelVote> =
+            [("Codex".to_string(), ModelVote::new("Codex", Vote::Pass, 90))]
+                .into_iter()
+                .collect();
+        let template = FeedbackTemplate::parse(
+            "{{gating}}{{disagreement}}{{findings}}{{files}}{{fix_plan}}end",
+        )
+        .unwrap();
+
+        assert_eq!(template.render(&context(&votes, &[])), "end");
+    }
+
+    #[test]
+    fn test_files_section_per_file_verdict() {
+        let votes = votes();
+        let findings = vec![
+            Finding::new(Severity::Error, "logic", "unwrap may panic")
+                .with_source("Gemini")
+                .with_lines(vec![3])
+                .with_file("src/b.rs"),
+            Finding::new(Severity::Warning, "style", "long function")
+                .with_source("Codex")
+                .with_file("src/a.rs"),
+            Finding::new(Severity::Info, "style", "naming").with_source("Qwen"),
+        ];
+
+        let expected = "\
+### Findings por Arquivo
+
+**src/a.rs** — pior severidade: WARNING (1 finding(s))
+- [WARNING] long function (Codex)
+
+**src/b.rs** — pior severidade: ERROR (1 finding(s))
+- [ERROR] unwrap may panic [linhas 3] (Gemini)
+
+**(sem arquivo)** — pior severidade: INFO (1 finding(s))
+- [INFO] naming (Qwen)
+
+";
+        let template = FeedbackTemplate::parse("{{ files }}").unwrap();
+        assert_eq!(template.render(&context(&votes, &findings)), expected);
+        assert!(context(&votes, &findings)
+            .render_default()
+            .contains("### Findings por Arquivo"));
+    }
+
+    #[test]
+    fn test_disagreement_without_majority_lists_everyone() {
+        let votes: HashMap<String, ModelVote> = [
+            ("Codex".to_string(), ModelVote::new("Codex", Vote::Pass, 90)),
+            (
+                "Gemini".to_string(),
+                ModelVote::new("Gemini", Vote::Fail, 40),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let template = FeedbackTemplate::parse("{{ disagreement }}").unwrap();
+
+        let rendered = template.render(&context(&votes, &[]));
+        assert!(rendered.contains("**Codex** votou PASS"));
+        assert!(rendered.contains("**Gemini** votou FAIL"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_variable() {
+        let err = FeedbackTemplate::parse("ok\n{{ verdict }}").unwrap_err();
+        let message = err.to_string();
+
+        assert!(message.contains("unknown variable 'verdict' on line 2"));
+        assert!(message.contains("decision"));
+    }
+
+    #[test]
+    fn test_parse_rejects_unclosed_placeholder() {
+        let err = FeedbackTemplate::parse("{{ decision }} {{ score").unwrap_err();
+        assert!(err.to_string().contains("unclosed '{{' on line 1"));
+    }
+
+    #[test]
+    fn test_load_reports_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("feedback.md");
+        std::fs::write(&path, "{{ nope }}").unwrap();
+
+        let message = FeedbackTemplate::load(&path).unwrap_err().to_string();
+        assert!(message.contains("invalid feedback template"));
+        assert!(message.contains("feedback.md"));
+        assert!(message.contains("unknown variable 'nope'"));
+
+        let missing = dir.path().join("missing.md");
+        let message = FeedbackTemplate::load(&missing).unwrap_err().to_string();
+        assert!(message.contains("cannot read feedback template"));
+    }
+}
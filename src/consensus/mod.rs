@@ -7,8 +7,12 @@
 //! ## Regras de Consenso
 //!
 //! - **Golden**: Unanimidade necessária (todos devem votar PASS)
-//! - **Strong**: Consenso forte (3/3 CLIs concordam)
-//! - **Weak**: Consenso fraco (2+ CLIs concordam)
+//! - **Strong**: Consenso forte (todos os CLIs habilitados concordam)
+//! - **Weak**: Consenso fraco (a maioria dos CLIs concorda)
+//! - **Custom**: expressões `pass`/`block` de `[consensus.custom]`
+//!
+//! Com `consensus.adaptive`, menos votantes que o esperado reduzem a regra:
+//! `single-evaluator` para 1 voto e `dual-evaluator` para 2.
 //!
 //! ## Exemplo
 //!
@@ -27,8 +31,14 @@
 
 mod aggregator;
 mod engine;
+mod feedback;
 mod rules;
 
-pub use aggregator::VoteAggregator;
-pub use engine::ConsensusEngine;
-pub use rules::{create_rule, ConsensusRule, GoldenRule, StrongRule, WeakRule};
+pub use aggregator::{DecisionData, VoteAggregator, DEFAULT_FINDING_SIMILARITY};
+pub use engine::{ConsensusEngine, StrictSettings, Turnout, MAX_SETTLE_PENDING};
+pub use feedback::{FeedbackContext, FeedbackTemplate, FEEDBACK_VARIABLES};
+pub use rules::{
+    adaptive_rule, create_rule, Comparison, Condition, ConsensusRule, CustomRule,
+    DualEvaluatorRule, ExpressionError, GoldenRule, Operand, RuleVariable, SingleEvaluatorRule,
+    StrongRule, VoteWeights, WeakRule, DEFAULT_VOTERS,
+};
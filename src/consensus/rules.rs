@@ -1,14 +1,35 @@
 //! Consensus rules for Tetrad.
 //!
-//! Defines the three available consensus rules:
+//! Defines the available consensus rules:
 //! - Golden: Unanimity (all must vote PASS)
-//! - Strong: Strong consensus (3/3 CLIs agree)
-//! - Weak: Weak consensus (2+ CLIs agree)
+//! - Strong: Strong consensus (all enabled CLIs agree)
+//! - Weak: Weak consensus (a majority of CLIs agree)
+//! - Custom: user-defined `pass`/`block` expressions over the vote counts
+//!
+//! Rules are parameterized by the number of voters (enabled executor seats),
+//! which defaults to the three built-in executors.
+//!
+//! When fewer executors vote than expected, [`adaptive_rule`] scales the
+//! configured rule down, with dedicated rules for one and two voters.
+//!
+//! Each executor's `weight` (see [`VoteWeights`]) scales its vote in the
+//! average scores and in the Weak rule's majority. Unanimity (Golden, and
+//! agreement under Strong) is still counted voter by voter, so no weight lets
+//! one executor pass code on its own under those rules.
 
 use std::collections::HashMap;
 
-use crate::types::config::ConsensusRule as ConsensusRuleConfig;
-use crate::types::responses::{Decision, ModelVote, Vote};
+use crate::types::config::{ConsensusRule as ConsensusRuleConfig, CustomRuleConfig};
+use crate::types::responses::{Decision, ModelVote, RuleExplanation, Vote};
+use crate::{TetradError, TetradResult};
+
+/// Default number of voters (Codex, Gemini and Qwen).
+pub const DEFAULT_VOTERS: usize = 3;
+
+/// Number of votes that form a simple majority of `voters`.
+pub fn majority(voters: usize) -> usize {
+    voters / 2 + 1
+}
 
 /// Trait for consensus rules.
 pub trait ConsensusRule: Send + Sync {
@@ -16,50 +37,268 @@ pub trait ConsensusRule: Send + Sync {
     fn name(&self) -> &str;
 
     /// Evaluates votes and returns the decision.
-    fn evaluate(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> Decision;
+    fn evaluate(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> Decision {
+        self.explain(votes, min_score).decision
+    }
+
+    /// Evaluates votes and explains the decision: the condition that
+    /// produced it and the executors whose votes triggered it.
+    fn explain(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> RuleExplanation;
 
     /// Minimum number of votes required for consensus.
     fn min_required(&self) -> usize;
 
     /// Checks if consensus was achieved.
     fn is_consensus_achieved(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> bool;
+
+    /// Voter weights the rule tallies with; `None` weighs every vote equally.
+    fn weights(&self) -> Option<&VoteWeights> {
+        None
+    }
+}
+
+/// Consensus weight of each voter, from the executors' `weight` setting.
+///
+/// With no weights, or all weights equal, every rule decides exactly as an
+/// unweighted vote.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoteWeights {
+    /// Weight by lowercase executor name.
+    weights: HashMap<String, u32>,
+}
+
+impl VoteWeights {
+    /// Weights keyed by executor name.
+    pub fn new<S: Into<String>>(weights: impl IntoIterator<Item = (S, u8)>) -> Self {
+        Self {
+            weights: weights
+                .into_iter()
+                .map(|(name, weight)| (name.into().to_lowercase(), weight as u32))
+                .collect(),
+        }
+    }
+
+    /// Whether every voter weighs the same.
+    pub fn is_uniform(&self) -> bool {
+        let mut weights = self.weights.values();
+        match weights.next() {
+            Some(first) => weights.all(|weight| weight == first),
+            None => true,
+        }
+    }
+
+    /// Weight of `voter`.
+    ///
+    /// A fallback seat ("Qwen (fallback for Gemini)") weighs as the executor
+    /// that answered; an unknown voter weighs the average.
+    pub fn of(&self, voter: &str) -> u32 {
+        if self.weights.is_empty() {
+            return 1;
+        }
+        let voter = voter.to_lowercase();
+        let executor = voter.split(" (fallback for ").next().unwrap_or(&voter);
+        self.weights.get(executor).copied().unwrap_or_else(|| {
+            let total: u32 = self.weights.values().sum();
+            (total / self.weights.len() as u32).max(1)
+        })
+    }
+
+    /// Total weight of `voters`.
+    pub fn total<'a>(&self, voters: impl IntoIterator<Item = &'a String>) -> u32 {
+        voters.into_iter().map(|voter| self.of(voter)).sum()
+    }
+
+    /// Weighted average score, rounded down like the unweighted one.
+    pub fn average_score<'a>(
+        &self,
+        votes: impl IntoIterator<Item = (&'a String, &'a ModelVote)>,
+    ) -> u8 {
+        let (sum, total) = votes
+            .into_iter()
+            .fold((0u64, 0u64), |(sum, total), (name, vote)| {
+                let weight = self.of(name) as u64;
+                (sum + vote.score as u64 * weight, total + weight)
+            });
+        if total == 0 {
+            return 0;
+        }
+        (sum / total) as u8
+    }
+
+    /// Whether `names` hold more than half of the weight of `voters` seats.
+    ///
+    /// Seats without a vote yet count with the average weight of `votes`, so
+    /// equal weights give the same majority as [`majority`].
+    fn is_majority(
+        &self,
+        votes: &HashMap<String, ModelVote>,
+        names: &[String],
+        voters: usize,
+    ) -> bool {
+        if votes.is_empty() {
+            return false;
+        }
+        let held = self.total(names) as u64;
+        let present = self.total(votes.keys()) as u64;
+        held * 2 * votes.len() as u64 > present * voters as u64
+    }
+}
+
+/// Sorted names of the voters whose vote matches `predicate`.
+fn voters_where(
+    votes: &HashMap<String, ModelVote>,
+    predicate: impl Fn(&ModelVote) -> bool,
+) -> Vec<String> {
+    let mut names: Vec<String> = votes
+        .iter()
+        .filter(|(_, vote)| predicate(vote))
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Revise while fewer votes than the rule requires have arrived.
+fn insufficient_votes(explanation: RuleExplanation) -> RuleExplanation {
+    let detail = format!(
+        "{} of {} required votes",
+        explanation.votes, explanation.min_required
+    );
+    explanation.decided(Decision::Revise, "insufficient_votes", detail)
+}
+
+/// Revise when every vote is PASS but the average score is too low.
+fn low_score(
+    explanation: RuleExplanation,
+    votes: &HashMap<String, ModelVote>,
+    avg_score: u8,
+    min_score: u8,
+) -> RuleExplanation {
+    explanation
+        .decided(
+            Decision::Revise,
+            "low_score",
+            format!(
+                "all votes are PASS, but the average score {} is below {}",
+                avg_score, min_score
+            ),
+        )
+        .driven_by(&voters_where(votes, |v| v.score < min_score))
+}
+
+/// Revise when the voters do not agree.
+fn disagreement(
+    explanation: RuleExplanation,
+    votes: &HashMap<String, ModelVote>,
+) -> RuleExplanation {
+    let holdouts = voters_where(votes, |v| v.vote != Vote::Pass);
+    explanation
+        .decided(
+            Decision::Revise,
+            "disagreement",
+            format!(
+                "votes disagree: {} PASS, {} WARN, {} FAIL",
+                votes.values().filter(|v| v.vote == Vote::Pass).count(),
+                votes.values().filter(|v| v.vote == Vote::Warn).count(),
+                votes.values().filter(|v| v.vote == Vote::Fail).count()
+            ),
+        )
+        .driven_by(&holdouts)
 }
 
 /// Golden Rule: Unanimity required.
 ///
 /// All evaluators must vote PASS with score >= min_score.
 /// This is the most restrictive rule, ideal for critical code.
-#[derive(Debug, Clone, Default)]
-pub struct GoldenRule;
+#[derive(Debug, Clone)]
+pub struct GoldenRule {
+    voters: usize,
+    weights: VoteWeights,
+}
+
+impl GoldenRule {
+    /// Creates the rule for the given number of voters.
+    pub fn new(voters: usize) -> Self {
+        Self {
+            voters: voters.max(1),
+            weights: VoteWeights::default(),
+        }
+    }
+
+    /// Sets the voter weights.
+    pub fn with_weights(mut self, weights: VoteWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+}
+
+impl Default for GoldenRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_VOTERS)
+    }
+}
 
 impl ConsensusRule for GoldenRule {
     fn name(&self) -> &str {
         "golden"
     }
 
-    fn evaluate(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> Decision {
+    fn explain(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> RuleExplanation {
+        let explanation = RuleExplanation::new(self.name(), votes, min_score, self.min_required());
+
         // Check minimum required votes
         if votes.len() < self.min_required() {
-            return Decision::Revise; // Not enough votes, need to wait
+            return insufficient_votes(explanation); // Not enough votes, need to wait
         }
 
         let all_pass = votes
             .values()
             .all(|v| v.vote == Vote::Pass && v.score >= min_score);
 
-        let any_fail = votes.values().any(|v| v.vote == Vote::Fail);
+        let failed = voters_where(votes, |v| v.vote == Vote::Fail);
 
         if all_pass {
-            Decision::Pass
-        } else if any_fail {
-            Decision::Block
+            explanation
+                .decided(
+                    Decision::Pass,
+                    "all_pass",
+                    format!(
+                        "all {} votes are PASS with score >= {}",
+                        votes.len(),
+                        min_score
+                    ),
+                )
+                .driven_by(votes.keys())
+        } else if !failed.is_empty() {
+            explanation
+                .decided(
+                    Decision::Block,
+                    "any_fail",
+                    format!("{} voted FAIL; unanimity allows no FAIL", failed.join(", ")),
+                )
+                .driven_by(&failed)
         } else {
-            Decision::Revise
+            let holdouts = voters_where(votes, |v| v.vote != Vote::Pass || v.score < min_score);
+            explanation
+                .decided(
+                    Decision::Revise,
+                    "not_unanimous",
+                    format!(
+                        "{} did not vote PASS with score >= {}",
+                        holdouts.join(", "),
+                        min_score
+                    ),
+                )
+                .driven_by(&holdouts)
         }
     }
 
     fn min_required(&self) -> usize {
-        3 // All 3 CLIs
+        self.voters // All enabled CLIs
+    }
+
+    fn weights(&self) -> Option<&VoteWeights> {
+        Some(&self.weights)
     }
 
     fn is_consensus_achieved(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> bool {
@@ -70,46 +309,94 @@ impl ConsensusRule for GoldenRule {
     }
 }
 
-/// Strong Consensus: 3/3 CLIs must agree.
+/// Strong Consensus: all enabled CLIs must agree.
 ///
 /// All evaluators must agree on the decision (PASS or FAIL).
 /// This is the default rule, balancing rigor and practicality.
-#[derive(Debug, Clone, Default)]
-pub struct StrongRule;
+#[derive(Debug, Clone)]
+pub struct StrongRule {
+    voters: usize,
+    weights: VoteWeights,
+}
+
+impl StrongRule {
+    /// Creates the rule for the given number of voters.
+    pub fn new(voters: usize) -> Self {
+        Self {
+            voters: voters.max(1),
+            weights: VoteWeights::default(),
+        }
+    }
+
+    /// Sets the voter weights.
+    pub fn with_weights(mut self, weights: VoteWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+}
+
+impl Default for StrongRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_VOTERS)
+    }
+}
 
 impl ConsensusRule for StrongRule {
     fn name(&self) -> &str {
         "strong"
     }
 
-    fn evaluate(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> Decision {
-        // Check minimum required votes (3/3)
+    fn explain(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> RuleExplanation {
+        let explanation = RuleExplanation::new(self.name(), votes, min_score, self.min_required());
+
+        // Check minimum required votes (all enabled)
         if votes.len() < self.min_required() {
-            return Decision::Revise; // Not enough votes, need to wait
+            return insufficient_votes(explanation); // Not enough votes, need to wait
         }
 
         let pass_count = votes.values().filter(|v| v.vote == Vote::Pass).count();
         let fail_count = votes.values().filter(|v| v.vote == Vote::Fail).count();
 
-        let avg_score = self.calculate_average_score(votes);
-
-        // Strong Rule: 3/3 must agree
-        // All pass (3/3 PASS)
-        if pass_count == self.min_required() && avg_score >= min_score {
-            return Decision::Pass;
+        let avg_score = self.weights.average_score(votes);
+
+        // Strong Rule: all voters must agree
+        // All pass
+        if pass_count == votes.len() {
+            return if avg_score >= min_score {
+                explanation
+                    .decided(
+                        Decision::Pass,
+                        "all_pass",
+                        format!(
+                            "all {} votes are PASS with average score {} >= {}",
+                            votes.len(),
+                            avg_score,
+                            min_score
+                        ),
+                    )
+                    .driven_by(votes.keys())
+            } else {
+                low_score(explanation, votes, avg_score, min_score)
+            };
         }
 
-        // All fail (3/3 FAIL)
-        if fail_count == self.min_required() {
-            return Decision::Block;
+        // All fail
+        if fail_count == votes.len() {
+            return explanation
+                .decided(
+                    Decision::Block,
+                    "all_fail",
+                    format!("all {} votes are FAIL", votes.len()),
+                )
+                .driven_by(votes.keys());
         }
 
-        // Any disagreement or low score = revision
-        Decision::Revise
+        // Any disagreement = revision
+        disagreement(explanation, votes)
     }
 
     fn min_required(&self) -> usize {
-        3
+        self.voters
     }
 
     fn is_consensus_achieved(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> bool {
@@ -120,57 +407,126 @@ impl ConsensusRule for StrongRule {
         let decision = self.evaluate(votes, min_score);
         matches!(decision, Decision::Pass | Decision::Block)
     }
-}
 
-impl StrongRule {
-    fn calculate_average_score(&self, votes: &HashMap<String, ModelVote>) -> u8 {
-        if votes.is_empty() {
-            return 0;
-        }
-        let total: u32 = votes.values().map(|v| v.score as u32).sum();
-        (total / votes.len() as u32) as u8
+    fn weights(&self) -> Option<&VoteWeights> {
+        Some(&self.weights)
     }
 }
 
-/// Weak Consensus: 2+ CLIs agree.
+/// Weak Consensus: a majority of CLIs agree.
 ///
 /// Simple majority decides. This is the most permissive rule,
 /// useful for prototypes and experiments.
-#[derive(Debug, Clone, Default)]
-pub struct WeakRule;
+#[derive(Debug, Clone)]
+pub struct WeakRule {
+    voters: usize,
+    weights: VoteWeights,
+}
+
+impl WeakRule {
+    /// Creates the rule for the given number of voters.
+    pub fn new(voters: usize) -> Self {
+        Self {
+            voters: voters.max(1),
+            weights: VoteWeights::default(),
+        }
+    }
+
+    /// Sets the voter weights.
+    pub fn with_weights(mut self, weights: VoteWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+}
+
+impl Default for WeakRule {
+    fn default() -> Self {
+        Self::new(DEFAULT_VOTERS)
+    }
+}
 
 impl ConsensusRule for WeakRule {
     fn name(&self) -> &str {
         "weak"
     }
 
-    fn evaluate(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> Decision {
+    fn explain(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> RuleExplanation {
+        let explanation = RuleExplanation::new(self.name(), votes, min_score, self.min_required());
         if votes.is_empty() {
-            return Decision::Block;
+            return explanation.decided(Decision::Block, "no_votes", "no executor voted");
         }
 
-        let pass_votes: Vec<_> = votes.values().filter(|v| v.vote == Vote::Pass).collect();
-        let fail_count = votes.values().filter(|v| v.vote == Vote::Fail).count();
+        let passed = voters_where(votes, |v| v.vote == Vote::Pass);
+        let failed = voters_where(votes, |v| v.vote == Vote::Fail);
+        let pass_majority = self.weights.is_majority(votes, &passed, self.voters);
+
+        // Majority passes - uses average only from PASS votes
+        let avg_pass_score = self
+            .weights
+            .average_score(votes.iter().filter(|(_, v)| v.vote == Vote::Pass));
+        if pass_majority && avg_pass_score >= min_score {
+            return explanation
+                .decided(
+                    Decision::Pass,
+                    "majority_pass",
+                    format!(
+                        "{} with average PASS score {} >= {}",
+                        self.share(votes, &passed, "PASS", true),
+                        avg_pass_score,
+                        min_score
+                    ),
+                )
+                .driven_by(&passed);
+        }
 
-        // Majority passes (2+ of 3) - uses average only from PASS votes
-        if pass_votes.len() >= 2 {
-            let avg_pass_score = self.calculate_average_score_of(&pass_votes);
-            if avg_pass_score >= min_score {
-                return Decision::Pass;
-            }
+        // Majority fails
+        if self.weights.is_majority(votes, &failed, self.voters) {
+            return explanation
+                .decided(
+                    Decision::Block,
+                    "majority_fail",
+                    self.share(votes, &failed, "FAIL", true),
+                )
+                .driven_by(&failed);
         }
 
-        // Majority fails (2+ of 3)
-        if fail_count >= 2 {
-            return Decision::Block;
+        // A PASS majority below the minimum score
+        if pass_majority {
+            return explanation
+                .decided(
+                    Decision::Revise,
+                    "low_score",
+                    format!(
+                        "{}, but their average score {} is below {}",
+                        self.share(votes, &passed, "PASS", false),
+                        avg_pass_score,
+                        min_score
+                    ),
+                )
+                .driven_by(&passed);
         }
 
         // Tie or no clear majority
-        Decision::Revise
+        let detail = if self.weights.is_uniform() {
+            format!(
+                "neither PASS ({}) nor FAIL ({}) reached the majority of {}",
+                passed.len(),
+                failed.len(),
+                self.min_required()
+            )
+        } else {
+            format!(
+                "neither PASS (weight {}) nor FAIL (weight {}) holds more than half of the weight {}",
+                self.weights.total(&passed),
+                self.weights.total(&failed),
+                self.weights.total(votes.keys())
+            )
+        };
+        explanation.decided(Decision::Revise, "no_majority", detail)
     }
 
     fn min_required(&self) -> usize {
-        2 // Only 2 required for decision
+        majority(self.voters) // 2 of 3 by default
     }
 
     fn is_consensus_achieved(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> bool {
@@ -181,47 +537,758 @@ impl ConsensusRule for WeakRule {
         let decision = self.evaluate(votes, min_score);
         matches!(decision, Decision::Pass | Decision::Block)
     }
+
+    fn weights(&self) -> Option<&VoteWeights> {
+        Some(&self.weights)
+    }
 }
 
 impl WeakRule {
-    fn calculate_average_score_of(&self, votes: &[&ModelVote]) -> u8 {
-        if votes.is_empty() {
-            return 0;
+    /// Describes how much of the vote `names` hold: a count with equal
+    /// weights, the summed weight otherwise.
+    fn share(
+        &self,
+        votes: &HashMap<String, ModelVote>,
+        names: &[String],
+        label: &str,
+        with_majority: bool,
+    ) -> String {
+        if !self.weights.is_uniform() {
+            return format!(
+                "{} votes hold weight {} of {} (more than half)",
+                label,
+                self.weights.total(names),
+                self.weights.total(votes.keys())
+            );
+        }
+        let count = format!("{} of {} votes are {}", names.len(), votes.len(), label);
+        if with_majority {
+            format!("{} (majority {})", count, self.min_required())
+        } else {
+            count
         }
-        let total: u32 = votes.iter().map(|v| v.score as u32).sum();
-        (total / votes.len() as u32) as u8
     }
 }
 
-/// Creates a consensus rule from configuration.
-pub fn create_rule(config: &ConsensusRuleConfig) -> Box<dyn ConsensusRule> {
-    match config {
-        ConsensusRuleConfig::Golden => Box::new(GoldenRule),
-        ConsensusRuleConfig::Strong => Box::new(StrongRule),
-        ConsensusRuleConfig::Weak => Box::new(WeakRule),
+/// Single evaluator: the only vote decides.
+///
+/// PASS needs the evaluator's score to reach min_score; FAIL blocks and
+/// anything else asks for revision.
+#[derive(Debug, Clone, Default)]
+pub struct SingleEvaluatorRule;
+
+impl ConsensusRule for SingleEvaluatorRule {
+    fn name(&self) -> &str {
+        "single-evaluator"
+    }
+
+    fn explain(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> RuleExplanation {
+        let explanation = RuleExplanation::new(self.name(), votes, min_score, self.min_required());
+        let Some((name, vote)) = votes.iter().next() else {
+            return insufficient_votes(explanation);
+        };
+
+        let explanation = match vote.vote {
+            Vote::Pass if vote.score >= min_score => explanation.decided(
+                Decision::Pass,
+                "pass_vote",
+                format!(
+                    "{} voted PASS with score {} >= {}",
+                    name, vote.score, min_score
+                ),
+            ),
+            Vote::Pass => explanation.decided(
+                Decision::Revise,
+                "low_score",
+                format!(
+                    "{} voted PASS with score {} < {}",
+                    name, vote.score, min_score
+                ),
+            ),
+            Vote::Fail => {
+                explanation.decided(Decision::Block, "fail_vote", format!("{} voted FAIL", name))
+            }
+            Vote::Warn => explanation.decided(
+                Decision::Revise,
+                "warn_vote",
+                format!("{} voted WARN", name),
+            ),
+        };
+        explanation.driven_by([name])
+    }
+
+    fn min_required(&self) -> usize {
+        1
+    }
+
+    fn is_consensus_achieved(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> bool {
+        if votes.len() < self.min_required() {
+            return false;
+        }
+        matches!(
+            self.evaluate(votes, min_score),
+            Decision::Pass | Decision::Block
+        )
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Dual evaluator: both evaluators must agree.
+///
+/// Two PASS votes with an average score >= min_score pass, two FAIL votes
+/// block, and any disagreement asks for revision.
+#[derive(Debug, Clone, Default)]
+pub struct DualEvaluatorRule {
+    weights: VoteWeights,
+}
 
-    fn create_vote(name: &str, vote: Vote, score: u8) -> (String, ModelVote) {
-        (name.to_string(), ModelVote::new(name, vote, score))
+impl DualEvaluatorRule {
+    /// Weighs the average score by the voters' weights.
+    pub fn with_weights(mut self, weights: VoteWeights) -> Self {
+        self.weights = weights;
+        self
     }
+}
 
-    fn create_votes(votes: Vec<(&str, Vote, u8)>) -> HashMap<String, ModelVote> {
-        votes
-            .into_iter()
-            .map(|(n, v, s)| create_vote(n, v, s))
-            .collect()
+impl ConsensusRule for DualEvaluatorRule {
+    fn name(&self) -> &str {
+        "dual-evaluator"
+    }
+
+    fn explain(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> RuleExplanation {
+        let explanation = RuleExplanation::new(self.name(), votes, min_score, self.min_required());
+        if votes.len() < self.min_required() {
+            return insufficient_votes(explanation);
+        }
+
+        let avg_score = self.weights.average_score(votes);
+
+        if votes.values().all(|v| v.vote == Vote::Pass) {
+            if avg_score >= min_score {
+                explanation
+                    .decided(
+                        Decision::Pass,
+                        "all_pass",
+                        format!(
+                            "both votes are PASS with average score {} >= {}",
+                            avg_score, min_score
+                        ),
+                    )
+                    .driven_by(votes.keys())
+            } else {
+                low_score(explanation, votes, avg_score, min_score)
+            }
+        } else if votes.values().all(|v| v.vote == Vote::Fail) {
+            explanation
+                .decided(Decision::Block, "all_fail", "both votes are FAIL")
+                .driven_by(votes.keys())
+        } else {
+            disagreement(explanation, votes)
+        }
+    }
+
+    fn min_required(&self) -> usize {
+        2
     }
 
+    fn is_consensus_achieved(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> bool {
+        if votes.len() < self.min_required() {
+            return false;
+        }
+        matches!(
+            self.evaluate(votes, min_score),
+            Decision::Pass | Decision::Block
+        )
+    }
+
+    fn weights(&self) -> Option<&VoteWeights> {
+        Some(&self.weights)
+    }
+}
+
+/// User-defined rule from the `pass` and `block` expressions of
+/// `[consensus.custom]`.
+///
+/// The expressions are parsed once, when the rule is built; `block` is
+/// checked before `pass`, and when neither holds the decision is Revise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CustomRule {
+    pass: Condition,
+    block: Option<Condition>,
+    /// Expression sources, quoted in explanations.
+    pass_source: String,
+    block_source: String,
+    voters: usize,
+    weights: VoteWeights,
+}
+
+impl CustomRule {
+    /// Parses the expressions of the configuration.
+    ///
+    /// Fails with [`TetradError::InvalidConfig`] listing each invalid
+    /// expression with the column of the error.
+    pub fn from_config(config: &CustomRuleConfig) -> TetradResult<Self> {
+        let mut problems = Vec::new();
+        let mut parse = |field: &str, source: &str| {
+            Condition::parse(source)
+                .map_err(|e| {
+                    problems.push(format!(
+                        "consensus.custom.{} (column {}): {}",
+                        field, e.column, e.message
+                    ))
+                })
+                .ok()
+        };
+
+        let pass = parse("pass", &config.pass);
+        let block = match config.block.trim() {
+            "" => None,
+            _ => parse("block", &config.block),
+        };
+
+        match pass {
+            Some(pass) if problems.is_empty() => Ok(Self {
+                pass,
+                block,
+                pass_source: config.pass.trim().to_string(),
+                block_source: config.block.trim().to_string(),
+                voters: DEFAULT_VOTERS,
+                weights: VoteWeights::default(),
+            }),
+            _ => Err(TetradError::InvalidConfig(problems)),
+        }
+    }
+
+    /// Sets the number of voters the rule waits for.
+    pub fn with_voters(mut self, voters: usize) -> Self {
+        self.voters = voters.max(1);
+        self
+    }
+
+    /// Weighs `avg_score` by the voters' weights; the counts stay per vote.
+    pub fn with_weights(mut self, weights: VoteWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+}
+
+impl ConsensusRule for CustomRule {
+    fn name(&self) -> &str {
+        "custom"
+    }
+
+    fn explain(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> RuleExplanation {
+        let explanation = RuleExplanation::new(self.name(), votes, min_score, self.min_required());
+        let tally = Tally::of(votes, min_score, &self.weights);
+        if self.block.as_ref().is_some_and(|block| block.holds(&tally)) {
+            explanation
+                .decided(
+                    Decision::Block,
+                    "block_expression",
+                    format!(
+                        "block expression `{}` holds with {}",
+                        self.block_source, tally
+                    ),
+                )
+                .driven_by(&voters_where(votes, |v| v.vote == Vote::Fail))
+        } else if self.pass.holds(&tally) {
+            explanation
+                .decided(
+                    Decision::Pass,
+                    "pass_expression",
+                    format!(
+                        "pass expression `{}` holds with {}",
+                        self.pass_source, tally
+                    ),
+                )
+                .driven_by(&voters_where(votes, |v| v.vote == Vote::Pass))
+        } else {
+            explanation.decided(
+                Decision::Revise,
+                "no_expression",
+                format!(
+                    "neither the block nor the pass expression holds with {}",
+                    tally
+                ),
+            )
+        }
+    }
+
+    fn min_required(&self) -> usize {
+        self.voters
+    }
+
+    fn is_consensus_achieved(&self, votes: &HashMap<String, ModelVote>, min_score: u8) -> bool {
+        if votes.len() < self.min_required() {
+            return false;
+        }
+        matches!(
+            self.evaluate(votes, min_score),
+            Decision::Pass | Decision::Block
+        )
+    }
+}
+
+/// Values the expressions of a [`CustomRule`] can refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleVariable {
+    /// Number of PASS votes.
+    PassCount,
+    /// Number of WARN votes.
+    WarnCount,
+    /// Number of FAIL votes.
+    FailCount,
+    /// Average score of all votes (0 without votes).
+    AvgScore,
+    /// Lowest score among the votes (0 without votes).
+    MinScoreVote,
+    /// The configured `consensus.min_score`.
+    MinScore,
+}
+
+impl RuleVariable {
+    /// Every variable with its name in expressions.
+    pub const ALL: [(&'static str, RuleVariable); 6] = [
+        ("pass_count", RuleVariable::PassCount),
+        ("warn_count", RuleVariable::WarnCount),
+        ("fail_count", RuleVariable::FailCount),
+        ("avg_score", RuleVariable::AvgScore),
+        ("min_score_vote", RuleVariable::MinScoreVote),
+        ("min_score", RuleVariable::MinScore),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, variable)| *variable)
+    }
+}
+
+/// Error in a rule expression, at a 1-based column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionError {
+    /// Column (in characters) where the error was found.
+    pub column: usize,
+    /// What is wrong.
+    pub message: String,
+}
+
+impl std::fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+/// Parsed rule expression: comparisons combined with `&&` and `||`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// `left op right`.
+    Compare(Operand, Comparison, Operand),
+    /// Both conditions hold.
+    And(Box<Condition>, Box<Condition>),
+    /// Either condition holds.
+    Or(Box<Condition>, Box<Condition>),
+}
+
+/// Side of a comparison.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operand {
+    /// A [`RuleVariable`].
+    Variable(RuleVariable),
+    /// A number literal.
+    Number(f64),
+}
+
+/// Comparison operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// `>=`
+    Ge,
+    /// `>`
+    Gt,
+    /// `<=`
+    Le,
+    /// `<`
+    Lt,
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+}
+
+impl Condition {
+    /// Parses an expression such as `pass_count >= 2 && avg_score >= 80`.
+    ///
+    /// `&&` binds tighter than `||`; parentheses group.
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+        let condition = parser.or()?;
+        match parser.peek() {
+            (Token::End, _) => Ok(condition),
+            (Token::Compare(_), column) => Err(ExpressionError {
+                column,
+                message: "comparisons cannot be chained; combine them with && or ||".to_string(),
+            }),
+            (token, column) => Err(ExpressionError {
+                column,
+                message: format!("expected && or ||, found {}", token),
+            }),
+        }
+    }
+
+    fn holds(&self, tally: &Tally) -> bool {
+        match self {
+            Condition::Compare(left, op, right) => {
+                let (left, right) = (tally.value(left), tally.value(right));
+                match op {
+                    Comparison::Ge => left >= right,
+                    Comparison::Gt => left > right,
+                    Comparison::Le => left <= right,
+                    Comparison::Lt => left < right,
+                    Comparison::Eq => left == right,
+                    Comparison::Ne => left != right,
+                }
+            }
+            Condition::And(left, right) => left.holds(tally) && right.holds(tally),
+            Condition::Or(left, right) => left.holds(tally) || right.holds(tally),
+        }
+    }
+}
+
+/// Values of the [`RuleVariable`]s for a set of votes.
+struct Tally {
+    pass_count: f64,
+    warn_count: f64,
+    fail_count: f64,
+    avg_score: f64,
+    min_score_vote: f64,
+    min_score: f64,
+}
+
+impl std::fmt::Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "pass_count={}, warn_count={}, fail_count={}, avg_score={:.1}, min_score_vote={}, min_score={}",
+            self.pass_count,
+            self.warn_count,
+            self.fail_count,
+            self.avg_score,
+            self.min_score_vote,
+            self.min_score
+        )
+    }
+}
+
+impl Tally {
+    fn of(votes: &HashMap<String, ModelVote>, min_score: u8, weights: &VoteWeights) -> Self {
+        let count = |vote: Vote| votes.values().filter(|v| v.vote == vote).count() as f64;
+        let (total, weight) = votes.iter().fold((0.0, 0.0), |(total, weight), (name, v)| {
+            let w = weights.of(name) as f64;
+            (total + v.score as f64 * w, weight + w)
+        });
+        Self {
+            pass_count: count(Vote::Pass),
+            warn_count: count(Vote::Warn),
+            fail_count: count(Vote::Fail),
+            avg_score: if votes.is_empty() {
+                0.0
+            } else {
+                total / weight
+            },
+            min_score_vote: votes.values().map(|v| v.score).min().unwrap_or(0) as f64,
+            min_score: min_score as f64,
+        }
+    }
+
+    fn value(&self, operand: &Operand) -> f64 {
+        match operand {
+            Operand::Number(number) => *number,
+            Operand::Variable(RuleVariable::PassCount) => self.pass_count,
+            Operand::Variable(RuleVariable::WarnCount) => self.warn_count,
+            Operand::Variable(RuleVariable::FailCount) => self.fail_count,
+            Operand::Variable(RuleVariable::AvgScore) => self.avg_score,
+            Operand::Variable(RuleVariable::MinScoreVote) => self.min_score_vote,
+            Operand::Variable(RuleVariable::MinScore) => self.min_score,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Variable(RuleVariable, String),
+    Number(f64),
+    Compare(Comparison),
+    And,
+    Or,
+    Open,
+    Close,
+    End,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Variable(_, name) => write!(f, "`{}`", name),
+            Token::Number(number) => write!(f, "`{}`", number),
+            Token::Compare(op) => f.write_str(match op {
+                Comparison::Ge => "`>=`",
+                Comparison::Gt => "`>`",
+                Comparison::Le => "`<=`",
+                Comparison::Lt => "`<`",
+                Comparison::Eq => "`==`",
+                Comparison::Ne => "`!=`",
+            }),
+            Token::And => f.write_str("`&&`"),
+            Token::Or => f.write_str("`||`"),
+            Token::Open => f.write_str("`(`"),
+            Token::Close => f.write_str("`)`"),
+            Token::End => f.write_str("the end of the expression"),
+        }
+    }
+}
+
+/// Splits the expression into tokens paired with their 1-based column.
+fn tokenize(source: &str) -> Result<Vec<(Token, usize)>, ExpressionError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let column = i + 1;
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            let Some(variable) = RuleVariable::from_name(&name) else {
+                let known: Vec<&str> = RuleVariable::ALL.iter().map(|(name, _)| *name).collect();
+                return Err(ExpressionError {
+                    column,
+                    message: format!(
+                        "unknown variable `{}` (expected one of {})",
+                        name,
+                        known.join(", ")
+                    ),
+                });
+            };
+            tokens.push((Token::Variable(variable, name), column));
+            continue;
+        }
+
+        if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let number = literal.parse().map_err(|_| ExpressionError {
+                column,
+                message: format!("invalid number `{}`", literal),
+            })?;
+            tokens.push((Token::Number(number), column));
+            continue;
+        }
+
+        let (token, width) = match (c, next) {
+            ('>', Some('=')) => (Token::Compare(Comparison::Ge), 2),
+            ('<', Some('=')) => (Token::Compare(Comparison::Le), 2),
+            ('=', Some('=')) => (Token::Compare(Comparison::Eq), 2),
+            ('!', Some('=')) => (Token::Compare(Comparison::Ne), 2),
+            ('&', Some('&')) => (Token::And, 2),
+            ('|', Some('|')) => (Token::Or, 2),
+            ('>', _) => (Token::Compare(Comparison::Gt), 1),
+            ('<', _) => (Token::Compare(Comparison::Lt), 1),
+            ('(', _) => (Token::Open, 1),
+            (')', _) => (Token::Close, 1),
+            ('=', _) => {
+                return Err(ExpressionError {
+                    column,
+                    message: "unexpected `=`; use `==` to compare".to_string(),
+                })
+            }
+            ('&' | '|', _) => {
+                return Err(ExpressionError {
+                    column,
+                    message: format!("unexpected `{}`; use `{}{}`", c, c, c),
+                })
+            }
+            _ => {
+                return Err(ExpressionError {
+                    column,
+                    message: format!("unexpected character `{}`", c),
+                })
+            }
+        };
+        tokens.push((token, column));
+        i += width;
+    }
+    tokens.push((Token::End, chars.len() + 1));
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens:
+///
+/// ```text
+/// or         := and ("||" and)*
+/// and        := primary ("&&" primary)*
+/// primary    := "(" or ")" | comparison
+/// comparison := operand op operand
+/// ```
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> (&Token, usize) {
+        let (token, column) = &self.tokens[self.position.min(self.tokens.len() - 1)];
+        (token, *column)
+    }
+
+    fn advance(&mut self) -> (&Token, usize) {
+        let position = self.position.min(self.tokens.len() - 1);
+        self.position += 1;
+        let (token, column) = &self.tokens[position];
+        (token, *column)
+    }
+
+    fn or(&mut self) -> Result<Condition, ExpressionError> {
+        let mut condition = self.and()?;
+        while matches!(self.peek().0, Token::Or) {
+            self.advance();
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, ExpressionError> {
+        let mut condition = self.primary()?;
+        while matches!(self.peek().0, Token::And) {
+            self.advance();
+            condition = Condition::And(Box::new(condition), Box::new(self.primary()?));
+        }
+        Ok(condition)
+    }
+
+    fn primary(&mut self) -> Result<Condition, ExpressionError> {
+        let (token, column) = self.peek();
+        if *token != Token::Open {
+            return self.comparison();
+        }
+
+        self.advance();
+        let condition = self.or()?;
+        match self.advance() {
+            (Token::Close, _) => Ok(condition),
+            (token, _) => Err(ExpressionError {
+                column,
+                message: format!("unclosed `(`, found {}", token),
+            }),
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Condition, ExpressionError> {
+        let left = self.operand()?;
+        let (token, column) = self.advance();
+        let Token::Compare(op) = *token else {
+            return Err(ExpressionError {
+                column,
+                message: format!(
+                    "expected a comparison (>=, >, <=, <, ==, !=), found {}",
+                    token
+                ),
+            });
+        };
+        let right = self.operand()?;
+        Ok(Condition::Compare(left, op, right))
+    }
+
+    fn operand(&mut self) -> Result<Operand, ExpressionError> {
+        match self.advance() {
+            (Token::Variable(variable, _), _) => Ok(Operand::Variable(*variable)),
+            (Token::Number(number), _) => Ok(Operand::Number(*number)),
+            (token, column) => Err(ExpressionError {
+                column,
+                message: format!("expected a variable or a number, found {}", token),
+            }),
+        }
+    }
+}
+
+/// Creates a consensus rule from configuration for `voters` enabled executors,
+/// tallying votes with `weights`.
+///
+/// The `custom` rule uses the parsed `custom` expressions; without them it
+/// falls back to the Strong rule.
+pub fn create_rule(
+    config: &ConsensusRuleConfig,
+    custom: Option<&CustomRule>,
+    voters: usize,
+    weights: &VoteWeights,
+) -> Box<dyn ConsensusRule> {
+    let weights = weights.clone();
+    match config {
+        ConsensusRuleConfig::Golden => Box::new(GoldenRule::new(voters).with_weights(weights)),
+        ConsensusRuleConfig::Strong => Box::new(StrongRule::new(voters).with_weights(weights)),
+        ConsensusRuleConfig::Weak => Box::new(WeakRule::new(voters).with_weights(weights)),
+        ConsensusRuleConfig::Custom => match custom {
+            Some(custom) => Box::new(custom.clone().with_voters(voters).with_weights(weights)),
+            None => {
+                tracing::warn!("consensus.default_rule is custom without [consensus.custom]; using the strong rule");
+                Box::new(StrongRule::new(voters).with_weights(weights))
+            }
+        },
+    }
+}
+
+/// Scales the configured rule to the executors that actually voted.
+///
+/// One voter gets [`SingleEvaluatorRule`], two get [`DualEvaluatorRule`], and
+/// three or more get the configured rule for `voted` voters when fewer than
+/// `expected` voted. Returns `None` when the configured rule applies as is.
+pub fn adaptive_rule(
+    config: &ConsensusRuleConfig,
+    custom: Option<&CustomRule>,
+    expected: usize,
+    voted: usize,
+    weights: &VoteWeights,
+) -> Option<Box<dyn ConsensusRule>> {
+    match voted {
+        0 => None,
+        1 => Some(Box::new(SingleEvaluatorRule)),
+        2 => Some(Box::new(
+            DualEvaluatorRule::default().with_weights(weights.clone()),
+        )),
+        _ if voted < expected => Some(create_rule(config, custom, voted, weights)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::vote_map;
+
     // Testes para GoldenRule
     #[test]
     fn test_golden_rule_all_pass() {
-        let rule = GoldenRule;
-        let votes = create_votes(vec![
+        let rule = GoldenRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Pass, 85),
             ("Gemini", Vote::Pass, 90),
             ("Qwen", Vote::Pass, 88),
@@ -233,8 +1300,8 @@ mod tests {
 
     #[test]
     fn test_golden_rule_one_fail() {
-        let rule = GoldenRule;
-        let votes = create_votes(vec![
+        let rule = GoldenRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Pass, 85),
             ("Gemini", Vote::Fail, 40),
             ("Qwen", Vote::Pass, 88),
@@ -246,8 +1313,8 @@ mod tests {
 
     #[test]
     fn test_golden_rule_low_score() {
-        let rule = GoldenRule;
-        let votes = create_votes(vec![
+        let rule = GoldenRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Pass, 60),
             ("Gemini", Vote::Pass, 65),
             ("Qwen", Vote::Pass, 68),
@@ -259,8 +1326,8 @@ mod tests {
     // Testes para StrongRule
     #[test]
     fn test_strong_rule_all_pass() {
-        let rule = StrongRule;
-        let votes = create_votes(vec![
+        let rule = StrongRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Pass, 85),
             ("Gemini", Vote::Pass, 90),
             ("Qwen", Vote::Pass, 88),
@@ -273,8 +1340,8 @@ mod tests {
     #[test]
     fn test_strong_rule_not_unanimous_revise() {
         // Strong Rule exige 3/3 - 2 PASS + 1 WARN = Revise
-        let rule = StrongRule;
-        let votes = create_votes(vec![
+        let rule = StrongRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Pass, 85),
             ("Gemini", Vote::Pass, 90),
             ("Qwen", Vote::Warn, 65),
@@ -286,8 +1353,8 @@ mod tests {
     #[test]
     fn test_strong_rule_not_unanimous_fail() {
         // Strong Rule exige 3/3 - 2 FAIL + 1 PASS = Revise (não Block)
-        let rule = StrongRule;
-        let votes = create_votes(vec![
+        let rule = StrongRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Fail, 30),
             ("Gemini", Vote::Fail, 25),
             ("Qwen", Vote::Pass, 85),
@@ -299,8 +1366,8 @@ mod tests {
     #[test]
     fn test_strong_rule_all_fail() {
         // Strong Rule: 3/3 FAIL = Block
-        let rule = StrongRule;
-        let votes = create_votes(vec![
+        let rule = StrongRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Fail, 30),
             ("Gemini", Vote::Fail, 25),
             ("Qwen", Vote::Fail, 20),
@@ -312,8 +1379,8 @@ mod tests {
     // Testes para WeakRule
     #[test]
     fn test_weak_rule_two_pass() {
-        let rule = WeakRule;
-        let votes = create_votes(vec![
+        let rule = WeakRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Pass, 85),
             ("Gemini", Vote::Pass, 90),
             ("Qwen", Vote::Fail, 30),
@@ -325,8 +1392,8 @@ mod tests {
 
     #[test]
     fn test_weak_rule_two_fail() {
-        let rule = WeakRule;
-        let votes = create_votes(vec![
+        let rule = WeakRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Fail, 30),
             ("Gemini", Vote::Fail, 25),
             ("Qwen", Vote::Pass, 85),
@@ -337,8 +1404,8 @@ mod tests {
 
     #[test]
     fn test_weak_rule_no_majority() {
-        let rule = WeakRule;
-        let votes = create_votes(vec![
+        let rule = WeakRule::default();
+        let votes = vote_map(&[
             ("Codex", Vote::Pass, 85),
             ("Gemini", Vote::Warn, 60),
             ("Qwen", Vote::Fail, 30),
@@ -347,16 +1414,610 @@ mod tests {
         assert_eq!(rule.evaluate(&votes, 70), Decision::Revise);
     }
 
+    #[test]
+    fn test_weak_rule_weighted_majority() {
+        let weights = VoteWeights::new([("codex", 5), ("gemini", 1), ("qwen", 1)]);
+        let rule = WeakRule::default().with_weights(weights);
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Warn, 60),
+            ("Qwen", Vote::Fail, 30),
+        ]);
+
+        // Sem pesos seria Revise; o PASS do Codex tem 5 de 7 do peso
+        let explanation = rule.explain(&votes, 70);
+        assert_eq!(explanation.decision, Decision::Pass);
+        assert_eq!(explanation.drivers, vec!["Codex".to_string()]);
+        assert!(explanation.detail.contains("weight 5 of 7"));
+
+        // Dois votos leves juntos não superam o pesado
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Fail, 30),
+            ("Qwen", Vote::Fail, 30),
+        ]);
+        assert_eq!(rule.evaluate(&votes, 70), Decision::Pass);
+    }
+
+    #[test]
+    fn test_golden_rule_heavy_executor_cannot_pass_alone() {
+        let weights = VoteWeights::new([("codex", 10), ("gemini", 1), ("qwen", 1)]);
+        let rule = GoldenRule::default().with_weights(weights);
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 95),
+            ("Gemini", Vote::Warn, 60),
+            ("Qwen", Vote::Warn, 60),
+        ]);
+
+        assert_eq!(rule.evaluate(&votes, 70), Decision::Revise);
+    }
+
+    #[test]
+    fn test_uniform_weights_keep_unweighted_explanations() {
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Pass, 90),
+            ("Qwen", Vote::Fail, 30),
+        ]);
+        let weights = VoteWeights::new([("codex", 4), ("gemini", 4), ("qwen", 4)]);
+
+        assert_eq!(
+            WeakRule::default()
+                .with_weights(weights)
+                .explain(&votes, 70),
+            WeakRule::default().explain(&votes, 70)
+        );
+    }
+
+    #[test]
+    fn test_vote_weights_of() {
+        let weights = VoteWeights::new([("Codex", 4), ("qwen", 2)]);
+
+        assert_eq!(weights.of("codex"), 4);
+        assert_eq!(weights.of("Codex"), 4);
+        // Um fallback pesa como o executor que respondeu
+        assert_eq!(weights.of("Qwen (fallback for Gemini)"), 2);
+        // Votante desconhecido pesa a média
+        assert_eq!(weights.of("Gemini"), 3);
+        // Sem pesos, todo voto pesa 1
+        assert_eq!(VoteWeights::default().of("Codex"), 1);
+        assert!(!weights.is_uniform());
+        assert!(VoteWeights::default().is_uniform());
+    }
+
     // Testes para create_rule
     #[test]
     fn test_create_rule() {
-        let golden = create_rule(&ConsensusRuleConfig::Golden);
+        let golden = create_rule(
+            &ConsensusRuleConfig::Golden,
+            None,
+            DEFAULT_VOTERS,
+            &VoteWeights::default(),
+        );
         assert_eq!(golden.name(), "golden");
 
-        let strong = create_rule(&ConsensusRuleConfig::Strong);
+        let strong = create_rule(
+            &ConsensusRuleConfig::Strong,
+            None,
+            DEFAULT_VOTERS,
+            &VoteWeights::default(),
+        );
         assert_eq!(strong.name(), "strong");
 
-        let weak = create_rule(&ConsensusRuleConfig::Weak);
+        let weak = create_rule(
+            &ConsensusRuleConfig::Weak,
+            None,
+            DEFAULT_VOTERS,
+            &VoteWeights::default(),
+        );
         assert_eq!(weak.name(), "weak");
     }
+
+    #[test]
+    fn test_rules_scale_with_voters() {
+        let five_pass = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Pass, 90),
+            ("Qwen", Vote::Pass, 88),
+            ("llm", Vote::Pass, 80),
+            ("local", Vote::Pass, 82),
+        ]);
+
+        // Strong with 5 voters requires 5/5
+        let strong = StrongRule::new(5);
+        assert_eq!(strong.min_required(), 5);
+        assert_eq!(strong.evaluate(&five_pass, 70), Decision::Pass);
+
+        let mut four_pass = five_pass.clone();
+        four_pass.get_mut("local").unwrap().vote = Vote::Warn;
+        assert_eq!(strong.evaluate(&four_pass, 70), Decision::Revise);
+
+        // Weak with 5 voters requires a majority of 3
+        let weak = WeakRule::new(5);
+        assert_eq!(weak.min_required(), 3);
+        let two_pass = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Pass, 90),
+            ("Qwen", Vote::Warn, 60),
+            ("llm", Vote::Warn, 60),
+            ("local", Vote::Fail, 30),
+        ]);
+        assert_eq!(weak.evaluate(&two_pass, 70), Decision::Revise);
+
+        // Golden with 2 voters does not wait for a third
+        let golden = GoldenRule::new(2);
+        let two_votes = vote_map(&[("Codex", Vote::Pass, 85), ("llm", Vote::Pass, 90)]);
+        assert_eq!(golden.evaluate(&two_votes, 70), Decision::Pass);
+    }
+
+    // Testes para as regras adaptativas
+    #[test]
+    fn test_single_evaluator_rule() {
+        let rule = SingleEvaluatorRule;
+        let pass = vote_map(&[("Codex", Vote::Pass, 85)]);
+        assert_eq!(rule.evaluate(&pass, 70), Decision::Pass);
+        assert!(rule.is_consensus_achieved(&pass, 70));
+
+        // O score mínimo continua valendo
+        assert_eq!(rule.evaluate(&pass, 90), Decision::Revise);
+
+        let warn = vote_map(&[("Codex", Vote::Warn, 85)]);
+        assert_eq!(rule.evaluate(&warn, 70), Decision::Revise);
+        assert!(!rule.is_consensus_achieved(&warn, 70));
+
+        let fail = vote_map(&[("Codex", Vote::Fail, 30)]);
+        assert_eq!(rule.evaluate(&fail, 70), Decision::Block);
+        assert!(rule.is_consensus_achieved(&fail, 70));
+
+        assert_eq!(rule.evaluate(&HashMap::new(), 70), Decision::Revise);
+    }
+
+    #[test]
+    fn test_dual_evaluator_rule() {
+        let rule = DualEvaluatorRule::default();
+        let agree = vote_map(&[("Codex", Vote::Pass, 85), ("Gemini", Vote::Pass, 80)]);
+        assert_eq!(rule.evaluate(&agree, 70), Decision::Pass);
+        assert!(rule.is_consensus_achieved(&agree, 70));
+        assert_eq!(rule.evaluate(&agree, 90), Decision::Revise);
+
+        let disagree = vote_map(&[("Codex", Vote::Pass, 85), ("Gemini", Vote::Fail, 30)]);
+        assert_eq!(rule.evaluate(&disagree, 70), Decision::Revise);
+        assert!(!rule.is_consensus_achieved(&disagree, 70));
+
+        let both_fail = vote_map(&[("Codex", Vote::Fail, 20), ("Gemini", Vote::Fail, 30)]);
+        assert_eq!(rule.evaluate(&both_fail, 70), Decision::Block);
+
+        let one = vote_map(&[("Codex", Vote::Pass, 85)]);
+        assert_eq!(rule.evaluate(&one, 70), Decision::Revise);
+    }
+
+    #[test]
+    fn test_adaptive_rule_selection() {
+        for config in [
+            ConsensusRuleConfig::Golden,
+            ConsensusRuleConfig::Strong,
+            ConsensusRuleConfig::Weak,
+        ] {
+            assert!(adaptive_rule(&config, None, 3, 0, &VoteWeights::default()).is_none());
+            assert_eq!(
+                adaptive_rule(&config, None, 3, 1, &VoteWeights::default())
+                    .unwrap()
+                    .name(),
+                "single-evaluator"
+            );
+            assert_eq!(
+                adaptive_rule(&config, None, 3, 2, &VoteWeights::default())
+                    .unwrap()
+                    .name(),
+                "dual-evaluator"
+            );
+            assert!(adaptive_rule(&config, None, 3, 3, &VoteWeights::default()).is_none());
+
+            // Com mais assentos, a regra configurada é reduzida aos votantes
+            let scaled = adaptive_rule(&config, None, 5, 3, &VoteWeights::default()).unwrap();
+            assert_eq!(
+                scaled.name(),
+                create_rule(&config, None, 3, &VoteWeights::default()).name()
+            );
+            assert_eq!(
+                scaled.min_required(),
+                create_rule(&config, None, 3, &VoteWeights::default()).min_required()
+            );
+        }
+    }
+
+    // Testes para CustomRule
+    fn custom(pass: &str, block: &str) -> CustomRule {
+        CustomRule::from_config(&CustomRuleConfig {
+            pass: pass.to_string(),
+            block: block.to_string(),
+        })
+        .unwrap()
+    }
+
+    fn parse_error(source: &str) -> ExpressionError {
+        Condition::parse(source).unwrap_err()
+    }
+
+    #[test]
+    fn test_expression_parses_comparison() {
+        assert_eq!(
+            Condition::parse("pass_count >= 2").unwrap(),
+            Condition::Compare(
+                Operand::Variable(RuleVariable::PassCount),
+                Comparison::Ge,
+                Operand::Number(2.0)
+            )
+        );
+
+        // Números podem ficar à esquerda e variáveis dos dois lados
+        assert_eq!(
+            Condition::parse("80 <= avg_score").unwrap(),
+            Condition::Compare(
+                Operand::Number(80.0),
+                Comparison::Le,
+                Operand::Variable(RuleVariable::AvgScore)
+            )
+        );
+        assert!(Condition::parse("pass_count > fail_count").is_ok());
+        assert!(Condition::parse("min_score_vote>=min_score").is_ok());
+        assert!(Condition::parse("avg_score < 72.5").is_ok());
+    }
+
+    #[test]
+    fn test_expression_operators() {
+        for (source, op) in [
+            ("warn_count >= 1", Comparison::Ge),
+            ("warn_count > 1", Comparison::Gt),
+            ("warn_count <= 1", Comparison::Le),
+            ("warn_count < 1", Comparison::Lt),
+            ("warn_count == 1", Comparison::Eq),
+            ("warn_count != 1", Comparison::Ne),
+        ] {
+            let Condition::Compare(_, parsed, _) = Condition::parse(source).unwrap() else {
+                panic!("{} is not a comparison", source);
+            };
+            assert_eq!(parsed, op, "{}", source);
+        }
+    }
+
+    #[test]
+    fn test_expression_and_binds_tighter_than_or() {
+        let compare = |variable, number| {
+            Box::new(Condition::Compare(
+                Operand::Variable(variable),
+                Comparison::Ge,
+                Operand::Number(number),
+            ))
+        };
+
+        assert_eq!(
+            Condition::parse("fail_count >= 1 || pass_count >= 2 && avg_score >= 80").unwrap(),
+            Condition::Or(
+                compare(RuleVariable::FailCount, 1.0),
+                Box::new(Condition::And(
+                    compare(RuleVariable::PassCount, 2.0),
+                    compare(RuleVariable::AvgScore, 80.0)
+                ))
+            )
+        );
+
+        assert_eq!(
+            Condition::parse("(fail_count >= 1 || pass_count >= 2) && avg_score >= 80").unwrap(),
+            Condition::And(
+                Box::new(Condition::Or(
+                    compare(RuleVariable::FailCount, 1.0),
+                    compare(RuleVariable::PassCount, 2.0)
+                )),
+                compare(RuleVariable::AvgScore, 80.0)
+            )
+        );
+    }
+
+    #[test]
+    fn test_expression_errors_have_columns() {
+        let error = parse_error("pas_count >= 2");
+        assert_eq!(error.column, 1);
+        assert!(error.message.starts_with("unknown variable `pas_count`"));
+        assert!(error.message.contains("pass_count"));
+
+        let error = parse_error("pass_count >= 2 && avg_scor >= 80");
+        assert_eq!(error.column, 20);
+
+        let error = parse_error("pass_count 2");
+        assert_eq!(error.column, 12);
+        assert!(error.message.starts_with("expected a comparison"));
+
+        let error = parse_error("pass_count >=");
+        assert_eq!(error.column, 14);
+        assert!(error.message.contains("the end of the expression"));
+
+        let error = parse_error("pass_count = 2");
+        assert_eq!(error.column, 12);
+        assert!(error.message.contains("`==`"));
+
+        let error = parse_error("pass_count >= 2 & avg_score >= 80");
+        assert_eq!(error.column, 17);
+        assert!(error.message.contains("`&&`"));
+
+        let error = parse_error("(pass_count >= 2");
+        assert_eq!(error.column, 1);
+        assert!(error.message.starts_with("unclosed `(`"));
+
+        let error = parse_error("pass_count >= 2)");
+        assert_eq!(error.column, 16);
+
+        let error = parse_error("1 < pass_count < 3");
+        assert_eq!(error.column, 16);
+        assert!(error.message.contains("chained"));
+
+        let error = parse_error("avg_score >= 8.0.1");
+        assert_eq!(error.column, 14);
+        assert!(error.message.contains("invalid number"));
+
+        let error = parse_error("pass_count >= 2 # comment");
+        assert_eq!(error.column, 17);
+
+        let error = parse_error("   ");
+        assert_eq!(error.column, 4);
+
+        assert_eq!(error.to_string(), format!("column 4: {}", error.message));
+    }
+
+    #[test]
+    fn test_expression_columns_count_characters() {
+        // Colunas em caracteres, não bytes
+        let error = parse_error("pass_count >= 2 && é");
+        assert_eq!(error.column, 20);
+    }
+
+    #[test]
+    fn test_custom_rule_policy() {
+        // Bloqueia com qualquer FAIL; aprova com média >= 80 e ao menos dois PASS
+        let rule = custom("pass_count >= 2 && avg_score >= 80", "fail_count >= 1");
+        assert_eq!(rule.name(), "custom");
+        assert_eq!(rule.min_required(), DEFAULT_VOTERS);
+
+        let pass = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Pass, 90),
+            ("Qwen", Vote::Warn, 70),
+        ]);
+        assert_eq!(rule.evaluate(&pass, 70), Decision::Pass);
+        assert!(rule.is_consensus_achieved(&pass, 70));
+
+        let low_average = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Pass, 80),
+            ("Qwen", Vote::Warn, 60),
+        ]);
+        assert_eq!(rule.evaluate(&low_average, 70), Decision::Revise);
+        assert!(!rule.is_consensus_achieved(&low_average, 70));
+
+        let one_fail = vote_map(&[
+            ("Codex", Vote::Pass, 95),
+            ("Gemini", Vote::Pass, 95),
+            ("Qwen", Vote::Fail, 90),
+        ]);
+        assert_eq!(rule.evaluate(&one_fail, 70), Decision::Block);
+        assert!(rule.is_consensus_achieved(&one_fail, 70));
+    }
+
+    #[test]
+    fn test_custom_rule_average_is_exact() {
+        // 85 + 80 + 74 = 239 / 3 = 79.67: não arredonda para 80
+        let rule = custom("avg_score >= 80", "");
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Pass, 80),
+            ("Qwen", Vote::Pass, 74),
+        ]);
+        assert_eq!(rule.evaluate(&votes, 70), Decision::Revise);
+        assert_eq!(
+            custom("avg_score > 79.6", "").evaluate(&votes, 70),
+            Decision::Pass
+        );
+    }
+
+    #[test]
+    fn test_custom_rule_variables() {
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 90),
+            ("Gemini", Vote::Warn, 60),
+            ("Qwen", Vote::Fail, 30),
+        ]);
+
+        for (expression, holds) in [
+            ("pass_count == 1", true),
+            ("warn_count == 1", true),
+            ("fail_count == 1", true),
+            ("avg_score == 60", true),
+            ("min_score_vote == 30", true),
+            ("min_score == 75", true),
+            ("min_score_vote >= min_score", false),
+            ("pass_count != 1", false),
+            ("pass_count > 1 || warn_count < 2", true),
+            ("pass_count > 1 || warn_count > 2", false),
+        ] {
+            let decision = custom(expression, "").evaluate(&votes, 75);
+            let expected = if holds {
+                Decision::Pass
+            } else {
+                Decision::Revise
+            };
+            assert_eq!(decision, expected, "{}", expression);
+        }
+    }
+
+    #[test]
+    fn test_custom_rule_block_wins_over_pass() {
+        let rule = custom("pass_count >= 1", "warn_count >= 1");
+        let votes = vote_map(&[("Codex", Vote::Pass, 90), ("Gemini", Vote::Warn, 60)]);
+        assert_eq!(rule.evaluate(&votes, 70), Decision::Block);
+    }
+
+    #[test]
+    fn test_custom_rule_without_votes() {
+        let rule = custom("min_score_vote >= 0 && avg_score == 0", "");
+        assert_eq!(rule.evaluate(&HashMap::new(), 70), Decision::Pass);
+        assert!(!rule.is_consensus_achieved(&HashMap::new(), 70));
+    }
+
+    #[test]
+    fn test_custom_rule_scales_to_voters() {
+        let rule = custom("pass_count >= 2", "").with_voters(5);
+        assert_eq!(rule.min_required(), 5);
+
+        let votes = vote_map(&[("Codex", Vote::Pass, 90), ("Gemini", Vote::Pass, 90)]);
+        assert_eq!(rule.evaluate(&votes, 70), Decision::Pass);
+        // Com menos votos que votantes, não há consenso
+        assert!(!rule.is_consensus_achieved(&votes, 70));
+
+        let created = create_rule(
+            &ConsensusRuleConfig::Custom,
+            Some(&rule),
+            4,
+            &VoteWeights::default(),
+        );
+        assert_eq!(created.name(), "custom");
+        assert_eq!(created.min_required(), 4);
+    }
+
+    #[test]
+    fn test_custom_rule_config_errors() {
+        let problems = |pass: &str, block: &str| match CustomRule::from_config(&CustomRuleConfig {
+            pass: pass.to_string(),
+            block: block.to_string(),
+        }) {
+            Err(TetradError::InvalidConfig(problems)) => problems,
+            other => panic!("expected InvalidConfig, got {:?}", other.map(|_| ())),
+        };
+
+        let found = problems("pass_count >= 2 &&", "fail_cnt >= 1");
+        assert_eq!(found.len(), 2);
+        assert!(found[0].starts_with("consensus.custom.pass (column 19): "));
+        assert!(found[1].starts_with("consensus.custom.block (column 1): unknown variable"));
+
+        // `pass` é obrigatório; `block` vazio nunca bloqueia
+        let found = problems("", "");
+        assert_eq!(found.len(), 1);
+        assert!(found[0].starts_with("consensus.custom.pass (column 1): "));
+    }
+
+    #[test]
+    fn test_custom_rule_without_expressions_falls_back_to_strong() {
+        let rule = create_rule(
+            &ConsensusRuleConfig::Custom,
+            None,
+            DEFAULT_VOTERS,
+            &VoteWeights::default(),
+        );
+        assert_eq!(rule.name(), "strong");
+    }
+
+    #[test]
+    fn test_adaptive_custom_rule() {
+        let rule = custom("pass_count >= 2", "");
+        let config = ConsensusRuleConfig::Custom;
+        assert_eq!(
+            adaptive_rule(&config, Some(&rule), 3, 1, &VoteWeights::default())
+                .unwrap()
+                .name(),
+            "single-evaluator"
+        );
+        let scaled = adaptive_rule(&config, Some(&rule), 5, 3, &VoteWeights::default()).unwrap();
+        assert_eq!(scaled.name(), "custom");
+        assert_eq!(scaled.min_required(), 3);
+        assert!(adaptive_rule(&config, Some(&rule), 3, 3, &VoteWeights::default()).is_none());
+    }
+
+    #[test]
+    fn test_explain_golden_any_fail() {
+        let votes = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Fail, 40),
+            ("Qwen", Vote::Pass, 88),
+        ]);
+
+        let explanation = GoldenRule::default().explain(&votes, 70);
+        assert_eq!(explanation.rule, "golden");
+        assert_eq!(explanation.decision, Decision::Block);
+        assert_eq!(explanation.condition, "any_fail");
+        assert_eq!(explanation.drivers, vec!["Gemini"]);
+        assert_eq!((explanation.pass_count, explanation.fail_count), (2, 1));
+        assert_eq!(explanation.votes, 3);
+        assert_eq!(explanation.min_required, 3);
+    }
+
+    #[test]
+    fn test_explain_strong_conditions() {
+        let rule = StrongRule::default();
+
+        let split = vote_map(&[
+            ("Codex", Vote::Pass, 85),
+            ("Gemini", Vote::Warn, 60),
+            ("Qwen", Vote::Fail, 40),
+        ]);
+        let explanation = rule.explain(&split, 70);
+        assert_eq!(explanation.decision, Decision::Revise);
+        assert_eq!(explanation.condition, "disagreement");
+        assert_eq!(explanation.drivers, vec!["Gemini", "Qwen"]);
+        assert!(explanation.detail.contains("1 PASS, 1 WARN, 1 FAIL"));
+
+        let low = vote_map(&[
+            ("Codex", Vote::Pass, 60),
+            ("Gemini", Vote::Pass, 75),
+            ("Qwen", Vote::Pass, 65),
+        ]);
+        let explanation = rule.explain(&low, 70);
+        assert_eq!(explanation.condition, "low_score");
+        assert_eq!(explanation.drivers, vec!["Codex", "Qwen"]);
+        assert_eq!(explanation.min_score, 70);
+
+        let partial = vote_map(&[("Codex", Vote::Pass, 90)]);
+        let explanation = rule.explain(&partial, 70);
+        assert_eq!(explanation.condition, "insufficient_votes");
+        assert!(explanation.drivers.is_empty());
+    }
+
+    #[test]
+    fn test_explain_weak_majority_fail() {
+        let votes = vote_map(&[
+            ("Codex", Vote::Fail, 30),
+            ("Gemini", Vote::Pass, 90),
+            ("Qwen", Vote::Fail, 20),
+        ]);
+
+        let explanation = WeakRule::default().explain(&votes, 70);
+        assert_eq!(explanation.decision, Decision::Block);
+        assert_eq!(explanation.condition, "majority_fail");
+        assert_eq!(explanation.drivers, vec!["Codex", "Qwen"]);
+        // evaluate() é a decisão da explicação
+        assert_eq!(WeakRule::default().evaluate(&votes, 70), Decision::Block);
+    }
+
+    #[test]
+    fn test_explain_custom_expressions() {
+        let rule = custom("pass_count >= 2", "fail_count >= 1");
+
+        let one_fail = vote_map(&[
+            ("Codex", Vote::Pass, 95),
+            ("Gemini", Vote::Pass, 95),
+            ("Qwen", Vote::Fail, 90),
+        ]);
+        let explanation = rule.explain(&one_fail, 70);
+        assert_eq!(explanation.condition, "block_expression");
+        assert_eq!(explanation.drivers, vec!["Qwen"]);
+        assert!(explanation.detail.contains("`fail_count >= 1`"));
+
+        let neither = vote_map(&[
+            ("Codex", Vote::Pass, 95),
+            ("Gemini", Vote::Warn, 60),
+            ("Qwen", Vote::Warn, 60),
+        ]);
+        let explanation = rule.explain(&neither, 70);
+        assert_eq!(explanation.decision, Decision::Revise);
+        assert_eq!(explanation.condition, "no_expression");
+        assert!(explanation.drivers.is_empty());
+    }
 }
@@ -1,9 +1,12 @@
 //! Trait base para executores CLI.
 
 use async_trait::async_trait;
+use std::time::Duration;
 
-use crate::types::requests::EvaluationRequest;
-use crate::types::responses::ModelVote;
+use super::prompts::{render_template, PromptTemplates};
+use super::text_language::{detect_response_language, keywords, FallbackKeywords};
+use crate::types::requests::{diff_files, DocType, EvaluationRequest, EvaluationType};
+use crate::types::responses::{ModelVote, ResponseLanguage, SuggestedFix};
 use crate::{TetradError, TetradResult};
 
 /// Trait para executores CLI de avaliação de código.
@@ -55,6 +58,22 @@ pub trait CliExecutor: Send + Sync {
     /// Voto do modelo com score, issues e sugestões.
     async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote>;
 
+    /// Executa uma avaliação com o prazo dado no lugar do `timeout_secs` da
+    /// configuração (usado pelo timeout adaptativo).
+    ///
+    /// Os executores de CLI passam o prazo ao processo. A implementação
+    /// padrão só limita [`CliExecutor::evaluate`] por fora, então não
+    /// estende um prazo interno menor.
+    async fn evaluate_with_timeout(
+        &self,
+        request: &EvaluationRequest,
+        timeout: Duration,
+    ) -> TetradResult<ModelVote> {
+        tokio::time::timeout(timeout, self.evaluate(request))
+            .await
+            .map_err(|_| TetradError::ExecutorTimeout(self.name().to_string()))?
+    }
+
     /// Retorna a especialização deste executor.
     ///
     /// - "syntax" para foco em sintaxe e convenções
@@ -62,22 +81,54 @@ pub trait CliExecutor: Send + Sync {
     /// - "logic" para foco em bugs lógicos
     fn specialization(&self) -> &str;
 
+    /// Retorna os templates de prompt configurados para este executor.
+    ///
+    /// `None` usa apenas os templates embutidos.
+    fn prompt_templates(&self) -> Option<&PromptTemplates> {
+        None
+    }
+
     /// Constrói o prompt para a avaliação.
+    ///
+    /// Usa o template mais específico para o tipo de avaliação e a linguagem
+    /// (veja [`PromptTemplates`]) ou, sem template, o prompt genérico.
     fn build_prompt(&self, request: &EvaluationRequest) -> String {
         let eval_type = request.evaluation_type.to_string();
         let language = &request.language;
         let code = &request.code;
 
-        let mut prompt = format!(
-            "Avalie o seguinte código {} para {}.\n\n",
-            language, eval_type
-        );
-
-        prompt.push_str("Código:\n```\n");
-        prompt.push_str(code);
-        prompt.push_str("\n```\n\n");
-
-        if let Some(context) = &request.context {
+        let builtin = PromptTemplates::new();
+        let template = self
+            .prompt_templates()
+            .unwrap_or(&builtin)
+            .select(request.evaluation_type, language.name());
+
+        // Arquivos relacionados vêm antes do código revisado, só como referência
+        let mut prompt = build_related_files_section(request);
+        prompt.push_str(&if let Some(template) = template {
+            let mut header = render_template(template, request, self.specialization());
+            if !header.ends_with("\n\n") {
+                header.push_str(if header.ends_with('\n') { "\n" } else { "\n\n" });
+            }
+            header
+        } else if request.evaluation_type == EvaluationType::Documentation {
+            build_documentation_header(request)
+        } else if request.evaluation_type == EvaluationType::Diff {
+            build_diff_header(request)
+        } else {
+            let mut header = format!(
+                "Avalie o seguinte código {} para {}.\n\n",
+                language, eval_type
+            );
+            header.push_str("Código:\n```\n");
+            header.push_str(code);
+            header.push_str("\n```\n\n");
+            header
+        });
+
+        // Templates com {context} já posicionaram o contexto
+        let context_in_template = template.is_some_and(|t| t.contains("{context}"));
+        if let Some(context) = request.context.as_ref().filter(|_| !context_in_template) {
             prompt.push_str("Contexto adicional:\n");
             prompt.push_str(context);
             prompt.push_str("\n\n");
@@ -88,12 +139,135 @@ pub trait CliExecutor: Send + Sync {
         prompt.push_str("  \"vote\": \"PASS\" | \"WARN\" | \"FAIL\",\n");
         prompt.push_str("  \"score\": 0-100,\n");
         prompt.push_str("  \"reasoning\": \"explicação\",\n");
-        prompt.push_str("  \"issues\": [\"issue1\", \"issue2\"],\n");
+        prompt.push_str("  \"issues\": [{\"issue\": \"descrição\", \"lines\": [12, 13], \"suggestion\": \"como corrigir\", \"fix\": {\"start_line\": 12, \"end_line\": 13, \"replacement\": \"código corrigido\"}}],\n");
         prompt.push_str("  \"suggestions\": [\"sugestão1\", \"sugestão2\"]\n");
         prompt.push_str("}\n");
+        prompt.push_str(
+            "Em cada issue, \"lines\" lista as linhas afetadas do código avaliado e \"suggestion\" diz como corrigir aquele problema.\n",
+        );
+        prompt.push_str(
+            "O campo \"fix\" é opcional: inclua-o apenas quando souber o código corrigido exato.\n",
+        );
+        if request.evaluation_type == EvaluationType::Diff {
+            prompt.push_str(
+                "Em diffs, inclua \"lines\": [n] em cada issue com as linhas do arquivo novo (lado +).\n",
+            );
+        }
 
         prompt
     }
+
+    /// Converte a saída bruta da CLI em resposta estruturada.
+    ///
+    /// Diferente de `evaluate`, não recorre a heurísticas de texto quando o
+    /// JSON esperado está ausente; usado pelo `tetrad doctor --deep` para
+    /// detectar argumentos de CLI mal configurados.
+    fn parse_structured(&self, output: &str) -> TetradResult<ExecutorResponse> {
+        ExecutorResponse::parse_from_output(output, self.name())
+    }
+}
+
+/// Seção com os arquivos relacionados (vazia se não houver nenhum).
+fn build_related_files_section(request: &EvaluationRequest) -> String {
+    if request.related_files.is_empty() {
+        return String::new();
+    }
+
+    let mut section = String::from(
+        "Arquivos relacionados (contexto somente leitura; não os avalie, use-os \
+         apenas para entender tipos e funções referenciados):\n\n",
+    );
+    for file in &request.related_files {
+        section.push_str(&format!("Arquivo: {}\n```\n", file.path));
+        section.push_str(&file.content);
+        section.push_str("\n```\n\n");
+    }
+    section
+}
+
+/// Constrói o início do prompt para revisão de documentação.
+///
+/// A rubrica cobre precisão em relação ao código, completude, exemplos e
+/// tom, com um critério extra para o tipo de documentação, se informado.
+fn build_documentation_header(request: &EvaluationRequest) -> String {
+    let mut header = String::from("Revise a seguinte documentação.\n\n");
+
+    header.push_str("Documentação:\n```\n");
+    header.push_str(&request.code);
+    header.push_str("\n```\n\n");
+
+    if let Some(related) = &request.related_code {
+        header.push_str(&format!(
+            "Código relacionado ({}):\n```\n",
+            request.language
+        ));
+        header.push_str(related);
+        header.push_str("\n```\n\n");
+    }
+
+    header.push_str("Critérios:\n");
+    header.push_str("- Precisão: a documentação descreve corretamente o comportamento do código\n");
+    header
+        .push_str("- Completude: parâmetros, retornos, erros e casos de uso estão documentados\n");
+    if request.language.as_str() == "markdown" {
+        header.push_str("- Exemplos: os trechos de código citados funcionam como mostrado\n");
+    } else {
+        header.push_str(&format!(
+            "- Exemplos: os exemplos em {} compilam e estão corretos\n",
+            request.language
+        ));
+    }
+    header.push_str("- Tom: linguagem clara, consistente e adequada ao público\n");
+    if let Some(doc_type) = request.doc_type {
+        header.push_str(match doc_type {
+            DocType::Readme => {
+                "- README: instalação, uso básico e configuração funcionam como descrito\n"
+            }
+            DocType::Api => "- API: cada item público documenta assinatura, erros e pânicos\n",
+            DocType::Changelog => {
+                "- Changelog: cada entrada descreve a mudança real e quebras de compatibilidade\n"
+            }
+        });
+    }
+    header.push_str(
+        "Inicie cada issue com \"accuracy:\" (imprecisões, exemplos que não compilam), \
+         \"completeness:\" (itens não documentados) ou \"documentation:\" (tom, \
+         clareza, links e formatação).\n\n",
+    );
+
+    header
+}
+
+/// Constrói o início do prompt para revisão de um diff unificado.
+///
+/// O executor deve avaliar apenas as linhas alteradas; o restante do diff
+/// serve como contexto.
+fn build_diff_header(request: &EvaluationRequest) -> String {
+    let mut header = format!(
+        "Avalie apenas as linhas alteradas do seguinte diff {}, usando o contexto ao redor \
+         para entender a mudança. Não reporte problemas em linhas não alteradas.\n\n",
+        request.language
+    );
+
+    if let Some(file_path) = &request.file_path {
+        header.push_str(&format!("Arquivo: {}\n\n", file_path));
+    }
+
+    // Diffs com vários arquivos: cada issue precisa dizer de qual arquivo é
+    let files = diff_files(&request.code);
+    if files.len() > 1 {
+        header.push_str(&format!(
+            "O diff altera {} arquivos ({}). Inclua \"file\" em cada issue com um desses \
+             caminhos, o do arquivo afetado.\n\n",
+            files.len(),
+            files.join(", ")
+        ));
+    }
+
+    header.push_str("Diff:\n```diff\n");
+    header.push_str(&request.code);
+    header.push_str("\n```\n\n");
+    header
 }
 
 /// Resposta parseada de um executor.
@@ -103,9 +277,53 @@ pub struct ExecutorResponse {
     pub score: u8,
     pub reasoning: String,
     #[serde(default)]
-    pub issues: Vec<String>,
+    pub issues: Vec<ExecutorIssue>,
     #[serde(default)]
     pub suggestions: Vec<String>,
+    /// Idioma da resposta, quando ela foi analisada como texto livre.
+    #[serde(skip)]
+    pub text_language: Option<ResponseLanguage>,
+}
+
+/// Score do voto neutro dado a respostas num idioma não suportado.
+pub const NEUTRAL_TEXT_SCORE: u8 = 50;
+
+/// Issue reportado por um executor.
+///
+/// Aceita tanto texto simples quanto um objeto com correção opcional.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(untagged)]
+pub enum ExecutorIssue {
+    /// Apenas a descrição do issue.
+    Text(String),
+    /// Descrição com correção sugerida, linhas afetadas e sugestão própria.
+    Detailed {
+        issue: String,
+        #[serde(default)]
+        fix: Option<SuggestedFix>,
+        #[serde(default)]
+        lines: Option<Vec<u32>>,
+        #[serde(default)]
+        suggestion: Option<String>,
+        #[serde(default)]
+        file: Option<String>,
+    },
+}
+
+impl ExecutorIssue {
+    /// Retorna a descrição do issue.
+    pub fn text(&self) -> &str {
+        match self {
+            ExecutorIssue::Text(issue) => issue,
+            ExecutorIssue::Detailed { issue, .. } => issue,
+        }
+    }
+}
+
+impl From<String> for ExecutorIssue {
+    fn from(issue: String) -> Self {
+        ExecutorIssue::Text(issue)
+    }
 }
 
 impl ExecutorResponse {
@@ -133,6 +351,80 @@ impl ExecutorResponse {
         ))
     }
 
+    /// Constrói uma resposta a partir de texto livre usando heurísticas.
+    ///
+    /// Usado quando a CLI não retorna o JSON esperado. As palavras-chave
+    /// seguem o idioma detectado na resposta; num idioma sem palavras-chave
+    /// o voto é um WARN neutro, em vez de um PASS por falta de sinal.
+    pub fn from_text(text: &str) -> Self {
+        let language = detect_response_language(text);
+        let sets = keywords(language);
+
+        // Frases como "sem problemas" não devem contar como problema
+        let mut lower = text.to_lowercase();
+        for phrase in sets.iter().flat_map(|k| k.no_problem) {
+            lower = lower.replace(phrase, " ");
+        }
+        let any = |pick: fn(&FallbackKeywords) -> &'static [&'static str]| {
+            sets.iter().flat_map(&pick).any(|word| lower.contains(word))
+        };
+
+        // Determina voto e score pelas palavras-chave
+        let (vote, score) = if !language.is_supported() {
+            ("WARN", NEUTRAL_TEXT_SCORE)
+        } else if any(|k| k.fail) {
+            ("FAIL", 35)
+        } else if any(|k| k.warn) {
+            ("WARN", if any(|k| k.minor) { 70 } else { 60 })
+        } else if any(|k| k.excellent) {
+            ("PASS", 95)
+        } else if any(|k| k.good) {
+            ("PASS", 85)
+        } else {
+            ("PASS", 80)
+        };
+
+        // Extrai issues do texto (linhas que começam com - ou *)
+        let issues: Vec<ExecutorIssue> = text
+            .lines()
+            .filter(|line| {
+                let trimmed = line.trim();
+                trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("• ")
+            })
+            .map(|line| {
+                line.trim()
+                    .trim_start_matches("- ")
+                    .trim_start_matches("* ")
+                    .trim_start_matches("• ")
+                    .to_string()
+                    .into()
+            })
+            .take(5)
+            .collect();
+
+        // Extrai sugestões (linhas com "sugest", "consider"...)
+        let suggestions: Vec<String> = text
+            .lines()
+            .filter(|line| {
+                let lower_line = line.to_lowercase();
+                sets.iter()
+                    .flat_map(|k| k.suggestion)
+                    .any(|word| lower_line.contains(word))
+            })
+            .map(|line| line.trim().to_string())
+            .take(3)
+            .collect();
+
+        Self {
+            vote: vote.to_string(),
+            score,
+            reasoning: text.chars().take(500).collect(),
+            issues,
+            suggestions,
+            text_language: Some(language),
+        }
+    }
+
     /// Remove code fences markdown (```json ... ```) do texto.
     fn strip_code_fences(input: &str) -> String {
         let mut result = input.to_string();
@@ -231,16 +523,58 @@ impl ExecutorResponse {
             _ => Vote::Fail,
         };
 
-        ModelVote::new(executor_name, vote, self.score)
+        let mut issues = Vec::with_capacity(self.issues.len());
+        let mut fixes = std::collections::HashMap::new();
+        let mut lines = std::collections::HashMap::new();
+        let mut issue_suggestions = std::collections::HashMap::new();
+        let mut issue_files = std::collections::HashMap::new();
+        for entry in self.issues {
+            match entry {
+                ExecutorIssue::Text(issue) => issues.push(issue),
+                ExecutorIssue::Detailed {
+                    issue,
+                    fix,
+                    lines: issue_lines,
+                    suggestion,
+                    file,
+                } => {
+                    if let Some(mut fix) = fix {
+                        fix.sources = vec![executor_name.to_string()];
+                        fixes.insert(issue.clone(), fix);
+                    }
+                    if let Some(issue_lines) = issue_lines.filter(|l| !l.is_empty()) {
+                        lines.insert(issue.clone(), issue_lines);
+                    }
+                    if let Some(suggestion) = suggestion.filter(|s| !s.trim().is_empty()) {
+                        issue_suggestions.insert(issue.clone(), suggestion);
+                    }
+                    if let Some(file) = file.filter(|f| !f.trim().is_empty()) {
+                        issue_files.insert(issue.clone(), file);
+                    }
+                    issues.push(issue);
+                }
+            }
+        }
+
+        let vote = ModelVote::new(executor_name, vote, self.score)
             .with_reasoning(self.reasoning)
-            .with_issues(self.issues)
+            .with_issues(issues)
             .with_suggestions(self.suggestions)
+            .with_fixes(fixes)
+            .with_lines(lines)
+            .with_issue_suggestions(issue_suggestions)
+            .with_issue_files(issue_files);
+        match self.text_language {
+            Some(language) => vote.with_text_language(language),
+            None => vote,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::requests::RelatedFile;
 
     struct MockExecutor;
 
@@ -287,6 +621,166 @@ mod tests {
         assert!(prompt.contains("Este é um teste"));
     }
 
+    #[test]
+    fn test_build_prompt_related_files() {
+        let executor = MockExecutor;
+        let request = EvaluationRequest::new("fn area(s: &Shape) -> f64 { s.w * s.h }", "rust")
+            .with_related_files(vec![RelatedFile {
+                path: "src/shape.rs".to_string(),
+                content: "pub struct Shape { pub w: f64, pub h: f64 }".to_string(),
+            }]);
+
+        let prompt = executor.build_prompt(&request);
+
+        let section = prompt.find("Arquivos relacionados").unwrap();
+        let types = prompt.find("Arquivo: src/shape.rs").unwrap();
+        let code = prompt.find("fn area").unwrap();
+        assert!(section < types && types < code);
+        assert!(!executor
+            .build_prompt(&EvaluationRequest::new("fn main() {}", "rust"))
+            .contains("Arquivos relacionados"));
+    }
+
+    #[test]
+    fn test_build_prompt_documentation() {
+        let executor = MockExecutor;
+        let request = EvaluationRequest::new("/// Soma dois números.", "rust")
+            .with_type(EvaluationType::Documentation)
+            .with_related_code("fn add(a: i32, b: i32) -> i32 { a + b }");
+
+        let prompt = executor.build_prompt(&request);
+
+        assert!(prompt.contains("Documentação:"));
+        assert!(prompt.contains("/// Soma dois números."));
+        assert!(prompt.contains("fn add(a: i32, b: i32)"));
+        assert!(prompt.contains("accuracy:"));
+        assert!(prompt.contains("completeness:"));
+        assert!(prompt.contains("Tom:"));
+        assert!(prompt.contains("JSON"));
+    }
+
+    #[test]
+    fn test_build_prompt_documentation_doc_type() {
+        let executor = MockExecutor;
+        let request = EvaluationRequest::new("## 1.2.0\n- Added `--json`", "markdown")
+            .with_type(EvaluationType::Documentation)
+            .with_doc_type(DocType::Changelog);
+
+        let prompt = executor.build_prompt(&request);
+
+        assert!(prompt.contains("- Changelog:"));
+        assert!(prompt.contains("trechos de código citados"));
+        assert!(!prompt.contains("exemplos em markdown"));
+        assert!(!prompt.contains("- README:"));
+    }
+
+    #[test]
+    fn test_build_prompt_language_template() {
+        let executor = MockExecutor;
+        let request = EvaluationRequest::new("SELECT * FROM users WHERE id = $1", "postgresql")
+            .with_context("Consulta do login");
+
+        let prompt = executor.build_prompt(&request);
+
+        assert!(prompt.contains("```sql\nSELECT * FROM users WHERE id = $1\n```"));
+        assert!(prompt.contains("Injeção de SQL"));
+        assert!(prompt.contains("Contexto adicional:\nConsulta do login"));
+        assert!(prompt.contains("JSON"));
+    }
+
+    #[test]
+    fn test_build_prompt_configured_template() {
+        use crate::types::config::PromptKey;
+
+        struct TemplatedExecutor(PromptTemplates);
+
+        #[async_trait]
+        impl CliExecutor for TemplatedExecutor {
+            fn name(&self) -> &str {
+                "templated"
+            }
+
+            fn command(&self) -> &str {
+                "echo"
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                unreachable!()
+            }
+
+            fn specialization(&self) -> &str {
+                "security"
+            }
+
+            fn prompt_templates(&self) -> Option<&PromptTemplates> {
+                Some(&self.0)
+            }
+        }
+
+        let executor = TemplatedExecutor(PromptTemplates::new().with_template(
+            PromptKey {
+                evaluation_type: Some(EvaluationType::Plan),
+                family: None,
+            },
+            "Foco em {specialization}. Plano ({language}): {code}\nContexto: {context}",
+        ));
+        let request = EvaluationRequest::new("1. Migrar o banco", "markdown")
+            .with_type(EvaluationType::Plan)
+            .with_context("Sem downtime");
+
+        let prompt = executor.build_prompt(&request);
+
+        assert!(prompt.starts_with(
+            "Foco em security. Plano (markdown): 1. Migrar o banco\nContexto: Sem downtime\n\n"
+        ));
+        assert!(!prompt.contains("Contexto adicional:"));
+        assert!(prompt.contains("\"vote\""));
+    }
+
+    #[test]
+    fn test_build_prompt_diff() {
+        let executor = MockExecutor;
+        let diff = "@@ -1,2 +1,2 @@\n fn main() {\n-    old();\n+    new();";
+        let request = EvaluationRequest::new(diff, "rust")
+            .with_type(EvaluationType::Diff)
+            .with_file_path("src/main.rs");
+
+        let prompt = executor.build_prompt(&request);
+
+        assert!(prompt.contains("Avalie apenas as linhas alteradas"));
+        assert!(prompt.contains("Arquivo: src/main.rs"));
+        assert!(prompt.contains("```diff\n@@ -1,2 +1,2 @@"));
+        assert!(prompt.contains("\"lines\": [n]"));
+        assert!(prompt.contains("\"suggestion\""));
+    }
+
+    #[test]
+    fn test_build_prompt_multi_file_diff() {
+        let executor = MockExecutor;
+        let diff = "--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-old();\n+new();\n\
+                    --- a/src/b.rs\n+++ b/src/b.rs\n@@ -1 +1 @@\n-old();\n+new();";
+        let request = EvaluationRequest::new(diff, "rust").with_type(EvaluationType::Diff);
+
+        let prompt = executor.build_prompt(&request);
+        assert!(prompt.contains("O diff altera 2 arquivos (src/a.rs, src/b.rs)"));
+
+        // Diff de um arquivo só não pede o campo "file"
+        let single = EvaluationRequest::new("+++ b/src/a.rs\n+new();", "rust")
+            .with_type(EvaluationType::Diff);
+        assert!(!executor.build_prompt(&single).contains("Inclua \"file\""));
+    }
+
+    #[test]
+    fn test_parse_issue_with_file() {
+        let output = r#"{"vote": "WARN", "score": 60, "reasoning": "Diff", "issues": [{"issue": "unwrap may panic", "file": "src/b.rs", "lines": [1]}, {"issue": "no file", "file": ""}]}"#;
+        let vote = ExecutorResponse::parse_from_output(output, "Test")
+            .unwrap()
+            .into_vote("Codex");
+
+        assert_eq!(vote.issue_files.len(), 1);
+        assert_eq!(vote.issue_files["unwrap may panic"], "src/b.rs");
+    }
+
     #[test]
     fn test_executor_response_into_vote() {
         let response = ExecutorResponse {
@@ -295,6 +789,7 @@ mod tests {
             reasoning: "Código bom".to_string(),
             issues: vec![],
             suggestions: vec!["Adicionar testes".to_string()],
+            text_language: None,
         };
 
         let vote = response.into_vote("test");
@@ -304,6 +799,69 @@ mod tests {
         assert_eq!(vote.suggestions.len(), 1);
     }
 
+    #[test]
+    fn test_from_text_spanish() {
+        let response = ExecutorResponse::from_text(
+            "El código tiene una vulnerabilidad de inyección SQL.\n\
+             - La consulta concatena la entrada del usuario",
+        );
+        assert_eq!(response.vote, "FAIL");
+        assert_eq!(response.issues.len(), 1);
+
+        // "sin problemas" não é um problema
+        let response =
+            ExecutorResponse::from_text("El código es correcto y no tiene problemas. Sin cambios.");
+        assert_eq!(response.vote, "PASS");
+        assert_eq!(response.score, 85);
+
+        let vote =
+            ExecutorResponse::from_text("Considera validar el índice, es un problema menor.")
+                .into_vote("Qwen");
+        assert_eq!(vote.vote, crate::types::responses::Vote::Warn);
+        assert_eq!(vote.score, 70);
+        assert_eq!(vote.response_language, Some(ResponseLanguage::Spanish));
+    }
+
+    #[test]
+    fn test_from_text_chinese() {
+        let response =
+            ExecutorResponse::from_text("代码存在严重的安全漏洞：`query(sql)` 未转义用户输入。");
+        assert_eq!(response.vote, "FAIL");
+
+        let response = ExecutorResponse::from_text("代码没有问题，实现正确。");
+        assert_eq!(response.vote, "PASS");
+        assert_eq!(response.score, 85);
+
+        let response = ExecutorResponse::from_text("建议为边界情况添加测试。");
+        assert_eq!(response.vote, "WARN");
+        assert_eq!(response.suggestions.len(), 1);
+    }
+
+    #[test]
+    fn test_from_text_unsupported_language_is_neutral() {
+        use crate::types::responses::{ParseQuality, Vote};
+
+        // Sem palavras-chave, um texto em japonês viraria um PASS espúrio
+        let vote = ExecutorResponse::from_text("このコードにはセキュリティの脆弱性があります。")
+            .into_vote("Qwen");
+        assert_eq!(vote.vote, Vote::Warn);
+        assert_eq!(vote.score, NEUTRAL_TEXT_SCORE);
+        assert_eq!(vote.parse_quality, Some(ParseQuality::UnsupportedLanguage));
+        assert_eq!(vote.response_language, Some(ResponseLanguage::Unsupported));
+
+        let json = serde_json::to_value(&vote).unwrap();
+        assert_eq!(json["parse_quality"], "unsupported_language");
+
+        // Respostas em JSON não são marcadas
+        let vote = ExecutorResponse::parse_from_output(
+            r#"{"vote":"PASS","score":90,"reasoning":"ok"}"#,
+            "Qwen",
+        )
+        .unwrap()
+        .into_vote("Qwen");
+        assert_eq!(vote.parse_quality, None);
+    }
+
     #[test]
     fn test_parse_json_with_code_fence() {
         let output = r#"
@@ -357,6 +915,56 @@ Some text with nested object: {"other": "data"}
         assert_eq!(response.score, 100);
     }
 
+    #[test]
+    fn test_parse_issue_with_fix() {
+        let output = r#"{"vote": "WARN", "score": 60, "reasoning": "Unwrap", "issues": ["plain issue", {"issue": "unwrap may panic", "fix": {"start_line": 2, "end_line": 2, "replacement": "    let x = y?;"}}], "suggestions": []}"#;
+        let response = ExecutorResponse::parse_from_output(output, "Test").unwrap();
+        assert_eq!(response.issues.len(), 2);
+        assert_eq!(response.issues[1].text(), "unwrap may panic");
+
+        let vote = response.into_vote("Codex");
+        assert_eq!(vote.issues, vec!["plain issue", "unwrap may panic"]);
+        assert_eq!(vote.fixes.len(), 1);
+
+        let fix = &vote.fixes["unwrap may panic"];
+        assert_eq!(fix.start_line, 2);
+        assert_eq!(fix.replacement, "    let x = y?;");
+        assert_eq!(fix.sources, vec!["Codex"]);
+    }
+
+    #[test]
+    fn test_parse_issue_with_lines() {
+        let output = r#"{"vote": "WARN", "score": 70, "reasoning": "Diff", "issues": [{"issue": "missing check", "lines": [4, 5]}, {"issue": "no lines", "lines": []}]}"#;
+        let vote = ExecutorResponse::parse_from_output(output, "Test")
+            .unwrap()
+            .into_vote("Gemini");
+
+        assert_eq!(vote.issues, vec!["missing check", "no lines"]);
+        assert_eq!(vote.lines.len(), 1);
+        assert_eq!(vote.lines["missing check"], vec![4, 5]);
+        assert!(vote.fixes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_issue_with_suggestion() {
+        let output = r#"{"vote": "WARN", "score": 65, "reasoning": "Panic", "issues": ["legacy issue", {"issue": "unwrap may panic", "lines": [3], "suggestion": "Propagar o erro com ?"}, {"issue": "blank", "suggestion": "  "}], "suggestions": ["Adicionar testes"]}"#;
+        let vote = ExecutorResponse::parse_from_output(output, "Test")
+            .unwrap()
+            .into_vote("Qwen");
+
+        assert_eq!(
+            vote.issues,
+            vec!["legacy issue", "unwrap may panic", "blank"]
+        );
+        assert_eq!(vote.lines["unwrap may panic"], vec![3]);
+        assert_eq!(vote.issue_suggestions.len(), 1);
+        assert_eq!(
+            vote.issue_suggestions["unwrap may panic"],
+            "Propagar o erro com ?"
+        );
+        assert_eq!(vote.suggestions, vec!["Adicionar testes"]);
+    }
+
     #[test]
     fn test_parse_json_no_valid_json() {
         let output = "No JSON here, just some text with { random braces }";
@@ -0,0 +1,189 @@
+//! Avaliação canário usada pelo `tetrad doctor --deep`.
+//!
+//! Uma CLI pode estar instalada e ainda assim configurada com argumentos
+//! errados (por exemplo, Gemini sem `-o json`). Nesse caso toda avaliação
+//! real cai silenciosamente nas heurísticas de texto. O canário envia um
+//! trecho trivial com os argumentos configurados e verifica se a saída bruta
+//! contém a resposta JSON esperada.
+
+use std::time::Duration;
+
+use super::base::CliExecutor;
+use super::input::run_cli;
+use crate::types::config::ExecutorConfig;
+use crate::types::requests::EvaluationRequest;
+
+/// Tamanho máximo do trecho de saída incluído nos diagnósticos.
+const EXCERPT_CHARS: usize = 300;
+
+/// Resultado da avaliação canário de um executor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanaryOutcome {
+    /// A saída continha a resposta JSON esperada.
+    Parsed {
+        /// Voto retornado.
+        vote: String,
+        /// Score retornado.
+        score: u8,
+    },
+    /// A CLI respondeu, mas a saída não pôde ser parseada.
+    Unparseable {
+        /// Erro de parse.
+        error: String,
+        /// Início da saída bruta.
+        excerpt: String,
+    },
+    /// A CLI não pôde ser executada, terminou com erro ou excedeu o timeout.
+    Failed(String),
+}
+
+/// Executa a avaliação canário com o comando e os argumentos configurados.
+pub async fn run_canary(executor: &dyn CliExecutor, config: &ExecutorConfig) -> CanaryOutcome {
+    let prompt = executor.build_prompt(&canary_request());
+
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let result = run_cli(
+        executor.command(),
+        &config.args,
+        &prompt,
+        config.input_mode,
+        timeout,
+    )
+    .await;
+    let output = match result {
+        Ok(Ok(output)) => output,
+        Ok(Err(e)) => return CanaryOutcome::Failed(e.to_string()),
+        Err(_) => return CanaryOutcome::Failed(format!("timed out after {}s", timeout.as_secs())),
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    if stdout.trim().is_empty() {
+        return if output.status.success() {
+            CanaryOutcome::Unparseable {
+                error: "empty output".to_string(),
+                excerpt: excerpt(&stderr),
+            }
+        } else {
+            CanaryOutcome::Failed(format!("{}: {}", output.status, excerpt(&stderr)))
+        };
+    }
+
+    match executor.parse_structured(&stdout) {
+        Ok(response) => CanaryOutcome::Parsed {
+            vote: response.vote,
+            score: response.score,
+        },
+        Err(e) => CanaryOutcome::Unparseable {
+            error: e.to_string(),
+            excerpt: excerpt(&stdout),
+        },
+    }
+}
+
+/// Requisição trivial enviada como canário.
+fn canary_request() -> EvaluationRequest {
+    EvaluationRequest::new("fn add(a: i32, b: i32) -> i32 {\n    a + b\n}", "rust")
+        .with_context("Avaliação de diagnóstico do tetrad doctor.")
+}
+
+/// Primeiros caracteres da saída, em uma linha.
+fn excerpt(output: &str) -> String {
+    let line = output.split_whitespace().collect::<Vec<_>>().join(" ");
+    match line.char_indices().nth(EXCERPT_CHARS) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::responses::ModelVote;
+    use crate::TetradResult;
+    use async_trait::async_trait;
+
+    /// Executor que roda o comando dado; com `sh -c <script>`, o prompt chega como `$1`.
+    struct ShellExecutor(&'static str);
+
+    #[async_trait]
+    impl CliExecutor for ShellExecutor {
+        fn name(&self) -> &str {
+            "Shell"
+        }
+
+        fn command(&self) -> &str {
+            self.0
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+            unreachable!()
+        }
+
+        fn specialization(&self) -> &str {
+            "test"
+        }
+    }
+
+    fn shell_config(script: &str) -> ExecutorConfig {
+        ExecutorConfig::new("sh", &["-c", script, "sh"])
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_canary_parses_json() {
+        let config = shell_config(
+            r#"echo '{"vote": "PASS", "score": 97, "reasoning": "ok"}'; case "$1" in *"fn add"*) ;; *) exit 1 ;; esac"#,
+        );
+        assert_eq!(
+            run_canary(&ShellExecutor("sh"), &config).await,
+            CanaryOutcome::Parsed {
+                vote: "PASS".to_string(),
+                score: 97
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_canary_reports_unparseable_output() {
+        let config = shell_config("echo 'Looks fine to me.'");
+        match run_canary(&ShellExecutor("sh"), &config).await {
+            CanaryOutcome::Unparseable { error, excerpt } => {
+                assert!(error.contains("JSON"), "{}", error);
+                assert_eq!(excerpt, "Looks fine to me.");
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_canary_reports_failures() {
+        let config = shell_config("echo 'unknown flag: -o' >&2; exit 2");
+        match run_canary(&ShellExecutor("sh"), &config).await {
+            CanaryOutcome::Failed(message) => {
+                assert!(message.contains("unknown flag"), "{}", message)
+            }
+            other => panic!("unexpected outcome: {:?}", other),
+        }
+
+        let missing = ShellExecutor("tetrad-missing-cli");
+        assert!(matches!(
+            run_canary(&missing, &shell_config("true")).await,
+            CanaryOutcome::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn test_excerpt_truncates() {
+        assert_eq!(excerpt("a\n  b\tc"), "a b c");
+        let long = "x".repeat(EXCERPT_CHARS + 10);
+        assert_eq!(excerpt(&long).len(), EXCERPT_CHARS + 3);
+    }
+}
@@ -1,13 +1,15 @@
 //! Executor para Codex CLI.
 
 use async_trait::async_trait;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::process::Command;
 
 use super::base::{CliExecutor, ExecutorResponse};
-use crate::types::config::ExecutorConfig;
+use super::input::run_cli;
+use super::prompts::PromptTemplates;
+use crate::types::config::{ExecutorConfig, InputMode};
 use crate::types::requests::EvaluationRequest;
-use crate::types::responses::{ModelVote, Vote};
+use crate::types::responses::ModelVote;
 use crate::{TetradError, TetradResult};
 
 /// Executor para Codex CLI (OpenAI).
@@ -18,6 +20,8 @@ pub struct CodexExecutor {
     command_name: String,
     args: Vec<String>,
     timeout: Duration,
+    input_mode: InputMode,
+    prompts: Arc<PromptTemplates>,
 }
 
 impl CodexExecutor {
@@ -28,6 +32,8 @@ impl CodexExecutor {
             // Usa exec --json para modo não-interativo
             args: vec!["exec".to_string(), "--json".to_string()],
             timeout: Duration::from_secs(60),
+            input_mode: InputMode::default(),
+            prompts: Arc::default(),
         }
     }
 
@@ -37,6 +43,8 @@ impl CodexExecutor {
             command_name: config.command.clone(),
             args: config.args.clone(),
             timeout: Duration::from_secs(config.timeout_secs),
+            input_mode: config.input_mode,
+            prompts: Arc::default(),
         }
     }
 
@@ -47,6 +55,13 @@ impl CodexExecutor {
         self
     }
 
+    /// Define os templates de prompt.
+    #[must_use]
+    pub fn with_prompts(mut self, prompts: Arc<PromptTemplates>) -> Self {
+        self.prompts = prompts;
+        self
+    }
+
     /// Parseia eventos JSON Lines (NDJSON) do codex exec --json.
     /// Extrai a mensagem do agente do evento item.completed com type: "agent_message".
     fn parse_codex_events(output: &str) -> Option<String> {
@@ -74,70 +89,7 @@ impl CodexExecutor {
 
     /// Analisa texto de resposta e extrai informações estruturadas.
     fn analyze_text_response(text: &str) -> ExecutorResponse {
-        let lower = text.to_lowercase();
-
-        // Determina o voto baseado em palavras-chave
-        let vote = if lower.contains("erro crítico")
-            || lower.contains("bug grave")
-            || lower.contains("vulnerabilidade")
-            || lower.contains("falha de segurança")
-        {
-            "FAIL"
-        } else if lower.contains("problema")
-            || lower.contains("issue")
-            || lower.contains("considere")
-            || lower.contains("sugestão")
-            || lower.contains("atenção")
-            || lower.contains("melhoria")
-            || lower.contains("overflow")
-        {
-            "WARN"
-        } else {
-            "PASS"
-        };
-
-        // Score baseado no voto e conteúdo
-        let score = if vote == "PASS" {
-            if lower.contains("perfeito") || lower.contains("excelente") {
-                95
-            } else if lower.contains("bom") || lower.contains("correto") {
-                85
-            } else {
-                80
-            }
-        } else if vote == "WARN" {
-            if lower.contains("menor") || lower.contains("minor") {
-                70
-            } else {
-                60
-            }
-        } else {
-            35
-        };
-
-        // Extrai issues do texto (linhas que começam com - ou *)
-        let issues: Vec<String> = text
-            .lines()
-            .filter(|line| {
-                let trimmed = line.trim();
-                trimmed.starts_with("- ") || trimmed.starts_with("* ")
-            })
-            .map(|line| {
-                line.trim()
-                    .trim_start_matches("- ")
-                    .trim_start_matches("* ")
-                    .to_string()
-            })
-            .take(5)
-            .collect();
-
-        ExecutorResponse {
-            vote: vote.to_string(),
-            score,
-            reasoning: text.chars().take(500).collect(),
-            issues,
-            suggestions: vec![],
-        }
+        ExecutorResponse::from_text(text)
     }
 }
 
@@ -161,22 +113,38 @@ impl CliExecutor for CodexExecutor {
         "syntax"
     }
 
-    async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
-        let prompt = self.build_prompt(request);
-
-        // Constrói o comando: codex exec --json "prompt"
-        let mut cmd = Command::new(&self.command_name);
+    fn prompt_templates(&self) -> Option<&PromptTemplates> {
+        Some(&self.prompts)
+    }
 
-        // Adiciona argumentos do config (deve incluir "exec" e "--json")
-        for arg in &self.args {
-            cmd.arg(arg);
+    fn parse_structured(&self, output: &str) -> TetradResult<ExecutorResponse> {
+        match Self::parse_codex_events(output) {
+            Some(agent_message) => ExecutorResponse::parse_from_output(&agent_message, self.name()),
+            None => ExecutorResponse::parse_from_output(output, self.name()),
         }
+    }
 
-        // Adiciona o prompt
-        cmd.arg(&prompt);
+    async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
+        self.evaluate_with_timeout(request, self.timeout).await
+    }
+
+    async fn evaluate_with_timeout(
+        &self,
+        request: &EvaluationRequest,
+        timeout: Duration,
+    ) -> TetradResult<ModelVote> {
+        let prompt = self.build_prompt(request);
 
-        // Executa a CLI com timeout
-        let result = tokio::time::timeout(self.timeout, cmd.output()).await;
+        // Executa a CLI com timeout: codex exec --json "prompt" (argumentos do
+        // config devem incluir "exec" e "--json")
+        let result = run_cli(
+            &self.command_name,
+            &self.args,
+            &prompt,
+            self.input_mode,
+            timeout,
+        )
+        .await;
 
         match result {
             Ok(Ok(output)) => {
@@ -222,8 +190,10 @@ impl CliExecutor for CodexExecutor {
             Ok(Err(e)) => {
                 // CLI não encontrada ou erro de execução
                 if e.kind() == std::io::ErrorKind::NotFound {
-                    Ok(ModelVote::new(self.name(), Vote::Warn, 50)
-                        .with_reasoning("Codex CLI não disponível"))
+                    Ok(
+                        ModelVote::synthetic(self.name())
+                            .with_reasoning("Codex CLI não disponível"),
+                    )
                 } else {
                     Err(TetradError::ExecutorFailed(
                         self.name().to_string(),
@@ -0,0 +1,887 @@
+//! Cadeia de fallback entre executores.
+//!
+//! Um executor com `fallback_for` configurado não vota por conta própria:
+//! ele só é invocado quando o executor primário está indisponível ou
+//! retorna erro de rate limit/autenticação. Primário e fallback ocupam
+//! um único assento na votação.
+//!
+//! Antes disso, cada executor tem `retry_attempts` novas tentativas, com
+//! espera exponencial a partir de `retry_backoff_ms`, para timeouts e falhas
+//! transitórias (veja [`is_retryable`]). O prazo total da avaliação continua
+//! valendo: um assento ainda tentando quando ele acaba é cortado.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
+
+use crate::types::config::ExecutorConfig;
+use crate::types::requests::EvaluationRequest;
+use crate::types::responses::ModelVote;
+use crate::{TetradError, TetradResult};
+
+use super::CliExecutor;
+
+/// Trechos de mensagens de erro que indicam rate limit ou falha de autenticação.
+const FALLBACK_ERROR_MARKERS: &[&str] = &[
+    "rate limit",
+    "rate-limit",
+    "ratelimit",
+    "too many requests",
+    "429",
+    "quota",
+    "resource_exhausted",
+    "unauthorized",
+    "unauthenticated",
+    "401",
+    "403",
+    "forbidden",
+    "authentication",
+    "api key",
+];
+
+/// Executor acompanhado da sua configuração.
+#[derive(Clone, Copy)]
+pub struct ExecutorSlot<'a> {
+    /// Executor.
+    pub executor: &'a dyn CliExecutor,
+
+    /// Configuração do executor.
+    pub config: &'a ExecutorConfig,
+
+    /// Prazo desta chamada no lugar do `timeout_secs` da configuração.
+    pub timeout: Option<Duration>,
+}
+
+impl<'a> ExecutorSlot<'a> {
+    /// Cria um novo slot.
+    pub fn new(executor: &'a dyn CliExecutor, config: &'a ExecutorConfig) -> Self {
+        Self {
+            executor,
+            config,
+            timeout: None,
+        }
+    }
+
+    /// Define o prazo da chamada (timeout adaptativo).
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Avalia com o prazo do slot, se houver, repetindo as falhas
+    /// transitórias conforme `retry_attempts`.
+    async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
+        let mut attempt = 0;
+        loop {
+            let result = match self.timeout {
+                Some(timeout) => self.executor.evaluate_with_timeout(request, timeout).await,
+                None => self.executor.evaluate(request).await,
+            };
+            match result {
+                Err(e) if attempt < self.config.retry_attempts && is_retryable(&e) => {
+                    let backoff = retry_backoff(self.config.retry_backoff_ms, attempt);
+                    attempt += 1;
+                    tracing::warn!(
+                        executor = self.executor.name(),
+                        attempt,
+                        max_attempts = self.config.retry_attempts,
+                        backoff_ms = backoff.as_millis() as u64,
+                        error = %e,
+                        "Executor failed, retrying"
+                    );
+                    tokio::time::sleep(backoff).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Verifica se este slot é fallback do executor `primary`.
+    fn is_fallback_of(&self, primary: &str) -> bool {
+        self.config
+            .fallback_for
+            .as_deref()
+            .is_some_and(|name| name.eq_ignore_ascii_case(primary))
+    }
+}
+
+/// Resultado de um assento na votação.
+#[derive(Debug)]
+pub struct SeatOutcome {
+    /// Nome do assento (ex: "Qwen (fallback for Gemini)").
+    pub name: String,
+
+    /// Voto obtido, ou o erro que o impediu.
+    pub result: TetradResult<ModelVote>,
+
+    /// Se o voto veio do executor de fallback.
+    pub used_fallback: bool,
+
+    /// Se o assento foi interrompido pelo prazo da avaliação.
+    pub cut_off: bool,
+
+    /// Se o assento foi cancelado porque a decisão já estava definida.
+    pub short_circuited: bool,
+}
+
+/// Verifica se um erro justifica acionar o executor de fallback.
+///
+/// Apenas indisponibilidade, rate limit e falhas de autenticação acionam o
+/// fallback; outros erros (timeout, resposta inválida) são do primário.
+pub fn is_fallback_trigger(error: &TetradError) -> bool {
+    match error {
+        TetradError::ExecutorNotFound(_) => true,
+        TetradError::Io(e) => e.kind() == std::io::ErrorKind::NotFound,
+        TetradError::ExecutorFailed(_, message) => {
+            let message = message.to_lowercase();
+            FALLBACK_ERROR_MARKERS
+                .iter()
+                .any(|marker| message.contains(marker))
+        }
+        _ => false,
+    }
+}
+
+/// Verifica se vale tentar o executor de novo depois deste erro.
+///
+/// Timeouts e falhas de execução passageiras são repetidos; CLI ausente,
+/// rate limit e autenticação não mudam numa nova tentativa e vão direto
+/// para o fallback.
+pub fn is_retryable(error: &TetradError) -> bool {
+    match error {
+        TetradError::ExecutorTimeout(_) => true,
+        TetradError::ExecutorFailed(..) | TetradError::Io(_) => !is_fallback_trigger(error),
+        _ => false,
+    }
+}
+
+/// Espera antes da nova tentativa `attempt` (0 para a primeira repetição):
+/// `backoff_ms` dobrando a cada tentativa.
+fn retry_backoff(backoff_ms: u64, attempt: u32) -> Duration {
+    Duration::from_millis(backoff_ms.saturating_mul(1 << attempt.min(16)))
+}
+
+/// Retorna o número de assentos na votação.
+///
+/// Cada executor primário habilitado (ou com fallback habilitado) ocupa um
+/// assento; fallbacks não contam separadamente.
+pub fn seat_count(slots: &[ExecutorSlot<'_>]) -> usize {
+    seat_names(slots).len()
+}
+
+/// Retorna os nomes dos executores primários que ocupam assentos, na ordem dos slots.
+pub fn seat_names<'a>(slots: &[ExecutorSlot<'a>]) -> Vec<&'a str> {
+    slots
+        .iter()
+        .filter(|primary| primary.config.fallback_for.is_none())
+        .filter(|primary| {
+            primary.config.enabled
+                || slots
+                    .iter()
+                    .any(|slot| slot.config.enabled && slot.is_fallback_of(primary.executor.name()))
+        })
+        .map(|primary| primary.executor.name())
+        .collect()
+}
+
+/// Retorna o peso no consenso de cada executor habilitado, fallbacks
+/// incluídos: um fallback vota com o próprio peso no assento que ocupa.
+pub fn seat_weights<'a>(slots: &[ExecutorSlot<'a>]) -> Vec<(&'a str, u8)> {
+    slots
+        .iter()
+        .filter(|slot| slot.config.enabled)
+        .map(|slot| (slot.executor.name(), slot.config.weight))
+        .collect()
+}
+
+/// Obtém o voto de um assento, acionando o fallback quando necessário.
+///
+/// Retorna `None` se o slot for um fallback (não tem assento próprio) ou se
+/// estiver desabilitado sem fallback habilitado.
+pub async fn run_seat(
+    slots: &[ExecutorSlot<'_>],
+    index: usize,
+    request: &EvaluationRequest,
+) -> Option<SeatOutcome> {
+    let primary = slots.get(index)?;
+    if primary.config.fallback_for.is_some() {
+        return None;
+    }
+
+    let primary_name = primary.executor.name();
+    let fallback = slots
+        .iter()
+        .find(|slot| slot.config.enabled && slot.is_fallback_of(primary_name));
+
+    let Some(fallback) = fallback else {
+        if !primary.config.enabled {
+            return None;
+        }
+        return Some(SeatOutcome {
+            name: primary_name.to_string(),
+            result: primary.evaluate(request).await,
+            used_fallback: false,
+            cut_off: false,
+            short_circuited: false,
+        });
+    };
+
+    let reason = if !primary.config.enabled {
+        "disabled".to_string()
+    } else if !primary.executor.is_available().await {
+        "unavailable".to_string()
+    } else {
+        match primary.evaluate(request).await {
+            Err(e) if is_fallback_trigger(&e) => e.to_string(),
+            result => {
+                return Some(SeatOutcome {
+                    name: primary_name.to_string(),
+                    result,
+                    used_fallback: false,
+                    cut_off: false,
+                    short_circuited: false,
+                })
+            }
+        }
+    };
+
+    let name = format!(
+        "{} (fallback for {})",
+        fallback.executor.name(),
+        primary_name
+    );
+    tracing::info!(
+        primary = primary_name,
+        fallback = fallback.executor.name(),
+        reason = %reason,
+        "Using fallback executor"
+    );
+
+    let result = fallback.evaluate(request).await.map(|mut vote| {
+        vote.executor = name.clone();
+        vote
+    });
+
+    Some(SeatOutcome {
+        name,
+        result,
+        used_fallback: true,
+        cut_off: false,
+        short_circuited: false,
+    })
+}
+
+/// Obtém os votos de todos os assentos em paralelo.
+///
+/// A ordem do resultado segue a ordem dos slots.
+pub async fn collect_seats(
+    slots: &[ExecutorSlot<'_>],
+    request: &EvaluationRequest,
+) -> Vec<SeatOutcome> {
+    collect_seats_with(slots, request, &|_| {}).await
+}
+
+/// Obtém os votos de todos os assentos em paralelo, chamando `on_outcome`
+/// assim que cada assento termina (na ordem de término).
+///
+/// A ordem do resultado segue a ordem dos slots.
+pub async fn collect_seats_with(
+    slots: &[ExecutorSlot<'_>],
+    request: &EvaluationRequest,
+    on_outcome: &(dyn Fn(&SeatOutcome) + Sync),
+) -> Vec<SeatOutcome> {
+    collect_seats_until(slots, request, None, on_outcome).await
+}
+
+/// Como [`collect_seats_with`], mas encerra a coleta em `deadline`.
+///
+/// Os votos que já chegaram são mantidos; cada assento ainda pendente recebe
+/// um erro de timeout com `cut_off` marcado, e sua execução é cancelada.
+pub async fn collect_seats_until(
+    slots: &[ExecutorSlot<'_>],
+    request: &EvaluationRequest,
+    deadline: Option<tokio::time::Instant>,
+    on_outcome: &(dyn Fn(&SeatOutcome) + Sync),
+) -> Vec<SeatOutcome> {
+    collect_seats_until_settled(slots, request, deadline, on_outcome, &|_, _| false).await
+}
+
+/// Como [`collect_seats_until`], mas consulta `settled` a cada voto novo.
+///
+/// `settled` recebe os assentos que já terminaram e os nomes dos pendentes;
+/// quando retorna `true`, cada assento pendente recebe um erro com
+/// `short_circuited` marcado e sua execução é cancelada.
+pub async fn collect_seats_until_settled(
+    slots: &[ExecutorSlot<'_>],
+    request: &EvaluationRequest,
+    deadline: Option<tokio::time::Instant>,
+    on_outcome: &(dyn Fn(&SeatOutcome) + Sync),
+    settled: &(dyn Fn(&[&SeatOutcome], &[&str]) -> bool + Sync),
+) -> Vec<SeatOutcome> {
+    type SeatFuture<'f> = Pin<Box<dyn Future<Output = Option<SeatOutcome>> + Send + 'f>>;
+
+    let mut pending: Vec<SeatFuture<'_>> = (0..slots.len())
+        .map(|index| Box::pin(run_seat(slots, index, request)) as SeatFuture<'_>)
+        .collect();
+    let mut outcomes: Vec<Option<Option<SeatOutcome>>> = (0..slots.len()).map(|_| None).collect();
+    let mut expired = deadline.map(|deadline| Box::pin(tokio::time::sleep_until(deadline)));
+
+    // join_all sem exigir 'static: faz poll de todos os assentos pendentes
+    std::future::poll_fn(|cx| {
+        let mut done = true;
+        let mut arrived = false;
+        for (future, outcome) in pending.iter_mut().zip(outcomes.iter_mut()) {
+            if outcome.is_none() {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(result) => {
+                        if let Some(seat) = &result {
+                            on_outcome(seat);
+                            arrived = true;
+                        }
+                        *outcome = Some(result);
+                    }
+                    Poll::Pending => done = false,
+                }
+            }
+        }
+        if done {
+            return Poll::Ready(());
+        }
+
+        // Decisão definida: os votos pendentes não mudariam o resultado
+        if arrived {
+            let finished: Vec<&SeatOutcome> = outcomes.iter().flatten().flatten().collect();
+            let waiting: Vec<&str> = slots
+                .iter()
+                .zip(&outcomes)
+                .filter(|(_, outcome)| outcome.is_none())
+                .map(|(slot, _)| slot.executor.name())
+                .collect();
+            if settled(&finished, &waiting) {
+                for (slot, outcome) in slots.iter().zip(outcomes.iter_mut()) {
+                    if outcome.is_none() {
+                        let name = slot.executor.name().to_string();
+                        let seat = SeatOutcome {
+                            result: Err(TetradError::ExecutorFailed(
+                                name.clone(),
+                                "cancelled: decision already settled".to_string(),
+                            )),
+                            name,
+                            used_fallback: false,
+                            cut_off: false,
+                            short_circuited: true,
+                        };
+                        on_outcome(&seat);
+                        *outcome = Some(Some(seat));
+                    }
+                }
+                return Poll::Ready(());
+            }
+        }
+
+        // Prazo esgotado: só assentos com voto próprio continuam pendentes,
+        // já que fallbacks e slots desabilitados terminam no primeiro poll
+        if expired
+            .as_mut()
+            .is_some_and(|sleep| sleep.as_mut().poll(cx).is_ready())
+        {
+            for (slot, outcome) in slots.iter().zip(outcomes.iter_mut()) {
+                if outcome.is_none() {
+                    let name = slot.executor.name().to_string();
+                    let seat = SeatOutcome {
+                        result: Err(TetradError::ExecutorTimeout(name.clone())),
+                        name,
+                        used_fallback: false,
+                        cut_off: true,
+                        short_circuited: false,
+                    };
+                    on_outcome(&seat);
+                    *outcome = Some(Some(seat));
+                }
+            }
+            return Poll::Ready(());
+        }
+
+        Poll::Pending
+    })
+    .await;
+
+    outcomes.into_iter().flatten().flatten().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::responses::Vote;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct MockExecutor {
+        name: &'static str,
+        error: Option<&'static str>,
+        calls: AtomicUsize,
+    }
+
+    impl MockExecutor {
+        fn new(name: &'static str, error: Option<&'static str>) -> Self {
+            Self {
+                name,
+                error,
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CliExecutor for MockExecutor {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn command(&self) -> &str {
+            "mock"
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            match self.error {
+                Some(message) => Err(TetradError::ExecutorFailed(
+                    self.name.to_string(),
+                    message.to_string(),
+                )),
+                None => Ok(ModelVote::new(self.name, Vote::Pass, 90)),
+            }
+        }
+
+        fn specialization(&self) -> &str {
+            "test"
+        }
+    }
+
+    fn fallback_config(primary: &str) -> ExecutorConfig {
+        ExecutorConfig {
+            fallback_for: Some(primary.to_string()),
+            ..ExecutorConfig::new("mock", &[])
+        }
+    }
+
+    #[test]
+    fn test_is_fallback_trigger() {
+        let rate_limited =
+            TetradError::ExecutorFailed("Gemini".into(), "429 Too Many Requests".into());
+        let auth = TetradError::ExecutorFailed("Gemini".into(), "Invalid API key".into());
+        let parse = TetradError::ExecutorFailed("Gemini".into(), "invalid JSON".into());
+
+        assert!(is_fallback_trigger(&rate_limited));
+        assert!(is_fallback_trigger(&auth));
+        assert!(is_fallback_trigger(&TetradError::ExecutorNotFound(
+            "Gemini".into()
+        )));
+        assert!(!is_fallback_trigger(&parse));
+        assert!(!is_fallback_trigger(&TetradError::ExecutorTimeout(
+            "Gemini".into()
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_fallback_fills_in_for_rate_limited_primary() {
+        let codex = MockExecutor::new("Codex", None);
+        let gemini = MockExecutor::new("Gemini", Some("Error: 429 rate limit exceeded"));
+        let qwen = MockExecutor::new("Qwen", None);
+
+        let enabled = ExecutorConfig::new("mock", &[]);
+        let qwen_config = fallback_config("gemini");
+        let slots = [
+            ExecutorSlot::new(&codex, &enabled),
+            ExecutorSlot::new(&gemini, &enabled),
+            ExecutorSlot::new(&qwen, &qwen_config),
+        ];
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let seats = collect_seats(&slots, &request).await;
+
+        assert_eq!(seats.len(), 2);
+        assert_eq!(seats[0].name, "Codex");
+        assert!(!seats[0].used_fallback);
+        assert_eq!(seats[1].name, "Qwen (fallback for Gemini)");
+        assert!(seats[1].used_fallback);
+
+        let vote = seats[1].result.as_ref().unwrap();
+        assert_eq!(vote.executor, "Qwen (fallback for Gemini)");
+
+        assert_eq!(gemini.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(qwen.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(seat_count(&slots), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_not_invoked_when_primary_succeeds() {
+        let gemini = MockExecutor::new("Gemini", None);
+        let qwen = MockExecutor::new("Qwen", None);
+
+        let enabled = ExecutorConfig::new("mock", &[]);
+        let qwen_config = fallback_config("Gemini");
+        let slots = [
+            ExecutorSlot::new(&gemini, &enabled),
+            ExecutorSlot::new(&qwen, &qwen_config),
+        ];
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let seats = collect_seats(&slots, &request).await;
+
+        assert_eq!(seats.len(), 1);
+        assert_eq!(seats[0].name, "Gemini");
+        assert_eq!(qwen.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_not_invoked_for_other_errors() {
+        let gemini = MockExecutor::new("Gemini", Some("invalid JSON"));
+        let qwen = MockExecutor::new("Qwen", None);
+
+        let enabled = ExecutorConfig::new("mock", &[]);
+        let qwen_config = fallback_config("Gemini");
+        let slots = [
+            ExecutorSlot::new(&gemini, &enabled),
+            ExecutorSlot::new(&qwen, &qwen_config),
+        ];
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let seats = collect_seats(&slots, &request).await;
+
+        assert_eq!(seats.len(), 1);
+        assert!(seats[0].result.is_err());
+        assert_eq!(qwen.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_collect_seats_with_reports_each_outcome() {
+        let codex = MockExecutor::new("Codex", None);
+        let gemini = MockExecutor::new("Gemini", Some("Error: 429 rate limit exceeded"));
+        let qwen = MockExecutor::new("Qwen", None);
+
+        let enabled = ExecutorConfig::new("mock", &[]);
+        let qwen_config = fallback_config("Gemini");
+        let slots = [
+            ExecutorSlot::new(&codex, &enabled),
+            ExecutorSlot::new(&gemini, &enabled),
+            ExecutorSlot::new(&qwen, &qwen_config),
+        ];
+        assert_eq!(seat_names(&slots), vec!["Codex", "Gemini"]);
+
+        let reported = std::sync::Mutex::new(Vec::new());
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let seats = collect_seats_with(&slots, &request, &|seat| {
+            reported.lock().unwrap().push(seat.name.clone());
+        })
+        .await;
+
+        let mut reported = reported.into_inner().unwrap();
+        reported.sort();
+        assert_eq!(seats.len(), 2);
+        assert_eq!(reported, vec!["Codex", "Qwen (fallback for Gemini)"]);
+    }
+
+    #[tokio::test]
+    async fn test_collect_seats_until_keeps_votes_that_arrived() {
+        struct SlowExecutor;
+
+        #[async_trait]
+        impl CliExecutor for SlowExecutor {
+            fn name(&self) -> &str {
+                "Slow"
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                Ok(ModelVote::new("Slow", Vote::Pass, 90))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        let codex = MockExecutor::new("Codex", None);
+        let slow = SlowExecutor;
+        let enabled = ExecutorConfig::new("mock", &[]);
+        let slots = [
+            ExecutorSlot::new(&codex, &enabled),
+            ExecutorSlot::new(&slow, &enabled),
+        ];
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let started = std::time::Instant::now();
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_millis(200);
+        let seats = collect_seats_until(&slots, &request, Some(deadline), &|_| {}).await;
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(seats.len(), 2);
+        assert!(seats[0].result.is_ok());
+        assert!(!seats[0].cut_off);
+        assert_eq!(seats[1].name, "Slow");
+        assert!(seats[1].cut_off);
+        assert!(matches!(
+            seats[1].result,
+            Err(TetradError::ExecutorTimeout(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_collect_seats_until_settled_cancels_pending_seats() {
+        struct SlowExecutor;
+
+        #[async_trait]
+        impl CliExecutor for SlowExecutor {
+            fn name(&self) -> &str {
+                "Slow"
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                Ok(ModelVote::new("Slow", Vote::Pass, 90))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        let codex = MockExecutor::new("Codex", None);
+        let slow = SlowExecutor;
+        let enabled = ExecutorConfig::new("mock", &[]);
+        let slots = [
+            ExecutorSlot::new(&codex, &enabled),
+            ExecutorSlot::new(&slow, &enabled),
+        ];
+
+        // Decide assim que o primeiro voto chega
+        let checked = std::sync::Mutex::new(Vec::new());
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let started = std::time::Instant::now();
+        let seats =
+            collect_seats_until_settled(&slots, &request, None, &|_| {}, &|finished, waiting| {
+                checked
+                    .lock()
+                    .unwrap()
+                    .push((finished.len(), waiting.join(",")));
+                true
+            })
+            .await;
+
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(checked.into_inner().unwrap(), vec![(1, "Slow".to_string())]);
+        assert_eq!(seats.len(), 2);
+        assert!(seats[0].result.is_ok());
+        assert!(!seats[0].short_circuited);
+        assert_eq!(seats[1].name, "Slow");
+        assert!(seats[1].short_circuited);
+        assert!(!seats[1].cut_off);
+        assert!(seats[1].result.is_err());
+    }
+
+    /// Executor que falha `failures` vezes antes de votar.
+    struct FlakyExecutor {
+        failures: usize,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CliExecutor for FlakyExecutor {
+        fn name(&self) -> &str {
+            "Flaky"
+        }
+
+        fn command(&self) -> &str {
+            "mock"
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err(TetradError::ExecutorFailed(
+                    "Flaky".into(),
+                    "failed to spawn".into(),
+                ));
+            }
+            Ok(ModelVote::new("Flaky", Vote::Pass, 90))
+        }
+
+        fn specialization(&self) -> &str {
+            "test"
+        }
+    }
+
+    fn retry_config(attempts: u32) -> ExecutorConfig {
+        ExecutorConfig {
+            retry_attempts: attempts,
+            retry_backoff_ms: 1,
+            ..ExecutorConfig::new("mock", &[])
+        }
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(&TetradError::ExecutorTimeout("Gemini".into())));
+        assert!(is_retryable(&TetradError::ExecutorFailed(
+            "Gemini".into(),
+            "exit status 1".into()
+        )));
+        assert!(!is_retryable(&TetradError::ExecutorFailed(
+            "Gemini".into(),
+            "429 Too Many Requests".into()
+        )));
+        assert!(!is_retryable(&TetradError::ExecutorNotFound(
+            "Gemini".into()
+        )));
+    }
+
+    #[test]
+    fn test_retry_backoff_doubles() {
+        assert_eq!(retry_backoff(100, 0), Duration::from_millis(100));
+        assert_eq!(retry_backoff(100, 1), Duration::from_millis(200));
+        assert_eq!(retry_backoff(100, 3), Duration::from_millis(800));
+        assert_eq!(retry_backoff(u64::MAX, 40), Duration::from_millis(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_retry_recovers_after_two_failures() {
+        let flaky = FlakyExecutor {
+            failures: 2,
+            calls: AtomicUsize::new(0),
+        };
+        let config = retry_config(2);
+        let slots = [ExecutorSlot::new(&flaky, &config)];
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let seats = collect_seats(&slots, &request).await;
+
+        let vote = seats[0].result.as_ref().unwrap();
+        assert_eq!(vote.vote, Vote::Pass);
+        assert!(!vote.synthetic);
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_gives_up_after_attempts() {
+        let flaky = FlakyExecutor {
+            failures: 5,
+            calls: AtomicUsize::new(0),
+        };
+        let config = retry_config(2);
+        let slots = [ExecutorSlot::new(&flaky, &config)];
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let seats = collect_seats(&slots, &request).await;
+
+        assert!(seats[0].result.is_err());
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_on_timeout_every_attempt() {
+        struct SlowExecutor(AtomicUsize);
+
+        #[async_trait]
+        impl CliExecutor for SlowExecutor {
+            fn name(&self) -> &str {
+                "Slow"
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(ModelVote::new("Slow", Vote::Pass, 90))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        let slow = SlowExecutor(AtomicUsize::new(0));
+        let config = retry_config(2);
+        let slots =
+            [ExecutorSlot::new(&slow, &config).with_timeout(Some(Duration::from_millis(20)))];
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let seats = collect_seats(&slots, &request).await;
+
+        assert!(matches!(
+            seats[0].result,
+            Err(TetradError::ExecutorTimeout(_))
+        ));
+        assert!(!seats[0].cut_off);
+        assert_eq!(slow.0.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_disabled_executor_is_never_retried() {
+        let flaky = FlakyExecutor {
+            failures: 5,
+            calls: AtomicUsize::new(0),
+        };
+        let config = ExecutorConfig {
+            enabled: false,
+            ..retry_config(3)
+        };
+        let slots = [ExecutorSlot::new(&flaky, &config)];
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        assert!(collect_seats(&slots, &request).await.is_empty());
+        assert_eq!(flaky.calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_goes_to_fallback_without_retry() {
+        let gemini = MockExecutor::new("Gemini", Some("Error: 429 rate limit exceeded"));
+        let qwen = MockExecutor::new("Qwen", None);
+
+        let primary = retry_config(3);
+        let qwen_config = fallback_config("Gemini");
+        let slots = [
+            ExecutorSlot::new(&gemini, &primary),
+            ExecutorSlot::new(&qwen, &qwen_config),
+        ];
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let seats = collect_seats(&slots, &request).await;
+
+        assert!(seats[0].used_fallback);
+        assert_eq!(gemini.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(qwen.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -2,13 +2,15 @@
 
 use async_trait::async_trait;
 use serde::Deserialize;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::process::Command;
 
 use super::base::{CliExecutor, ExecutorResponse};
-use crate::types::config::ExecutorConfig;
+use super::input::run_cli;
+use super::prompts::PromptTemplates;
+use crate::types::config::{ExecutorConfig, InputMode};
 use crate::types::requests::EvaluationRequest;
-use crate::types::responses::{ModelVote, Vote};
+use crate::types::responses::ModelVote;
 use crate::{TetradError, TetradResult};
 
 /// Estrutura do wrapper JSON retornado pelo Gemini CLI com -o json.
@@ -31,6 +33,8 @@ pub struct GeminiExecutor {
     command_name: String,
     args: Vec<String>,
     timeout: Duration,
+    input_mode: InputMode,
+    prompts: Arc<PromptTemplates>,
 }
 
 impl GeminiExecutor {
@@ -41,6 +45,8 @@ impl GeminiExecutor {
             // -o json para formato de saída estruturado
             args: vec!["-o".to_string(), "json".to_string()],
             timeout: Duration::from_secs(60),
+            input_mode: InputMode::default(),
+            prompts: Arc::default(),
         }
     }
 
@@ -50,6 +56,8 @@ impl GeminiExecutor {
             command_name: config.command.clone(),
             args: config.args.clone(),
             timeout: Duration::from_secs(config.timeout_secs),
+            input_mode: config.input_mode,
+            prompts: Arc::default(),
         }
     }
 
@@ -60,6 +68,13 @@ impl GeminiExecutor {
         self
     }
 
+    /// Define os templates de prompt.
+    #[must_use]
+    pub fn with_prompts(mut self, prompts: Arc<PromptTemplates>) -> Self {
+        self.prompts = prompts;
+        self
+    }
+
     /// Parseia o output do Gemini CLI que vem em formato wrapper JSON.
     /// O Gemini retorna: {"session_id": "...", "response": "texto", "stats": {...}}
     fn parse_gemini_output(output: &str) -> TetradResult<ExecutorResponse> {
@@ -95,93 +110,7 @@ impl GeminiExecutor {
 
     /// Analisa texto de resposta e extrai informações estruturadas.
     fn analyze_text_response(text: &str) -> ExecutorResponse {
-        let lower = text.to_lowercase();
-
-        // Determina o voto baseado em palavras-chave
-        let vote = if lower.contains("erro crítico")
-            || lower.contains("bug grave")
-            || lower.contains("vulnerabilidade")
-            || lower.contains("falha de segurança")
-            || lower.contains("critical error")
-            || lower.contains("security vulnerability")
-        {
-            "FAIL"
-        } else if lower.contains("problema")
-            || lower.contains("issue")
-            || lower.contains("considere")
-            || lower.contains("sugestão")
-            || lower.contains("atenção")
-            || lower.contains("melhoria")
-            || lower.contains("overflow")
-            || lower.contains("observação")
-            || lower.contains("consider")
-            || lower.contains("suggestion")
-        {
-            "WARN"
-        } else {
-            "PASS"
-        };
-
-        // Score baseado no voto e conteúdo
-        let score = if vote == "PASS" {
-            if lower.contains("perfeito")
-                || lower.contains("excelente")
-                || lower.contains("perfect")
-            {
-                95
-            } else if lower.contains("bom")
-                || lower.contains("correto")
-                || lower.contains("idiomático")
-            {
-                85
-            } else {
-                80
-            }
-        } else if vote == "WARN" {
-            if lower.contains("menor") || lower.contains("minor") {
-                70
-            } else {
-                60
-            }
-        } else {
-            35
-        };
-
-        // Extrai issues do texto (linhas que começam com - ou *)
-        let issues: Vec<String> = text
-            .lines()
-            .filter(|line| {
-                let trimmed = line.trim();
-                trimmed.starts_with("- ") || trimmed.starts_with("* ") || trimmed.starts_with("• ")
-            })
-            .map(|line| {
-                line.trim()
-                    .trim_start_matches("- ")
-                    .trim_start_matches("* ")
-                    .trim_start_matches("• ")
-                    .to_string()
-            })
-            .take(5)
-            .collect();
-
-        // Extrai sugestões (linhas que contêm "sugest" ou "consider")
-        let suggestions: Vec<String> = text
-            .lines()
-            .filter(|line| {
-                let lower_line = line.to_lowercase();
-                lower_line.contains("sugest") || lower_line.contains("consider")
-            })
-            .map(|line| line.trim().to_string())
-            .take(3)
-            .collect();
-
-        ExecutorResponse {
-            vote: vote.to_string(),
-            score,
-            reasoning: text.chars().take(500).collect(),
-            issues,
-            suggestions,
-        }
+        ExecutorResponse::from_text(text)
     }
 }
 
@@ -205,22 +134,40 @@ impl CliExecutor for GeminiExecutor {
         "architecture"
     }
 
-    async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
-        let prompt = self.build_prompt(request);
-
-        // Constrói o comando: gemini -o json "prompt"
-        let mut cmd = Command::new(&self.command_name);
+    fn prompt_templates(&self) -> Option<&PromptTemplates> {
+        Some(&self.prompts)
+    }
 
-        // Adiciona argumentos do config (deve incluir "-o" e "json")
-        for arg in &self.args {
-            cmd.arg(arg);
+    fn parse_structured(&self, output: &str) -> TetradResult<ExecutorResponse> {
+        // Mesmo formato de parse_gemini_output, mas sem a análise de texto
+        let json_start = output.find('{').unwrap_or(0);
+        match serde_json::from_str::<GeminiWrapper>(&output[json_start..]) {
+            Ok(wrapper) => ExecutorResponse::parse_from_output(&wrapper.response, self.name()),
+            Err(_) => ExecutorResponse::parse_from_output(output, self.name()),
         }
+    }
 
-        // Adiciona o prompt
-        cmd.arg(&prompt);
+    async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
+        self.evaluate_with_timeout(request, self.timeout).await
+    }
+
+    async fn evaluate_with_timeout(
+        &self,
+        request: &EvaluationRequest,
+        timeout: Duration,
+    ) -> TetradResult<ModelVote> {
+        let prompt = self.build_prompt(request);
 
-        // Executa a CLI com timeout
-        let result = tokio::time::timeout(self.timeout, cmd.output()).await;
+        // Executa a CLI com timeout: gemini -o json "prompt" (argumentos do
+        // config devem incluir "-o" e "json")
+        let result = run_cli(
+            &self.command_name,
+            &self.args,
+            &prompt,
+            self.input_mode,
+            timeout,
+        )
+        .await;
 
         match result {
             Ok(Ok(output)) => {
@@ -267,7 +214,7 @@ impl CliExecutor for GeminiExecutor {
             Ok(Err(e)) => {
                 // CLI não encontrada ou erro de execução
                 if e.kind() == std::io::ErrorKind::NotFound {
-                    Ok(ModelVote::new(self.name(), Vote::Warn, 50)
+                    Ok(ModelVote::synthetic(self.name())
                         .with_reasoning("Gemini CLI não disponível"))
                 } else {
                     Err(TetradError::ExecutorFailed(
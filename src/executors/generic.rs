@@ -0,0 +1,212 @@
+//! Executor genérico para CLIs definidas na configuração.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::base::{CliExecutor, ExecutorResponse};
+use super::input::run_cli;
+use super::prompts::PromptTemplates;
+use crate::types::config::{CustomExecutorConfig, ExecutorOutputFormat, InputMode};
+use crate::types::requests::EvaluationRequest;
+use crate::types::responses::ModelVote;
+use crate::{TetradError, TetradResult};
+
+/// Executor para uma CLI arbitrária configurada em `[[executors.custom]]`.
+///
+/// O prompt é entregue conforme `input_mode`, como nas demais CLIs.
+pub struct GenericExecutor {
+    name: String,
+    command_name: String,
+    args: Vec<String>,
+    timeout: Duration,
+    input_mode: InputMode,
+    specialization: String,
+    output_format: ExecutorOutputFormat,
+    prompts: Arc<PromptTemplates>,
+}
+
+impl GenericExecutor {
+    /// Cria executor a partir da configuração do TOML.
+    pub fn from_config(config: &CustomExecutorConfig) -> Self {
+        Self {
+            name: config.name.clone(),
+            command_name: config.executor.command.clone(),
+            args: config.executor.args.clone(),
+            timeout: Duration::from_secs(config.executor.timeout_secs),
+            input_mode: config.executor.input_mode,
+            specialization: config.specialization.clone(),
+            output_format: config.output_format,
+            prompts: Arc::default(),
+        }
+    }
+
+    /// Define o timeout.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Define os templates de prompt.
+    #[must_use]
+    pub fn with_prompts(mut self, prompts: Arc<PromptTemplates>) -> Self {
+        self.prompts = prompts;
+        self
+    }
+
+    /// Converte a saída da CLI em resposta, conforme o formato configurado.
+    fn parse_output(&self, stdout: &str) -> TetradResult<ExecutorResponse> {
+        match self.output_format {
+            ExecutorOutputFormat::Json => ExecutorResponse::parse_from_output(stdout, &self.name),
+            ExecutorOutputFormat::Text => {
+                // Texto livre: usa JSON se o modelo o retornou, senão heurísticas
+                let response = ExecutorResponse::parse_from_output(stdout, &self.name)
+                    .unwrap_or_else(|_| ExecutorResponse::from_text(stdout));
+                Ok(response)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl CliExecutor for GenericExecutor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn command(&self) -> &str {
+        &self.command_name
+    }
+
+    fn specialization(&self) -> &str {
+        &self.specialization
+    }
+
+    fn prompt_templates(&self) -> Option<&PromptTemplates> {
+        Some(&self.prompts)
+    }
+
+    fn parse_structured(&self, output: &str) -> TetradResult<ExecutorResponse> {
+        // Em formato texto, as heurísticas são o comportamento esperado
+        self.parse_output(output)
+    }
+
+    async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
+        self.evaluate_with_timeout(request, self.timeout).await
+    }
+
+    async fn evaluate_with_timeout(
+        &self,
+        request: &EvaluationRequest,
+        timeout: Duration,
+    ) -> TetradResult<ModelVote> {
+        let prompt = self.build_prompt(request);
+
+        // Executa a CLI com timeout, com os argumentos do config
+        let result = run_cli(
+            &self.command_name,
+            &self.args,
+            &prompt,
+            self.input_mode,
+            timeout,
+        )
+        .await;
+
+        match result {
+            Ok(Ok(output)) => {
+                if output.status.success() {
+                    let stdout = String::from_utf8_lossy(&output.stdout);
+                    let response = self.parse_output(&stdout)?;
+                    Ok(response.into_vote(&self.name))
+                } else {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    Err(TetradError::ExecutorFailed(
+                        self.name.clone(),
+                        stderr.to_string(),
+                    ))
+                }
+            }
+            Ok(Err(e)) => {
+                if e.kind() == std::io::ErrorKind::NotFound {
+                    Err(TetradError::ExecutorNotFound(self.name.clone()))
+                } else {
+                    Err(TetradError::ExecutorFailed(
+                        self.name.clone(),
+                        e.to_string(),
+                    ))
+                }
+            }
+            Err(_) => Err(TetradError::ExecutorTimeout(self.name.clone())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_config(output_format: ExecutorOutputFormat) -> CustomExecutorConfig {
+        toml::from_str::<CustomExecutorConfig>(&format!(
+            r#"
+            name = "llm"
+            command = "llm"
+            args = ["-m", "local"]
+            specialization = "security"
+            output_format = "{}"
+            "#,
+            match output_format {
+                ExecutorOutputFormat::Json => "json",
+                ExecutorOutputFormat::Text => "text",
+            }
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = custom_config(ExecutorOutputFormat::Json);
+        assert!(config.executor.enabled);
+        assert_eq!(config.executor.timeout_secs, 30);
+
+        let executor = GenericExecutor::from_config(&config);
+        assert_eq!(executor.name(), "llm");
+        assert_eq!(executor.command(), "llm");
+        assert_eq!(executor.specialization(), "security");
+        assert_eq!(executor.args, vec!["-m", "local"]);
+    }
+
+    #[test]
+    fn test_parse_output_json() {
+        let executor = GenericExecutor::from_config(&custom_config(ExecutorOutputFormat::Json));
+
+        let output = r#"{"vote": "PASS", "score": 88, "reasoning": "ok", "issues": []}"#;
+        let response = executor.parse_output(output).unwrap();
+        assert_eq!(response.vote, "PASS");
+        assert_eq!(response.score, 88);
+
+        assert!(executor.parse_output("looks fine to me").is_err());
+    }
+
+    #[test]
+    fn test_parse_output_text() {
+        let executor = GenericExecutor::from_config(&custom_config(ExecutorOutputFormat::Text));
+
+        let response = executor
+            .parse_output("Found a critical error:\n- unchecked index")
+            .unwrap();
+        assert_eq!(response.vote, "FAIL");
+        assert_eq!(response.issues.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_missing_command_is_not_found() {
+        let mut config = custom_config(ExecutorOutputFormat::Json);
+        config.executor.command = "tetrad-nonexistent-cli".to_string();
+        let executor = GenericExecutor::from_config(&config);
+
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let result = executor.evaluate(&request).await;
+        assert!(matches!(result, Err(TetradError::ExecutorNotFound(_))));
+    }
+}
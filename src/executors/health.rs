@@ -0,0 +1,586 @@
+//! Circuit breaker de saúde dos executores.
+//!
+//! Após `circuit_failure_threshold` falhas consecutivas (erros ou timeouts),
+//! o circuito do executor abre e ele deixa de ser chamado durante
+//! `circuit_cooldown_secs`. Passado o cooldown, uma única avaliação de teste
+//! (half-open) é permitida: sucesso fecha o circuito, falha o reabre.
+//!
+//! Os circuitos abertos também vão para a [`QuarantineList`], para sobreviver
+//! a reinícios: um executor restaurado dela fica aberto até `retry_after` e
+//! então recebe uma única avaliação de teste.
+//!
+//! Também guarda a latência das últimas avaliações de cada executor, usada
+//! nas estimativas de `tetrad_estimate` e no timeout adaptativo, e quantos votos inferidos de texto
+//! livre concordaram com a decisão final, por idioma da resposta.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use serde::Serialize;
+
+use crate::types::config::ExecutorConfig;
+use crate::types::responses::ResponseLanguage;
+
+use super::quarantine::{QuarantineEntry, QuarantineList};
+
+/// Número de latências recentes mantidas por executor para a média móvel.
+pub const LATENCY_WINDOW: usize = 20;
+
+/// Estado do circuito de um executor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Executor chamado normalmente.
+    Closed,
+    /// Executor ignorado até o fim do cooldown.
+    Open {
+        /// Tempo restante até a próxima tentativa.
+        retry_in: Duration,
+    },
+    /// Uma avaliação de teste está liberada ou em andamento.
+    HalfOpen,
+}
+
+impl std::fmt::Display for CircuitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitState::Closed => write!(f, "circuit closed"),
+            CircuitState::Open { retry_in } => {
+                write!(f, "circuit open, retry in {}s", retry_in.as_secs())
+            }
+            CircuitState::HalfOpen => write!(f, "circuit half-open"),
+        }
+    }
+}
+
+/// Contadores de um executor.
+#[derive(Debug, Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// Fim do cooldown de um circuito restaurado da quarentena; os demais
+    /// usam `circuit_cooldown_secs`.
+    retry_at: Option<Instant>,
+    probing: bool,
+}
+
+/// Votos de um executor inferidos de texto livre num idioma.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct FallbackAccuracy {
+    /// Votos inferidos de texto.
+    pub votes: u64,
+    /// Votos que concordaram com a decisão final.
+    pub agreed: u64,
+    /// Fração de votos que concordaram (0.0 a 1.0).
+    pub accuracy: f64,
+}
+
+/// Circuit breakers de todos os executores, indexados pelo nome.
+#[derive(Debug, Default)]
+pub struct ExecutorHealth {
+    breakers: Mutex<HashMap<String, Breaker>>,
+    latencies: Mutex<HashMap<String, VecDeque<Duration>>>,
+    text_fallbacks: Mutex<HashMap<String, BTreeMap<ResponseLanguage, FallbackAccuracy>>>,
+    quarantine: Mutex<QuarantineList>,
+}
+
+impl ExecutorHealth {
+    /// Cria um registro vazio (todos os circuitos fechados).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cria um registro que persiste a quarentena em `path`.
+    ///
+    /// Os executores já em quarentena começam com o circuito aberto até o
+    /// seu `retry_after`; com o prazo vencido, a próxima chamada é o teste.
+    pub fn with_quarantine(path: &Path) -> Self {
+        let quarantine = QuarantineList::load(path);
+        let now = Instant::now();
+        let breakers = quarantine
+            .entries()
+            .map(|entry| {
+                let remaining = (entry.retry_after - Utc::now())
+                    .to_std()
+                    .unwrap_or_default();
+                tracing::info!(
+                    executor = %entry.executor,
+                    retry_in_secs = remaining.as_secs(),
+                    reason = %entry.reason,
+                    "Executor restored from quarantine"
+                );
+                let breaker = Breaker {
+                    opened_at: Some(now),
+                    retry_at: now.checked_add(remaining),
+                    ..Breaker::default()
+                };
+                (entry.executor.clone(), breaker)
+            })
+            .collect();
+
+        Self {
+            breakers: Mutex::new(breakers),
+            quarantine: Mutex::new(quarantine),
+            ..Self::default()
+        }
+    }
+
+    /// Executores em quarentena, em ordem alfabética.
+    pub fn quarantined(&self) -> Vec<QuarantineEntry> {
+        let quarantine = self.quarantine.lock().unwrap_or_else(|e| e.into_inner());
+        quarantine.entries().cloned().collect()
+    }
+
+    /// Verifica se o executor pode ser chamado agora.
+    ///
+    /// Com o cooldown esgotado, libera uma única chamada de teste e passa o
+    /// circuito para half-open.
+    pub fn allow(&self, name: &str, config: &ExecutorConfig) -> bool {
+        self.allow_at(name, config, Instant::now())
+    }
+
+    /// Registra uma avaliação bem-sucedida, fechando o circuito e tirando o
+    /// executor da quarentena.
+    pub fn record_success(&self, name: &str) {
+        let mut breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(breaker) = breakers.get_mut(name) {
+            if breaker.opened_at.is_some() {
+                tracing::info!(executor = name, "Circuit closed after successful probe");
+                self.release(name);
+            }
+            *breaker = Breaker::default();
+        }
+    }
+
+    /// Registra uma falha ou timeout; `reason` é guardado na quarentena se
+    /// o circuito abrir.
+    ///
+    /// Retorna `true` se esta falha abriu o circuito.
+    pub fn record_failure(&self, name: &str, config: &ExecutorConfig, reason: &str) -> bool {
+        let opened = self.record_failure_at(name, config, Instant::now());
+        if opened {
+            let opened_at = Utc::now();
+            let cooldown = chrono::Duration::seconds(
+                i64::try_from(config.circuit_cooldown_secs).unwrap_or(i64::MAX),
+            );
+            let entry = QuarantineEntry {
+                executor: name.to_string(),
+                reason: reason.to_string(),
+                opened_at,
+                retry_after: opened_at
+                    .checked_add_signed(cooldown)
+                    .unwrap_or(chrono::DateTime::<Utc>::MAX_UTC),
+            };
+            let mut quarantine = self.quarantine.lock().unwrap_or_else(|e| e.into_inner());
+            if let Err(e) = quarantine.insert(entry) {
+                tracing::warn!(executor = name, error = %e, "Could not save quarantine");
+            }
+        }
+        opened
+    }
+
+    /// Retorna o estado atual do circuito do executor.
+    pub fn state(&self, name: &str, config: &ExecutorConfig) -> CircuitState {
+        self.state_at(name, config, Instant::now())
+    }
+
+    /// Registra a duração de uma avaliação bem-sucedida.
+    ///
+    /// Só as últimas [`LATENCY_WINDOW`] amostras são mantidas.
+    pub fn record_latency(&self, name: &str, latency: Duration) {
+        let mut latencies = self.latencies.lock().unwrap_or_else(|e| e.into_inner());
+        let samples = latencies.entry(name.to_string()).or_default();
+        if samples.len() == LATENCY_WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+    }
+
+    /// Média móvel das latências recentes, ou `None` sem amostras.
+    pub fn average_latency(&self, name: &str) -> Option<Duration> {
+        let latencies = self.latencies.lock().unwrap_or_else(|e| e.into_inner());
+        let samples = latencies.get(name).filter(|s| !s.is_empty())?;
+        Some(samples.iter().sum::<Duration>() / samples.len() as u32)
+    }
+
+    /// Percentil 90 (nearest-rank) das latências recentes, ou `None` sem
+    /// amostras. Usado pelo timeout adaptativo.
+    pub fn p90_latency(&self, name: &str) -> Option<Duration> {
+        let latencies = self.latencies.lock().unwrap_or_else(|e| e.into_inner());
+        let mut samples: Vec<Duration> = latencies.get(name)?.iter().copied().collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort();
+        let rank = (samples.len() * 9).div_ceil(10);
+        Some(samples[rank - 1])
+    }
+
+    /// Registra um voto inferido de texto livre e se ele concordou com a
+    /// decisão final.
+    pub fn record_text_vote(&self, name: &str, language: ResponseLanguage, agreed: bool) {
+        let mut fallbacks = self
+            .text_fallbacks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let counter = fallbacks
+            .entry(name.to_string())
+            .or_default()
+            .entry(language)
+            .or_default();
+        counter.votes += 1;
+        if agreed {
+            counter.agreed += 1;
+        }
+        counter.accuracy = counter.agreed as f64 / counter.votes as f64;
+    }
+
+    /// Votos inferidos de texto livre do executor, indexados pelo código do
+    /// idioma (`es`, `zh`, `unsupported`...).
+    pub fn text_fallback_accuracy(&self, name: &str) -> BTreeMap<&'static str, FallbackAccuracy> {
+        let fallbacks = self
+            .text_fallbacks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        fallbacks
+            .get(name)
+            .map(|languages| {
+                languages
+                    .iter()
+                    .map(|(language, counter)| (language.code(), *counter))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn release(&self, name: &str) {
+        let mut quarantine = self.quarantine.lock().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = quarantine.remove(name) {
+            tracing::warn!(executor = name, error = %e, "Could not save quarantine");
+        }
+    }
+
+    fn allow_at(&self, name: &str, config: &ExecutorConfig, now: Instant) -> bool {
+        let mut breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(breaker) = breakers.get_mut(name) else {
+            return true;
+        };
+
+        match Self::breaker_state(breaker, config, now) {
+            CircuitState::Closed => true,
+            CircuitState::Open { .. } => false,
+            CircuitState::HalfOpen if breaker.probing => false,
+            CircuitState::HalfOpen => {
+                tracing::info!(executor = name, "Circuit half-open, probing executor");
+                breaker.probing = true;
+                true
+            }
+        }
+    }
+
+    fn record_failure_at(&self, name: &str, config: &ExecutorConfig, now: Instant) -> bool {
+        if config.circuit_failure_threshold == 0 {
+            return false;
+        }
+
+        let mut breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        let breaker = breakers.entry(name.to_string()).or_default();
+        breaker.consecutive_failures = breaker.consecutive_failures.saturating_add(1);
+
+        // Uma falha no teste reabre o circuito com um novo cooldown
+        let reopen = breaker.probing;
+        let trip = breaker.opened_at.is_none()
+            && breaker.consecutive_failures >= config.circuit_failure_threshold;
+        if !reopen && !trip {
+            return false;
+        }
+
+        breaker.opened_at = Some(now);
+        breaker.retry_at = None;
+        breaker.probing = false;
+        tracing::warn!(
+            executor = name,
+            failures = breaker.consecutive_failures,
+            cooldown_secs = config.circuit_cooldown_secs,
+            "Circuit opened, skipping executor until cooldown expires"
+        );
+        true
+    }
+
+    fn state_at(&self, name: &str, config: &ExecutorConfig, now: Instant) -> CircuitState {
+        let breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        breakers
+            .get(name)
+            .map(|breaker| Self::breaker_state(breaker, config, now))
+            .unwrap_or(CircuitState::Closed)
+    }
+
+    fn breaker_state(breaker: &Breaker, config: &ExecutorConfig, now: Instant) -> CircuitState {
+        let Some(opened_at) = breaker.opened_at else {
+            return CircuitState::Closed;
+        };
+        let retry_in = match breaker.retry_at {
+            Some(retry_at) => retry_at.saturating_duration_since(now),
+            None => Duration::from_secs(config.circuit_cooldown_secs)
+                .saturating_sub(now.saturating_duration_since(opened_at)),
+        };
+
+        if breaker.probing || retry_in.is_zero() {
+            CircuitState::HalfOpen
+        } else {
+            CircuitState::Open { retry_in }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executors::{collect_seats, CliExecutor, ExecutorSlot};
+    use crate::types::requests::EvaluationRequest;
+    use crate::types::responses::{ModelVote, Vote};
+    use crate::{TetradError, TetradResult};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    /// Executor que falha enquanto `failing` estiver ativo.
+    struct MockExecutor {
+        failing: AtomicBool,
+        calls: AtomicUsize,
+    }
+
+    impl MockExecutor {
+        fn failing() -> Self {
+            Self {
+                failing: AtomicBool::new(true),
+                calls: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl CliExecutor for MockExecutor {
+        fn name(&self) -> &str {
+            "Gemini"
+        }
+
+        fn command(&self) -> &str {
+            "mock"
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+
+        async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.failing.load(Ordering::SeqCst) {
+                Err(TetradError::ExecutorTimeout("Gemini".to_string()))
+            } else {
+                Ok(ModelVote::new("Gemini", Vote::Pass, 90))
+            }
+        }
+
+        fn specialization(&self) -> &str {
+            "test"
+        }
+    }
+
+    fn breaker_config() -> ExecutorConfig {
+        ExecutorConfig {
+            circuit_failure_threshold: 3,
+            circuit_cooldown_secs: 60,
+            ..ExecutorConfig::new("mock", &[])
+        }
+    }
+
+    /// Executa uma avaliação respeitando o circuito, como o `ToolHandler`.
+    async fn evaluate(
+        health: &ExecutorHealth,
+        executor: &MockExecutor,
+        config: &ExecutorConfig,
+        now: Instant,
+    ) {
+        if !health.allow_at(executor.name(), config, now) {
+            return;
+        }
+        let request = EvaluationRequest::new("fn main() {}", "rust");
+        let seats = collect_seats(&[ExecutorSlot::new(executor, config)], &request).await;
+        match &seats[0].result {
+            Ok(_) => health.record_success(executor.name()),
+            Err(_) => {
+                health.record_failure_at(executor.name(), config, now);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_opens_after_consecutive_failures() {
+        let health = ExecutorHealth::new();
+        let executor = MockExecutor::failing();
+        let config = breaker_config();
+        let now = Instant::now();
+
+        for _ in 0..5 {
+            evaluate(&health, &executor, &config, now).await;
+        }
+
+        // Só as 3 primeiras chamadas chegam ao executor
+        assert_eq!(executor.calls.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            health.state_at("Gemini", &config, now + Duration::from_secs(15)),
+            CircuitState::Open {
+                retry_in: Duration::from_secs(45)
+            }
+        );
+        assert_eq!(
+            health
+                .state_at("Gemini", &config, now + Duration::from_secs(15))
+                .to_string(),
+            "circuit open, retry in 45s"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_successful_probe_closes_circuit() {
+        let health = ExecutorHealth::new();
+        let executor = MockExecutor::failing();
+        let config = breaker_config();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            evaluate(&health, &executor, &config, now).await;
+        }
+        executor.failing.store(false, Ordering::SeqCst);
+
+        let later = now + Duration::from_secs(61);
+        assert_eq!(
+            health.state_at("Gemini", &config, later),
+            CircuitState::HalfOpen
+        );
+        evaluate(&health, &executor, &config, later).await;
+
+        assert_eq!(executor.calls.load(Ordering::SeqCst), 4);
+        assert_eq!(
+            health.state_at("Gemini", &config, later),
+            CircuitState::Closed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_probe_reopens_circuit() {
+        let health = ExecutorHealth::new();
+        let executor = MockExecutor::failing();
+        let config = breaker_config();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            evaluate(&health, &executor, &config, now).await;
+        }
+
+        let later = now + Duration::from_secs(61);
+        evaluate(&health, &executor, &config, later).await;
+        assert_eq!(executor.calls.load(Ordering::SeqCst), 4);
+
+        // Novo cooldown a partir da falha do teste
+        assert_eq!(
+            health.state_at("Gemini", &config, later + Duration::from_secs(30)),
+            CircuitState::Open {
+                retry_in: Duration::from_secs(30)
+            }
+        );
+    }
+
+    #[test]
+    fn test_only_one_probe_at_a_time() {
+        let health = ExecutorHealth::new();
+        let config = breaker_config();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            health.record_failure_at("Gemini", &config, now);
+        }
+
+        let later = now + Duration::from_secs(60);
+        assert!(health.allow_at("Gemini", &config, later));
+        assert!(!health.allow_at("Gemini", &config, later));
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let health = ExecutorHealth::new();
+        let config = breaker_config();
+
+        health.record_failure("Gemini", &config, "timeout");
+        health.record_failure("Gemini", &config, "timeout");
+        health.record_success("Gemini");
+        assert!(!health.record_failure("Gemini", &config, "timeout"));
+        assert!(!health.record_failure("Gemini", &config, "timeout"));
+        assert!(health.record_failure("Gemini", &config, "timeout"));
+    }
+
+    #[test]
+    fn test_zero_threshold_disables_breaker() {
+        let health = ExecutorHealth::new();
+        let config = ExecutorConfig {
+            circuit_failure_threshold: 0,
+            ..breaker_config()
+        };
+
+        for _ in 0..10 {
+            assert!(!health.record_failure("Gemini", &config, "timeout"));
+        }
+        assert!(health.allow("Gemini", &config));
+        assert_eq!(health.state("Gemini", &config), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_average_latency_uses_recent_window() {
+        let health = ExecutorHealth::new();
+        assert_eq!(health.average_latency("Gemini"), None);
+
+        health.record_latency("Gemini", Duration::from_secs(100));
+        for _ in 0..LATENCY_WINDOW {
+            health.record_latency("Gemini", Duration::from_secs(2));
+        }
+
+        // A amostra mais antiga saiu da janela
+        assert_eq!(
+            health.average_latency("Gemini"),
+            Some(Duration::from_secs(2))
+        );
+    }
+
+    #[test]
+    fn test_p90_latency() {
+        let health = ExecutorHealth::new();
+        assert_eq!(health.p90_latency("Codex"), None);
+
+        for secs in (1..=10).rev() {
+            health.record_latency("Codex", Duration::from_secs(secs));
+        }
+        assert_eq!(health.p90_latency("Codex"), Some(Duration::from_secs(9)));
+
+        health.record_latency("Qwen", Duration::from_secs(3));
+        assert_eq!(health.p90_latency("Qwen"), Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn test_text_fallback_accuracy_per_language() {
+        let health = ExecutorHealth::new();
+        assert!(health.text_fallback_accuracy("Qwen").is_empty());
+
+        health.record_text_vote("Qwen", ResponseLanguage::Spanish, true);
+        health.record_text_vote("Qwen", ResponseLanguage::Spanish, false);
+        health.record_text_vote("Qwen", ResponseLanguage::Chinese, true);
+
+        let accuracy = health.text_fallback_accuracy("Qwen");
+        assert_eq!(accuracy["es"].votes, 2);
+        assert_eq!(accuracy["es"].agreed, 1);
+        assert_eq!(accuracy["es"].accuracy, 0.5);
+        assert_eq!(accuracy["zh"].accuracy, 1.0);
+        assert!(health.text_fallback_accuracy("Codex").is_empty());
+    }
+}
@@ -0,0 +1,266 @@
+//! Entrega do prompt às CLIs dos executores.
+//!
+//! Por padrão o prompt vai como último argumento, mas arquivos grandes
+//! estouram o limite da linha de comando (E2BIG no Linux, truncamento
+//! silencioso no Windows). Com `input_mode = "stdin"` o prompt é escrito na
+//! entrada padrão da CLI; com `"temp_file"`, gravado em um arquivo temporário
+//! (ver [`super::scratch`]) cujo caminho substitui `{prompt_file}` nos
+//! argumentos. No modo `arg`,
+//! prompts maiores que [`ARG_PROMPT_LIMIT`] também vão pela entrada padrão.
+
+use std::io;
+use std::path::Path;
+use std::process::{Output, Stdio};
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time::error::Elapsed;
+
+use super::scratch::Scratch;
+use crate::types::config::InputMode;
+
+/// Placeholder substituído pelo caminho do arquivo no modo `temp_file`.
+pub const PROMPT_FILE_PLACEHOLDER: &str = "{prompt_file}";
+
+/// Tamanho máximo (em bytes) de um prompt passado como argumento.
+///
+/// Fica abaixo do limite da linha de comando do Windows (32.767 caracteres).
+pub const ARG_PROMPT_LIMIT: usize = 30_000;
+
+/// Modo efetivo para `prompt`: prompts grandes não vão como argumento.
+pub fn effective_mode(mode: InputMode, prompt: &str) -> InputMode {
+    if mode == InputMode::Arg && prompt.len() > ARG_PROMPT_LIMIT {
+        InputMode::Stdin
+    } else {
+        mode
+    }
+}
+
+/// Executa `command` com `args`, entregando `prompt` conforme `mode`.
+///
+/// O resultado tem a mesma forma de `tokio::time::timeout(.., cmd.output())`.
+/// No timeout o processo filho é encerrado, assim como quando o future é
+/// descartado (requisição cancelada pelo cliente MCP), e o arquivo
+/// temporário é removido em todos os caminhos.
+pub async fn run_cli(
+    command: &str,
+    args: &[String],
+    prompt: &str,
+    mode: InputMode,
+    timeout: Duration,
+) -> Result<io::Result<Output>, Elapsed> {
+    let mode = effective_mode(mode, prompt);
+
+    // Removido no drop, inclusive após erro, timeout ou cancelamento
+    let mut scratch = Scratch::new();
+    let prompt_file = match mode {
+        InputMode::TempFile => match scratch.file("tetrad-prompt", "txt", prompt.as_bytes()) {
+            Ok(path) => Some(path),
+            Err(e) => return Ok(Err(e)),
+        },
+        _ => None,
+    };
+
+    let mut cmd = Command::new(command);
+    match &prompt_file {
+        Some(path) => cmd.args(file_args(args, path)),
+        None => cmd.args(args),
+    };
+    if mode == InputMode::Arg {
+        cmd.arg(prompt);
+    }
+
+    // Sem stdin explícito a CLI herdaria o stdin do servidor MCP
+    cmd.stdin(if mode == InputMode::Stdin {
+        Stdio::piped()
+    } else {
+        Stdio::null()
+    })
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .kill_on_drop(true);
+
+    let result = tokio::time::timeout(timeout, async {
+        let mut child = cmd.spawn()?;
+        let stdin = child.stdin.take();
+
+        // Escreve e lê ao mesmo tempo: a CLI pode encher o pipe de saída
+        // antes de consumir todo o prompt
+        let write = async {
+            if let Some(mut stdin) = stdin {
+                match stdin.write_all(prompt.as_bytes()).await {
+                    // A CLI pode terminar sem ler tudo
+                    Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e),
+                    _ => {}
+                }
+                // O drop fecha o stdin e sinaliza EOF
+            }
+            Ok(())
+        };
+        let (written, output) = tokio::join!(write, child.wait_with_output());
+        written?;
+        output
+    })
+    .await;
+
+    drop(scratch);
+    result
+}
+
+/// Argumentos com `{prompt_file}` substituído pelo caminho do arquivo.
+///
+/// Sem o placeholder, o caminho é acrescentado como último argumento.
+fn file_args(args: &[String], path: &Path) -> Vec<String> {
+    let path = path.to_string_lossy();
+    let mut substituted = false;
+
+    let mut result: Vec<String> = args
+        .iter()
+        .map(|arg| {
+            if arg.contains(PROMPT_FILE_PLACEHOLDER) {
+                substituted = true;
+                arg.replace(PROMPT_FILE_PLACEHOLDER, &path)
+            } else {
+                arg.clone()
+            }
+        })
+        .collect();
+    if !substituted {
+        result.push(path.into_owned());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str, extra: &[&str]) -> Vec<String> {
+        ["-c", script, "sh"]
+            .iter()
+            .chain(extra)
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    async fn run(args: &[String], prompt: &str, mode: InputMode) -> String {
+        let output = run_cli("sh", args, prompt, mode, Duration::from_secs(10))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn test_effective_mode() {
+        let large = "x".repeat(ARG_PROMPT_LIMIT + 1);
+        assert_eq!(effective_mode(InputMode::Arg, "small"), InputMode::Arg);
+        assert_eq!(effective_mode(InputMode::Arg, &large), InputMode::Stdin);
+        assert_eq!(
+            effective_mode(InputMode::TempFile, &large),
+            InputMode::TempFile
+        );
+    }
+
+    #[test]
+    fn test_file_args() {
+        let path = Path::new("/tmp/prompt.txt");
+        let args = vec!["-f".to_string(), "--prompt={prompt_file}".to_string()];
+        assert_eq!(
+            file_args(&args, path),
+            vec!["-f", "--prompt=/tmp/prompt.txt"]
+        );
+        assert_eq!(
+            file_args(&["-f".to_string()], path),
+            vec!["-f", "/tmp/prompt.txt"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_arg_and_stdin_modes() {
+        // Imprime o argumento, ou o stdin quando não há argumento
+        let args = sh(
+            r#"if [ $# -gt 0 ]; then printf 'arg:%s' "$1"; else cat; fi"#,
+            &[],
+        );
+
+        assert_eq!(run(&args, "hello", InputMode::Arg).await, "arg:hello");
+        assert_eq!(run(&args, "hello", InputMode::Stdin).await, "hello");
+
+        // Prompt grande demais para a linha de comando vai pelo stdin
+        let large = "y".repeat(300 * 1024);
+        assert_eq!(run(&args, &large, InputMode::Arg).await, large);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_temp_file_mode_cleans_up() {
+        let args = sh(
+            r#"printf '%s\n' "$1"; cat "$1""#,
+            &[PROMPT_FILE_PLACEHOLDER],
+        );
+
+        let stdout = run(&args, "from a file", InputMode::TempFile).await;
+        let (path, contents) = stdout.split_once('\n').unwrap();
+        assert_eq!(contents, "from a file");
+        assert!(path.contains("tetrad-prompt-"));
+        assert!(!Path::new(path).exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_timeout_kills_child_and_removes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let marker = dir.path().join("survived");
+        let path_file = dir.path().join("path");
+        let script = format!(
+            r#"printf '%s' "$1" > '{}'; sleep 1; touch '{}'"#,
+            path_file.display(),
+            marker.display()
+        );
+
+        let result = run_cli(
+            "sh",
+            &sh(&script, &[]),
+            "slow",
+            InputMode::TempFile,
+            Duration::from_millis(300),
+        )
+        .await;
+        assert!(result.is_err());
+
+        let prompt_path = std::fs::read_to_string(&path_file).unwrap();
+        assert!(!Path::new(&prompt_path).exists());
+
+        // O processo foi encerrado antes de criar o marcador
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(!marker.exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancelled_evaluation_removes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path_file = dir.path().join("path");
+        let script = format!(r#"printf '%s' "$1" > '{}'; sleep 5"#, path_file.display());
+        let args = sh(&script, &[]);
+
+        // Cancela a avaliação por fora, antes do timeout do próprio run_cli
+        let evaluation = run_cli(
+            "sh",
+            &args,
+            "cancelled",
+            InputMode::TempFile,
+            Duration::from_secs(30),
+        );
+        let cancelled = tokio::time::timeout(Duration::from_millis(500), evaluation).await;
+        assert!(cancelled.is_err());
+
+        let prompt_path = std::fs::read_to_string(&path_file).unwrap();
+        assert!(prompt_path.contains(crate::executors::SCRATCH_DIR_NAME));
+        assert!(!Path::new(&prompt_path).exists());
+    }
+}
@@ -0,0 +1,180 @@
+//! Executor determinístico para demos, CI e o modo `--offline`.
+//!
+//! Não chama nenhuma CLI: o voto é derivado só do conteúdo de
+//! `request.code`, então a mesma entrada sempre produz o mesmo voto. É
+//! ativado por `command = "mock"` em `[executors.<nome>]` ou pela flag
+//! global `--offline`, que troca todos os executores por mocks.
+//!
+//! Regras (a busca ignora maiúsculas/minúsculas):
+//!
+//! | Conteúdo                    | Voto | Score | Issue                                   |
+//! |-----------------------------|------|-------|-----------------------------------------|
+//! | `password =` ou `password=` | FAIL | 20    | [`HARDCODED_PASSWORD_ISSUE`]            |
+//! | `unwrap()`                  | WARN | 65    | [`UNWRAP_ISSUE`]                        |
+//! | nenhum dos anteriores       | PASS | 90    | —                                       |
+//!
+//! Quando as duas regras casam, os dois issues são reportados e vale o voto
+//! mais severo (FAIL). Cada issue traz as linhas (1-based) em que o trecho
+//! aparece e uma sugestão.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use super::base::CliExecutor;
+use crate::types::requests::EvaluationRequest;
+use crate::types::responses::{ModelVote, Vote};
+use crate::TetradResult;
+
+/// Valor de `command` que seleciona o [`MockExecutor`].
+pub const MOCK_COMMAND: &str = "mock";
+
+/// Issue reportado para senhas no código.
+pub const HARDCODED_PASSWORD_ISSUE: &str = "Security: hardcoded password in source code";
+
+/// Issue reportado para `unwrap()`.
+pub const UNWRAP_ISSUE: &str = "Error handling: unwrap() panics on None or Err";
+
+/// Executor que vota por regras fixas, sem processo externo.
+pub struct MockExecutor {
+    name: String,
+    specialization: String,
+}
+
+impl MockExecutor {
+    /// Cria um mock com o nome e a especialização do executor que substitui.
+    pub fn new(name: impl Into<String>, specialization: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            specialization: specialization.into(),
+        }
+    }
+
+    /// Aplica as regras do módulo ao código.
+    pub fn vote_for(&self, code: &str) -> ModelVote {
+        let lower = code.to_lowercase();
+        let password_lines = lines_matching(&lower, &["password =", "password="]);
+        let unwrap_lines = lines_matching(&lower, &["unwrap()"]);
+
+        let mut issues = Vec::new();
+        let mut suggestions = Vec::new();
+        let mut lines = HashMap::new();
+        let mut issue_suggestions = HashMap::new();
+        let mut add = |issue: &str, found: Vec<u32>, suggestion: &str| {
+            issues.push(issue.to_string());
+            suggestions.push(suggestion.to_string());
+            lines.insert(issue.to_string(), found);
+            issue_suggestions.insert(issue.to_string(), suggestion.to_string());
+        };
+
+        if !password_lines.is_empty() {
+            add(
+                HARDCODED_PASSWORD_ISSUE,
+                password_lines,
+                "Read the password from an environment variable or a secret store",
+            );
+        }
+        if !unwrap_lines.is_empty() {
+            add(
+                UNWRAP_ISSUE,
+                unwrap_lines,
+                "Propagate the error with ? or handle it with match",
+            );
+        }
+
+        let (vote, score, reasoning) = if lines.contains_key(HARDCODED_PASSWORD_ISSUE) {
+            (Vote::Fail, 20, "Mock review: hardcoded password found.")
+        } else if lines.contains_key(UNWRAP_ISSUE) {
+            (Vote::Warn, 65, "Mock review: unwrap() found.")
+        } else {
+            (Vote::Pass, 90, "Mock review: no issues found.")
+        };
+
+        ModelVote::new(&self.name, vote, score)
+            .with_reasoning(reasoning)
+            .with_issues(issues)
+            .with_suggestions(suggestions)
+            .with_lines(lines)
+            .with_issue_suggestions(issue_suggestions)
+    }
+}
+
+/// Linhas (1-based) que contêm algum dos trechos.
+fn lines_matching(code: &str, needles: &[&str]) -> Vec<u32> {
+    code.lines()
+        .enumerate()
+        .filter(|(_, line)| needles.iter().any(|needle| line.contains(needle)))
+        .map(|(i, _)| i as u32 + 1)
+        .collect()
+}
+
+#[async_trait]
+impl CliExecutor for MockExecutor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn command(&self) -> &str {
+        MOCK_COMMAND
+    }
+
+    async fn is_available(&self) -> bool {
+        true
+    }
+
+    async fn version(&self) -> TetradResult<String> {
+        Ok(format!("{} (offline mock)", env!("CARGO_PKG_VERSION")))
+    }
+
+    async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
+        Ok(self.vote_for(&request.code))
+    }
+
+    fn specialization(&self) -> &str {
+        &self.specialization
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mock() -> MockExecutor {
+        MockExecutor::new("Codex", "syntax")
+    }
+
+    #[test]
+    fn test_clean_code_passes() {
+        let vote = mock().vote_for("fn add(a: i32, b: i32) -> i32 { a + b }");
+        assert_eq!(vote.executor, "Codex");
+        assert_eq!(vote.vote, Vote::Pass);
+        assert_eq!(vote.score, 90);
+        assert!(vote.issues.is_empty());
+    }
+
+    #[test]
+    fn test_unwrap_warns_with_lines() {
+        let vote = mock().vote_for("let a = 1;\nlet x = parse(s).unwrap();");
+        assert_eq!(vote.vote, Vote::Warn);
+        assert_eq!(vote.score, 65);
+        assert_eq!(vote.issues, vec![UNWRAP_ISSUE.to_string()]);
+        assert_eq!(vote.lines[UNWRAP_ISSUE], vec![2]);
+    }
+
+    #[test]
+    fn test_password_fails_and_wins_over_unwrap() {
+        let vote = mock().vote_for("let PASSWORD = \"hunter2\";\nlet v = x.unwrap();");
+        assert_eq!(vote.vote, Vote::Fail);
+        assert_eq!(vote.score, 20);
+        assert_eq!(
+            vote.issues,
+            vec![
+                HARDCODED_PASSWORD_ISSUE.to_string(),
+                UNWRAP_ISSUE.to_string()
+            ]
+        );
+        // Mesma entrada, mesmo voto
+        let again = mock().vote_for("let PASSWORD = \"hunter2\";\nlet v = x.unwrap();");
+        assert_eq!(again.issues, vote.issues);
+        assert_eq!(again.lines, vote.lines);
+    }
+}
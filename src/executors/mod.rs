@@ -1,14 +1,132 @@
 //! Executores CLI do Tetrad.
 //!
 //! Este módulo contém as implementações dos wrappers para as CLIs
-//! de avaliação de código: Codex, Gemini e Qwen.
+//! de avaliação de código: Codex, Gemini e Qwen, além de executores
+//! genéricos definidos em `[[executors.custom]]` e do [`MockExecutor`]
+//! determinístico usado em demos, CI e no modo `--offline`.
 
 mod base;
+mod canary;
 mod codex;
+mod fallback;
 mod gemini;
+mod generic;
+mod health;
+mod input;
+mod mock;
+mod prompts;
+mod quarantine;
 mod qwen;
+mod scratch;
+mod text_language;
+mod timeout;
 
 pub use base::CliExecutor;
+pub use canary::{run_canary, CanaryOutcome};
 pub use codex::CodexExecutor;
+pub use fallback::{
+    collect_seats, collect_seats_until, collect_seats_until_settled, collect_seats_with,
+    is_fallback_trigger, is_retryable, run_seat, seat_count, seat_names, seat_weights,
+    ExecutorSlot, SeatOutcome,
+};
 pub use gemini::GeminiExecutor;
+pub use generic::GenericExecutor;
+pub use health::{CircuitState, ExecutorHealth, FallbackAccuracy};
+pub use input::{effective_mode, run_cli, ARG_PROMPT_LIMIT, PROMPT_FILE_PLACEHOLDER};
+pub use mock::{MockExecutor, HARDCODED_PASSWORD_ISSUE, MOCK_COMMAND, UNWRAP_ISSUE};
+pub use prompts::{language_family, render_template, PromptTemplates, PROMPT_PLACEHOLDERS};
+pub use quarantine::{QuarantineEntry, QuarantineList, QUARANTINE_FILE_NAME};
 pub use qwen::QwenExecutor;
+pub use scratch::{scratch_root, sweep_orphans, Scratch, ORPHAN_MAX_AGE, SCRATCH_DIR_NAME};
+pub use text_language::detect_response_language;
+pub use timeout::adaptive_timeout;
+
+use std::sync::Arc;
+
+use crate::types::config::{ExecutorConfig, ExecutorsConfig, PromptsConfig};
+use crate::TetradResult;
+
+/// Executor acompanhado de uma cópia da sua configuração.
+pub type ConfiguredExecutor = (Box<dyn CliExecutor>, ExecutorConfig);
+
+/// Cria todos os executores configurados, embutidos e customizados.
+///
+/// A ordem é estável: Codex, Gemini, Qwen e depois os customizados na ordem
+/// do arquivo. Executores desabilitados também são retornados; use o
+/// `enabled` da configuração para filtrá-los. Todos compartilham os
+/// templates de prompt de `[prompts]`. Um executor com `command = "mock"`
+/// vira um [`MockExecutor`] com o mesmo nome.
+pub fn build_executors(
+    config: &ExecutorsConfig,
+    prompts: &PromptsConfig,
+) -> TetradResult<Vec<ConfiguredExecutor>> {
+    let prompts = Arc::new(PromptTemplates::from_config(prompts)?);
+
+    let mut executors: Vec<ConfiguredExecutor> = vec![
+        (
+            mock_or(&config.codex, "Codex", "syntax", || {
+                Box::new(CodexExecutor::from_config(&config.codex).with_prompts(prompts.clone()))
+            }),
+            config.codex.clone(),
+        ),
+        (
+            mock_or(&config.gemini, "Gemini", "architecture", || {
+                Box::new(GeminiExecutor::from_config(&config.gemini).with_prompts(prompts.clone()))
+            }),
+            config.gemini.clone(),
+        ),
+        (
+            mock_or(&config.qwen, "Qwen", "logic", || {
+                Box::new(QwenExecutor::from_config(&config.qwen).with_prompts(prompts.clone()))
+            }),
+            config.qwen.clone(),
+        ),
+    ];
+
+    for custom in &config.custom {
+        executors.push((
+            mock_or(
+                &custom.executor,
+                &custom.name,
+                &custom.specialization,
+                || Box::new(GenericExecutor::from_config(custom).with_prompts(prompts.clone())),
+            ),
+            custom.executor.clone(),
+        ));
+    }
+
+    Ok(executors)
+}
+
+/// Um [`MockExecutor`] se `config` pede o mock, senão o executor real.
+fn mock_or(
+    config: &ExecutorConfig,
+    name: &str,
+    specialization: &str,
+    real: impl FnOnce() -> Box<dyn CliExecutor>,
+) -> Box<dyn CliExecutor> {
+    if config.command == MOCK_COMMAND {
+        Box::new(MockExecutor::new(name, specialization))
+    } else {
+        real()
+    }
+}
+
+/// Troca todos os executores, embutidos e customizados, por mocks (flag
+/// `--offline`). Executores desabilitados continuam desabilitados.
+pub fn use_mock_executors(config: &mut ExecutorsConfig) {
+    let builtins = [&mut config.codex, &mut config.gemini, &mut config.qwen];
+    let custom = config.custom.iter_mut().map(|custom| &mut custom.executor);
+    for executor in builtins.into_iter().chain(custom) {
+        executor.command = MOCK_COMMAND.to_string();
+        executor.args.clear();
+    }
+}
+
+/// Cria os slots de votação para uma lista de executores.
+pub fn slots(executors: &[ConfiguredExecutor]) -> Vec<ExecutorSlot<'_>> {
+    executors
+        .iter()
+        .map(|(executor, config)| ExecutorSlot::new(executor.as_ref(), config))
+        .collect()
+}
@@ -0,0 +1,348 @@
+//! Templates de prompt por tipo de avaliação e família de linguagem.
+//!
+//! O prompt genérico de `CliExecutor::build_prompt` funciona mal para SQL,
+//! Terraform e YAML. Este módulo fornece templates embutidos para essas
+//! famílias e aplica os templates configurados em `[prompts]`.
+//!
+//! ## Seleção
+//!
+//! O template mais específico vence; em caso de empate, o configurado
+//! vence o embutido:
+//!
+//! 1. tipo + família (`code.sql`)
+//! 2. qualquer tipo + família (`*.sql`)
+//! 3. tipo (`code`)
+//! 4. qualquer tipo (`*`)
+//!
+//! Sem template, o prompt genérico é usado.
+//!
+//! ## Placeholders
+//!
+//! `{code}`, `{language}`, `{context}` e `{specialization}`. Outras chaves
+//! entre chaves são mantidas como estão.
+
+use crate::types::config::{PromptKey, PromptsConfig};
+use crate::types::language::Language;
+use crate::types::requests::{EvaluationRequest, EvaluationType};
+use crate::TetradResult;
+
+/// Placeholders substituídos nos templates.
+pub const PROMPT_PLACEHOLDERS: &[&str] = &["code", "language", "context", "specialization"];
+
+/// Templates embutidos: (tipo, família, texto).
+const BUILTIN_TEMPLATES: &[(EvaluationType, &str, &str)] = &[
+    (
+        EvaluationType::Code,
+        "sql",
+        "Avalie a seguinte consulta {language}.\n\n\
+         Código:\n```sql\n{code}\n```\n\n\
+         Verifique em especial:\n\
+         - Injeção de SQL e concatenação de entrada do usuário\n\
+         - Uso de índices, varreduras completas e joins sem condição\n\
+         - Transações, locks e consistência dos dados\n\
+         - Tratamento de NULL e conversões de tipo\n\n",
+    ),
+    (
+        EvaluationType::Code,
+        "terraform",
+        "Avalie a seguinte configuração Terraform.\n\n\
+         Código:\n```hcl\n{code}\n```\n\n\
+         Verifique em especial:\n\
+         - Segredos ou credenciais em texto plano\n\
+         - Recursos expostos publicamente (security groups, buckets, IAM amplo demais)\n\
+         - Versões de providers e módulos não fixadas\n\
+         - Mudanças que recriariam ou destruiriam recursos sem necessidade\n\n",
+    ),
+    (
+        EvaluationType::Code,
+        "yaml",
+        "Avalie o seguinte arquivo YAML.\n\n\
+         Código:\n```yaml\n{code}\n```\n\n\
+         Verifique em especial:\n\
+         - Sintaxe e indentação\n\
+         - Chaves duplicadas e tipos implícitos (ex.: `no` lido como booleano)\n\
+         - Segredos em texto plano\n\
+         - Campos obrigatórios ausentes para a ferramenta que consome o arquivo \
+         (CI, Kubernetes, Compose)\n\n",
+    ),
+];
+
+/// Retorna a família de uma linguagem, usada na seleção de templates.
+///
+/// A linguagem é normalizada por [`Language`] e dialetos conhecidos são
+/// agrupados (`postgresql` → `sql`, `hcl` → `terraform`); linguagens fora da
+/// tabela usam a própria grafia em minúsculas, para que templates
+/// configurados para elas continuem valendo.
+pub fn language_family(language: &str) -> String {
+    let language = Language::parse(language);
+    match language.as_str() {
+        "sql" | "postgresql" | "mysql" | "sqlite" | "plpgsql" | "tsql" => "sql".to_string(),
+        "terraform" | "hcl" => "terraform".to_string(),
+        _ => language.name().to_lowercase(),
+    }
+}
+
+/// Templates de prompt configurados, somados aos embutidos.
+#[derive(Debug, Clone, Default)]
+pub struct PromptTemplates {
+    overrides: Vec<(PromptKey, String)>,
+}
+
+impl PromptTemplates {
+    /// Cria o conjunto apenas com os templates embutidos.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Carrega os templates de `[prompts]`.
+    ///
+    /// Falha se uma chave for inválida, um arquivo não puder ser lido ou um
+    /// template não tiver `{code}`.
+    pub fn from_config(config: &PromptsConfig) -> TetradResult<Self> {
+        let overrides = config
+            .load()?
+            .into_iter()
+            .map(|(key, text)| {
+                let family = key.family.as_deref().map(language_family);
+                (PromptKey { family, ..key }, text)
+            })
+            .collect();
+        Ok(Self { overrides })
+    }
+
+    /// Adiciona um template, com precedência sobre os existentes para a mesma chave.
+    #[must_use]
+    pub fn with_template(mut self, key: PromptKey, text: impl Into<String>) -> Self {
+        let family = key.family.as_deref().map(language_family);
+        self.overrides
+            .insert(0, (PromptKey { family, ..key }, text.into()));
+        self
+    }
+
+    /// Seleciona o template mais específico para o tipo e a linguagem.
+    ///
+    /// Retorna `None` quando o prompt genérico deve ser usado.
+    pub fn select(&self, evaluation_type: EvaluationType, language: &str) -> Option<&str> {
+        let family = language_family(language);
+        let levels = [
+            (Some(evaluation_type), Some(family.as_str())),
+            (None, Some(family.as_str())),
+            (Some(evaluation_type), None),
+            (None, None),
+        ];
+
+        for (kind, family) in levels {
+            if let Some(template) = self
+                .configured(kind, family)
+                .or_else(|| builtin(kind, family))
+            {
+                return Some(template);
+            }
+        }
+        None
+    }
+
+    fn configured(&self, kind: Option<EvaluationType>, family: Option<&str>) -> Option<&str> {
+        self.overrides
+            .iter()
+            .find(|(key, _)| key.evaluation_type == kind && key.family.as_deref() == family)
+            .map(|(_, text)| text.as_str())
+    }
+}
+
+fn builtin(kind: Option<EvaluationType>, family: Option<&str>) -> Option<&'static str> {
+    let (kind, family) = (kind?, family?);
+    BUILTIN_TEMPLATES
+        .iter()
+        .find(|(t, f, _)| *t == kind && *f == family)
+        .map(|(_, _, text)| *text)
+}
+
+/// Preenche os placeholders de um template.
+///
+/// A substituição é feita em uma única passada, então placeholders que
+/// aparecem dentro do código avaliado não são expandidos.
+pub fn render_template(
+    template: &str,
+    request: &EvaluationRequest,
+    specialization: &str,
+) -> String {
+    let mut output = String::with_capacity(template.len() + request.code.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let value = after.find('}').and_then(|end| {
+            let value = match &after[..end] {
+                "code" => request.code.as_str(),
+                "language" => request.language.name(),
+                "context" => request.context.as_deref().unwrap_or(""),
+                "specialization" => specialization,
+                _ => return None,
+            };
+            Some((value, end))
+        });
+
+        match value {
+            Some((value, end)) => {
+                output.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(evaluation_type: Option<EvaluationType>, family: Option<&str>) -> PromptKey {
+        PromptKey {
+            evaluation_type,
+            family: family.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_language_family() {
+        assert_eq!(language_family("PostgreSQL"), "sql");
+        assert_eq!(language_family("hcl"), "terraform");
+        assert_eq!(language_family("yml"), "yaml");
+        assert_eq!(language_family("Rust"), "rust");
+        assert_eq!(language_family("rs"), "rust");
+        assert_eq!(language_family("COBOL"), "cobol");
+    }
+
+    #[test]
+    fn test_builtin_templates() {
+        let templates = PromptTemplates::new();
+        assert!(templates
+            .select(EvaluationType::Code, "sql")
+            .unwrap()
+            .contains("Injeção de SQL"));
+        assert!(templates.select(EvaluationType::Code, "tf").is_some());
+        assert!(templates.select(EvaluationType::Code, "rust").is_none());
+        assert!(templates.select(EvaluationType::Plan, "sql").is_none());
+    }
+
+    #[test]
+    fn test_selection_precedence() {
+        let templates = PromptTemplates::new()
+            .with_template(key(None, None), "any {code}")
+            .with_template(key(Some(EvaluationType::Code), None), "code {code}")
+            .with_template(key(None, Some("yaml")), "any yaml {code}");
+
+        // Tipo + família embutido vence templates menos específicos
+        assert!(templates
+            .select(EvaluationType::Code, "yaml")
+            .unwrap()
+            .contains("Chaves duplicadas"));
+        assert_eq!(
+            templates.select(EvaluationType::Tests, "yml"),
+            Some("any yaml {code}")
+        );
+        assert_eq!(
+            templates.select(EvaluationType::Code, "rust"),
+            Some("code {code}")
+        );
+        assert_eq!(
+            templates.select(EvaluationType::Plan, "rust"),
+            Some("any {code}")
+        );
+
+        // Configurado vence embutido na mesma especificidade
+        let templates = templates.with_template(
+            key(Some(EvaluationType::Code), Some("postgresql")),
+            "custom sql {code}",
+        );
+        assert_eq!(
+            templates.select(EvaluationType::Code, "sql"),
+            Some("custom sql {code}")
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        let mut config = PromptsConfig::default();
+        config.templates.insert(
+            "code.mysql".to_string(),
+            crate::types::config::PromptSource::Inline("mysql {code}".to_string()),
+        );
+        let templates = PromptTemplates::from_config(&config).unwrap();
+        assert_eq!(
+            templates.select(EvaluationType::Code, "sqlite"),
+            Some("mysql {code}")
+        );
+    }
+
+    #[test]
+    fn test_config_validation() {
+        use crate::types::config::PromptSource;
+
+        let dir = tempfile::tempdir().unwrap();
+        let valid = dir.path().join("sql.md");
+        std::fs::write(&valid, "SQL: {code}").unwrap();
+        let invalid = dir.path().join("yaml.md");
+        std::fs::write(&invalid, "YAML sem código").unwrap();
+
+        let config = |key: &str, source: PromptSource| PromptsConfig {
+            templates: [(key.to_string(), source)].into_iter().collect(),
+        };
+
+        assert!(config(
+            "code.sql",
+            PromptSource::File {
+                path: valid.clone()
+            }
+        )
+        .validate()
+        .is_ok());
+
+        let err = config("code.yaml", PromptSource::File { path: invalid })
+            .validate()
+            .unwrap_err();
+        assert!(err.to_string().contains("{code}"), "{}", err);
+
+        let err = config(
+            "code.sql",
+            PromptSource::File {
+                path: dir.path().join("missing.md"),
+            },
+        )
+        .validate()
+        .unwrap_err();
+        assert!(err.to_string().contains("missing.md"), "{}", err);
+
+        let err = config("review.sql", PromptSource::File { path: valid })
+            .validate()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("unknown evaluation type"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_render_template() {
+        let request =
+            EvaluationRequest::new("let x = \"{language}\";", "rust").with_context("hot path");
+        let rendered = render_template(
+            "[{specialization}] {language}: {code} ({context}) {unknown}",
+            &request,
+            "logic",
+        );
+        assert_eq!(
+            rendered,
+            "[logic] rust: let x = \"{language}\"; (hot path) {unknown}"
+        );
+    }
+}
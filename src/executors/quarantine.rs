@@ -0,0 +1,183 @@
+//! Quarentena persistente de executores.
+//!
+//! O circuit breaker de [`ExecutorHealth`](super::ExecutorHealth) vive em
+//! memória; sem a quarentena, uma CLI quebrada voltaria a ser chamada (e a
+//! estourar o prazo) a cada reinício do servidor. Quando um circuito abre,
+//! o executor entra em `quarantine.json` no diretório de dados com o motivo
+//! e o horário da próxima tentativa, e sai dela quando uma avaliação de
+//! teste ou o canary de `tetrad doctor --deep` tem sucesso.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::types::config::write_atomic;
+use crate::TetradResult;
+
+/// Nome do arquivo da quarentena dentro do diretório de dados.
+pub const QUARANTINE_FILE_NAME: &str = "quarantine.json";
+
+/// Um executor em quarentena.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    /// Nome do executor.
+    pub executor: String,
+    /// Última falha antes de o circuito abrir.
+    pub reason: String,
+    /// Quando o circuito abriu.
+    pub opened_at: DateTime<Utc>,
+    /// A partir de quando uma avaliação de teste é permitida.
+    pub retry_after: DateTime<Utc>,
+}
+
+/// Executores em quarentena, gravados em disco a cada mudança.
+///
+/// Sem caminho ([`QuarantineList::default`]) a lista fica só em memória.
+#[derive(Debug, Default)]
+pub struct QuarantineList {
+    path: Option<PathBuf>,
+    entries: BTreeMap<String, QuarantineEntry>,
+}
+
+impl QuarantineList {
+    /// Lê a quarentena gravada em `path`.
+    ///
+    /// Um arquivo ausente é uma quarentena vazia; um arquivo corrompido
+    /// também, com um aviso no log, para não impedir o servidor de subir.
+    pub fn load(path: &Path) -> Self {
+        let entries = match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str::<Vec<QuarantineEntry>>(&content)
+                .inspect_err(|e| {
+                    tracing::warn!(
+                        path = %path.display(),
+                        error = %e,
+                        "Ignoring unreadable quarantine file"
+                    )
+                })
+                .unwrap_or_default(),
+            Err(_) => Vec::new(),
+        };
+
+        Self {
+            path: Some(path.to_path_buf()),
+            entries: entries
+                .into_iter()
+                .map(|entry| (entry.executor.clone(), entry))
+                .collect(),
+        }
+    }
+
+    /// Executores em quarentena, em ordem alfabética.
+    pub fn entries(&self) -> impl Iterator<Item = &QuarantineEntry> {
+        self.entries.values()
+    }
+
+    /// Entrada do executor, se ele estiver em quarentena.
+    pub fn get(&self, executor: &str) -> Option<&QuarantineEntry> {
+        self.entries.get(executor)
+    }
+
+    /// Se não há nenhum executor em quarentena.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Coloca (ou mantém) um executor em quarentena e grava o arquivo.
+    pub fn insert(&mut self, entry: QuarantineEntry) -> TetradResult<()> {
+        self.entries.insert(entry.executor.clone(), entry);
+        self.save()
+    }
+
+    /// Tira um executor da quarentena. Retorna `false` se ele não estava nela.
+    pub fn remove(&mut self, executor: &str) -> TetradResult<bool> {
+        if self.entries.remove(executor).is_none() {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Esvazia a quarentena, retornando quantos executores saíram dela.
+    pub fn clear(&mut self) -> TetradResult<usize> {
+        let cleared = self.entries.len();
+        self.entries.clear();
+        self.save()?;
+        Ok(cleared)
+    }
+
+    /// Grava a lista; vazia, o arquivo é removido.
+    fn save(&self) -> TetradResult<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        if self.entries.is_empty() {
+            match std::fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                _ => return Ok(()),
+            }
+        }
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let entries: Vec<&QuarantineEntry> = self.entries.values().collect();
+        write_atomic(path, serde_json::to_string_pretty(&entries)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(executor: &str) -> QuarantineEntry {
+        let opened_at = Utc::now();
+        QuarantineEntry {
+            executor: executor.to_string(),
+            reason: "Timeout executing 'Gemini'".to_string(),
+            opened_at,
+            retry_after: opened_at + chrono::Duration::seconds(60),
+        }
+    }
+
+    #[test]
+    fn test_round_trip_and_removal() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data").join(QUARANTINE_FILE_NAME);
+
+        let mut list = QuarantineList::load(&path);
+        assert!(list.is_empty());
+        list.insert(entry("Gemini")).unwrap();
+        list.insert(entry("Codex")).unwrap();
+
+        let loaded = QuarantineList::load(&path);
+        let names: Vec<&str> = loaded.entries().map(|e| e.executor.as_str()).collect();
+        assert_eq!(names, vec!["Codex", "Gemini"]);
+        assert_eq!(loaded.get("Gemini"), list.get("Gemini"));
+
+        assert!(list.remove("Gemini").unwrap());
+        assert!(!list.remove("Gemini").unwrap());
+        assert_eq!(list.clear().unwrap(), 1);
+        // A quarentena vazia não deixa arquivo para trás
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_unreadable_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(QUARANTINE_FILE_NAME);
+        std::fs::write(&path, "{not json").unwrap();
+
+        assert!(QuarantineList::load(&path).is_empty());
+    }
+
+    #[test]
+    fn test_in_memory_list_writes_nothing() {
+        let mut list = QuarantineList::default();
+        list.insert(entry("Qwen")).unwrap();
+        assert!(list.get("Qwen").is_some());
+    }
+}
@@ -1,13 +1,21 @@
 //! Executor para Qwen CLI.
+//!
+//! Versões recentes da CLI escrevem cores ANSI e quadros de spinner
+//! (reescritos com `\r`) junto com a resposta, e às vezes formatam o JSON em
+//! várias linhas. A saída passa por [`sanitize_output`] antes da extração do
+//! JSON, que usa o scanner de chaves balanceadas e não depende de linhas; sem
+//! JSON, a resposta em texto livre ainda vira um voto por heurística.
 
 use async_trait::async_trait;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::process::Command;
 
 use super::base::{CliExecutor, ExecutorResponse};
-use crate::types::config::ExecutorConfig;
+use super::input::run_cli;
+use super::prompts::PromptTemplates;
+use crate::types::config::{ExecutorConfig, InputMode};
 use crate::types::requests::EvaluationRequest;
-use crate::types::responses::{ModelVote, Vote};
+use crate::types::responses::ModelVote;
 use crate::{TetradError, TetradResult};
 
 /// Executor para Qwen CLI (Alibaba).
@@ -17,6 +25,8 @@ pub struct QwenExecutor {
     command_name: String,
     args: Vec<String>,
     timeout: Duration,
+    input_mode: InputMode,
+    prompts: Arc<PromptTemplates>,
 }
 
 impl QwenExecutor {
@@ -27,6 +37,8 @@ impl QwenExecutor {
             // Prompt é passado como argumento posicional
             args: vec![],
             timeout: Duration::from_secs(30),
+            input_mode: InputMode::default(),
+            prompts: Arc::default(),
         }
     }
 
@@ -36,6 +48,8 @@ impl QwenExecutor {
             command_name: config.command.clone(),
             args: config.args.clone(),
             timeout: Duration::from_secs(config.timeout_secs),
+            input_mode: config.input_mode,
+            prompts: Arc::default(),
         }
     }
 
@@ -45,6 +59,88 @@ impl QwenExecutor {
         self.timeout = timeout;
         self
     }
+
+    /// Define os templates de prompt.
+    #[must_use]
+    pub fn with_prompts(mut self, prompts: Arc<PromptTemplates>) -> Self {
+        self.prompts = prompts;
+        self
+    }
+
+    /// Converte a saída da CLI em resposta, com análise de texto como fallback.
+    fn parse_qwen_output(output: &str) -> TetradResult<ExecutorResponse> {
+        let cleaned = sanitize_output(output);
+
+        if let Ok(response) = ExecutorResponse::parse_from_output(&cleaned, "Qwen") {
+            return Ok(response);
+        }
+
+        // Sem JSON: o modelo respondeu em prosa
+        if !cleaned.trim().is_empty() {
+            return Ok(Self::analyze_text_response(cleaned.trim()));
+        }
+
+        Err(TetradError::ExecutorFailed(
+            "Qwen".to_string(),
+            "Resposta vazia do Qwen".to_string(),
+        ))
+    }
+
+    /// Analisa texto de resposta e extrai informações estruturadas.
+    fn analyze_text_response(text: &str) -> ExecutorResponse {
+        ExecutorResponse::from_text(text)
+    }
+}
+
+/// Remove da saída do terminal o que não faz parte da resposta.
+///
+/// Tira sequências de escape ANSI (CSI, como cores e limpeza de linha, e OSC,
+/// como o título da janela) e, em cada linha, mantém só o que vem depois do
+/// último `\r`: é o que o terminal mostraria depois dos quadros do spinner.
+fn sanitize_output(output: &str) -> String {
+    let mut plain = String::with_capacity(output.len());
+    let mut chars = output.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            plain.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parâmetros e intermediários até o byte final (@ a ~)
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: até BEL ou ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' {
+                        chars.next_if_eq(&'\\');
+                        break;
+                    }
+                }
+            }
+            // Demais escapes têm um único caractere depois do ESC
+            _ => {}
+        }
+    }
+
+    let mut cleaned = String::with_capacity(plain.len());
+    for line in plain.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let visible = line.rsplit('\r').next().unwrap_or(line);
+        cleaned.push_str(visible);
+        cleaned.push('\n');
+    }
+    cleaned.pop();
+    cleaned
 }
 
 impl Default for QwenExecutor {
@@ -67,24 +163,40 @@ impl CliExecutor for QwenExecutor {
         "logic"
     }
 
+    fn prompt_templates(&self) -> Option<&PromptTemplates> {
+        Some(&self.prompts)
+    }
+
+    fn parse_structured(&self, output: &str) -> TetradResult<ExecutorResponse> {
+        ExecutorResponse::parse_from_output(&sanitize_output(output), self.name())
+    }
+
     async fn evaluate(&self, request: &EvaluationRequest) -> TetradResult<ModelVote> {
-        let prompt = self.build_prompt(request);
+        self.evaluate_with_timeout(request, self.timeout).await
+    }
 
-        // Constrói o comando com argumentos do config
-        let mut cmd = Command::new(&self.command_name);
-        for arg in &self.args {
-            cmd.arg(arg);
-        }
-        cmd.arg(&prompt);
+    async fn evaluate_with_timeout(
+        &self,
+        request: &EvaluationRequest,
+        timeout: Duration,
+    ) -> TetradResult<ModelVote> {
+        let prompt = self.build_prompt(request);
 
-        // Executa a CLI com timeout
-        let result = tokio::time::timeout(self.timeout, cmd.output()).await;
+        // Executa a CLI com timeout, com os argumentos do config
+        let result = run_cli(
+            &self.command_name,
+            &self.args,
+            &prompt,
+            self.input_mode,
+            timeout,
+        )
+        .await;
 
         match result {
             Ok(Ok(output)) => {
                 if output.status.success() {
                     let stdout = String::from_utf8_lossy(&output.stdout);
-                    let response = ExecutorResponse::parse_from_output(&stdout, self.name())?;
+                    let response = Self::parse_qwen_output(&stdout)?;
                     Ok(response.into_vote(self.name()))
                 } else {
                     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -98,8 +210,7 @@ impl CliExecutor for QwenExecutor {
                 // CLI não encontrada ou erro de execução
                 if e.kind() == std::io::ErrorKind::NotFound {
                     // Retorna voto neutro se CLI não estiver disponível
-                    Ok(ModelVote::new(self.name(), Vote::Warn, 50)
-                        .with_reasoning("Qwen CLI não disponível"))
+                    Ok(ModelVote::synthetic(self.name()).with_reasoning("Qwen CLI não disponível"))
                 } else {
                     Err(TetradError::ExecutorFailed(
                         self.name().to_string(),
@@ -115,6 +226,82 @@ impl CliExecutor for QwenExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::responses::Vote;
+
+    const ANSI: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/qwen/ansi.txt"
+    ));
+    const STREAMED: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/qwen/streamed.txt"
+    ));
+    const PRETTY: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/qwen/pretty.txt"
+    ));
+    const PROSE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/qwen/prose.txt"
+    ));
+
+    #[test]
+    fn test_sanitize_strips_ansi_and_spinner_frames() {
+        let cleaned = sanitize_output(ANSI);
+        assert!(!cleaned.contains('\x1b'));
+        assert!(!cleaned.contains('\r'));
+        assert!(!cleaned.contains("Thinking"));
+        assert!(cleaned.starts_with(r#"{"vote": "FAIL""#));
+        assert!(cleaned.contains(r#""reasoning": "Off-by-one in the loop"#));
+
+        assert_eq!(sanitize_output("a\x1b[31mb\x1b[0m\r\nc"), "ab\nc");
+        assert_eq!(sanitize_output("\x1b]0;title\x1b\\x"), "x");
+    }
+
+    #[test]
+    fn test_parse_ansi_output() {
+        // Os códigos dentro das strings deixariam o JSON inválido
+        assert!(ExecutorResponse::parse_from_output(ANSI, "Qwen").is_err());
+
+        let response = QwenExecutor::parse_qwen_output(ANSI).unwrap();
+        assert_eq!(response.vote, "FAIL");
+        assert_eq!(response.score, 25);
+        assert_eq!(response.issues.len(), 1);
+        assert!(response.text_language.is_none());
+    }
+
+    #[test]
+    fn test_parse_streamed_multiline_json() {
+        let response = QwenExecutor::parse_qwen_output(STREAMED).unwrap();
+        assert_eq!(response.vote, "WARN");
+        assert_eq!(response.score, 68);
+        assert_eq!(response.suggestions, vec!["Propagate the error with ?"]);
+    }
+
+    #[test]
+    fn test_parse_pretty_printed_json_with_crlf() {
+        let response = QwenExecutor::parse_qwen_output(PRETTY).unwrap();
+        assert_eq!(response.vote, "PASS");
+        assert_eq!(response.score, 92);
+        // Chaves dentro de strings não confundem o scanner
+        assert!(response.reasoning.ends_with("{correctly}."));
+        assert!(response.issues.is_empty());
+    }
+
+    #[test]
+    fn test_parse_prose_falls_back_to_text_analysis() {
+        let response = QwenExecutor::parse_qwen_output(PROSE).unwrap();
+        assert_eq!(response.vote, "FAIL");
+        assert_eq!(response.issues.len(), 2);
+        assert!(response.text_language.is_some());
+        assert!(!response.reasoning.contains('\x1b'));
+
+        // O doctor não aceita prosa como resposta estruturada
+        assert!(QwenExecutor::new().parse_structured(PROSE).is_err());
+        assert!(QwenExecutor::new().parse_structured(STREAMED).is_ok());
+
+        assert!(QwenExecutor::parse_qwen_output("\x1b[?25l\r\n").is_err());
+    }
 
     #[test]
     fn test_parse_response_valid() {
@@ -133,4 +320,29 @@ mod tests {
         let executor = QwenExecutor::new();
         assert_eq!(executor.specialization(), "logic");
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_large_prompt_via_stdin() {
+        // CLI falsa: só aprova se o código inteiro chegar pelo stdin
+        let config = ExecutorConfig {
+            input_mode: InputMode::Stdin,
+            ..ExecutorConfig::new(
+                "sh",
+                &[
+                    "-c",
+                    r#"grep -q 'const LAST' && echo '{"vote": "PASS", "score": 91, "reasoning": "ok"}'"#,
+                ],
+            )
+        };
+        let executor = QwenExecutor::from_config(&config);
+
+        let code = format!("{}const LAST: u8 = 1;", "let x = 1;\n".repeat(40_000));
+        let vote = executor
+            .evaluate(&EvaluationRequest::new(code, "rust"))
+            .await
+            .unwrap();
+        assert_eq!(vote.vote, Vote::Pass);
+        assert_eq!(vote.score, 91);
+    }
 }
@@ -0,0 +1,205 @@
+//! Artefatos temporários das avaliações.
+//!
+//! Arquivos de prompt e diretórios do selftest ficam em
+//! `<temp>/tetrad-scratch`, registrados num [`Scratch`] que vive tanto quanto
+//! a avaliação: o drop remove os artefatos mesmo quando a avaliação é
+//! cancelada ou estoura o timeout (o future é descartado). Se o processo
+//! morrer antes do drop, [`sweep_orphans`] remove na próxima inicialização os
+//! artefatos mais antigos que [`ORPHAN_MAX_AGE`].
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Nome do diretório de artefatos dentro do diretório temporário do sistema.
+pub const SCRATCH_DIR_NAME: &str = "tetrad-scratch";
+
+/// Idade a partir da qual um artefato é considerado órfão.
+pub const ORPHAN_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Diretório padrão dos artefatos.
+pub fn scratch_root() -> PathBuf {
+    std::env::temp_dir().join(SCRATCH_DIR_NAME)
+}
+
+/// Artefatos temporários de uma avaliação, removidos no drop.
+#[derive(Debug)]
+pub struct Scratch {
+    root: PathBuf,
+    paths: Vec<PathBuf>,
+}
+
+impl Scratch {
+    /// Cria um guard em [`scratch_root`].
+    pub fn new() -> Self {
+        Self::in_dir(scratch_root())
+    }
+
+    /// Cria um guard que grava os artefatos em `root`.
+    pub fn in_dir(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            paths: Vec::new(),
+        }
+    }
+
+    /// Cria um arquivo `<prefix>-<uuid>.<extension>` com `contents`.
+    ///
+    /// O arquivo é registrado antes da escrita, então uma falha também o remove.
+    pub fn file(&mut self, prefix: &str, extension: &str, contents: &[u8]) -> io::Result<PathBuf> {
+        std::fs::create_dir_all(&self.root)?;
+        let path = self
+            .root
+            .join(format!("{}-{}.{}", prefix, uuid::Uuid::new_v4(), extension));
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+
+        self.paths.push(path.clone());
+        file.write_all(contents)?;
+        Ok(path)
+    }
+
+    /// Cria um diretório `<prefix>-<uuid>`, removido com todo o conteúdo.
+    pub fn dir(&mut self, prefix: &str) -> io::Result<PathBuf> {
+        let path = self
+            .root
+            .join(format!("{}-{}", prefix, uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path)?;
+        self.paths.push(path.clone());
+        Ok(path)
+    }
+
+    /// Artefatos registrados, na ordem de criação.
+    pub fn paths(&self) -> &[PathBuf] {
+        &self.paths
+    }
+}
+
+impl Default for Scratch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        for path in self.paths.iter().rev() {
+            let removed = if path.is_dir() {
+                std::fs::remove_dir_all(path)
+            } else {
+                std::fs::remove_file(path)
+            };
+            // Um artefato já removido não é erro
+            match removed {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => {
+                    tracing::debug!(path = %path.display(), error = %e, "Failed to remove scratch artifact")
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Remove de `root` os artefatos modificados há mais de `max_age`.
+///
+/// Retorna quantos foram removidos. Um `root` inexistente não é erro.
+pub fn sweep_orphans(root: &Path, max_age: Duration) -> usize {
+    let Ok(entries) = std::fs::read_dir(root) else {
+        return 0;
+    };
+    let now = SystemTime::now();
+
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let age = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| now.duration_since(modified).ok());
+        if age.is_none_or(|age| age < max_age) {
+            continue;
+        }
+
+        let result = if metadata.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        match result {
+            Ok(()) => removed += 1,
+            Err(e) => {
+                tracing::debug!(path = %path.display(), error = %e, "Failed to remove orphaned artifact")
+            }
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scratch_removes_artifacts_on_drop() {
+        let root = tempfile::tempdir().unwrap();
+        let (file, dir) = {
+            let mut scratch = Scratch::in_dir(root.path());
+            let file = scratch.file("tetrad-prompt", "txt", b"prompt").unwrap();
+            let dir = scratch.dir("tetrad-selftest").unwrap();
+            std::fs::write(dir.join("selftest.db"), b"db").unwrap();
+
+            assert_eq!(std::fs::read(&file).unwrap(), b"prompt");
+            assert_eq!(scratch.paths(), &[file.clone(), dir.clone()]);
+            (file, dir)
+        };
+
+        assert!(!file.exists());
+        assert!(!dir.exists());
+    }
+
+    #[test]
+    fn test_scratch_tolerates_removed_artifacts() {
+        let root = tempfile::tempdir().unwrap();
+        let mut scratch = Scratch::in_dir(root.path());
+        let file = scratch.file("tetrad-prompt", "txt", b"x").unwrap();
+        std::fs::remove_file(&file).unwrap();
+        drop(scratch);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sweep_orphans_removes_only_stale_artifacts() {
+        let root = tempfile::tempdir().unwrap();
+        let stale_file = root.path().join("tetrad-prompt-old.txt");
+        let stale_dir = root.path().join("tetrad-selftest-old");
+        let fresh_file = root.path().join("tetrad-prompt-new.txt");
+        std::fs::write(&stale_file, b"old").unwrap();
+        std::fs::create_dir(&stale_dir).unwrap();
+        std::fs::write(stale_dir.join("selftest.db"), b"old").unwrap();
+        std::fs::write(&fresh_file, b"new").unwrap();
+
+        // Envelhece os artefatos plantados em dois dias
+        let old = SystemTime::now() - 2 * ORPHAN_MAX_AGE;
+        for path in [&stale_file, &stale_dir] {
+            std::fs::File::open(path)
+                .unwrap()
+                .set_modified(old)
+                .unwrap();
+        }
+
+        assert_eq!(sweep_orphans(root.path(), ORPHAN_MAX_AGE), 2);
+        assert!(!stale_file.exists());
+        assert!(!stale_dir.exists());
+        assert!(fresh_file.exists());
+
+        assert_eq!(
+            sweep_orphans(&root.path().join("missing"), ORPHAN_MAX_AGE),
+            0
+        );
+    }
+}
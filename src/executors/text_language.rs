@@ -0,0 +1,325 @@
+//! Idioma das respostas em texto livre.
+//!
+//! Quando a CLI não devolve o JSON esperado, o voto é inferido por
+//! palavras-chave (`ExecutorResponse::from_text`). As palavras dependem do
+//! idioma da resposta, estimado aqui pela escrita (ideogramas, kana,
+//! cirílico...) e por palavras funcionais frequentes.
+//! Respostas em um idioma sem palavras-chave recebem um voto neutro.
+
+use crate::types::responses::ResponseLanguage;
+
+/// Palavras-chave da análise de texto em um idioma.
+///
+/// Todas em minúsculas; são procuradas como substrings do texto.
+pub(crate) struct FallbackKeywords {
+    /// Indicam um problema grave (FAIL).
+    pub fail: &'static [&'static str],
+    /// Indicam problemas a revisar (WARN).
+    pub warn: &'static [&'static str],
+    /// Aprovação enfática (score 95).
+    pub excellent: &'static [&'static str],
+    /// Aprovação (score 85).
+    pub good: &'static [&'static str],
+    /// Problemas menores (score 70).
+    pub minor: &'static [&'static str],
+    /// Linhas com sugestões.
+    pub suggestion: &'static [&'static str],
+    /// Frases que negam um problema ("sem problemas"), removidas antes da busca.
+    pub no_problem: &'static [&'static str],
+}
+
+const PORTUGUESE: FallbackKeywords = FallbackKeywords {
+    fail: &[
+        "erro crítico",
+        "bug grave",
+        "vulnerabilidade",
+        "falha de segurança",
+    ],
+    warn: &[
+        "problema",
+        "considere",
+        "sugestão",
+        "atenção",
+        "melhoria",
+        "observação",
+    ],
+    excellent: &["perfeito", "excelente"],
+    good: &["bom", "correto", "idiomático"],
+    minor: &["menor"],
+    suggestion: &["sugest"],
+    no_problem: &[],
+};
+
+const ENGLISH: FallbackKeywords = FallbackKeywords {
+    fail: &["critical error", "security vulnerability"],
+    warn: &["issue", "overflow", "consider", "suggestion"],
+    excellent: &["perfect"],
+    good: &[],
+    minor: &["minor"],
+    suggestion: &["consider"],
+    no_problem: &[],
+};
+
+const SPANISH: FallbackKeywords = FallbackKeywords {
+    fail: &[
+        "error crítico",
+        "bug grave",
+        "vulnerabilidad",
+        "fallo de seguridad",
+        "falla de seguridad",
+    ],
+    warn: &[
+        "problema",
+        "considera",
+        "sugerencia",
+        "atención",
+        "mejora",
+        "observación",
+        "cuidado",
+    ],
+    excellent: &["perfecto", "excelente"],
+    good: &["bueno", "correcto", "idiomático"],
+    minor: &["menor"],
+    suggestion: &["sugier", "sugerencia", "considera"],
+    no_problem: &[
+        "no tiene problemas",
+        "no hay problemas",
+        "sin problemas",
+        "ningún problema",
+    ],
+};
+
+const CHINESE: FallbackKeywords = FallbackKeywords {
+    fail: &["严重错误", "严重问题", "安全漏洞", "漏洞", "严重缺陷"],
+    warn: &["问题", "建议", "注意", "改进", "考虑", "潜在"],
+    excellent: &["完美", "优秀"],
+    good: &["良好", "正确", "不错"],
+    minor: &["轻微", "次要"],
+    suggestion: &["建议", "考虑"],
+    no_problem: &[
+        "没有发现问题",
+        "没有问题",
+        "无问题",
+        "未发现问题",
+        "没有漏洞",
+    ],
+};
+
+/// Conjuntos de palavras-chave usados para um idioma.
+///
+/// Inglês entra em todos, já que termos técnicos ("overflow", "issue")
+/// aparecem em respostas de qualquer idioma. Sem idioma definido, valem
+/// português e inglês, os idiomas dos prompts. Idiomas não suportados não
+/// têm palavras-chave.
+pub(crate) fn keywords(language: ResponseLanguage) -> &'static [FallbackKeywords] {
+    const PORTUGUESE_ENGLISH: &[FallbackKeywords] = &[PORTUGUESE, ENGLISH];
+    const SPANISH_ENGLISH: &[FallbackKeywords] = &[SPANISH, ENGLISH];
+    const CHINESE_ENGLISH: &[FallbackKeywords] = &[CHINESE, ENGLISH];
+
+    match language {
+        ResponseLanguage::Portuguese | ResponseLanguage::English | ResponseLanguage::Unknown => {
+            PORTUGUESE_ENGLISH
+        }
+        ResponseLanguage::Spanish => SPANISH_ENGLISH,
+        ResponseLanguage::Chinese => CHINESE_ENGLISH,
+        ResponseLanguage::Unsupported => &[],
+    }
+}
+
+/// Idiomas reconhecidos pelas palavras funcionais.
+#[derive(Clone, Copy)]
+enum Latin {
+    Portuguese,
+    English,
+    Spanish,
+    French,
+    German,
+    Italian,
+}
+
+/// Palavras funcionais frequentes e exclusivas de cada idioma (palavras
+/// comuns a mais de um, como "que" e "de", ficam de fora), na ordem de
+/// [`Latin`], que indexa as pontuações.
+const STOPWORDS: &[(Latin, &[&str])] = &[
+    (
+        Latin::Portuguese,
+        &[
+            "o", "não", "é", "são", "uma", "um", "com", "do", "da", "dos", "das", "você", "também",
+            "isso", "muito", "já", "pode", "deve", "ao", "em", "função", "mais",
+        ],
+    ),
+    (
+        Latin::English,
+        &[
+            "the", "is", "are", "and", "of", "to", "this", "that", "with", "it", "should", "be",
+            "not", "for", "in", "can", "function", "code", "looks", "there",
+        ],
+    ),
+    (
+        Latin::Spanish,
+        &[
+            "el", "es", "son", "los", "las", "una", "con", "del", "al", "pero", "esto", "muy",
+            "puede", "debe", "también", "función", "no", "hay", "sin", "más", "y",
+        ],
+    ),
+    (
+        Latin::French,
+        &[
+            "le", "les", "est", "des", "une", "pour", "avec", "pas", "ce", "cette", "du", "dans",
+            "sont", "fonction", "et", "qui", "sur",
+        ],
+    ),
+    (
+        Latin::German,
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "mit", "ein", "eine", "für", "funktion",
+            "sie", "wird", "auf", "sollte", "zu", "den", "dem", "von",
+        ],
+    ),
+    (
+        Latin::Italian,
+        &[
+            "il", "che", "della", "di", "per", "non", "è", "sono", "funzione", "gli", "questo",
+            "questa", "anche",
+        ],
+    ),
+];
+
+/// Pontuação mínima para classificar a resposta num idioma não suportado.
+const MIN_UNSUPPORTED_SCORE: usize = 3;
+
+/// Estima o idioma de uma resposta em texto livre.
+pub fn detect_response_language(text: &str) -> ResponseLanguage {
+    let mut han = 0;
+    let mut kana = 0;
+    let mut hangul = 0;
+    let mut other_script = 0;
+    let mut latin = 0;
+    for c in text.chars() {
+        match c {
+            '\u{3040}'..='\u{30FF}' => kana += 1,
+            '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' => han += 1,
+            '\u{1100}'..='\u{11FF}' | '\u{AC00}'..='\u{D7AF}' => hangul += 1,
+            // Grego, cirílico, hebraico, árabe, devanágari e tailandês
+            '\u{0370}'..='\u{06FF}' | '\u{0900}'..='\u{097F}' | '\u{0E00}'..='\u{0E7F}' => {
+                other_script += 1
+            }
+            c if c.is_alphabetic() => latin += 1,
+            _ => {}
+        }
+    }
+
+    // Um ideograma ou sílaba vale por uma palavra curta; código e
+    // identificadores em inglês não devem esconder a escrita da resposta
+    let syllabic = (han + kana + hangul) * 3;
+    if syllabic + other_script > latin {
+        return if han > 0 && kana * 10 < han + kana && hangul == 0 && other_script < han {
+            ResponseLanguage::Chinese
+        } else {
+            ResponseLanguage::Unsupported
+        };
+    }
+
+    detect_latin(text)
+}
+
+/// Escolhe entre os idiomas de escrita latina pelas palavras funcionais e
+/// por marcas de ortografia ("ção", "ñ").
+fn detect_latin(text: &str) -> ResponseLanguage {
+    let lower = text.to_lowercase();
+    let mut scores = [0usize; STOPWORDS.len()];
+
+    for word in lower.split(|c: char| !c.is_alphabetic()) {
+        if word.is_empty() {
+            continue;
+        }
+        for (score, (_, words)) in scores.iter_mut().zip(STOPWORDS) {
+            if words.contains(&word) {
+                *score += 1;
+            }
+        }
+        if word.contains(['ã', 'õ', 'ç']) {
+            scores[Latin::Portuguese as usize] += 1;
+        }
+        if word.contains('ñ') || word.ends_with("ción") {
+            scores[Latin::Spanish as usize] += 1;
+        }
+    }
+    scores[Latin::Spanish as usize] += lower.matches(['¿', '¡']).count();
+
+    let (best, best_score) = STOPWORDS
+        .iter()
+        .zip(scores)
+        .map(|((language, _), score)| (*language, score))
+        .fold((Latin::Portuguese, 0), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        });
+
+    match best {
+        _ if best_score == 0 => ResponseLanguage::Unknown,
+        Latin::Portuguese => ResponseLanguage::Portuguese,
+        Latin::English => ResponseLanguage::English,
+        Latin::Spanish => ResponseLanguage::Spanish,
+        Latin::French | Latin::German | Latin::Italian => {
+            if best_score >= MIN_UNSUPPORTED_SCORE {
+                ResponseLanguage::Unsupported
+            } else {
+                ResponseLanguage::Unknown
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_response_language() {
+        let cases = [
+            (
+                "O código está correto e bem estruturado. Bom trabalho!",
+                ResponseLanguage::Portuguese,
+            ),
+            (
+                "The code looks correct, but consider handling overflow.",
+                ResponseLanguage::English,
+            ),
+            (
+                "El código es correcto y no tiene problemas de rendimiento.",
+                ResponseLanguage::Spanish,
+            ),
+            (
+                "代码存在SQL注入安全漏洞，必须使用参数化查询修复 `query(sql)`。",
+                ResponseLanguage::Chinese,
+            ),
+            (
+                "このコードにはセキュリティの脆弱性があります。",
+                ResponseLanguage::Unsupported,
+            ),
+            (
+                "В коде есть уязвимость: нет проверки входных данных.",
+                ResponseLanguage::Unsupported,
+            ),
+            (
+                "Le code est correct, mais il y a une faille de sécurité dans la fonction.",
+                ResponseLanguage::Unsupported,
+            ),
+            ("LGTM", ResponseLanguage::Unknown),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(detect_response_language(text), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_unsupported_languages_have_no_keywords() {
+        assert!(keywords(ResponseLanguage::Unsupported).is_empty());
+        assert_eq!(keywords(ResponseLanguage::Spanish).len(), 2);
+    }
+}
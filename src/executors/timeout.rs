@@ -0,0 +1,91 @@
+//! Timeout adaptativo por chamada.
+//!
+//! Um prazo fixo é longo demais para trechos de 10 linhas (a falha demora a
+//! aparecer) e curto demais para arquivos de 2 mil linhas em CLIs lentas.
+//! Com `general.adaptive_timeouts`, cada chamada recebe um prazo que cresce
+//! com o tamanho do código, sobe para acompanhar o p90 das latências
+//! recentes do executor e fica entre os limites configurados.
+
+use std::time::Duration;
+
+use crate::types::config::AdaptiveTimeoutConfig;
+
+/// Prazo de uma chamada com `code_bytes` de código.
+///
+/// `base_secs + per_kb_secs * kb`, elevado a `p90_factor * p90` quando o
+/// executor costuma ser mais lento que isso, e limitado a
+/// `min_secs..=max_secs`. O resultado é arredondado para cima, em segundos.
+pub fn adaptive_timeout(
+    config: &AdaptiveTimeoutConfig,
+    code_bytes: usize,
+    p90: Option<Duration>,
+) -> Duration {
+    let kb = code_bytes as f64 / 1024.0;
+    let by_size = config.base_secs as f64 + config.per_kb_secs * kb;
+    let by_latency = p90.map_or(0.0, |p90| p90.as_secs_f64() * config.p90_factor);
+
+    // max/min em vez de clamp: limites invertidos não entram em pânico
+    let secs = by_size
+        .max(by_latency)
+        .max(config.min_secs as f64)
+        .min(config.max_secs as f64);
+    Duration::from_secs(secs.ceil() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AdaptiveTimeoutConfig {
+        AdaptiveTimeoutConfig {
+            base_secs: 15,
+            per_kb_secs: 2.0,
+            min_secs: 20,
+            max_secs: 120,
+            p90_factor: 1.5,
+        }
+    }
+
+    #[test]
+    fn test_grows_with_code_size_within_bounds() {
+        let config = config();
+        // 15s + 2s/KB: trechos pequenos ficam no mínimo
+        assert_eq!(
+            adaptive_timeout(&config, 200, None),
+            Duration::from_secs(20)
+        );
+        assert_eq!(
+            adaptive_timeout(&config, 10 * 1024, None),
+            Duration::from_secs(35)
+        );
+        assert_eq!(
+            adaptive_timeout(&config, 10 * 1024 + 100, None),
+            Duration::from_secs(36)
+        );
+        assert_eq!(
+            adaptive_timeout(&config, 1024 * 1024, None),
+            Duration::from_secs(120)
+        );
+    }
+
+    #[test]
+    fn test_slow_executor_raises_the_timeout() {
+        let config = config();
+        let p90 = Some(Duration::from_secs(40));
+        // 1.5 x p90 = 60s supera os 35s do tamanho
+        assert_eq!(
+            adaptive_timeout(&config, 10 * 1024, p90),
+            Duration::from_secs(60)
+        );
+        // Um executor rápido não encurta o prazo do tamanho
+        assert_eq!(
+            adaptive_timeout(&config, 10 * 1024, Some(Duration::from_secs(2))),
+            Duration::from_secs(35)
+        );
+        // E o p90 também respeita o máximo
+        assert_eq!(
+            adaptive_timeout(&config, 0, Some(Duration::from_secs(600))),
+            Duration::from_secs(120)
+        );
+    }
+}
@@ -3,12 +3,22 @@
 //! Este módulo contém hooks que vêm pré-configurados com o Tetrad:
 //! - `LoggingHook`: Registra avaliações no log
 //! - `MetricsHook`: Coleta métricas de avaliação
+//! - `ErrorLoggingHook`: Registra falhas de executores no log
+//! - `WebhookHook`: Envia resultados para uma URL externa (`[hooks.webhook]`)
 
+use std::collections::BTreeMap;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use async_trait::async_trait;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
 
-use crate::TetradResult;
+use crate::reasoning::MetricCounters;
+use crate::types::config::WebhookConfig;
+use crate::{TetradError, TetradResult};
 
 use super::{Hook, HookContext, HookEvent, HookResult};
 
@@ -66,13 +76,65 @@ impl Hook for LoggingHook {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// ErrorLoggingHook
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Hook que registra falhas de executores no log.
+///
+/// Executado em on_error, quando um executor retorna erro ou é interrompido
+/// pelo prazo da avaliação.
+#[derive(Debug, Default)]
+pub struct ErrorLoggingHook;
+
+impl ErrorLoggingHook {
+    /// Cria um novo ErrorLoggingHook.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl Hook for ErrorLoggingHook {
+    fn name(&self) -> &str {
+        "error_logging"
+    }
+
+    fn event(&self) -> HookEvent {
+        HookEvent::OnError
+    }
+
+    async fn execute(&self, context: &HookContext<'_>) -> TetradResult<HookResult> {
+        if let HookContext::OnError {
+            request,
+            executor_name,
+            error,
+        } = context
+        {
+            tracing::error!(
+                request_id = %request.request_id,
+                executor = %executor_name,
+                timeout = matches!(error, crate::TetradError::ExecutorTimeout(_)),
+                error = %error,
+                "Executor failed"
+            );
+        }
+
+        Ok(HookResult::Continue)
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // MetricsHook
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Hook que coleta métricas de avaliação.
 ///
-/// Mantém contadores de avaliações, passes, bloqueios e score médio.
+/// Mantém contadores de avaliações, passes, bloqueios e score médio, no
+/// total e por tipo de avaliação.
+///
+/// Para consultar as métricas depois de registrar o hook, registre um
+/// `Arc<MetricsHook>` e mantenha um clone do `Arc`.
 #[derive(Debug, Default)]
 pub struct MetricsHook {
     /// Total de avaliações.
@@ -89,6 +151,13 @@ pub struct MetricsHook {
 
     /// Soma de todos os scores (para calcular média).
     score_sum: AtomicU64,
+
+    /// Contadores por tipo de avaliação (`plan`, `code`, `tests`...).
+    by_type: Mutex<BTreeMap<String, TypeMetrics>>,
+
+    /// Verificações finais respondidas com um certificado existente, sem
+    /// avaliação (não entram nos demais contadores).
+    recertifications: AtomicU64,
 }
 
 impl MetricsHook {
@@ -137,6 +206,16 @@ impl MetricsHook {
         }
     }
 
+    /// Registra uma verificação final re-certificada sem chamar os executores.
+    pub fn record_recertification(&self) {
+        self.recertifications.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Retorna o total de re-certificações.
+    pub fn total_recertifications(&self) -> u64 {
+        self.recertifications.load(Ordering::Relaxed)
+    }
+
     /// Retorna as métricas em formato estruturado.
     pub fn metrics(&self) -> Metrics {
         Metrics {
@@ -146,12 +225,53 @@ impl MetricsHook {
             blocks: self.total_blocks(),
             success_rate: self.success_rate(),
             average_score: self.average_score(),
+            by_type: self.by_type(),
+            recertifications: self.total_recertifications(),
+        }
+    }
+
+    /// Retorna os contadores por tipo de avaliação.
+    pub fn by_type(&self) -> BTreeMap<String, TypeMetrics> {
+        self.by_type
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    fn record(&self, context: &HookContext<'_>) {
+        if let HookContext::PostEvaluate { request, result } = context {
+            // Incrementa contador de avaliações
+            self.evaluations.fetch_add(1, Ordering::Relaxed);
+
+            // Incrementa contador específico da decisão
+            match result.decision {
+                crate::types::responses::Decision::Pass => {
+                    self.passes.fetch_add(1, Ordering::Relaxed);
+                }
+                crate::types::responses::Decision::Revise => {
+                    self.revises.fetch_add(1, Ordering::Relaxed);
+                }
+                crate::types::responses::Decision::Block => {
+                    self.blocks.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+
+            // Acumula score
+            self.score_sum
+                .fetch_add(result.score as u64, Ordering::Relaxed);
+
+            // Contadores do tipo de avaliação
+            let mut by_type = self.by_type.lock().unwrap_or_else(|e| e.into_inner());
+            by_type
+                .entry(request.evaluation_type.to_string())
+                .or_default()
+                .record(result.decision);
         }
     }
 }
 
 /// Métricas coletadas pelo MetricsHook.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Metrics {
     pub total_evaluations: u64,
     pub passes: u64,
@@ -159,6 +279,78 @@ pub struct Metrics {
     pub blocks: u64,
     pub success_rate: f64,
     pub average_score: f64,
+
+    /// Contadores por tipo de avaliação.
+    pub by_type: BTreeMap<String, TypeMetrics>,
+
+    /// Verificações finais que reaproveitaram um certificado.
+    pub recertifications: u64,
+}
+
+impl Metrics {
+    /// Monta as métricas a partir dos contadores persistidos no
+    /// ReasoningBank (`tetrad metrics`).
+    pub fn from_counters(counters: &[MetricCounters]) -> Self {
+        let mut metrics = Self {
+            total_evaluations: 0,
+            passes: 0,
+            revises: 0,
+            blocks: 0,
+            success_rate: 0.0,
+            average_score: 0.0,
+            by_type: BTreeMap::new(),
+            recertifications: 0,
+        };
+        let mut score_sum = 0;
+
+        for counter in counters {
+            metrics.total_evaluations += counter.evaluations;
+            metrics.passes += counter.passes;
+            metrics.revises += counter.revises;
+            metrics.blocks += counter.blocks;
+            metrics.recertifications += counter.recertifications;
+            score_sum += counter.score_sum;
+
+            // Tipos só com re-certificações não aparecem em by_type, como no hook
+            if counter.evaluations > 0 {
+                let by_type = metrics
+                    .by_type
+                    .entry(counter.evaluation_type.clone())
+                    .or_default();
+                by_type.evaluations += counter.evaluations;
+                by_type.passes += counter.passes;
+                by_type.revises += counter.revises;
+                by_type.blocks += counter.blocks;
+            }
+        }
+
+        if metrics.total_evaluations > 0 {
+            let total = metrics.total_evaluations as f64;
+            metrics.success_rate = metrics.passes as f64 / total;
+            metrics.average_score = score_sum as f64 / total;
+        }
+        metrics
+    }
+}
+
+/// Contadores de um tipo de avaliação.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TypeMetrics {
+    pub evaluations: u64,
+    pub passes: u64,
+    pub revises: u64,
+    pub blocks: u64,
+}
+
+impl TypeMetrics {
+    fn record(&mut self, decision: crate::types::responses::Decision) {
+        self.evaluations += 1;
+        match decision {
+            crate::types::responses::Decision::Pass => self.passes += 1,
+            crate::types::responses::Decision::Revise => self.revises += 1,
+            crate::types::responses::Decision::Block => self.blocks += 1,
+        }
+    }
 }
 
 #[async_trait]
@@ -172,27 +364,190 @@ impl Hook for MetricsHook {
     }
 
     async fn execute(&self, context: &HookContext<'_>) -> TetradResult<HookResult> {
-        if let HookContext::PostEvaluate { result, .. } = context {
-            // Incrementa contador de avaliações
-            self.evaluations.fetch_add(1, Ordering::Relaxed);
+        self.record(context);
+        Ok(HookResult::Continue)
+    }
+}
 
-            // Incrementa contador específico da decisão
-            match result.decision {
-                crate::types::responses::Decision::Pass => {
-                    self.passes.fetch_add(1, Ordering::Relaxed);
-                }
-                crate::types::responses::Decision::Revise => {
-                    self.revises.fetch_add(1, Ordering::Relaxed);
-                }
-                crate::types::responses::Decision::Block => {
-                    self.blocks.fetch_add(1, Ordering::Relaxed);
-                }
+/// Permite registrar o hook e continuar lendo as métricas pelo `Arc`.
+#[async_trait]
+impl Hook for Arc<MetricsHook> {
+    fn name(&self) -> &str {
+        "metrics"
+    }
+
+    fn event(&self) -> HookEvent {
+        HookEvent::PostEvaluate
+    }
+
+    async fn execute(&self, context: &HookContext<'_>) -> TetradResult<HookResult> {
+        self.record(context);
+        Ok(HookResult::Continue)
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// WebhookHook
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Destino compartilhado pelos hooks de um mesmo webhook.
+#[derive(Debug)]
+struct WebhookTarget {
+    url: String,
+    bearer_token: Option<String>,
+    timeout: Duration,
+}
+
+/// Hook que envia o resultado de cada avaliação para uma URL externa.
+///
+/// O POST roda em segundo plano: a avaliação nunca espera pela entrega, e
+/// falhas só aparecem no log. Respostas 5xx são repetidas uma vez.
+///
+/// Cada hook atende a um evento; [`WebhookHook::from_config`] cria um por
+/// evento configurado.
+#[derive(Debug)]
+pub struct WebhookHook {
+    event: HookEvent,
+    target: Arc<WebhookTarget>,
+}
+
+impl WebhookHook {
+    /// Cria os hooks da seção `[hooks.webhook]`, um por evento.
+    ///
+    /// Falha com URL que não seja `http://` ou `https://` ou com evento
+    /// desconhecido.
+    pub fn from_config(config: &WebhookConfig) -> TetradResult<Vec<Self>> {
+        let url = config.url.trim();
+        if !crate::reasoning::is_http_url(url) {
+            return Err(TetradError::config(format!(
+                "hooks.webhook.url must be an http:// or https:// URL: {}",
+                url
+            )));
+        }
+
+        let target = Arc::new(WebhookTarget {
+            url: url.to_string(),
+            bearer_token: config.bearer_token.clone(),
+            timeout: Duration::from_secs(config.timeout_secs.max(1)),
+        });
+
+        let mut events: Vec<HookEvent> = Vec::new();
+        for name in &config.events {
+            let event: HookEvent = name.parse()?;
+            if !events.contains(&event) {
+                events.push(event);
             }
+        }
 
-            // Acumula score
-            self.score_sum
-                .fetch_add(result.score as u64, Ordering::Relaxed);
+        Ok(events
+            .into_iter()
+            .map(|event| Self {
+                event,
+                target: Arc::clone(&target),
+            })
+            .collect())
+    }
+
+    /// Corpo JSON enviado para o contexto.
+    fn payload(context: &HookContext<'_>) -> serde_json::Value {
+        let event = context.event().to_string();
+        match context {
+            HookContext::PreEvaluate { request } => json!({
+                "event": event,
+                "request_id": request.request_id,
+                "language": request.language,
+                "timestamp": Utc::now(),
+            }),
+            HookContext::PostEvaluate { result, .. }
+            | HookContext::OnConsensus { result }
+            | HookContext::OnBlock { result } => json!({
+                "event": event,
+                "request_id": result.request_id,
+                "decision": result.decision.to_string(),
+                "score": result.score,
+                "findings": result.findings,
+                "timestamp": result.timestamp,
+            }),
+            HookContext::OnError {
+                request,
+                executor_name,
+                error,
+            } => json!({
+                "event": event,
+                "request_id": request.request_id,
+                "executor": executor_name,
+                "error": error.to_string(),
+                "timestamp": Utc::now(),
+            }),
+            HookContext::OnShutdown { reason } => json!({
+                "event": event,
+                "reason": reason,
+                "timestamp": Utc::now(),
+            }),
         }
+    }
+}
+
+/// Envia o payload, repetindo uma vez em caso de 5xx.
+async fn deliver(target: &WebhookTarget, event: HookEvent, body: Vec<u8>) {
+    for attempt in 1..=2 {
+        match crate::reasoning::post_json(
+            &target.url,
+            body.clone(),
+            target.bearer_token.as_deref(),
+            target.timeout,
+        )
+        .await
+        {
+            Ok(status) if (200..300).contains(&status) => return,
+            Ok(status) if status >= 500 && attempt == 1 => {
+                tracing::debug!(url = %target.url, status, "Webhook failed, retrying");
+            }
+            Ok(status) => {
+                tracing::warn!(
+                    url = %target.url,
+                    event = %event,
+                    status,
+                    "Webhook rejected the payload"
+                );
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(
+                    url = %target.url,
+                    event = %event,
+                    error = %e,
+                    "Webhook delivery failed"
+                );
+                return;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Hook for WebhookHook {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    fn event(&self) -> HookEvent {
+        self.event
+    }
+
+    async fn execute(&self, context: &HookContext<'_>) -> TetradResult<HookResult> {
+        let body = match serde_json::to_vec(&Self::payload(context)) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to serialize webhook payload");
+                return Ok(HookResult::Continue);
+            }
+        };
+
+        // A entrega não atrasa a avaliação
+        let target = Arc::clone(&self.target);
+        let event = self.event;
+        tokio::spawn(async move { deliver(&target, event, body).await });
 
         Ok(HookResult::Continue)
     }
@@ -201,26 +556,16 @@ impl Hook for MetricsHook {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{RequestBuilder, ResultBuilder};
     use crate::types::requests::EvaluationRequest;
     use crate::types::responses::{Decision, EvaluationResult};
-    use chrono::Utc;
-    use std::collections::HashMap;
 
     fn create_test_request() -> EvaluationRequest {
-        EvaluationRequest::new("fn main() {}", "rust")
+        RequestBuilder::new().build()
     }
 
     fn create_test_result(decision: Decision, score: u8) -> EvaluationResult {
-        EvaluationResult {
-            request_id: "test-123".to_string(),
-            decision,
-            score,
-            consensus_achieved: true,
-            votes: HashMap::new(),
-            findings: vec![],
-            feedback: "Test feedback".to_string(),
-            timestamp: Utc::now(),
-        }
+        ResultBuilder::new(decision, score).consensus(true).build()
     }
 
     #[test]
@@ -250,6 +595,24 @@ mod tests {
         assert!(matches!(hook_result, HookResult::Continue));
     }
 
+    #[tokio::test]
+    async fn test_error_logging_hook() {
+        let hook = ErrorLoggingHook::new();
+        assert_eq!(hook.name(), "error_logging");
+        assert_eq!(hook.event(), HookEvent::OnError);
+
+        let request = create_test_request();
+        let error = crate::TetradError::ExecutorTimeout("Gemini".to_string());
+        let context = HookContext::OnError {
+            request: &request,
+            executor_name: "Gemini",
+            error: &error,
+        };
+
+        let hook_result = hook.execute(&context).await.unwrap();
+        assert!(matches!(hook_result, HookResult::Continue));
+    }
+
     #[test]
     fn test_metrics_hook_name() {
         let hook = MetricsHook::new();
@@ -384,4 +747,206 @@ mod tests {
         assert!((metrics.success_rate - 1.0).abs() < 0.01);
         assert!((metrics.average_score - 85.0).abs() < 0.01);
     }
+
+    #[tokio::test]
+    async fn test_metrics_hook_by_type() {
+        use crate::types::requests::EvaluationType;
+
+        // Registrado como Arc, as métricas continuam acessíveis pelo clone
+        let hook = Arc::new(MetricsHook::new());
+        let registered: Box<dyn Hook> = Box::new(Arc::clone(&hook));
+
+        let plan = create_test_request().with_type(EvaluationType::Plan);
+        let code = create_test_request();
+        let pass = create_test_result(Decision::Pass, 90);
+        let block = create_test_result(Decision::Block, 20);
+
+        for (request, result) in [(&plan, &pass), (&code, &block), (&code, &block)] {
+            registered
+                .execute(&HookContext::PostEvaluate { request, result })
+                .await
+                .unwrap();
+        }
+
+        let metrics = hook.metrics();
+        assert_eq!(metrics.total_evaluations, 3);
+        assert_eq!(
+            metrics.by_type["plan"],
+            TypeMetrics {
+                evaluations: 1,
+                passes: 1,
+                revises: 0,
+                blocks: 0
+            }
+        );
+        assert_eq!(metrics.by_type["code"].blocks, 2);
+        assert!(!metrics.by_type.contains_key("tests"));
+
+        let json = serde_json::to_value(&metrics).unwrap();
+        assert_eq!(json["by_type"]["code"]["evaluations"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_from_counters_matches_hook() {
+        use crate::types::requests::EvaluationType;
+
+        let hook = MetricsHook::new();
+        let plan = create_test_request().with_type(EvaluationType::Plan);
+        let code = create_test_request();
+        let evaluations = [
+            (&plan, create_test_result(Decision::Revise, 60)),
+            (&code, create_test_result(Decision::Pass, 90)),
+            (&code, create_test_result(Decision::Block, 20)),
+        ];
+
+        let mut counters: Vec<MetricCounters> = Vec::new();
+        for (request, result) in &evaluations {
+            hook.execute(&HookContext::PostEvaluate { request, result })
+                .await
+                .unwrap();
+            counters.push(MetricCounters::evaluation(
+                request.evaluation_type,
+                result.decision,
+                result.score,
+            ));
+        }
+        hook.record_recertification();
+        counters.push(MetricCounters::recertification());
+
+        // Os contadores persistidos produzem o mesmo JSON do hook
+        assert_eq!(
+            serde_json::to_value(Metrics::from_counters(&counters)).unwrap(),
+            serde_json::to_value(hook.metrics()).unwrap()
+        );
+        assert_eq!(Metrics::from_counters(&[]).total_evaluations, 0);
+    }
+
+    /// Servidor local que responde `status` a cada POST autenticado em
+    /// `/hook` e envia o corpo de cada requisição pelo canal.
+    async fn serve(
+        status: usize,
+    ) -> (
+        mockito::ServerGuard,
+        String,
+        tokio::sync::mpsc::UnboundedReceiver<String>,
+    ) {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/hook")
+            .match_header("authorization", "Bearer s3cret")
+            .match_header("content-type", "application/json")
+            .with_status(status)
+            .with_body_from_request(move |request| {
+                let body = request.utf8_lossy_body().unwrap_or_default();
+                let _ = tx.send(body.to_string());
+                Vec::new()
+            })
+            .create_async()
+            .await;
+        let url = format!("{}/hook", server.url());
+        (server, url, rx)
+    }
+
+    /// Próximo corpo recebido pelo servidor de [`serve`].
+    async fn next_body(requests: &mut tokio::sync::mpsc::UnboundedReceiver<String>) -> String {
+        tokio::time::timeout(Duration::from_secs(5), requests.recv())
+            .await
+            .expect("webhook not delivered")
+            .unwrap()
+    }
+
+    fn webhook_config(url: &str, events: &[&str]) -> WebhookConfig {
+        WebhookConfig {
+            url: url.to_string(),
+            events: events.iter().map(|e| e.to_string()).collect(),
+            bearer_token: Some("s3cret".to_string()),
+            timeout_secs: 5,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_hook_posts_payload() {
+        let (_server, url, mut requests) = serve(200).await;
+        let hooks = WebhookHook::from_config(&webhook_config(&url, &["on_block"])).unwrap();
+        assert_eq!(hooks.len(), 1);
+        assert_eq!(hooks[0].name(), "webhook");
+        assert_eq!(hooks[0].event(), HookEvent::OnBlock);
+
+        let mut result = create_test_result(Decision::Block, 20);
+        result.findings = vec![crate::types::responses::Finding::new(
+            crate::types::responses::Severity::Critical,
+            "security",
+            "SQL injection",
+        )];
+        let hook_result = hooks[0]
+            .execute(&HookContext::OnBlock { result: &result })
+            .await
+            .unwrap();
+        assert!(matches!(hook_result, HookResult::Continue));
+
+        let body = next_body(&mut requests).await;
+        let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["event"], "on_block");
+        assert_eq!(payload["request_id"], "test-123");
+        assert_eq!(payload["decision"], "BLOCK");
+        assert_eq!(payload["score"], 20);
+        assert_eq!(payload["findings"][0]["issue"], "SQL injection");
+        assert!(payload["timestamp"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_webhook_hook_retries_once_on_server_error() {
+        let (_server, url, mut requests) = serve(503).await;
+        let hooks = WebhookHook::from_config(&webhook_config(&url, &["post_evaluate"])).unwrap();
+
+        let request = create_test_request();
+        let result = create_test_result(Decision::Pass, 90);
+        hooks[0]
+            .execute(&HookContext::PostEvaluate {
+                request: &request,
+                result: &result,
+            })
+            .await
+            .unwrap();
+
+        // Uma tentativa e uma repetição
+        next_body(&mut requests).await;
+        next_body(&mut requests).await;
+    }
+
+    #[tokio::test]
+    async fn test_webhook_hook_ignores_unreachable_endpoint() {
+        // Porta fechada: a falha só vai para o log
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+
+        let hooks = WebhookHook::from_config(&webhook_config(&url, &["on_block"])).unwrap();
+        let result = create_test_result(Decision::Block, 20);
+        let hook_result = hooks[0]
+            .execute(&HookContext::OnBlock { result: &result })
+            .await
+            .unwrap();
+        assert!(matches!(hook_result, HookResult::Continue));
+    }
+
+    #[test]
+    fn test_webhook_config_validation() {
+        let hooks = WebhookHook::from_config(&webhook_config(
+            "http://localhost/hook",
+            &["post_evaluate", "ON_BLOCK", "post_evaluate"],
+        ))
+        .unwrap();
+        let events: Vec<HookEvent> = hooks.iter().map(|h| h.event()).collect();
+        assert_eq!(events, vec![HookEvent::PostEvaluate, HookEvent::OnBlock]);
+
+        let err =
+            WebhookHook::from_config(&webhook_config("ftp://example.com/hook", &[])).unwrap_err();
+        assert!(err.to_string().contains("http:// or https://"));
+
+        let err = WebhookHook::from_config(&webhook_config("http://localhost/hook", &["later"]))
+            .unwrap_err();
+        assert!(err.to_string().contains("Unknown hook event 'later'"));
+    }
 }
@@ -7,23 +7,44 @@
 //! - `post_evaluate`: Após receber resultado da avaliação
 //! - `on_consensus`: Quando consenso é alcançado
 //! - `on_block`: Quando código é bloqueado
+//! - `on_error`: Quando um executor falha ou estoura o prazo
+//!
+//! Além dos hooks padrão, a seção `[hooks.webhook]` da configuração ativa o
+//! [`WebhookHook`], que envia os resultados para uma URL externa, e a seção
+//! `[hooks.redaction]` ativa o [`RedactionHook`], que remove segredos da
+//! request antes que ela chegue aos avaliadores.
+//!
+//! Uma falha em hooks de `post_evaluate`, `on_consensus`, `on_block` ou
+//! `on_error` é registrada no log e contada, e a execução segue para o
+//! próximo hook: o resultado da avaliação não se perde por causa de um hook.
+//! Em `pre_evaluate` a falha de um hook [`Hook::critical`] interrompe a
+//! avaliação. Hooks de `post_evaluate` podem reescrever o resultado com
+//! [`HookResult::ModifyResult`]. Execuções, falhas e latência de cada hook ficam em
+//! [`HookSystem::stats`].
 
 mod builtin;
+mod redaction;
+
+pub use builtin::{ErrorLoggingHook, LoggingHook, Metrics, MetricsHook, TypeMetrics, WebhookHook};
+pub use redaction::{RedactionHook, Redactor, SecretMatch, BUILTIN_PATTERNS};
 
-pub use builtin::{LoggingHook, MetricsHook};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
+use serde::Serialize;
 
 use crate::types::requests::EvaluationRequest;
-use crate::types::responses::EvaluationResult;
-use crate::TetradResult;
+use crate::types::responses::{Decision, EvaluationResult};
+use crate::{TetradError, TetradResult};
 
 // ═══════════════════════════════════════════════════════════════════════════
 // Tipos de eventos
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Evento que dispara um hook.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HookEvent {
     /// Antes de iniciar avaliação.
     PreEvaluate,
@@ -36,6 +57,12 @@ pub enum HookEvent {
 
     /// Quando código é bloqueado.
     OnBlock,
+
+    /// Quando um executor falha ou estoura o prazo.
+    OnError,
+
+    /// Quando a sessão MCP termina (cliente desconectou).
+    OnShutdown,
 }
 
 impl std::fmt::Display for HookEvent {
@@ -45,6 +72,28 @@ impl std::fmt::Display for HookEvent {
             HookEvent::PostEvaluate => write!(f, "post_evaluate"),
             HookEvent::OnConsensus => write!(f, "on_consensus"),
             HookEvent::OnBlock => write!(f, "on_block"),
+            HookEvent::OnError => write!(f, "on_error"),
+            HookEvent::OnShutdown => write!(f, "on_shutdown"),
+        }
+    }
+}
+
+impl std::str::FromStr for HookEvent {
+    type Err = TetradError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "pre_evaluate" => Ok(HookEvent::PreEvaluate),
+            "post_evaluate" => Ok(HookEvent::PostEvaluate),
+            "on_consensus" => Ok(HookEvent::OnConsensus),
+            "on_block" => Ok(HookEvent::OnBlock),
+            "on_error" => Ok(HookEvent::OnError),
+            "on_shutdown" => Ok(HookEvent::OnShutdown),
+            other => Err(TetradError::config(format!(
+                "Unknown hook event '{}' (expected pre_evaluate, post_evaluate, on_consensus, \
+                 on_block, on_error or on_shutdown)",
+                other
+            ))),
         }
     }
 }
@@ -80,6 +129,22 @@ pub enum HookContext<'a> {
         /// Resultado da avaliação (com decisão Block).
         result: &'a EvaluationResult,
     },
+
+    /// Contexto para on_error.
+    OnError {
+        /// Request em avaliação.
+        request: &'a EvaluationRequest,
+        /// Nome do executor (ou assento, quando o fallback também falhou).
+        executor_name: &'a str,
+        /// Erro retornado pelo executor.
+        error: &'a TetradError,
+    },
+
+    /// Contexto para on_shutdown.
+    OnShutdown {
+        /// Motivo do encerramento (ex: "client disconnected").
+        reason: &'a str,
+    },
 }
 
 impl<'a> HookContext<'a> {
@@ -90,6 +155,8 @@ impl<'a> HookContext<'a> {
             HookContext::PostEvaluate { .. } => HookEvent::PostEvaluate,
             HookContext::OnConsensus { .. } => HookEvent::OnConsensus,
             HookContext::OnBlock { .. } => HookEvent::OnBlock,
+            HookContext::OnError { .. } => HookEvent::OnError,
+            HookContext::OnShutdown { .. } => HookEvent::OnShutdown,
         }
     }
 }
@@ -106,10 +173,35 @@ pub enum HookResult {
     Continue,
 
     /// Pula a avaliação (apenas válido para pre_evaluate).
-    Skip,
+    ///
+    /// O resultado sintético leva `reason` no feedback e em `skip_reason`.
+    /// Sem `decision`, a avaliação pulada vale Revise: o código não foi
+    /// revisado e não pode passar por aprovado.
+    Skip {
+        /// Motivo exibido ao cliente (ex: "arquivo gerado").
+        reason: String,
+        /// Decisão do resultado sintético (padrão: Revise).
+        decision: Option<Decision>,
+    },
 
     /// Modifica a request (apenas válido para pre_evaluate).
     ModifyRequest(EvaluationRequest),
+
+    /// Substitui o resultado (apenas válido para post_evaluate).
+    ///
+    /// O próximo hook recebe o resultado substituído, e é ele que vai para o
+    /// cache, para o ReasoningBank e para o cliente.
+    ModifyResult(Box<EvaluationResult>),
+}
+
+impl HookResult {
+    /// Pula a avaliação com o motivo dado e a decisão padrão (Revise).
+    pub fn skip(reason: impl Into<String>) -> Self {
+        HookResult::Skip {
+            reason: reason.into(),
+            decision: None,
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -127,6 +219,38 @@ pub trait Hook: Send + Sync {
 
     /// Executa o hook.
     async fn execute(&self, context: &HookContext<'_>) -> TetradResult<HookResult>;
+
+    /// Se uma falha deste hook interrompe a avaliação.
+    ///
+    /// Só vale para `pre_evaluate`: nos demais eventos a falha é sempre
+    /// isolada. Um hook de `pre_evaluate` não crítico que falha é ignorado,
+    /// como se tivesse retornado `Continue`.
+    fn critical(&self) -> bool {
+        true
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Estatísticas de hooks
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Estatísticas de execução de um hook, expostas em `tetrad_metrics`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HookStats {
+    /// Nome do hook.
+    pub name: String,
+
+    /// Evento do hook (`pre_evaluate`, `post_evaluate`...).
+    pub event: String,
+
+    /// Execuções, com ou sem falha.
+    pub executions: u64,
+
+    /// Execuções que retornaram erro.
+    pub failures: u64,
+
+    /// Latência acumulada, em milissegundos.
+    pub total_latency_ms: f64,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -134,11 +258,20 @@ pub trait Hook: Send + Sync {
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// Gerenciador de hooks.
+///
+/// Clonar o sistema é barato: os hooks são compartilhados e as estatísticas
+/// também, então um clone alterado com [`register`](Self::register) ou
+/// [`remove`](Self::remove) pode substituir o original enquanto avaliações
+/// em andamento terminam com o snapshot que já tinham.
+#[derive(Clone)]
 pub struct HookSystem {
-    pre_evaluate: Vec<Box<dyn Hook>>,
-    post_evaluate: Vec<Box<dyn Hook>>,
-    on_consensus: Vec<Box<dyn Hook>>,
-    on_block: Vec<Box<dyn Hook>>,
+    pre_evaluate: Vec<Arc<dyn Hook>>,
+    post_evaluate: Vec<Arc<dyn Hook>>,
+    on_consensus: Vec<Arc<dyn Hook>>,
+    on_block: Vec<Arc<dyn Hook>>,
+    on_error: Vec<Arc<dyn Hook>>,
+    on_shutdown: Vec<Arc<dyn Hook>>,
+    stats: Arc<Mutex<HashMap<(HookEvent, String), HookStats>>>,
 }
 
 impl HookSystem {
@@ -149,18 +282,35 @@ impl HookSystem {
             post_evaluate: Vec::new(),
             on_consensus: Vec::new(),
             on_block: Vec::new(),
+            on_error: Vec::new(),
+            on_shutdown: Vec::new(),
+            stats: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    /// Cria um sistema com hooks padrão (logging).
+    /// Cria um sistema com hooks padrão (logging, métricas e log de erros).
     pub fn with_defaults() -> Self {
+        Self::with_metrics(Arc::new(MetricsHook::new()))
+    }
+
+    /// Cria um sistema com os hooks padrão usando o `MetricsHook` dado.
+    ///
+    /// Quem chama mantém um clone do `Arc` para consultar as métricas.
+    pub fn with_metrics(metrics: Arc<MetricsHook>) -> Self {
         let mut system = Self::new();
         system.register(Box::new(LoggingHook));
+        system.register(Box::new(metrics));
+        system.register(Box::new(ErrorLoggingHook));
         system
     }
 
     /// Registra um hook.
     pub fn register(&mut self, hook: Box<dyn Hook>) {
+        self.register_shared(Arc::from(hook));
+    }
+
+    /// Registra um hook compartilhado com outros sistemas.
+    pub fn register_shared(&mut self, hook: Arc<dyn Hook>) {
         let event = hook.event();
         tracing::debug!(
             hook_name = hook.name(),
@@ -173,64 +323,213 @@ impl HookSystem {
             HookEvent::PostEvaluate => self.post_evaluate.push(hook),
             HookEvent::OnConsensus => self.on_consensus.push(hook),
             HookEvent::OnBlock => self.on_block.push(hook),
+            HookEvent::OnError => self.on_error.push(hook),
+            HookEvent::OnShutdown => self.on_shutdown.push(hook),
         }
     }
 
+    /// Remove todos os hooks chamados `name`, de qualquer evento.
+    ///
+    /// Retorna quantos foram removidos. As estatísticas do hook são mantidas.
+    pub fn remove(&mut self, name: &str) -> usize {
+        let before = self.count();
+        for hooks in self.hooks_mut() {
+            hooks.retain(|hook| hook.name() != name);
+        }
+        let removed = before - self.count();
+        if removed > 0 {
+            tracing::debug!(hook_name = name, removed, "Removing hook");
+        }
+        removed
+    }
+
+    /// Substitui todos os hooks pelos de `other`, mantendo as estatísticas.
+    pub fn replace_all(&mut self, other: HookSystem) {
+        let stats = Arc::clone(&self.stats);
+        *self = HookSystem { stats, ..other };
+    }
+
+    fn hooks_mut(&mut self) -> [&mut Vec<Arc<dyn Hook>>; 6] {
+        [
+            &mut self.pre_evaluate,
+            &mut self.post_evaluate,
+            &mut self.on_consensus,
+            &mut self.on_block,
+            &mut self.on_error,
+            &mut self.on_shutdown,
+        ]
+    }
+
     /// Executa hooks de pre_evaluate.
     ///
-    /// Retorna o resultado final (Continue, Skip ou ModifyRequest).
+    /// Retorna o resultado final (Continue, Skip ou ModifyRequest). Um
+    /// `ModifyRequest` não encerra a cadeia: os hooks seguintes recebem a
+    /// request modificada, e um `Skip` posterior ainda vale. A falha de um
+    /// hook crítico é retornada; a de um não crítico é ignorada.
     pub async fn run_pre_evaluate(&self, request: &EvaluationRequest) -> TetradResult<HookResult> {
-        let context = HookContext::PreEvaluate { request };
+        let mut modified: Option<EvaluationRequest> = None;
 
         for hook in &self.pre_evaluate {
-            let result = hook.execute(&context).await?;
+            let context = HookContext::PreEvaluate {
+                request: modified.as_ref().unwrap_or(request),
+            };
+            let result = match self.execute(hook.as_ref(), &context).await {
+                Ok(result) => result,
+                Err(e) if hook.critical() => return Err(e),
+                Err(e) => {
+                    tracing::warn!(
+                        hook_name = hook.name(),
+                        error = %e,
+                        "Non-critical pre_evaluate hook failed, continuing"
+                    );
+                    continue;
+                }
+            };
             match result {
                 HookResult::Continue => continue,
-                HookResult::Skip => return Ok(HookResult::Skip),
-                HookResult::ModifyRequest(new_request) => {
-                    return Ok(HookResult::ModifyRequest(new_request))
+                skip @ HookResult::Skip { .. } => return Ok(skip),
+                HookResult::ModifyRequest(new_request) => modified = Some(new_request),
+                HookResult::ModifyResult(_) => {
+                    tracing::warn!(
+                        hook_name = hook.name(),
+                        "ModifyResult is only valid in post_evaluate, ignoring"
+                    );
                 }
             }
         }
 
-        Ok(HookResult::Continue)
+        Ok(modified.map_or(HookResult::Continue, HookResult::ModifyRequest))
     }
 
-    /// Executa hooks de post_evaluate.
+    /// Executa hooks de post_evaluate em ordem de registro, isolando falhas.
+    ///
+    /// Retorna o resultado depois de aplicar cada [`HookResult::ModifyResult`];
+    /// cada hook recebe o resultado deixado pelo anterior. `Skip` e
+    /// `ModifyRequest` não têm efeito aqui e só são registrados no log.
     pub async fn run_post_evaluate(
         &self,
         request: &EvaluationRequest,
-        result: &EvaluationResult,
-    ) -> TetradResult<()> {
-        let context = HookContext::PostEvaluate { request, result };
-
+        mut result: EvaluationResult,
+    ) -> EvaluationResult {
         for hook in &self.post_evaluate {
-            hook.execute(&context).await?;
+            let context = HookContext::PostEvaluate {
+                request,
+                result: &result,
+            };
+            match self.execute(hook.as_ref(), &context).await {
+                Ok(HookResult::Continue) => {}
+                Ok(HookResult::ModifyResult(modified)) => {
+                    tracing::info!(
+                        hook_name = hook.name(),
+                        "Result modified by post_evaluate hook"
+                    );
+                    result = *modified;
+                }
+                Ok(_) => {
+                    tracing::warn!(
+                        hook_name = hook.name(),
+                        "Skip and ModifyRequest are only valid in pre_evaluate, ignoring"
+                    );
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        hook_name = hook.name(),
+                        event = %HookEvent::PostEvaluate,
+                        error = %e,
+                        "Hook failed, continuing with the next hook"
+                    );
+                }
+            }
         }
-
-        Ok(())
+        result
     }
 
-    /// Executa hooks de on_consensus.
-    pub async fn run_on_consensus(&self, result: &EvaluationResult) -> TetradResult<()> {
+    /// Executa hooks de on_consensus, isolando falhas.
+    pub async fn run_on_consensus(&self, result: &EvaluationResult) {
         let context = HookContext::OnConsensus { result };
+        self.run_isolated(&self.on_consensus, &context).await;
+    }
 
-        for hook in &self.on_consensus {
-            hook.execute(&context).await?;
-        }
+    /// Executa hooks de on_block, isolando falhas.
+    pub async fn run_on_block(&self, result: &EvaluationResult) {
+        let context = HookContext::OnBlock { result };
+        self.run_isolated(&self.on_block, &context).await;
+    }
+
+    /// Executa hooks de on_error para a falha de um executor, isolando falhas.
+    pub async fn run_on_error(
+        &self,
+        request: &EvaluationRequest,
+        executor_name: &str,
+        error: &TetradError,
+    ) {
+        let context = HookContext::OnError {
+            request,
+            executor_name,
+            error,
+        };
+        self.run_isolated(&self.on_error, &context).await;
+    }
 
-        Ok(())
+    /// Executa hooks de on_shutdown ao fim da sessão, isolando falhas.
+    pub async fn run_on_shutdown(&self, reason: &str) {
+        let context = HookContext::OnShutdown { reason };
+        self.run_isolated(&self.on_shutdown, &context).await;
     }
 
-    /// Executa hooks de on_block.
-    pub async fn run_on_block(&self, result: &EvaluationResult) -> TetradResult<()> {
-        let context = HookContext::OnBlock { result };
+    /// Estatísticas de cada hook executado, ordenadas por evento e nome.
+    pub fn stats(&self) -> Vec<HookStats> {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<HookStats> = stats.values().cloned().collect();
+        stats.sort_by(|a, b| (&a.event, &a.name).cmp(&(&b.event, &b.name)));
+        stats
+    }
 
-        for hook in &self.on_block {
-            hook.execute(&context).await?;
+    /// Executa os hooks em ordem; uma falha é registrada e não interrompe os demais.
+    async fn run_isolated(&self, hooks: &[Arc<dyn Hook>], context: &HookContext<'_>) {
+        for hook in hooks {
+            if let Err(e) = self.execute(hook.as_ref(), context).await {
+                tracing::warn!(
+                    hook_name = hook.name(),
+                    event = %context.event(),
+                    error = %e,
+                    "Hook failed, continuing with the next hook"
+                );
+            }
         }
+    }
 
-        Ok(())
+    /// Executa um hook, registrando latência e falha.
+    async fn execute(
+        &self,
+        hook: &dyn Hook,
+        context: &HookContext<'_>,
+    ) -> TetradResult<HookResult> {
+        let started = Instant::now();
+        let result = hook.execute(context).await;
+        self.record(
+            hook.name(),
+            context.event(),
+            started.elapsed(),
+            result.is_err(),
+        );
+        result
+    }
+
+    fn record(&self, name: &str, event: HookEvent, latency: Duration, failed: bool) {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = stats
+            .entry((event, name.to_string()))
+            .or_insert_with(|| HookStats {
+                name: name.to_string(),
+                event: event.to_string(),
+                ..Default::default()
+            });
+        entry.executions += 1;
+        if failed {
+            entry.failures += 1;
+        }
+        entry.total_latency_ms += latency.as_secs_f64() * 1000.0;
     }
 
     /// Retorna o número total de hooks registrados.
@@ -239,6 +538,8 @@ impl HookSystem {
             + self.post_evaluate.len()
             + self.on_consensus.len()
             + self.on_block.len()
+            + self.on_error.len()
+            + self.on_shutdown.len()
     }
 
     /// Retorna o número de hooks para um evento específico.
@@ -248,6 +549,8 @@ impl HookSystem {
             HookEvent::PostEvaluate => self.post_evaluate.len(),
             HookEvent::OnConsensus => self.on_consensus.len(),
             HookEvent::OnBlock => self.on_block.len(),
+            HookEvent::OnError => self.on_error.len(),
+            HookEvent::OnShutdown => self.on_shutdown.len(),
         }
     }
 }
@@ -261,9 +564,7 @@ impl Default for HookSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::responses::Decision;
-    use chrono::Utc;
-    use std::collections::HashMap;
+    use crate::test_support::{RequestBuilder, ResultBuilder};
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
@@ -301,20 +602,11 @@ mod tests {
     }
 
     fn create_test_request() -> EvaluationRequest {
-        EvaluationRequest::new("fn main() {}", "rust")
+        RequestBuilder::new().build()
     }
 
     fn create_test_result() -> EvaluationResult {
-        EvaluationResult {
-            request_id: "test-123".to_string(),
-            decision: Decision::Pass,
-            score: 85,
-            consensus_achieved: true,
-            votes: HashMap::new(),
-            findings: vec![],
-            feedback: "Test feedback".to_string(),
-            timestamp: Utc::now(),
-        }
+        ResultBuilder::pass(85).build()
     }
 
     #[test]
@@ -327,6 +619,8 @@ mod tests {
     fn test_hook_system_with_defaults() {
         let system = HookSystem::with_defaults();
         assert!(system.count() > 0);
+        assert_eq!(system.count_for_event(HookEvent::PostEvaluate), 2);
+        assert_eq!(system.count_for_event(HookEvent::OnError), 1);
     }
 
     #[test]
@@ -344,6 +638,75 @@ mod tests {
         assert_eq!(system.count_for_event(HookEvent::PostEvaluate), 0);
     }
 
+    #[test]
+    fn test_remove_hook_by_name() {
+        let mut system = HookSystem::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        for event in [HookEvent::PreEvaluate, HookEvent::OnBlock] {
+            system.register(Box::new(CountingHook::new("audit", event, count.clone())));
+        }
+        system.register(Box::new(CountingHook::new(
+            "other",
+            HookEvent::OnBlock,
+            count,
+        )));
+
+        assert_eq!(system.remove("audit"), 2);
+        assert_eq!(system.remove("audit"), 0);
+        assert_eq!(system.count(), 1);
+        assert_eq!(system.count_for_event(HookEvent::OnBlock), 1);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_unaffected_by_changes_to_clone() {
+        let mut system = HookSystem::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        system.register(Box::new(CountingHook::new(
+            "test",
+            HookEvent::PostEvaluate,
+            count.clone(),
+        )));
+
+        // Uma avaliação em andamento segura o snapshot antigo
+        let snapshot = system.clone();
+        assert_eq!(system.remove("test"), 1);
+
+        let request = create_test_request();
+        let result = create_test_result();
+        snapshot.run_post_evaluate(&request, result.clone()).await;
+        system.run_post_evaluate(&request, result).await;
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        // As estatísticas são compartilhadas entre os clones
+        assert_eq!(system.stats().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_replace_all_keeps_stats() {
+        let mut system = HookSystem::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        system.register(Box::new(CountingHook::new(
+            "old",
+            HookEvent::OnConsensus,
+            count.clone(),
+        )));
+        system.run_on_consensus(&create_test_result()).await;
+
+        let mut replacement = HookSystem::new();
+        replacement.register(Box::new(CountingHook::new(
+            "new",
+            HookEvent::OnConsensus,
+            count.clone(),
+        )));
+        system.replace_all(replacement);
+        system.run_on_consensus(&create_test_result()).await;
+
+        assert_eq!(system.count(), 1);
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        let names: Vec<String> = system.stats().into_iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["new", "old"]);
+    }
+
     #[tokio::test]
     async fn test_pre_evaluate_hook() {
         let mut system = HookSystem::new();
@@ -374,10 +737,87 @@ mod tests {
         )));
 
         let request = create_test_request();
-        let result = create_test_result();
-        system.run_post_evaluate(&request, &result).await.unwrap();
+        let result = system
+            .run_post_evaluate(&request, create_test_result())
+            .await;
 
         assert_eq!(count.load(Ordering::SeqCst), 1);
+        // Sem ModifyResult o resultado volta intacto
+        assert_eq!(result.feedback, "Test feedback");
+        assert_eq!(result.decision, Decision::Pass);
+    }
+
+    // Hook de teste que acrescenta uma nota ao feedback do resultado que recebe
+    struct AnnotatingHook(&'static str);
+
+    #[async_trait]
+    impl Hook for AnnotatingHook {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn event(&self) -> HookEvent {
+            HookEvent::PostEvaluate
+        }
+
+        async fn execute(&self, context: &HookContext<'_>) -> TetradResult<HookResult> {
+            let HookContext::PostEvaluate { result, .. } = context else {
+                return Ok(HookResult::Continue);
+            };
+            let mut result = (*result).clone();
+            result.feedback = format!("{} [{}]", result.feedback, self.0);
+            if self.0 == "policy" {
+                result.decision = Decision::Block;
+            }
+            Ok(HookResult::ModifyResult(Box::new(result)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_post_evaluate_modifications_chain_in_order() {
+        let mut system = HookSystem::new();
+        system.register(Box::new(AnnotatingHook("policy")));
+        system.register(Box::new(AnnotatingHook("compliance")));
+
+        let result = system
+            .run_post_evaluate(&create_test_request(), create_test_result())
+            .await;
+
+        // O segundo hook vê a saída do primeiro
+        assert_eq!(result.feedback, "Test feedback [policy] [compliance]");
+        assert_eq!(result.decision, Decision::Block);
+    }
+
+    // Hook de post_evaluate que devolve um resultado só válido em pre_evaluate
+    struct MisplacedHook;
+
+    #[async_trait]
+    impl Hook for MisplacedHook {
+        fn name(&self) -> &str {
+            "misplaced"
+        }
+
+        fn event(&self) -> HookEvent {
+            HookEvent::PostEvaluate
+        }
+
+        async fn execute(&self, _context: &HookContext<'_>) -> TetradResult<HookResult> {
+            Ok(HookResult::skip("too late"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_skip_in_post_evaluate_is_ignored() {
+        let mut system = HookSystem::new();
+        system.register(Box::new(MisplacedHook));
+        system.register(Box::new(AnnotatingHook("after")));
+
+        let result = system
+            .run_post_evaluate(&create_test_request(), create_test_result())
+            .await;
+
+        assert_eq!(result.feedback, "Test feedback [after]");
+        assert_eq!(result.skip_reason, None);
     }
 
     #[tokio::test]
@@ -392,7 +832,7 @@ mod tests {
         )));
 
         let result = create_test_result();
-        system.run_on_consensus(&result).await.unwrap();
+        system.run_on_consensus(&result).await;
 
         assert_eq!(count.load(Ordering::SeqCst), 1);
     }
@@ -409,11 +849,129 @@ mod tests {
         )));
 
         let result = create_test_result();
-        system.run_on_block(&result).await.unwrap();
+        system.run_on_block(&result).await;
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_on_error_hook() {
+        let mut system = HookSystem::new();
+        let count = Arc::new(AtomicUsize::new(0));
+
+        system.register(Box::new(CountingHook::new(
+            "test",
+            HookEvent::OnError,
+            count.clone(),
+        )));
+
+        let request = create_test_request();
+        let error = TetradError::ExecutorTimeout("Codex".to_string());
+        system.run_on_error(&request, "Codex", &error).await;
 
         assert_eq!(count.load(Ordering::SeqCst), 1);
     }
 
+    // Hook de teste que pula a avaliação
+    struct SkippingHook;
+
+    #[async_trait]
+    impl Hook for SkippingHook {
+        fn name(&self) -> &str {
+            "skipping"
+        }
+
+        fn event(&self) -> HookEvent {
+            HookEvent::PreEvaluate
+        }
+
+        async fn execute(&self, _context: &HookContext<'_>) -> TetradResult<HookResult> {
+            Ok(HookResult::skip("generated file"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_skip_stops_chain_with_reason() {
+        let mut system = HookSystem::new();
+        let count = Arc::new(AtomicUsize::new(0));
+
+        system.register(Box::new(SkippingHook));
+        system.register(Box::new(CountingHook::new(
+            "after",
+            HookEvent::PreEvaluate,
+            count.clone(),
+        )));
+
+        let result = system
+            .run_pre_evaluate(&create_test_request())
+            .await
+            .unwrap();
+
+        match result {
+            HookResult::Skip { reason, decision } => {
+                assert_eq!(reason, "generated file");
+                assert_eq!(decision, None);
+            }
+            other => panic!("expected Skip, got {:?}", other),
+        }
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+
+    // Hook de teste que acrescenta uma marca ao código
+    struct AppendingHook(&'static str);
+
+    #[async_trait]
+    impl Hook for AppendingHook {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn event(&self) -> HookEvent {
+            HookEvent::PreEvaluate
+        }
+
+        async fn execute(&self, context: &HookContext<'_>) -> TetradResult<HookResult> {
+            let HookContext::PreEvaluate { request } = context else {
+                return Ok(HookResult::Continue);
+            };
+            let mut modified = (*request).clone();
+            modified.code.push_str(self.0);
+            Ok(HookResult::ModifyRequest(modified))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_modify_request_chains_to_next_hook() {
+        let mut system = HookSystem::new();
+        system.register(Box::new(AppendingHook(" // a")));
+        system.register(Box::new(AppendingHook(" // b")));
+
+        let request = create_test_request();
+        let result = system.run_pre_evaluate(&request).await.unwrap();
+
+        // O segundo hook recebe a request já modificada pelo primeiro
+        match result {
+            HookResult::ModifyRequest(modified) => {
+                assert_eq!(modified.code, format!("{} // a // b", request.code));
+            }
+            other => panic!("expected ModifyRequest, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_skip_after_modify_request_still_skips() {
+        let mut system = HookSystem::new();
+        system.register(Box::new(AppendingHook(" // a")));
+        system.register(Box::new(SkippingHook));
+
+        let result = system
+            .run_pre_evaluate(&create_test_request())
+            .await
+            .unwrap();
+
+        assert!(matches!(result, HookResult::Skip { .. }));
+    }
+
     #[tokio::test]
     async fn test_multiple_hooks_chain() {
         let mut system = HookSystem::new();
@@ -439,12 +997,106 @@ mod tests {
         assert_eq!(count2.load(Ordering::SeqCst), 1);
     }
 
+    // Hook de teste que sempre falha
+    struct FailingHook {
+        event: HookEvent,
+        critical: bool,
+    }
+
+    #[async_trait]
+    impl Hook for FailingHook {
+        fn name(&self) -> &str {
+            "failing"
+        }
+
+        fn event(&self) -> HookEvent {
+            self.event
+        }
+
+        async fn execute(&self, _context: &HookContext<'_>) -> TetradResult<HookResult> {
+            Err(TetradError::config("hook crashed"))
+        }
+
+        fn critical(&self) -> bool {
+            self.critical
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_post_evaluate_hook_is_isolated() {
+        let mut system = HookSystem::new();
+        let count = Arc::new(AtomicUsize::new(0));
+
+        system.register(Box::new(FailingHook {
+            event: HookEvent::PostEvaluate,
+            critical: true,
+        }));
+        system.register(Box::new(CountingHook::new(
+            "after",
+            HookEvent::PostEvaluate,
+            count.clone(),
+        )));
+
+        let result = system
+            .run_post_evaluate(&create_test_request(), create_test_result())
+            .await;
+        assert_eq!(result.request_id, "test-123");
+
+        // O hook seguinte roda mesmo após a falha
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        let stats = system.stats();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].name, "after");
+        assert_eq!(stats[0].failures, 0);
+        assert_eq!(stats[1].name, "failing");
+        assert_eq!(stats[1].event, "post_evaluate");
+        assert_eq!(stats[1].executions, 1);
+        assert_eq!(stats[1].failures, 1);
+        assert!(stats[1].total_latency_ms >= 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_pre_evaluate_failure_respects_critical() {
+        let request = create_test_request();
+        let count = Arc::new(AtomicUsize::new(0));
+
+        let mut critical = HookSystem::new();
+        critical.register(Box::new(FailingHook {
+            event: HookEvent::PreEvaluate,
+            critical: true,
+        }));
+        critical.register(Box::new(CountingHook::new(
+            "after",
+            HookEvent::PreEvaluate,
+            count.clone(),
+        )));
+        assert!(critical.run_pre_evaluate(&request).await.is_err());
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+
+        let mut lenient = HookSystem::new();
+        lenient.register(Box::new(FailingHook {
+            event: HookEvent::PreEvaluate,
+            critical: false,
+        }));
+        lenient.register(Box::new(CountingHook::new(
+            "after",
+            HookEvent::PreEvaluate,
+            count.clone(),
+        )));
+        let result = lenient.run_pre_evaluate(&request).await.unwrap();
+        assert!(matches!(result, HookResult::Continue));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert_eq!(lenient.stats()[1].failures, 1);
+    }
+
     #[test]
     fn test_hook_event_display() {
         assert_eq!(format!("{}", HookEvent::PreEvaluate), "pre_evaluate");
         assert_eq!(format!("{}", HookEvent::PostEvaluate), "post_evaluate");
         assert_eq!(format!("{}", HookEvent::OnConsensus), "on_consensus");
         assert_eq!(format!("{}", HookEvent::OnBlock), "on_block");
+        assert_eq!(format!("{}", HookEvent::OnError), "on_error");
     }
 
     #[test]
@@ -466,5 +1118,13 @@ mod tests {
 
         let ctx_block = HookContext::OnBlock { result: &result };
         assert_eq!(ctx_block.event(), HookEvent::OnBlock);
+
+        let error = TetradError::ExecutorTimeout("Codex".to_string());
+        let ctx_error = HookContext::OnError {
+            request: &request,
+            executor_name: "Codex",
+            error: &error,
+        };
+        assert_eq!(ctx_error.event(), HookEvent::OnError);
     }
 }
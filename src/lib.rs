@@ -15,6 +15,7 @@
 //! - [`hooks`] - Sistema de hooks para customização
 //! - [`cache`] - Cache LRU para resultados de avaliação
 //! - [`types`] - Tipos compartilhados
+//! - `test_support` - Builders para testes (feature `test-util`)
 
 pub mod cache;
 pub mod cli;
@@ -23,6 +24,8 @@ pub mod executors;
 pub mod hooks;
 pub mod mcp;
 pub mod reasoning;
+#[cfg(any(test, feature = "test-util"))]
+pub mod test_support;
 pub mod types;
 
 pub use types::config::Config;
@@ -1,19 +1,53 @@
 use clap::Parser;
-use tetrad::cli::{Cli, Commands};
-use tetrad::types::config::Config;
-use tetrad::TetradResult;
+use tetrad::cli::{Cli, Commands, TrendBucket};
+use tetrad::types::config::{Config, CONFIG_FILE_NAME};
+use tetrad::{TetradError, TetradResult};
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 
 #[tokio::main]
 async fn main() -> TetradResult<()> {
     let cli = Cli::parse();
+    let style = tetrad::cli::style::init(cli.color);
 
-    // Load configuration first (no logging yet)
-    let config = if cli.config.exists() {
-        Config::load(&cli.config).unwrap_or_else(|_| Config::default_config())
+    // Without an explicit --config, look for tetrad.toml upwards from here
+    let config_path = if !cli.config.exists() && cli.config.as_os_str() == CONFIG_FILE_NAME {
+        std::env::current_dir()
+            .ok()
+            .and_then(|dir| Config::discover(&dir))
+            .unwrap_or_else(|| cli.config.clone())
+    } else {
+        cli.config.clone()
+    };
+
+    // A missing file means defaults; a file that does not load is fatal.
+    // doctor reads it without validation so it can list the problems itself.
+    let config = if config_path.exists() {
+        let loaded = if matches!(cli.command, Commands::Doctor { .. }) {
+            Config::read(&config_path)
+        } else {
+            Config::load(&config_path)
+        };
+        match loaded {
+            Ok(config) => config,
+            Err(TetradError::InvalidConfig(problems)) => {
+                eprintln!("Error: invalid configuration at {}:", config_path.display());
+                for problem in problems {
+                    eprintln!("  - {}", problem);
+                }
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Error: could not load {}: {}", config_path.display(), e);
+                std::process::exit(1);
+            }
+        }
     } else {
         Config::default_config()
     };
+    let mut config = config.with_resolved_paths();
+    if cli.offline {
+        tetrad::executors::use_mock_executors(&mut config.executors);
+    }
 
     // Determine log level: CLI flags take precedence over config
     let log_level = if cli.quiet {
@@ -33,44 +67,270 @@ async fn main() -> TetradResult<()> {
     );
 
     tracing_subscriber::registry()
-        .with(fmt::layer().with_writer(std::io::stderr))
+        .with(
+            fmt::layer()
+                .with_writer(std::io::stderr)
+                .with_ansi(style.is_styled()),
+        )
         .with(filter)
         .init();
 
-    tracing::debug!("Configuration loaded from: {}", cli.config.display());
+    tracing::debug!("Configuration loaded from: {}", config_path.display());
+    tracing::debug!("Data directory: {}", config.data_dir().display());
+
+    // A project config that defines commands runs only once approved
+    if cli.command.runs_executors() {
+        match tetrad::cli::commands::ensure_trusted(&config_path, &config, cli.trust) {
+            Ok(()) => {}
+            Err(e @ TetradError::UntrustedConfig { .. }) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+            Err(e) => return Err(e),
+        }
+    }
 
     match cli.command {
-        Commands::Init { path } => {
-            tetrad::cli::commands::init(path).await?;
+        Commands::Init {
+            path,
+            claude,
+            scope,
+        } => {
+            tetrad::cli::commands::init(path, claude.then_some(scope)).await?;
         }
-        Commands::Serve { port } => {
-            tetrad::cli::commands::serve(port, &config).await?;
+        Commands::Serve {
+            port,
+            api,
+            watch_config,
+        } => {
+            if api {
+                tetrad::cli::commands::serve_api(
+                    port,
+                    watch_config,
+                    cli.offline,
+                    &config_path,
+                    &config,
+                )
+                .await?;
+            } else {
+                tetrad::cli::commands::serve(
+                    port,
+                    watch_config,
+                    cli.offline,
+                    &config_path,
+                    &config,
+                )
+                .await?;
+            }
         }
         Commands::Status => {
             tetrad::cli::commands::status(&config).await?;
         }
-        Commands::Config => {
-            tetrad::cli::commands::config_cmd(&cli.config).await?;
+        Commands::Config { effective } => {
+            if effective {
+                tetrad::cli::commands::effective_config(&config_path, &config)?;
+            } else {
+                tetrad::cli::commands::config_cmd(&config_path).await?;
+            }
+        }
+        Commands::Doctor {
+            deep,
+            clear_quarantine,
+        } => {
+            let has_issues = tetrad::cli::commands::doctor(&config, deep, clear_quarantine).await?;
+            if has_issues {
+                std::process::exit(1);
+            }
         }
-        Commands::Doctor => {
-            tetrad::cli::commands::doctor(&config).await?;
+        Commands::Selftest { live } => {
+            if !tetrad::cli::commands::selftest(live, &config).await? {
+                std::process::exit(1);
+            }
         }
         Commands::Version => {
             tetrad::cli::commands::version();
         }
-        Commands::Evaluate { code, language } => {
-            tetrad::cli::commands::evaluate(&code, &language, &config).await?;
+        Commands::Evaluate {
+            code,
+            dir,
+            staged,
+            max_file_bytes,
+            language,
+            max_files,
+            jobs,
+            format,
+            show_fixes,
+            review_type,
+            related,
+            override_budget,
+        } => {
+            if staged {
+                let failed = or_evaluate_failed(
+                    tetrad::cli::commands::evaluate_staged(
+                        &language,
+                        max_file_bytes,
+                        jobs,
+                        format,
+                        override_budget,
+                        &config,
+                    )
+                    .await,
+                );
+                if failed {
+                    std::process::exit(1);
+                }
+            } else if let Some(dir) = dir {
+                let any_blocked = or_evaluate_failed(
+                    tetrad::cli::commands::evaluate_dir(
+                        &dir,
+                        &language,
+                        max_files,
+                        jobs,
+                        override_budget,
+                        &config,
+                    )
+                    .await,
+                );
+                if any_blocked {
+                    std::process::exit(1);
+                }
+            } else if let Some(code) = code {
+                let decision = or_evaluate_failed(
+                    tetrad::cli::commands::evaluate(
+                        &code,
+                        &language,
+                        format,
+                        show_fixes,
+                        review_type,
+                        related.as_deref(),
+                        override_budget,
+                        &config,
+                    )
+                    .await,
+                );
+                // Nothing reviewed is never a PASS
+                let exit_code = decision.map_or(
+                    tetrad::cli::commands::EVALUATE_FAILED_EXIT_CODE,
+                    tetrad::cli::commands::decision_exit_code,
+                );
+                if exit_code != 0 {
+                    std::process::exit(exit_code);
+                }
+            }
+        }
+        Commands::Watch {
+            path,
+            language,
+            debounce_ms,
+            max_files,
+            jobs,
+            override_budget,
+        } => {
+            tetrad::cli::commands::watch(
+                &path,
+                &language,
+                std::time::Duration::from_millis(debounce_ms),
+                max_files,
+                jobs,
+                override_budget,
+                &config,
+            )
+            .await?;
+        }
+        Commands::Estimate {
+            code,
+            language,
+            review_type,
+            format,
+        } => {
+            tetrad::cli::commands::estimate(&code, &language, review_type, format, &config).await?;
         }
-        Commands::History { limit } => {
-            tetrad::cli::commands::history(limit, &config).await?;
+        Commands::History { executors, limit } => {
+            tetrad::cli::commands::history(executors, limit, &config).await?;
         }
-        Commands::Export { output } => {
-            tetrad::cli::commands::export_patterns(&output, &config).await?;
+        Commands::Stats {
+            flakiness,
+            limit,
+            bucket,
+            since,
+            format,
+        } => {
+            // --since alone shows weekly trends
+            let bucket = bucket.or(since.map(|_| TrendBucket::Week));
+            match bucket {
+                Some(bucket) => {
+                    tetrad::cli::commands::stats_trends(bucket, since, format, &config).await?;
+                }
+                None => tetrad::cli::commands::stats(flakiness, limit, &config).await?,
+            }
         }
-        Commands::Import { input } => {
-            tetrad::cli::commands::import_patterns(&input, &config).await?;
+        Commands::Metrics { format } => {
+            tetrad::cli::commands::metrics(format, &config).await?;
+        }
+        Commands::Maintenance { vacuum } => {
+            tetrad::cli::commands::maintenance(vacuum, &config).await?;
+        }
+        Commands::Export {
+            output,
+            format,
+            language,
+            pattern_type,
+            min_confidence,
+            sign_key,
+        } => {
+            let options = tetrad::reasoning::ExportOptions {
+                format: format.export_format(),
+                language,
+                pattern_type: pattern_type.map(|kind| kind.pattern_type()),
+                min_confidence,
+                signing_key: sign_key
+                    .as_deref()
+                    .map(tetrad::reasoning::load_signing_key)
+                    .transpose()?,
+            };
+            tetrad::cli::commands::export_patterns(output.as_deref(), &options, &config).await?;
+        }
+        Commands::Import {
+            input,
+            sha256,
+            require_signature,
+            trusted_keys,
+        } => {
+            tetrad::cli::commands::import_patterns(
+                &input,
+                sha256.as_deref(),
+                require_signature,
+                trusted_keys.as_deref(),
+                &config,
+            )
+            .await?;
+        }
+        Commands::Keygen { force } => {
+            tetrad::cli::commands::keygen(force, &config)?;
+        }
+        Commands::Cache { action } => {
+            tetrad::cli::commands::cache(action, &config).await?;
+        }
+        Commands::Patterns { action } => {
+            tetrad::cli::commands::patterns(action, &config).await?;
+        }
+        Commands::Trust { action } => {
+            tetrad::cli::commands::trust(action, &config_path).await?;
+        }
+        Commands::Hook { action } => {
+            tetrad::cli::commands::hook(action)?;
         }
     }
 
     Ok(())
 }
+
+/// Unwraps the outcome of `tetrad evaluate`; an error exits with
+/// [`EVALUATE_FAILED_EXIT_CODE`](tetrad::cli::commands::EVALUATE_FAILED_EXIT_CODE)
+/// so scripts never mistake it for a decision.
+fn or_evaluate_failed<T>(outcome: TetradResult<T>) -> T {
+    outcome.unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(tetrad::cli::commands::EVALUATE_FAILED_EXIT_CODE);
+    })
+}
@@ -0,0 +1,488 @@
+//! API HTTP de avaliação para CI (`tetrad serve --api`).
+//!
+//! Em vez de instalar as três CLIs em cada job, o CI envia o código (ou um
+//! diff) para uma instância do Tetrad com `POST /api/evaluate`. O corpo tem
+//! o formato dos parâmetros de `tetrad_review_code` ou, com o campo `diff`,
+//! de `tetrad_review_diff`, e passa pelo mesmo pipeline do servidor MCP.
+//!
+//! Campos extras do corpo:
+//! - `metadata`: qualquer JSON, devolvido junto com o resultado
+//! - `callback_url`: URL `http://` ou `https://` que recebe o resultado via
+//!   POST; a resposta é `202 Accepted` com um `delivery_id` em vez de
+//!   esperar a avaliação
+//!
+//! Toda requisição passa, nesta ordem, pelo limite por endereço de
+//! `rate_limit_per_minute`, pelo token `Authorization: Bearer` e pelo
+//! limite de `max_body_bytes`. Cada conexão atende uma requisição.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Map, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::types::config::ApiConfig;
+use crate::{TetradError, TetradResult};
+
+use super::protocol::{ToolContent, ToolResult};
+use super::tools::ToolHandler;
+
+/// Caminho do endpoint de avaliação.
+pub const EVALUATE_PATH: &str = "/api/evaluate";
+
+/// Maior cabeçalho HTTP aceito (linha de requisição + headers).
+const MAX_HEAD_BYTES: u64 = 16 * 1024;
+
+/// Prazo para o cliente enviar o cabeçalho e, depois, o corpo.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Quanto esperar, após responder, pelo resto do que o cliente enviou.
+const LINGER_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Janela do limite de requisições.
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Servidor da API de avaliação.
+pub struct ApiServer {
+    tools: Arc<ToolHandler>,
+    config: ApiConfig,
+    limiter: Mutex<RateLimiter>,
+}
+
+impl ApiServer {
+    /// Cria o servidor sobre as ferramentas dadas.
+    ///
+    /// Falha sem token: a API nunca roda aberta.
+    pub fn new(tools: ToolHandler, config: ApiConfig) -> TetradResult<Self> {
+        if config.bearer_token.trim().is_empty() {
+            return Err(TetradError::config(
+                "mcp.api.bearer_token is required to serve the evaluation API",
+            ));
+        }
+
+        let limiter = RateLimiter::new(config.rate_limit_per_minute);
+        Ok(Self {
+            tools: Arc::new(tools),
+            config,
+            limiter: Mutex::new(limiter),
+        })
+    }
+
+    /// Escuta em `mcp.api.bind` até o processo terminar.
+    pub async fn serve(self) -> TetradResult<()> {
+        let listener = TcpListener::bind(&self.config.bind).await?;
+        tracing::info!(addr = %listener.local_addr()?, "Evaluation API listening");
+        self.run(listener).await
+    }
+
+    /// Atende conexões do `listener` dado, cada uma numa tarefa própria.
+    pub async fn run(self, listener: TcpListener) -> TetradResult<()> {
+        let server = Arc::new(self);
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to accept API connection");
+                    continue;
+                }
+            };
+            tokio::spawn(Arc::clone(&server).handle_connection(stream, peer));
+        }
+    }
+
+    /// Lê uma requisição, responde e fecha a conexão.
+    async fn handle_connection(self: Arc<Self>, stream: TcpStream, peer: SocketAddr) {
+        let mut reader = BufReader::new(stream);
+
+        let response = self.respond(&mut reader, peer).await;
+
+        tracing::info!(
+            peer = %peer,
+            status = response.status,
+            "API request"
+        );
+        if let Err(e) = response.write_to(reader.get_mut()).await {
+            tracing::debug!(peer = %peer, error = %e, "Failed to write API response");
+            return;
+        }
+
+        // Descarta o corpo ainda não lido (ex.: após um 413), senão fechar o
+        // socket com dados pendentes envia RST e o cliente perde a resposta.
+        let limit = self.config.max_body_bytes as u64 + MAX_HEAD_BYTES;
+        let mut rest = (&mut reader).take(limit);
+        let mut sink = tokio::io::sink();
+        let _ = tokio::time::timeout(LINGER_TIMEOUT, tokio::io::copy(&mut rest, &mut sink)).await;
+    }
+
+    /// Aplica os limites, o token e a rota, e processa a avaliação.
+    async fn respond(&self, reader: &mut BufReader<TcpStream>, peer: SocketAddr) -> ApiResponse {
+        let head = match tokio::time::timeout(READ_TIMEOUT, read_head(reader)).await {
+            Ok(Ok(head)) => head,
+            Ok(Err(response)) => return response,
+            Err(_) => return ApiResponse::error(408, "request not received in time"),
+        };
+
+        let retry_after = self
+            .limiter
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .check(peer.ip(), Instant::now());
+        if let Some(retry_after) = retry_after {
+            return ApiResponse::error(429, "rate limit exceeded")
+                .with_header("Retry-After", retry_after.as_secs().max(1).to_string());
+        }
+
+        if head.path != EVALUATE_PATH {
+            return ApiResponse::error(404, "not found");
+        }
+        if head.method != "POST" {
+            return ApiResponse::error(405, "use POST").with_header("Allow", "POST".to_string());
+        }
+
+        let authorized = head
+            .header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.trim(), self.config.bearer_token.trim()));
+        if !authorized {
+            return ApiResponse::error(401, "missing or invalid bearer token")
+                .with_header("WWW-Authenticate", "Bearer".to_string());
+        }
+
+        let Some(length) = head.header("content-length") else {
+            return ApiResponse::error(411, "Content-Length is required");
+        };
+        let Ok(length) = length.parse::<usize>() else {
+            return ApiResponse::error(400, "invalid Content-Length");
+        };
+        if length > self.config.max_body_bytes {
+            return ApiResponse::error(
+                413,
+                format!("body exceeds {} bytes", self.config.max_body_bytes),
+            );
+        }
+
+        let mut body = vec![0u8; length];
+        match tokio::time::timeout(READ_TIMEOUT, reader.read_exact(&mut body)).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(_)) => return ApiResponse::error(400, "body shorter than Content-Length"),
+            Err(_) => return ApiResponse::error(408, "request not received in time"),
+        }
+
+        match serde_json::from_slice::<Value>(&body) {
+            Ok(Value::Object(payload)) => self.evaluate(payload).await,
+            Ok(_) => ApiResponse::error(400, "body must be a JSON object"),
+            Err(e) => ApiResponse::error(400, format!("invalid JSON: {}", e)),
+        }
+    }
+
+    /// Roda a avaliação agora ou, com `callback_url`, em segundo plano.
+    async fn evaluate(&self, mut payload: Map<String, Value>) -> ApiResponse {
+        let metadata = payload.remove("metadata");
+        let callback_url = match payload.remove("callback_url") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(url)) if crate::reasoning::is_http_url(&url) => Some(url),
+            Some(_) => {
+                return ApiResponse::error(400, "callback_url must be an http:// or https:// URL")
+            }
+        };
+
+        let Some(callback_url) = callback_url else {
+            let (status, body) = run_evaluation(&self.tools, payload, metadata).await;
+            return ApiResponse::new(status, body);
+        };
+
+        let delivery_id = uuid::Uuid::new_v4().to_string();
+        let tools = Arc::clone(&self.tools);
+        let timeout = Duration::from_secs(self.config.callback_timeout_secs.max(1));
+        let id = delivery_id.clone();
+        let url = callback_url.clone();
+        tokio::spawn(async move {
+            let (_, result) = run_evaluation(&tools, payload, metadata).await;
+            let body = json!({ "delivery_id": id, "result": result });
+            deliver_callback(url, body, timeout).await;
+        });
+
+        ApiResponse::new(
+            202,
+            json!({
+                "status": "accepted",
+                "delivery_id": delivery_id,
+                "callback_url": callback_url,
+            }),
+        )
+    }
+}
+
+/// Avalia o payload como `tetrad_review_diff` (com `diff`) ou
+/// `tetrad_review_code`, retornando o status HTTP e o corpo.
+async fn run_evaluation(
+    tools: &ToolHandler,
+    payload: Map<String, Value>,
+    metadata: Option<Value>,
+) -> (u16, Value) {
+    let tool = if payload.contains_key("diff") {
+        "tetrad_review_diff"
+    } else {
+        "tetrad_review_code"
+    };
+
+    let result = tools.handle_tool_call(tool, Value::Object(payload)).await;
+    let (status, mut body) = tool_response(&result);
+    if let (Some(metadata), Value::Object(body)) = (metadata, &mut body) {
+        body.insert("metadata".to_string(), metadata);
+    }
+    (status, body)
+}
+
+/// Converte o resultado da ferramenta em status e corpo JSON.
+///
+/// Erros com `retryable` (servidor ocupado) viram 503; os demais
+/// viram 422.
+fn tool_response(result: &ToolResult) -> (u16, Value) {
+    let text = match result.content.first() {
+        Some(ToolContent::Text { text }) => text.as_str(),
+        None => "",
+    };
+    let parsed = serde_json::from_str::<Value>(text).ok();
+
+    if !result.is_error {
+        return (200, parsed.unwrap_or_else(|| json!({ "result": text })));
+    }
+    match parsed {
+        Some(body) if body["retryable"] == true => (503, body),
+        Some(body) if body.is_object() => (422, body),
+        _ => (422, json!({ "error": text })),
+    }
+}
+
+/// Envia o resultado para o callback, repetindo uma vez em caso de falha.
+async fn deliver_callback(url: String, body: Value, timeout: Duration) {
+    let bytes = body.to_string().into_bytes();
+    for attempt in 1..=2 {
+        match crate::reasoning::post_json(&url, bytes.clone(), None, timeout).await {
+            Ok(status) if (200..300).contains(&status) => return,
+            Ok(status) => {
+                tracing::warn!(url = %url, status, attempt, "Callback rejected the result")
+            }
+            Err(e) => {
+                tracing::warn!(url = %url, error = %e, attempt, "Callback delivery failed")
+            }
+        }
+    }
+    tracing::error!("Evaluation result could not be delivered to the callback");
+}
+
+/// Compara sem sair no primeiro byte diferente, para não vazar o token
+/// pelo tempo de resposta.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Linha de requisição e headers.
+struct RequestHead {
+    method: String,
+    path: String,
+    /// Nomes em minúsculas.
+    headers: Vec<(String, String)>,
+}
+
+impl RequestHead {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Lê a linha de requisição e os headers, até [`MAX_HEAD_BYTES`].
+async fn read_head(reader: &mut BufReader<TcpStream>) -> Result<RequestHead, ApiResponse> {
+    let mut limited = (&mut *reader).take(MAX_HEAD_BYTES);
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        match limited.read_line(&mut line).await {
+            Ok(0) => return Err(ApiResponse::error(400, "incomplete request head")),
+            Ok(_) if !line.ends_with('\n') => {
+                return Err(ApiResponse::error(431, "request head too large"))
+            }
+            Ok(_) => {}
+            Err(_) => return Err(ApiResponse::error(400, "malformed request head")),
+        }
+
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+
+    let mut request_line = lines
+        .first()
+        .map(|l| l.split_whitespace())
+        .into_iter()
+        .flatten();
+    let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+        return Err(ApiResponse::error(400, "malformed request line"));
+    };
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let method = method.to_string();
+
+    let headers = lines[1..]
+        .iter()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    Ok(RequestHead {
+        method,
+        path,
+        headers,
+    })
+}
+
+/// Resposta HTTP com corpo JSON.
+struct ApiResponse {
+    status: u16,
+    body: Value,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl ApiResponse {
+    fn new(status: u16, body: Value) -> Self {
+        Self {
+            status,
+            body,
+            headers: Vec::new(),
+        }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self::new(status, json!({ "error": message.into() }))
+    }
+
+    fn with_header(mut self, name: &'static str, value: String) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    async fn write_to(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+        let body = self.body.to_string();
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            reason_phrase(self.status),
+            body.len()
+        );
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str("\r\n");
+
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(body.as_bytes()).await?;
+        stream.shutdown().await
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+/// Limite de requisições por endereço numa janela fixa de um minuto.
+struct RateLimiter {
+    limit: u32,
+    windows: HashMap<IpAddr, (Instant, u32)>,
+}
+
+impl RateLimiter {
+    fn new(limit: u32) -> Self {
+        Self {
+            limit,
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Conta uma requisição de `ip`; retorna quanto falta para a janela
+    /// reabrir se o limite já foi atingido.
+    fn check(&mut self, ip: IpAddr, now: Instant) -> Option<Duration> {
+        // Janelas vencidas não limitam mais ninguém
+        self.windows
+            .retain(|_, (start, _)| now.duration_since(*start) < RATE_WINDOW);
+
+        let (start, count) = self.windows.entry(ip).or_insert((now, 0));
+        if *count >= self.limit {
+            return Some(RATE_WINDOW.saturating_sub(now.duration_since(*start)));
+        }
+        *count += 1;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_window() {
+        let mut limiter = RateLimiter::new(2);
+        let ip: IpAddr = "10.0.0.1".parse().unwrap();
+        let other: IpAddr = "10.0.0.2".parse().unwrap();
+        let start = Instant::now();
+
+        assert!(limiter.check(ip, start).is_none());
+        assert!(limiter.check(ip, start).is_none());
+        let retry = limiter.check(ip, start + Duration::from_secs(20)).unwrap();
+        assert_eq!(retry, Duration::from_secs(40));
+
+        // Cada endereço tem a sua janela
+        assert!(limiter.check(other, start).is_none());
+
+        // Passado um minuto a janela reabre
+        assert!(limiter.check(ip, start + RATE_WINDOW).is_none());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("secret-token", "secret-token"));
+        assert!(!constant_time_eq("secret-token", "secret-tokem"));
+        assert!(!constant_time_eq("secret", "secret-token"));
+    }
+
+    #[test]
+    fn test_tool_response_status() {
+        let ok = ToolResult::success_json(&json!({"decision": "PASS"}));
+        assert_eq!(tool_response(&ok), (200, json!({"decision": "PASS"})));
+
+        let busy = ToolResult::error_json(&json!({"error": "server_busy", "retryable": true}));
+        assert_eq!(tool_response(&busy).0, 503);
+
+        let invalid = ToolResult::error("Invalid parameters: missing field `code`");
+        assert_eq!(
+            tool_response(&invalid),
+            (
+                422,
+                json!({"error": "Invalid parameters: missing field `code`"})
+            )
+        );
+    }
+}
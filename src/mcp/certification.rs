@@ -0,0 +1,440 @@
+//! Confirmações e certificados emitidos pelo servidor MCP.
+//!
+//! `tetrad_confirm` registra confirmações e `tetrad_final_check` emite
+//! certificados. Ambos expiram conforme `consensus.confirmation_ttl_hours` e
+//! `consensus.certificate_ttl_days`: uma confirmação do mês passado não
+//! certifica o código de hoje. Entradas expiradas continuam respondendo
+//! "expired" por [`EXPIRED_RETENTION_DAYS`] dias antes de serem removidas.
+//!
+//! Cada certificado guarda o que atestou ([`CertifiedCode`]): o hash do
+//! código, as configurações efetivas e as versões dos executores. Uma
+//! verificação final do mesmo código, com as mesmas configurações e
+//! executores, reaproveita o certificado válido em vez de chamar as CLIs.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::consensus::StrictSettings;
+use crate::types::config::{ConsensusConfig, ConsensusRule, SeverityGate};
+
+/// Dias que uma entrada expirada é mantida antes da limpeza.
+pub const EXPIRED_RETENTION_DAYS: i64 = 7;
+
+/// Confirmação registrada por `tetrad_confirm`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Confirmation {
+    /// Se o cliente concordou com o feedback.
+    pub agreed: bool,
+
+    /// Quando a confirmação foi registrada.
+    pub confirmed_at: DateTime<Utc>,
+
+    /// Quando a confirmação deixa de valer (`None` = nunca).
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Certificado emitido por `tetrad_final_check`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Certificate {
+    /// ID do certificado (`TETRAD-<request_id>`).
+    pub id: String,
+
+    /// Requisição certificada.
+    pub request_id: String,
+
+    /// Quando o certificado foi emitido.
+    pub issued_at: DateTime<Utc>,
+
+    /// Quando o certificado deixa de valer (`None` = nunca).
+    pub expires_at: Option<DateTime<Utc>>,
+
+    /// Score da avaliação certificada.
+    pub score: u8,
+
+    /// Código, configurações e executores certificados.
+    pub certified: CertifiedCode,
+}
+
+/// O que um certificado atesta.
+///
+/// Um certificado só é reaproveitado quando todos os campos coincidem.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CertifiedCode {
+    /// Hash do código e da linguagem (veja [`code_hash`]).
+    pub code_hash: String,
+
+    /// Configurações efetivas da verificação.
+    pub settings: CertifiedSettings,
+
+    /// Executores que podiam votar, com suas versões, em ordem.
+    pub executor_versions: Vec<(String, String)>,
+}
+
+/// Configurações efetivas de uma verificação final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CertifiedSettings {
+    /// Se o modo estrito foi aplicado.
+    pub strict: bool,
+
+    /// Regra de consenso.
+    pub rule: ConsensusRule,
+
+    /// Score mínimo.
+    pub min_score: u8,
+
+    /// Gate de severidade.
+    pub block_on_severity: SeverityGate,
+}
+
+impl CertifiedSettings {
+    /// Configurações de uma verificação com `config` e, se houver, o modo estrito.
+    pub fn new(config: &ConsensusConfig, strict: Option<&StrictSettings>) -> Self {
+        match strict {
+            Some(strict) => Self {
+                strict: true,
+                rule: strict.rule,
+                min_score: strict.min_score,
+                block_on_severity: strict.block_on_severity,
+            },
+            None => Self {
+                strict: false,
+                rule: config.default_rule,
+                min_score: config.min_score,
+                block_on_severity: config.block_on_severity,
+            },
+        }
+    }
+}
+
+/// Hash SHA-256 (hex) do código e da linguagem de uma verificação.
+pub fn code_hash(code: &str, language: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(language.to_lowercase().as_bytes());
+    hasher.update([0]);
+    hasher.update(code.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Situação da confirmação de uma requisição.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfirmationStatus {
+    /// Confirmada e dentro da validade.
+    Confirmed,
+    /// O cliente discordou do feedback.
+    Rejected,
+    /// Confirmada, mas a validade já passou.
+    Expired,
+    /// Nenhuma confirmação registrada.
+    NotFound,
+}
+
+/// Situação de um certificado.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificateStatus {
+    /// Emitido e dentro da validade.
+    Valid,
+    /// Emitido, mas a validade já passou.
+    Expired,
+    /// Nenhum certificado com esse ID.
+    NotFound,
+}
+
+/// Registro de confirmações e certificados com expiração.
+#[derive(Debug, Clone, Default)]
+pub struct Certifications {
+    confirmations: HashMap<String, Confirmation>,
+    certificates: HashMap<String, Certificate>,
+    confirmation_ttl: Option<Duration>,
+    certificate_ttl: Option<Duration>,
+}
+
+impl Certifications {
+    /// Cria o registro com as validades configuradas (0 = nunca expira).
+    pub fn from_config(config: &ConsensusConfig) -> Self {
+        Self {
+            confirmation_ttl: (config.confirmation_ttl_hours > 0)
+                .then(|| Duration::hours(config.confirmation_ttl_hours as i64)),
+            certificate_ttl: (config.certificate_ttl_days > 0)
+                .then(|| Duration::days(config.certificate_ttl_days as i64)),
+            ..Default::default()
+        }
+    }
+
+    /// Registra a confirmação de `request_id`, substituindo a anterior.
+    pub fn confirm(&mut self, request_id: &str, agreed: bool, now: DateTime<Utc>) -> Confirmation {
+        self.sweep(now);
+        let confirmation = Confirmation {
+            agreed,
+            confirmed_at: now,
+            expires_at: self.confirmation_ttl.map(|ttl| now + ttl),
+        };
+        self.confirmations
+            .insert(request_id.to_string(), confirmation.clone());
+        confirmation
+    }
+
+    /// Situação da confirmação de `request_id` em `now`.
+    pub fn confirmation_status(&self, request_id: &str, now: DateTime<Utc>) -> ConfirmationStatus {
+        match self.confirmations.get(request_id) {
+            None => ConfirmationStatus::NotFound,
+            Some(c) if is_expired(c.expires_at, now) => ConfirmationStatus::Expired,
+            Some(c) if !c.agreed => ConfirmationStatus::Rejected,
+            Some(_) => ConfirmationStatus::Confirmed,
+        }
+    }
+
+    /// Emite o certificado de `request_id` para `certified`.
+    pub fn issue(
+        &mut self,
+        request_id: &str,
+        certified: CertifiedCode,
+        score: u8,
+        now: DateTime<Utc>,
+    ) -> Certificate {
+        self.sweep(now);
+        let certificate = Certificate {
+            id: format!("TETRAD-{}", request_id),
+            request_id: request_id.to_string(),
+            issued_at: now,
+            expires_at: self.certificate_ttl.map(|ttl| now + ttl),
+            score,
+            certified,
+        };
+        self.certificates
+            .insert(certificate.id.clone(), certificate.clone());
+        certificate
+    }
+
+    /// Situação do certificado `certificate_id` em `now`.
+    pub fn certificate_status(
+        &self,
+        certificate_id: &str,
+        now: DateTime<Utc>,
+    ) -> CertificateStatus {
+        match self.certificates.get(certificate_id) {
+            None => CertificateStatus::NotFound,
+            Some(c) if is_expired(c.expires_at, now) => CertificateStatus::Expired,
+            Some(_) => CertificateStatus::Valid,
+        }
+    }
+
+    /// Certificado válido em `now` para exatamente `certified`, o mais
+    /// recente se houver vários.
+    pub fn find_certified(
+        &self,
+        certified: &CertifiedCode,
+        now: DateTime<Utc>,
+    ) -> Option<&Certificate> {
+        self.certificates
+            .values()
+            .filter(|c| !is_expired(c.expires_at, now) && &c.certified == certified)
+            .max_by_key(|c| c.issued_at)
+    }
+
+    /// Remove as entradas expiradas há mais de [`EXPIRED_RETENTION_DAYS`] dias.
+    ///
+    /// Retorna quantas entradas foram removidas.
+    pub fn sweep(&mut self, now: DateTime<Utc>) -> usize {
+        let cutoff = now - Duration::days(EXPIRED_RETENTION_DAYS);
+        let before = self.confirmations.len() + self.certificates.len();
+        self.confirmations
+            .retain(|_, c| !is_expired(c.expires_at, cutoff));
+        self.certificates
+            .retain(|_, c| !is_expired(c.expires_at, cutoff));
+        before - self.confirmations.len() - self.certificates.len()
+    }
+}
+
+/// Uma validade vence no instante `expires_at`, inclusive.
+fn is_expired(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    expires_at.is_some_and(|expires_at| now >= expires_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn certifications() -> Certifications {
+        Certifications::from_config(&ConsensusConfig {
+            confirmation_ttl_hours: 24,
+            certificate_ttl_days: 30,
+            ..Default::default()
+        })
+    }
+
+    fn certified(code: &str) -> CertifiedCode {
+        CertifiedCode {
+            code_hash: code_hash(code, "rust"),
+            settings: CertifiedSettings::new(&ConsensusConfig::default(), None),
+            executor_versions: vec![("Codex".to_string(), "1.0.0".to_string())],
+        }
+    }
+
+    fn at(hours: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_700_000_000, 0).unwrap() + Duration::hours(hours)
+    }
+
+    #[test]
+    fn test_confirmation_expires_at_boundary() {
+        let mut certs = certifications();
+        let confirmation = certs.confirm("req-1", true, at(0));
+        assert_eq!(confirmation.expires_at, Some(at(24)));
+
+        assert_eq!(
+            certs.confirmation_status("req-1", at(24) - Duration::seconds(1)),
+            ConfirmationStatus::Confirmed
+        );
+        assert_eq!(
+            certs.confirmation_status("req-1", at(24)),
+            ConfirmationStatus::Expired
+        );
+        assert_eq!(
+            certs.confirmation_status("req-2", at(0)),
+            ConfirmationStatus::NotFound
+        );
+    }
+
+    #[test]
+    fn test_rejected_confirmation() {
+        let mut certs = certifications();
+        certs.confirm("req-1", false, at(0));
+        assert_eq!(
+            certs.confirmation_status("req-1", at(1)),
+            ConfirmationStatus::Rejected
+        );
+
+        // Expirada vence rejeitada: a confirmação não vale mais de todo jeito
+        assert_eq!(
+            certs.confirmation_status("req-1", at(24)),
+            ConfirmationStatus::Expired
+        );
+    }
+
+    #[test]
+    fn test_certificate_expires_at_boundary() {
+        let mut certs = certifications();
+        let certificate = certs.issue("req-1", certified("fn a() {}"), 90, at(0));
+        assert_eq!(certificate.id, "TETRAD-req-1");
+        assert_eq!(certificate.expires_at, Some(at(30 * 24)));
+
+        assert_eq!(
+            certs.certificate_status("TETRAD-req-1", at(30 * 24) - Duration::seconds(1)),
+            CertificateStatus::Valid
+        );
+        assert_eq!(
+            certs.certificate_status("TETRAD-req-1", at(30 * 24)),
+            CertificateStatus::Expired
+        );
+        assert_eq!(
+            certs.certificate_status("TETRAD-req-2", at(0)),
+            CertificateStatus::NotFound
+        );
+    }
+
+    #[test]
+    fn test_zero_ttl_never_expires() {
+        let mut certs = Certifications::from_config(&ConsensusConfig {
+            confirmation_ttl_hours: 0,
+            certificate_ttl_days: 0,
+            ..Default::default()
+        });
+        certs.confirm("req-1", true, at(0));
+        certs.issue("req-1", certified("fn a() {}"), 90, at(0));
+
+        let much_later = at(24 * 365 * 10);
+        assert_eq!(
+            certs.confirmation_status("req-1", much_later),
+            ConfirmationStatus::Confirmed
+        );
+        assert_eq!(
+            certs.certificate_status("TETRAD-req-1", much_later),
+            CertificateStatus::Valid
+        );
+        assert_eq!(certs.sweep(much_later), 0);
+    }
+
+    #[test]
+    fn test_sweep_removes_long_expired_entries() {
+        let mut certs = certifications();
+        certs.confirm("old", true, at(0));
+        certs.issue("old", certified("fn a() {}"), 90, at(0));
+
+        // Recém-expirada continua respondendo "expired"
+        let removal = at(24) + Duration::days(EXPIRED_RETENTION_DAYS);
+        let before = removal - Duration::seconds(1);
+        assert_eq!(certs.sweep(before), 0);
+        assert_eq!(
+            certs.confirmation_status("old", before),
+            ConfirmationStatus::Expired
+        );
+
+        // Depois da retenção, some
+        assert_eq!(certs.sweep(removal), 1);
+        assert_eq!(
+            certs.confirmation_status("old", removal),
+            ConfirmationStatus::NotFound
+        );
+
+        // O certificado vale 30 dias e ainda não foi removido
+        assert_eq!(
+            certs.certificate_status("TETRAD-old", removal),
+            CertificateStatus::Valid
+        );
+
+        // Novas confirmações disparam a limpeza
+        let much_later = at(24 * 60);
+        certs.confirm("new", true, much_later);
+        assert_eq!(
+            certs.certificate_status("TETRAD-old", much_later),
+            CertificateStatus::NotFound
+        );
+    }
+
+    #[test]
+    fn test_find_certified_matches_code_settings_and_versions() {
+        let mut certs = certifications();
+        certs.issue("req-1", certified("fn a() {}"), 90, at(0));
+
+        let found = certs
+            .find_certified(&certified("fn a() {}"), at(1))
+            .unwrap();
+        assert_eq!(found.id, "TETRAD-req-1");
+        assert_eq!(found.score, 90);
+
+        // Outro código, outra linguagem, outro modo ou outra versão: nada
+        assert!(certs
+            .find_certified(&certified("fn b() {}"), at(1))
+            .is_none());
+        let mut other_language = certified("fn a() {}");
+        other_language.code_hash = code_hash("fn a() {}", "python");
+        assert!(certs.find_certified(&other_language, at(1)).is_none());
+        let mut strict = certified("fn a() {}");
+        strict.settings.strict = true;
+        assert!(certs.find_certified(&strict, at(1)).is_none());
+        let mut upgraded = certified("fn a() {}");
+        upgraded.executor_versions[0].1 = "1.1.0".to_string();
+        assert!(certs.find_certified(&upgraded, at(1)).is_none());
+
+        // Expirado não vale
+        assert!(certs
+            .find_certified(&certified("fn a() {}"), at(30 * 24))
+            .is_none());
+    }
+
+    #[test]
+    fn test_find_certified_prefers_newest() {
+        let mut certs = certifications();
+        certs.issue("req-1", certified("fn a() {}"), 80, at(0));
+        certs.issue("req-2", certified("fn a() {}"), 95, at(2));
+
+        let found = certs
+            .find_certified(&certified("fn a() {}"), at(3))
+            .unwrap();
+        assert_eq!(found.id, "TETRAD-req-2");
+    }
+}
@@ -0,0 +1,617 @@
+//! Avaliação em partes de código maior que `general.max_code_bytes`.
+//!
+//! O código é dividido em trechos que terminam em limites de funções e
+//! classes, detectados por um divisor simples que acompanha chaves e
+//! indentação. Cada trecho repete as últimas linhas do anterior como
+//! contexto e passa pelo pipeline normal (cache, hooks, executores e
+//! consenso); os resultados são então combinados em um só com
+//! [`merge_results`].
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use crate::types::responses::{ChunkOutcome, Decision, EvaluationResult, ModelVote, Vote};
+
+/// Linhas do fim de um trecho repetidas no início do seguinte.
+pub const CHUNK_OVERLAP_LINES: usize = 8;
+
+/// Máximo de trechos de uma avaliação; código que precisaria de mais é recusado.
+pub const MAX_CHUNKS: usize = 16;
+
+/// Fração do limite reservada às linhas repetidas do trecho anterior.
+const OVERLAP_SHARE: usize = 4;
+
+/// Um trecho do código avaliado.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// Número do trecho, a partir de 1.
+    pub number: usize,
+
+    /// Primeira linha do trecho no código original, a partir de 1 (inclui
+    /// as linhas repetidas do trecho anterior).
+    pub start_line: usize,
+
+    /// Última linha do trecho no código original.
+    pub end_line: usize,
+
+    /// Linhas do início do trecho repetidas do trecho anterior.
+    pub overlap_lines: usize,
+
+    /// Código do trecho.
+    pub code: String,
+}
+
+/// Divide `code` em trechos de até `max_bytes` bytes.
+///
+/// Os trechos agrupam unidades de topo (funções, classes, blocos separados
+/// por linha em branco) enquanto couberem em três quartos do limite; o resto
+/// fica para as linhas repetidas do trecho anterior. Uma unidade maior que
+/// isso é cortada entre linhas, e uma linha maior que o limite forma um
+/// trecho sozinha. Como os cortes dependem só das unidades, editar uma
+/// função muda apenas o trecho dela (e o seguinte, pelas linhas repetidas),
+/// a menos que ela cresça a ponto de mudar o agrupamento.
+pub fn split_code(code: &str, max_bytes: usize) -> Vec<Chunk> {
+    let lines: Vec<&str> = code.split_inclusive('\n').collect();
+    let body_budget = (max_bytes - max_bytes / OVERLAP_SHARE).max(1);
+    let overlap_budget = max_bytes / OVERLAP_SHARE;
+
+    // Agrupa as unidades de topo em corpos de trecho
+    let mut bodies: Vec<Range<usize>> = Vec::new();
+    let mut current: Option<(Range<usize>, usize)> = None;
+    for unit in units(&lines) {
+        let size = byte_len(&lines[unit.clone()]);
+        if size > body_budget {
+            bodies.extend(current.take().map(|(range, _)| range));
+            bodies.extend(split_lines(&lines, unit, body_budget));
+            continue;
+        }
+        current = match current.take() {
+            Some((range, used)) if used + size <= body_budget => {
+                Some((range.start..unit.end, used + size))
+            }
+            Some((range, _)) => {
+                bodies.push(range);
+                Some((unit, size))
+            }
+            None => Some((unit, size)),
+        };
+    }
+    bodies.extend(current.map(|(range, _)| range));
+
+    bodies
+        .into_iter()
+        .enumerate()
+        .map(|(i, body)| {
+            // Linhas do trecho anterior repetidas como contexto, dentro da reserva
+            let mut start = body.start.saturating_sub(CHUNK_OVERLAP_LINES);
+            while start < body.start && byte_len(&lines[start..body.start]) > overlap_budget {
+                start += 1;
+            }
+            Chunk {
+                number: i + 1,
+                start_line: start + 1,
+                end_line: body.end,
+                overlap_lines: body.start - start,
+                code: lines[start..body.end].concat(),
+            }
+        })
+        .collect()
+}
+
+/// Combina os resultados dos trechos em um único resultado.
+///
+/// A decisão é a mais severa entre os trechos (Block domina), o score é a
+/// média dos scores ponderada pelo tamanho de cada trecho e os findings são
+/// concatenados com o número do trecho e as linhas convertidas para o código
+/// original. Os votos de um mesmo executor são combinados da mesma forma, e
+/// o feedback resume o resultado de cada trecho.
+pub fn merge_results(
+    request_id: &str,
+    chunks: &[Chunk],
+    results: Vec<EvaluationResult>,
+) -> EvaluationResult {
+    let weights: Vec<usize> = chunks.iter().map(|c| c.code.len().max(1)).collect();
+    let score = weighted_score(results.iter().map(|r| r.score), &weights);
+    let outcomes: Vec<ChunkOutcome> = chunks
+        .iter()
+        .zip(&results)
+        .map(|(chunk, result)| ChunkOutcome {
+            chunk: chunk.number,
+            start_line: chunk.start_line,
+            end_line: chunk.end_line,
+            decision: result.decision,
+            score: result.score,
+            request_id: result.request_id.clone(),
+        })
+        .collect();
+
+    let mut merged = EvaluationResult::success(request_id, score, summary(&outcomes));
+    merged.decision = results
+        .iter()
+        .map(|r| r.decision)
+        .max_by_key(|&d| decision_rank(d))
+        .unwrap_or(Decision::Pass);
+    merged.consensus_achieved = results.iter().all(|r| r.consensus_achieved);
+    merged.confidence = results
+        .iter()
+        .map(|r| r.confidence)
+        .fold(None, |min: Option<f64>, c| {
+            Some(min.map_or(c, |m| m.min(c)))
+        })
+        .unwrap_or(0.0);
+    merged.low_confidence = results.iter().any(|r| r.low_confidence);
+    merged.effective_rule = results
+        .first()
+        .and_then(|first| first.effective_rule.clone())
+        .filter(|rule| {
+            results
+                .iter()
+                .all(|r| r.effective_rule.as_ref() == Some(rule))
+        });
+    merged.strictness = results.iter().find_map(|r| r.strictness.clone());
+    merged.executor_selection = results.iter().find_map(|r| r.executor_selection.clone());
+    merged.flaky_with = results.iter().find_map(|r| r.flaky_with.clone());
+    merged.from_cache = results.iter().all(|r| r.from_cache);
+    // A explicação vem do primeiro trecho com a decisão final
+    merged.explanation = results
+        .iter()
+        .find(|r| r.decision == merged.decision)
+        .and_then(|r| r.explanation.clone());
+    for result in &results {
+        for pattern in &result.matched_patterns {
+            if !merged.matched_patterns.iter().any(|p| p.id == pattern.id) {
+                merged.matched_patterns.push(pattern.clone());
+            }
+        }
+    }
+    if results.iter().all(|r| r.skip_reason.is_some()) {
+        merged.skip_reason = results.iter().find_map(|r| r.skip_reason.clone());
+    }
+
+    let mut votes: Vec<(String, Vec<(&ModelVote, &Chunk)>)> = Vec::new();
+    for (chunk, result) in chunks.iter().zip(&results) {
+        let offset = (chunk.start_line - 1) as u32;
+        for finding in &result.findings {
+            let mut finding = finding.clone();
+            finding.chunk = Some(chunk.number);
+            if let Some(lines) = &mut finding.lines {
+                lines.iter_mut().for_each(|line| *line += offset);
+            }
+            for fix in finding.fix.iter_mut().chain(&mut finding.alternative_fixes) {
+                fix.start_line += offset;
+                fix.end_line += offset;
+            }
+            merged.findings.push(finding);
+        }
+
+        let mut names: Vec<&String> = result.votes.keys().collect();
+        names.sort();
+        for name in names {
+            let vote = (&result.votes[name], chunk);
+            match votes.iter_mut().find(|(n, _)| n == name) {
+                Some((_, list)) => list.push(vote),
+                None => votes.push((name.clone(), vec![vote])),
+            }
+        }
+
+        merged.budget_exceeded |= result.budget_exceeded;
+        merged.degraded |= result.degraded;
+        merged.loop_limit_exceeded |= result.loop_limit_exceeded;
+        extend_unique(&mut merged.cut_off_executors, &result.cut_off_executors);
+        extend_unique(&mut merged.degraded_executors, &result.degraded_executors);
+        extend_unique(
+            &mut merged.short_circuited_executors,
+            &result.short_circuited_executors,
+        );
+        for (name, &secs) in &result.executor_timeouts {
+            let timeout = merged.executor_timeouts.entry(name.clone()).or_insert(0);
+            *timeout = (*timeout).max(secs);
+        }
+    }
+    merged.votes = votes
+        .into_iter()
+        .map(|(name, list)| {
+            let vote = merge_votes(&name, &list);
+            (name, vote)
+        })
+        .collect();
+    merged.chunks = outcomes;
+    merged
+}
+
+/// Combina os votos de um executor nos trechos em que ele votou.
+fn merge_votes(name: &str, votes: &[(&ModelVote, &Chunk)]) -> ModelVote {
+    let weights: Vec<usize> = votes.iter().map(|(_, c)| c.code.len().max(1)).collect();
+    let score = weighted_score(votes.iter().map(|(v, _)| v.score), &weights);
+    let worst = votes
+        .iter()
+        .map(|(v, _)| v.vote)
+        .max_by_key(|&v| vote_rank(v))
+        .unwrap_or(Vote::Pass);
+
+    let mut merged = ModelVote::new(name, worst, score);
+    let mut reasoning = Vec::new();
+    let mut lines = HashMap::new();
+    for (vote, chunk) in votes {
+        if !vote.reasoning.is_empty() {
+            reasoning.push(format!("[trecho {}] {}", chunk.number, vote.reasoning));
+        }
+        merged.issues.extend(vote.issues.iter().cloned());
+        merged.suggestions.extend(vote.suggestions.iter().cloned());
+        let offset = (chunk.start_line - 1) as u32;
+        for (issue, issue_lines) in &vote.lines {
+            lines.insert(
+                issue.clone(),
+                issue_lines.iter().map(|line| line + offset).collect(),
+            );
+        }
+        merged.parse_quality = merged.parse_quality.or(vote.parse_quality);
+        merged.response_language = merged.response_language.or(vote.response_language);
+    }
+    merged.reasoning = reasoning.join("\n");
+    merged.with_lines(lines)
+}
+
+/// Feedback com a decisão e o score de cada trecho.
+fn summary(outcomes: &[ChunkOutcome]) -> String {
+    let mut feedback = format!(
+        "## Avaliação em Partes\n\n\
+         O código passou de `general.max_code_bytes` e foi avaliado em {} trechos. \
+         A decisão é a mais severa entre eles e o score é a média ponderada pelo \
+         tamanho de cada trecho.\n\n\
+         | Trecho | Linhas | Decisão | Score |\n\
+         |--------|--------|---------|-------|\n",
+        outcomes.len()
+    );
+    for outcome in outcomes {
+        feedback.push_str(&format!(
+            "| {} | {}-{} | {} | {} |\n",
+            outcome.chunk, outcome.start_line, outcome.end_line, outcome.decision, outcome.score
+        ));
+    }
+    feedback
+}
+
+/// Início das unidades de topo, como intervalos de linhas.
+///
+/// Uma unidade começa numa linha sem indentação, fora de chaves, colchetes
+/// e parênteses abertos, logo depois de uma linha em branco, de uma linha
+/// indentada (o fim de um bloco em Python) ou de uma linha que fecha um
+/// bloco. Comentários e atributos colados à definição ficam com ela.
+fn units(lines: &[&str]) -> Vec<Range<usize>> {
+    let mut starts = vec![0];
+    let mut depth = 0usize;
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 && depth == 0 && opens_unit(line) && ends_unit(lines[i - 1]) {
+            starts.push(i);
+        }
+        depth = track_depth(line, depth);
+    }
+
+    let mut ends: Vec<usize> = starts.iter().skip(1).copied().collect();
+    ends.push(lines.len());
+    starts
+        .into_iter()
+        .zip(ends)
+        .filter(|(start, end)| start < end)
+        .map(|(start, end)| start..end)
+        .collect()
+}
+
+/// Se a linha pode começar uma unidade de topo.
+fn opens_unit(line: &str) -> bool {
+    !line.trim().is_empty()
+        && !line.starts_with(char::is_whitespace)
+        && !line.starts_with(['}', ')', ']'])
+}
+
+/// Se a linha pode encerrar a unidade anterior.
+fn ends_unit(line: &str) -> bool {
+    line.trim().is_empty()
+        || line.starts_with(char::is_whitespace)
+        || line.starts_with(['}', ')', ']'])
+}
+
+/// Profundidade de chaves, colchetes e parênteses depois de `line`.
+///
+/// Ignora o conteúdo de strings entre aspas duplas, literais de caractere e
+/// comentários de linha (`//` e `#`, exceto atributos `#[...]`). Strings de
+/// várias linhas e comentários de bloco não são tratados.
+fn track_depth(line: &str, mut depth: usize) -> usize {
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '"' => {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+            }
+            // 'x' e '\n'; lifetimes como 'a não são literais
+            '\'' if chars.get(i + 1) == Some(&'\\') => {
+                i += 2;
+                while i < chars.len() && chars[i] != '\'' {
+                    i += 1;
+                }
+            }
+            '\'' if chars.get(i + 2) == Some(&'\'') => i += 2,
+            '/' if chars.get(i + 1) == Some(&'/') => break,
+            '#' if chars.get(i + 1) != Some(&'[') => break,
+            '{' | '(' | '[' => depth += 1,
+            '}' | ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        i += 1;
+    }
+    depth
+}
+
+/// Corta uma unidade grande entre linhas, em partes de até `budget` bytes.
+fn split_lines(lines: &[&str], unit: Range<usize>, budget: usize) -> Vec<Range<usize>> {
+    let mut parts = Vec::new();
+    let mut start = unit.start;
+    let mut used = 0;
+    for i in unit.clone() {
+        let size = lines[i].len();
+        if used > 0 && used + size > budget {
+            parts.push(start..i);
+            start = i;
+            used = 0;
+        }
+        used += size;
+    }
+    parts.push(start..unit.end);
+    parts
+}
+
+/// Tamanho em bytes de um intervalo de linhas.
+fn byte_len(lines: &[&str]) -> usize {
+    lines.iter().map(|line| line.len()).sum()
+}
+
+/// Média dos scores ponderada pelos pesos.
+fn weighted_score(scores: impl Iterator<Item = u8>, weights: &[usize]) -> u8 {
+    let (sum, total) = scores
+        .zip(weights)
+        .fold((0usize, 0usize), |(sum, total), (score, &weight)| {
+            (sum + score as usize * weight, total + weight)
+        });
+    if total == 0 {
+        return 0;
+    }
+    ((sum as f64 / total as f64).round() as u8).min(100)
+}
+
+/// Ordem de severidade das decisões.
+fn decision_rank(decision: Decision) -> u8 {
+    match decision {
+        Decision::Pass => 0,
+        Decision::Revise => 1,
+        Decision::Block => 2,
+    }
+}
+
+/// Ordem de severidade dos votos.
+fn vote_rank(vote: Vote) -> u8 {
+    match vote {
+        Vote::Pass => 0,
+        Vote::Warn => 1,
+        Vote::Fail => 2,
+    }
+}
+
+/// Acrescenta os nomes ainda ausentes, mantendo a ordem.
+fn extend_unique(target: &mut Vec<String>, names: &[String]) {
+    for name in names {
+        if !target.contains(name) {
+            target.push(name.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::responses::{Finding, Severity, SuggestedFix};
+
+    /// Arquivo Rust sintético com `count` funções de ~10 linhas.
+    fn large_file(count: usize) -> String {
+        (0..count)
+            .map(|i| {
+                format!(
+                    "/// Soma os valores da função {i}.\n\
+                     pub fn function_{i}(values: &[u32]) -> u32 {{\n\
+                     \x20   let mut total = 0;\n\
+                     \x20   for value in values {{\n\
+                     \x20       if *value > {i} {{\n\
+                     \x20           total += value;\n\
+                     \x20       }}\n\
+                     \x20   }}\n\
+                     \x20   total\n\
+                     }}\n\n"
+                )
+            })
+            .collect()
+    }
+
+    fn chunk(number: usize, start_line: usize, end_line: usize, bytes: usize) -> Chunk {
+        Chunk {
+            number,
+            start_line,
+            end_line,
+            overlap_lines: 0,
+            code: "x".repeat(bytes),
+        }
+    }
+
+    fn result(decision: Decision, score: u8) -> EvaluationResult {
+        let mut result = EvaluationResult::success("chunk", score, "");
+        result.decision = decision;
+        result
+    }
+
+    #[test]
+    fn test_small_code_is_one_chunk() {
+        let code = large_file(3);
+        let chunks = split_code(&code, 64 * 1024);
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].code, code);
+        assert_eq!(chunks[0].start_line, 1);
+        assert_eq!(chunks[0].end_line, code.lines().count());
+        assert_eq!(chunks[0].overlap_lines, 0);
+    }
+
+    #[test]
+    fn test_chunks_cut_at_function_boundaries() {
+        let code = large_file(500);
+        let lines: Vec<&str> = code.lines().collect();
+        let chunks = split_code(&code, 16 * 1024);
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.code.len() <= 16 * 1024);
+            // O corpo do trecho (sem as linhas repetidas) começa numa definição
+            let first = lines[chunk.start_line - 1 + chunk.overlap_lines];
+            assert!(first.starts_with("/// Soma"), "{}", first);
+            assert!(lines[chunk.end_line - 1].is_empty());
+        }
+
+        // Os corpos cobrem o arquivo inteiro, em ordem e sem buracos
+        for pair in chunks.windows(2) {
+            assert_eq!(
+                pair[1].start_line + pair[1].overlap_lines,
+                pair[0].end_line + 1
+            );
+            assert_eq!(pair[1].overlap_lines, CHUNK_OVERLAP_LINES);
+        }
+        assert_eq!(chunks.last().unwrap().end_line, lines.len());
+    }
+
+    #[test]
+    fn test_editing_one_function_keeps_other_chunks() {
+        let code = large_file(500);
+        let edited = code.replace("if *value > 250 {", "if *value >= 250 {");
+        let before = split_code(&code, 16 * 1024);
+        let after = split_code(&edited, 16 * 1024);
+
+        assert_eq!(before.len(), after.len());
+        let changed: Vec<usize> = before
+            .iter()
+            .zip(&after)
+            .filter(|(a, b)| a.code != b.code)
+            .map(|(a, _)| a.number)
+            .collect();
+        assert!(!changed.is_empty());
+        assert!(changed.len() <= 2, "{:?}", changed);
+    }
+
+    #[test]
+    fn test_braces_in_strings_and_comments_are_ignored() {
+        let code = "fn a() {\n    let s = \"}\";\n    let c = '}';\n    // }\n}\n\nfn b() {}\n";
+        let lines: Vec<&str> = code.split_inclusive('\n').collect();
+
+        assert_eq!(units(&lines), vec![0..6, 6..7]);
+    }
+
+    #[test]
+    fn test_python_units_follow_indentation() {
+        let code =
+            "import os\n\n@cached\ndef a():\n    return {\n'k': 1\n    }\ndef b():\n    pass\n";
+        let lines: Vec<&str> = code.split_inclusive('\n').collect();
+
+        // O decorator fica com a função; a linha sem indentação dentro do dict não corta
+        assert_eq!(units(&lines), vec![0..2, 2..7, 7..9]);
+    }
+
+    #[test]
+    fn test_oversized_unit_is_cut_between_lines() {
+        let body: String = (0..400).map(|i| format!("    let v{i} = {i};\n")).collect();
+        let code = format!("fn huge() {{\n{}}}\n", body);
+        let chunks = split_code(&code, 2048);
+
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.code.len() <= 2048));
+        assert_eq!(chunks.last().unwrap().end_line, code.lines().count());
+    }
+
+    #[test]
+    fn test_merge_block_dominates_and_score_is_weighted() {
+        let chunks = vec![chunk(1, 1, 100, 3000), chunk(2, 95, 120, 1000)];
+        let merged = merge_results(
+            "req",
+            &chunks,
+            vec![result(Decision::Pass, 90), result(Decision::Block, 30)],
+        );
+
+        assert_eq!(merged.request_id, "req");
+        assert_eq!(merged.decision, Decision::Block);
+        // (90 * 3000 + 30 * 1000) / 4000
+        assert_eq!(merged.score, 75);
+        assert_eq!(merged.chunks.len(), 2);
+        assert_eq!(merged.chunks[1].decision, Decision::Block);
+        assert!(merged.feedback.contains("| 2 | 95-120 | BLOCK | 30 |"));
+    }
+
+    #[test]
+    fn test_merge_revise_over_pass_and_consensus_needs_every_chunk() {
+        let chunks = vec![chunk(1, 1, 10, 100), chunk(2, 11, 20, 100)];
+        let mut revise = result(Decision::Revise, 70);
+        revise.consensus_achieved = false;
+        let merged = merge_results("req", &chunks, vec![result(Decision::Pass, 90), revise]);
+
+        assert_eq!(merged.decision, Decision::Revise);
+        assert!(!merged.consensus_achieved);
+        assert_eq!(merged.score, 80);
+    }
+
+    #[test]
+    fn test_merge_annotates_findings_with_chunk_and_file_lines() {
+        let chunks = vec![chunk(1, 1, 50, 100), chunk(2, 43, 90, 100)];
+        let mut first = result(Decision::Revise, 70);
+        first.findings =
+            vec![Finding::new(Severity::Warning, "style", "long line").with_lines(vec![3])];
+        let mut second = result(Decision::Block, 20);
+        let mut finding =
+            Finding::new(Severity::Critical, "security", "sql injection").with_lines(vec![10, 11]);
+        finding.fix = Some(SuggestedFix::new(10, 11, "query(&[id])"));
+        second.findings = vec![finding];
+
+        let merged = merge_results("req", &chunks, vec![first, second]);
+
+        assert_eq!(merged.findings.len(), 2);
+        assert_eq!(merged.findings[0].chunk, Some(1));
+        assert_eq!(merged.findings[0].lines, Some(vec![3]));
+        assert_eq!(merged.findings[1].chunk, Some(2));
+        assert_eq!(merged.findings[1].lines, Some(vec![52, 53]));
+        let fix = merged.findings[1].fix.as_ref().unwrap();
+        assert_eq!((fix.start_line, fix.end_line), (52, 53));
+    }
+
+    #[test]
+    fn test_merge_combines_votes_per_executor() {
+        let chunks = vec![chunk(1, 1, 10, 100), chunk(2, 11, 20, 300)];
+        let mut first = result(Decision::Pass, 90);
+        first.votes.insert(
+            "Codex".to_string(),
+            ModelVote::new("Codex", Vote::Pass, 90).with_issues(vec!["a".to_string()]),
+        );
+        let mut second = result(Decision::Revise, 60);
+        second.votes.insert(
+            "Codex".to_string(),
+            ModelVote::new("Codex", Vote::Warn, 50).with_issues(vec!["b".to_string()]),
+        );
+        second.degraded = true;
+        second.degraded_executors = vec!["Gemini".to_string()];
+
+        let merged = merge_results("req", &chunks, vec![first, second]);
+
+        let codex = &merged.votes["Codex"];
+        assert_eq!(codex.vote, Vote::Warn);
+        assert_eq!(codex.score, 60);
+        assert_eq!(codex.issues, vec!["a", "b"]);
+        assert!(merged.degraded);
+        assert_eq!(merged.degraded_executors, vec!["Gemini"]);
+    }
+}
@@ -0,0 +1,173 @@
+//! Estimativa de latência e custo de uma revisão, sem chamar os executores.
+//!
+//! Usada por `tetrad_estimate` e `tetrad estimate`. Combina a média móvel de
+//! latência de cada executor (ou uma heurística pelo tamanho do prompt), o
+//! `cost_per_call` configurado, o estado dos circuit breakers e a presença do
+//! resultado no cache.
+
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Caracteres por token na estimativa de tokens do prompt.
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Latência fixa da heurística: inicialização da CLI e ida e volta à API.
+const HEURISTIC_BASE_MS: u64 = 5_000;
+
+/// Latência por token de prompt na heurística.
+const HEURISTIC_MS_PER_TOKEN: u64 = 10;
+
+/// Estima os tokens de um prompt pelo número de caracteres.
+pub fn estimate_tokens(prompt: &str) -> usize {
+    prompt.chars().count().div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Latência estimada de um executor sem histórico, pelo tamanho do prompt.
+pub fn heuristic_latency(prompt_tokens: usize) -> Duration {
+    Duration::from_millis(HEURISTIC_BASE_MS + prompt_tokens as u64 * HEURISTIC_MS_PER_TOKEN)
+}
+
+/// Estimativa para um executor.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExecutorEstimate {
+    /// Nome do executor.
+    pub name: String,
+
+    /// Estado do circuit breaker.
+    pub circuit: String,
+
+    /// Se o executor não seria chamado (desabilitado, circuito aberto ou
+    /// fallback de um primário saudável).
+    pub skipped: bool,
+
+    /// Tokens estimados do prompt.
+    pub prompt_tokens: usize,
+
+    /// Latência esperada, em milissegundos.
+    pub latency_ms: u64,
+
+    /// `measured` (média das últimas avaliações) ou `heuristic`.
+    pub latency_source: &'static str,
+
+    /// Se o voto viria do cache de votos.
+    pub vote_cached: bool,
+
+    /// Custo estimado da chamada (`general.budget.cost_per_call`).
+    pub cost: f64,
+}
+
+impl ExecutorEstimate {
+    /// Se a revisão chamaria a CLI deste executor.
+    fn calls_cli(&self) -> bool {
+        !self.skipped && !self.vote_cached
+    }
+}
+
+/// Estimativa de uma revisão.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReviewEstimate {
+    /// Probabilidade de a revisão vir do cache (1.0 se a chave exata está lá).
+    pub cache_hit_probability: f64,
+
+    /// Latência esperada, em milissegundos: o executor mais lento, já que
+    /// eles rodam em paralelo, limitado por `general.timeout_secs`.
+    pub expected_latency_ms: u64,
+
+    /// Custo esperado: a soma das chamadas que seriam feitas.
+    pub expected_cost: f64,
+
+    /// Tokens estimados somando os prompts de todos os executores chamados.
+    pub prompt_tokens: usize,
+
+    /// Estimativa por executor.
+    pub executors: Vec<ExecutorEstimate>,
+}
+
+impl ReviewEstimate {
+    /// Combina as estimativas dos executores.
+    ///
+    /// `timeout` é o prazo total da avaliação (`None` sem prazo).
+    pub fn new(
+        executors: Vec<ExecutorEstimate>,
+        cache_hit: bool,
+        timeout: Option<Duration>,
+    ) -> Self {
+        let calling = || executors.iter().filter(|e| e.calls_cli());
+
+        let slowest = calling().map(|e| e.latency_ms).max().unwrap_or(0);
+        let slowest = match timeout {
+            Some(timeout) => slowest.min(timeout.as_millis() as u64),
+            None => slowest,
+        };
+        let cost: f64 = calling().map(|e| e.cost).sum();
+        let prompt_tokens = calling().map(|e| e.prompt_tokens).sum();
+
+        let cache_hit_probability = if cache_hit { 1.0 } else { 0.0 };
+        let miss = 1.0 - cache_hit_probability;
+
+        Self {
+            cache_hit_probability,
+            expected_latency_ms: (slowest as f64 * miss).round() as u64,
+            expected_cost: cost * miss,
+            prompt_tokens,
+            executors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn executor(name: &str, latency_ms: u64, cost: f64) -> ExecutorEstimate {
+        ExecutorEstimate {
+            name: name.to_string(),
+            circuit: "circuit closed".to_string(),
+            skipped: false,
+            prompt_tokens: 100,
+            latency_ms,
+            latency_source: "measured",
+            vote_cached: false,
+            cost,
+        }
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcd"), 1);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+
+    #[test]
+    fn test_heuristic_latency_grows_with_prompt() {
+        assert_eq!(heuristic_latency(0), Duration::from_secs(5));
+        assert_eq!(heuristic_latency(1_000), Duration::from_secs(15));
+    }
+
+    #[test]
+    fn test_review_estimate_arithmetic() {
+        let mut skipped = executor("Qwen", 90_000, 0.02);
+        skipped.skipped = true;
+        let mut cached = executor("Gemini", 60_000, 0.02);
+        cached.vote_cached = true;
+        let executors = vec![executor("Codex", 12_000, 0.03), cached, skipped];
+
+        // Só Codex chamaria a CLI
+        let estimate = ReviewEstimate::new(executors.clone(), false, None);
+        assert_eq!(estimate.expected_latency_ms, 12_000);
+        assert!((estimate.expected_cost - 0.03).abs() < 1e-9);
+        assert_eq!(estimate.prompt_tokens, 100);
+
+        // O prazo limita a latência
+        let estimate = ReviewEstimate::new(executors.clone(), false, Some(Duration::from_secs(10)));
+        assert_eq!(estimate.expected_latency_ms, 10_000);
+
+        // Com o resultado no cache, nada é chamado
+        let estimate = ReviewEstimate::new(executors, true, None);
+        assert_eq!(estimate.cache_hit_probability, 1.0);
+        assert_eq!(estimate.expected_latency_ms, 0);
+        assert_eq!(estimate.expected_cost, 0.0);
+    }
+}
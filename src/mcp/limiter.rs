@@ -0,0 +1,170 @@
+//! Limite de avaliações simultâneas.
+//!
+//! Cada avaliação dispara um subprocesso por executor, e um cliente que
+//! revisa vários arquivos em sequência pode saturar a máquina. O limitador
+//! deixa no máximo `general.max_concurrent_evaluations` avaliações rodando;
+//! as demais esperam na fila, por até `general.queue_timeout_secs`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::types::config::GeneralConfig;
+use crate::{TetradError, TetradResult};
+
+/// Limitador de avaliações simultâneas.
+pub struct EvaluationLimiter {
+    /// `None` quando não há limite.
+    semaphore: Option<Semaphore>,
+    max_concurrent: usize,
+    queue_timeout: Option<Duration>,
+    in_flight: AtomicUsize,
+    queued: AtomicUsize,
+}
+
+/// Vaga de uma avaliação em andamento, liberada ao sair de escopo.
+pub struct EvaluationPermit<'a> {
+    _permit: Option<SemaphorePermit<'a>>,
+    _running: Counted<'a>,
+}
+
+/// Estado do limitador, exposto em `tetrad_status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LimiterStatus {
+    /// Avaliações simultâneas permitidas (0 = sem limite).
+    pub max_concurrent: usize,
+
+    /// Avaliações rodando agora.
+    pub in_flight: usize,
+
+    /// Avaliações esperando uma vaga.
+    pub queued: usize,
+
+    /// Espera máxima na fila, em segundos (`None` = sem limite).
+    pub queue_timeout_secs: Option<u64>,
+}
+
+/// Incrementa um contador e o decrementa ao sair de escopo, inclusive quando
+/// a avaliação é cancelada.
+struct Counted<'a>(&'a AtomicUsize);
+
+impl<'a> Counted<'a> {
+    fn new(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for Counted<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl EvaluationLimiter {
+    /// Cria um limitador; `max_concurrent` 0 remove o limite.
+    pub fn new(max_concurrent: usize, queue_timeout: Option<Duration>) -> Self {
+        Self {
+            semaphore: (max_concurrent > 0).then(|| Semaphore::new(max_concurrent)),
+            max_concurrent,
+            queue_timeout,
+            in_flight: AtomicUsize::new(0),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Cria o limitador a partir da seção `[general]`.
+    pub fn from_config(config: &GeneralConfig) -> Self {
+        let timeout = config.queue_timeout_secs;
+        Self::new(
+            config.max_concurrent_evaluations,
+            (timeout > 0).then(|| Duration::from_secs(timeout)),
+        )
+    }
+
+    /// Espera uma vaga para avaliar.
+    ///
+    /// Retorna [`TetradError::ServerBusy`] se a espera passar do tempo máximo
+    /// da fila.
+    pub async fn acquire(&self) -> TetradResult<EvaluationPermit<'_>> {
+        let Some(semaphore) = &self.semaphore else {
+            return Ok(EvaluationPermit {
+                _permit: None,
+                _running: Counted::new(&self.in_flight),
+            });
+        };
+
+        let queued = Counted::new(&self.queued);
+        let permit = match self.queue_timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, semaphore.acquire()).await {
+                Ok(permit) => permit,
+                Err(_) => {
+                    return Err(TetradError::ServerBusy(format!(
+                        "{} evaluations running and {} queued; gave up after {}s, retry later",
+                        self.in_flight.load(Ordering::SeqCst),
+                        self.queued.load(Ordering::SeqCst),
+                        timeout.as_secs()
+                    )))
+                }
+            },
+            None => semaphore.acquire().await,
+        }
+        .map_err(|_| TetradError::other("Evaluation limiter closed"))?;
+        drop(queued);
+
+        Ok(EvaluationPermit {
+            _permit: Some(permit),
+            _running: Counted::new(&self.in_flight),
+        })
+    }
+
+    /// Estado atual do limitador.
+    pub fn status(&self) -> LimiterStatus {
+        LimiterStatus {
+            max_concurrent: self.max_concurrent,
+            in_flight: self.in_flight.load(Ordering::SeqCst),
+            queued: self.queued.load(Ordering::SeqCst),
+            queue_timeout_secs: self.queue_timeout.map(|t| t.as_secs()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_permits_are_counted_and_released() {
+        let limiter = EvaluationLimiter::new(1, None);
+
+        let permit = limiter.acquire().await.unwrap();
+        assert_eq!(limiter.status().in_flight, 1);
+
+        drop(permit);
+        let status = limiter.status();
+        assert_eq!(status.in_flight, 0);
+        assert_eq!(status.queued, 0);
+    }
+
+    #[tokio::test]
+    async fn test_queue_timeout_returns_busy() {
+        let limiter = EvaluationLimiter::new(1, Some(Duration::from_millis(20)));
+        let _permit = limiter.acquire().await.unwrap();
+
+        let err = limiter.acquire().await.err().unwrap();
+        assert!(matches!(err, TetradError::ServerBusy(_)));
+        // A chamada que desistiu sai da fila
+        assert_eq!(limiter.status().queued, 0);
+        assert_eq!(limiter.status().in_flight, 1);
+    }
+
+    #[tokio::test]
+    async fn test_zero_means_unlimited() {
+        let limiter = EvaluationLimiter::new(0, Some(Duration::from_millis(1)));
+        let _a = limiter.acquire().await.unwrap();
+        let _b = limiter.acquire().await.unwrap();
+        assert_eq!(limiter.status().in_flight, 2);
+    }
+}
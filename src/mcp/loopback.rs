@@ -0,0 +1,197 @@
+//! Transporte em memória para rodar o servidor MCP no mesmo processo.
+//!
+//! Usado pelo `tetrad selftest`: o [`LoopbackClient`] fala com o
+//! [`McpServer`](super::McpServer) por canais, com as mensagens serializadas
+//! em JSON exatamente como no transporte stdio, mas sem tocar stdin/stdout.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+
+use crate::types::errors::TetradError;
+use crate::TetradResult;
+
+use super::protocol::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+use super::transport::{NotificationSink, ResponseSink, Transport};
+
+/// Lado do servidor do transporte em memória.
+pub struct LoopbackTransport {
+    incoming: UnboundedReceiver<String>,
+    outgoing: LoopbackSink,
+}
+
+/// Lado do cliente do transporte em memória.
+pub struct LoopbackClient {
+    outgoing: UnboundedSender<String>,
+    incoming: UnboundedReceiver<String>,
+    next_id: i64,
+    notifications: Vec<Value>,
+}
+
+/// Destino de notificações do servidor, compartilhado com as respostas.
+#[derive(Clone)]
+struct LoopbackSink {
+    sender: UnboundedSender<String>,
+}
+
+impl LoopbackSink {
+    fn send(&self, body: String) -> TetradResult<()> {
+        self.sender
+            .send(body)
+            .map_err(|_| TetradError::McpServer("loopback client disconnected".into()))
+    }
+}
+
+impl NotificationSink for LoopbackSink {
+    fn send_notification(&self, notification: &JsonRpcNotification) -> TetradResult<()> {
+        self.send(serde_json::to_string(notification)?)
+    }
+}
+
+impl ResponseSink for LoopbackSink {
+    fn send_response(&self, response: &JsonRpcResponse) -> TetradResult<()> {
+        self.send(serde_json::to_string(response)?)
+    }
+}
+
+impl LoopbackTransport {
+    /// Cria um par servidor/cliente conectado.
+    ///
+    /// Quando o cliente é descartado, o servidor recebe EOF e encerra o loop.
+    pub fn pair() -> (LoopbackTransport, LoopbackClient) {
+        let (to_server, incoming) = unbounded_channel();
+        let (sender, from_server) = unbounded_channel();
+
+        let transport = LoopbackTransport {
+            incoming,
+            outgoing: LoopbackSink { sender },
+        };
+        let client = LoopbackClient {
+            outgoing: to_server,
+            incoming: from_server,
+            next_id: 1,
+            notifications: Vec::new(),
+        };
+        (transport, client)
+    }
+}
+
+#[async_trait]
+impl Transport for LoopbackTransport {
+    async fn read_message(&mut self) -> TetradResult<JsonRpcRequest> {
+        let line = self
+            .incoming
+            .recv()
+            .await
+            .ok_or_else(|| TetradError::config("EOF"))?;
+        Ok(serde_json::from_str(&line)?)
+    }
+
+    fn write_response(&mut self, response: &JsonRpcResponse) -> TetradResult<()> {
+        self.outgoing.send_response(response)
+    }
+
+    fn notifier(&self) -> Arc<dyn NotificationSink> {
+        Arc::new(self.outgoing.clone())
+    }
+
+    fn responder(&self) -> Arc<dyn ResponseSink> {
+        Arc::new(self.outgoing.clone())
+    }
+}
+
+impl LoopbackClient {
+    /// Envia uma requisição e aguarda a resposta com o mesmo ID.
+    ///
+    /// Notificações recebidas antes da resposta são guardadas em
+    /// [`notifications`](Self::notifications).
+    pub async fn request(&mut self, method: &str, params: Option<Value>) -> TetradResult<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let mut request = JsonRpcRequest::new(method, Some(id.into()));
+        if let Some(params) = params {
+            request = request.with_params(params);
+        }
+        self.send(&request)?;
+
+        while let Some(line) = self.incoming.recv().await {
+            let message: Value = serde_json::from_str(&line)?;
+            match message.get("id") {
+                Some(response_id) if *response_id == json!(id) => return Ok(message),
+                Some(_) => tracing::debug!(%line, "Ignoring response to another request"),
+                None => self.notifications.push(message),
+            }
+        }
+
+        Err(TetradError::McpServer(format!(
+            "server closed the connection before answering '{}'",
+            method
+        )))
+    }
+
+    /// Envia uma notificação (sem ID, sem resposta).
+    pub fn notify(&mut self, method: &str, params: Option<Value>) -> TetradResult<()> {
+        let mut request = JsonRpcRequest::new(method, None);
+        if let Some(params) = params {
+            request = request.with_params(params);
+        }
+        self.send(&request)
+    }
+
+    /// Notificações recebidas até agora.
+    pub fn notifications(&self) -> &[Value] {
+        &self.notifications
+    }
+
+    fn send(&self, request: &JsonRpcRequest) -> TetradResult<()> {
+        self.outgoing
+            .send(serde_json::to_string(request)?)
+            .map_err(|_| TetradError::McpServer("loopback server stopped".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_roundtrip() {
+        let (mut transport, mut client) = LoopbackTransport::pair();
+
+        let server = async move {
+            let request = transport.read_message().await.unwrap();
+            assert_eq!(request.method, "ping");
+
+            transport
+                .notifier()
+                .send_notification(&JsonRpcNotification::new("notifications/progress"))
+                .unwrap();
+            transport
+                .write_response(&JsonRpcResponse::success(request.id, json!({"ok": true})))
+                .unwrap();
+
+            // Cliente descartado: o servidor recebe EOF
+            let eof = transport.read_message().await.unwrap_err();
+            assert!(eof.to_string().contains("EOF"));
+        };
+
+        let client = async move {
+            let response = client.request("ping", None).await.unwrap();
+            assert_eq!(response["result"]["ok"], true);
+            assert_eq!(client.notifications().len(), 1);
+        };
+
+        tokio::join!(server, client);
+    }
+
+    #[tokio::test]
+    async fn test_request_fails_when_server_stops() {
+        let (transport, mut client) = LoopbackTransport::pair();
+        drop(transport);
+
+        assert!(client.request("ping", None).await.is_err());
+    }
+}
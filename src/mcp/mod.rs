@@ -8,9 +8,27 @@
 //! - `tetrad_review_plan` - Revisa planos de implementação
 //! - `tetrad_review_code` - Revisa código antes de salvar
 //! - `tetrad_review_tests` - Revisa testes
+//! - `tetrad_review_docs` - Revisa documentação contra o código
 //! - `tetrad_confirm` - Confirma acordo com feedback
 //! - `tetrad_final_check` - Verificação final antes de commit
 //! - `tetrad_status` - Status dos avaliadores
+//! - `tetrad_estimate` - Latência e custo esperados de uma revisão, sem executá-la
+//! - `tetrad_explain` - Por que uma revisão anterior chegou à sua decisão
+//! - `tetrad_get_patterns` - O que o ReasoningBank já sabe sobre um código
+//! - `tetrad_security_review` / `tetrad_perf_review` - Presets de revisão
+//!   (com `mcp.expose_presets = true`)
+//!
+//! ## Recursos Expostos
+//!
+//! - `tetrad://knowledge/summary` - Conhecimento destilado do ReasoningBank
+//! - `tetrad://knowledge/antipatterns` - Anti-patterns mais frequentes
+//! - `tetrad://digest` - Digest curto dos anti-patterns recorrentes
+//! - `tetrad://config` - Configuração atual, sem segredos
+//!
+//! ## API HTTP
+//!
+//! Com `[mcp.api]` configurado, `tetrad serve --api` atende
+//! `POST /api/evaluate` para CI (veja [`ApiServer`]).
 //!
 //! ## Exemplo de Uso
 //!
@@ -26,18 +44,53 @@
 //! }
 //! ```
 
+mod api;
+mod certification;
+mod chunking;
+mod estimate;
+mod limiter;
+mod loopback;
+mod presets;
+mod progress;
 mod protocol;
+mod reload;
+mod resources;
+mod results;
 mod server;
 mod tools;
 mod transport;
+mod warmup;
 
 pub use protocol::{
     CallToolParams, InitializeResult, JsonRpcError, JsonRpcId, JsonRpcNotification, JsonRpcRequest,
-    JsonRpcResponse, ListToolsResult, ServerCapabilities, ServerInfo, ToolContent, ToolDescription,
-    ToolResult, ToolsCapability, INTERNAL_ERROR, INVALID_PARAMS, INVALID_REQUEST, METHOD_NOT_FOUND,
-    PARSE_ERROR,
+    JsonRpcResponse, ListResourcesResult, ListToolsResult, ReadResourceParams, ReadResourceResult,
+    RequestMeta, Resource, ResourceContents, ResourcesCapability, ServerCapabilities, ServerInfo,
+    ToolContent, ToolDescription, ToolResult, ToolsCapability, INTERNAL_ERROR, INVALID_PARAMS,
+    INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR, RESOURCE_NOT_FOUND,
 };
 
+pub use api::{ApiServer, EVALUATE_PATH};
+pub use certification::{
+    code_hash, Certificate, CertificateStatus, Certifications, CertifiedCode, CertifiedSettings,
+    Confirmation, ConfirmationStatus, EXPIRED_RETENTION_DAYS,
+};
+pub use chunking::{merge_results, split_code, Chunk, CHUNK_OVERLAP_LINES, MAX_CHUNKS};
+pub use estimate::{
+    estimate_tokens, heuristic_latency, ExecutorEstimate, ReviewEstimate, CHARS_PER_TOKEN,
+};
+pub use limiter::{EvaluationLimiter, EvaluationPermit, LimiterStatus};
+pub use loopback::{LoopbackClient, LoopbackTransport};
+pub use presets::{EffectiveSettings, ReviewPreset};
+pub use progress::ProgressReporter;
+pub use reload::{ConfigWatcher, WATCH_INTERVAL};
+pub use resources::{
+    list_resources, CONFIG_URI, KNOWLEDGE_ANTIPATTERNS_URI, KNOWLEDGE_SUMMARY_URI,
+};
+pub use results::{ResultComparison, ResultStore};
 pub use server::McpServer;
-pub use tools::ToolHandler;
-pub use transport::StdioTransport;
+pub use tools::{ConfigReloader, ToolHandler};
+pub use transport::{
+    is_disconnect, stdout_reserved, NotificationSink, ResponseSink, StdioTransport,
+    StdoutReservation, Transport,
+};
+pub use warmup::WarmUpStatus;
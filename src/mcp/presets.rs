@@ -0,0 +1,350 @@
+//! Presets de revisão expostos como ferramentas MCP próprias.
+//!
+//! Cada preset é uma camada fina sobre o pipeline de revisão de código: fixa
+//! as configurações de consenso, acrescenta instruções ao prompt e ajusta o
+//! resultado. Só aparecem em `tools/list` com `mcp.expose_presets = true`.
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::consensus::StrictSettings;
+use crate::types::config::{ConsensusConfig, ConsensusRule, SeverityGate};
+use crate::types::responses::{Decision, EvaluationResult, Severity};
+
+use super::protocol::ToolDescription;
+
+/// Presets de revisão disponíveis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewPreset {
+    /// Revisão de segurança adversarial.
+    Security,
+    /// Revisão de desempenho.
+    Performance,
+}
+
+/// Configurações efetivamente aplicadas por um preset, devolvidas na resposta.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EffectiveSettings {
+    /// Preset aplicado.
+    pub preset: ReviewPreset,
+
+    /// Regra de consenso aplicada.
+    pub rule: ConsensusRule,
+
+    /// Se a regra se adapta aos executores que votaram.
+    pub adaptive: bool,
+
+    /// Score mínimo para PASS.
+    pub min_score: u8,
+
+    /// Gate de severidade aplicado.
+    pub block_on_severity: SeverityGate,
+
+    /// Categorias cujos findings de severidade `error` ou maior bloqueiam.
+    pub veto_categories: Vec<&'static str>,
+
+    /// Categorias mantidas nos findings (`None` = todas).
+    pub finding_categories: Option<Vec<&'static str>>,
+
+    /// Se instruções do preset foram acrescentadas ao prompt.
+    pub prompt_addendum: bool,
+}
+
+impl ReviewPreset {
+    /// Todos os presets, na ordem de `tools/list`.
+    pub const ALL: [ReviewPreset; 2] = [ReviewPreset::Security, ReviewPreset::Performance];
+
+    /// Preset correspondente ao nome da ferramenta.
+    pub fn from_tool_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.tool_name() == name)
+    }
+
+    /// Nome da ferramenta MCP.
+    pub fn tool_name(self) -> &'static str {
+        match self {
+            ReviewPreset::Security => "tetrad_security_review",
+            ReviewPreset::Performance => "tetrad_perf_review",
+        }
+    }
+
+    /// Nome curto, usado na chave do cache e nos logs.
+    pub fn name(self) -> &'static str {
+        match self {
+            ReviewPreset::Security => "security",
+            ReviewPreset::Performance => "performance",
+        }
+    }
+
+    /// Descrição da ferramenta, com as configurações fixas do preset.
+    pub fn description(self) -> &'static str {
+        match self {
+            ReviewPreset::Security => {
+                "Adversarial security review of code. Fixed settings: Golden rule (unanimity, \
+                 not adapted), error findings force REVISE and critical findings BLOCK, any \
+                 security finding of severity error or higher BLOCKS (category veto), and the \
+                 evaluators are told to review as attackers."
+            }
+            ReviewPreset::Performance => {
+                "Performance review of code. Fixed settings: configured consensus rule, \
+                 performance-focused prompt, and only performance and logic findings are \
+                 returned (the decision still reflects every vote)."
+            }
+        }
+    }
+
+    /// Descrição MCP da ferramenta; aceita os mesmos parâmetros de `tetrad_review_code`.
+    pub fn tool_description(self) -> ToolDescription {
+        ToolDescription::new(
+            self.tool_name(),
+            self.description(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "code": {
+                        "type": "string",
+                        "description": "The code to be reviewed"
+                    },
+                    "language": {
+                        "type": "string",
+                        "description": "Programming language (rust, python, typescript, etc.)"
+                    },
+                    "file_path": {
+                        "type": "string",
+                        "description": "File path (optional)"
+                    },
+                    "context": {
+                        "type": "string",
+                        "description": "Additional context about the code"
+                    },
+                    "related_files": {
+                        "type": "array",
+                        "description": "Related project files sent as read-only context",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "path": { "type": "string" },
+                                "content": { "type": "string" }
+                            },
+                            "required": ["path", "content"]
+                        }
+                    }
+                },
+                "required": ["code", "language"]
+            }),
+        )
+    }
+
+    /// Instruções acrescentadas ao contexto enviado aos executores.
+    pub fn prompt_addendum(self) -> &'static str {
+        match self {
+            ReviewPreset::Security => {
+                "## Security review\n\
+                 Review this code as an attacker would. Assume every input is hostile and look \
+                 for injection, broken authentication or authorization, leaked secrets, unsafe \
+                 deserialization, path traversal, SSRF and race conditions. Report each \
+                 vulnerability with category \"security\" and rate its severity by exploitability."
+            }
+            ReviewPreset::Performance => {
+                "## Performance review\n\
+                 Focus on performance: algorithmic complexity, needless allocations and copies, \
+                 blocking calls in async code, N+1 queries and logic errors that waste work. \
+                 Report each issue with category \"performance\" or \"logic\"."
+            }
+        }
+    }
+
+    /// Categorias cujos findings graves forçam BLOCK.
+    pub fn veto_categories(self) -> &'static [&'static str] {
+        match self {
+            ReviewPreset::Security => &["security"],
+            ReviewPreset::Performance => &[],
+        }
+    }
+
+    /// Categorias mantidas nos findings (`None` = todas).
+    pub fn finding_categories(self) -> Option<&'static [&'static str]> {
+        match self {
+            ReviewPreset::Security => None,
+            ReviewPreset::Performance => Some(&["performance", "logic"]),
+        }
+    }
+
+    /// Configurações estritas do preset; `None` usa as do consenso configurado.
+    pub fn strict_settings(self, config: &ConsensusConfig) -> Option<StrictSettings> {
+        match self {
+            ReviewPreset::Security => Some(StrictSettings {
+                rule: ConsensusRule::Golden,
+                min_score: config.min_score,
+                block_on_severity: SeverityGate::Error,
+            }),
+            ReviewPreset::Performance => None,
+        }
+    }
+
+    /// Configurações efetivas do preset sobre a configuração de consenso.
+    pub fn effective_settings(self, config: &ConsensusConfig) -> EffectiveSettings {
+        let (rule, adaptive, min_score, block_on_severity) = match self.strict_settings(config) {
+            Some(strict) => (
+                strict.rule,
+                false,
+                strict.min_score,
+                strict.block_on_severity,
+            ),
+            None => (
+                config.default_rule,
+                config.adaptive,
+                config.min_score,
+                config.block_on_severity,
+            ),
+        };
+
+        EffectiveSettings {
+            preset: self,
+            rule,
+            adaptive,
+            min_score,
+            block_on_severity,
+            veto_categories: self.veto_categories().to_vec(),
+            finding_categories: self.finding_categories().map(<[_]>::to_vec),
+            prompt_addendum: true,
+        }
+    }
+
+    /// Aplica o veto por categoria e o filtro de findings ao resultado.
+    pub fn apply(self, result: &mut EvaluationResult) {
+        let vetoed: Vec<String> = result
+            .findings
+            .iter()
+            .filter(|f| f.severity >= Severity::Error)
+            .filter(|f| matches_category(&f.category, self.veto_categories()))
+            .map(|f| f.issue.clone())
+            .collect();
+        if !vetoed.is_empty() && result.decision != Decision::Block {
+            result.decision = Decision::Block;
+            result.feedback = format!(
+                "BLOCKED by the {} veto: {}\n\n{}",
+                self.name(),
+                vetoed.join("; "),
+                result.feedback
+            );
+        }
+
+        if let Some(categories) = self.finding_categories() {
+            result
+                .findings
+                .retain(|f| matches_category(&f.category, categories));
+        }
+    }
+}
+
+/// Compara categorias sem diferenciar maiúsculas ("Security", "security-auth").
+fn matches_category(category: &str, categories: &[&str]) -> bool {
+    let category = category.to_lowercase();
+    categories.iter().any(|c| category.contains(c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::responses::Finding;
+
+    fn finding(severity: Severity, category: &str) -> Finding {
+        Finding::new(severity, category, format!("{} issue", category))
+    }
+
+    fn result(decision: Decision, findings: Vec<Finding>) -> EvaluationResult {
+        let mut result = EvaluationResult::skipped("req-1", "test", decision);
+        result.findings = findings;
+        result
+    }
+
+    #[test]
+    fn test_from_tool_name() {
+        assert_eq!(
+            ReviewPreset::from_tool_name("tetrad_security_review"),
+            Some(ReviewPreset::Security)
+        );
+        assert_eq!(
+            ReviewPreset::from_tool_name("tetrad_perf_review"),
+            Some(ReviewPreset::Performance)
+        );
+        assert_eq!(ReviewPreset::from_tool_name("tetrad_review_code"), None);
+    }
+
+    #[test]
+    fn test_security_settings() {
+        let config = ConsensusConfig {
+            default_rule: ConsensusRule::Weak,
+            block_on_severity: SeverityGate::Off,
+            ..Default::default()
+        };
+        let settings = ReviewPreset::Security.effective_settings(&config);
+
+        assert_eq!(settings.rule, ConsensusRule::Golden);
+        assert!(!settings.adaptive);
+        assert_eq!(settings.min_score, config.min_score);
+        assert_eq!(settings.block_on_severity, SeverityGate::Error);
+        assert_eq!(settings.veto_categories, vec!["security"]);
+        assert_eq!(settings.finding_categories, None);
+    }
+
+    #[test]
+    fn test_performance_keeps_configured_consensus() {
+        let config = ConsensusConfig {
+            default_rule: ConsensusRule::Weak,
+            ..Default::default()
+        };
+        let settings = ReviewPreset::Performance.effective_settings(&config);
+
+        assert_eq!(settings.rule, ConsensusRule::Weak);
+        assert_eq!(settings.block_on_severity, config.block_on_severity);
+        assert!(settings.veto_categories.is_empty());
+        assert_eq!(
+            settings.finding_categories,
+            Some(vec!["performance", "logic"])
+        );
+    }
+
+    #[test]
+    fn test_security_veto_blocks() {
+        let mut vetoed = result(
+            Decision::Revise,
+            vec![
+                finding(Severity::Error, "Security"),
+                finding(Severity::Info, "style"),
+            ],
+        );
+        ReviewPreset::Security.apply(&mut vetoed);
+        assert_eq!(vetoed.decision, Decision::Block);
+        assert!(vetoed.feedback.contains("security veto"));
+        assert_eq!(vetoed.findings.len(), 2);
+
+        // Findings de segurança menores não vetam
+        let mut warning = result(Decision::Pass, vec![finding(Severity::Warning, "security")]);
+        ReviewPreset::Security.apply(&mut warning);
+        assert_eq!(warning.decision, Decision::Pass);
+    }
+
+    #[test]
+    fn test_performance_filters_findings() {
+        let mut filtered = result(
+            Decision::Revise,
+            vec![
+                finding(Severity::Warning, "performance"),
+                finding(Severity::Error, "Logic"),
+                finding(Severity::Error, "security"),
+                finding(Severity::Info, "style"),
+            ],
+        );
+        ReviewPreset::Performance.apply(&mut filtered);
+
+        let categories: Vec<_> = filtered
+            .findings
+            .iter()
+            .map(|f| f.category.as_str())
+            .collect();
+        assert_eq!(categories, vec!["performance", "Logic"]);
+        assert_eq!(filtered.decision, Decision::Revise);
+    }
+}
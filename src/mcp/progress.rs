@@ -0,0 +1,112 @@
+//! Notificações de progresso MCP.
+//!
+//! Avaliações com três CLIs levam de 30 a 90 segundos. Durante esse tempo o
+//! servidor envia `notifications/progress` ao cliente: uma quando cada
+//! executor inicia, uma quando cada voto retorna e uma antes do consenso.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::protocol::{JsonRpcId, JsonRpcNotification};
+use super::transport::{is_disconnect, NotificationSink};
+
+/// Emite notificações de progresso para uma chamada de ferramenta.
+///
+/// O valor de `progress` cresce a cada notificação, como exige a
+/// especificação MCP. Um reporter desabilitado ignora todas as chamadas.
+pub struct ProgressReporter {
+    target: Option<(Arc<dyn NotificationSink>, JsonRpcId)>,
+    progress: AtomicU64,
+    total: AtomicU64,
+}
+
+impl ProgressReporter {
+    /// Cria um reporter que envia notificações com o token informado.
+    pub fn new(sink: Arc<dyn NotificationSink>, token: JsonRpcId) -> Self {
+        Self {
+            target: Some((sink, token)),
+            progress: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    /// Cria um reporter que não envia nada.
+    pub fn disabled() -> Self {
+        Self {
+            target: None,
+            progress: AtomicU64::new(0),
+            total: AtomicU64::new(0),
+        }
+    }
+
+    /// Indica se as notificações serão enviadas.
+    pub fn is_enabled(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Define o número total de etapas esperadas.
+    pub fn set_total(&self, total: u64) {
+        self.total.store(total, Ordering::SeqCst);
+    }
+
+    /// Avança uma etapa e notifica o cliente.
+    ///
+    /// Falhas de envio são apenas registradas: o progresso nunca interrompe
+    /// a avaliação.
+    pub fn report(&self, message: &str) {
+        let Some((sink, token)) = &self.target else {
+            return;
+        };
+
+        let progress = self.progress.fetch_add(1, Ordering::SeqCst) + 1;
+        let total = self.total.load(Ordering::SeqCst).max(progress);
+        let notification = JsonRpcNotification::progress(token, progress, total, message);
+
+        match sink.send_notification(&notification) {
+            // O servidor encerra a sessão; não há a quem avisar
+            Err(e) if is_disconnect(&e) => {
+                tracing::debug!(error = %e, "Client gone, progress notification dropped");
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to send progress notification"),
+            Ok(()) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::transport::StringTransport;
+
+    #[test]
+    fn test_disabled_reporter_sends_nothing() {
+        let reporter = ProgressReporter::disabled();
+        reporter.report("ignored");
+        assert!(!reporter.is_enabled());
+    }
+
+    #[test]
+    fn test_progress_increases() {
+        let transport = Arc::new(StringTransport::new(""));
+        let reporter = ProgressReporter::new(transport.clone(), 7.into());
+        reporter.set_total(2);
+
+        reporter.report("first");
+        reporter.report("second");
+        reporter.report("extra");
+
+        let output = transport.get_output();
+        let notifications: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(notifications.len(), 3);
+        assert_eq!(notifications[0]["params"]["progressToken"], 7);
+        assert_eq!(notifications[0]["params"]["progress"], 1);
+        assert_eq!(notifications[1]["params"]["progress"], 2);
+        assert_eq!(notifications[1]["params"]["total"], 2);
+        // O total nunca fica abaixo do progresso
+        assert_eq!(notifications[2]["params"]["total"], 3);
+    }
+}
@@ -6,6 +6,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::types::build::BuildInfo;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Códigos de erro JSON-RPC padrão
 // ═══════════════════════════════════════════════════════════════════════════
@@ -25,12 +27,15 @@ pub const INVALID_PARAMS: i32 = -32602;
 /// Erro interno do servidor.
 pub const INTERNAL_ERROR: i32 = -32603;
 
+/// Recurso não encontrado (código definido pelo MCP).
+pub const RESOURCE_NOT_FOUND: i32 = -32002;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Tipos básicos JSON-RPC
 // ═══════════════════════════════════════════════════════════════════════════
 
 /// ID de uma request JSON-RPC (pode ser número ou string).
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum JsonRpcId {
     Number(i64),
@@ -196,6 +201,12 @@ impl JsonRpcError {
     pub fn internal_error(message: impl Into<String>) -> Self {
         Self::new(INTERNAL_ERROR, message)
     }
+
+    /// Recurso não encontrado; a URI vai em `data`.
+    pub fn resource_not_found(uri: &str) -> Self {
+        Self::new(RESOURCE_NOT_FOUND, format!("Resource not found: {}", uri))
+            .with_data(serde_json::json!({ "uri": uri }))
+    }
 }
 
 /// Notificação JSON-RPC (request sem ID, não espera resposta).
@@ -227,6 +238,16 @@ impl JsonRpcNotification {
         self.params = Some(params);
         self
     }
+
+    /// Cria uma notificação `notifications/progress`.
+    pub fn progress(token: &JsonRpcId, progress: u64, total: u64, message: &str) -> Self {
+        Self::new("notifications/progress").with_params(serde_json::json!({
+            "progressToken": token,
+            "progress": progress,
+            "total": total,
+            "message": message,
+        }))
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -242,6 +263,10 @@ pub struct ServerInfo {
 
     /// Versão do servidor.
     pub version: String,
+
+    /// Metadados do build (commit, data, rustc, features).
+    #[serde(default)]
+    pub build: BuildInfo,
 }
 
 impl Default for ServerInfo {
@@ -249,6 +274,7 @@ impl Default for ServerInfo {
         Self {
             name: "tetrad".to_string(),
             version: env!("CARGO_PKG_VERSION").to_string(),
+            build: BuildInfo::current(),
         }
     }
 }
@@ -259,6 +285,10 @@ pub struct ServerCapabilities {
     /// Capacidades de ferramentas.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tools: Option<ToolsCapability>,
+
+    /// Capacidades de recursos.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourcesCapability>,
 }
 
 /// Capacidade de ferramentas.
@@ -270,6 +300,19 @@ pub struct ToolsCapability {
     pub list_changed: Option<bool>,
 }
 
+/// Capacidade de recursos.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourcesCapability {
+    /// Suporta inscrição em mudanças de um recurso.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscribe: Option<bool>,
+
+    /// Notifica mudanças na lista de recursos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub list_changed: Option<bool>,
+}
+
 /// Resultado da inicialização.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -290,6 +333,7 @@ impl Default for InitializeResult {
             protocol_version: "2024-11-05".to_string(),
             capabilities: ServerCapabilities {
                 tools: Some(ToolsCapability::default()),
+                resources: Some(ResourcesCapability::default()),
             },
             server_info: ServerInfo::default(),
         }
@@ -332,6 +376,78 @@ pub struct ListToolsResult {
     pub tools: Vec<ToolDescription>,
 }
 
+/// Recurso MCP legível pelo cliente.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    /// URI do recurso (ex: `tetrad://config`).
+    pub uri: String,
+
+    /// Nome legível do recurso.
+    pub name: String,
+
+    /// Descrição do conteúdo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Tipo MIME do conteúdo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+impl Resource {
+    /// Cria a descrição de um recurso.
+    pub fn new(
+        uri: impl Into<String>,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        mime_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            uri: uri.into(),
+            name: name.into(),
+            description: Some(description.into()),
+            mime_type: Some(mime_type.into()),
+        }
+    }
+}
+
+/// Resultado da listagem de recursos.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListResourcesResult {
+    /// Lista de recursos disponíveis.
+    pub resources: Vec<Resource>,
+}
+
+/// Parâmetros para leitura de recurso.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadResourceParams {
+    /// URI do recurso.
+    pub uri: String,
+}
+
+/// Conteúdo textual de um recurso.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceContents {
+    /// URI do recurso lido.
+    pub uri: String,
+
+    /// Tipo MIME do conteúdo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+
+    /// Conteúdo do recurso.
+    pub text: String,
+}
+
+/// Resultado da leitura de recurso.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadResourceResult {
+    /// Conteúdos do recurso.
+    pub contents: Vec<ResourceContents>,
+}
+
 /// Parâmetros para chamada de ferramenta.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallToolParams {
@@ -341,6 +457,26 @@ pub struct CallToolParams {
     /// Argumentos da ferramenta.
     #[serde(default)]
     pub arguments: Value,
+
+    /// Metadados da requisição (`_meta`).
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RequestMeta>,
+}
+
+impl CallToolParams {
+    /// Token de progresso informado pelo cliente, se houver.
+    pub fn progress_token(&self) -> Option<&JsonRpcId> {
+        self.meta.as_ref()?.progress_token.as_ref()
+    }
+}
+
+/// Metadados opcionais de uma requisição MCP.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestMeta {
+    /// Token usado para associar `notifications/progress` à requisição.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress_token: Option<JsonRpcId>,
 }
 
 /// Conteúdo retornado por uma ferramenta.
@@ -396,6 +532,14 @@ impl ToolResult {
             is_error: true,
         }
     }
+
+    /// Cria um resultado de erro com JSON, para erros que o cliente trata.
+    pub fn error_json(value: &Value) -> Self {
+        Self {
+            is_error: true,
+            ..Self::success_json(value)
+        }
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -505,6 +649,7 @@ mod tests {
     fn test_initialize_result_default() {
         let result = InitializeResult::default();
         assert_eq!(result.server_info.name, "tetrad");
+        assert_eq!(result.server_info.build, BuildInfo::current());
         assert!(result.capabilities.tools.is_some());
     }
 
@@ -514,4 +659,31 @@ mod tests {
         assert_eq!(notif.method, "initialized");
         assert!(notif.params.is_none());
     }
+
+    #[test]
+    fn test_progress_notification() {
+        let notif = JsonRpcNotification::progress(&"tok".into(), 2, 7, "Codex started");
+        let json = serde_json::to_value(&notif).unwrap();
+
+        assert_eq!(json["method"], "notifications/progress");
+        assert_eq!(json["params"]["progressToken"], "tok");
+        assert_eq!(json["params"]["progress"], 2);
+        assert_eq!(json["params"]["total"], 7);
+        assert_eq!(json["params"]["message"], "Codex started");
+    }
+
+    #[test]
+    fn test_call_tool_params_progress_token() {
+        let params: CallToolParams = serde_json::from_value(json!({
+            "name": "tetrad_status",
+            "arguments": {},
+            "_meta": {"progressToken": 42}
+        }))
+        .unwrap();
+        assert_eq!(params.progress_token(), Some(&JsonRpcId::Number(42)));
+
+        let params: CallToolParams =
+            serde_json::from_value(json!({"name": "tetrad_status"})).unwrap();
+        assert!(params.progress_token().is_none());
+    }
 }
@@ -0,0 +1,277 @@
+//! Recarga da configuração com o servidor rodando (`tetrad serve --watch-config`).
+//!
+//! O arquivo é verificado periodicamente pela data de modificação e pelo
+//! tamanho. Quando muda, a configuração é carregada e aplicada com
+//! [`ConfigReloader`]; uma configuração inválida, ou que passou a definir
+//! comandos não aprovados, é registrada como erro e a anterior continua ativa.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::executors::use_mock_executors;
+use crate::types::config::Config;
+use crate::types::trust::{executable_surface, TrustStatus, TrustStore};
+use crate::{TetradError, TetradResult};
+
+use super::tools::ConfigReloader;
+
+/// Intervalo padrão entre verificações do arquivo.
+pub const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Observa o arquivo de configuração e recarrega quando ele muda.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    interval: Duration,
+    last: Option<FileStamp>,
+    offline: bool,
+}
+
+/// O que identifica uma versão do arquivo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+impl ConfigWatcher {
+    /// Cria um observador para `path`; o estado atual do arquivo é a
+    /// referência, então só mudanças posteriores disparam a recarga.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let last = FileStamp::of(&path);
+        Self {
+            path,
+            interval: WATCH_INTERVAL,
+            last,
+            offline: false,
+        }
+    }
+
+    /// Define o intervalo entre verificações.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Mantém os executores trocados por mocks (`--offline`) nas
+    /// configurações recarregadas.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Retorna `true` se o arquivo mudou desde a última verificação.
+    ///
+    /// Um arquivo removido não conta como mudança: a configuração atual é
+    /// mantida até ele voltar.
+    pub fn changed(&mut self) -> bool {
+        let current = FileStamp::of(&self.path);
+        if current.is_none() {
+            if self.last.is_some() {
+                tracing::warn!(
+                    path = %self.path.display(),
+                    "Config file is missing; keeping the current configuration"
+                );
+            }
+            self.last = None;
+            return false;
+        }
+        if current == self.last {
+            return false;
+        }
+        self.last = current;
+        true
+    }
+
+    /// Carrega o arquivo e aplica a configuração.
+    ///
+    /// Retorna `false` se o arquivo for inválido ou definir comandos sem
+    /// aprovação no trust store; o erro é registrado e a configuração
+    /// anterior continua valendo.
+    pub async fn apply(&self, reloader: &ConfigReloader) -> bool {
+        let loaded = Config::load(&self.path).map(|mut config| {
+            if self.offline {
+                use_mock_executors(&mut config.executors);
+            }
+            config
+        });
+        let result = match loaded.and_then(|c| self.check_trust(c)) {
+            Ok(config) => reloader.reload(config.with_resolved_paths()).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::error!(
+                    path = %self.path.display(),
+                    error = %e,
+                    "Invalid configuration; keeping the current one"
+                );
+                false
+            }
+        }
+    }
+
+    /// Exige aprovação do arquivo quando ele define comandos, como na
+    /// inicialização; aqui não há como perguntar ao usuário.
+    fn check_trust(&self, config: Config) -> TetradResult<Config> {
+        let surface = executable_surface(&config);
+        if surface.is_empty() {
+            return Ok(config);
+        }
+        let contents = std::fs::read(&self.path)?;
+        if TrustStore::open_default()?.status(&self.path, &contents) == TrustStatus::Trusted {
+            return Ok(config);
+        }
+        Err(TetradError::config(format!(
+            "the new configuration defines commands ({}) and is not trusted; \
+             run `tetrad --trust status`, then save the file again",
+            surface.join("; ")
+        )))
+    }
+
+    /// Verifica o arquivo a cada intervalo e recarrega quando ele muda.
+    ///
+    /// Roda até a task ser abortada.
+    pub async fn run(mut self, reloader: ConfigReloader) {
+        tracing::info!(path = %self.path.display(), "Watching config file for changes");
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            if self.changed() {
+                self.apply(&reloader).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mcp::ToolHandler;
+
+    fn write_config(path: &Path, min_score: u8, extra: &str) {
+        let content = format!(
+            "[consensus]\nmin_score = {min_score}\n{extra}\n[reasoning]\nenabled = false\n"
+        );
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn handler(path: &Path) -> ToolHandler {
+        let config = Config::load(path).unwrap().with_resolved_paths();
+        ToolHandler::with_executors(config, Vec::new()).unwrap()
+    }
+
+    async fn min_score(handler: &ToolHandler) -> u64 {
+        let status = handler
+            .handle_tool_call("tetrad_status", serde_json::json!({}))
+            .await;
+        let crate::mcp::ToolContent::Text { text } = &status.content[0];
+        let status: serde_json::Value = serde_json::from_str(text).unwrap();
+        status["consensus"]["min_score"].as_u64().unwrap()
+    }
+
+    #[test]
+    fn test_changed_detects_new_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tetrad.toml");
+        write_config(&path, 70, "");
+
+        let mut watcher = ConfigWatcher::new(&path);
+        assert!(!watcher.changed());
+
+        write_config(&path, 85, "max_loops = 5");
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+    }
+
+    #[test]
+    fn test_missing_file_is_not_a_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tetrad.toml");
+        write_config(&path, 70, "");
+
+        let mut watcher = ConfigWatcher::new(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(!watcher.changed());
+
+        // Voltar a existir conta como mudança
+        write_config(&path, 70, "");
+        assert!(watcher.changed());
+    }
+
+    #[tokio::test]
+    async fn test_apply_reloads_valid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tetrad.toml");
+        write_config(&path, 70, "");
+        let handler = handler(&path);
+        let watcher = ConfigWatcher::new(&path);
+
+        write_config(&path, 90, "");
+        assert!(watcher.apply(&handler.reloader()).await);
+
+        assert_eq!(min_score(&handler).await, 90);
+    }
+
+    #[tokio::test]
+    async fn test_apply_keeps_config_when_file_is_invalid() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tetrad.toml");
+        write_config(&path, 70, "");
+        let handler = handler(&path);
+        let watcher = ConfigWatcher::new(&path);
+
+        std::fs::write(&path, "[consensus\nmin_score = ").unwrap();
+        assert!(!watcher.apply(&handler.reloader()).await);
+
+        assert_eq!(min_score(&handler).await, 70);
+    }
+
+    #[tokio::test]
+    async fn test_apply_rejects_untrusted_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tetrad.toml");
+        write_config(&path, 70, "");
+        let handler = handler(&path);
+        let watcher = ConfigWatcher::new(&path);
+
+        // Um executor novo só roda depois de aprovado
+        write_config(
+            &path,
+            90,
+            "[[executors.custom]]\nname = \"Local\"\ncommand = \"./review.sh\"",
+        );
+        assert!(!watcher.apply(&handler.reloader()).await);
+        assert_eq!(min_score(&handler).await, 70);
+    }
+
+    #[tokio::test]
+    async fn test_offline_watcher_keeps_mock_executors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tetrad.toml");
+        write_config(&path, 70, "");
+        let handler = handler(&path);
+        let watcher = ConfigWatcher::new(&path).with_offline(true);
+
+        // Com --offline o comando novo vira mock e não precisa de aprovação
+        write_config(
+            &path,
+            90,
+            "[[executors.custom]]\nname = \"Local\"\ncommand = \"./review.sh\"",
+        );
+        assert!(watcher.apply(&handler.reloader()).await);
+        assert_eq!(min_score(&handler).await, 90);
+    }
+}
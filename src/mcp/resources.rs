@@ -0,0 +1,174 @@
+//! Recursos MCP expostos pelo servidor.
+//!
+//! O conhecimento destilado do ReasoningBank e a configuração atual ficam
+//! disponíveis via `resources/read`, para o cliente trazê-los ao contexto
+//! sem chamar uma ferramenta de avaliação.
+
+use crate::reasoning::{format_knowledge, DistilledKnowledge, Pattern};
+use crate::types::config::Config;
+use crate::TetradResult;
+
+use super::protocol::{Resource, ResourceContents};
+
+/// Resumo do conhecimento destilado (Markdown).
+pub const KNOWLEDGE_SUMMARY_URI: &str = "tetrad://knowledge/summary";
+
+/// Anti-patterns mais frequentes (Markdown).
+pub const KNOWLEDGE_ANTIPATTERNS_URI: &str = "tetrad://knowledge/antipatterns";
+
+/// Digest dos anti-patterns recorrentes, o mesmo de `.tetrad/digest.md` (Markdown).
+pub const DIGEST_URI: &str = "tetrad://digest";
+
+/// Configuração atual, sem segredos (JSON).
+pub const CONFIG_URI: &str = "tetrad://config";
+
+/// Recursos anunciados em `resources/list`.
+pub fn list_resources() -> Vec<Resource> {
+    vec![
+        Resource::new(
+            KNOWLEDGE_SUMMARY_URI,
+            "ReasoningBank knowledge summary",
+            "Distilled knowledge: top patterns, problematic categories, language and executor statistics",
+            "text/markdown",
+        ),
+        Resource::new(
+            KNOWLEDGE_ANTIPATTERNS_URI,
+            "Known anti-patterns",
+            "Anti-patterns the evaluators flagged most often, with known solutions",
+            "text/markdown",
+        ),
+        Resource::new(
+            DIGEST_URI,
+            "Recurring anti-patterns digest",
+            "Top recurring anti-patterns with one-line fixes, short enough for every session",
+            "text/markdown",
+        ),
+        Resource::new(
+            CONFIG_URI,
+            "Tetrad configuration",
+            "Current configuration with secrets redacted",
+            "application/json",
+        ),
+    ]
+}
+
+/// Conteúdo Markdown de um recurso de conhecimento.
+///
+/// `knowledge` é `None` quando o ReasoningBank está desabilitado.
+pub fn knowledge_contents(uri: &str, knowledge: Option<&DistilledKnowledge>) -> ResourceContents {
+    let text = match knowledge {
+        None => "ReasoningBank is disabled in configuration.\n".to_string(),
+        Some(knowledge) if uri == KNOWLEDGE_ANTIPATTERNS_URI => {
+            format_antipatterns(&knowledge.top_antipatterns)
+        }
+        Some(knowledge) => format_knowledge(knowledge),
+    };
+
+    ResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some("text/markdown".to_string()),
+        text,
+    }
+}
+
+/// Conteúdo de `tetrad://digest`.
+///
+/// `digest` é `None` quando o ReasoningBank está desabilitado.
+pub fn digest_contents(digest: Option<TetradResult<String>>) -> ResourceContents {
+    let text = match digest {
+        None => "ReasoningBank is disabled in configuration.\n".to_string(),
+        Some(Ok(digest)) => digest,
+        Some(Err(e)) => format!("Could not read the ReasoningBank: {}\n", e),
+    };
+
+    ResourceContents {
+        uri: DIGEST_URI.to_string(),
+        mime_type: Some("text/markdown".to_string()),
+        text,
+    }
+}
+
+/// Conteúdo de `tetrad://config`.
+pub fn config_contents(config: &Config) -> ResourceContents {
+    ResourceContents {
+        uri: CONFIG_URI.to_string(),
+        mime_type: Some("application/json".to_string()),
+        text: serde_json::to_string_pretty(&config.redacted()).unwrap_or_default(),
+    }
+}
+
+/// Formata os anti-patterns em Markdown.
+fn format_antipatterns(patterns: &[Pattern]) -> String {
+    let mut output = String::from("# Known Anti-patterns\n\n");
+
+    if patterns.is_empty() {
+        output.push_str("No anti-patterns recorded yet.\n");
+        return output;
+    }
+
+    for (i, pattern) in patterns.iter().enumerate() {
+        output.push_str(&format!(
+            "{}. **{}** ({}): {}\n   - Failures: {}\n   - Confidence: {:.0}%\n",
+            i + 1,
+            pattern.issue_category,
+            pattern.language,
+            pattern.description,
+            pattern.failure_count,
+            pattern.confidence * 100.0
+        ));
+        if let Some(solution) = &pattern.solution {
+            output.push_str(&format!("   - Solution: {}\n", solution));
+        }
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::{WebhookConfig, REDACTED};
+
+    #[test]
+    fn test_list_resources() {
+        let uris: Vec<_> = list_resources().into_iter().map(|r| r.uri).collect();
+        assert_eq!(
+            uris,
+            vec![
+                KNOWLEDGE_SUMMARY_URI,
+                KNOWLEDGE_ANTIPATTERNS_URI,
+                DIGEST_URI,
+                CONFIG_URI
+            ]
+        );
+    }
+
+    #[test]
+    fn test_knowledge_disabled() {
+        let contents = knowledge_contents(KNOWLEDGE_SUMMARY_URI, None);
+        assert!(contents.text.contains("disabled"));
+        assert_eq!(contents.mime_type.as_deref(), Some("text/markdown"));
+    }
+
+    #[test]
+    fn test_config_redacts_secrets() {
+        let mut config = Config::default();
+        config.reasoning.connection_string = Some("postgres://tetrad:secret@db/tetrad".into());
+        config.hooks.webhook = Some(WebhookConfig {
+            url: "http://hooks.internal/tetrad".into(),
+            events: vec!["post_evaluate".into()],
+            bearer_token: Some("token-123".into()),
+            timeout_secs: 10,
+        });
+
+        let contents = config_contents(&config);
+        assert!(!contents.text.contains("tetrad:secret"));
+        assert!(!contents.text.contains("token-123"));
+        assert!(contents.text.contains(REDACTED));
+        assert!(contents.text.contains("hooks.internal"));
+
+        let value: serde_json::Value = serde_json::from_str(&contents.text).unwrap();
+        assert_eq!(value["consensus"]["min_score"], config.consensus.min_score);
+    }
+}
@@ -0,0 +1,300 @@
+//! Resultados de avaliações emitidos pelo servidor MCP.
+//!
+//! Cada avaliação fica guardada pelo `request_id`, para `tetrad_final_check`
+//! comparar o código final com a revisão indicada em `previous_request_id`.
+//! Os mais recentes ficam em memória (até `mcp.results_capacity`); com
+//! `mcp.results_db_path` eles também são gravados na tabela SQLite
+//! `evaluation_results` e sobrevivem a reinícios do servidor.
+
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+
+use crate::types::config::McpConfig;
+use crate::types::responses::{Decision, EvaluationResult, Finding, Severity};
+use crate::TetradResult;
+
+/// Resultados recentes, com persistência opcional em SQLite.
+pub struct ResultStore {
+    recent: VecDeque<EvaluationResult>,
+    capacity: usize,
+    conn: Option<Connection>,
+}
+
+impl ResultStore {
+    /// Cria um registro só em memória.
+    pub fn in_memory(capacity: usize) -> Self {
+        Self {
+            recent: VecDeque::new(),
+            capacity,
+            conn: None,
+        }
+    }
+
+    /// Cria o registro conforme `[mcp]`, abrindo o banco se configurado.
+    pub fn from_config(config: &McpConfig) -> TetradResult<Self> {
+        let mut store = Self::in_memory(config.results_capacity);
+        if let Some(path) = &config.results_db_path {
+            store.conn = Some(open_database(path)?);
+        }
+        Ok(store)
+    }
+
+    /// Guarda `result`, substituindo um resultado anterior com o mesmo ID.
+    ///
+    /// O resultado fica em memória mesmo se a gravação no banco falhar.
+    pub fn record(&mut self, result: &EvaluationResult) -> TetradResult<()> {
+        self.recent.retain(|r| r.request_id != result.request_id);
+        self.recent.push_back(result.clone());
+        while self.recent.len() > self.capacity {
+            self.recent.pop_front();
+        }
+
+        if let Some(conn) = &self.conn {
+            conn.execute(
+                "INSERT OR REPLACE INTO evaluation_results (request_id, result, recorded_at)
+                 VALUES (?1, ?2, ?3)",
+                params![
+                    result.request_id,
+                    serde_json::to_string(result)?,
+                    Utc::now().to_rfc3339()
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Busca o resultado de `request_id`, primeiro em memória e depois no banco.
+    pub fn get(&self, request_id: &str) -> TetradResult<Option<EvaluationResult>> {
+        if let Some(result) = self.recent.iter().find(|r| r.request_id == request_id) {
+            return Ok(Some(result.clone()));
+        }
+
+        let Some(conn) = &self.conn else {
+            return Ok(None);
+        };
+        let json: Option<String> = conn
+            .query_row(
+                "SELECT result FROM evaluation_results WHERE request_id = ?1",
+                params![request_id],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(match json {
+            Some(json) => Some(serde_json::from_str(&json)?),
+            None => None,
+        })
+    }
+
+    /// Número de resultados em memória.
+    pub fn len(&self) -> usize {
+        self.recent.len()
+    }
+
+    /// Se não há resultados em memória.
+    pub fn is_empty(&self) -> bool {
+        self.recent.is_empty()
+    }
+}
+
+/// Abre (ou cria) o banco de resultados.
+fn open_database(path: &Path) -> TetradResult<Connection> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    let conn = Connection::open(path)?;
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS evaluation_results (
+            request_id TEXT PRIMARY KEY,
+            result TEXT NOT NULL,
+            recorded_at TEXT NOT NULL
+        );
+        "#,
+    )?;
+    Ok(conn)
+}
+
+/// Comparação entre a revisão anterior e a verificação final.
+///
+/// Findings são pareados pelo issue normalizado (minúsculas, espaços
+/// colapsados, sem pontuação final).
+#[derive(Debug, Clone, Serialize)]
+pub struct ResultComparison {
+    /// Revisão usada como base.
+    pub previous_request_id: String,
+
+    /// Decisão da revisão anterior.
+    pub previous_decision: Decision,
+
+    /// Score da revisão anterior.
+    pub previous_score: u8,
+
+    /// Score atual menos o anterior.
+    pub score_delta: i16,
+
+    /// Findings da revisão anterior que não aparecem mais.
+    pub resolved: Vec<Finding>,
+
+    /// Findings que continuam presentes (na versão atual).
+    pub still_open: Vec<Finding>,
+
+    /// Findings que não existiam na revisão anterior.
+    pub introduced: Vec<Finding>,
+
+    /// Se algum finding crítico da revisão anterior continua presente.
+    pub critical_remaining: bool,
+}
+
+impl ResultComparison {
+    /// Compara `current` com `previous`.
+    pub fn new(previous: &EvaluationResult, current: &EvaluationResult) -> Self {
+        let previous_issues: HashSet<String> = previous
+            .findings
+            .iter()
+            .map(|f| normalize_issue(&f.issue))
+            .collect();
+        let current_issues: HashSet<String> = current
+            .findings
+            .iter()
+            .map(|f| normalize_issue(&f.issue))
+            .collect();
+
+        let resolved: Vec<Finding> = previous
+            .findings
+            .iter()
+            .filter(|f| !current_issues.contains(&normalize_issue(&f.issue)))
+            .cloned()
+            .collect();
+        let (still_open, introduced): (Vec<Finding>, Vec<Finding>) = current
+            .findings
+            .iter()
+            .cloned()
+            .partition(|f| previous_issues.contains(&normalize_issue(&f.issue)));
+
+        // Um crítico anterior conta mesmo se a severidade atual tiver mudado
+        let critical_remaining = previous
+            .findings
+            .iter()
+            .filter(|f| f.severity == Severity::Critical)
+            .any(|f| current_issues.contains(&normalize_issue(&f.issue)));
+
+        Self {
+            previous_request_id: previous.request_id.clone(),
+            previous_decision: previous.decision,
+            previous_score: previous.score,
+            score_delta: current.score as i16 - previous.score as i16,
+            resolved,
+            still_open,
+            introduced,
+            critical_remaining,
+        }
+    }
+}
+
+/// Normaliza um issue para o pareamento entre revisões.
+fn normalize_issue(issue: &str) -> String {
+    issue
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_end_matches(['.', ';', ':', '!'])
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn result(request_id: &str, score: u8, findings: Vec<Finding>) -> EvaluationResult {
+        let mut result = EvaluationResult::skipped(request_id, "test", Decision::Revise);
+        result.score = score;
+        result.findings = findings;
+        result
+    }
+
+    #[test]
+    fn test_ring_buffer_capacity() {
+        let mut store = ResultStore::in_memory(2);
+        for id in ["a", "b", "c"] {
+            store.record(&result(id, 50, vec![])).unwrap();
+        }
+
+        assert_eq!(store.len(), 2);
+        assert!(store.get("a").unwrap().is_none());
+        assert!(store.get("c").unwrap().is_some());
+
+        // Regravar o mesmo ID não duplica
+        store.record(&result("c", 90, vec![])).unwrap();
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get("c").unwrap().unwrap().score, 90);
+    }
+
+    #[test]
+    fn test_results_persist_across_stores() {
+        let dir = TempDir::new().unwrap();
+        let config = McpConfig {
+            results_capacity: 1,
+            results_db_path: Some(dir.path().join("results.db")),
+            ..Default::default()
+        };
+
+        let mut store = ResultStore::from_config(&config).unwrap();
+        store
+            .record(&result(
+                "old",
+                40,
+                vec![Finding::new(Severity::Error, "logic", "Off by one")],
+            ))
+            .unwrap();
+        store.record(&result("new", 60, vec![])).unwrap();
+
+        // Fora da memória, mas ainda no banco
+        let old = store.get("old").unwrap().unwrap();
+        assert_eq!(old.findings[0].issue, "Off by one");
+
+        let reopened = ResultStore::from_config(&config).unwrap();
+        assert!(reopened.is_empty());
+        assert_eq!(reopened.get("new").unwrap().unwrap().score, 60);
+        assert!(reopened.get("missing").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_comparison() {
+        let previous = result(
+            "prev",
+            55,
+            vec![
+                Finding::new(Severity::Critical, "security", "SQL injection in query."),
+                Finding::new(Severity::Warning, "style", "Unclear variable name"),
+            ],
+        );
+        let current = result(
+            "final",
+            80,
+            vec![
+                Finding::new(Severity::Critical, "security", "sql  injection in query"),
+                Finding::new(Severity::Info, "logic", "Missing bounds check"),
+            ],
+        );
+
+        let comparison = ResultComparison::new(&previous, &current);
+        assert_eq!(comparison.score_delta, 25);
+        assert_eq!(comparison.resolved[0].issue, "Unclear variable name");
+        assert_eq!(comparison.still_open[0].issue, "sql  injection in query");
+        assert_eq!(comparison.introduced[0].issue, "Missing bounds check");
+        assert!(comparison.critical_remaining);
+
+        let fixed = result("fixed", 95, vec![]);
+        let comparison = ResultComparison::new(&previous, &fixed);
+        assert_eq!(comparison.resolved.len(), 2);
+        assert!(!comparison.critical_remaining);
+    }
+}
@@ -2,52 +2,132 @@
 //!
 //! Implementa o servidor MCP (Model Context Protocol) que expõe
 //! as ferramentas de avaliação do Tetrad para o Claude Code.
+//!
+//! O loop principal só lê mensagens: cada requisição roda numa tarefa
+//! própria sobre o [`ToolHandler`] compartilhado, de forma que um
+//! `tools/call` demorado não atrasa `tools/list` ou outras chamadas. As
+//! respostas saem na ordem em que ficam prontas, casadas pelo ID. Apenas as
+//! mensagens de ciclo de vida (`initialize`, `shutdown`) são tratadas no
+//! próprio loop, na ordem de chegada.
+//!
+//! Cada requisição em andamento tem um [`CancellationToken`]. Um
+//! `notifications/cancelled` (ou `$/cancelRequest`) com o seu ID cancela a
+//! tarefa sem resposta: o future da avaliação é descartado e os processos
+//! das CLIs, criados com `kill_on_drop`, são encerrados junto.
+
+use std::collections::HashMap;
+use std::sync::Arc;
 
-use serde_json::json;
+use serde_json::{json, Value};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
 
 use crate::types::config::Config;
 use crate::TetradResult;
 
 use super::protocol::{
-    CallToolParams, InitializeResult, JsonRpcError, JsonRpcRequest, JsonRpcResponse,
-    ListToolsResult,
+    CallToolParams, InitializeResult, JsonRpcError, JsonRpcId, JsonRpcRequest, JsonRpcResponse,
+    ListResourcesResult, ListToolsResult, ReadResourceParams, ReadResourceResult,
 };
-use super::tools::ToolHandler;
-use super::transport::StdioTransport;
+use super::resources;
+use super::tools::{ConfigReloader, ToolHandler};
+use super::transport::{is_disconnect, ResponseSink, StdioTransport, StdoutReservation, Transport};
 
 /// Servidor MCP do Tetrad.
-pub struct McpServer {
-    transport: StdioTransport,
-    tools: ToolHandler,
+///
+/// Usa o transporte stdio por padrão; outro [`Transport`] pode ser dado com
+/// [`McpServer::with_transport`].
+pub struct McpServer<T: Transport = StdioTransport> {
+    transport: T,
+    tools: Arc<ToolHandler>,
     initialized: bool,
 }
 
 impl McpServer {
-    /// Cria um novo servidor MCP.
+    /// Cria um novo servidor MCP sobre stdin/stdout.
     pub fn new(config: Config) -> TetradResult<Self> {
-        let tools = ToolHandler::new(config)?;
+        Ok(Self::with_transport(
+            ToolHandler::new(config)?,
+            StdioTransport::new(),
+        ))
+    }
+}
 
-        Ok(Self {
-            transport: StdioTransport::new(),
-            tools,
+impl<T: Transport> McpServer<T> {
+    /// Cria um servidor com as ferramentas e o transporte dados.
+    ///
+    /// As notificações de progresso passam a usar o mesmo transporte.
+    pub fn with_transport(tools: ToolHandler, transport: T) -> Self {
+        let tools = tools.with_notifications(transport.notifier());
+
+        Self {
+            transport,
+            tools: Arc::new(tools),
             initialized: false,
-        })
+        }
+    }
+
+    /// Handle para recarregar a configuração com o servidor rodando.
+    pub fn reloader(&self) -> ConfigReloader {
+        self.tools.reloader()
     }
 
     /// Inicia o servidor (loop principal).
     ///
-    /// Este método bloqueia e processa mensagens indefinidamente.
+    /// Processa mensagens até o cliente desconectar. Requisições rodam em
+    /// paralelo e podem ser canceladas pelo cliente. No EOF da leitura, o
+    /// stdio cancela as que estão em andamento (o cliente foi embora);
+    /// outros transportes esperam e respondem a elas. Pipe quebrado na
+    /// escrita encerra a sessão na hora: as requisições em andamento e o
+    /// aquecimento são cancelados. Em todos os casos os hooks de on_shutdown
+    /// rodam e o ReasoningBank é consolidado.
     pub async fn run(&mut self) -> TetradResult<()> {
         tracing::info!("Tetrad MCP Server starting...");
 
+        // A partir daqui stdout pertence exclusivamente ao JSON-RPC
+        let _stdout = self
+            .transport
+            .uses_stdout()
+            .then(StdoutReservation::acquire);
+
+        // Aquecimento em segundo plano; o initialize responde sem esperar
+        let warm_up = self.tools.start_warm_up();
+        let mut closed = self.transport.closed();
+        let responder = self.transport.responder();
+        let mut in_flight = JoinSet::new();
+        let disconnected;
+        // Cancelar a sessão cancela todas as requisições
+        let session = CancellationToken::new();
+        let mut cancellations: HashMap<JsonRpcId, CancellationToken> = HashMap::new();
+
         loop {
-            // Lê a próxima mensagem
-            let request = match self.transport.read_message() {
+            // Lê a próxima mensagem; se uma escrita detectar que o cliente
+            // caiu, a leitura é abandonada
+            let read = match closed.as_mut() {
+                Some(closed) => tokio::select! {
+                    read = self.transport.read_message() => read,
+                    _ = closed.wait_for(|closed| *closed) => {
+                        disconnected = true;
+                        break;
+                    }
+                },
+                None => self.transport.read_message().await,
+            };
+
+            // Libera as tarefas que já terminaram
+            while let Some(finished) = in_flight.try_join_next() {
+                if let Ok(Some(id)) = finished {
+                    cancellations.remove(&id);
+                }
+            }
+
+            let request = match read {
                 Ok(req) => req,
                 Err(e) => {
                     // EOF ou erro de leitura - cliente desconectou
-                    if e.to_string().contains("EOF") || e.to_string().contains("empty") {
+                    if is_disconnect(&e) || e.to_string().contains("empty") {
                         tracing::info!("Client disconnected");
+                        disconnected = self.transport.cancels_on_eof();
                         break;
                     }
                     tracing::error!(error = %e, "Failed to read message");
@@ -55,20 +135,73 @@ impl McpServer {
                 }
             };
 
-            // Notificações (sem ID) não devem receber resposta segundo JSON-RPC 2.0
-            let is_notification = request.id.is_none();
-
-            // Processa a request
-            let response = self.handle_request(request).await;
-
-            // Envia resposta apenas se não for notificação
-            if !is_notification {
-                if let Err(e) = self.transport.write_response(&response) {
-                    tracing::error!(error = %e, "Failed to write response");
+            if is_cancellation(&request.method) {
+                // Notificação: nunca recebe resposta, mesmo para IDs desconhecidos
+                if let Some(id) = cancelled_id(request.params.as_ref()) {
+                    match cancellations.remove(&id) {
+                        Some(token) => {
+                            tracing::info!(id = ?id, "Client cancelled the request");
+                            token.cancel();
+                        }
+                        None => tracing::debug!(id = ?id, "Cancellation for unknown request"),
+                    }
+                }
+            } else if is_lifecycle(&request.method) {
+                let is_notification = request.id.is_none();
+                let response = self.handle_request(request).await;
+                if !is_notification {
+                    send_response(responder.as_ref(), &response);
+                }
+            } else {
+                let tools = Arc::clone(&self.tools);
+                let responder = Arc::clone(&responder);
+                let cancelled = session.child_token();
+                if let Some(id) = &request.id {
+                    cancellations.insert(id.clone(), cancelled.clone());
                 }
+                in_flight.spawn(async move {
+                    // Notificações (sem ID) não devem receber resposta segundo JSON-RPC 2.0
+                    let id = request.id.clone();
+                    tokio::select! {
+                        response = dispatch(&tools, request) => {
+                            if id.is_some() {
+                                send_response(responder.as_ref(), &response);
+                            }
+                        }
+                        // Descarta a avaliação, encerrando as CLIs; uma
+                        // requisição cancelada não é respondida
+                        _ = cancelled.cancelled() => {}
+                    }
+                    id
+                });
             }
         }
 
+        // Sem pipe quebrado nem EOF do stdio, as requisições já lidas ainda são respondidas
+        if !disconnected {
+            match closed.as_mut() {
+                Some(closed) => tokio::select! {
+                    _ = async { while in_flight.join_next().await.is_some() {} } => {}
+                    _ = closed.wait_for(|closed| *closed) => {}
+                },
+                None => while in_flight.join_next().await.is_some() {},
+            }
+        }
+        if !in_flight.is_empty() {
+            tracing::info!(
+                requests = in_flight.len(),
+                "Client disconnected, cancelling the requests in progress"
+            );
+            // Espera os cancelamentos para que nenhuma CLI sobreviva ao servidor
+            session.cancel();
+            while in_flight.join_next().await.is_some() {}
+        }
+
+        if let Some(warm_up) = warm_up {
+            warm_up.abort();
+        }
+        self.tools.shutdown("client disconnected").await;
+
         tracing::info!("Tetrad MCP Server stopped");
         Ok(())
     }
@@ -83,14 +216,7 @@ impl McpServer {
             "initialized" => self.handle_initialized(request),
             "shutdown" => self.handle_shutdown(request),
 
-            // Tools
-            "tools/list" => self.handle_tools_list(request),
-            "tools/call" => self.handle_tools_call(request).await,
-
-            // Método desconhecido
-            _ => {
-                JsonRpcResponse::error(request.id, JsonRpcError::method_not_found(&request.method))
-            }
+            _ => dispatch(&self.tools, request).await,
         }
     }
 
@@ -129,59 +255,161 @@ impl McpServer {
 
         JsonRpcResponse::success(request.id, json!(null))
     }
+}
 
-    // ═══════════════════════════════════════════════════════════════════════
-    // Handlers de tools
-    // ═══════════════════════════════════════════════════════════════════════
+/// Notificações de cancelamento: a do MCP e a genérica do LSP.
+fn is_cancellation(method: &str) -> bool {
+    matches!(method, "notifications/cancelled" | "$/cancelRequest")
+}
 
-    /// Handler para tools/list.
-    fn handle_tools_list(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        let tools = ToolHandler::list_tools();
+/// ID da requisição cancelada: `requestId` no MCP, `id` no `$/cancelRequest`.
+fn cancelled_id(params: Option<&Value>) -> Option<JsonRpcId> {
+    let params = params?;
+    let id = params.get("requestId").or_else(|| params.get("id"))?;
+    serde_json::from_value(id.clone()).ok()
+}
 
-        let result = ListToolsResult { tools };
+/// Métodos de ciclo de vida, tratados no loop principal na ordem de chegada.
+fn is_lifecycle(method: &str) -> bool {
+    matches!(method, "initialize" | "initialized" | "shutdown")
+}
 
-        JsonRpcResponse::success(
-            request.id,
-            serde_json::to_value(result).unwrap_or(json!({"tools": []})),
-        )
+/// Processa uma requisição que não é de ciclo de vida.
+///
+/// Só usa o [`ToolHandler`], então pode rodar em paralelo com outras.
+async fn dispatch(tools: &ToolHandler, request: JsonRpcRequest) -> JsonRpcResponse {
+    match request.method.as_str() {
+        // Tools
+        "tools/list" => handle_tools_list(tools, request),
+        "tools/call" => handle_tools_call(tools, request).await,
+
+        // Resources
+        "resources/list" => handle_resources_list(request),
+        "resources/read" => handle_resources_read(tools, request).await,
+
+        // Método desconhecido
+        _ => JsonRpcResponse::error(request.id, JsonRpcError::method_not_found(&request.method)),
     }
+}
 
-    /// Handler para tools/call.
-    async fn handle_tools_call(&self, request: JsonRpcRequest) -> JsonRpcResponse {
-        let params: CallToolParams = match request.params {
-            Some(p) => match serde_json::from_value(p) {
-                Ok(params) => params,
-                Err(e) => {
-                    return JsonRpcResponse::error(
-                        request.id,
-                        JsonRpcError::invalid_params(format!("Invalid params: {}", e)),
-                    );
-                }
-            },
-            None => {
+/// Escreve uma resposta; falhas só são registradas, e um pipe quebrado
+/// encerra o loop pelo sinal `closed` do transporte.
+fn send_response(responder: &dyn ResponseSink, response: &JsonRpcResponse) {
+    if let Err(e) = responder.send_response(response) {
+        if is_disconnect(&e) {
+            tracing::info!("Client disconnected");
+        } else {
+            tracing::error!(error = %e, "Failed to write response");
+        }
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Handlers de tools
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Handler para tools/list.
+fn handle_tools_list(tools: &ToolHandler, request: JsonRpcRequest) -> JsonRpcResponse {
+    let result = ListToolsResult {
+        tools: tools.available_tools(),
+    };
+
+    JsonRpcResponse::success(
+        request.id,
+        serde_json::to_value(result).unwrap_or(json!({"tools": []})),
+    )
+}
+
+/// Handler para tools/call.
+async fn handle_tools_call(tools: &ToolHandler, request: JsonRpcRequest) -> JsonRpcResponse {
+    let params: CallToolParams = match request.params {
+        Some(p) => match serde_json::from_value(p) {
+            Ok(params) => params,
+            Err(e) => {
                 return JsonRpcResponse::error(
                     request.id,
-                    JsonRpcError::invalid_params("Missing params"),
+                    JsonRpcError::invalid_params(format!("Invalid params: {}", e)),
                 );
             }
-        };
+        },
+        None => {
+            return JsonRpcResponse::error(
+                request.id,
+                JsonRpcError::invalid_params("Missing params"),
+            );
+        }
+    };
 
-        tracing::info!(tool = %params.name, "Calling tool");
+    tracing::info!(tool = %params.name, "Calling tool");
 
-        let result = self
-            .tools
-            .handle_tool_call(&params.name, params.arguments)
-            .await;
+    // Sem progressToken do cliente, o ID da requisição identifica o progresso
+    let progress_token = params
+        .progress_token()
+        .cloned()
+        .or_else(|| request.id.clone());
 
-        // Converte ToolResult para Value
-        let result_value = serde_json::to_value(&result).unwrap_or_else(|_| {
-            json!({
-                "content": [{"type": "text", "text": "Internal error"}],
-                "isError": true
-            })
-        });
+    let result = tools
+        .handle_tool_call_with_progress(&params.name, params.arguments, progress_token)
+        .await;
+
+    // Converte ToolResult para Value
+    let result_value = serde_json::to_value(&result).unwrap_or_else(|_| {
+        json!({
+            "content": [{"type": "text", "text": "Internal error"}],
+            "isError": true
+        })
+    });
+
+    JsonRpcResponse::success(request.id, result_value)
+}
+
+// ═══════════════════════════════════════════════════════════════════════
+// Handlers de resources
+// ═══════════════════════════════════════════════════════════════════════
+
+/// Handler para resources/list.
+fn handle_resources_list(request: JsonRpcRequest) -> JsonRpcResponse {
+    let result = ListResourcesResult {
+        resources: resources::list_resources(),
+    };
 
-        JsonRpcResponse::success(request.id, result_value)
+    JsonRpcResponse::success(
+        request.id,
+        serde_json::to_value(result).unwrap_or(json!({"resources": []})),
+    )
+}
+
+/// Handler para resources/read.
+///
+/// URIs desconhecidas recebem o erro `RESOURCE_NOT_FOUND`.
+async fn handle_resources_read(tools: &ToolHandler, request: JsonRpcRequest) -> JsonRpcResponse {
+    let params: ReadResourceParams = match request.params.map(serde_json::from_value) {
+        Some(Ok(params)) => params,
+        Some(Err(e)) => {
+            return JsonRpcResponse::error(
+                request.id,
+                JsonRpcError::invalid_params(format!("Invalid params: {}", e)),
+            );
+        }
+        None => {
+            return JsonRpcResponse::error(
+                request.id,
+                JsonRpcError::invalid_params("Missing params"),
+            );
+        }
+    };
+
+    let Some(contents) = tools.read_resource(&params.uri).await else {
+        return JsonRpcResponse::error(request.id, JsonRpcError::resource_not_found(&params.uri));
+    };
+
+    let result = ReadResourceResult {
+        contents: vec![contents],
+    };
+
+    match serde_json::to_value(result) {
+        Ok(value) => JsonRpcResponse::success(request.id, value),
+        Err(e) => JsonRpcResponse::error(request.id, JsonRpcError::internal_error(e.to_string())),
     }
 }
 
@@ -191,6 +419,16 @@ mod tests {
     use crate::mcp::protocol::JsonRpcId;
     use serde_json::Value;
 
+    /// Respostas escritas pelo servidor, ordenadas por ID (sem ID primeiro).
+    fn responses_by_id(output: &str) -> Vec<Value> {
+        let mut responses: Vec<Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        responses.sort_by_key(|response| response["id"].as_i64());
+        responses
+    }
+
     fn create_test_request(method: &str, params: Option<Value>) -> JsonRpcRequest {
         JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
@@ -216,6 +454,21 @@ mod tests {
         assert!(result["serverInfo"]["name"].as_str() == Some("tetrad"));
     }
 
+    #[tokio::test]
+    async fn test_initialize_does_not_wait_for_warm_up() {
+        let mut config = Config::default();
+        config.general.warm_up = true;
+        let mut server = McpServer::new(config).unwrap();
+
+        let warm_up = server.tools.start_warm_up().expect("warm-up enabled");
+        let request = create_test_request("initialize", Some(json!({})));
+        let response = server.handle_request(request).await;
+        assert!(!response.is_error());
+
+        // O aquecimento termina por conta própria
+        warm_up.await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_handle_tools_list() {
         let config = Config::default();
@@ -228,7 +481,7 @@ mod tests {
 
         let result = response.result.unwrap();
         let tools = result["tools"].as_array().unwrap();
-        assert_eq!(tools.len(), 6);
+        assert_eq!(tools.len(), 12);
 
         // Verifica que todos os tools esperados estão presentes
         let tool_names: Vec<&str> = tools.iter().map(|t| t["name"].as_str().unwrap()).collect();
@@ -327,4 +580,470 @@ mod tests {
         assert!(!response.is_error());
         assert!(!server.initialized);
     }
+
+    #[tokio::test]
+    async fn test_run_over_loopback_transport() {
+        use crate::mcp::LoopbackTransport;
+
+        let (transport, mut client) = LoopbackTransport::pair();
+        let tools = ToolHandler::with_executors(Config::default(), Vec::new()).unwrap();
+        let mut server = McpServer::with_transport(tools, transport);
+
+        let client = async move {
+            let response = client.request("initialize", Some(json!({}))).await.unwrap();
+            assert_eq!(response["result"]["serverInfo"]["name"], "tetrad");
+
+            // Notificações não recebem resposta
+            client.notify("notifications/initialized", None).unwrap();
+
+            let response = client.request("tools/list", None).await.unwrap();
+            assert!(response["result"]["tools"].as_array().unwrap().len() >= 9);
+            assert_eq!(response["id"], 2);
+        };
+
+        // O loop termina quando o cliente é descartado
+        let (result, ()) = tokio::join!(server.run(), client);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_tool_handler_is_shareable() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ToolHandler>();
+    }
+
+    #[tokio::test]
+    async fn test_slow_call_does_not_block_fast_requests() {
+        use std::time::Duration;
+
+        use async_trait::async_trait;
+
+        use crate::executors::{CliExecutor, ConfiguredExecutor};
+        use crate::mcp::transport::StringTransport;
+        use crate::types::config::ExecutorConfig;
+        use crate::types::requests::EvaluationRequest;
+        use crate::types::responses::{ModelVote, Vote};
+
+        /// Executor que demora a votar.
+        struct SlowExecutor(&'static str);
+
+        #[async_trait]
+        impl CliExecutor for SlowExecutor {
+            fn name(&self) -> &str {
+                self.0
+            }
+
+            fn command(&self) -> &str {
+                "slow"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                tokio::time::sleep(Duration::from_millis(300)).await;
+                Ok(ModelVote::new(self.0, Vote::Pass, 90))
+            }
+
+            fn specialization(&self) -> &str {
+                "logic"
+            }
+        }
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.reasoning.db_path = dir.path().join("patterns.db");
+        let executors: Vec<ConfiguredExecutor> = ["Codex", "Gemini", "Qwen"]
+            .into_iter()
+            .map(|name| {
+                let executor: Box<dyn CliExecutor> = Box::new(SlowExecutor(name));
+                (executor, ExecutorConfig::new("slow", &[]))
+            })
+            .collect();
+
+        let input = [
+            json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": {
+                "name": "tetrad_review_code",
+                "arguments": {"code": "fn main() {}", "language": "rust"}}}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "resources/list"}),
+        ]
+        .iter()
+        .map(|message| format!("{}\n", message))
+        .collect::<String>();
+
+        let tools = ToolHandler::with_executors(config, executors).unwrap();
+        let mut server = McpServer::with_transport(tools, StringTransport::new(&input));
+        server.run().await.unwrap();
+
+        // As requisições rápidas respondem antes da revisão lenta, cada uma
+        // com o seu ID
+        let responses: Vec<Value> = server
+            .transport
+            .get_output()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let ids: Vec<i64> = responses
+            .iter()
+            .map(|response| response["id"].as_i64().unwrap())
+            .collect();
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[2], 1);
+
+        let by_id = responses_by_id(&server.transport.get_output());
+        assert!(by_id[0]["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("\"decision\""));
+        assert!(by_id[1]["result"]["tools"].is_array());
+        assert!(by_id[2]["result"]["resources"].is_array());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_ends_cleanly_when_client_stops_reading() {
+        use std::io::{BufRead, BufReader, Write};
+        use std::sync::{Arc, Mutex};
+
+        use crate::hooks::{Hook, HookContext, HookEvent, HookResult};
+
+        /// Registra os motivos de encerramento recebidos.
+        struct ShutdownRecorder(Arc<Mutex<Vec<String>>>);
+
+        #[async_trait::async_trait]
+        impl Hook for ShutdownRecorder {
+            fn name(&self) -> &str {
+                "shutdown_recorder"
+            }
+
+            fn event(&self) -> HookEvent {
+                HookEvent::OnShutdown
+            }
+
+            async fn execute(&self, context: &HookContext<'_>) -> TetradResult<HookResult> {
+                if let HookContext::OnShutdown { reason } = context {
+                    self.0.lock().unwrap().push(reason.to_string());
+                }
+                Ok(HookResult::Continue)
+            }
+        }
+
+        let (server_in, mut client_out) = std::io::pipe().unwrap();
+        let (client_in, server_out) = std::io::pipe().unwrap();
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.reasoning.db_path = dir.path().join("patterns.db");
+        let reasons = Arc::new(Mutex::new(Vec::new()));
+        let tools = ToolHandler::with_executors(config, Vec::new())
+            .unwrap()
+            .with_hook(Box::new(ShutdownRecorder(reasons.clone())));
+        let mut server =
+            McpServer::with_transport(tools, StdioTransport::from_io(server_in, server_out));
+
+        // O cliente lê a primeira resposta e cai; stdin continua aberto, então
+        // só o EPIPE da segunda resposta pode encerrar o servidor
+        let client = std::thread::spawn(move || {
+            writeln!(
+                client_out,
+                r#"{{"jsonrpc":"2.0","id":1,"method":"initialize","params":{{}}}}"#
+            )
+            .unwrap();
+            let mut reader = BufReader::new(client_in);
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            drop(reader);
+
+            writeln!(
+                client_out,
+                r#"{{"jsonrpc":"2.0","id":2,"method":"tools/list"}}"#
+            )
+            .unwrap();
+            (line, client_out)
+        });
+
+        let result = tokio::time::timeout(std::time::Duration::from_secs(10), server.run())
+            .await
+            .expect("server should stop after the broken pipe");
+        assert!(result.is_ok());
+
+        let (first_response, _stdin) = client.join().unwrap();
+        assert!(first_response.contains("serverInfo"));
+        assert_eq!(*reasons.lock().unwrap(), vec!["client disconnected"]);
+    }
+
+    #[tokio::test]
+    async fn test_session_survives_malformed_input() {
+        use crate::mcp::protocol::PARSE_ERROR;
+        use crate::mcp::transport::StringTransport;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.reasoning.db_path = dir.path().join("patterns.db");
+
+        // Dois objetos na mesma linha, um objeto quebrado em duas linhas,
+        // lixo e uma requisição normal no fim
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#,
+            r#"{"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":2,"#,
+            "\n",
+            r#""method":"tools/list"}"#,
+            "\n",
+            "not json\n",
+            r#"{"jsonrpc":"2.0","id":3,"method":"resources/list"}"#,
+            "\n"
+        );
+
+        let tools = ToolHandler::with_executors(config, Vec::new()).unwrap();
+        let mut server = McpServer::with_transport(tools, StringTransport::new(input));
+        server.run().await.unwrap();
+
+        // Respostas saem na ordem em que ficam prontas; o erro de parse não tem ID
+        let responses = responses_by_id(&server.transport.get_output());
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[0]["error"]["code"], PARSE_ERROR);
+        assert!(responses[1]["result"]["serverInfo"].is_object());
+        assert!(responses[2]["result"]["tools"].is_array());
+        assert!(responses[3]["result"]["resources"].is_array());
+    }
+
+    #[tokio::test]
+    async fn test_resources_over_string_transport() {
+        use crate::mcp::protocol::RESOURCE_NOT_FOUND;
+        use crate::mcp::transport::StringTransport;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.reasoning.db_path = dir.path().join("patterns.db");
+
+        let input = [
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "resources/list"}),
+            json!({"jsonrpc": "2.0", "id": 3, "method": "resources/read",
+                "params": {"uri": "tetrad://knowledge/summary"}}),
+            json!({"jsonrpc": "2.0", "id": 4, "method": "resources/read",
+                "params": {"uri": "tetrad://config"}}),
+            json!({"jsonrpc": "2.0", "id": 5, "method": "resources/read",
+                "params": {"uri": "tetrad://unknown"}}),
+        ]
+        .iter()
+        .map(|message| format!("{}\n", message))
+        .collect::<String>();
+
+        let tools = ToolHandler::with_executors(config, Vec::new()).unwrap();
+        let mut server = McpServer::with_transport(tools, StringTransport::new(&input));
+        server.run().await.unwrap();
+
+        let responses = responses_by_id(&server.transport.get_output());
+        assert_eq!(responses.len(), 5);
+
+        assert!(responses[0]["result"]["capabilities"]["resources"].is_object());
+
+        let uris: Vec<&str> = responses[1]["result"]["resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["uri"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            uris,
+            vec![
+                "tetrad://knowledge/summary",
+                "tetrad://knowledge/antipatterns",
+                "tetrad://digest",
+                "tetrad://config"
+            ]
+        );
+
+        let summary = &responses[2]["result"]["contents"][0];
+        assert_eq!(summary["uri"], "tetrad://knowledge/summary");
+        assert_eq!(summary["mimeType"], "text/markdown");
+        assert!(summary["text"]
+            .as_str()
+            .unwrap()
+            .contains("# ReasoningBank Knowledge"));
+
+        let config: Value = serde_json::from_str(
+            responses[3]["result"]["contents"][0]["text"]
+                .as_str()
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(config["consensus"]["min_score"].is_number());
+
+        assert_eq!(responses[4]["id"], 5);
+        assert_eq!(responses[4]["error"]["code"], RESOURCE_NOT_FOUND);
+        assert_eq!(responses[4]["error"]["data"]["uri"], "tetrad://unknown");
+    }
+
+    /// Servidor sobre pipes com um executor cuja CLI grava o PID e dorme.
+    #[cfg(unix)]
+    fn sleeping_server(
+        dir: &tempfile::TempDir,
+    ) -> (
+        McpServer,
+        std::io::PipeWriter,
+        std::io::PipeReader,
+        std::path::PathBuf,
+    ) {
+        use crate::executors::{CliExecutor, GenericExecutor};
+        use crate::types::config::{CustomExecutorConfig, ExecutorConfig};
+
+        let pid_file = dir.path().join("cli.pid");
+        let script = format!("echo $$ > '{}'; exec sleep 30", pid_file.display());
+        let custom = CustomExecutorConfig {
+            name: "Sleeper".to_string(),
+            executor: ExecutorConfig::new("sh", &["-c", &script, "sh"]),
+            specialization: "logic".to_string(),
+            output_format: Default::default(),
+        };
+        let executor: Box<dyn CliExecutor> = Box::new(GenericExecutor::from_config(&custom));
+
+        let mut config = Config::default();
+        config.reasoning.db_path = dir.path().join("patterns.db");
+        let tools =
+            ToolHandler::with_executors(config, vec![(executor, custom.executor.clone())]).unwrap();
+
+        let (server_in, client_out) = std::io::pipe().unwrap();
+        let (client_in, server_out) = std::io::pipe().unwrap();
+        let server =
+            McpServer::with_transport(tools, StdioTransport::from_io(server_in, server_out));
+        (server, client_out, client_in, pid_file)
+    }
+
+    /// Espera a CLI gravar o PID.
+    #[cfg(unix)]
+    fn wait_for_pid(pid_file: &std::path::Path) -> u32 {
+        for _ in 0..500 {
+            if let Some(pid) = std::fs::read_to_string(pid_file)
+                .ok()
+                .and_then(|pid| pid.trim().parse().ok())
+            {
+                return pid;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        panic!("the CLI never started");
+    }
+
+    /// Se o processo ainda roda (zumbis já terminaram).
+    #[cfg(unix)]
+    fn is_running(pid: u32) -> bool {
+        let output = std::process::Command::new("ps")
+            .args(["-o", "stat=", "-p", &pid.to_string()])
+            .output()
+            .unwrap();
+        let stat = String::from_utf8_lossy(&output.stdout);
+        output.status.success() && !stat.trim().is_empty() && !stat.trim().starts_with('Z')
+    }
+
+    /// Espera até um segundo o processo terminar.
+    #[cfg(unix)]
+    fn ends_within_a_second(pid: u32) -> bool {
+        let start = std::time::Instant::now();
+        while start.elapsed() < std::time::Duration::from_secs(1) {
+            if !is_running(pid) {
+                return true;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        false
+    }
+
+    const REVIEW_CALL: &str = r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"tetrad_review_code","arguments":{"code":"fn main() {}","language":"rust"}}}"#;
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancelled_request_kills_the_cli() {
+        use std::io::{BufRead, BufReader, Write};
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let (mut server, mut client_out, client_in, pid_file) = sleeping_server(&dir);
+
+        let client = std::thread::spawn(move || {
+            writeln!(client_out, "{}", REVIEW_CALL).unwrap();
+            let pid = wait_for_pid(&pid_file);
+            assert!(is_running(pid));
+
+            writeln!(
+                client_out,
+                r#"{{"jsonrpc":"2.0","method":"notifications/cancelled","params":{{"requestId":1,"reason":"user pressed Esc"}}}}"#
+            )
+            .unwrap();
+            let killed = ends_within_a_second(pid);
+
+            // A sessão continua, e a requisição cancelada não é respondida
+            writeln!(
+                client_out,
+                r#"{{"jsonrpc":"2.0","id":2,"method":"tools/list"}}"#
+            )
+            .unwrap();
+            let mut reader = BufReader::new(client_in);
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            drop(client_out);
+            (killed, line)
+        });
+
+        let result = tokio::time::timeout(std::time::Duration::from_secs(10), server.run())
+            .await
+            .expect("server should stop on EOF");
+        assert!(result.is_ok());
+
+        let (killed, line) = client.join().unwrap();
+        assert!(killed, "the CLI outlived the cancellation");
+        let response: Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(response["id"], 2);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_stdin_eof_cancels_requests_in_progress() {
+        use std::io::Write;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let (mut server, mut client_out, _client_in, pid_file) = sleeping_server(&dir);
+
+        writeln!(client_out, "{}", REVIEW_CALL).unwrap();
+        let server = tokio::spawn(async move {
+            let started = std::time::Instant::now();
+            let result = server.run().await;
+            (result, started.elapsed())
+        });
+        let pid = tokio::task::spawn_blocking(move || wait_for_pid(&pid_file))
+            .await
+            .unwrap();
+        drop(client_out);
+
+        let (result, elapsed) = server.await.unwrap();
+        assert!(result.is_ok());
+        // O servidor não espera os 30s da CLI
+        assert!(elapsed < std::time::Duration::from_secs(10));
+        assert!(
+            tokio::task::spawn_blocking(move || ends_within_a_second(pid))
+                .await
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_cancelled_id() {
+        assert_eq!(
+            cancelled_id(Some(&json!({"requestId": 7, "reason": "timeout"}))),
+            Some(JsonRpcId::Number(7))
+        );
+        assert_eq!(
+            cancelled_id(Some(&json!({"id": "call-1"}))),
+            Some(JsonRpcId::String("call-1".to_string()))
+        );
+        assert_eq!(cancelled_id(Some(&json!({"reason": "timeout"}))), None);
+        assert_eq!(cancelled_id(None), None);
+        assert!(is_cancellation("notifications/cancelled"));
+        assert!(is_cancellation("$/cancelRequest"));
+        assert!(!is_cancellation("tools/call"));
+    }
 }
@@ -1,33 +1,70 @@
 //! MCP tool handlers for Tetrad.
 //!
-//! This module implements the 6 tools exposed by the MCP server:
+//! This module implements the 12 tools exposed by the MCP server:
 //!
 //! 1. `tetrad_review_plan` - Reviews implementation plans
 //! 2. `tetrad_review_code` - Reviews code before saving
-//! 3. `tetrad_review_tests` - Reviews tests
-//! 4. `tetrad_confirm` - Confirms agreement with feedback
-//! 5. `tetrad_final_check` - Final check before commit
-//! 6. `tetrad_status` - Evaluator status
+//! 3. `tetrad_review_diff` - Reviews only the changed lines of a unified diff
+//! 4. `tetrad_review_tests` - Reviews tests
+//! 5. `tetrad_review_docs` - Reviews documentation against the code
+//! 6. `tetrad_confirm` - Confirms agreement with feedback
+//! 7. `tetrad_final_check` - Final check before commit
+//! 8. `tetrad_status` - Evaluator status
+//! 9. `tetrad_metrics` - Evaluation metrics since startup
+//! 10. `tetrad_estimate` - Expected latency and cost of a review, without running it
+//! 11. `tetrad_explain` - Why a previous review reached its decision
+//! 12. `tetrad_get_patterns` - What the ReasoningBank already knows about some code
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::{Arc, RwLock as StdRwLock};
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
+use lru::LruCache;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use tokio::sync::{Mutex, RwLock};
 
-use crate::cache::EvaluationCache;
-use crate::consensus::ConsensusEngine;
-use crate::executors::{CliExecutor, CodexExecutor, GeminiExecutor, QwenExecutor};
-use crate::hooks::HookSystem;
-use crate::reasoning::ReasoningBank;
-use crate::types::config::Config;
-use crate::types::requests::{EvaluationRequest, EvaluationType};
-use crate::types::responses::{Decision, EvaluationResult, ModelVote};
-use crate::TetradResult;
+use crate::cache::{CachedVoteExecutor, EvaluationCache, VoteCache};
+use crate::consensus::{ConsensusEngine, StrictSettings, Turnout, VoteWeights};
+use crate::executors::{
+    adaptive_timeout, build_executors, collect_seats_until_settled, seat_count, seat_names,
+    seat_weights, slots as executor_slots, CircuitState, ConfiguredExecutor, ExecutorHealth,
+    ExecutorSlot, SeatOutcome, QUARANTINE_FILE_NAME,
+};
+use crate::hooks::{Hook, HookSystem, MetricsHook, RedactionHook, WebhookHook};
+use crate::reasoning::{
+    known_antipatterns, matched_patterns, prompt_context, score_adjustment, ArtifactKind,
+    BudgetCharge, PatternMatcher, ReasoningBank, DIGEST_FILE_NAME,
+};
+use crate::types::build::BuildInfo;
+use crate::types::config::{BudgetAction, Config, ExecutorConfig};
+use crate::types::requests::{
+    fit_related_files, DocType, EvaluationRequest, EvaluationType, RelatedFile,
+};
+use crate::types::responses::{
+    Decision, EvaluationResult, ExecutorSelection, FileFindings, Finding, ModelVote, Severity,
+};
+use crate::{TetradError, TetradResult};
 
-use super::protocol::{ToolDescription, ToolResult};
+use super::certification::{
+    code_hash, Certifications, CertifiedCode, CertifiedSettings, ConfirmationStatus,
+};
+use super::chunking::{merge_results, split_code, Chunk, MAX_CHUNKS};
+use super::estimate::{estimate_tokens, heuristic_latency, ExecutorEstimate, ReviewEstimate};
+use super::limiter::EvaluationLimiter;
+use super::presets::ReviewPreset;
+use super::progress::ProgressReporter;
+use super::protocol::{JsonRpcId, ResourceContents, ToolDescription, ToolResult};
+use super::resources;
+use super::results::{ResultComparison, ResultStore};
+use super::transport::NotificationSink;
+use super::warmup::{self, WarmUp};
+
+/// Reviews whose refinement loop is remembered for `previous_request_id`.
+const MAX_TRACKED_LOOPS: usize = 10_000;
 
 // ═══════════════════════════════════════════════════════════════════════════
 // Tool parameters
@@ -60,6 +97,36 @@ pub struct ReviewCodeParams {
     /// Additional context.
     #[serde(default)]
     pub context: Option<String>,
+
+    /// Related project files sent as read-only context.
+    #[serde(default)]
+    pub related_files: Vec<RelatedFile>,
+
+    /// Review this one revises; continues its refinement loop count.
+    #[serde(default)]
+    pub previous_request_id: Option<String>,
+
+    /// Executors allowed to vote on this request; all enabled ones when omitted.
+    #[serde(default)]
+    pub executors: Option<Vec<String>>,
+}
+
+/// Parameters for review_diff.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewDiffParams {
+    /// Unified diff text.
+    pub diff: String,
+
+    /// Code language.
+    pub language: String,
+
+    /// File path.
+    #[serde(default)]
+    pub file_path: Option<String>,
+
+    /// Additional context.
+    #[serde(default)]
+    pub context: Option<String>,
 }
 
 /// Parameters for review_tests.
@@ -74,6 +141,38 @@ pub struct ReviewTestsParams {
     /// Additional context.
     #[serde(default)]
     pub context: Option<String>,
+
+    /// Executors allowed to vote on this request; all enabled ones when omitted.
+    #[serde(default)]
+    pub executors: Option<Vec<String>>,
+}
+
+/// Parameters for review_docs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewDocsParams {
+    /// Documentation text (docstrings, README section, etc.).
+    #[serde(alias = "content")]
+    pub docs: String,
+
+    /// Kind of documentation; adds criteria specific to it.
+    #[serde(default)]
+    pub doc_type: Option<DocType>,
+
+    /// Code the documentation describes.
+    #[serde(default)]
+    pub code: Option<String>,
+
+    /// Language of the related code and of the examples (defaults to markdown).
+    #[serde(default)]
+    pub language: Option<String>,
+
+    /// File path.
+    #[serde(default)]
+    pub file_path: Option<String>,
+
+    /// Additional context.
+    #[serde(default)]
+    pub context: Option<String>,
 }
 
 /// Parameters for confirm.
@@ -90,6 +189,45 @@ pub struct ConfirmParams {
     pub notes: Option<String>,
 }
 
+/// Parameters for estimate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EstimateParams {
+    /// Code (or plan, tests, docs, diff) that would be reviewed.
+    pub code: String,
+
+    /// Language.
+    pub language: String,
+
+    /// Review type (default: code).
+    #[serde(default)]
+    pub evaluation_type: Option<EvaluationType>,
+
+    /// File path (optional).
+    #[serde(default)]
+    pub file_path: Option<String>,
+}
+
+/// Parameters for explain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplainParams {
+    /// ID of the review to explain.
+    pub request_id: String,
+}
+
+/// Parameters for get_patterns.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetPatternsParams {
+    /// Code to look up.
+    pub code: String,
+
+    /// Language.
+    pub language: String,
+
+    /// Maximum number of patterns; capped by `reasoning.max_patterns_per_query`.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
 /// Parameters for final_check.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FinalCheckParams {
@@ -102,6 +240,20 @@ pub struct FinalCheckParams {
     /// Previous request ID (for comparison).
     #[serde(default)]
     pub previous_request_id: Option<String>,
+
+    /// Strict certification: Golden rule, raised minimum score and no
+    /// critical/error findings. Overrides `consensus.strict_final_check`.
+    #[serde(default)]
+    pub strict: Option<bool>,
+
+    /// Executors allowed to vote on this request; all enabled ones when omitted.
+    #[serde(default)]
+    pub executors: Option<Vec<String>>,
+
+    /// Run the executors even when identical code already has a valid
+    /// certificate with the same settings.
+    #[serde(default)]
+    pub force: bool,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -110,53 +262,376 @@ pub struct FinalCheckParams {
 
 /// MCP tool handler for Tetrad.
 pub struct ToolHandler {
-    config: Config,
-    codex: CodexExecutor,
-    gemini: GeminiExecutor,
-    qwen: QwenExecutor,
-    consensus: ConsensusEngine,
+    state: Arc<StdRwLock<Arc<ToolHandlerState>>>,
+    /// Hooks added with [`ToolHandler::add_hook`], registered again on reload.
+    extra_hooks: Arc<StdRwLock<Vec<Arc<dyn Hook>>>>,
+    /// False when the executors were given explicitly; reloads keep them.
+    rebuild_executors: bool,
+    health: ExecutorHealth,
     // Uses Mutex instead of RwLock because rusqlite::Connection is not Sync
     reasoning_bank: Arc<Mutex<Option<ReasoningBank>>>,
     cache: Arc<RwLock<EvaluationCache>>,
-    hooks: HookSystem,
-    confirmations: Arc<RwLock<HashMap<String, bool>>>,
+    vote_cache: Option<Arc<VoteCache>>,
+    metrics: Arc<MetricsHook>,
+    certifications: Arc<RwLock<Certifications>>,
+    results: Arc<Mutex<ResultStore>>,
+    /// Refinement loop reached by each review that revised an earlier one,
+    /// by request_id; a review missing here started its cycle (loop 1).
+    /// Capped at [`MAX_TRACKED_LOOPS`], least recently revised first out.
+    loops: Arc<RwLock<LruCache<String, u32>>>,
+    clock: Arc<dyn Fn() -> DateTime<Utc> + Send + Sync>,
+    notifications: Option<Arc<dyn NotificationSink>>,
+    warm_up: Arc<WarmUp>,
+    limiter: EvaluationLimiter,
+}
+
+/// Components rebuilt when the configuration is reloaded.
+///
+/// Each request works on a snapshot taken when it starts, so a reload or a
+/// hook added or removed never changes a review in progress, and running
+/// hooks hold no lock. Cloning is cheap; changes swap in a new snapshot.
+#[derive(Clone)]
+struct ToolHandlerState {
+    config: Config,
+    executors: Arc<Vec<ConfiguredExecutor>>,
+    consensus: Arc<ConsensusEngine>,
+    hooks: Arc<HookSystem>,
+}
+
+impl ToolHandlerState {
+    /// Builds the consensus engine for `config`.
+    fn build(
+        config: Config,
+        executors: Arc<Vec<ConfiguredExecutor>>,
+        hooks: HookSystem,
+    ) -> TetradResult<Self> {
+        let slots = executor_slots(&executors);
+        let consensus = ConsensusEngine::from_config(config.consensus.clone(), seat_count(&slots))?
+            .with_weights(VoteWeights::new(seat_weights(&slots)));
+
+        Ok(Self {
+            config,
+            executors,
+            consensus: Arc::new(consensus),
+            hooks: Arc::new(hooks),
+        })
+    }
+
+    /// Default hooks plus the redaction and webhooks configured in `config`.
+    ///
+    /// Redaction is the first pre_evaluate hook, so every later hook only
+    /// sees the scrubbed request.
+    fn configured_hooks(config: &Config, metrics: &Arc<MetricsHook>) -> TetradResult<HookSystem> {
+        let mut hooks = HookSystem::with_metrics(Arc::clone(metrics));
+        if let Some(redaction) = &config.hooks.redaction {
+            if let Some(hook) = RedactionHook::from_config(redaction)? {
+                hooks.register(Box::new(hook));
+            }
+        }
+        if let Some(webhook) = &config.hooks.webhook {
+            for hook in WebhookHook::from_config(webhook)? {
+                hooks.register(Box::new(hook));
+            }
+        }
+        Ok(hooks)
+    }
+
+    /// Executor configs for `request`: executors its `executors` filter
+    /// leaves out are disabled.
+    fn requested_configs(&self, request: &EvaluationRequest) -> Vec<ExecutorConfig> {
+        self.executors
+            .iter()
+            .map(|(executor, config)| {
+                let mut config = config.clone();
+                if !request_allows(request, executor.name()) {
+                    config.enabled = false;
+                }
+                config
+            })
+            .collect()
+    }
+
+    /// Seats in the vote on `request`.
+    fn requested_seats(&self, request: &EvaluationRequest) -> usize {
+        let configs = self.requested_configs(request);
+        let slots: Vec<ExecutorSlot<'_>> = self
+            .executors
+            .iter()
+            .zip(&configs)
+            .map(|((executor, _), config)| ExecutorSlot::new(executor.as_ref(), config))
+            .collect();
+        seat_count(&slots)
+    }
+}
+
+/// Applies a new configuration to a running [`ToolHandler`].
+///
+/// Obtained with [`ToolHandler::reloader`]; it can be moved to another task
+/// while the handler keeps serving requests.
+#[derive(Clone)]
+pub struct ConfigReloader {
+    state: Arc<StdRwLock<Arc<ToolHandlerState>>>,
+    cache: Arc<RwLock<EvaluationCache>>,
+    metrics: Arc<MetricsHook>,
+    extra_hooks: Arc<StdRwLock<Vec<Arc<dyn Hook>>>>,
+    rebuild_executors: bool,
+}
+
+impl ConfigReloader {
+    /// Rebuilds the consensus engine, executors and hooks from `config` and
+    /// applies its cache parameters.
+    ///
+    /// Everything is built before anything is swapped, so an invalid
+    /// configuration returns an error and the current one stays active.
+    /// Components opened once at startup (ReasoningBank, result store, vote
+    /// cache, concurrency limit, cache persistence) keep their settings until
+    /// the server restarts.
+    pub async fn reload(&self, config: Config) -> TetradResult<()> {
+        let executors = if self.rebuild_executors {
+            Arc::new(build_executors(&config.executors, &config.prompts)?)
+        } else {
+            Arc::clone(&self.current().executors)
+        };
+        let configured = ToolHandlerState::configured_hooks(&config, &self.metrics)?;
+        let built = ToolHandlerState::build(config, executors, HookSystem::new())?;
+
+        self.cache.write().await.reconfigure(&built.config.cache);
+
+        // Hooks added at runtime are read under the state lock, so one added
+        // concurrently is never lost; replace_all keeps the hook statistics
+        let mut current = self.state.write().unwrap_or_else(|e| e.into_inner());
+        let mut hooks = HookSystem::clone(&current.hooks);
+        hooks.replace_all(configured);
+        for hook in self
+            .extra_hooks
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+        {
+            hooks.register_shared(Arc::clone(hook));
+        }
+        let state = ToolHandlerState {
+            hooks: Arc::new(hooks),
+            ..built
+        };
+
+        tracing::info!(
+            rule = ?state.config.consensus.default_rule,
+            min_score = state.config.consensus.min_score,
+            executors = state.executors.iter().filter(|(_, c)| c.enabled).count(),
+            hooks = state.hooks.count(),
+            "Configuration reloaded"
+        );
+        *current = Arc::new(state);
+        Ok(())
+    }
+
+    fn current(&self) -> Arc<ToolHandlerState> {
+        Arc::clone(&self.state.read().unwrap_or_else(|e| e.into_inner()))
+    }
 }
 
 impl ToolHandler {
     /// Creates a new tool handler.
+    ///
+    /// Executors are built from `config.executors` and rebuilt on reload.
+    /// Quarantined executors are read from (and saved to) `quarantine.json`
+    /// in the data directory, so they stay skipped across restarts.
     pub fn new(config: Config) -> TetradResult<Self> {
-        let codex = CodexExecutor::from_config(&config.executors.codex);
-        let gemini = GeminiExecutor::from_config(&config.executors.gemini);
-        let qwen = QwenExecutor::from_config(&config.executors.qwen);
-        let consensus = ConsensusEngine::new(config.consensus.clone());
+        let executors = build_executors(&config.executors, &config.prompts)?;
+        let quarantine = config.data_dir().join(QUARANTINE_FILE_NAME);
+        let mut handler = Self::with_executors(config, executors)?.with_quarantine(&quarantine);
+        handler.rebuild_executors = true;
+        Ok(handler)
+    }
+
+    /// Persists the executor quarantine in `path` and skips the executors
+    /// already in it until their retry time.
+    ///
+    /// Replaces the circuit breaker state, so call it before any evaluation.
+    pub fn with_quarantine(mut self, path: &Path) -> Self {
+        self.health = ExecutorHealth::with_quarantine(path);
+        self
+    }
 
+    /// Creates a tool handler with an explicit list of executors.
+    ///
+    /// `config.executors` is ignored, also on reload; useful for embedding
+    /// and tests.
+    pub fn with_executors(
+        config: Config,
+        executors: Vec<ConfiguredExecutor>,
+    ) -> TetradResult<Self> {
         // Initialize ReasoningBank if enabled
         let reasoning_bank = if config.reasoning.enabled {
-            let bank = ReasoningBank::new(&config.reasoning.db_path)?;
+            let bank =
+                ReasoningBank::new_with_config(&config.reasoning.db_path, &config.reasoning)?;
             Some(bank)
         } else {
             None
         };
 
         // Initialize cache using settings
-        let cache = EvaluationCache::new(
-            config.cache.capacity,
-            Duration::from_secs(config.cache.ttl_secs),
-        );
+        let cache = EvaluationCache::from_config(&config.cache);
+        let vote_cache = VoteCache::from_config(&config.cache.votes).map(Arc::new);
+
+        // The metrics hook is shared so status and tetrad_metrics can read it
+        let metrics = Arc::new(MetricsHook::new());
+
+        let warm_up = Arc::new(WarmUp::new(config.general.warm_up));
+        let certifications = Certifications::from_config(&config.consensus);
+        let results = ResultStore::from_config(&config.mcp)?;
+        let limiter = EvaluationLimiter::from_config(&config.general);
+        let hooks = ToolHandlerState::configured_hooks(&config, &metrics)?;
+        let state = ToolHandlerState::build(config, Arc::new(executors), hooks)?;
 
         Ok(Self {
-            config,
-            codex,
-            gemini,
-            qwen,
-            consensus,
+            state: Arc::new(StdRwLock::new(Arc::new(state))),
+            extra_hooks: Arc::new(StdRwLock::new(Vec::new())),
+            rebuild_executors: false,
+            health: ExecutorHealth::new(),
             reasoning_bank: Arc::new(Mutex::new(reasoning_bank)),
             cache: Arc::new(RwLock::new(cache)),
-            hooks: HookSystem::with_defaults(),
-            confirmations: Arc::new(RwLock::new(HashMap::new())),
+            vote_cache,
+            metrics,
+            certifications: Arc::new(RwLock::new(certifications)),
+            results: Arc::new(Mutex::new(results)),
+            loops: Arc::new(RwLock::new(LruCache::new(
+                NonZeroUsize::new(MAX_TRACKED_LOOPS).unwrap_or(NonZeroUsize::MIN),
+            ))),
+            clock: Arc::new(Utc::now),
+            notifications: None,
+            warm_up,
+            limiter,
+        })
+    }
+
+    /// Sets where progress notifications are sent during evaluations.
+    ///
+    /// Notifications are only sent when `general.progress_notifications` is enabled.
+    pub fn with_notifications(mut self, sink: Arc<dyn NotificationSink>) -> Self {
+        self.notifications = Some(sink);
+        self
+    }
+
+    /// Sets the clock used to expire confirmations and certificates.
+    pub fn with_clock(mut self, clock: impl Fn() -> DateTime<Utc> + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Registers an extra hook alongside the default ones (see [`ToolHandler::add_hook`]).
+    pub fn with_hook(self, hook: Box<dyn Hook>) -> Self {
+        self.add_hook(hook);
+        self
+    }
+
+    /// Registers an extra hook while the handler is serving.
+    ///
+    /// Requests that start afterwards run it; requests already running keep
+    /// their hooks. The hook stays registered across configuration reloads.
+    pub fn add_hook(&self, hook: Box<dyn Hook>) {
+        let hook: Arc<dyn Hook> = Arc::from(hook);
+        self.update_hooks(|hooks, extra| {
+            extra.push(Arc::clone(&hook));
+            hooks.register_shared(hook);
+        });
+    }
+
+    /// Removes every hook named `name`, returning how many were removed.
+    ///
+    /// Requests already running keep their hooks. Default hooks and webhooks
+    /// come from the configuration and come back on the next reload.
+    pub fn remove_hook(&self, name: &str) -> usize {
+        self.update_hooks(|hooks, extra| {
+            extra.retain(|hook| hook.name() != name);
+            hooks.remove(name)
         })
     }
 
+    /// Applies `change` to a copy of the current hooks and swaps it in.
+    ///
+    /// Takes the state lock before the extra hooks, in the same order as
+    /// [`ConfigReloader::reload`].
+    fn update_hooks<R>(
+        &self,
+        change: impl FnOnce(&mut HookSystem, &mut Vec<Arc<dyn Hook>>) -> R,
+    ) -> R {
+        let mut state = self.state.write().unwrap_or_else(|e| e.into_inner());
+        let mut extra = self.extra_hooks.write().unwrap_or_else(|e| e.into_inner());
+
+        let mut hooks = HookSystem::clone(&state.hooks);
+        let result = change(&mut hooks, &mut extra);
+        *state = Arc::new(ToolHandlerState {
+            hooks: Arc::new(hooks),
+            ..ToolHandlerState::clone(&state)
+        });
+        result
+    }
+
+    /// Handle that applies a new configuration while the handler is serving.
+    pub fn reloader(&self) -> ConfigReloader {
+        ConfigReloader {
+            state: Arc::clone(&self.state),
+            cache: Arc::clone(&self.cache),
+            metrics: Arc::clone(&self.metrics),
+            extra_hooks: Arc::clone(&self.extra_hooks),
+            rebuild_executors: self.rebuild_executors,
+        }
+    }
+
+    /// Applies a new configuration (see [`ConfigReloader::reload`]).
+    pub async fn reload(&self, config: Config) -> TetradResult<()> {
+        self.reloader().reload(config).await
+    }
+
+    /// Snapshot of the reloadable components.
+    fn state(&self) -> Arc<ToolHandlerState> {
+        Arc::clone(&self.state.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Starts warming up executors and the ReasoningBank in the background.
+    ///
+    /// Does nothing unless `general.warm_up` is enabled, and only runs once.
+    /// Must be called from within a tokio runtime; the returned handle can be
+    /// awaited to wait for completion.
+    pub fn start_warm_up(&self) -> Option<tokio::task::JoinHandle<()>> {
+        if !self.warm_up.begin() {
+            return None;
+        }
+
+        let state = self.state();
+        Some(tokio::spawn(warmup::run(
+            self.warm_up.clone(),
+            state.executors.clone(),
+            self.reasoning_bank.clone(),
+            state.config.general.warm_up_ping,
+        )))
+    }
+
+    /// Ends the session: runs the on_shutdown hooks and checkpoints the
+    /// ReasoningBank so nothing is left only in its journal.
+    pub async fn shutdown(&self, reason: &str) {
+        self.state().hooks.run_on_shutdown(reason).await;
+
+        let mut bank = self.reasoning_bank.lock().await;
+        if let Some(ref mut b) = *bank {
+            if let Err(e) = b.checkpoint() {
+                tracing::warn!(error = %e, "Failed to checkpoint the ReasoningBank");
+            }
+        }
+    }
+
+    /// Lists the tools this handler serves: [`Self::list_tools`] plus the
+    /// preset reviews when `mcp.expose_presets` is enabled.
+    pub fn available_tools(&self) -> Vec<ToolDescription> {
+        let mut tools = Self::list_tools();
+        if self.state().config.mcp.expose_presets {
+            tools.extend(ReviewPreset::ALL.iter().map(|p| p.tool_description()));
+        }
+        tools
+    }
+
     /// Lists all available tools.
     pub fn list_tools() -> Vec<ToolDescription> {
         vec![
@@ -190,7 +665,7 @@ impl ToolHandler {
                         },
                         "language": {
                             "type": "string",
-                            "description": "Programming language (rust, python, javascript, etc.)"
+                            "description": "Programming language (rust, python, javascript, etc.), or \"auto\" to detect it from file_path and the code"
                         },
                         "file_path": {
                             "type": "string",
@@ -199,11 +674,58 @@ impl ToolHandler {
                         "context": {
                             "type": "string",
                             "description": "Additional context"
+                        },
+                        "previous_request_id": {
+                            "type": "string",
+                            "description": "request_id of the review this code revises; counts refinement loops against max_loops"
+                        },
+                        "executors": {
+                            "type": "array",
+                            "description": "Only these executors vote on this request (e.g. [\"Codex\", \"Qwen\"]); defaults to all enabled executors",
+                            "items": { "type": "string" }
+                        },
+                        "related_files": {
+                            "type": "array",
+                            "description": "Related project files (type definitions, called functions) sent as read-only context",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "path": { "type": "string" },
+                                    "content": { "type": "string" }
+                                },
+                                "required": ["path", "content"]
+                            }
                         }
                     },
                     "required": ["code", "language"]
                 }),
             ),
+            ToolDescription::new(
+                "tetrad_review_diff",
+                "Reviews a unified diff, evaluating only the changed lines. Use BEFORE saving edits to an existing file.",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "diff": {
+                            "type": "string",
+                            "description": "Unified diff of the change (as produced by git diff)"
+                        },
+                        "language": {
+                            "type": "string",
+                            "description": "Programming language (rust, python, javascript, etc.)"
+                        },
+                        "file_path": {
+                            "type": "string",
+                            "description": "File path (optional)"
+                        },
+                        "context": {
+                            "type": "string",
+                            "description": "Additional context"
+                        }
+                    },
+                    "required": ["diff", "language"]
+                }),
+            ),
             ToolDescription::new(
                 "tetrad_review_tests",
                 "Reviews tests before finalizing. Use BEFORE considering tests ready.",
@@ -221,11 +743,51 @@ impl ToolHandler {
                         "context": {
                             "type": "string",
                             "description": "Context about what is being tested"
+                        },
+                        "executors": {
+                            "type": "array",
+                            "description": "Only these executors vote on this request (e.g. [\"Codex\", \"Qwen\"]); defaults to all enabled executors",
+                            "items": { "type": "string" }
                         }
                     },
                     "required": ["tests", "language"]
                 }),
             ),
+            ToolDescription::new(
+                "tetrad_review_docs",
+                "Reviews documentation (docstrings, README, changelog) for accuracy against the code, completeness, working examples and tone. Use BEFORE saving documentation changes.",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "docs": {
+                            "type": "string",
+                            "description": "The documentation text to be reviewed (alias: content)"
+                        },
+                        "doc_type": {
+                            "type": "string",
+                            "enum": ["readme", "api", "changelog"],
+                            "description": "Kind of documentation (optional)"
+                        },
+                        "code": {
+                            "type": "string",
+                            "description": "Code the documentation describes (optional)"
+                        },
+                        "language": {
+                            "type": "string",
+                            "description": "Language of the related code and examples (optional, default: markdown)"
+                        },
+                        "file_path": {
+                            "type": "string",
+                            "description": "File path (optional)"
+                        },
+                        "context": {
+                            "type": "string",
+                            "description": "Additional context"
+                        }
+                    },
+                    "required": ["docs"]
+                }),
+            ),
             ToolDescription::new(
                 "tetrad_confirm",
                 "Confirms that you agree with the feedback received and made the necessary corrections.",
@@ -265,6 +827,19 @@ impl ToolHandler {
                         "previous_request_id": {
                             "type": "string",
                             "description": "Previous evaluation ID for comparison"
+                        },
+                        "strict": {
+                            "type": "boolean",
+                            "description": "Require unanimity (Golden rule), a score of at least 85 and no critical/error findings"
+                        },
+                        "executors": {
+                            "type": "array",
+                            "description": "Only these executors vote on this request (e.g. [\"Codex\", \"Qwen\"]); defaults to all enabled executors",
+                            "items": { "type": "string" }
+                        },
+                        "force": {
+                            "type": "boolean",
+                            "description": "Review again even if identical code already holds a valid certificate"
                         }
                     },
                     "required": ["code", "language"]
@@ -279,91 +854,326 @@ impl ToolHandler {
                     "required": []
                 }),
             ),
-        ]
-    }
-
-    /// Processes a tool call.
-    pub async fn handle_tool_call(&self, name: &str, arguments: Value) -> ToolResult {
-        tracing::info!(tool = name, "Processing tool call");
-
-        match name {
-            "tetrad_review_plan" => self.handle_review_plan(arguments).await,
-            "tetrad_review_code" => self.handle_review_code(arguments).await,
-            "tetrad_review_tests" => self.handle_review_tests(arguments).await,
-            "tetrad_confirm" => self.handle_confirm(arguments).await,
-            "tetrad_final_check" => self.handle_final_check(arguments).await,
-            "tetrad_status" => self.handle_status().await,
-            _ => ToolResult::error(format!("Unknown tool: {}", name)),
-        }
-    }
-
-    // ═══════════════════════════════════════════════════════════════════════
-    // Individual handlers
-    // ═══════════════════════════════════════════════════════════════════════
-
-    async fn handle_review_plan(&self, arguments: Value) -> ToolResult {
-        let params: ReviewPlanParams = match serde_json::from_value(arguments) {
-            Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
-        };
-
-        let mut request =
-            EvaluationRequest::new(&params.plan, "text").with_type(EvaluationType::Plan);
-
-        if let Some(ctx) = params.context {
-            request = request.with_context(&ctx);
-        }
-
-        self.evaluate_request(request).await
-    }
-
-    async fn handle_review_code(&self, arguments: Value) -> ToolResult {
-        let params: ReviewCodeParams = match serde_json::from_value(arguments) {
-            Ok(p) => p,
-            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
-        };
-
-        // Verifica cache
-        {
-            let mut cache = self.cache.write().await;
-            if let Some(cached) =
-                cache.get_by_code(&params.code, &params.language, &EvaluationType::Code)
-            {
-                tracing::info!("Cache hit for review_code");
-                return self.format_result(cached);
-            }
-        }
+            ToolDescription::new(
+                "tetrad_metrics",
+                "Shows evaluation metrics since the server started: decisions, success rate, average score, a breakdown per evaluation type and per-hook executions, failures and latency.",
+                json!({
+                    "type": "object",
+                    "properties": {},
+                    "required": []
+                }),
+            ),
+            ToolDescription::new(
+                "tetrad_estimate",
+                "Estimates how long a review would take and what it would cost, without calling any evaluator. Use it to decide whether a trivial edit is worth a full review.",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "code": {
+                            "type": "string",
+                            "description": "The code (or plan, tests, docs, diff) that would be reviewed"
+                        },
+                        "language": {
+                            "type": "string",
+                            "description": "Programming language"
+                        },
+                        "evaluation_type": {
+                            "type": "string",
+                            "enum": ["plan", "code", "tests", "final_check", "documentation", "diff"],
+                            "description": "Review type (default: code)"
+                        },
+                        "file_path": {
+                            "type": "string",
+                            "description": "File path (optional)"
+                        }
+                    },
+                    "required": ["code", "language"]
+                }),
+            ),
+            ToolDescription::new(
+                "tetrad_explain",
+                "Explains why a previous review reached its decision: each evaluator's vote and reasoning, the consensus rule and the exact condition that triggered the decision, what changed it afterwards, confidence, the ReasoningBank patterns that matched and whether the result came from the cache. Use it after a BLOCK or REVISE.",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "request_id": {
+                            "type": "string",
+                            "description": "request_id returned by the review to explain"
+                        }
+                    },
+                    "required": ["request_id"]
+                }),
+            ),
+            ToolDescription::new(
+                "tetrad_get_patterns",
+                "Lists what the ReasoningBank already knows about a piece of code: anti-patterns and good patterns learned from earlier reviews of the same or similar code, with their solutions and confidence. Does not run a review. Use it before writing code to avoid repeating known mistakes.",
+                json!({
+                    "type": "object",
+                    "properties": {
+                        "code": {
+                            "type": "string",
+                            "description": "The code to look up"
+                        },
+                        "language": {
+                            "type": "string",
+                            "description": "Programming language"
+                        },
+                        "limit": {
+                            "type": "integer",
+                            "minimum": 0,
+                            "description": "Maximum number of patterns (capped by reasoning.max_patterns_per_query)"
+                        }
+                    },
+                    "required": ["code", "language"]
+                }),
+            ),
+        ]
+    }
+
+    /// Reads a resource advertised by [`resources::list_resources`].
+    ///
+    /// Returns `None` for unknown URIs.
+    pub async fn read_resource(&self, uri: &str) -> Option<ResourceContents> {
+        match uri {
+            resources::KNOWLEDGE_SUMMARY_URI | resources::KNOWLEDGE_ANTIPATTERNS_URI => {
+                let knowledge = {
+                    let bank = self.reasoning_bank.lock().await;
+                    bank.as_ref().map(|b| b.distill())
+                };
+                Some(resources::knowledge_contents(uri, knowledge.as_ref()))
+            }
+            resources::DIGEST_URI => {
+                let digest = {
+                    let bank = self.reasoning_bank.lock().await;
+                    bank.as_ref().map(|b| b.digest())
+                };
+                Some(resources::digest_contents(digest))
+            }
+            resources::CONFIG_URI => Some(resources::config_contents(&self.state().config)),
+            _ => None,
+        }
+    }
+
+    /// Processes a tool call.
+    pub async fn handle_tool_call(&self, name: &str, arguments: Value) -> ToolResult {
+        self.handle_tool_call_with_progress(name, arguments, None)
+            .await
+    }
+
+    /// Processes a tool call, reporting progress under `progress_token`.
+    ///
+    /// Progress is only reported when a token is given, a notification sink
+    /// is set and `general.progress_notifications` is enabled.
+    pub async fn handle_tool_call_with_progress(
+        &self,
+        name: &str,
+        arguments: Value,
+        progress_token: Option<JsonRpcId>,
+    ) -> ToolResult {
+        tracing::info!(tool = name, "Processing tool call");
+
+        let config = &self.state().config;
+        let progress = match (&self.notifications, progress_token) {
+            (Some(sink), Some(token)) if config.general.progress_notifications => {
+                ProgressReporter::new(sink.clone(), token)
+            }
+            _ => ProgressReporter::disabled(),
+        };
+
+        // Preset tools only exist when exposed
+        let preset = ReviewPreset::from_tool_name(name).filter(|_| config.mcp.expose_presets);
+        if let Some(preset) = preset {
+            return self
+                .handle_preset_review(preset, arguments, &progress)
+                .await;
+        }
+
+        match name {
+            "tetrad_review_plan" => self.handle_review_plan(arguments, &progress).await,
+            "tetrad_review_code" => self.handle_review_code(arguments, &progress).await,
+            "tetrad_review_diff" => self.handle_review_diff(arguments, &progress).await,
+            "tetrad_review_tests" => self.handle_review_tests(arguments, &progress).await,
+            "tetrad_review_docs" => self.handle_review_docs(arguments, &progress).await,
+            "tetrad_confirm" => self.handle_confirm(arguments).await,
+            "tetrad_final_check" => self.handle_final_check(arguments, &progress).await,
+            "tetrad_status" => self.handle_status().await,
+            "tetrad_metrics" => self.handle_metrics(),
+            "tetrad_estimate" => self.handle_estimate(arguments).await,
+            "tetrad_explain" => self.handle_explain(arguments).await,
+            "tetrad_get_patterns" => self.handle_get_patterns(arguments).await,
+            _ => ToolResult::error(format!("Unknown tool: {}", name)),
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Individual handlers
+    // ═══════════════════════════════════════════════════════════════════════
+
+    async fn handle_review_plan(
+        &self,
+        arguments: Value,
+        progress: &ProgressReporter,
+    ) -> ToolResult {
+        let params: ReviewPlanParams = match serde_json::from_value(arguments) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
 
         let mut request =
-            EvaluationRequest::new(&params.code, &params.language).with_type(EvaluationType::Code);
+            EvaluationRequest::new(&params.plan, "text").with_type(EvaluationType::Plan);
+
+        if let Some(ctx) = params.context {
+            request = request.with_context(&ctx);
+        }
+
+        self.evaluate_request(request, progress).await
+    }
+
+    async fn handle_review_code(
+        &self,
+        arguments: Value,
+        progress: &ProgressReporter,
+    ) -> ToolResult {
+        let (request, previous_request_id) = match self.code_review_request(arguments) {
+            Ok(request) => request,
+            Err(error) => return error,
+        };
+
+        let refinement_loop = self.next_loop(previous_request_id.as_deref()).await;
+        match self
+            .evaluate_internal_with(request, progress, None, None, refinement_loop)
+            .await
+        {
+            Ok(result) => self.format_result(&result),
+            Err(e) => evaluation_error(e),
+        }
+    }
+
+    /// Builds the request for `tetrad_review_code` and the preset reviews,
+    /// with the `previous_request_id` it revises.
+    ///
+    /// Related files are fitted to `general.max_context_bytes`; requests
+    /// that cannot fit are rejected.
+    fn code_review_request(
+        &self,
+        arguments: Value,
+    ) -> Result<(EvaluationRequest, Option<String>), ToolResult> {
+        let params: ReviewCodeParams = serde_json::from_value(arguments)
+            .map_err(|e| ToolResult::error(format!("Invalid parameters: {}", e)))?;
+
+        let related_files = fit_related_files(
+            params.related_files,
+            self.state().config.general.max_context_bytes,
+        )
+        .map_err(|e| ToolResult::error(e.to_string()))?;
+
+        // "auto" detects the language from the file name and the code
+        let language = if params.language.eq_ignore_ascii_case("auto") {
+            PatternMatcher::detect_language_with_confidence(
+                &params.code,
+                params.file_path.as_deref(),
+            )
+            .0
+        } else {
+            params.language
+        };
+
+        let mut request = EvaluationRequest::new(&params.code, &language)
+            .with_type(EvaluationType::Code)
+            .with_related_files(related_files);
 
-        if let Some(fp) = params.file_path.clone() {
+        if let Some(fp) = params.file_path {
             request = request.with_file_path(&fp);
         }
-        if let Some(ctx) = params.context.clone() {
+        if let Some(ctx) = params.context {
             request = request.with_context(&ctx);
         }
+        if let Some(executors) = self.executor_filter(params.executors)? {
+            request = request.with_executors(executors);
+        }
+        Ok((request, params.previous_request_id))
+    }
 
-        // Executa avaliação internamente para poder cachear o resultado
-        match self.evaluate_internal(request).await {
-            Ok(eval_result) => {
-                // Armazena em cache
-                {
-                    let mut cache = self.cache.write().await;
-                    cache.insert_by_code(
-                        &params.code,
-                        &params.language,
-                        &EvaluationType::Code,
-                        eval_result.clone(),
-                    );
-                }
-                self.format_result(&eval_result)
-            }
-            Err(e) => ToolResult::error(format!("Evaluation failed: {}", e)),
+    /// Validates a request's `executors` filter against the configured
+    /// executors, returning their configured names.
+    ///
+    /// Unknown names are rejected, and so is a filter that leaves no enabled
+    /// executor to vote.
+    fn executor_filter(
+        &self,
+        requested: Option<Vec<String>>,
+    ) -> Result<Option<Vec<String>>, ToolResult> {
+        let Some(requested) = requested else {
+            return Ok(None);
+        };
+        if requested.is_empty() {
+            return Err(ToolResult::error(
+                "executors must name at least one executor",
+            ));
+        }
+
+        let state = self.state();
+        let known: Vec<&str> = state.executors.iter().map(|(e, _)| e.name()).collect();
+        if let Some(unknown) = requested
+            .iter()
+            .find(|name| !known.iter().any(|k| k.eq_ignore_ascii_case(name)))
+        {
+            return Err(ToolResult::error(format!(
+                "Unknown executor '{}'; known executors: {}",
+                unknown,
+                known.join(", ")
+            )));
         }
+        // Configured order and spelling, so equivalent filters share a cache entry
+        let names: Vec<String> = known
+            .into_iter()
+            .filter(|k| requested.iter().any(|name| k.eq_ignore_ascii_case(name)))
+            .map(str::to_string)
+            .collect();
+
+        // Fallbacks only vote in a primary's seat
+        let can_vote = state.executors.iter().any(|(executor, config)| {
+            config.enabled
+                && config.fallback_for.is_none()
+                && names.iter().any(|n| n == executor.name())
+        });
+        if !can_vote {
+            return Err(ToolResult::error(format!(
+                "None of the requested executors can vote: {} (disabled or fallback only)",
+                names.join(", ")
+            )));
+        }
+        Ok(Some(names))
+    }
+
+    async fn handle_review_diff(
+        &self,
+        arguments: Value,
+        progress: &ProgressReporter,
+    ) -> ToolResult {
+        let params: ReviewDiffParams = match serde_json::from_value(arguments) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let mut request =
+            EvaluationRequest::new(&params.diff, &params.language).with_type(EvaluationType::Diff);
+
+        if let Some(fp) = params.file_path {
+            request = request.with_file_path(&fp);
+        }
+        if let Some(ctx) = params.context {
+            request = request.with_context(&ctx);
+        }
+
+        self.evaluate_request(request, progress).await
     }
 
-    async fn handle_review_tests(&self, arguments: Value) -> ToolResult {
+    async fn handle_review_tests(
+        &self,
+        arguments: Value,
+        progress: &ProgressReporter,
+    ) -> ToolResult {
         let params: ReviewTestsParams = match serde_json::from_value(arguments) {
             Ok(p) => p,
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
@@ -375,8 +1185,83 @@ impl ToolHandler {
         if let Some(ctx) = params.context {
             request = request.with_context(&ctx);
         }
+        match self.executor_filter(params.executors) {
+            Ok(Some(executors)) => request = request.with_executors(executors),
+            Ok(None) => {}
+            Err(error) => return error,
+        }
+
+        self.evaluate_request(request, progress).await
+    }
+
+    async fn handle_review_docs(
+        &self,
+        arguments: Value,
+        progress: &ProgressReporter,
+    ) -> ToolResult {
+        let params: ReviewDocsParams = match serde_json::from_value(arguments) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let language = params.language.as_deref().unwrap_or("markdown");
+
+        let mut request =
+            EvaluationRequest::new(&params.docs, language).with_type(EvaluationType::Documentation);
+
+        if let Some(doc_type) = params.doc_type {
+            request = request.with_doc_type(doc_type);
+        }
+        if let Some(code) = params.code {
+            request = request.with_related_code(code);
+        }
+        if let Some(fp) = params.file_path {
+            request = request.with_file_path(&fp);
+        }
+        if let Some(ctx) = params.context {
+            request = request.with_context(&ctx);
+        }
+
+        self.evaluate_request(request, progress).await
+    }
 
-        self.evaluate_request(request).await
+    /// Reviews code with a preset's fixed settings.
+    ///
+    /// Takes the same parameters as `tetrad_review_code`; the response also
+    /// carries the preset's `effective_settings`.
+    async fn handle_preset_review(
+        &self,
+        preset: ReviewPreset,
+        arguments: Value,
+        progress: &ProgressReporter,
+    ) -> ToolResult {
+        let (request, previous_request_id) = match self.code_review_request(arguments) {
+            Ok(request) => request,
+            Err(error) => return error,
+        };
+
+        let consensus = self.state().config.consensus.clone();
+        let strict = preset.strict_settings(&consensus);
+        let refinement_loop = self.next_loop(previous_request_id.as_deref()).await;
+        let result = self
+            .evaluate_internal_with(
+                request,
+                progress,
+                strict.as_ref(),
+                Some(preset),
+                refinement_loop,
+            )
+            .await;
+
+        match result {
+            Ok(result) => {
+                let mut response = self.result_json(&result);
+                response["preset"] = json!(preset);
+                response["effective_settings"] = json!(preset.effective_settings(&consensus));
+                ToolResult::success_json(&response)
+            }
+            Err(e) => evaluation_error(e),
+        }
     }
 
     async fn handle_confirm(&self, arguments: Value) -> ToolResult {
@@ -386,15 +1271,17 @@ impl ToolHandler {
         };
 
         // Registra confirmação
-        {
-            let mut confirmations = self.confirmations.write().await;
-            confirmations.insert(params.request_id.clone(), params.agreed);
-        }
+        let confirmation = self.certifications.write().await.confirm(
+            &params.request_id,
+            params.agreed,
+            (self.clock)(),
+        );
 
         let response = json!({
             "confirmed": true,
             "request_id": params.request_id,
             "agreed": params.agreed,
+            "expires_at": confirmation.expires_at,
             "notes": params.notes,
             "can_proceed": params.agreed,
             "message": if params.agreed {
@@ -407,186 +1294,734 @@ impl ToolHandler {
         ToolResult::success_json(&response)
     }
 
-    async fn handle_final_check(&self, arguments: Value) -> ToolResult {
+    async fn handle_final_check(
+        &self,
+        arguments: Value,
+        progress: &ProgressReporter,
+    ) -> ToolResult {
         let params: FinalCheckParams = match serde_json::from_value(arguments) {
             Ok(p) => p,
             Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
         };
 
-        // Verifica se há confirmação prévia do previous_request_id
-        let previous_confirmed = if let Some(ref prev_id) = params.previous_request_id {
-            let confirmations = self.confirmations.read().await;
-            confirmations.get(prev_id).copied().unwrap_or(false)
-        } else {
-            false
+        // Verifica se há confirmação prévia (e ainda válida) do previous_request_id
+        let confirmation_status = match params.previous_request_id {
+            Some(ref prev_id) => Some(
+                self.certifications
+                    .read()
+                    .await
+                    .confirmation_status(prev_id, (self.clock)()),
+            ),
+            None => None,
+        };
+        let previous_confirmed = confirmation_status == Some(ConfirmationStatus::Confirmed);
+
+        // The previous review is compared with this check when it is still stored
+        let previous_result = match params.previous_request_id {
+            Some(ref prev_id) => self.results.lock().await.get(prev_id).unwrap_or_else(|e| {
+                tracing::warn!(request_id = %prev_id, error = %e, "Failed to load previous result");
+                None
+            }),
+            None => None,
         };
 
-        let request = EvaluationRequest::new(&params.code, &params.language)
+        let mut request = EvaluationRequest::new(&params.code, &params.language)
             .with_type(EvaluationType::FinalCheck);
+        match self.executor_filter(params.executors) {
+            Ok(Some(executors)) => request = request.with_executors(executors),
+            Ok(None) => {}
+            Err(error) => return error,
+        }
+
+        // An explicit `strict` parameter wins over the configured default
+        let state = self.state();
+        let strict = state.consensus.strict_settings(params.strict);
+
+        // Identical code already certified with the same settings and
+        // executor versions is not sent to the executors again; a given
+        // previous_request_id must still be confirmed
+        let certified_code = CertifiedCode {
+            code_hash: code_hash(&params.code, &params.language),
+            settings: CertifiedSettings::new(&state.config.consensus, strict.as_ref()),
+            executor_versions: executor_versions(&state, request.executors.as_deref()).await,
+        };
+        if params.force {
+            // A forced check must reach the executors, not the cached result
+            // of the code or of any of its chunks
+            let chunks = chunk_requests(&state.config, &request)
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+            let mut cache = self.cache.write().await;
+            for part in std::iter::once(&request).chain(chunks.iter().map(|(_, part)| part)) {
+                let key = EvaluationCache::cache_key(
+                    &cache_content(part, strict.as_ref(), None),
+                    part.language.as_str(),
+                    &part.evaluation_type,
+                );
+                cache.invalidate(&key);
+            }
+        } else if params.previous_request_id.is_none() || previous_confirmed {
+            let original = self
+                .certifications
+                .read()
+                .await
+                .find_certified(&certified_code, (self.clock)())
+                .cloned();
+            if let Some(original) = original {
+                self.metrics.record_recertification();
+                if let Some(ref mut b) = *self.reasoning_bank.lock().await {
+                    if let Err(e) = b.record_recertification() {
+                        tracing::warn!("Error recording metrics: {}", e);
+                    }
+                }
+                tracing::info!(
+                    certificate_id = %original.id,
+                    "Final check re-certified without running executors"
+                );
+                return ToolResult::success_json(&json!({
+                    "certified": true,
+                    "recertified": true,
+                    "decision": "Pass",
+                    "score": original.score,
+                    "previous_request_id": params.previous_request_id,
+                    "certificate_id": original.id,
+                    "original_request_id": original.request_id,
+                    "certified_at": original.issued_at,
+                    "certificate_expires_at": original.expires_at,
+                    "strict": strict,
+                    "build": BuildInfo::current(),
+                    "message": format!(
+                        "RE-CERTIFIED: Code is identical to the code certified by {}; no executors were run. Pass force: true to review it again.",
+                        original.id
+                    )
+                }));
+            }
+        }
 
-        let result = self.evaluate_internal(request).await;
+        let refinement_loop = self.next_loop(params.previous_request_id.as_deref()).await;
+        let result = self
+            .evaluate_internal_with(request, progress, strict.as_ref(), None, refinement_loop)
+            .await;
 
         match result {
             Ok(eval_result) => {
+                let strict_violation = strict_violation(&eval_result, strict.as_ref());
+
                 // Certificação requer: consenso + score mínimo + confirmação prévia (se fornecida)
-                let meets_requirements = eval_result.consensus_achieved
-                    && eval_result.score >= self.config.consensus.min_score;
+                let meets_requirements =
+                    meets_final_requirements(&state.config, &eval_result, strict.as_ref());
 
-                // Se previous_request_id foi fornecido, exige confirmação
+                let comparison = previous_result
+                    .as_ref()
+                    .map(|previous| ResultComparison::new(previous, &eval_result));
+                let critical_remaining = comparison.as_ref().is_some_and(|c| c.critical_remaining);
+
+                // Se previous_request_id foi fornecido, exige confirmação e que
+                // nenhum finding crítico da revisão anterior continue presente
                 let certified = if params.previous_request_id.is_some() {
-                    meets_requirements && previous_confirmed
+                    meets_requirements && previous_confirmed && !critical_remaining
                 } else {
                     meets_requirements
                 };
 
-                let message = if certified {
+                let message = if certified && strict.is_some() {
+                    "CERTIFIED (strict): Code approved unanimously by Tetrad's quadruple consensus."
+                } else if certified {
                     "CERTIFIED: Code approved by Tetrad's quadruple consensus."
+                } else if eval_result.loop_limit_exceeded {
+                    "NOT CERTIFIED: The review exceeded consensus.max_loops refinement loops."
+                } else if strict_violation {
+                    "NOT CERTIFIED: Strict mode requires zero critical or error findings."
                 } else if !meets_requirements {
                     "NOT CERTIFIED: Code did not reach consensus or minimum score."
+                } else if critical_remaining {
+                    "NOT CERTIFIED: Critical findings from the previous review remain."
+                } else if confirmation_status == Some(ConfirmationStatus::Expired) {
+                    "NOT CERTIFIED: Prior confirmation expired. Use tetrad_confirm again."
                 } else {
                     "NOT CERTIFIED: Prior confirmation pending. Use tetrad_confirm first."
                 };
 
-                let response = json!({
+                let certificate = if certified {
+                    Some(self.certifications.write().await.issue(
+                        &eval_result.request_id,
+                        certified_code,
+                        eval_result.score,
+                        (self.clock)(),
+                    ))
+                } else {
+                    None
+                };
+
+                let mut response = json!({
                     "certified": certified,
+                    "recertified": false,
                     "decision": format!("{:?}", eval_result.decision),
                     "score": eval_result.score,
                     "consensus_achieved": eval_result.consensus_achieved,
                     "previous_request_id": params.previous_request_id,
                     "previous_confirmed": previous_confirmed,
-                    "certificate_id": if certified {
-                        Some(format!("TETRAD-{}", eval_result.request_id))
-                    } else {
-                        None
-                    },
+                    "confirmation_status": confirmation_status,
+                    "comparison": comparison,
+                    "certificate_id": certificate.as_ref().map(|c| &c.id),
+                    "certificate_expires_at": certificate.as_ref().and_then(|c| c.expires_at),
+                    "strict": strict,
+                    "build": BuildInfo::current(),
                     "feedback": eval_result.feedback,
                     "findings_count": eval_result.findings.len(),
                     "message": message
                 });
+                if let Some(selection) = &eval_result.executor_selection {
+                    response["executors"] = json!(selection);
+                }
+                self.add_loop_fields(&mut response, &eval_result);
 
                 ToolResult::success_json(&response)
             }
-            Err(e) => ToolResult::error(format!("Evaluation failed: {}", e)),
+            Err(e) => evaluation_error(e),
         }
     }
 
     async fn handle_status(&self) -> ToolResult {
-        let codex_available = self.codex.is_available().await;
-        let gemini_available = self.gemini.is_available().await;
-        let qwen_available = self.qwen.is_available().await;
-
-        let codex_version = if codex_available {
-            self.codex
-                .version()
-                .await
-                .unwrap_or_else(|_| "unknown".to_string())
-        } else {
-            "unavailable".to_string()
-        };
+        let state = self.state();
+        let config = &state.config;
+        let mut executors = serde_json::Map::new();
 
-        let gemini_version = if gemini_available {
-            self.gemini
-                .version()
-                .await
-                .unwrap_or_else(|_| "unknown".to_string())
-        } else {
-            "unavailable".to_string()
-        };
+        let quarantined = self.health.quarantined();
+        for (executor, executor_config) in state.executors.iter() {
+            let available = executor.is_available().await;
+            let version = if available {
+                executor
+                    .version()
+                    .await
+                    .unwrap_or_else(|_| "unknown".to_string())
+            } else {
+                "unavailable".to_string()
+            };
 
-        let qwen_version = if qwen_available {
-            self.qwen
-                .version()
-                .await
-                .unwrap_or_else(|_| "unknown".to_string())
-        } else {
-            "unavailable".to_string()
-        };
+            executors.insert(
+                executor.name().to_lowercase(),
+                json!({
+                    "available": available,
+                    "version": version,
+                    "specialization": executor.specialization(),
+                    "enabled": executor_config.enabled,
+                    "fallback_for": executor_config.fallback_for,
+                    "health": self.health.state(executor.name(), executor_config).to_string(),
+                    "quarantine": quarantined.iter().find(|e| e.executor == executor.name()),
+                    "text_fallback": self.health.text_fallback_accuracy(executor.name())
+                }),
+            );
+        }
 
         let cache_stats = {
             let cache = self.cache.read().await;
             cache.stats()
         };
+        // Vote-level cache, reported apart from the result cache; null when disabled
+        let vote_cache = self.vote_cache.as_ref().map(|cache| {
+            let stats = cache.stats();
+            json!({
+                "size": stats.size,
+                "capacity": stats.capacity,
+                "expired": stats.expired,
+                "hits": stats.hits,
+                "misses": stats.misses,
+                "hit_rate": format!("{:.1}%", stats.hit_rate() * 100.0)
+            })
+        });
 
-        let response = json!({
-            "codex": {
-                "available": codex_available,
-                "version": codex_version,
-                "specialization": self.codex.specialization(),
-                "enabled": self.config.executors.codex.enabled
-            },
-            "gemini": {
-                "available": gemini_available,
-                "version": gemini_version,
-                "specialization": self.gemini.specialization(),
-                "enabled": self.config.executors.gemini.enabled
-            },
-            "qwen": {
-                "available": qwen_available,
-                "version": qwen_version,
-                "specialization": self.qwen.specialization(),
-                "enabled": self.config.executors.qwen.enabled
-            },
+        // Remaining monthly budget; null when the ReasoningBank is disabled
+        let budget = {
+            let bank = self.reasoning_bank.lock().await;
+            bank.as_ref()
+                .and_then(|b| b.budget_status(&config.general.budget, (self.clock)()).ok())
+        };
+
+        let mut response = json!({
+            "build": BuildInfo::current(),
             "consensus": {
-                "rule": format!("{:?}", self.config.consensus.default_rule),
-                "min_score": self.config.consensus.min_score,
-                "max_loops": self.config.consensus.max_loops
+                "rule": format!("{:?}", config.consensus.default_rule),
+                "min_score": config.consensus.min_score,
+                "max_loops": config.consensus.max_loops
             },
             "cache": {
                 "size": cache_stats.size,
                 "capacity": cache_stats.capacity,
-                "hit_rate": format!("{:.1}%", cache_stats.hit_rate() * 100.0)
+                "expired": cache_stats.expired,
+                "hit_rate": format!("{:.1}%", cache_stats.hit_rate() * 100.0),
+                "votes": vote_cache
             },
             "reasoning_bank": {
-                "enabled": self.config.reasoning.enabled
-            }
+                "enabled": config.reasoning.enabled
+            },
+            "budget": budget,
+            "metrics": self.metrics.metrics(),
+            "hooks": state.hooks.stats(),
+            "warm_up": self.warm_up.status(),
+            "concurrency": self.limiter.status()
         });
 
+        // Executors are listed at the top level, keyed by lowercase name
+        if let Some(object) = response.as_object_mut() {
+            object.extend(executors);
+        }
+
         ToolResult::success_json(&response)
     }
 
-    // ═══════════════════════════════════════════════════════════════════════
-    // Helper methods
-    // ═══════════════════════════════════════════════════════════════════════
-
-    /// Executes an evaluation and returns formatted result.
-    async fn evaluate_request(&self, request: EvaluationRequest) -> ToolResult {
-        match self.evaluate_internal(request).await {
-            Ok(result) => self.format_result(&result),
-            Err(e) => ToolResult::error(format!("Evaluation failed: {}", e)),
-        }
+    fn handle_metrics(&self) -> ToolResult {
+        let mut response = json!(self.metrics.metrics());
+        response["hooks"] = json!(self.state().hooks.stats());
+        ToolResult::success_json(&response)
     }
 
-    /// Executes the internal evaluation.
-    async fn evaluate_internal(
-        &self,
-        request: EvaluationRequest,
-    ) -> TetradResult<EvaluationResult> {
-        // Run pre_evaluate hooks
-        let hook_result = self.hooks.run_pre_evaluate(&request).await?;
+    /// Handler for tetrad_explain.
+    ///
+    /// Explains a stored result: the rule condition behind the decision,
+    /// what changed the decision afterwards, each executor's vote and the
+    /// ReasoningBank patterns that matched. Pattern statistics are the
+    /// current ones while the pattern is still in the bank, and those at
+    /// evaluation time otherwise.
+    async fn handle_explain(&self, arguments: Value) -> ToolResult {
+        let params: ExplainParams = match serde_json::from_value(arguments) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
 
-        // Handle hook result
-        let request = match hook_result {
-            crate::hooks::HookResult::Skip => {
-                // Return skip result
-                return Ok(EvaluationResult::success(
-                    &request.request_id,
-                    100,
-                    "Skipped by hook",
-                ));
+        let result = match self.results.lock().await.get(&params.request_id) {
+            Ok(Some(result)) => result,
+            Ok(None) => {
+                return ToolResult::error(format!(
+                    "Unknown request_id: {}. Only stored results can be explained (the last mcp.results_capacity, or all of them with mcp.results_db_path).",
+                    params.request_id
+                ))
             }
-            crate::hooks::HookResult::ModifyRequest(modified) => {
-                // Use the modified request from hook
-                tracing::info!("Request modified by pre_evaluate hook");
+            Err(e) => {
+                return ToolResult::error(format!(
+                    "Failed to load result {}: {}",
+                    params.request_id, e
+                ))
+            }
+        };
+
+        let drivers = result
+            .explanation
+            .as_ref()
+            .map(|e| e.drivers.as_slice())
+            .unwrap_or_default();
+        let mut votes: Vec<(&String, &ModelVote)> = result.votes.iter().collect();
+        votes.sort_by_key(|(name, _)| *name);
+        let votes: Vec<Value> = votes
+            .into_iter()
+            .map(|(name, vote)| {
+                json!({
+                    "executor": name,
+                    "vote": format!("{:?}", vote.vote),
+                    "score": vote.score,
+                    "reasoning": vote.reasoning,
+                    "issues": vote.issues,
+                    "suggestions": vote.suggestions,
+                    "drove_decision": drivers.contains(name)
+                })
+            })
+            .collect();
+
+        let patterns: Vec<Value> = {
+            let bank = self.reasoning_bank.lock().await;
+            result
+                .matched_patterns
+                .iter()
+                .map(|matched| {
+                    let mut pattern = json!(matched);
+                    let current = bank
+                        .as_ref()
+                        .and_then(|b| b.get_pattern(matched.id).ok().flatten());
+                    match current {
+                        Some(current) => {
+                            pattern["confidence"] = json!(current.confidence);
+                            pattern["success_count"] = json!(current.success_count);
+                            pattern["failure_count"] = json!(current.failure_count);
+                            pattern["stats"] = json!("current");
+                        }
+                        None => pattern["stats"] = json!("at_evaluation"),
+                    }
+                    pattern
+                })
+                .collect()
+        };
+
+        let mut response = json!({
+            "request_id": result.request_id,
+            "decision": format!("{:?}", result.decision),
+            "score": result.score,
+            "confidence": result.confidence,
+            "consensus_achieved": result.consensus_achieved,
+            "from_cache": result.from_cache,
+            "evaluated_at": result.timestamp,
+            "rule": result.explanation,
+            "decision_changed_after_rule": result
+                .explanation
+                .as_ref()
+                .is_some_and(|e| e.decision != result.decision),
+            "adjustments": decision_adjustments(&result),
+            "votes": votes,
+            "patterns": patterns,
+            "findings_count": result.findings.len()
+        });
+        if !result.chunks.is_empty() {
+            response["chunks"] = json!(result.chunks);
+        }
+        ToolResult::success_json(&response)
+    }
+
+    /// Handler for tetrad_get_patterns.
+    ///
+    /// Runs the ReasoningBank retrieval a review would run, without the
+    /// review. With reasoning disabled the list is empty.
+    async fn handle_get_patterns(&self, arguments: Value) -> ToolResult {
+        let params: GetPatternsParams = match serde_json::from_value(arguments) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let (enabled, mut matches) = {
+            let bank = self.reasoning_bank.lock().await;
+            match bank.as_ref() {
+                Some(bank) => (true, bank.retrieve(&params.code, &params.language)),
+                None => (false, Vec::new()),
+            }
+        };
+        // The bank already stops at max_patterns_per_query
+        if let Some(limit) = params.limit {
+            matches.truncate(limit);
+        }
+
+        let patterns: Vec<Value> = matches
+            .iter()
+            .map(|matched| {
+                json!({
+                    "pattern_type": matched.pattern.pattern_type,
+                    "issue_category": matched.pattern.issue_category,
+                    "description": matched.pattern.description,
+                    "solution": matched.pattern.solution,
+                    "confidence": matched.pattern.confidence,
+                    "match_type": matched.match_type,
+                    "relevance": matched.relevance
+                })
+            })
+            .collect();
+        ToolResult::success_json(&json!({
+            "reasoning_enabled": enabled,
+            "count": patterns.len(),
+            "patterns": patterns
+        }))
+    }
+
+    /// Handler for tetrad_estimate.
+    async fn handle_estimate(&self, arguments: Value) -> ToolResult {
+        let params: EstimateParams = match serde_json::from_value(arguments) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(format!("Invalid parameters: {}", e)),
+        };
+
+        let mut request = EvaluationRequest::new(&params.code, &params.language)
+            .with_type(params.evaluation_type.unwrap_or(EvaluationType::Code));
+        if let Some(fp) = params.file_path {
+            request = request.with_file_path(&fp);
+        }
+
+        ToolResult::success_json(&json!(self.estimate(&request).await))
+    }
+
+    /// Estimates latency and cost of reviewing `request` without calling any executor.
+    ///
+    /// Executors whose circuit is open are skipped (their fallback takes the
+    /// seat), as are fallbacks of healthy primaries. Latency is the rolling
+    /// average of recent evaluations, or a prompt-size heuristic without
+    /// history. The result cache is checked with the exact key of a plain review.
+    pub async fn estimate(&self, request: &EvaluationRequest) -> ReviewEstimate {
+        let state = self.state();
+        let configs: Vec<_> = state
+            .executors
+            .iter()
+            .map(|(executor, config)| {
+                let mut config = config.clone();
+                let state = self.health.state(executor.name(), &config);
+                if matches!(state, CircuitState::Open { .. }) {
+                    config.enabled = false;
+                }
+                config
+            })
+            .collect();
+        let slots: Vec<ExecutorSlot<'_>> = state
+            .executors
+            .iter()
+            .zip(&configs)
+            .map(|((executor, _), config)| ExecutorSlot::new(executor.as_ref(), config))
+            .collect();
+
+        // Each seat is answered by its primary, or by the fallback when the
+        // primary is disabled or its circuit is open
+        let callers: Vec<&str> = seat_names(&slots)
+            .into_iter()
+            .filter_map(|primary| {
+                let enabled = || slots.iter().filter(|slot| slot.config.enabled);
+                enabled()
+                    .find(|slot| slot.executor.name() == primary)
+                    .or_else(|| {
+                        enabled().find(|slot| {
+                            slot.config
+                                .fallback_for
+                                .as_deref()
+                                .is_some_and(|p| p.eq_ignore_ascii_case(primary))
+                        })
+                    })
+                    .map(|slot| slot.executor.name())
+            })
+            .collect();
+
+        let cost = state.config.general.budget.cost_per_call;
+        let executors = state
+            .executors
+            .iter()
+            .map(|(executor, config)| {
+                let name = executor.name();
+                let prompt = executor.build_prompt(request);
+                let prompt_tokens = estimate_tokens(&prompt);
+                let (latency, latency_source) = match self.health.average_latency(name) {
+                    Some(latency) => (latency, "measured"),
+                    None => (heuristic_latency(prompt_tokens), "heuristic"),
+                };
+                let vote_cached = self
+                    .vote_cache
+                    .as_ref()
+                    .is_some_and(|cache| cache.contains(&VoteCache::key(name, &prompt)));
+
+                ExecutorEstimate {
+                    name: name.to_string(),
+                    circuit: self.health.state(name, config).to_string(),
+                    skipped: !callers.contains(&name),
+                    prompt_tokens,
+                    latency_ms: latency.as_millis() as u64,
+                    latency_source,
+                    vote_cached,
+                    cost,
+                }
+            })
+            .collect();
+
+        let key = EvaluationCache::cache_key(
+            &request.cache_content(),
+            request.language.as_str(),
+            &request.evaluation_type,
+        );
+        let cache_hit = self.cache.read().await.contains(&key);
+
+        let timeout = state.config.general.timeout_secs;
+        let timeout = (timeout > 0).then(|| Duration::from_secs(timeout));
+        ReviewEstimate::new(executors, cache_hit, timeout)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════
+    // Helper methods
+    // ═══════════════════════════════════════════════════════════════════════
+
+    /// Executes an evaluation and returns formatted result.
+    async fn evaluate_request(
+        &self,
+        request: EvaluationRequest,
+        progress: &ProgressReporter,
+    ) -> ToolResult {
+        match self.evaluate_internal(request, progress).await {
+            Ok(result) => self.format_result(&result),
+            Err(e) => evaluation_error(e),
+        }
+    }
+
+    /// Executes the internal evaluation.
+    async fn evaluate_internal(
+        &self,
+        request: EvaluationRequest,
+        progress: &ProgressReporter,
+    ) -> TetradResult<EvaluationResult> {
+        self.evaluate_internal_with(request, progress, None, None, 1)
+            .await
+    }
+
+    /// Executes the internal evaluation, optionally with strict consensus
+    /// settings and a review preset, and stores the result for later
+    /// `tetrad_final_check` comparisons.
+    ///
+    /// `refinement_loop` is the review's loop in its refinement cycle; past
+    /// `consensus.max_loops` the result is blocked.
+    async fn evaluate_internal_with(
+        &self,
+        request: EvaluationRequest,
+        progress: &ProgressReporter,
+        strict: Option<&StrictSettings>,
+        preset: Option<ReviewPreset>,
+        refinement_loop: u32,
+    ) -> TetradResult<EvaluationResult> {
+        // Code over general.max_code_bytes is rejected or reviewed in chunks
+        let mut result = match chunk_requests(&self.state().config, &request)? {
+            None => {
+                self.run_evaluation(request, progress, strict, preset, refinement_loop)
+                    .await?
+            }
+            Some(parts) => {
+                self.run_chunked(
+                    &request.request_id,
+                    parts,
+                    progress,
+                    strict,
+                    preset,
+                    refinement_loop,
+                )
+                .await?
+            }
+        };
+
+        // The loop belongs to this request, not to the cached result
+        result.refinement_loop = Some(refinement_loop);
+        if refinement_loop > 1 {
+            self.loops
+                .write()
+                .await
+                .put(result.request_id.clone(), refinement_loop);
+        }
+
+        // A storage failure must not fail the review itself
+        if let Err(e) = self.results.lock().await.record(&result) {
+            tracing::warn!(request_id = %result.request_id, error = %e, "Failed to store evaluation result");
+        }
+
+        Ok(result)
+    }
+
+    /// Runs each chunk of an oversized request through the evaluation
+    /// pipeline, one after the other, and merges the chunk results.
+    ///
+    /// Every chunk is cached on its own, so editing one function only
+    /// re-evaluates the chunks that changed.
+    async fn run_chunked(
+        &self,
+        request_id: &str,
+        parts: Vec<(Chunk, EvaluationRequest)>,
+        progress: &ProgressReporter,
+        strict: Option<&StrictSettings>,
+        preset: Option<ReviewPreset>,
+        refinement_loop: u32,
+    ) -> TetradResult<EvaluationResult> {
+        let total = parts.len();
+        tracing::info!(request_id = %request_id, chunks = total, "Code over max_code_bytes, evaluating in chunks");
+
+        let mut chunks = Vec::with_capacity(total);
+        let mut results = Vec::with_capacity(total);
+        for (chunk, part) in parts {
+            progress.report(&format!("Evaluating chunk {}/{}", chunk.number, total));
+            let result = self
+                .run_evaluation(part, progress, strict, preset, refinement_loop)
+                .await?;
+            results.push(result.with_materialized_feedback());
+            chunks.push(chunk);
+        }
+
+        Ok(merge_results(request_id, &chunks, results))
+    }
+
+    /// Runs the evaluation pipeline: cache, hooks, budget, executors and consensus.
+    ///
+    /// The ReasoningBank judges the result as reached in `refinement_loop`;
+    /// past `consensus.max_loops` the result is blocked before hooks and the
+    /// ReasoningBank see it, and it is not cached.
+    async fn run_evaluation(
+        &self,
+        request: EvaluationRequest,
+        progress: &ProgressReporter,
+        strict: Option<&StrictSettings>,
+        preset: Option<ReviewPreset>,
+        refinement_loop: u32,
+    ) -> TetradResult<EvaluationResult> {
+        // A reload during the evaluation takes effect on the next one
+        let state = self.state();
+        let config = &state.config;
+        let max_loops = config.consensus.max_loops;
+        let over_loop_limit = refinement_loop > max_loops as u32;
+
+        // Identical requests are answered from the cache
+        let cache_content = cache_content(&request, strict, preset);
+        {
+            let mut cache = self.cache.write().await;
+            if let Some(cached) = cache.get_by_code(
+                &cache_content,
+                request.language.as_str(),
+                &request.evaluation_type,
+            ) {
+                tracing::info!(evaluation_type = %request.evaluation_type, "Cache hit");
+                let mut cached = cached.clone();
+                cached.from_cache = true;
+                if over_loop_limit {
+                    cached.mark_loop_limit_exceeded(refinement_loop, max_loops);
+                }
+                return Ok(cached);
+            }
+        }
+
+        // Run pre_evaluate hooks
+        let hook_result = state.hooks.run_pre_evaluate(&request).await?;
+
+        // Handle hook result
+        let mut request = match hook_result {
+            crate::hooks::HookResult::Skip { reason, decision } => {
+                // Return skip result; unreviewed code is not approved by default
+                tracing::info!(reason = %reason, "Evaluation skipped by pre_evaluate hook");
+                let mut skipped = EvaluationResult::skipped(
+                    &request.request_id,
+                    reason,
+                    decision.unwrap_or(Decision::Revise),
+                );
+                if over_loop_limit {
+                    skipped.mark_loop_limit_exceeded(refinement_loop, max_loops);
+                }
+                return Ok(skipped);
+            }
+            crate::hooks::HookResult::ModifyRequest(modified) => {
+                // Use the modified request from hook
+                tracing::info!("Request modified by pre_evaluate hook");
                 modified
             }
-            crate::hooks::HookResult::Continue => request,
+            // run_pre_evaluate ignores ModifyResult, so it never gets here
+            crate::hooks::HookResult::Continue | crate::hooks::HookResult::ModifyResult(_) => {
+                request
+            }
         };
 
+        // Wait for a free slot so back-to-back reviews don't spawn every
+        // executor at once; cache hits and skipped reviews never wait
+        let _permit = self.limiter.acquire().await?;
+
+        // Count the evaluation against the monthly budget before any executor runs
+        self.charge_budget(&state, &request).await?;
+
+        // Preset instructions go to the executors along with the caller's context
+        if let Some(preset) = preset {
+            request.context = Some(match request.context.take() {
+                Some(context) => format!("{}\n\n{}", context, preset.prompt_addendum()),
+                None => preset.prompt_addendum().to_string(),
+            });
+        }
+
         // Query ReasoningBank
         let known_patterns = {
             let bank = self.reasoning_bank.lock().await;
             if let Some(ref b) = *bank {
-                b.retrieve(&request.code, &request.language)
+                b.retrieve_artifact(
+                    &request.signature_source(),
+                    request.language.as_str(),
+                    ArtifactKind::from(request.evaluation_type),
+                )
             } else {
                 vec![]
             }
@@ -600,144 +2035,932 @@ impl ToolHandler {
             );
         }
 
-        // Collect votes from executors in parallel
-        let votes = self.collect_votes(&request).await;
+        // Known anti-patterns go to the executors as prompt context
+        let antipatterns =
+            known_antipatterns(&known_patterns, config.reasoning.pattern_min_confidence);
+        if config.reasoning.apply_to_prompt {
+            if let Some(section) = prompt_context(&antipatterns) {
+                request.context = Some(match request.context.take() {
+                    Some(context) => format!("{}\n\n{}", context, section),
+                    None => section,
+                });
+            }
+        }
+
+        // Collect votes from executors in parallel, within the overall budget.
+        // Executors still running when it expires count as neutral votes
+        let budget_secs = config.general.timeout_secs;
+        let deadline = (budget_secs > 0)
+            .then(|| tokio::time::Instant::now() + Duration::from_secs(budget_secs));
+        // With early exit, collection stops once the outstanding votes can no
+        // longer change the decision. Final checks and strict reviews always
+        // wait for every vote; a preset veto can still block, so only a
+        // settled BLOCK ends a vetoing preset's vote early
+        let early_exit = (config.consensus.early_exit
+            && strict.is_none()
+            && request.evaluation_type != EvaluationType::FinalCheck)
+            .then(|| EarlyExit {
+                block_only: preset.is_some_and(|p| !p.veto_categories().is_empty()),
+            });
+        let CollectedVotes {
+            votes,
+            cut_off,
+            turnout,
+            degraded,
+            short_circuited,
+            settled,
+            selection,
+            timeouts,
+        } = self
+            .collect_votes(&state, &request, progress, deadline, early_exit)
+            .await;
+
+        // Apply consensus; the configured rule adapts to the executors that voted
+        progress.report("All votes collected, applying consensus");
+        let mut result = match (strict, settled) {
+            (Some(strict), _) => state.consensus.evaluate_strict_with_turnout(
+                votes,
+                &request.request_id,
+                strict,
+                &turnout,
+            ),
+            (None, Some(settled)) => {
+                state
+                    .consensus
+                    .evaluate_settled(votes, &request.request_id, &turnout, settled)
+            }
+            (None, None) => {
+                state
+                    .consensus
+                    .evaluate_with_turnout(votes, &request.request_id, &turnout)
+            }
+        };
+
+        // Matched patterns are kept for tetrad_explain
+        result.matched_patterns = matched_patterns(&known_patterns);
+
+        // Penalize the score for known anti-patterns
+        if let Some(adjustment) = score_adjustment(
+            &antipatterns,
+            result.score,
+            config.reasoning.score_penalty_max,
+        ) {
+            state
+                .consensus
+                .apply_pattern_adjustment(&mut result, adjustment);
+        }
+
+        if !cut_off.is_empty() {
+            tracing::warn!(
+                request_id = %request.request_id,
+                budget_secs,
+                cut_off = ?cut_off,
+                "Evaluation budget exceeded, using the votes received so far"
+            );
+            result.mark_budget_exceeded(cut_off, budget_secs);
+        }
+        if !degraded.is_empty() {
+            result.mark_degraded(degraded);
+        }
+        result.executor_selection = selection;
+        result.executor_timeouts = timeouts;
+        if !short_circuited.is_empty() {
+            tracing::info!(
+                request_id = %request.request_id,
+                decision = %result.decision,
+                short_circuited = ?short_circuited,
+                "Decision settled early, outstanding executors cancelled"
+            );
+            result.mark_short_circuited(short_circuited, turnout.expected);
+        }
+
+        // Preset vetoes and finding filters
+        if let Some(preset) = preset {
+            preset.apply(&mut result);
+        }
+
+        // Votes inferred from free text are scored against the decision, per
+        // response language, to show which CLI needs prompt fixes
+        for vote in result.votes.values() {
+            if let Some(language) = vote.response_language {
+                let agreed = vote.vote.agrees_with(result.decision);
+                self.health
+                    .record_text_vote(&vote.executor, language, agreed);
+            }
+        }
+
+        // Flag flaky results: same code, different decision recently
+        {
+            let bank = self.reasoning_bank.lock().await;
+            if let Some(ref b) = *bank {
+                if let Ok(Some((previous_id, previous_decision))) = b.find_divergent(
+                    &request.signature_source(),
+                    result.decision,
+                    config.reasoning.flaky_window_secs,
+                ) {
+                    tracing::warn!(
+                        request_id = %request.request_id,
+                        previous_request_id = %previous_id,
+                        "Flaky evaluation detected"
+                    );
+                    result.mark_flaky(&previous_id, previous_decision);
+                }
+            }
+        }
 
-        // Apply consensus
-        let result = self.consensus.evaluate(votes, &request.request_id);
+        // A cycle that is not converging is blocked before anyone sees the result
+        if over_loop_limit {
+            result.mark_loop_limit_exceeded(refinement_loop, max_loops);
+        }
 
-        // Run post_evaluate hooks
-        self.hooks.run_post_evaluate(&request, &result).await?;
+        // Run post_evaluate hooks; what they return is what gets cached,
+        // judged and sent back
+        let result = state.hooks.run_post_evaluate(&request, result).await;
 
         // Run specific hooks
         if result.consensus_achieved {
-            self.hooks.run_on_consensus(&result).await?;
+            state.hooks.run_on_consensus(&result).await;
         }
         if matches!(result.decision, Decision::Block) {
-            self.hooks.run_on_block(&result).await?;
+            state.hooks.run_on_block(&result).await;
         }
 
         // Register in ReasoningBank
         {
             let mut bank = self.reasoning_bank.lock().await;
             if let Some(ref mut b) = *bank {
-                let _ = b.judge(
-                    &result.request_id,
-                    &request.code,
-                    &request.language,
-                    &result,
-                    1,
-                    self.config.consensus.max_loops,
+                let _ = b.judge_request(&request, &result, refinement_loop, max_loops);
+                // The same counters as the metrics hook, kept across restarts
+                if let Err(e) =
+                    b.record_metrics(request.evaluation_type, result.decision, result.score)
+                {
+                    tracing::warn!("Error recording metrics: {}", e);
+                }
+                if b.consolidation_due() {
+                    consolidate(b, config);
+                }
+            }
+        }
+
+        // Final checks are only cached when certifiable, so a failed check
+        // always runs again after the code is fixed. A loop-limit block
+        // belongs to this cycle, not to the code
+        let cacheable = !result.loop_limit_exceeded
+            && (request.evaluation_type != EvaluationType::FinalCheck
+                || meets_final_requirements(config, &result, strict));
+        if cacheable {
+            let mut cache = self.cache.write().await;
+            let degraded_ttl = config.cache.degraded_ttl_secs;
+            if !result.degraded {
+                cache.insert_by_code(
+                    &cache_content,
+                    request.language.as_str(),
+                    &request.evaluation_type,
+                    result.clone(),
                 );
+            } else if degraded_ttl > 0 {
+                // Degraded verdicts expire quickly so a fixed executor gets a real vote
+                let key = EvaluationCache::cache_key(
+                    &cache_content,
+                    request.language.as_str(),
+                    &request.evaluation_type,
+                );
+                cache.insert_with_ttl(key, result.clone(), Duration::from_secs(degraded_ttl));
             }
         }
 
         Ok(result)
     }
 
-    /// Collects votes from all enabled executors.
-    async fn collect_votes(&self, request: &EvaluationRequest) -> HashMap<String, ModelVote> {
-        let mut votes = HashMap::new();
-
-        // Execute in parallel
-        let (codex_vote, gemini_vote, qwen_vote) = tokio::join!(
-            self.get_vote_if_enabled(&self.codex, request, self.config.executors.codex.enabled),
-            self.get_vote_if_enabled(&self.gemini, request, self.config.executors.gemini.enabled),
-            self.get_vote_if_enabled(&self.qwen, request, self.config.executors.qwen.enabled),
-        );
+    /// Counts one evaluation against the monthly budget.
+    ///
+    /// The estimated cost is one call per executor seat. MCP clients cannot
+    /// pass `--override-budget`, so `require_override` refuses here like
+    /// `refuse`. Without a ReasoningBank there is nowhere to keep the count
+    /// and the budget is not enforced.
+    async fn charge_budget(
+        &self,
+        state: &ToolHandlerState,
+        request: &EvaluationRequest,
+    ) -> TetradResult<()> {
+        let budget = &state.config.general.budget;
+        let mut bank = self.reasoning_bank.lock().await;
+        let Some(ref mut b) = *bank else {
+            return Ok(());
+        };
 
-        if let Some(vote) = codex_vote {
-            votes.insert("Codex".to_string(), vote);
-        }
-        if let Some(vote) = gemini_vote {
-            votes.insert("Gemini".to_string(), vote);
-        }
-        if let Some(vote) = qwen_vote {
-            votes.insert("Qwen".to_string(), vote);
+        let seats = state.requested_seats(request);
+        let cost = seats as f64 * budget.cost_per_call;
+        match b.charge_usage(budget, 1, cost, false, (self.clock)())? {
+            BudgetCharge::Charged(_) => Ok(()),
+            BudgetCharge::OverBudget(status) => {
+                tracing::warn!(
+                    request_id = %request.request_id,
+                    usage = %status.summary(),
+                    "Monthly budget exhausted, evaluating anyway"
+                );
+                Ok(())
+            }
+            BudgetCharge::Refused(status) => {
+                let hint = match budget.action {
+                    BudgetAction::RequireOverride => {
+                        " (run `tetrad evaluate --override-budget` to evaluate anyway)"
+                    }
+                    _ => "",
+                };
+                Err(TetradError::BudgetExceeded(format!(
+                    "{}{}",
+                    status.summary(),
+                    hint
+                )))
+            }
         }
-
-        votes
     }
 
-    /// Gets vote from an executor if enabled.
-    async fn get_vote_if_enabled<E: CliExecutor>(
+    /// Collects votes from the enabled executors the request allows.
+    ///
+    /// Seats still pending at `deadline` get a neutral vote and are reported
+    /// as cut off. Seats whose executor failed also get a neutral vote and are
+    /// reported as absent in the turnout. `degraded` lists every enabled
+    /// executor without a real vote: seats with a neutral or synthetic vote
+    /// (a CLI that is not installed) plus executors left without a seat by an
+    /// open circuit.
+    async fn collect_votes(
         &self,
-        executor: &E,
+        state: &ToolHandlerState,
         request: &EvaluationRequest,
-        enabled: bool,
-    ) -> Option<ModelVote> {
-        if !enabled {
-            return None;
+        progress: &ProgressReporter,
+        deadline: Option<tokio::time::Instant>,
+        early_exit: Option<EarlyExit>,
+    ) -> CollectedVotes {
+        // Executors the request left out sit this evaluation out, and the rule
+        // is sized for the seats it asked for
+        let requested = state.requested_configs(request);
+        let voters = request
+            .executors
+            .is_some()
+            .then(|| state.requested_seats(request));
+
+        // Executors with an open circuit are treated as disabled for this request,
+        // so a configured fallback takes their seat
+        let configs: Vec<_> = state
+            .executors
+            .iter()
+            .zip(requested)
+            .map(|((executor, _), mut config)| {
+                if config.enabled && !self.health.allow(executor.name(), &config) {
+                    tracing::debug!(
+                        executor = executor.name(),
+                        "Circuit open, skipping executor"
+                    );
+                    config.enabled = false;
+                }
+                config
+            })
+            .collect();
+        // Each executor answers from the vote cache when its prompt is unchanged;
+        // real calls feed its latency average
+        let cached: Vec<CachedVoteExecutor<'_>> = state
+            .executors
+            .iter()
+            .map(|(executor, _)| {
+                CachedVoteExecutor::new(executor.as_ref(), self.vote_cache.as_deref())
+                    .with_health(&self.health)
+            })
+            .collect();
+        // With adaptive timeouts, each call gets a timeout sized to the code
+        // and to how slow the executor has been lately
+        let general = &state.config.general;
+        let call_timeouts: Vec<Option<Duration>> = state
+            .executors
+            .iter()
+            .zip(&configs)
+            .map(|((executor, _), config)| {
+                (general.adaptive_timeouts && config.enabled).then(|| {
+                    adaptive_timeout(
+                        &general.adaptive_timeout,
+                        request.code.len(),
+                        self.health.p90_latency(executor.name()),
+                    )
+                })
+            })
+            .collect();
+        let timeouts: HashMap<String, u64> = state
+            .executors
+            .iter()
+            .zip(&call_timeouts)
+            .filter_map(|((executor, _), timeout)| {
+                timeout.map(|timeout| (executor.name().to_string(), timeout.as_secs()))
+            })
+            .collect();
+        if !timeouts.is_empty() {
+            tracing::info!(
+                request_id = %request.request_id,
+                code_bytes = request.code.len(),
+                timeouts = ?timeouts,
+                "Adaptive executor timeouts"
+            );
+        }
+        let slots: Vec<ExecutorSlot<'_>> = cached
+            .iter()
+            .zip(&configs)
+            .zip(&call_timeouts)
+            .map(|((executor, config), timeout)| {
+                ExecutorSlot::new(executor, config).with_timeout(*timeout)
+            })
+            .collect();
+
+        // One notification per start and per vote, plus one before consensus
+        let seats = seat_names(&slots);
+        let turnout_of = |absent: Vec<String>| {
+            let turnout = Turnout::new(seats.len()).with_absent(absent);
+            match voters {
+                Some(voters) => turnout.with_voters(voters),
+                None => turnout,
+            }
+        };
+        let selection = request.executors.is_some().then(|| ExecutorSelection {
+            consulted: seats.iter().map(|name| name.to_string()).collect(),
+            skipped_by_request: state
+                .executors
+                .iter()
+                .filter(|(executor, config)| {
+                    config.enabled && !request_allows(request, executor.name())
+                })
+                .map(|(executor, _)| executor.name().to_string())
+                .collect(),
+            disabled_in_config: state
+                .executors
+                .iter()
+                .filter(|(_, config)| !config.enabled)
+                .map(|(executor, _)| executor.name().to_string())
+                .collect(),
+        });
+        progress.set_total(seats.len() as u64 * 2 + 1);
+        for name in &seats {
+            progress.report(&format!("{} started", name));
         }
 
-        match executor.evaluate(request).await {
-            Ok(vote) => Some(vote),
-            Err(e) => {
-                tracing::warn!(
-                    executor = executor.name(),
-                    error = %e,
-                    "Executor failed, using fallback vote"
-                );
-                // Neutral vote in case of error
-                Some(ModelVote::new(
-                    executor.name(),
-                    crate::types::responses::Vote::Warn,
-                    50,
-                ))
+        // Checked after each vote: the decision is settled once every way the
+        // outstanding seats could still vote leads to the same outcome
+        let settled = std::sync::Mutex::new(None);
+        let settle = |finished: &[&SeatOutcome], waiting: &[&str]| {
+            let Some(early_exit) = early_exit else {
+                return false;
+            };
+            let votes: HashMap<String, ModelVote> = finished
+                .iter()
+                .map(|seat| (seat.name.clone(), seat_vote(seat)))
+                .collect();
+            let absent = finished
+                .iter()
+                .filter(|seat| seat.result.is_err())
+                .map(|seat| seat.name.clone())
+                .collect();
+            let turnout = turnout_of(absent);
+            match state.consensus.settled_decision(&votes, &turnout, waiting) {
+                Some(fixed) if !early_exit.block_only || fixed.0 == Decision::Block => {
+                    *settled.lock().unwrap_or_else(|e| e.into_inner()) = Some(fixed);
+                    true
+                }
+                _ => false,
+            }
+        };
+
+        // Execute in parallel; a fallback shares its primary's seat
+        let outcomes = collect_seats_until_settled(
+            &slots,
+            request,
+            deadline,
+            &|seat| match &seat.result {
+                Ok(vote) => progress.report(&format!(
+                    "{} voted {} (score: {})",
+                    seat.name, vote.vote, vote.score
+                )),
+                Err(_) if seat.cut_off => {
+                    progress.report(&format!("{} cut off by the time budget", seat.name))
+                }
+                Err(_) if seat.short_circuited => {
+                    progress.report(&format!("{} skipped, decision already settled", seat.name))
+                }
+                Err(e) => progress.report(&format!("{} failed: {}", seat.name, e)),
+            },
+            &settle,
+        )
+        .await;
+        let settled = settled.into_inner().unwrap_or_else(|e| e.into_inner());
+
+        // Seats and outcomes are in the same order; a fallback only answers
+        // for an enabled primary when the primary failed. A seat cut off by
+        // the budget or by early exit says nothing about the executor's health
+        for (primary, seat) in seats.iter().zip(&outcomes) {
+            let slot = slots.iter().find(|s| s.executor.name() == *primary);
+            let Some(slot) =
+                slot.filter(|s| s.config.enabled && !seat.cut_off && !seat.short_circuited)
+            else {
+                continue;
+            };
+            if seat.used_fallback || seat.result.is_err() {
+                let reason = match &seat.result {
+                    Err(e) => e.to_string(),
+                    Ok(_) => "failed, its fallback answered".to_string(),
+                };
+                self.health.record_failure(primary, slot.config, &reason);
+            } else {
+                self.health.record_success(primary);
+            }
+        }
+
+        // Seats cancelled by early exit did not vote at all
+        let (short_circuited, outcomes): (Vec<SeatOutcome>, Vec<SeatOutcome>) =
+            outcomes.into_iter().partition(|seat| seat.short_circuited);
+        let short_circuited = short_circuited.into_iter().map(|seat| seat.name).collect();
+
+        // on_error hooks see every seat left without a vote, timeouts included
+        for seat in &outcomes {
+            if let Err(error) = &seat.result {
+                state.hooks.run_on_error(request, &seat.name, error).await;
             }
         }
+
+        let cut_off = outcomes
+            .iter()
+            .filter(|seat| seat.cut_off)
+            .map(|seat| seat.name.clone())
+            .collect();
+        let absent = outcomes
+            .iter()
+            .filter(|seat| seat.result.is_err() && !seat.cut_off)
+            .map(|seat| seat.name.clone())
+            .collect();
+        let turnout = turnout_of(absent);
+        let mut degraded: Vec<String> = outcomes
+            .iter()
+            .filter(|seat| seat.result.as_ref().map_or(true, |vote| vote.synthetic))
+            .map(|seat| seat.name.clone())
+            .collect();
+        degraded.extend(
+            state
+                .executors
+                .iter()
+                .filter(|(_, config)| config.enabled && config.fallback_for.is_none())
+                .map(|(executor, _)| executor.name())
+                .filter(|name| !seats.contains(name) && request_allows(request, name))
+                .map(str::to_string),
+        );
+        let votes = outcomes
+            .into_iter()
+            .map(|seat| {
+                if let Err(e) = &seat.result {
+                    tracing::warn!(
+                        executor = %seat.name,
+                        error = %e,
+                        "Executor failed, using neutral vote"
+                    );
+                }
+                let vote = seat_vote(&seat);
+                (seat.name, vote)
+            })
+            .collect();
+        CollectedVotes {
+            votes,
+            cut_off,
+            turnout,
+            degraded,
+            short_circuited,
+            settled,
+            selection,
+            timeouts,
+        }
     }
 
     /// Formats the result for MCP return.
     fn format_result(&self, result: &EvaluationResult) -> ToolResult {
+        ToolResult::success_json(&self.result_json(result))
+    }
+
+    /// JSON response for an evaluation result.
+    fn result_json(&self, result: &EvaluationResult) -> Value {
         let status = match result.decision {
             Decision::Pass => "PASS",
             Decision::Revise => "REVISE",
             Decision::Block => "BLOCK",
         };
 
-        let response = json!({
-            "request_id": result.request_id,
-            "decision": status,
-            "score": result.score,
-            "consensus_achieved": result.consensus_achieved,
-            "findings": result.findings.iter().map(|f| json!({
+        let finding_json = |f: &Finding| {
+            let mut finding = json!({
                 "severity": format!("{:?}", f.severity),
                 "category": f.category,
                 "issue": f.issue,
+                "lines": f.lines,
                 "suggestion": f.suggestion,
-                "consensus_strength": f.consensus_strength
-            })).collect::<Vec<_>>(),
+                "consensus_strength": f.consensus_strength,
+                "fixes": f.fixes().collect::<Vec<_>>()
+            });
+            if let Some(file) = &f.file {
+                finding["file"] = json!(file);
+            }
+            if let Some(chunk) = f.chunk {
+                finding["chunk"] = json!(chunk);
+            }
+            finding
+        };
+
+        let mut response = json!({
+            "request_id": result.request_id,
+            "decision": status,
+            "score": result.score,
+            "consensus_achieved": result.consensus_achieved,
+            "confidence": result.confidence,
+            "findings": result.findings.iter().map(finding_json).collect::<Vec<_>>(),
             "feedback": result.feedback,
             "votes": result.votes.iter().map(|(name, vote)| {
-                json!({
+                let mut entry = json!({
                     "executor": name,
                     "vote": format!("{:?}", vote.vote),
                     "score": vote.score
-                })
+                });
+                // Neutral stand-ins for executors that gave no answer
+                if vote.synthetic {
+                    entry["synthetic"] = json!(true);
+                }
+                entry
             }).collect::<Vec<_>>()
         });
+        // Multi-file diffs also get the findings grouped by file; the flat
+        // array above stays for compatibility
+        let groups = FileFindings::group(&result.findings);
+        if !groups.is_empty() {
+            let by_file: serde_json::Map<String, Value> = groups
+                .iter()
+                .map(|group| {
+                    (
+                        group
+                            .file
+                            .clone()
+                            .unwrap_or_else(|| "(unattributed)".to_string()),
+                        json!({
+                            "verdict": format!("{:?}", group.verdict),
+                            "findings": group.findings.iter().map(finding_json).collect::<Vec<_>>()
+                        }),
+                    )
+                })
+                .collect();
+            response["findings_by_file"] = Value::Object(by_file);
+        }
+        if !result.chunks.is_empty() {
+            response["chunks"] = json!(result.chunks);
+        }
+        if let Some(rule) = &result.effective_rule {
+            response["effective_rule"] = json!(rule);
+        }
+        if let Some(strictness) = &result.strictness {
+            response["strictness"] = json!(strictness);
+        }
+        if let Some(reason) = &result.skip_reason {
+            response["skip_reason"] = json!(reason);
+        }
+        if result.budget_exceeded {
+            response["budget_exceeded"] = json!(true);
+            response["cut_off_executors"] = json!(result.cut_off_executors);
+        }
+        if result.low_confidence {
+            response["low_confidence"] = json!(true);
+        }
+        if result.degraded {
+            response["degraded"] = json!(true);
+            response["degraded_executors"] = json!(result.degraded_executors);
+        }
+        if let Some(selection) = &result.executor_selection {
+            response["executors"] = json!(selection);
+        }
+        if !result.executor_timeouts.is_empty() {
+            response["executor_timeouts_secs"] = json!(result.executor_timeouts);
+        }
+        if !result.short_circuited_executors.is_empty() {
+            // The score only covers the votes received before the decision settled
+            response["short_circuited_executors"] = json!(result.short_circuited_executors);
+            response["score_basis"] = json!({
+                "votes_received": result.votes.len(),
+                "votes_cancelled": result.short_circuited_executors.len()
+            });
+        }
+        self.add_loop_fields(&mut response, result);
 
-        ToolResult::success_json(&response)
+        response
+    }
+
+    /// Adds the result's refinement `loop` and the loops left before
+    /// `consensus.max_loops` to a tool response.
+    fn add_loop_fields(&self, response: &mut Value, result: &EvaluationResult) {
+        let Some(refinement_loop) = result.refinement_loop else {
+            return;
+        };
+        let max_loops = self.state().config.consensus.max_loops as u32;
+        response["loop"] = json!(refinement_loop);
+        response["max_loops_remaining"] = json!(max_loops.saturating_sub(refinement_loop));
+        if result.loop_limit_exceeded {
+            response["loop_limit_exceeded"] = json!(true);
+        }
+    }
+
+    /// Refinement loop of a review that revises `previous`: one past the
+    /// previous review's loop, or 1 when it starts a cycle.
+    async fn next_loop(&self, previous: Option<&str>) -> u32 {
+        match previous {
+            Some(previous) => self.loops.read().await.peek(previous).copied().unwrap_or(1) + 1,
+            None => 1,
+        }
+    }
+}
+
+/// Votes gathered for one evaluation.
+struct CollectedVotes {
+    /// One vote per seat; seats without a vote of their own count as neutral.
+    votes: HashMap<String, ModelVote>,
+    /// Seats cut off by the evaluation budget.
+    cut_off: Vec<String>,
+    turnout: Turnout,
+    /// Seats without a real vote (failed or synthetic), plus enabled
+    /// executors without a seat.
+    degraded: Vec<String>,
+    /// Seats cancelled because the decision was already settled.
+    short_circuited: Vec<String>,
+    /// Decision and consensus fixed before the cancelled seats voted.
+    settled: Option<(Decision, bool)>,
+    /// Who was consulted, when the request restricted the executors.
+    selection: Option<ExecutorSelection>,
+    /// Adaptive timeout of each enabled executor, in seconds.
+    timeouts: HashMap<String, u64>,
+}
+
+/// Early exit settings for one evaluation.
+#[derive(Debug, Clone, Copy)]
+struct EarlyExit {
+    /// Only a settled BLOCK ends the vote early.
+    block_only: bool,
+}
+
+/// Steps after the consensus rule that changed or qualified a result.
+fn decision_adjustments(result: &EvaluationResult) -> Vec<Value> {
+    let mut adjustments = Vec::new();
+    if let Some(reason) = &result.skip_reason {
+        adjustments.push(json!({ "cause": "skipped_by_hook", "reason": reason }));
+    }
+    if let Some(gate) = result
+        .explanation
+        .as_ref()
+        .and_then(|e| e.severity_gate.as_ref())
+    {
+        adjustments.push(json!({ "cause": "severity_gate", "block_on_severity": gate }));
+    }
+    if let Some(adjustment) = &result.pattern_adjustment {
+        adjustments.push(json!({
+            "cause": "pattern_adjustment",
+            "original_score": adjustment.original_score,
+            "penalty": adjustment.penalty,
+            "patterns": adjustment.patterns
+        }));
+    }
+    if result.low_confidence {
+        adjustments.push(json!({ "cause": "low_confidence", "confidence": result.confidence }));
+    }
+    if !result.short_circuited_executors.is_empty() {
+        adjustments.push(json!({
+            "cause": "early_exit",
+            "executors": result.short_circuited_executors
+        }));
+    }
+    if result.budget_exceeded {
+        adjustments.push(json!({
+            "cause": "budget_exceeded",
+            "executors": result.cut_off_executors
+        }));
+    }
+    if result.degraded {
+        adjustments.push(json!({ "cause": "degraded", "executors": result.degraded_executors }));
+    }
+    if let Some(previous) = &result.flaky_with {
+        adjustments.push(json!({ "cause": "flaky", "previous_request_id": previous }));
+    }
+    if result.loop_limit_exceeded {
+        adjustments.push(json!({ "cause": "loop_limit", "loop": result.refinement_loop }));
+    }
+    adjustments
+}
+
+/// Whether the request's `executors` filter lets `executor` vote.
+fn request_allows(request: &EvaluationRequest, executor: &str) -> bool {
+    request
+        .executors
+        .as_ref()
+        .is_none_or(|names| names.iter().any(|name| name == executor))
+}
+
+/// A seat's vote; a seat left without one counts as a neutral vote.
+fn seat_vote(seat: &SeatOutcome) -> ModelVote {
+    match &seat.result {
+        Ok(vote) => vote.clone(),
+        Err(_) => ModelVote::synthetic(&seat.name),
+    }
+}
+
+/// Whether a final check result meets the certification requirements:
+/// consensus, the minimum score and, in strict mode, no critical or error findings.
+///
+/// Prior confirmation is checked separately by `handle_final_check`.
+fn meets_final_requirements(
+    config: &Config,
+    result: &EvaluationResult,
+    strict: Option<&StrictSettings>,
+) -> bool {
+    let min_score = strict.map_or(config.consensus.min_score, |s| s.min_score);
+    result.consensus_achieved && result.score >= min_score && !strict_violation(result, strict)
+}
+
+/// Content the evaluation cache is keyed on.
+///
+/// The key includes the evaluation type; strict, preset and executor-restricted
+/// reviews are keyed apart from plain ones.
+fn cache_content(
+    request: &EvaluationRequest,
+    strict: Option<&StrictSettings>,
+    preset: Option<ReviewPreset>,
+) -> String {
+    let mut content = request.cache_content().into_owned();
+    if strict.is_some() {
+        content.push_str("\n[strict]");
+    }
+    if let Some(preset) = preset {
+        content.push_str(&format!("\n[{}]", preset.name()));
+    }
+    if let Some(executors) = &request.executors {
+        content.push_str(&format!("\n[executors {}]", executors.join(",")));
+    }
+    content
+}
+
+/// Splits a request whose code is over `general.max_code_bytes`.
+///
+/// Returns `None` when the code fits or the limit is 0. Oversized code is
+/// rejected unless `general.chunking` is set; diffs, whose hunks cannot be
+/// cut apart, are always rejected, and so is code that would need more than
+/// [`MAX_CHUNKS`] chunks. Each chunk request tells the executors which part
+/// of the file they are looking at.
+fn chunk_requests(
+    config: &Config,
+    request: &EvaluationRequest,
+) -> TetradResult<Option<Vec<(Chunk, EvaluationRequest)>>> {
+    let limit = config.general.max_code_bytes;
+    let size = request.code.len();
+    if limit == 0 || size <= limit {
+        return Ok(None);
+    }
+    if !config.general.chunking || request.evaluation_type == EvaluationType::Diff {
+        return Err(TetradError::CodeTooLarge(size, limit));
+    }
+
+    let chunks = split_code(&request.code, limit);
+    if chunks.len() > MAX_CHUNKS {
+        return Err(TetradError::other(format!(
+            "Code is {} bytes and would need {} chunks of general.max_code_bytes ({}); at most {} chunks are reviewed",
+            size,
+            chunks.len(),
+            limit,
+            MAX_CHUNKS
+        )));
+    }
+
+    let total = chunks.len();
+    let parts = chunks
+        .into_iter()
+        .map(|chunk| {
+            let mut part = request.clone();
+            part.request_id = format!("{}-{}", request.request_id, chunk.number);
+            part.code = chunk.code.clone();
+
+            let mut note = format!(
+                "## Partial review\n\
+                 This is chunk {} of {} (lines {}-{}) of a larger file; review only this chunk \
+                 and report line numbers relative to it.",
+                chunk.number, total, chunk.start_line, chunk.end_line
+            );
+            if chunk.overlap_lines > 0 {
+                note.push_str(&format!(
+                    " Its first {} lines repeat the end of the previous chunk as context.",
+                    chunk.overlap_lines
+                ));
+            }
+            part.context = Some(match part.context.take() {
+                Some(context) => format!("{}\n\n{}", context, note),
+                None => note,
+            });
+            (chunk, part)
+        })
+        .collect();
+    Ok(Some(parts))
+}
+
+/// Versions of the executors that may vote on a request, in configured order.
+///
+/// Covers the enabled executors (fallbacks included), restricted to
+/// `selected` when the request names its executors. A CLI that does not
+/// report a version is listed as `"unknown"`.
+async fn executor_versions(
+    state: &ToolHandlerState,
+    selected: Option<&[String]>,
+) -> Vec<(String, String)> {
+    let mut versions = Vec::new();
+    for (executor, config) in state.executors.iter() {
+        let name = executor.name();
+        if !config.enabled || selected.is_some_and(|names| !names.iter().any(|n| n == name)) {
+            continue;
+        }
+        let version = executor
+            .version()
+            .await
+            .unwrap_or_else(|_| "unknown".to_string());
+        versions.push((name.to_string(), version));
+    }
+    versions
+}
+
+/// Strict mode tolerates no critical or error findings.
+fn strict_violation(result: &EvaluationResult, strict: Option<&StrictSettings>) -> bool {
+    strict.is_some()
+        && result
+            .findings
+            .iter()
+            .any(|f| matches!(f.severity, Severity::Critical | Severity::Error))
+}
+
+/// Consolidates the ReasoningBank and rewrites `digest.md` in the data
+/// directory. Failures are only logged: the evaluation already succeeded.
+fn consolidate(bank: &mut ReasoningBank, config: &Config) {
+    match bank.consolidate() {
+        Ok(consolidation) => tracing::info!(
+            merged = consolidation.patterns_merged,
+            pruned = consolidation.patterns_pruned,
+            good_patterns_collapsed = consolidation.good_patterns_collapsed,
+            trajectories_pruned = consolidation.trajectories_pruned,
+            "ReasoningBank consolidated"
+        ),
+        Err(e) => {
+            tracing::warn!("ReasoningBank consolidation failed: {}", e);
+            return;
+        }
+    }
+
+    let path = config.data_dir().join(DIGEST_FILE_NAME);
+    if let Err(e) = bank.write_digest(&path) {
+        tracing::warn!("Could not write digest to {}: {}", path.display(), e);
+    }
+}
+
+/// Tool result for a failed evaluation. A busy server gets a structured
+/// error so the client knows it can retry, and oversized code one that
+/// carries the limit.
+fn evaluation_error(error: TetradError) -> ToolResult {
+    match error {
+        TetradError::ServerBusy(message) => ToolResult::error_json(&json!({
+            "error": "server_busy",
+            "message": message,
+            "retryable": true
+        })),
+        TetradError::CodeTooLarge(size, limit) => ToolResult::error_json(&json!({
+            "error": "code_too_large",
+            "message": TetradError::CodeTooLarge(size, limit).to_string(),
+            "code_bytes": size,
+            "max_code_bytes": limit,
+            "retryable": false
+        })),
+        e => ToolResult::error(format!("Evaluation failed: {}", e)),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::responses::Vote;
 
     #[test]
     fn test_list_tools() {
         let tools = ToolHandler::list_tools();
-        assert_eq!(tools.len(), 6);
+        assert_eq!(tools.len(), 12);
 
         let tool_names: Vec<&str> = tools.iter().map(|t| t.name.as_str()).collect();
         assert!(tool_names.contains(&"tetrad_review_plan"));
         assert!(tool_names.contains(&"tetrad_review_code"));
+        assert!(tool_names.contains(&"tetrad_review_diff"));
         assert!(tool_names.contains(&"tetrad_review_tests"));
+        assert!(tool_names.contains(&"tetrad_review_docs"));
         assert!(tool_names.contains(&"tetrad_confirm"));
         assert!(tool_names.contains(&"tetrad_final_check"));
         assert!(tool_names.contains(&"tetrad_status"));
+        assert!(tool_names.contains(&"tetrad_metrics"));
+        assert!(tool_names.contains(&"tetrad_estimate"));
+        assert!(tool_names.contains(&"tetrad_explain"));
+        assert!(tool_names.contains(&"tetrad_get_patterns"));
     }
 
     #[test]
@@ -754,6 +2977,49 @@ mod tests {
         assert_eq!(params.file_path, Some("src/main.rs".to_string()));
     }
 
+    #[test]
+    fn test_review_code_params_related_files() {
+        let json = json!({
+            "code": "fn main() { greet(); }",
+            "language": "rust",
+            "related_files": [{"path": "src/greet.rs", "content": "pub fn greet() {}"}]
+        });
+
+        let params: ReviewCodeParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.related_files.len(), 1);
+        assert_eq!(params.related_files[0].path, "src/greet.rs");
+    }
+
+    #[test]
+    fn test_review_docs_params_deserialize() {
+        let json = json!({
+            "docs": "/// Adds two numbers.",
+            "code": "fn add(a: i32, b: i32) -> i32 { a + b }"
+        });
+
+        let params: ReviewDocsParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.docs, "/// Adds two numbers.");
+        assert!(params.code.is_some());
+        assert!(params.language.is_none());
+        assert!(params.doc_type.is_none());
+    }
+
+    #[test]
+    fn test_review_docs_params_content_and_doc_type() {
+        let json = json!({
+            "content": "# Changelog\n## 1.2.0",
+            "doc_type": "changelog",
+            "context": "Release notes for 1.2.0"
+        });
+
+        let params: ReviewDocsParams = serde_json::from_value(json).unwrap();
+        assert_eq!(params.docs, "# Changelog\n## 1.2.0");
+        assert_eq!(params.doc_type, Some(DocType::Changelog));
+
+        let invalid = json!({ "content": "# Notes", "doc_type": "blog" });
+        assert!(serde_json::from_value::<ReviewDocsParams>(invalid).is_err());
+    }
+
     #[test]
     fn test_confirm_params_deserialize() {
         let json = json!({
@@ -787,4 +3053,1417 @@ mod tests {
             .unwrap()
             .contains(&json!("code")));
     }
+
+    mod progress_tests {
+        use super::*;
+        use crate::executors::CliExecutor;
+        use crate::mcp::transport::StringTransport;
+        use crate::mcp::ToolContent;
+        use crate::types::config::ExecutorConfig;
+        use crate::types::responses::Vote;
+        use async_trait::async_trait;
+
+        struct ScoredExecutor {
+            name: &'static str,
+            score: u8,
+        }
+
+        #[async_trait]
+        impl CliExecutor for ScoredExecutor {
+            fn name(&self) -> &str {
+                self.name
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                Ok(ModelVote::new(self.name, Vote::Pass, self.score))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        fn handler(dir: &tempfile::TempDir, progress_notifications: bool) -> ToolHandler {
+            let executors: Vec<ConfiguredExecutor> = [("Codex", 80), ("Gemini", 85), ("Qwen", 90)]
+                .into_iter()
+                .map(|(name, score)| {
+                    let executor: Box<dyn CliExecutor> = Box::new(ScoredExecutor { name, score });
+                    (executor, ExecutorConfig::new("mock", &[]))
+                })
+                .collect();
+
+            let mut config = Config::default();
+            config.reasoning.db_path = dir.path().join("patterns.db");
+            config.general.progress_notifications = progress_notifications;
+
+            ToolHandler::with_executors(config, executors).unwrap()
+        }
+
+        fn notifications(transport: &StringTransport) -> Vec<Value> {
+            transport
+                .get_output()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn test_progress_notifications_ordering() {
+            let dir = tempfile::tempdir().unwrap();
+            let transport = Arc::new(StringTransport::new(""));
+            let handler = handler(&dir, true).with_notifications(transport.clone());
+
+            let result = handler
+                .handle_tool_call_with_progress(
+                    "tetrad_review_plan",
+                    json!({"plan": "Add a cache"}),
+                    Some("call-1".into()),
+                )
+                .await;
+            assert!(!result.is_error);
+
+            let notifications = notifications(&transport);
+            let messages: Vec<&str> = notifications
+                .iter()
+                .map(|n| n["params"]["message"].as_str().unwrap())
+                .collect();
+
+            assert_eq!(notifications.len(), 7);
+            assert_eq!(
+                &messages[..3],
+                &["Codex started", "Gemini started", "Qwen started"]
+            );
+            let mut votes = messages[3..6].to_vec();
+            votes.sort();
+            assert_eq!(
+                votes,
+                vec![
+                    "Codex voted PASS (score: 80)",
+                    "Gemini voted PASS (score: 85)",
+                    "Qwen voted PASS (score: 90)",
+                ]
+            );
+            assert_eq!(messages[6], "All votes collected, applying consensus");
+
+            for (index, notification) in notifications.iter().enumerate() {
+                assert_eq!(notification["method"], "notifications/progress");
+                assert_eq!(notification["params"]["progressToken"], "call-1");
+                assert_eq!(notification["params"]["progress"], index as u64 + 1);
+                assert_eq!(notification["params"]["total"], 7);
+            }
+        }
+
+        #[tokio::test]
+        async fn test_progress_notifications_disabled_by_config() {
+            let dir = tempfile::tempdir().unwrap();
+            let transport = Arc::new(StringTransport::new(""));
+            let handler = handler(&dir, false).with_notifications(transport.clone());
+
+            let result = handler
+                .handle_tool_call_with_progress(
+                    "tetrad_review_plan",
+                    json!({"plan": "Add a cache"}),
+                    Some("call-1".into()),
+                )
+                .await;
+
+            assert!(!result.is_error);
+            assert!(transport.get_output().is_empty());
+        }
+
+        #[tokio::test]
+        async fn test_review_code_rejects_oversized_related_files() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir, false);
+            let mut config = handler.state().config.clone();
+            config.general.max_context_bytes = 512;
+            handler.reload(config).await.unwrap();
+
+            let related: Vec<Value> = (0..3)
+                .map(|i| json!({"path": format!("src/m{i}.rs"), "content": "x".repeat(400)}))
+                .collect();
+            let result = handler
+                .handle_tool_call(
+                    "tetrad_review_code",
+                    json!({"code": "fn main() {}", "language": "rust", "related_files": related}),
+                )
+                .await;
+
+            assert!(result.is_error);
+            let ToolContent::Text { text } = &result.content[0];
+            assert!(text.contains("max_context_bytes"));
+        }
+    }
+
+    mod circuit_tests {
+        use super::*;
+        use crate::executors::CliExecutor;
+        use crate::mcp::ToolContent;
+        use crate::types::config::ExecutorConfig;
+        use crate::types::responses::Vote;
+        use crate::TetradError;
+        use async_trait::async_trait;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Executor that always times out, counting its calls.
+        struct DeadExecutor {
+            calls: Arc<AtomicUsize>,
+        }
+
+        #[async_trait]
+        impl CliExecutor for DeadExecutor {
+            fn name(&self) -> &str {
+                "Gemini"
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Err(TetradError::ExecutorTimeout("Gemini".to_string()))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        struct PassExecutor(&'static str);
+
+        #[async_trait]
+        impl CliExecutor for PassExecutor {
+            fn name(&self) -> &str {
+                self.0
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                Ok(ModelVote::new(self.0, Vote::Pass, 90))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        #[tokio::test]
+        async fn test_open_circuit_skips_dead_executor() {
+            let dir = tempfile::tempdir().unwrap();
+            let calls = Arc::new(AtomicUsize::new(0));
+            let gemini_config = ExecutorConfig {
+                circuit_failure_threshold: 2,
+                ..ExecutorConfig::new("mock", &[])
+            };
+            let executors: Vec<ConfiguredExecutor> = vec![
+                (
+                    Box::new(PassExecutor("Codex")),
+                    ExecutorConfig::new("mock", &[]),
+                ),
+                (
+                    Box::new(DeadExecutor {
+                        calls: calls.clone(),
+                    }),
+                    gemini_config,
+                ),
+            ];
+            let mut config = Config::default();
+            config.reasoning.db_path = dir.path().join("patterns.db");
+            let handler = ToolHandler::with_executors(config, executors).unwrap();
+
+            // Distinct plans, so no call is answered from the cache
+            for i in 0..4 {
+                handler
+                    .handle_tool_call(
+                        "tetrad_review_plan",
+                        json!({ "plan": format!("Add cache layer {}", i) }),
+                    )
+                    .await;
+            }
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+            let status = handler.handle_tool_call("tetrad_status", json!({})).await;
+            let ToolContent::Text { text } = &status.content[0];
+            let status: Value = serde_json::from_str(text).unwrap();
+            assert!(status["gemini"]["health"]
+                .as_str()
+                .unwrap()
+                .starts_with("circuit open, retry in"));
+            assert_eq!(status["codex"]["health"], "circuit closed");
+        }
+
+        fn quarantine_handler(
+            dir: &tempfile::TempDir,
+            gemini: Box<dyn CliExecutor>,
+        ) -> ToolHandler {
+            let gemini_config = ExecutorConfig {
+                circuit_failure_threshold: 2,
+                circuit_cooldown_secs: 3600,
+                ..ExecutorConfig::new("mock", &[])
+            };
+            let executors: Vec<ConfiguredExecutor> = vec![
+                (
+                    Box::new(PassExecutor("Codex")),
+                    ExecutorConfig::new("mock", &[]),
+                ),
+                (gemini, gemini_config),
+            ];
+            let mut config = Config::default();
+            config.reasoning.db_path = dir.path().join("patterns.db");
+            ToolHandler::with_executors(config, executors)
+                .unwrap()
+                .with_quarantine(&dir.path().join(QUARANTINE_FILE_NAME))
+        }
+
+        async fn review_plans(handler: &ToolHandler, prefix: &str, count: usize) {
+            for i in 0..count {
+                handler
+                    .handle_tool_call(
+                        "tetrad_review_plan",
+                        json!({ "plan": format!("{} {}", prefix, i) }),
+                    )
+                    .await;
+            }
+        }
+
+        #[tokio::test]
+        async fn test_quarantine_survives_restart() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join(QUARANTINE_FILE_NAME);
+            let calls = Arc::new(AtomicUsize::new(0));
+
+            let first = quarantine_handler(
+                &dir,
+                Box::new(DeadExecutor {
+                    calls: calls.clone(),
+                }),
+            );
+            review_plans(&first, "Add cache layer", 3).await;
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+            assert!(path.exists());
+            drop(first);
+
+            // A new handler (server restart) does not call the broken CLI again
+            let second = quarantine_handler(
+                &dir,
+                Box::new(DeadExecutor {
+                    calls: calls.clone(),
+                }),
+            );
+            review_plans(&second, "Add retry queue", 3).await;
+            assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+            let status = second.handle_tool_call("tetrad_status", json!({})).await;
+            let ToolContent::Text { text } = &status.content[0];
+            let status: Value = serde_json::from_str(text).unwrap();
+            assert!(status["gemini"]["health"]
+                .as_str()
+                .unwrap()
+                .starts_with("circuit open, retry in"));
+            assert_eq!(status["gemini"]["quarantine"]["executor"], "Gemini");
+            assert_eq!(
+                status["gemini"]["quarantine"]["reason"],
+                "Timeout executing 'Gemini'"
+            );
+            assert!(status["codex"]["quarantine"].is_null());
+        }
+
+        #[tokio::test]
+        async fn test_successful_probe_releases_quarantine() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join(QUARANTINE_FILE_NAME);
+            let calls = Arc::new(AtomicUsize::new(0));
+
+            let first = quarantine_handler(&dir, Box::new(DeadExecutor { calls }));
+            review_plans(&first, "Add cache layer", 2).await;
+            drop(first);
+
+            // Retry time already passed: the next evaluation probes the fixed CLI
+            let mut quarantine = crate::executors::QuarantineList::load(&path);
+            let mut entry = quarantine.get("Gemini").unwrap().clone();
+            entry.retry_after = entry.opened_at;
+            quarantine.insert(entry).unwrap();
+
+            let second = quarantine_handler(&dir, Box::new(PassExecutor("Gemini")));
+            review_plans(&second, "Add retry queue", 1).await;
+
+            assert!(second.health.quarantined().is_empty());
+            assert!(!path.exists());
+        }
+    }
+
+    mod estimate_tests {
+        use super::*;
+        use crate::executors::CliExecutor;
+        use crate::mcp::ToolContent;
+        use crate::types::config::ExecutorConfig;
+        use async_trait::async_trait;
+
+        struct PassExecutor(&'static str);
+
+        #[async_trait]
+        impl CliExecutor for PassExecutor {
+            fn name(&self) -> &str {
+                self.0
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                panic!("estimates must not call executors");
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        fn handler(dir: &tempfile::TempDir, disabled: Option<&str>) -> ToolHandler {
+            let executors: Vec<ConfiguredExecutor> = ["Codex", "Gemini", "Qwen"]
+                .into_iter()
+                .map(|name| {
+                    let executor: Box<dyn CliExecutor> = Box::new(PassExecutor(name));
+                    let mut config = ExecutorConfig::new("mock", &[]);
+                    config.enabled = disabled != Some(name);
+                    (executor, config)
+                })
+                .collect();
+
+            let mut config = Config::default();
+            config.reasoning.db_path = dir.path().join("patterns.db");
+            config.general.budget.cost_per_call = 0.02;
+
+            ToolHandler::with_executors(config, executors).unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_estimate_uses_latency_history_and_costs() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir, None);
+            handler
+                .health
+                .record_latency("Codex", Duration::from_secs(4));
+            handler
+                .health
+                .record_latency("Gemini", Duration::from_secs(6));
+            handler
+                .health
+                .record_latency("Gemini", Duration::from_secs(10));
+
+            let request = EvaluationRequest::new("fn main() {}", "rust");
+            let estimate = handler.estimate(&request).await;
+
+            let codex = &estimate.executors[0];
+            assert_eq!(codex.latency_ms, 4_000);
+            assert_eq!(codex.latency_source, "measured");
+            let gemini = &estimate.executors[1];
+            assert_eq!(gemini.latency_ms, 8_000);
+
+            // Qwen has no history: its latency comes from the prompt size
+            let qwen = &estimate.executors[2];
+            assert_eq!(qwen.latency_source, "heuristic");
+            assert_eq!(
+                qwen.latency_ms,
+                heuristic_latency(qwen.prompt_tokens).as_millis() as u64
+            );
+
+            // Executors run in parallel: the slowest one sets the latency
+            assert_eq!(estimate.cache_hit_probability, 0.0);
+            assert_eq!(
+                estimate.expected_latency_ms,
+                gemini.latency_ms.max(qwen.latency_ms)
+            );
+            assert!((estimate.expected_cost - 0.06).abs() < 1e-9);
+            assert_eq!(
+                estimate.prompt_tokens,
+                codex.prompt_tokens + gemini.prompt_tokens + qwen.prompt_tokens
+            );
+        }
+
+        #[tokio::test]
+        async fn test_estimate_of_cached_review_is_free() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir, None);
+            let request = EvaluationRequest::new("fn main() {}", "rust");
+
+            handler.cache.write().await.insert_by_code(
+                &request.cache_content(),
+                request.language.as_str(),
+                &request.evaluation_type,
+                EvaluationResult::success("cached", 90, "ok"),
+            );
+
+            let result = handler
+                .handle_tool_call(
+                    "tetrad_estimate",
+                    json!({"code": "fn main() {}", "language": "rust"}),
+                )
+                .await;
+            assert!(!result.is_error);
+            let ToolContent::Text { text } = &result.content[0];
+            let estimate: Value = serde_json::from_str(text).unwrap();
+
+            assert_eq!(estimate["cache_hit_probability"], 1.0);
+            assert_eq!(estimate["expected_latency_ms"], 0);
+            assert_eq!(estimate["expected_cost"], 0.0);
+
+            // Stats are untouched by the lookup
+            assert_eq!(handler.cache.read().await.stats().hits, 0);
+        }
+
+        #[tokio::test]
+        async fn test_estimate_skips_disabled_executor() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir, Some("Qwen"));
+
+            let estimate = handler
+                .estimate(&EvaluationRequest::new("fn main() {}", "rust"))
+                .await;
+
+            assert!(estimate.executors[2].skipped);
+            assert!((estimate.expected_cost - 0.04).abs() < 1e-9);
+        }
+    }
+
+    mod concurrency_tests {
+        use super::*;
+        use crate::executors::CliExecutor;
+        use crate::mcp::ToolContent;
+        use crate::types::config::ExecutorConfig;
+        use crate::types::responses::Vote;
+        use async_trait::async_trait;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Executor that takes a while and records how many of its calls
+        /// overlap.
+        struct SlowExecutor {
+            name: &'static str,
+            delay: Duration,
+            running: AtomicUsize,
+            peak: Arc<AtomicUsize>,
+        }
+
+        #[async_trait]
+        impl CliExecutor for SlowExecutor {
+            fn name(&self) -> &str {
+                self.name
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+                self.peak.fetch_max(running, Ordering::SeqCst);
+                tokio::time::sleep(self.delay).await;
+                self.running.fetch_sub(1, Ordering::SeqCst);
+                Ok(ModelVote::new(self.name, Vote::Pass, 90))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        fn handler(
+            dir: &tempfile::TempDir,
+            delay: Duration,
+            queue_timeout_secs: u64,
+        ) -> (ToolHandler, Vec<Arc<AtomicUsize>>) {
+            let mut peaks = Vec::new();
+            let executors: Vec<ConfiguredExecutor> = ["Codex", "Gemini", "Qwen"]
+                .into_iter()
+                .map(|name| {
+                    let peak = Arc::new(AtomicUsize::new(0));
+                    peaks.push(Arc::clone(&peak));
+                    let executor: Box<dyn CliExecutor> = Box::new(SlowExecutor {
+                        name,
+                        delay,
+                        running: AtomicUsize::new(0),
+                        peak,
+                    });
+                    (executor, ExecutorConfig::new("mock", &[]))
+                })
+                .collect();
+
+            let mut config = Config::default();
+            config.reasoning.db_path = dir.path().join("patterns.db");
+            config.general.queue_timeout_secs = queue_timeout_secs;
+
+            (
+                ToolHandler::with_executors(config, executors).unwrap(),
+                peaks,
+            )
+        }
+
+        async fn review(handler: &ToolHandler, code: &str) -> ToolResult {
+            handler
+                .handle_tool_call(
+                    "tetrad_review_code",
+                    json!({"code": code, "language": "rust"}),
+                )
+                .await
+        }
+
+        #[tokio::test]
+        async fn test_at_most_two_evaluations_run_at_once() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, peaks) = handler(&dir, Duration::from_millis(100), 0);
+
+            let results = tokio::join!(
+                review(&handler, "fn a() {}"),
+                review(&handler, "fn b() {}"),
+                review(&handler, "fn c() {}"),
+                review(&handler, "fn d() {}"),
+                review(&handler, "fn e() {}"),
+            );
+
+            for result in [results.0, results.1, results.2, results.3, results.4] {
+                assert!(!result.is_error);
+            }
+            for peak in &peaks {
+                assert_eq!(peak.load(Ordering::SeqCst), 2);
+            }
+            let status = handler.limiter.status();
+            assert_eq!(status.in_flight, 0);
+            assert_eq!(status.queued, 0);
+        }
+
+        #[tokio::test]
+        async fn test_queue_timeout_returns_server_busy() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, _) = handler(&dir, Duration::from_millis(1500), 1);
+
+            let (first, second, third) = tokio::join!(
+                review(&handler, "fn a() {}"),
+                review(&handler, "fn b() {}"),
+                review(&handler, "fn c() {}"),
+            );
+            assert!(!first.is_error);
+            assert!(!second.is_error);
+
+            assert!(third.is_error);
+            let ToolContent::Text { text } = &third.content[0];
+            let error: Value = serde_json::from_str(text).unwrap();
+            assert_eq!(error["error"], "server_busy");
+            assert_eq!(error["retryable"], true);
+        }
+
+        #[tokio::test]
+        async fn test_status_reports_concurrency() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, _) = handler(&dir, Duration::ZERO, 0);
+
+            let result = handler.handle_tool_call("tetrad_status", json!({})).await;
+            let ToolContent::Text { text } = &result.content[0];
+            let status: Value = serde_json::from_str(text).unwrap();
+
+            assert_eq!(status["concurrency"]["max_concurrent"], 2);
+            assert_eq!(status["concurrency"]["in_flight"], 0);
+            assert_eq!(status["concurrency"]["queued"], 0);
+            assert!(status["concurrency"]["queue_timeout_secs"].is_null());
+        }
+    }
+
+    mod early_exit_tests {
+        use super::*;
+        use crate::executors::CliExecutor;
+        use crate::mcp::ToolContent;
+        use crate::types::config::{ConsensusConfig, ConsensusRule, ExecutorConfig, SeverityGate};
+        use async_trait::async_trait;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        const FAST: Duration = Duration::from_millis(10);
+        const SLOW: Duration = Duration::from_millis(300);
+
+        /// Executor that votes after a delay and records whether it finished.
+        struct StaggeredExecutor {
+            name: &'static str,
+            vote: Vote,
+            score: u8,
+            delay: Duration,
+            finished: Arc<AtomicBool>,
+        }
+
+        #[async_trait]
+        impl CliExecutor for StaggeredExecutor {
+            fn name(&self) -> &str {
+                self.name
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                tokio::time::sleep(self.delay).await;
+                self.finished.store(true, Ordering::SeqCst);
+                Ok(ModelVote::new(self.name, self.vote, self.score))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        /// Handler whose executors vote `seats` (vote, score, delay), in order
+        /// Codex, Gemini, Qwen; returns whether each one finished.
+        fn handler(
+            dir: &tempfile::TempDir,
+            consensus: ConsensusConfig,
+            seats: [(Vote, u8, Duration); 3],
+        ) -> (ToolHandler, Vec<Arc<AtomicBool>>) {
+            let mut finished = Vec::new();
+            let executors: Vec<ConfiguredExecutor> = ["Codex", "Gemini", "Qwen"]
+                .into_iter()
+                .zip(seats)
+                .map(|(name, (vote, score, delay))| {
+                    let done = Arc::new(AtomicBool::new(false));
+                    finished.push(Arc::clone(&done));
+                    let executor: Box<dyn CliExecutor> = Box::new(StaggeredExecutor {
+                        name,
+                        vote,
+                        score,
+                        delay,
+                        finished: done,
+                    });
+                    (executor, ExecutorConfig::new("mock", &[]))
+                })
+                .collect();
+
+            let mut config = Config::default();
+            config.reasoning.db_path = dir.path().join("patterns.db");
+            config.consensus = consensus;
+
+            (
+                ToolHandler::with_executors(config, executors).unwrap(),
+                finished,
+            )
+        }
+
+        /// Early exit with a fixed seat count: with `adaptive`, a seat that
+        /// fails changes the rule, so fewer decisions settle early.
+        fn consensus(rule: ConsensusRule, gate: SeverityGate) -> ConsensusConfig {
+            ConsensusConfig {
+                default_rule: rule,
+                block_on_severity: gate,
+                adaptive: false,
+                early_exit: true,
+                ..Default::default()
+            }
+        }
+
+        async fn call(handler: &ToolHandler, tool: &str) -> Value {
+            let result = handler
+                .handle_tool_call(tool, json!({"code": "fn main() {}", "language": "rust"}))
+                .await;
+            let ToolContent::Text { text } = &result.content[0];
+            serde_json::from_str(text).unwrap()
+        }
+
+        fn finished(executors: &[Arc<AtomicBool>]) -> Vec<bool> {
+            executors
+                .iter()
+                .map(|done| done.load(Ordering::SeqCst))
+                .collect()
+        }
+
+        #[tokio::test]
+        async fn test_golden_blocks_on_first_fail() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, executors) = handler(
+                &dir,
+                consensus(ConsensusRule::Golden, SeverityGate::Critical),
+                [
+                    (Vote::Fail, 20, FAST),
+                    (Vote::Pass, 90, SLOW),
+                    (Vote::Pass, 90, SLOW),
+                ],
+            );
+
+            let result = call(&handler, "tetrad_review_code").await;
+
+            assert_eq!(result["decision"], "BLOCK");
+            assert_eq!(
+                result["short_circuited_executors"],
+                json!(["Gemini", "Qwen"])
+            );
+            assert_eq!(finished(&executors), [true, false, false]);
+
+            // The score only covers the vote that arrived
+            assert_eq!(result["score"], 20);
+            assert_eq!(result["score_basis"]["votes_received"], 1);
+            assert_eq!(result["score_basis"]["votes_cancelled"], 2);
+            assert!(result["feedback"]
+                .as_str()
+                .unwrap()
+                .contains("Decisão Antecipada"));
+            assert!(result["degraded"].is_null());
+        }
+
+        #[tokio::test]
+        async fn test_weak_blocks_on_failing_majority() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, executors) = handler(
+                &dir,
+                consensus(ConsensusRule::Weak, SeverityGate::Critical),
+                [
+                    (Vote::Fail, 30, FAST),
+                    (Vote::Fail, 20, FAST),
+                    (Vote::Pass, 90, SLOW),
+                ],
+            );
+
+            let result = call(&handler, "tetrad_review_code").await;
+
+            assert_eq!(result["decision"], "BLOCK");
+            assert_eq!(result["consensus_achieved"], true);
+            assert_eq!(result["short_circuited_executors"], json!(["Qwen"]));
+            assert_eq!(finished(&executors), [true, true, false]);
+            assert_eq!(result["score"], 25);
+        }
+
+        #[tokio::test]
+        async fn test_strong_settles_revise_without_severity_gate() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, executors) = handler(
+                &dir,
+                consensus(ConsensusRule::Strong, SeverityGate::Off),
+                [
+                    (Vote::Pass, 90, FAST),
+                    (Vote::Fail, 30, FAST),
+                    (Vote::Pass, 90, SLOW),
+                ],
+            );
+
+            let result = call(&handler, "tetrad_review_code").await;
+
+            assert_eq!(result["decision"], "REVISE");
+            assert_eq!(result["consensus_achieved"], false);
+            assert_eq!(result["short_circuited_executors"], json!(["Qwen"]));
+            assert_eq!(finished(&executors), [true, true, false]);
+        }
+
+        #[tokio::test]
+        async fn test_strong_waits_when_a_finding_could_still_block() {
+            // Same votes, but the outstanding executor could report a
+            // critical finding and turn REVISE into BLOCK
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, executors) = handler(
+                &dir,
+                consensus(ConsensusRule::Strong, SeverityGate::Critical),
+                [
+                    (Vote::Pass, 90, FAST),
+                    (Vote::Fail, 30, FAST),
+                    (Vote::Pass, 90, SLOW),
+                ],
+            );
+
+            let result = call(&handler, "tetrad_review_code").await;
+
+            assert_eq!(result["decision"], "REVISE");
+            assert!(result["short_circuited_executors"].is_null());
+            assert_eq!(finished(&executors), [true, true, true]);
+        }
+
+        #[tokio::test]
+        async fn test_weak_waits_while_the_pass_average_is_open() {
+            // A low-scoring PASS from Qwen would drag the PASS average below
+            // min_score, so two passes do not settle the decision
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, executors) = handler(
+                &dir,
+                consensus(ConsensusRule::Weak, SeverityGate::Off),
+                [
+                    (Vote::Pass, 90, FAST),
+                    (Vote::Pass, 90, FAST),
+                    (Vote::Pass, 90, SLOW),
+                ],
+            );
+
+            let result = call(&handler, "tetrad_review_code").await;
+
+            assert_eq!(result["decision"], "PASS");
+            assert!(result["short_circuited_executors"].is_null());
+            assert_eq!(finished(&executors), [true, true, true]);
+            assert!(result["score_basis"].is_null());
+        }
+
+        #[tokio::test]
+        async fn test_disabled_by_default() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, executors) = handler(
+                &dir,
+                ConsensusConfig {
+                    early_exit: false,
+                    ..consensus(ConsensusRule::Golden, SeverityGate::Critical)
+                },
+                [
+                    (Vote::Fail, 20, FAST),
+                    (Vote::Pass, 90, SLOW),
+                    (Vote::Pass, 90, SLOW),
+                ],
+            );
+
+            let result = call(&handler, "tetrad_review_code").await;
+
+            assert_eq!(result["decision"], "BLOCK");
+            assert!(result["short_circuited_executors"].is_null());
+            assert_eq!(finished(&executors), [true, true, true]);
+        }
+
+        #[tokio::test]
+        async fn test_adaptive_golden_waits_for_every_vote() {
+            // If Gemini failed, the adaptive rule would judge Codex and Qwen
+            // with dual-evaluator, where FAIL + PASS is only REVISE
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, executors) = handler(
+                &dir,
+                ConsensusConfig {
+                    adaptive: true,
+                    ..consensus(ConsensusRule::Golden, SeverityGate::Critical)
+                },
+                [
+                    (Vote::Fail, 20, FAST),
+                    (Vote::Pass, 90, SLOW),
+                    (Vote::Pass, 90, SLOW),
+                ],
+            );
+
+            let result = call(&handler, "tetrad_review_code").await;
+
+            assert_eq!(result["decision"], "BLOCK");
+            assert!(result["short_circuited_executors"].is_null());
+            assert_eq!(finished(&executors), [true, true, true]);
+        }
+
+        #[tokio::test]
+        async fn test_final_check_waits_for_every_vote() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, executors) = handler(
+                &dir,
+                consensus(ConsensusRule::Golden, SeverityGate::Critical),
+                [
+                    (Vote::Fail, 20, FAST),
+                    (Vote::Pass, 90, SLOW),
+                    (Vote::Pass, 90, SLOW),
+                ],
+            );
+
+            let result = call(&handler, "tetrad_final_check").await;
+
+            assert_eq!(result["certified"], false);
+            assert_eq!(finished(&executors), [true, true, true]);
+        }
+    }
+
+    mod executor_filter_tests {
+        use super::*;
+        use crate::executors::CliExecutor;
+        use crate::mcp::ToolContent;
+        use crate::types::config::ExecutorConfig;
+        use async_trait::async_trait;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// Executor with a fixed vote that counts its calls.
+        struct FixedExecutor {
+            name: &'static str,
+            vote: Vote,
+            score: u8,
+            calls: Arc<AtomicUsize>,
+        }
+
+        #[async_trait]
+        impl CliExecutor for FixedExecutor {
+            fn name(&self) -> &str {
+                self.name
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                Ok(ModelVote::new(self.name, self.vote, self.score))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        /// Codex and Qwen pass, Gemini fails; a disabled "Local" executor is
+        /// also configured. Adaptive consensus is off, so only the requested
+        /// voter count lets two executors reach a Strong decision.
+        fn handler(dir: &tempfile::TempDir) -> (ToolHandler, Arc<AtomicUsize>) {
+            let gemini_calls = Arc::new(AtomicUsize::new(0));
+            let seats = [
+                ("Codex", Vote::Pass, 90, true),
+                ("Gemini", Vote::Fail, 10, true),
+                ("Qwen", Vote::Pass, 90, true),
+                ("Local", Vote::Pass, 90, false),
+            ];
+            let executors: Vec<ConfiguredExecutor> = seats
+                .into_iter()
+                .map(|(name, vote, score, enabled)| {
+                    let calls = if name == "Gemini" {
+                        Arc::clone(&gemini_calls)
+                    } else {
+                        Arc::new(AtomicUsize::new(0))
+                    };
+                    let executor: Box<dyn CliExecutor> = Box::new(FixedExecutor {
+                        name,
+                        vote,
+                        score,
+                        calls,
+                    });
+                    let config = ExecutorConfig {
+                        enabled,
+                        ..ExecutorConfig::new("mock", &[])
+                    };
+                    (executor, config)
+                })
+                .collect();
+
+            let mut config = Config::default();
+            config.reasoning.db_path = dir.path().join("patterns.db");
+            config.consensus.adaptive = false;
+
+            (
+                ToolHandler::with_executors(config, executors).unwrap(),
+                gemini_calls,
+            )
+        }
+
+        async fn call(handler: &ToolHandler, tool: &str, arguments: Value) -> ToolResult {
+            handler.handle_tool_call(tool, arguments).await
+        }
+
+        fn json_of(result: &ToolResult) -> Value {
+            let ToolContent::Text { text } = &result.content[0];
+            serde_json::from_str(text).unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_filter_restricts_voters_and_sizes_the_rule() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, gemini_calls) = handler(&dir);
+
+            let result = call(
+                &handler,
+                "tetrad_review_code",
+                json!({"code": "fn a() {}", "language": "rust", "executors": ["codex", "Qwen"]}),
+            )
+            .await;
+            assert!(!result.is_error);
+            let response = json_of(&result);
+
+            // Strong needs every voter, and the request asked for two
+            assert_eq!(response["decision"], "PASS");
+            assert_eq!(response["consensus_achieved"], true);
+            assert_eq!(response["votes"].as_array().unwrap().len(), 2);
+            assert_eq!(gemini_calls.load(Ordering::SeqCst), 0);
+            assert!(response["degraded"].is_null());
+
+            assert_eq!(response["executors"]["consulted"], json!(["Codex", "Qwen"]));
+            assert_eq!(
+                response["executors"]["skipped_by_request"],
+                json!(["Gemini"])
+            );
+            assert_eq!(
+                response["executors"]["disabled_in_config"],
+                json!(["Local"])
+            );
+        }
+
+        #[tokio::test]
+        async fn test_without_filter_every_enabled_executor_votes() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, gemini_calls) = handler(&dir);
+
+            let unfiltered = json_of(
+                &call(
+                    &handler,
+                    "tetrad_review_code",
+                    json!({"code": "fn a() {}", "language": "rust"}),
+                )
+                .await,
+            );
+            assert_eq!(unfiltered["decision"], "REVISE");
+            assert_eq!(gemini_calls.load(Ordering::SeqCst), 1);
+            assert!(unfiltered["executors"].is_null());
+
+            // The same code with a filter is not answered from the cache
+            let filtered = json_of(
+                &call(
+                    &handler,
+                    "tetrad_review_code",
+                    json!({"code": "fn a() {}", "language": "rust", "executors": ["Codex", "Qwen"]}),
+                )
+                .await,
+            );
+            assert_eq!(filtered["decision"], "PASS");
+        }
+
+        #[tokio::test]
+        async fn test_unknown_executor_is_rejected() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, _) = handler(&dir);
+
+            let result = call(
+                &handler,
+                "tetrad_review_tests",
+                json!({"tests": "#[test] fn t() {}", "language": "rust", "executors": ["Claude"]}),
+            )
+            .await;
+            assert!(result.is_error);
+            let ToolContent::Text { text } = &result.content[0];
+            assert!(text.contains("Unknown executor 'Claude'"));
+            assert!(text.contains("Codex, Gemini, Qwen, Local"));
+        }
+
+        #[tokio::test]
+        async fn test_filter_needs_an_enabled_executor() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, _) = handler(&dir);
+
+            for executors in [json!([]), json!(["Local"])] {
+                let result = call(
+                    &handler,
+                    "tetrad_review_code",
+                    json!({"code": "fn a() {}", "language": "rust", "executors": executors}),
+                )
+                .await;
+                assert!(result.is_error);
+            }
+        }
+
+        #[tokio::test]
+        async fn test_review_tests_and_final_check_accept_filter() {
+            let dir = tempfile::tempdir().unwrap();
+            let (handler, gemini_calls) = handler(&dir);
+
+            let tests = json_of(
+                &call(
+                    &handler,
+                    "tetrad_review_tests",
+                    json!({"tests": "#[test] fn t() {}", "language": "rust", "executors": ["Codex", "Qwen"]}),
+                )
+                .await,
+            );
+            assert_eq!(tests["decision"], "PASS");
+
+            let final_check = json_of(
+                &call(
+                    &handler,
+                    "tetrad_final_check",
+                    json!({"code": "fn a() {}", "language": "rust", "executors": ["Codex", "Qwen"]}),
+                )
+                .await,
+            );
+            assert_eq!(final_check["certified"], true);
+            assert_eq!(
+                final_check["executors"]["skipped_by_request"],
+                json!(["Gemini"])
+            );
+            assert_eq!(gemini_calls.load(Ordering::SeqCst), 0);
+        }
+    }
+
+    mod reload_tests {
+        use super::*;
+        use crate::executors::CliExecutor;
+        use crate::hooks::{HookContext, HookEvent, HookResult, LoggingHook};
+        use crate::mcp::ToolContent;
+        use crate::types::config::ExecutorConfig;
+        use crate::types::responses::Vote;
+        use async_trait::async_trait;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct PassExecutor(&'static str);
+
+        #[async_trait]
+        impl CliExecutor for PassExecutor {
+            fn name(&self) -> &str {
+                self.0
+            }
+
+            fn command(&self) -> &str {
+                "mock"
+            }
+
+            async fn is_available(&self) -> bool {
+                true
+            }
+
+            async fn evaluate(&self, _request: &EvaluationRequest) -> TetradResult<ModelVote> {
+                Ok(ModelVote::new(self.0, Vote::Pass, 75))
+            }
+
+            fn specialization(&self) -> &str {
+                "test"
+            }
+        }
+
+        fn config(dir: &tempfile::TempDir) -> Config {
+            let mut config = Config::default();
+            config.reasoning.db_path = dir.path().join("patterns.db");
+            config.consensus.min_score = 70;
+            config
+        }
+
+        fn handler(dir: &tempfile::TempDir) -> ToolHandler {
+            let executors: Vec<ConfiguredExecutor> = ["Codex", "Gemini", "Qwen"]
+                .into_iter()
+                .map(|name| {
+                    let executor: Box<dyn CliExecutor> = Box::new(PassExecutor(name));
+                    (executor, ExecutorConfig::new("mock", &[]))
+                })
+                .collect();
+            ToolHandler::with_executors(config(dir), executors).unwrap()
+        }
+
+        async fn final_check(handler: &ToolHandler, code: &str) -> Value {
+            let result = handler
+                .handle_tool_call(
+                    "tetrad_final_check",
+                    json!({"code": code, "language": "rust"}),
+                )
+                .await;
+            let ToolContent::Text { text } = &result.content[0];
+            serde_json::from_str(text).unwrap()
+        }
+
+        #[tokio::test]
+        async fn test_reload_applies_new_min_score() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir);
+            assert_eq!(final_check(&handler, "fn a() {}").await["certified"], true);
+
+            let mut config = config(&dir);
+            config.consensus.min_score = 80;
+            handler.reload(config).await.unwrap();
+
+            assert_eq!(handler.state().config.consensus.min_score, 80);
+            assert_eq!(final_check(&handler, "fn b() {}").await["certified"], false);
+        }
+
+        #[tokio::test]
+        async fn test_reload_rejects_invalid_config() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir);
+
+            let mut config = config(&dir);
+            config.consensus.min_score = 80;
+            config.consensus.feedback_template = Some(dir.path().join("missing.txt"));
+            assert!(handler.reload(config).await.is_err());
+
+            // The previous configuration stays active
+            assert_eq!(handler.state().config.consensus.min_score, 70);
+            assert_eq!(final_check(&handler, "fn a() {}").await["certified"], true);
+        }
+
+        #[tokio::test]
+        async fn test_reload_keeps_explicit_executors_and_extra_hooks() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir);
+            let defaults = handler.state().hooks.count();
+            let handler = handler.with_hook(Box::new(LoggingHook::new()));
+            assert_eq!(handler.state().hooks.count(), defaults + 1);
+
+            handler.reload(config(&dir)).await.unwrap();
+
+            let state = handler.state();
+            assert_eq!(state.executors.len(), 3);
+            assert_eq!(state.hooks.count(), defaults + 1);
+        }
+
+        /// post_evaluate hook that takes a while, so evaluations overlap the swaps.
+        struct SlowHook(Arc<AtomicUsize>);
+
+        #[async_trait]
+        impl Hook for SlowHook {
+            fn name(&self) -> &str {
+                "slow"
+            }
+
+            fn event(&self) -> HookEvent {
+                HookEvent::PostEvaluate
+            }
+
+            async fn execute(&self, _context: &HookContext<'_>) -> TetradResult<HookResult> {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                self.0.fetch_add(1, Ordering::SeqCst);
+                Ok(HookResult::Continue)
+            }
+        }
+
+        #[tokio::test]
+        async fn test_hooks_swapped_while_evaluations_run() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir);
+            let defaults = handler.state().hooks.count();
+            let runs = Arc::new(AtomicUsize::new(0));
+            handler.add_hook(Box::new(SlowHook(runs.clone())));
+
+            let swaps = async {
+                for i in 0..20 {
+                    assert_eq!(handler.remove_hook("slow"), 1);
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                    handler.add_hook(Box::new(SlowHook(runs.clone())));
+                    if i % 5 == 0 {
+                        handler.reload(config(&dir)).await.unwrap();
+                    }
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                }
+            };
+            let (a, b, c, d, ()) = tokio::join!(
+                final_check(&handler, "fn a() {}"),
+                final_check(&handler, "fn b() {}"),
+                final_check(&handler, "fn c() {}"),
+                final_check(&handler, "fn d() {}"),
+                swaps
+            );
+
+            for response in [a, b, c, d] {
+                assert_eq!(response["certified"], true, "{}", response);
+            }
+            // The removals and reloads left exactly one copy of the runtime hook
+            let state = handler.state();
+            assert_eq!(state.hooks.count(), defaults + 1);
+            assert_eq!(state.hooks.count_for_event(HookEvent::PostEvaluate), 3);
+
+            final_check(&handler, "fn e() {}").await;
+            assert!(runs.load(Ordering::SeqCst) >= 1);
+        }
+
+        /// Org policy: anything touching `unsafe` is blocked.
+        struct UnsafePolicyHook;
+
+        #[async_trait]
+        impl Hook for UnsafePolicyHook {
+            fn name(&self) -> &str {
+                "unsafe_policy"
+            }
+
+            fn event(&self) -> HookEvent {
+                HookEvent::PostEvaluate
+            }
+
+            async fn execute(&self, context: &HookContext<'_>) -> TetradResult<HookResult> {
+                let HookContext::PostEvaluate { request, result } = context else {
+                    return Ok(HookResult::Continue);
+                };
+                if !request.code.contains("unsafe") {
+                    return Ok(HookResult::Continue);
+                }
+                let mut result = (*result).clone();
+                result.decision = Decision::Block;
+                result.feedback.push_str("\nBlocked by policy: unsafe code");
+                Ok(HookResult::ModifyResult(Box::new(result)))
+            }
+        }
+
+        #[tokio::test]
+        async fn test_post_evaluate_hook_rewrites_cached_and_judged_result() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir).with_hook(Box::new(UnsafePolicyHook));
+
+            for _ in 0..2 {
+                let result = handler
+                    .handle_tool_call(
+                        "tetrad_review_code",
+                        json!({"code": "unsafe { f() }", "language": "rust"}),
+                    )
+                    .await;
+                let ToolContent::Text { text } = &result.content[0];
+                let result: Value = serde_json::from_str(text).unwrap();
+                assert_eq!(result["decision"], "BLOCK", "{}", result);
+                assert!(result["feedback"]
+                    .as_str()
+                    .unwrap()
+                    .contains("Blocked by policy"));
+            }
+
+            // The second call came from the cache, which holds the rewritten result
+            let stats = handler.cache.read().await.stats();
+            assert_eq!((stats.size, stats.hits), (1, 1));
+
+            // The ReasoningBank judged the rewritten decision
+            let bank = handler.reasoning_bank.lock().await;
+            let history = bank.as_ref().unwrap().store().decision_history().unwrap();
+            assert_eq!(history.len(), 1);
+            assert_eq!(history[0].decision, "block");
+        }
+
+        #[tokio::test]
+        async fn test_remove_hook_survives_reload() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir).with_hook(Box::new(LoggingHook::new()));
+            let with_extra = handler.state().hooks.count();
+
+            // The running request's snapshot keeps the hook
+            let snapshot = handler.state();
+            assert_eq!(handler.remove_hook("logging"), 2);
+            assert_eq!(snapshot.hooks.count(), with_extra);
+
+            // Removed default hooks come back from the configuration; the
+            // removed runtime hook does not
+            handler.reload(config(&dir)).await.unwrap();
+            assert_eq!(handler.state().hooks.count(), with_extra - 1);
+            assert_eq!(handler.remove_hook("missing"), 0);
+        }
+
+        #[tokio::test]
+        async fn test_reload_resizes_cache_and_keeps_entries() {
+            let dir = tempfile::tempdir().unwrap();
+            let handler = handler(&dir);
+            final_check(&handler, "fn a() {}").await;
+            assert_eq!(handler.cache.read().await.stats().size, 1);
+
+            let mut config = config(&dir);
+            config.cache.capacity = 500;
+            handler.reload(config).await.unwrap();
+
+            let stats = handler.cache.read().await.stats();
+            assert_eq!(stats.capacity, 500);
+            assert_eq!(stats.size, 1);
+        }
+    }
 }
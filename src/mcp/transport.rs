@@ -17,83 +17,188 @@
 //! {"jsonrpc":"2.0","id":1,"method":"initialize","params":{...}}\n
 //! {"jsonrpc":"2.0","id":1,"result":{...}}\n
 //! ```
+//!
+//! ## Entrada fora do formato
+//!
+//! Alguns clientes (ou proxies entre eles e o Tetrad) enviam dois objetos na
+//! mesma linha ou quebram um objeto em várias linhas. A leitura usa um
+//! [`StreamDeserializer`](serde_json::StreamDeserializer) sobre um buffer:
+//! objetos concatenados são processados um a um e objetos incompletos ficam
+//! no buffer até a próxima linha completá-los, até [`MAX_MESSAGE_BYTES`].
+//! JSON inválido ou grande demais recebe uma resposta `PARSE_ERROR` e a
+//! sessão continua.
+//!
+//! ## Concorrência
+//!
+//! O servidor processa cada requisição numa tarefa própria, então as
+//! respostas podem sair fora de ordem (o JSON-RPC as casa pelo ID). Elas são
+//! escritas por um [`ResponseSink`] compartilhado, protegido pelo mesmo mutex
+//! das notificações, para que duas tarefas nunca intercalem uma linha. A
+//! leitura de stdin roda numa thread dedicada e não bloqueia o runtime.
+//!
+//! ## Desconexão
+//!
+//! Se o cliente cai, a leitura recebe EOF e a escrita falha com `EPIPE`.
+//! [`is_disconnect`] reconhece os dois casos, que encerram a sessão
+//! normalmente em vez de virar erros repetidos no log.
 
-use std::io::{BufRead, BufReader, BufWriter, Stdin, Stdout, Write};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
+use async_trait::async_trait;
+use serde_json::Value;
+use tokio::sync::{mpsc, watch};
+
+use crate::types::errors::TetradError;
 use crate::TetradResult;
 
-use super::protocol::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
+use super::protocol::{JsonRpcError, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse};
 
-/// Transporte stdio para comunicação com o cliente MCP.
+/// Maior mensagem incompleta mantida no buffer à espera das próximas linhas.
+pub const MAX_MESSAGE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Indica se stdout está reservado exclusivamente para mensagens JSON-RPC.
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+/// Retorna `true` enquanto o transporte stdio é dono de stdout.
 ///
-/// Implementa o protocolo MCP usando newline-delimited JSON sobre stdin/stdout.
-pub struct StdioTransport {
-    reader: BufReader<Stdin>,
-    writer: BufWriter<Stdout>,
+/// Qualquer saída incidental (avisos, dicas, progresso) deve ir para stderr
+/// enquanto esta função retornar `true`, caso contrário o stream JSON-RPC
+/// é corrompido e o cliente MCP encerra a sessão.
+pub fn stdout_reserved() -> bool {
+    STDOUT_RESERVED.load(Ordering::SeqCst)
 }
 
-impl StdioTransport {
-    /// Cria um novo transporte stdio.
-    pub fn new() -> Self {
-        Self {
-            reader: BufReader::new(std::io::stdin()),
-            writer: BufWriter::new(std::io::stdout()),
-        }
+/// Guard que reserva stdout para o transporte MCP enquanto existir.
+///
+/// Adquirido pelo servidor durante o loop principal e liberado no drop.
+pub struct StdoutReservation {
+    _private: (),
+}
+
+impl StdoutReservation {
+    /// Reserva stdout para o transporte MCP.
+    pub fn acquire() -> Self {
+        STDOUT_RESERVED.store(true, Ordering::SeqCst);
+        Self { _private: () }
     }
+}
 
-    /// Lê uma mensagem JSON-RPC de stdin.
-    ///
-    /// O formato esperado é newline-delimited JSON:
-    /// ```text
-    /// {"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}\n
-    /// ```
-    ///
-    /// Esta função bloqueia até receber uma linha completa.
-    pub fn read_message(&mut self) -> TetradResult<JsonRpcRequest> {
-        let mut line = String::new();
+impl Drop for StdoutReservation {
+    fn drop(&mut self) {
+        STDOUT_RESERVED.store(false, Ordering::SeqCst);
+    }
+}
 
-        // Lê uma linha completa de stdin
-        let bytes_read = self
-            .reader
-            .read_line(&mut line)
-            .map_err(crate::types::errors::TetradError::Io)?;
+/// Se o erro indica que o cliente encerrou a conexão: EOF na leitura, ou
+/// pipe quebrado / conexão resetada na escrita.
+pub fn is_disconnect(error: &TetradError) -> bool {
+    match error {
+        TetradError::Io(e) => matches!(
+            e.kind(),
+            io::ErrorKind::BrokenPipe
+                | io::ErrorKind::UnexpectedEof
+                | io::ErrorKind::ConnectionReset
+                | io::ErrorKind::ConnectionAborted
+        ),
+        TetradError::Config(msg) => msg == "EOF",
+        _ => false,
+    }
+}
 
-        // EOF detectado (0 bytes lidos)
-        if bytes_read == 0 {
-            return Err(crate::types::errors::TetradError::config("EOF"));
-        }
+/// Destino de notificações enviadas durante o processamento de uma requisição.
+///
+/// Permite que os handlers enviem mensagens (ex: `notifications/progress`)
+/// antes da resposta, sem acesso exclusivo ao transporte.
+pub trait NotificationSink: Send + Sync {
+    /// Envia uma notificação ao cliente.
+    fn send_notification(&self, notification: &JsonRpcNotification) -> TetradResult<()>;
+}
 
-        // Remove whitespace (incluindo \n e \r\n)
-        let trimmed = line.trim();
+/// Destino das respostas, compartilhado entre as tarefas que processam
+/// requisições em paralelo.
+pub trait ResponseSink: Send + Sync {
+    /// Envia uma resposta ao cliente.
+    fn send_response(&self, response: &JsonRpcResponse) -> TetradResult<()>;
+}
 
-        // Linha vazia = EOF ou mensagem inválida
-        if trimmed.is_empty() {
-            return Err(crate::types::errors::TetradError::config(
-                "Empty message received",
-            ));
-        }
+/// Transporte usado pelo loop principal do [`McpServer`](super::McpServer).
+#[async_trait]
+pub trait Transport: Send {
+    /// Lê a próxima requisição.
+    ///
+    /// Um erro contendo "EOF" indica que o cliente desconectou.
+    async fn read_message(&mut self) -> TetradResult<JsonRpcRequest>;
 
-        // Parse do JSON
-        let request: JsonRpcRequest =
-            serde_json::from_str(trimmed).map_err(crate::types::errors::TetradError::Json)?;
+    /// Escreve uma resposta.
+    fn write_response(&mut self, response: &JsonRpcResponse) -> TetradResult<()>;
 
-        tracing::debug!(
-            method = %request.method,
-            id = ?request.id,
-            "Received request"
-        );
+    /// Retorna um destino de notificações que escreve no mesmo canal das respostas.
+    fn notifier(&self) -> Arc<dyn NotificationSink>;
 
-        Ok(request)
+    /// Retorna um destino de respostas que pode ser usado fora do loop de
+    /// leitura, no mesmo canal de [`Transport::write_response`].
+    fn responder(&self) -> Arc<dyn ResponseSink>;
+
+    /// Se o transporte escreve em stdout, que fica reservado ao JSON-RPC
+    /// enquanto o servidor roda.
+    fn uses_stdout(&self) -> bool {
+        false
     }
 
-    /// Escreve uma resposta JSON-RPC para stdout.
+    /// Sinal que vira `true` quando uma escrita detecta que o cliente fechou
+    /// a conexão. O servidor o usa para cancelar a requisição em andamento.
+    fn closed(&self) -> Option<watch::Receiver<bool>> {
+        None
+    }
+
+    /// Se o EOF da leitura significa que o cliente foi embora, e as
+    /// requisições em andamento devem ser canceladas em vez de respondidas.
+    fn cancels_on_eof(&self) -> bool {
+        false
+    }
+}
+
+/// Escritor de stdout compartilhado entre respostas e notificações.
+///
+/// O mutex garante que mensagens de tarefas diferentes não se intercalem
+/// no meio de uma linha.
+#[derive(Clone)]
+struct SharedStdout {
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
+    closed: Arc<watch::Sender<bool>>,
+}
+
+impl SharedStdout {
+    /// Escreve uma mensagem no formato MCP (newline-delimited JSON).
     ///
-    /// A resposta é serializada como JSON compacto (sem newlines embutidos)
-    /// seguido de um caractere newline (`\n`).
-    pub fn write_response(&mut self, response: &JsonRpcResponse) -> TetradResult<()> {
-        // Serializa como JSON compacto (sem pretty print para evitar newlines)
-        let body =
-            serde_json::to_string(response).map_err(crate::types::errors::TetradError::Json)?;
+    /// Formato: `<json>\n`
+    fn write_message(&self, body: &str) -> TetradResult<()> {
+        let mut writer = self.writer.lock().map_err(|_| {
+            crate::types::errors::TetradError::McpServer("stdout lock poisoned".into())
+        })?;
+
+        // Escreve o JSON seguido de newline; o flush é crítico para garantir
+        // que a mensagem seja enviada imediatamente
+        let written = writer
+            .write_all(body.as_bytes())
+            .and_then(|()| writer.write_all(b"\n"))
+            .and_then(|()| writer.flush())
+            .map_err(crate::types::errors::TetradError::Io);
+
+        if let Err(e) = &written {
+            if is_disconnect(e) {
+                self.closed.send_replace(true);
+            }
+        }
+        written
+    }
+}
+
+impl ResponseSink for SharedStdout {
+    fn send_response(&self, response: &JsonRpcResponse) -> TetradResult<()> {
+        let body = serde_json::to_string(response).map_err(TetradError::Json)?;
 
         self.write_message(&body)?;
 
@@ -105,9 +210,10 @@ impl StdioTransport {
 
         Ok(())
     }
+}
 
-    /// Envia uma notificação (mensagem sem ID que não espera resposta).
-    pub fn send_notification(&mut self, notification: &JsonRpcNotification) -> TetradResult<()> {
+impl NotificationSink for SharedStdout {
+    fn send_notification(&self, notification: &JsonRpcNotification) -> TetradResult<()> {
         let body =
             serde_json::to_string(notification).map_err(crate::types::errors::TetradError::Json)?;
 
@@ -120,26 +226,314 @@ impl StdioTransport {
 
         Ok(())
     }
+}
 
-    /// Escreve uma mensagem no formato MCP (newline-delimited JSON).
+/// Mensagem extraída do stream de entrada.
+enum Frame {
+    /// Requisição ou notificação JSON-RPC.
+    Request(JsonRpcRequest),
+    /// Entrada inválida; a resposta de erro deve ser enviada ao cliente.
+    Invalid(JsonRpcResponse),
+}
+
+/// Leitor de mensagens JSON-RPC tolerante a objetos concatenados ou
+/// quebrados em várias linhas.
+struct MessageReader {
+    reader: Box<dyn BufRead + Send + Sync>,
+    buffer: String,
+    /// Início, no buffer, da última linha lida.
+    last_line: usize,
+    max_bytes: usize,
+}
+
+impl MessageReader {
+    fn new(reader: impl BufRead + Send + Sync + 'static) -> Self {
+        Self {
+            reader: Box::new(reader),
+            buffer: String::new(),
+            last_line: 0,
+            max_bytes: MAX_MESSAGE_BYTES,
+        }
+    }
+
+    /// Lê a próxima mensagem, consumindo linhas de entrada conforme preciso.
     ///
-    /// Formato: `<json>\n`
-    fn write_message(&mut self, body: &str) -> TetradResult<()> {
-        // Escreve o JSON seguido de newline
-        self.writer
-            .write_all(body.as_bytes())
-            .map_err(crate::types::errors::TetradError::Io)?;
+    /// Retorna erro "EOF" quando a entrada acaba; um objeto incompleto
+    /// pendente nesse momento é descartado.
+    fn next_frame(&mut self) -> TetradResult<Frame> {
+        loop {
+            self.consume(self.buffer.len() - self.buffer.trim_start().len());
+
+            if !self.buffer.is_empty() {
+                let (parsed, offset) = {
+                    let mut stream =
+                        serde_json::Deserializer::from_str(&self.buffer).into_iter::<Value>();
+                    let parsed = stream.next();
+                    (parsed, stream.byte_offset())
+                };
+
+                match parsed {
+                    Some(Ok(value)) => {
+                        self.consume(offset);
+                        return Ok(request_frame(value));
+                    }
+                    Some(Err(e)) if e.is_eof() => {
+                        // Fragmento truncado seguido de uma requisição inteira: o
+                        // fragmento é descartado em vez de engolir a requisição
+                        if self.last_line > 0 && self.last_line_is_request() {
+                            tracing::warn!("Discarding truncated message");
+                            self.consume(self.last_line);
+                            return Ok(parse_error("truncated message".to_string()));
+                        }
+                        if self.buffer.len() > self.max_bytes {
+                            tracing::warn!(
+                                bytes = self.buffer.len(),
+                                limit = self.max_bytes,
+                                "Discarding incomplete message over the size limit"
+                            );
+                            self.buffer.clear();
+                            self.last_line = 0;
+                            return Ok(parse_error(format!(
+                                "message exceeds {} bytes without completing a JSON object",
+                                self.max_bytes
+                            )));
+                        }
+                    }
+                    Some(Err(e)) => {
+                        tracing::warn!(error = %e, "Discarding malformed message");
+                        // A última linha pode ser uma mensagem nova depois de um
+                        // fragmento inválido; ela é mantida para a próxima leitura
+                        let discard = if self.last_line > 0 {
+                            self.last_line
+                        } else {
+                            self.buffer.len()
+                        };
+                        self.consume(discard);
+                        return Ok(parse_error(e.to_string()));
+                    }
+                    None => {}
+                }
+            }
+
+            let mut line = String::new();
+            let bytes_read = self.reader.read_line(&mut line).map_err(TetradError::Io)?;
+            if bytes_read == 0 {
+                if !self.buffer.is_empty() {
+                    tracing::warn!(
+                        bytes = self.buffer.len(),
+                        "Discarding incomplete message at EOF"
+                    );
+                    self.buffer.clear();
+                }
+                return Err(TetradError::config("EOF"));
+            }
+
+            // Mensagens MCP não têm newlines embutidos: uma quebra dentro de
+            // um objeto veio de quem dividiu a mensagem e é removida
+            self.last_line = self.buffer.len();
+            self.buffer.push_str(line.trim_end_matches(['\r', '\n']));
+        }
+    }
 
-        self.writer
-            .write_all(b"\n")
-            .map_err(crate::types::errors::TetradError::Io)?;
+    /// Se a última linha lida é, sozinha, uma requisição JSON-RPC completa.
+    fn last_line_is_request(&self) -> bool {
+        let mut stream = serde_json::Deserializer::from_str(&self.buffer[self.last_line..])
+            .into_iter::<JsonRpcRequest>();
+        matches!(stream.next(), Some(Ok(request)) if request.jsonrpc == "2.0")
+    }
 
-        // Flush é crítico para garantir que a mensagem seja enviada imediatamente
-        self.writer
-            .flush()
-            .map_err(crate::types::errors::TetradError::Io)?;
+    /// Remove os primeiros `bytes` do buffer.
+    fn consume(&mut self, bytes: usize) {
+        self.buffer.drain(..bytes);
+        self.last_line = self.last_line.saturating_sub(bytes);
+    }
+}
 
-        Ok(())
+/// Converte um valor JSON completo em requisição, ou em `INVALID_REQUEST`
+/// se ele não tiver o formato JSON-RPC.
+fn request_frame(value: Value) -> Frame {
+    let id = value
+        .get("id")
+        .and_then(|id| serde_json::from_value(id.clone()).ok());
+
+    match serde_json::from_value::<JsonRpcRequest>(value) {
+        Ok(request) => {
+            tracing::debug!(
+                method = %request.method,
+                id = ?request.id,
+                "Received request"
+            );
+            Frame::Request(request)
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "Received a JSON value that is not a JSON-RPC request");
+            Frame::Invalid(JsonRpcResponse::error(
+                id,
+                JsonRpcError::invalid_request().with_data(Value::String(e.to_string())),
+            ))
+        }
+    }
+}
+
+/// Resposta `PARSE_ERROR` (sem ID) com o motivo em `data`.
+fn parse_error(reason: String) -> Frame {
+    Frame::Invalid(JsonRpcResponse::error(
+        None,
+        JsonRpcError::parse_error().with_data(Value::String(reason)),
+    ))
+}
+
+/// Transporte stdio para comunicação com o cliente MCP.
+///
+/// Implementa o protocolo MCP usando newline-delimited JSON sobre stdin/stdout.
+/// Na primeira leitura, o [`MessageReader`] passa para uma thread dedicada
+/// que entrega as requisições por um canal.
+pub struct StdioTransport {
+    reader: Option<MessageReader>,
+    incoming: Option<mpsc::UnboundedReceiver<TetradResult<JsonRpcRequest>>>,
+    writer: SharedStdout,
+    owns_stdout: bool,
+}
+
+impl StdioTransport {
+    /// Cria um novo transporte stdio.
+    pub fn new() -> Self {
+        let mut transport = Self::from_io(std::io::stdin(), std::io::stdout());
+        transport.owns_stdout = true;
+        transport
+    }
+
+    /// Cria o transporte sobre outro par de streams (ex: pipes em testes).
+    pub fn from_io(
+        reader: impl Read + Send + Sync + 'static,
+        writer: impl Write + Send + 'static,
+    ) -> Self {
+        Self {
+            reader: Some(MessageReader::new(BufReader::new(reader))),
+            incoming: None,
+            writer: SharedStdout {
+                writer: Arc::new(Mutex::new(Box::new(BufWriter::new(writer)))),
+                closed: Arc::new(watch::channel(false).0),
+            },
+            owns_stdout: false,
+        }
+    }
+
+    /// Retorna um destino de notificações que escreve no mesmo stdout.
+    pub fn notifier(&self) -> Arc<dyn NotificationSink> {
+        Arc::new(self.writer.clone())
+    }
+
+    /// Define o maior objeto incompleto mantido entre leituras.
+    pub fn with_max_message_bytes(mut self, max_bytes: usize) -> Self {
+        if let Some(reader) = self.reader.as_mut() {
+            reader.max_bytes = max_bytes;
+        }
+        self
+    }
+
+    /// Lê uma mensagem JSON-RPC de stdin.
+    ///
+    /// O formato esperado é newline-delimited JSON:
+    /// ```text
+    /// {"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}\n
+    /// ```
+    ///
+    /// Objetos concatenados na mesma linha são retornados um por chamada e
+    /// objetos quebrados em várias linhas são remontados. Entrada inválida é
+    /// respondida com `PARSE_ERROR` (ou `INVALID_REQUEST`) e a leitura segue.
+    ///
+    /// Espera sem bloquear o runtime e pode ser cancelada (ex: num
+    /// `tokio::select!`) sem perder mensagens.
+    pub async fn read_message(&mut self) -> TetradResult<JsonRpcRequest> {
+        if let Some(reader) = self.reader.take() {
+            self.incoming = Some(spawn_reader(reader, self.writer.clone()));
+        }
+
+        match self.incoming.as_mut() {
+            Some(incoming) => incoming
+                .recv()
+                .await
+                .unwrap_or_else(|| Err(TetradError::config("EOF"))),
+            None => Err(TetradError::config("EOF")),
+        }
+    }
+
+    /// Escreve uma resposta JSON-RPC para stdout.
+    ///
+    /// A resposta é serializada como JSON compacto (sem newlines embutidos)
+    /// seguido de um caractere newline (`\n`).
+    pub fn write_response(&mut self, response: &JsonRpcResponse) -> TetradResult<()> {
+        self.writer.send_response(response)
+    }
+}
+
+/// Lê stdin numa thread dedicada até EOF ou erro, que é repassado por último.
+///
+/// Respostas a entrada inválida são escritas pela própria thread.
+fn spawn_reader(
+    mut reader: MessageReader,
+    writer: SharedStdout,
+) -> mpsc::UnboundedReceiver<TetradResult<JsonRpcRequest>> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    std::thread::spawn(move || loop {
+        match reader.next_frame() {
+            Ok(Frame::Request(request)) => {
+                if sender.send(Ok(request)).is_err() {
+                    break;
+                }
+            }
+            Ok(Frame::Invalid(response)) => {
+                if let Err(e) = writer.send_response(&response) {
+                    tracing::warn!(error = %e, "Failed to answer invalid input");
+                }
+            }
+            Err(e) => {
+                let _ = sender.send(Err(e));
+                break;
+            }
+        }
+    });
+
+    receiver
+}
+
+#[async_trait]
+impl Transport for StdioTransport {
+    async fn read_message(&mut self) -> TetradResult<JsonRpcRequest> {
+        StdioTransport::read_message(self).await
+    }
+
+    fn write_response(&mut self, response: &JsonRpcResponse) -> TetradResult<()> {
+        StdioTransport::write_response(self, response)
+    }
+
+    fn notifier(&self) -> Arc<dyn NotificationSink> {
+        StdioTransport::notifier(self)
+    }
+
+    fn responder(&self) -> Arc<dyn ResponseSink> {
+        Arc::new(self.writer.clone())
+    }
+
+    fn uses_stdout(&self) -> bool {
+        self.owns_stdout
+    }
+
+    fn closed(&self) -> Option<watch::Receiver<bool>> {
+        Some(self.writer.closed.subscribe())
+    }
+
+    fn cancels_on_eof(&self) -> bool {
+        true
+    }
+}
+
+impl NotificationSink for StdioTransport {
+    /// Envia uma notificação (mensagem sem ID que não espera resposta).
+    fn send_notification(&self, notification: &JsonRpcNotification) -> TetradResult<()> {
+        self.writer.send_notification(notification)
     }
 }
 
@@ -151,11 +545,12 @@ impl Default for StdioTransport {
 
 /// Transporte baseado em strings para testes.
 ///
-/// Usa o mesmo formato newline-delimited JSON do StdioTransport.
+/// Usa o mesmo formato newline-delimited JSON e o mesmo leitor do
+/// StdioTransport.
 #[cfg(test)]
 pub struct StringTransport {
-    input: std::io::Cursor<Vec<u8>>,
-    output: Vec<u8>,
+    input: MessageReader,
+    output: Arc<Mutex<Vec<u8>>>,
 }
 
 #[cfg(test)]
@@ -163,51 +558,97 @@ impl StringTransport {
     /// Cria um transporte com input pré-definido (newline-delimited JSON).
     pub fn new(input: &str) -> Self {
         Self {
-            input: std::io::Cursor::new(input.as_bytes().to_vec()),
-            output: Vec::new(),
+            input: MessageReader::new(std::io::Cursor::new(input.as_bytes().to_vec())),
+            output: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
+    /// Define o maior objeto incompleto mantido entre leituras.
+    pub fn with_max_message_bytes(mut self, max_bytes: usize) -> Self {
+        self.input.max_bytes = max_bytes;
+        self
+    }
+
     /// Lê uma mensagem JSON-RPC (newline-delimited).
     pub fn read_message(&mut self) -> TetradResult<JsonRpcRequest> {
-        let mut line = String::new();
+        loop {
+            match self.input.next_frame()? {
+                Frame::Request(request) => return Ok(request),
+                Frame::Invalid(response) => self.write_response(&response)?,
+            }
+        }
+    }
 
-        use std::io::BufRead;
-        let bytes_read = self
-            .input
-            .read_line(&mut line)
-            .map_err(crate::types::errors::TetradError::Io)?;
+    /// Escreve uma resposta (newline-delimited JSON).
+    pub fn write_response(&mut self, response: &JsonRpcResponse) -> TetradResult<()> {
+        self.send_response(response)
+    }
 
-        if bytes_read == 0 {
-            return Err(crate::types::errors::TetradError::config("EOF"));
+    /// Transporte sem entrada que escreve no mesmo output.
+    fn output_only(&self) -> Self {
+        StringTransport {
+            input: MessageReader::new(std::io::Cursor::new(Vec::new())),
+            output: self.output.clone(),
         }
+    }
 
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            return Err(crate::types::errors::TetradError::config("Empty message"));
-        }
+    /// Retorna o output acumulado.
+    pub fn get_output(&self) -> String {
+        String::from_utf8_lossy(&self.output.lock().unwrap()).to_string()
+    }
 
-        serde_json::from_str(trimmed).map_err(crate::types::errors::TetradError::Json)
+    fn write_line(&self, body: &str) {
+        let mut output = self.output.lock().unwrap();
+        output.extend_from_slice(body.as_bytes());
+        output.push(b'\n');
     }
+}
 
-    /// Escreve uma resposta (newline-delimited JSON).
-    pub fn write_response(&mut self, response: &JsonRpcResponse) -> TetradResult<()> {
-        let body =
-            serde_json::to_string(response).map_err(crate::types::errors::TetradError::Json)?;
+#[cfg(test)]
+#[async_trait]
+impl Transport for StringTransport {
+    async fn read_message(&mut self) -> TetradResult<JsonRpcRequest> {
+        StringTransport::read_message(self)
+    }
+
+    fn write_response(&mut self, response: &JsonRpcResponse) -> TetradResult<()> {
+        StringTransport::write_response(self, response)
+    }
 
-        self.output.extend_from_slice(body.as_bytes());
-        self.output.push(b'\n');
+    /// As notificações vão para o mesmo output das respostas.
+    fn notifier(&self) -> Arc<dyn NotificationSink> {
+        Arc::new(self.output_only())
+    }
+
+    fn responder(&self) -> Arc<dyn ResponseSink> {
+        Arc::new(self.output_only())
+    }
+}
+
+#[cfg(test)]
+impl ResponseSink for StringTransport {
+    fn send_response(&self, response: &JsonRpcResponse) -> TetradResult<()> {
+        let body = serde_json::to_string(response).map_err(TetradError::Json)?;
+
+        self.write_line(&body);
         Ok(())
     }
+}
 
-    /// Retorna o output acumulado.
-    pub fn get_output(&self) -> String {
-        String::from_utf8_lossy(&self.output).to_string()
+#[cfg(test)]
+impl NotificationSink for StringTransport {
+    fn send_notification(&self, notification: &JsonRpcNotification) -> TetradResult<()> {
+        let body =
+            serde_json::to_string(notification).map_err(crate::types::errors::TetradError::Json)?;
+
+        self.write_line(&body);
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::protocol::{JsonRpcId, INVALID_REQUEST, PARSE_ERROR};
     use super::*;
     use serde_json::json;
 
@@ -311,7 +752,148 @@ mod tests {
     fn test_invalid_json() {
         let mut transport = StringTransport::new("not valid json\n");
         let result = transport.read_message();
-        assert!(result.is_err());
+        assert!(is_disconnect(&result.unwrap_err()));
+
+        let errors = output_errors(&transport);
+        assert_eq!(errors, vec![PARSE_ERROR]);
+    }
+
+    /// Códigos de erro das respostas escritas pelo transporte.
+    fn output_errors(transport: &StringTransport) -> Vec<i32> {
+        transport
+            .get_output()
+            .lines()
+            .map(|line| {
+                let response: JsonRpcResponse = serde_json::from_str(line).unwrap();
+                response.error.unwrap().code
+            })
+            .collect()
+    }
+
+    fn method_and_id(request: &JsonRpcRequest) -> (&str, Option<JsonRpcId>) {
+        (request.method.as_str(), request.id.clone())
+    }
+
+    #[test]
+    fn test_concatenated_messages_on_one_line() {
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{}}"#,
+            r#"  {"jsonrpc":"2.0","method":"notifications/initialized"}"#,
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":3,"method":"tools/list"}"#,
+            "\n"
+        );
+        let mut transport = StringTransport::new(input);
+
+        let first = transport.read_message().unwrap();
+        assert_eq!(method_and_id(&first), ("initialize", Some(1.into())));
+        let second = transport.read_message().unwrap();
+        assert_eq!(method_and_id(&second), ("notifications/initialized", None));
+        let third = transport.read_message().unwrap();
+        assert_eq!(method_and_id(&third), ("tools/list", Some(2.into())));
+        let fourth = transport.read_message().unwrap();
+        assert_eq!(method_and_id(&fourth), ("tools/list", Some(3.into())));
+
+        assert!(is_disconnect(&transport.read_message().unwrap_err()));
+        assert!(transport.get_output().is_empty());
+    }
+
+    #[test]
+    fn test_message_split_across_lines() {
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"#,
+            "\r\n",
+            r#""method":"tools/call","params":{"name":"tetrad_review_code","#,
+            "\n",
+            r#""arguments":{"code":"fn ma"#,
+            "\n",
+            r#"in() {}","language":"rust"}}}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+            "\n"
+        );
+        let mut transport = StringTransport::new(input);
+
+        let request = transport.read_message().unwrap();
+        assert_eq!(method_and_id(&request), ("tools/call", Some(1.into())));
+        assert_eq!(
+            request.params.unwrap()["arguments"]["code"],
+            json!("fn main() {}")
+        );
+
+        let next = transport.read_message().unwrap();
+        assert_eq!(method_and_id(&next), ("tools/list", Some(2.into())));
+        assert!(transport.get_output().is_empty());
+    }
+
+    #[test]
+    fn test_truncated_fragment_does_not_swallow_next_message() {
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":1,"method":"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#,
+            "\n"
+        );
+        let mut transport = StringTransport::new(input);
+
+        let request = transport.read_message().unwrap();
+        assert_eq!(method_and_id(&request), ("tools/list", Some(2.into())));
+        assert_eq!(output_errors(&transport), vec![PARSE_ERROR]);
+    }
+
+    #[test]
+    fn test_oversized_incomplete_message() {
+        let filler = "x".repeat(64);
+        let input = format!(
+            "{}\n{}\n{}\n{}\n",
+            r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"code":""#,
+            filler,
+            filler,
+            r#"{"jsonrpc":"2.0","id":2,"method":"tools/list"}"#
+        );
+        let mut transport = StringTransport::new(&input).with_max_message_bytes(128);
+
+        let request = transport.read_message().unwrap();
+        assert_eq!(method_and_id(&request), ("tools/list", Some(2.into())));
+        assert_eq!(output_errors(&transport), vec![PARSE_ERROR]);
+
+        let response: Value = serde_json::from_str(transport.get_output().trim()).unwrap();
+        assert!(response["error"]["data"]
+            .as_str()
+            .unwrap()
+            .contains("128 bytes"));
+    }
+
+    #[test]
+    fn test_json_that_is_not_a_request() {
+        let input = concat!(
+            r#"[1, 2]{"id":7,"params":{}}"#,
+            "\n",
+            r#"{"jsonrpc":"2.0","id":8,"method":"tools/list"}"#,
+            "\n"
+        );
+        let mut transport = StringTransport::new(input);
+
+        let request = transport.read_message().unwrap();
+        assert_eq!(method_and_id(&request), ("tools/list", Some(8.into())));
+        assert_eq!(
+            output_errors(&transport),
+            vec![INVALID_REQUEST, INVALID_REQUEST]
+        );
+        let ids: Vec<Value> = transport
+            .get_output()
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["id"].clone())
+            .collect();
+        assert_eq!(ids, vec![Value::Null, json!(7)]);
+    }
+
+    #[test]
+    fn test_incomplete_message_at_eof() {
+        let mut transport = StringTransport::new(r#"{"jsonrpc":"2.0","id":1"#);
+        assert!(is_disconnect(&transport.read_message().unwrap_err()));
+        assert!(transport.get_output().is_empty());
     }
 
     #[test]
@@ -326,6 +908,33 @@ mod tests {
         assert!(request.id.is_none());
     }
 
+    #[test]
+    fn test_is_disconnect() {
+        let broken = TetradError::Io(io::Error::from(io::ErrorKind::BrokenPipe));
+        assert!(is_disconnect(&broken));
+        assert!(is_disconnect(&TetradError::config("EOF")));
+
+        let denied = TetradError::Io(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(!is_disconnect(&denied));
+        assert!(!is_disconnect(&TetradError::config("Empty message")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_broken_pipe_signals_closed() {
+        let (reader, writer) = std::io::pipe().unwrap();
+        drop(reader);
+
+        let mut transport = StdioTransport::from_io(std::io::empty(), writer);
+        let closed = transport.closed().unwrap();
+        assert!(!*closed.borrow());
+
+        let response = JsonRpcResponse::success(Some(1.into()), json!({}));
+        let error = transport.write_response(&response).unwrap_err();
+        assert!(is_disconnect(&error));
+        assert!(*closed.borrow());
+    }
+
     #[test]
     fn test_output_format() {
         let mut transport = StringTransport::new("");
@@ -0,0 +1,197 @@
+//! Aquecimento do servidor MCP.
+//!
+//! A primeira revisão de uma sessão paga de uma vez a verificação de
+//! disponibilidade das CLIs, o primeiro acesso ao ReasoningBank e a partida
+//! a frio de cada executor. Com `general.warm_up`, esse trabalho roda em uma
+//! tarefa de fundo assim que o servidor inicia, sem atrasar a resposta ao
+//! `initialize`.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use serde::Serialize;
+use tokio::task::JoinSet;
+
+use crate::executors::ConfiguredExecutor;
+use crate::reasoning::ReasoningBank;
+use crate::types::requests::EvaluationRequest;
+
+/// Estado do aquecimento, exibido pelo `tetrad_status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum WarmUpStatus {
+    /// `general.warm_up` desligado.
+    Disabled,
+    /// Habilitado, mas ainda não iniciado.
+    Pending,
+    /// Tarefa de fundo em andamento.
+    Running,
+    /// Aquecimento concluído.
+    Completed {
+        /// Duração total, em milissegundos.
+        duration_ms: u64,
+        /// Resultado por executor (`ready`, `unavailable`, `disabled` ou o erro).
+        executors: BTreeMap<String, String>,
+    },
+}
+
+/// Estado compartilhado entre o `ToolHandler` e a tarefa de aquecimento.
+#[derive(Debug)]
+pub(super) struct WarmUp {
+    status: Mutex<WarmUpStatus>,
+}
+
+impl WarmUp {
+    /// Cria o estado inicial conforme a configuração.
+    pub fn new(enabled: bool) -> Self {
+        let status = if enabled {
+            WarmUpStatus::Pending
+        } else {
+            WarmUpStatus::Disabled
+        };
+        Self {
+            status: Mutex::new(status),
+        }
+    }
+
+    /// Retorna o estado atual.
+    pub fn status(&self) -> WarmUpStatus {
+        self.status
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Marca o aquecimento como iniciado.
+    ///
+    /// Retorna `false` se estiver desligado ou já tiver sido iniciado.
+    pub fn begin(&self) -> bool {
+        let mut status = self.status.lock().unwrap_or_else(|e| e.into_inner());
+        if *status != WarmUpStatus::Pending {
+            return false;
+        }
+        *status = WarmUpStatus::Running;
+        true
+    }
+
+    fn complete(&self, started: Instant, executors: BTreeMap<String, String>) {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        tracing::info!(duration_ms, "Warm-up completed");
+        *self.status.lock().unwrap_or_else(|e| e.into_inner()) = WarmUpStatus::Completed {
+            duration_ms,
+            executors,
+        };
+    }
+}
+
+/// Executa o aquecimento: verifica os executores em paralelo (opcionalmente
+/// enviando um prompt trivial) e faz uma consulta vazia ao ReasoningBank.
+pub(super) async fn run(
+    state: Arc<WarmUp>,
+    executors: Arc<Vec<ConfiguredExecutor>>,
+    bank: Arc<tokio::sync::Mutex<Option<ReasoningBank>>>,
+    ping: bool,
+) {
+    let started = Instant::now();
+    tracing::info!(executors = executors.len(), ping, "Warm-up started");
+
+    let mut probes = JoinSet::new();
+    for index in 0..executors.len() {
+        let executors = executors.clone();
+        probes.spawn(async move {
+            let (executor, config) = &executors[index];
+            let outcome = probe(executor.as_ref(), config.enabled, ping).await;
+            (executor.name().to_lowercase(), outcome)
+        });
+    }
+
+    // Primeira consulta ao banco enquanto as CLIs respondem
+    {
+        let bank = bank.lock().await;
+        if let Some(ref b) = *bank {
+            let _ = b.retrieve("", "text");
+        }
+    }
+
+    let mut results = BTreeMap::new();
+    while let Some(joined) = probes.join_next().await {
+        match joined {
+            Ok((name, outcome)) => {
+                results.insert(name, outcome);
+            }
+            Err(e) => tracing::warn!(error = %e, "Warm-up probe failed"),
+        }
+    }
+
+    state.complete(started, results);
+}
+
+async fn probe(executor: &dyn crate::executors::CliExecutor, enabled: bool, ping: bool) -> String {
+    if !enabled {
+        return "disabled".to_string();
+    }
+    if !executor.is_available().await {
+        return "unavailable".to_string();
+    }
+    if !ping {
+        return "ready".to_string();
+    }
+
+    match executor.evaluate(&ping_request()).await {
+        Ok(_) => "ready".to_string(),
+        Err(e) => {
+            tracing::debug!(executor = executor.name(), error = %e, "Warm-up ping failed");
+            format!("error: {}", e)
+        }
+    }
+}
+
+/// Requisição mínima usada para aquecer a sessão da CLI.
+fn ping_request() -> EvaluationRequest {
+    EvaluationRequest::new("ok", "text")
+        .with_context("Aquecimento do Tetrad: não há nada para avaliar, responda PASS.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_begin_only_once() {
+        let warm_up = WarmUp::new(true);
+        assert_eq!(warm_up.status(), WarmUpStatus::Pending);
+        assert!(warm_up.begin());
+        assert!(!warm_up.begin());
+        assert_eq!(warm_up.status(), WarmUpStatus::Running);
+    }
+
+    #[test]
+    fn test_disabled_never_begins() {
+        let warm_up = WarmUp::new(false);
+        assert!(!warm_up.begin());
+        assert_eq!(
+            serde_json::to_value(warm_up.status()).unwrap(),
+            serde_json::json!({ "state": "disabled" })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_completes_without_executors() {
+        let state = Arc::new(WarmUp::new(true));
+        assert!(state.begin());
+
+        run(
+            state.clone(),
+            Arc::new(Vec::new()),
+            Arc::new(tokio::sync::Mutex::new(None)),
+            false,
+        )
+        .await;
+
+        assert!(matches!(
+            state.status(),
+            WarmUpStatus::Completed { ref executors, .. } if executors.is_empty()
+        ));
+    }
+}
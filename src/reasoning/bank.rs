@@ -6,19 +6,25 @@
 use std::collections::HashMap;
 use std::path::Path;
 
-use chrono::{DateTime, Utc};
-use rusqlite::{params, Connection};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::types::config::ReasoningConfig;
-use crate::types::responses::EvaluationResult;
-use crate::TetradResult;
+use crate::types::config::{write_atomic, ReasoningConfig, StorageBackend};
+use crate::types::language::Language;
+use crate::types::requests::{EvaluationRequest, EvaluationType};
+use crate::types::responses::{Decision, EvaluationResult, Finding, Vote};
+use crate::{TetradError, TetradResult};
 
-use super::patterns::PatternMatcher;
+use super::digest::{render_digest, DIGEST_MAX_PATTERNS};
+use super::patterns::{ChunkSignature, PatternMatcher, SIGNATURE_VERSION};
+use super::store::{
+    decision_from_str, vote_from_str, DecisionRecord, PatternStore, SqliteStore, StoredVote,
+    TrajectoryRecord,
+};
 
 /// ReasoningBank - Sistema de aprendizado contínuo.
 pub struct ReasoningBank {
-    pub(crate) conn: Connection,
+    pub(super) store: Box<dyn PatternStore>,
     config: ReasoningConfig,
 }
 
@@ -45,7 +51,7 @@ impl std::fmt::Display for PatternType {
 }
 
 impl PatternType {
-    fn from_str(s: &str) -> Self {
+    pub(super) fn from_str(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "anti_pattern" | "antipattern" => PatternType::AntiPattern,
             "good_pattern" | "goodpattern" => PatternType::GoodPattern,
@@ -54,6 +60,44 @@ impl PatternType {
     }
 }
 
+/// Tipo de artefato ao qual um pattern se aplica.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    /// Código fonte (inclui planos e testes).
+    #[default]
+    Code,
+    /// Documentação (docstrings, README).
+    Documentation,
+}
+
+impl std::fmt::Display for ArtifactKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArtifactKind::Code => write!(f, "code"),
+            ArtifactKind::Documentation => write!(f, "documentation"),
+        }
+    }
+}
+
+impl ArtifactKind {
+    pub(super) fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "documentation" | "docs" => ArtifactKind::Documentation,
+            _ => ArtifactKind::Code,
+        }
+    }
+}
+
+impl From<EvaluationType> for ArtifactKind {
+    fn from(eval_type: EvaluationType) -> Self {
+        match eval_type {
+            EvaluationType::Documentation => ArtifactKind::Documentation,
+            _ => ArtifactKind::Code,
+        }
+    }
+}
+
 /// Um pattern aprendido pelo ReasoningBank.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pattern {
@@ -69,6 +113,52 @@ pub struct Pattern {
     pub confidence: f64,
     pub last_seen: DateTime<Utc>,
     pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub artifact_kind: ArtifactKind,
+    /// Assinatura de forma do código (sem os nomes dos identificadores).
+    #[serde(default)]
+    pub shape_signature: Option<String>,
+    /// Versão do algoritmo que gerou as assinaturas (0 = legado).
+    #[serde(default)]
+    pub signature_version: u32,
+}
+
+impl Pattern {
+    /// Se as assinaturas vieram de uma versão anterior do algoritmo.
+    ///
+    /// O código original não é guardado, então elas não podem ser
+    /// recalculadas: patterns legados só são encontrados por keyword.
+    pub fn is_legacy(&self) -> bool {
+        self.signature_version != SIGNATURE_VERSION
+    }
+}
+
+/// Ordenação de [`ReasoningBank::list_patterns`].
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PatternSort {
+    /// Maior confiança primeiro.
+    #[default]
+    Confidence,
+    /// Mais avaliações (sucessos + falhas) primeiro.
+    Usage,
+}
+
+/// Filtro de [`ReasoningBank::list_patterns`]; campos `None` não filtram.
+#[derive(Debug, Clone, Default)]
+pub struct PatternFilter {
+    /// Tipo do pattern.
+    pub pattern_type: Option<PatternType>,
+    /// Linguagem (qualquer grafia; comparada pelo id canônico).
+    pub language: Option<String>,
+    /// Categoria do issue (comparação sem diferenciar maiúsculas).
+    pub category: Option<String>,
+    /// Confiança mínima (0.0-1.0).
+    pub min_confidence: Option<f64>,
+    /// Ordenação do resultado.
+    pub sort: PatternSort,
+    /// Número máximo de patterns retornados.
+    pub limit: Option<usize>,
 }
 
 /// Tipo de match ao buscar patterns.
@@ -77,10 +167,25 @@ pub struct Pattern {
 pub enum MatchType {
     /// Match exato por assinatura.
     Exact,
+    /// Match pela forma do código (mesma estrutura, outros nomes).
+    Shape,
+    /// Match pela assinatura de um trecho (função ou bloco).
+    Chunk,
     /// Match por keyword.
     Keyword,
 }
 
+impl std::fmt::Display for MatchType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchType::Exact => write!(f, "exact"),
+            MatchType::Shape => write!(f, "shape"),
+            MatchType::Chunk => write!(f, "chunk"),
+            MatchType::Keyword => write!(f, "keyword"),
+        }
+    }
+}
+
 /// Um pattern encontrado em uma busca.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PatternMatch {
@@ -104,9 +209,17 @@ pub struct DistilledKnowledge {
     pub top_good_patterns: Vec<Pattern>,
     pub problematic_categories: HashMap<String, usize>,
     pub language_stats: HashMap<String, LanguageStats>,
+    /// Média de loops nas trajetórias bem-sucedidas ainda guardadas: depois
+    /// de uma poda (ver [`ReasoningBank::prune_trajectories`]) reflete apenas
+    /// as avaliações retidas.
     pub avg_loops_to_consensus: f64,
     pub total_patterns: usize,
+    /// Trajetórias guardadas, não o total histórico de avaliações; as
+    /// contagens dos patterns não são afetadas pela poda.
     pub total_trajectories: usize,
+    /// Desempenho de cada executor nas trajetórias guardadas, por nome.
+    #[serde(default)]
+    pub executor_stats: HashMap<String, ExecutorAccuracy>,
 }
 
 /// Estatísticas por linguagem.
@@ -117,97 +230,199 @@ pub struct LanguageStats {
     pub avg_score: f64,
 }
 
+/// Como um executor votou nas trajetórias guardadas.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorAccuracy {
+    /// Votos registrados.
+    pub votes: usize,
+    pub pass: usize,
+    pub warn: usize,
+    pub fail: usize,
+    pub mean_score: f64,
+    /// Fração dos votos que apontaram na direção da decisão final
+    /// (PASS → pass, WARN → revise, FAIL → block). Trajetórias sem decisão
+    /// não entram na conta.
+    pub agreement_rate: f64,
+}
+
+/// Código cujas avaliações alternaram de decisão.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlakySignature {
+    /// Assinatura (hash) do código normalizado.
+    pub code_hash: String,
+
+    /// Número de avaliações registradas.
+    pub evaluations: usize,
+
+    /// Quantas vezes a decisão mudou entre avaliações consecutivas.
+    pub flips: usize,
+
+    /// Última avaliação registrada.
+    pub last_request_id: String,
+
+    /// Momento da última avaliação.
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Tamanho dos períodos em [`ReasoningBank::stats_by_period`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsBucket {
+    /// Um período por dia (UTC).
+    Day,
+    /// Um período por semana, de segunda a domingo (UTC).
+    Week,
+}
+
+impl std::fmt::Display for StatsBucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatsBucket::Day => write!(f, "day"),
+            StatsBucket::Week => write!(f, "week"),
+        }
+    }
+}
+
+/// Avaliações de um período, para acompanhar tendências.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeriodStats {
+    /// Primeiro dia do período (`YYYY-MM-DD`); semanas começam na segunda-feira.
+    pub period: String,
+
+    /// Trajetórias registradas no período.
+    pub evaluations: usize,
+
+    /// Fração das avaliações com consenso dentro do limite de loops.
+    pub success_rate: f64,
+
+    /// Score final médio.
+    pub avg_score: f64,
+
+    /// Média de loops até o consenso.
+    pub avg_loops: f64,
+
+    /// Fração das avaliações bloqueadas.
+    pub block_rate: f64,
+
+    /// Score médio dos votos individuais; `None` sem votos guardados no período.
+    pub avg_vote_score: Option<f64>,
+}
+
 /// Resultado de uma consolidação.
 #[derive(Debug, Clone)]
 pub struct ConsolidationResult {
     pub patterns_merged: usize,
+    /// GoodPatterns avulsos recolhidos nos contadores por linguagem.
+    pub good_patterns_collapsed: usize,
     pub patterns_pruned: usize,
     pub patterns_reinforced: usize,
+    pub patterns_normalized: usize,
+    pub trajectories_pruned: usize,
 }
 
 impl ReasoningBank {
     /// Cria ou abre o banco de patterns.
     pub fn new(db_path: &Path) -> TetradResult<Self> {
-        // Create parent directory if it doesn't exist
-        if let Some(parent) = db_path.parent() {
-            if !parent.as_os_str().is_empty() && !parent.exists() {
-                std::fs::create_dir_all(parent)?;
-            }
-        }
+        Self::new_with_config(db_path, &ReasoningConfig::default())
+    }
 
-        let conn = Connection::open(db_path)?;
-
-        // Cria as tabelas se não existirem
-        conn.execute_batch(
-            r#"
-            CREATE TABLE IF NOT EXISTS patterns (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                pattern_type TEXT NOT NULL,
-                code_signature TEXT NOT NULL,
-                language TEXT NOT NULL,
-                issue_category TEXT NOT NULL,
-                description TEXT NOT NULL,
-                solution TEXT,
-                success_count INTEGER DEFAULT 0,
-                failure_count INTEGER DEFAULT 0,
-                confidence REAL DEFAULT 0.5,
-                last_seen TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                UNIQUE(code_signature, issue_category)
-            );
-
-            CREATE TABLE IF NOT EXISTS trajectories (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                pattern_id INTEGER REFERENCES patterns(id),
-                request_id TEXT NOT NULL,
-                code_hash TEXT NOT NULL,
-                initial_score INTEGER,
-                final_score INTEGER,
-                loops_to_consensus INTEGER,
-                was_successful BOOLEAN,
-                timestamp TEXT NOT NULL
-            );
-
-            CREATE INDEX IF NOT EXISTS idx_patterns_signature ON patterns(code_signature);
-            CREATE INDEX IF NOT EXISTS idx_patterns_category ON patterns(issue_category);
-            CREATE INDEX IF NOT EXISTS idx_patterns_type ON patterns(pattern_type);
-            CREATE INDEX IF NOT EXISTS idx_trajectories_pattern ON trajectories(pattern_id);
-        "#,
-        )?;
-
-        Ok(Self {
-            conn,
-            config: ReasoningConfig::default(),
-        })
+    /// Cria ou abre o banco com configuração customizada.
+    ///
+    /// `config.backend` escolhe o armazenamento: SQLite em `db_path` (padrão)
+    /// ou Postgres em `config.connection_string`, que exige a feature
+    /// `postgres`.
+    pub fn new_with_config(db_path: &Path, config: &ReasoningConfig) -> TetradResult<Self> {
+        let store: Box<dyn PatternStore> = match config.backend {
+            StorageBackend::Sqlite => Box::new(SqliteStore::open(db_path, config)?),
+            StorageBackend::Postgres => Self::open_postgres(config)?,
+        };
+
+        Ok(Self::with_store(store, config.clone()))
+    }
+
+    #[cfg(feature = "postgres")]
+    fn open_postgres(config: &ReasoningConfig) -> TetradResult<Box<dyn PatternStore>> {
+        let url = config.connection_string.as_deref().ok_or_else(|| {
+            TetradError::config(
+                "reasoning.backend = \"postgres\" requires reasoning.connection_string",
+            )
+        })?;
+        Ok(Box::new(super::store::PostgresStore::connect(url)?))
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    fn open_postgres(_config: &ReasoningConfig) -> TetradResult<Box<dyn PatternStore>> {
+        Err(TetradError::config(
+            "reasoning.backend = \"postgres\" requires tetrad built with the `postgres` feature",
+        ))
     }
 
     /// Cria banco com configuração específica.
     pub fn with_config(db_path: &Path, config: ReasoningConfig) -> TetradResult<Self> {
-        let mut bank = Self::new(db_path)?;
-        bank.config = config;
-        Ok(bank)
+        Self::new_with_config(db_path, &config)
     }
 
-    /// Cria banco com configuração por referência.
-    pub fn new_with_config(db_path: &Path, config: &ReasoningConfig) -> TetradResult<Self> {
-        let mut bank = Self::new(db_path)?;
-        bank.config = config.clone();
-        Ok(bank)
+    /// Cria o banco sobre um armazenamento já aberto.
+    pub fn with_store(store: Box<dyn PatternStore>, config: ReasoningConfig) -> Self {
+        Self { store, config }
+    }
+
+    /// Armazenamento em uso.
+    pub fn store(&self) -> &dyn PatternStore {
+        self.store.as_ref()
+    }
+
+    /// Executa `f` em uma transação de escrita.
+    ///
+    /// Uma falha no meio da operação desfaz tudo, sem deixar contagens
+    /// parciais.
+    pub(super) fn in_write_transaction<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> TetradResult<T>,
+    ) -> TetradResult<T> {
+        self.store.begin_write()?;
+
+        match f(self) {
+            Ok(value) => match self.store.commit() {
+                Ok(()) => Ok(value),
+                Err(e) => {
+                    self.store.rollback();
+                    Err(e)
+                }
+            },
+            Err(e) => {
+                self.store.rollback();
+                Err(e)
+            }
+        }
     }
 
     // ═══════════════════════════════════════════════════════════════════════
     // FASE 1: RETRIEVE - Busca patterns similares
     // ═══════════════════════════════════════════════════════════════════════
 
-    /// Busca patterns conhecidos que podem afetar a avaliação.
+    /// Busca patterns conhecidos que podem afetar a avaliação de código.
     pub fn retrieve(&self, code: &str, language: &str) -> Vec<PatternMatch> {
-        let signature = PatternMatcher::compute_signature(code);
+        self.retrieve_artifact(code, language, ArtifactKind::Code)
+    }
+
+    /// Busca patterns conhecidos para um tipo de artefato.
+    ///
+    /// Patterns de documentação e de código não se misturam.
+    pub fn retrieve_artifact(
+        &self,
+        code: &str,
+        language: &str,
+        kind: ArtifactKind,
+    ) -> Vec<PatternMatch> {
+        let (signature, shape) = artifact_signatures(code, kind);
         let keywords = PatternMatcher::extract_keywords(code);
+        let language = Language::canonical(language);
 
         let mut matches = Vec::new();
 
         // Busca por assinatura exata
-        if let Ok(exact) = self.find_by_signature(&signature) {
+        if let Ok(exact) = self.store.find_by_signature(&signature, kind) {
             matches.extend(exact.into_iter().map(|p| PatternMatch {
                 pattern: p,
                 match_type: MatchType::Exact,
@@ -215,9 +430,32 @@ impl ReasoningBank {
             }));
         }
 
+        // Busca pela forma: renomear variáveis não esconde o que já foi aprendido
+        if let Some(shape) = &shape {
+            if let Ok(shape_matches) = self.store.find_by_shape(shape, kind) {
+                matches.extend(shape_matches.into_iter().map(|p| PatternMatch {
+                    pattern: p,
+                    match_type: MatchType::Shape,
+                    relevance: 0.85,
+                }));
+            }
+        }
+
+        // Busca por trechos: uma edição em outra função não esconde o que já
+        // foi aprendido sobre este trecho
+        for chunk in PatternMatcher::chunk_signatures(code) {
+            if let Ok(chunk_matches) = self.store.find_by_sub_signature(&chunk.signature, kind) {
+                matches.extend(chunk_matches.into_iter().map(|p| PatternMatch {
+                    pattern: p,
+                    match_type: MatchType::Chunk,
+                    relevance: 0.85,
+                }));
+            }
+        }
+
         // Busca por keywords
         for keyword in &keywords {
-            if let Ok(keyword_matches) = self.find_by_keyword(keyword, language) {
+            if let Ok(keyword_matches) = self.store.find_by_keyword(keyword, language, kind) {
                 matches.extend(keyword_matches.into_iter().map(|p| PatternMatch {
                     relevance: 0.7,
                     pattern: p,
@@ -225,7 +463,6 @@ impl ReasoningBank {
                 }));
             }
         }
-
         // Remove duplicatas por ID
         let mut seen_ids = std::collections::HashSet::new();
         matches.retain(|m| seen_ids.insert(m.pattern.id));
@@ -244,94 +481,11 @@ impl ReasoningBank {
         matches
     }
 
-    fn find_by_signature(&self, signature: &str) -> TetradResult<Vec<Pattern>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, pattern_type, code_signature, language, issue_category,
-                    description, solution, success_count, failure_count, confidence,
-                    last_seen, created_at
-             FROM patterns WHERE code_signature = ?",
-        )?;
-
-        let patterns = stmt
-            .query_map(params![signature], |row| {
-                Ok(Pattern {
-                    id: row.get(0)?,
-                    pattern_type: PatternType::from_str(&row.get::<_, String>(1)?),
-                    code_signature: row.get(2)?,
-                    language: row.get(3)?,
-                    issue_category: row.get(4)?,
-                    description: row.get(5)?,
-                    solution: row.get(6)?,
-                    success_count: row.get(7)?,
-                    failure_count: row.get(8)?,
-                    confidence: row.get(9)?,
-                    last_seen: row
-                        .get::<_, String>(10)?
-                        .parse()
-                        .unwrap_or_else(|_| Utc::now()),
-                    created_at: row
-                        .get::<_, String>(11)?
-                        .parse()
-                        .unwrap_or_else(|_| Utc::now()),
-                })
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        Ok(patterns)
-    }
-
-    fn find_by_keyword(&self, keyword: &str, language: &str) -> TetradResult<Vec<Pattern>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, pattern_type, code_signature, language, issue_category,
-                    description, solution, success_count, failure_count, confidence,
-                    last_seen, created_at
-             FROM patterns
-             WHERE (language = ? OR language = 'any')
-               AND (issue_category LIKE ? OR description LIKE ?)
-             ORDER BY confidence DESC
-             LIMIT 10",
-        )?;
-
-        let keyword_pattern = format!("%{}%", keyword);
-
-        let patterns = stmt
-            .query_map(
-                params![language, &keyword_pattern, &keyword_pattern],
-                |row| {
-                    Ok(Pattern {
-                        id: row.get(0)?,
-                        pattern_type: PatternType::from_str(&row.get::<_, String>(1)?),
-                        code_signature: row.get(2)?,
-                        language: row.get(3)?,
-                        issue_category: row.get(4)?,
-                        description: row.get(5)?,
-                        solution: row.get(6)?,
-                        success_count: row.get(7)?,
-                        failure_count: row.get(8)?,
-                        confidence: row.get(9)?,
-                        last_seen: row
-                            .get::<_, String>(10)?
-                            .parse()
-                            .unwrap_or_else(|_| Utc::now()),
-                        created_at: row
-                            .get::<_, String>(11)?
-                            .parse()
-                            .unwrap_or_else(|_| Utc::now()),
-                    })
-                },
-            )?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        Ok(patterns)
-    }
-
     // ═══════════════════════════════════════════════════════════════════════
     // FASE 2: JUDGE - Avalia sucesso/falha da avaliação
     // ═══════════════════════════════════════════════════════════════════════
 
-    /// Julga o resultado de uma avaliação e atualiza patterns.
+    /// Julga o resultado de uma avaliação de código e atualiza patterns.
     ///
     /// # Parâmetros
     /// - `request_id`: ID único da requisição
@@ -349,21 +503,86 @@ impl ReasoningBank {
         loops_to_consensus: u32,
         max_loops: u8,
     ) -> TetradResult<JudgmentResult> {
-        let signature = PatternMatcher::compute_signature(code);
+        let request = EvaluationRequest {
+            request_id: request_id.to_string(),
+            code: code.to_string(),
+            language: language.into(),
+            evaluation_type: EvaluationType::Code,
+            context: None,
+            file_path: None,
+            related_code: None,
+            doc_type: None,
+            related_files: Vec::new(),
+            executors: None,
+        };
+        self.judge_request(&request, result, loops_to_consensus, max_loops)
+    }
+
+    /// Julga o resultado de uma requisição e atualiza patterns.
+    ///
+    /// O tipo de artefato é derivado do tipo de avaliação da requisição.
+    pub fn judge_request(
+        &mut self,
+        request: &EvaluationRequest,
+        result: &EvaluationResult,
+        loops_to_consensus: u32,
+        max_loops: u8,
+    ) -> TetradResult<JudgmentResult> {
+        self.in_write_transaction(|bank| {
+            bank.record_judgment(request, result, loops_to_consensus, max_loops)
+        })
+    }
+
+    fn record_judgment(
+        &mut self,
+        request: &EvaluationRequest,
+        result: &EvaluationResult,
+        loops_to_consensus: u32,
+        max_loops: u8,
+    ) -> TetradResult<JudgmentResult> {
+        let request_id = request.request_id.as_str();
+        let language = request.language.as_str();
+        let kind = ArtifactKind::from(request.evaluation_type);
+        // Em diffs, a assinatura considera apenas as linhas adicionadas
+        let source = request.signature_source();
+        let code_hash = PatternMatcher::compute_signature(&source);
+        let (signature, shape) = artifact_signatures(&source, kind);
+        // Diffs já são um recorte do arquivo; os demais são divididos em trechos
+        let chunks = if request.evaluation_type == EvaluationType::Diff {
+            Vec::new()
+        } else {
+            PatternMatcher::chunk_signatures(&request.code)
+        };
         // Sucesso = consenso alcançado dentro do limite de loops permitido
         let was_successful = result.consensus_achieved && loops_to_consensus <= max_loops as u32;
 
         let initial_score = result.votes.values().map(|v| v.score).min().unwrap_or(0);
 
-        // Registra trajetória
-        self.save_trajectory(
+        // Registra trajetória e os votos que levaram à decisão
+        let trajectory_id = self.store.insert_trajectory(&TrajectoryRecord {
             request_id,
-            &signature,
+            code_hash: &code_hash,
             initial_score,
-            result.score,
+            final_score: result.score,
             loops_to_consensus,
             was_successful,
-        )?;
+            decision: result.decision,
+            timestamp: &Utc::now().to_rfc3339(),
+        })?;
+        let mut votes: Vec<StoredVote> = result
+            .votes
+            .values()
+            .map(|vote| StoredVote {
+                executor: vote.executor.clone(),
+                vote: vote.vote,
+                score: vote.score,
+                reasoning: vote.reasoning.clone(),
+                issues: vote.issues.clone(),
+                suggestions: vote.suggestions.clone(),
+            })
+            .collect();
+        votes.sort_by(|a, b| a.executor.cmp(&b.executor));
+        self.store.insert_votes(trajectory_id, &votes)?;
 
         let mut patterns_updated = 0;
         let mut new_patterns_created = 0;
@@ -372,12 +591,13 @@ impl ReasoningBank {
         for finding in &result.findings {
             let created = self.update_or_create_pattern(
                 &signature,
+                shape.as_deref(),
                 language,
-                &finding.issue,
-                finding.suggestion.as_deref(),
-                &finding.category,
+                kind,
+                finding,
                 was_successful,
             )?;
+            self.link_chunks(&signature, &chunks, finding)?;
 
             if created {
                 new_patterns_created += 1;
@@ -388,8 +608,11 @@ impl ReasoningBank {
 
         // Se não houve findings e foi sucesso, registra como GoodPattern
         if result.findings.is_empty() && was_successful {
-            self.register_good_pattern(&signature, language)?;
-            new_patterns_created += 1;
+            if self.register_good_pattern(&signature, shape.as_deref(), language, kind)? {
+                new_patterns_created += 1;
+            } else {
+                patterns_updated += 1;
+            }
         }
 
         Ok(JudgmentResult {
@@ -399,62 +622,178 @@ impl ReasoningBank {
         })
     }
 
-    fn save_trajectory(
+    /// Busca uma avaliação recente do mesmo código que chegou a outra decisão.
+    ///
+    /// Considera apenas trajetórias dos últimos `window_secs` segundos (0
+    /// desabilita a busca) e retorna o `request_id` e a decisão da mais recente.
+    pub fn find_divergent(
         &self,
-        request_id: &str,
-        code_hash: &str,
-        initial_score: u8,
-        final_score: u8,
-        loops_to_consensus: u32,
-        was_successful: bool,
-    ) -> TetradResult<()> {
-        self.conn.execute(
-            "INSERT INTO trajectories (pattern_id, request_id, code_hash, initial_score,
-                                       final_score, loops_to_consensus, was_successful, timestamp)
-             VALUES (NULL, ?, ?, ?, ?, ?, ?, ?)",
-            params![
-                request_id,
+        code: &str,
+        decision: Decision,
+        window_secs: u64,
+    ) -> TetradResult<Option<(String, Decision)>> {
+        if window_secs == 0 {
+            return Ok(None);
+        }
+
+        let signature = PatternMatcher::compute_signature(code);
+        let since = (Utc::now() - chrono::Duration::seconds(window_secs as i64)).to_rfc3339();
+
+        self.store.latest_divergent(&signature, &since, decision)
+    }
+
+    /// Lista os códigos cujas decisões mais alternaram entre avaliações.
+    ///
+    /// Ordena por número de mudanças de decisão e depois por avaliações.
+    pub fn flakiness_report(&self, limit: usize) -> TetradResult<Vec<FlakySignature>> {
+        let mut signatures: Vec<FlakySignature> = Vec::new();
+        let mut last_decision = String::new();
+
+        for record in self.store.decision_history()? {
+            let DecisionRecord {
                 code_hash,
-                initial_score as i32,
-                final_score as i32,
-                loops_to_consensus as i32,
-                was_successful,
-                Utc::now().to_rfc3339()
-            ],
-        )?;
-        Ok(())
+                request_id,
+                decision,
+                timestamp,
+            } = record;
+            let last_seen = DateTime::parse_from_rfc3339(&timestamp)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now());
+
+            match signatures.last_mut() {
+                Some(current) if current.code_hash == code_hash => {
+                    current.evaluations += 1;
+                    if decision != last_decision {
+                        current.flips += 1;
+                    }
+                    current.last_request_id = request_id;
+                    current.last_seen = last_seen;
+                }
+                _ => signatures.push(FlakySignature {
+                    code_hash,
+                    evaluations: 1,
+                    flips: 0,
+                    last_request_id: request_id,
+                    last_seen,
+                }),
+            }
+            last_decision = decision;
+        }
+
+        signatures.retain(|signature| signature.flips > 0);
+        signatures.sort_by(|a, b| {
+            b.flips
+                .cmp(&a.flips)
+                .then(b.evaluations.cmp(&a.evaluations))
+                .then(b.last_seen.cmp(&a.last_seen))
+        });
+        signatures.truncate(limit);
+
+        Ok(signatures)
+    }
+
+    /// Votos individuais de uma trajetória, por ordem de executor.
+    pub fn votes_for_trajectory(&self, trajectory_id: i64) -> TetradResult<Vec<StoredVote>> {
+        self.store.votes_for_trajectory(trajectory_id)
+    }
+
+    /// Distribuição dos votos, score médio e taxa de concordância com a
+    /// decisão final de cada executor, por nome.
+    pub fn executor_accuracy_stats(&self) -> TetradResult<HashMap<String, ExecutorAccuracy>> {
+        // Soma dos scores, votos com decisão e votos que concordaram
+        let mut totals: HashMap<String, (ExecutorAccuracy, i64, usize, usize)> = HashMap::new();
+
+        for tally in self.store.vote_tallies()? {
+            let Some(vote) = vote_from_str(&tally.vote) else {
+                continue;
+            };
+            let (stats, score_sum, decided, agreed) = totals.entry(tally.executor).or_default();
+            stats.votes += tally.count;
+            match vote {
+                Vote::Pass => stats.pass += tally.count,
+                Vote::Warn => stats.warn += tally.count,
+                Vote::Fail => stats.fail += tally.count,
+            }
+            *score_sum += tally.score_sum;
+            if let Some(decision) = tally.decision.as_deref().and_then(decision_from_str) {
+                *decided += tally.count;
+                if vote.agrees_with(decision) {
+                    *agreed += tally.count;
+                }
+            }
+        }
+
+        Ok(totals
+            .into_iter()
+            .map(|(executor, (mut stats, score_sum, decided, agreed))| {
+                stats.mean_score = score_sum as f64 / stats.votes.max(1) as f64;
+                if decided > 0 {
+                    stats.agreement_rate = agreed as f64 / decided as f64;
+                }
+                (executor, stats)
+            })
+            .collect())
+    }
+
+    /// Avaliações agrupadas por dia ou semana, do período mais antigo ao
+    /// mais recente.
+    ///
+    /// Com `since`, considera só as avaliações a partir desse dia (inclusive);
+    /// o primeiro período pode então ficar incompleto. Os períodos seguem o
+    /// horário UTC em que as trajetórias foram gravadas, e períodos sem
+    /// avaliações não aparecem.
+    pub fn stats_by_period(
+        &self,
+        since: Option<NaiveDate>,
+        bucket: StatsBucket,
+    ) -> TetradResult<Vec<PeriodStats>> {
+        let since = since.map(|date| date.format("%Y-%m-%d").to_string());
+
+        Ok(self
+            .store
+            .period_tallies(since.as_deref(), bucket)?
+            .into_iter()
+            .map(|tally| {
+                let evaluations = tally.evaluations.max(1) as f64;
+                PeriodStats {
+                    period: tally.period,
+                    evaluations: tally.evaluations,
+                    success_rate: tally.successes as f64 / evaluations,
+                    avg_score: tally.score_sum as f64 / evaluations,
+                    avg_loops: tally.loops_sum as f64 / evaluations,
+                    block_rate: tally.blocks as f64 / evaluations,
+                    avg_vote_score: (tally.votes > 0)
+                        .then(|| tally.vote_score_sum as f64 / tally.votes as f64),
+                }
+            })
+            .collect())
     }
 
     fn update_or_create_pattern(
         &mut self,
         signature: &str,
+        shape: Option<&str>,
         language: &str,
-        issue: &str,
-        solution: Option<&str>,
-        category: &str,
+        kind: ArtifactKind,
+        finding: &Finding,
         was_successful: bool,
     ) -> TetradResult<bool> {
-        let now = Utc::now().to_rfc3339();
+        let now = Utc::now();
+        let category = finding.category.as_str();
+        // Executores às vezes mandam parágrafos inteiros; o banco guarda o essencial
+        let description =
+            PatternMatcher::normalize_text(&finding.issue, self.config.max_description_chars);
+        let solution = finding
+            .suggestion
+            .as_deref()
+            .map(|s| PatternMatcher::normalize_text(s, self.config.max_solution_chars));
 
         // Tenta atualizar existente
-        let updated = self.conn.execute(
-            "UPDATE patterns
-             SET success_count = success_count + ?,
-                 failure_count = failure_count + ?,
-                 last_seen = ?,
-                 confidence = CAST(success_count + ? AS REAL) / (success_count + failure_count + 1)
-             WHERE code_signature = ? AND issue_category = ?",
-            params![
-                if was_successful { 1 } else { 0 },
-                if was_successful { 0 } else { 1 },
-                &now,
-                if was_successful { 1 } else { 0 },
-                signature,
-                category
-            ],
-        )?;
+        let updated =
+            self.store
+                .record_outcome(signature, category, was_successful, &now.to_rfc3339())?;
 
-        if updated == 0 {
+        if !updated {
             // Cria novo pattern
             let pattern_type = if was_successful {
                 PatternType::Ambiguous
@@ -462,55 +801,125 @@ impl ReasoningBank {
                 PatternType::AntiPattern
             };
 
-            self.conn.execute(
-                "INSERT INTO patterns (pattern_type, code_signature, language, issue_category,
-                                       description, solution, success_count, failure_count,
-                                       confidence, last_seen, created_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, 0.5, ?, ?)",
-                params![
-                    pattern_type.to_string(),
-                    signature,
-                    language,
-                    category,
-                    issue,
-                    solution,
-                    if was_successful { 1 } else { 0 },
-                    if was_successful { 0 } else { 1 },
-                    &now,
-                    &now
-                ],
-            )?;
+            self.store.insert_pattern(&Pattern {
+                id: 0,
+                pattern_type,
+                code_signature: signature.to_string(),
+                language: language.to_string(),
+                issue_category: category.to_string(),
+                description,
+                solution,
+                success_count: if was_successful { 1 } else { 0 },
+                failure_count: if was_successful { 0 } else { 1 },
+                confidence: 0.5,
+                last_seen: now,
+                created_at: now,
+                artifact_kind: kind,
+                shape_signature: shape.map(str::to_string),
+                signature_version: SIGNATURE_VERSION,
+            })?;
             return Ok(true);
         }
 
+        // Mesma descrição com outra redação: mantém a mais curta
+        if let Some(current) = self.store.find_pattern(signature, category)? {
+            if description.chars().count() < current.description.chars().count()
+                && PatternMatcher::text_key(&description)
+                    == PatternMatcher::text_key(&current.description)
+            {
+                self.store
+                    .update_description(signature, category, &description)?;
+            }
+        }
+
         Ok(false)
     }
 
-    fn register_good_pattern(&mut self, signature: &str, language: &str) -> TetradResult<()> {
-        let now = Utc::now().to_rfc3339();
+    /// Liga o pattern do finding aos trechos onde ele foi encontrado.
+    ///
+    /// Com linhas informadas, só o trecho que contém (ou está mais perto de)
+    /// a primeira delas; sem linhas, todos os trechos.
+    fn link_chunks(
+        &mut self,
+        signature: &str,
+        chunks: &[ChunkSignature],
+        finding: &Finding,
+    ) -> TetradResult<()> {
+        if chunks.is_empty() {
+            return Ok(());
+        }
+        let Some(pattern) = self.store.find_pattern(signature, &finding.category)? else {
+            return Ok(());
+        };
+
+        let first_line = finding.lines.as_ref().and_then(|lines| lines.iter().min());
+        let targets: Vec<&ChunkSignature> = match first_line {
+            Some(&line) => PatternMatcher::nearest_chunk(chunks, line)
+                .into_iter()
+                .collect(),
+            None => chunks.iter().collect(),
+        };
+
+        for chunk in targets {
+            self.store
+                .link_sub_signature(&chunk.signature, pattern.id)?;
+        }
+        Ok(())
+    }
 
-        // Tenta atualizar existente
-        let updated = self.conn.execute(
-            "UPDATE patterns
-             SET success_count = success_count + 1,
-                 pattern_type = 'good_pattern',
-                 last_seen = ?,
-                 confidence = CAST(success_count + 1 AS REAL) / (success_count + failure_count + 1)
-             WHERE code_signature = ? AND issue_category = 'success'",
-            params![&now, signature],
-        )?;
-
-        if updated == 0 {
-            self.conn.execute(
-                "INSERT INTO patterns (pattern_type, code_signature, language, issue_category,
-                                       description, solution, success_count, failure_count,
-                                       confidence, last_seen, created_at)
-                 VALUES ('good_pattern', ?, ?, 'success', 'Código aprovado sem issues', NULL, 1, 0, 1.0, ?, ?)",
-                params![signature, language, &now, &now],
-            )?;
+    /// Registra um sucesso sem findings.
+    ///
+    /// Sucessos do mesmo código ou da mesma forma (mesma estrutura, outros
+    /// nomes e literais) somam no mesmo GoodPattern. Código novo cria um
+    /// GoodPattern avulso; os avulsos de cada linguagem além de
+    /// `max_good_patterns_per_language` são recolhidos nos contadores por
+    /// linguagem. Retorna `true` se um pattern foi criado.
+    fn register_good_pattern(
+        &mut self,
+        signature: &str,
+        shape: Option<&str>,
+        language: &str,
+        kind: ArtifactKind,
+    ) -> TetradResult<bool> {
+        let now = Utc::now();
+        let timestamp = now.to_rfc3339();
+
+        // Tenta atualizar existente: mesmo código, depois mesma forma
+        if self.store.record_good_outcome(signature, &timestamp)? {
+            return Ok(false);
+        }
+        if let Some(shape) = shape {
+            if self
+                .store
+                .record_good_shape(shape, language, kind, &timestamp)?
+            {
+                return Ok(false);
+            }
         }
 
-        Ok(())
+        self.store.insert_pattern(&Pattern {
+            id: 0,
+            pattern_type: PatternType::GoodPattern,
+            code_signature: signature.to_string(),
+            language: language.to_string(),
+            issue_category: "success".to_string(),
+            description: "Código aprovado sem issues".to_string(),
+            solution: None,
+            success_count: 1,
+            failure_count: 0,
+            confidence: 1.0,
+            last_seen: now,
+            created_at: now,
+            artifact_kind: kind,
+            shape_signature: shape.map(str::to_string),
+            signature_version: SIGNATURE_VERSION,
+        })?;
+
+        let keep = self.config.max_good_patterns_per_language;
+        if keep > 0 {
+            self.store.collapse_good_patterns(Some(keep), None)?;
+        }
+        Ok(true)
     }
 
     // ═══════════════════════════════════════════════════════════════════════
@@ -520,14 +929,17 @@ impl ReasoningBank {
     /// Destila conhecimento dos patterns para gerar insights.
     pub fn distill(&self) -> DistilledKnowledge {
         let top_antipatterns = self
-            .get_top_patterns(PatternType::AntiPattern, 10)
+            .store
+            .top_patterns(PatternType::AntiPattern, 10)
             .unwrap_or_default();
         let top_good_patterns = self
-            .get_top_patterns(PatternType::GoodPattern, 10)
+            .store
+            .top_patterns(PatternType::GoodPattern, 10)
             .unwrap_or_default();
-        let problematic_categories = self.get_problematic_categories().unwrap_or_default();
-        let language_stats = self.get_language_stats().unwrap_or_default();
-        let avg_loops = self.get_average_loops_to_consensus().unwrap_or(0.0);
+        let problematic_categories = self.store.problematic_categories().unwrap_or_default();
+        let language_stats = self.store.language_stats().unwrap_or_default();
+        let avg_loops = self.store.average_loops_to_consensus().unwrap_or(0.0);
+        let executor_stats = self.executor_accuracy_stats().unwrap_or_default();
 
         DistilledKnowledge {
             top_antipatterns,
@@ -537,125 +949,22 @@ impl ReasoningBank {
             avg_loops_to_consensus: avg_loops,
             total_patterns: self.count_patterns().unwrap_or(0),
             total_trajectories: self.count_trajectories().unwrap_or(0),
+            executor_stats,
         }
     }
 
-    fn get_top_patterns(
-        &self,
-        pattern_type: PatternType,
-        limit: usize,
-    ) -> TetradResult<Vec<Pattern>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, pattern_type, code_signature, language, issue_category,
-                    description, solution, success_count, failure_count, confidence,
-                    last_seen, created_at
-             FROM patterns
-             WHERE pattern_type = ?
-             ORDER BY (success_count + failure_count) DESC, confidence DESC
-             LIMIT ?",
-        )?;
-
-        let patterns = stmt
-            .query_map(params![pattern_type.to_string(), limit as i32], |row| {
-                Ok(Pattern {
-                    id: row.get(0)?,
-                    pattern_type: PatternType::from_str(&row.get::<_, String>(1)?),
-                    code_signature: row.get(2)?,
-                    language: row.get(3)?,
-                    issue_category: row.get(4)?,
-                    description: row.get(5)?,
-                    solution: row.get(6)?,
-                    success_count: row.get(7)?,
-                    failure_count: row.get(8)?,
-                    confidence: row.get(9)?,
-                    last_seen: row
-                        .get::<_, String>(10)?
-                        .parse()
-                        .unwrap_or_else(|_| Utc::now()),
-                    created_at: row
-                        .get::<_, String>(11)?
-                        .parse()
-                        .unwrap_or_else(|_| Utc::now()),
-                })
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        Ok(patterns)
-    }
-
-    fn get_problematic_categories(&self) -> TetradResult<HashMap<String, usize>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT issue_category, COUNT(*) as count
-             FROM patterns
-             WHERE pattern_type = 'anti_pattern'
-             GROUP BY issue_category
-             ORDER BY count DESC",
-        )?;
-
-        let categories: HashMap<String, usize> = stmt
-            .query_map([], |row| {
-                Ok((row.get::<_, String>(0)?, row.get::<_, usize>(1)?))
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        Ok(categories)
-    }
-
-    fn get_language_stats(&self) -> TetradResult<HashMap<String, LanguageStats>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT language,
-                    COUNT(*) as total,
-                    AVG(CASE WHEN pattern_type = 'good_pattern' THEN 1.0 ELSE 0.0 END) as success_rate,
-                    AVG(confidence * 100) as avg_score
-             FROM patterns
-             GROUP BY language",
-        )?;
-
-        let stats: HashMap<String, LanguageStats> = stmt
-            .query_map([], |row| {
-                Ok((
-                    row.get::<_, String>(0)?,
-                    LanguageStats {
-                        total_evaluations: row.get::<_, usize>(1)?,
-                        success_rate: row.get::<_, f64>(2)?,
-                        avg_score: row.get::<_, f64>(3)?,
-                    },
-                ))
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        Ok(stats)
-    }
-
-    fn get_average_loops_to_consensus(&self) -> TetradResult<f64> {
-        let avg: f64 = self
-            .conn
-            .query_row(
-                "SELECT AVG(loops_to_consensus) FROM trajectories WHERE was_successful = 1",
-                [],
-                |row| row.get(0),
-            )
-            .unwrap_or(0.0);
-
-        Ok(avg)
-    }
-
     fn count_patterns(&self) -> TetradResult<usize> {
-        let count: i64 = self
-            .conn
-            .query_row("SELECT COUNT(*) FROM patterns", [], |row| row.get(0))?;
-        Ok(count as usize)
+        self.store.count_patterns()
     }
 
     /// Conta o número total de trajetórias.
     pub fn count_trajectories(&self) -> TetradResult<usize> {
-        let count: i64 = self
-            .conn
-            .query_row("SELECT COUNT(*) FROM trajectories", [], |row| row.get(0))?;
-        Ok(count as usize)
+        self.store.count_trajectories()
+    }
+
+    /// Conta os patterns com assinaturas legadas (ver [`Pattern::is_legacy`]).
+    pub fn count_legacy_patterns(&self) -> TetradResult<usize> {
+        self.store.count_legacy_patterns()
     }
 
     // ═══════════════════════════════════════════════════════════════════════
@@ -664,99 +973,123 @@ impl ReasoningBank {
 
     /// Consolida conhecimento, prevenindo esquecimento de patterns importantes.
     pub fn consolidate(&mut self) -> TetradResult<ConsolidationResult> {
-        let merged = self.merge_similar_patterns()?;
-        let pruned = self.prune_low_quality_patterns()?;
-        let reinforced = self.reinforce_high_value_patterns()?;
-        self.recalculate_all_confidences()?;
-
-        Ok(ConsolidationResult {
-            patterns_merged: merged,
-            patterns_pruned: pruned,
-            patterns_reinforced: reinforced,
+        self.in_write_transaction(|bank| {
+            let normalized = bank.normalize_stored_texts()?;
+            // Por enquanto, merge apenas duplicatas exatas
+            let merged = bank.store.merge_duplicates()?;
+            let good_patterns_collapsed = bank.collapse_good_patterns()?;
+            // Remove patterns com baixa confiança e pouco uso (< 3 ocorrências)
+            let pruned = bank.store.prune_low_quality()?;
+            // Aumenta ligeiramente a confiança de patterns muito usados
+            let reinforced = bank.store.reinforce_high_value()?;
+            bank.store.recalculate_confidences()?;
+            let trajectories_pruned = bank.apply_trajectory_retention()?;
+
+            Ok(ConsolidationResult {
+                patterns_merged: merged,
+                good_patterns_collapsed,
+                patterns_pruned: pruned,
+                patterns_reinforced: reinforced,
+                patterns_normalized: normalized,
+                trajectories_pruned,
+            })
         })
     }
 
-    fn merge_similar_patterns(&mut self) -> TetradResult<usize> {
-        // Encontra patterns com mesma categoria e assinatura similar
-        let mut merged = 0;
-
-        // Por enquanto, merge apenas duplicatas exatas
-        let duplicates: Vec<(i64, i64)> = self
-            .conn
-            .prepare(
-                "SELECT p1.id, p2.id
-                 FROM patterns p1
-                 JOIN patterns p2 ON p1.code_signature = p2.code_signature
-                                  AND p1.issue_category = p2.issue_category
-                                  AND p1.id < p2.id",
-            )?
-            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
-            .filter_map(|r| r.ok())
-            .collect();
+    /// Se já é hora de consolidar: a cada `consolidation_interval` trajetórias.
+    pub fn consolidation_due(&self) -> bool {
+        let interval = self.config.consolidation_interval;
+        interval > 0
+            && self
+                .count_trajectories()
+                .is_ok_and(|count| count > 0 && count % interval == 0)
+    }
 
-        for (keep_id, remove_id) in duplicates {
-            // Soma os counts do pattern removido ao mantido
-            self.conn.execute(
-                "UPDATE patterns
-                 SET success_count = success_count + (SELECT success_count FROM patterns WHERE id = ?),
-                     failure_count = failure_count + (SELECT failure_count FROM patterns WHERE id = ?)
-                 WHERE id = ?",
-                params![remove_id, remove_id, keep_id],
-            )?;
+    /// Digest dos anti-patterns mais recorrentes (ver [`render_digest`]).
+    pub fn digest(&self) -> TetradResult<String> {
+        let antipatterns = self
+            .store
+            .top_patterns(PatternType::AntiPattern, DIGEST_MAX_PATTERNS)?;
+        Ok(render_digest(&antipatterns))
+    }
 
-            // Remove o duplicado
-            self.conn
-                .execute("DELETE FROM patterns WHERE id = ?", params![remove_id])?;
-            merged += 1;
+    /// Grava o [`digest`](Self::digest) em `path`, criando o diretório se
+    /// preciso. Retorna `false` sem tocar no arquivo quando o conteúdo não
+    /// mudou.
+    pub fn write_digest(&self, path: &Path) -> TetradResult<bool> {
+        let digest = self.digest()?;
+        if std::fs::read_to_string(path).is_ok_and(|current| current == digest) {
+            return Ok(false);
+        }
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
         }
+        write_atomic(path, digest.as_bytes())?;
+        Ok(true)
+    }
 
-        Ok(merged)
+    /// Aplica a retenção de trajetórias da configuração: remove as mais
+    /// antigas que `trajectory_retention_days` e mantém no máximo
+    /// `max_trajectories`. Retorna quantas foram removidas.
+    ///
+    /// Com `vacuum`, o espaço liberado é devolvido ao sistema em seguida
+    /// (fora da transação, o que pode demorar em bancos grandes).
+    pub fn prune_trajectories(&mut self, vacuum: bool) -> TetradResult<usize> {
+        let pruned = self.in_write_transaction(|bank| bank.apply_trajectory_retention())?;
+        if vacuum {
+            self.store.vacuum()?;
+        }
+        Ok(pruned)
     }
 
-    fn prune_low_quality_patterns(&mut self) -> TetradResult<usize> {
-        // Remove patterns com baixa confiança e pouco uso (< 3 ocorrências)
-        // Nota: created_at está em formato RFC3339 (ex: 2024-01-15T10:30:00+00:00),
-        // então usamos strftime para gerar comparação compatível
-        let pruned = self.conn.execute(
-            "DELETE FROM patterns
-             WHERE confidence < 0.3
-               AND (success_count + failure_count) < 3
-               AND created_at < strftime('%Y-%m-%dT%H:%M:%S+00:00', datetime('now', '-30 days'))",
-            [],
-        )?;
+    /// Recolhe os GoodPatterns avulsos mais antigos que
+    /// `good_pattern_retention_days` ou além de `max_good_patterns_per_language`.
+    fn collapse_good_patterns(&mut self) -> TetradResult<usize> {
+        let keep = Some(self.config.max_good_patterns_per_language).filter(|&max| max > 0);
+        let before = Some(self.config.good_pattern_retention_days)
+            .filter(|&days| days > 0)
+            .and_then(|days| chrono::Duration::try_days(days as i64))
+            .and_then(|retention| Utc::now().checked_sub_signed(retention))
+            .map(|cutoff| cutoff.to_rfc3339());
+
+        self.store.collapse_good_patterns(keep, before.as_deref())
+    }
 
-        Ok(pruned)
+    fn apply_trajectory_retention(&mut self) -> TetradResult<usize> {
+        let keep = Some(self.config.max_trajectories).filter(|&max| max > 0);
+        let before = Some(self.config.trajectory_retention_days)
+            .filter(|&days| days > 0)
+            .and_then(|days| chrono::Duration::try_days(days as i64))
+            .and_then(|retention| Utc::now().checked_sub_signed(retention))
+            .map(|cutoff| cutoff.to_rfc3339());
+
+        if keep.is_none() && before.is_none() {
+            return Ok(0);
+        }
+        self.store.prune_trajectories(keep, before.as_deref())
     }
 
-    fn reinforce_high_value_patterns(&mut self) -> TetradResult<usize> {
-        // Aumenta ligeiramente a confiança de patterns muito usados
-        let reinforced = self.conn.execute(
-            "UPDATE patterns
-             SET confidence = MIN(confidence * 1.05, 1.0)
-             WHERE (success_count + failure_count) > 10
-               AND confidence > 0.7",
-            [],
-        )?;
-
-        Ok(reinforced)
-    }
-
-    fn recalculate_all_confidences(&mut self) -> TetradResult<()> {
-        self.conn.execute(
-            "UPDATE patterns
-             SET confidence = CASE
-                 WHEN (success_count + failure_count) = 0 THEN 0.5
-                 ELSE CAST(success_count AS REAL) / (success_count + failure_count)
-             END,
-             pattern_type = CASE
-                 WHEN CAST(success_count AS REAL) / (success_count + failure_count + 0.001) > 0.8 THEN 'good_pattern'
-                 WHEN CAST(failure_count AS REAL) / (success_count + failure_count + 0.001) > 0.8 THEN 'anti_pattern'
-                 ELSE 'ambiguous'
-             END",
-            [],
-        )?;
+    fn normalize_stored_texts(&mut self) -> TetradResult<usize> {
+        // Limpa patterns gravados antes da normalização; depois da primeira
+        // passada nenhuma linha muda
+        let rows = self.store.pattern_texts()?;
+
+        let mut normalized = 0;
+        for (id, description, solution) in rows {
+            let new_description =
+                PatternMatcher::normalize_text(&description, self.config.max_description_chars);
+            let new_solution = solution
+                .as_deref()
+                .map(|s| PatternMatcher::normalize_text(s, self.config.max_solution_chars));
+
+            if new_description != description || new_solution != solution {
+                self.store
+                    .update_texts(id, &new_description, new_solution.as_deref())?;
+                normalized += 1;
+            }
+        }
 
-        Ok(())
+        Ok(normalized)
     }
 
     // ═══════════════════════════════════════════════════════════════════════
@@ -765,61 +1098,131 @@ impl ReasoningBank {
 
     /// Retorna todos os patterns.
     pub fn get_all_patterns(&self) -> TetradResult<Vec<Pattern>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, pattern_type, code_signature, language, issue_category,
-                    description, solution, success_count, failure_count, confidence,
-                    last_seen, created_at
-             FROM patterns
-             ORDER BY (success_count + failure_count) DESC",
-        )?;
-
-        let patterns = stmt
-            .query_map([], |row| {
-                Ok(Pattern {
-                    id: row.get(0)?,
-                    pattern_type: PatternType::from_str(&row.get::<_, String>(1)?),
-                    code_signature: row.get(2)?,
-                    language: row.get(3)?,
-                    issue_category: row.get(4)?,
-                    description: row.get(5)?,
-                    solution: row.get(6)?,
-                    success_count: row.get(7)?,
-                    failure_count: row.get(8)?,
-                    confidence: row.get(9)?,
-                    last_seen: row
-                        .get::<_, String>(10)?
-                        .parse()
-                        .unwrap_or_else(|_| Utc::now()),
-                    created_at: row
-                        .get::<_, String>(11)?
-                        .parse()
-                        .unwrap_or_else(|_| Utc::now()),
-                })
-            })?
-            .filter_map(|r| r.ok())
-            .collect();
-
-        Ok(patterns)
+        self.store.all_patterns()
     }
 
     /// Verifica se um pattern existe.
     pub fn pattern_exists(&self, signature: &str, category: &str) -> TetradResult<bool> {
-        let count: i64 = self.conn.query_row(
-            "SELECT COUNT(*) FROM patterns WHERE code_signature = ? AND issue_category = ?",
-            params![signature, category],
-            |row| row.get(0),
-        )?;
+        Ok(self.store.find_pattern(signature, category)?.is_some())
+    }
 
-        Ok(count > 0)
+    /// Lista os patterns que atendem ao filtro.
+    pub fn list_patterns(&self, filter: &PatternFilter) -> TetradResult<Vec<Pattern>> {
+        match &filter.language {
+            Some(language) => self.store.list_patterns(&PatternFilter {
+                language: Some(Language::canonical(language).to_string()),
+                ..filter.clone()
+            }),
+            None => self.store.list_patterns(filter),
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::types::responses::{Decision, Finding};
-    use std::collections::HashMap;
-    use tempfile::tempdir;
+    /// Busca um pattern pelo id.
+    pub fn get_pattern(&self, id: i64) -> TetradResult<Option<Pattern>> {
+        self.store.get_pattern(id)
+    }
+
+    /// Remove um pattern; trajetórias que apontavam para ele ficam sem pattern.
+    ///
+    /// Retorna `false` se o pattern não existir.
+    pub fn delete_pattern(&mut self, id: i64) -> TetradResult<bool> {
+        self.in_write_transaction(|bank| bank.store.delete_pattern(id))
+    }
+
+    /// Número de linhas de cada tabela do banco.
+    pub fn table_counts(&self) -> TetradResult<Vec<(String, usize)>> {
+        self.store.table_counts()
+    }
+
+    /// Consolida o journal do banco no arquivo principal (fim da sessão).
+    pub fn checkpoint(&mut self) -> TetradResult<()> {
+        self.store.checkpoint()
+    }
+
+    /// Registra manualmente um anti-pattern e retorna seu id.
+    ///
+    /// Sem código associado, a assinatura é derivada da descrição; o pattern
+    /// é encontrado nas buscas por categoria e linguagem. Falha se já houver
+    /// um pattern com a mesma descrição e categoria.
+    pub fn insert_manual_pattern(
+        &mut self,
+        language: &str,
+        category: &str,
+        description: &str,
+        solution: Option<&str>,
+    ) -> TetradResult<i64> {
+        let description =
+            PatternMatcher::normalize_text(description, self.config.max_description_chars);
+        if description.is_empty() {
+            return Err(TetradError::ReasoningBank(
+                "pattern description must not be empty".to_string(),
+            ));
+        }
+        let solution = solution
+            .map(|s| PatternMatcher::normalize_text(s, self.config.max_solution_chars))
+            .filter(|s| !s.is_empty());
+        let signature = format!(
+            "manual:{}",
+            PatternMatcher::hash(&PatternMatcher::text_key(&description))
+        );
+        let language = Language::canonical(language).to_string();
+        let category = category.trim().to_lowercase();
+
+        if self.pattern_exists(&signature, &category)? {
+            return Err(TetradError::ReasoningBank(format!(
+                "a pattern with this description already exists in category '{}'",
+                category
+            )));
+        }
+
+        let now = Utc::now();
+        self.store.insert_pattern(&Pattern {
+            id: 0,
+            pattern_type: PatternType::AntiPattern,
+            code_signature: signature,
+            language,
+            issue_category: category,
+            description,
+            solution,
+            success_count: 0,
+            failure_count: 1,
+            confidence: 0.0,
+            last_seen: now,
+            created_at: now,
+            artifact_kind: ArtifactKind::Code,
+            // Sem código não há forma; a assinatura da descrição não muda
+            // entre versões
+            shape_signature: None,
+            signature_version: SIGNATURE_VERSION,
+        })
+    }
+}
+
+/// Assinaturas exata e de forma de um artefato.
+///
+/// Em documentação, comentários e strings são o próprio conteúdo avaliado:
+/// a assinatura cobre o texto com os espaços normalizados, sem forma.
+fn artifact_signatures(source: &str, kind: ArtifactKind) -> (String, Option<String>) {
+    match kind {
+        ArtifactKind::Code => (
+            PatternMatcher::compute_signature(source),
+            Some(PatternMatcher::compute_shape_signature(source)),
+        ),
+        ArtifactKind::Documentation => (
+            PatternMatcher::hash(&source.split_whitespace().collect::<Vec<_>>().join(" ")),
+            None,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{ResultBuilder, VoteBuilder};
+    use crate::types::responses::{Decision, Finding};
+    use rusqlite::{params, Connection};
+    use std::collections::HashMap;
+    use tempfile::tempdir;
 
     fn create_test_bank() -> (ReasoningBank, tempfile::TempDir) {
         let dir = tempdir().unwrap();
@@ -828,21 +1231,22 @@ mod tests {
         (bank, dir)
     }
 
+    fn sqlite_conn(bank: &ReasoningBank) -> &Connection {
+        bank.store()
+            .as_any()
+            .downcast_ref::<SqliteStore>()
+            .expect("test bank uses SQLite")
+            .connection()
+    }
+
     fn create_test_result(
         decision: Decision,
         score: u8,
         findings: Vec<Finding>,
     ) -> EvaluationResult {
-        EvaluationResult {
-            request_id: "test-123".to_string(),
-            decision,
-            score,
-            consensus_achieved: decision == Decision::Pass,
-            votes: HashMap::new(),
-            findings,
-            feedback: String::new(),
-            timestamp: Utc::now(),
-        }
+        ResultBuilder::new(decision, score)
+            .with_findings(findings)
+            .build()
     }
 
     #[test]
@@ -898,6 +1302,239 @@ mod tests {
         assert!(!matches.is_empty());
     }
 
+    #[test]
+    fn test_judge_stores_canonical_language() {
+        let (mut bank, _dir) = create_test_bank();
+        let finding = Finding::new(
+            crate::types::responses::Severity::Error,
+            "logic",
+            "unwrap may panic",
+        );
+        let result = create_test_result(Decision::Revise, 60, vec![finding]);
+
+        bank.judge("eval-1", "fn a() { x.unwrap() }", "RS", &result, 1, 3)
+            .unwrap();
+        bank.judge("eval-2", "fn b() { y.unwrap() }", "Rust", &result, 1, 3)
+            .unwrap();
+
+        let patterns = bank.get_all_patterns().unwrap();
+        assert_eq!(patterns.len(), 2);
+        assert!(patterns.iter().all(|p| p.language == "rust"));
+
+        // Filtros aceitam qualquer grafia
+        let filter = PatternFilter {
+            language: Some("rs".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(bank.list_patterns(&filter).unwrap().len(), 2);
+
+        let knowledge = bank.distill();
+        assert_eq!(knowledge.language_stats.len(), 1);
+        assert_eq!(knowledge.language_stats["rust"].total_evaluations, 2);
+    }
+
+    #[test]
+    fn test_retrieve_separates_artifact_kinds() {
+        let (mut bank, _dir) = create_test_bank();
+
+        let finding = Finding::new(
+            crate::types::responses::Severity::Warning,
+            "completeness",
+            "completeness: return value is not documented",
+        );
+        let result = create_test_result(Decision::Revise, 60, vec![finding]);
+
+        let request = EvaluationRequest::new("/// Adds two numbers.", "rust")
+            .with_type(EvaluationType::Documentation);
+        bank.judge_request(&request, &result, 1, 3).unwrap();
+
+        let docs =
+            bank.retrieve_artifact("/// Adds two numbers.", "rust", ArtifactKind::Documentation);
+        assert!(!docs.is_empty());
+        assert_eq!(docs[0].pattern.artifact_kind, ArtifactKind::Documentation);
+
+        assert!(bank.retrieve("/// Adds two numbers.", "rust").is_empty());
+    }
+
+    #[test]
+    fn test_retrieve_matches_unchanged_function_after_edit() {
+        let (mut bank, _dir) = create_test_bank();
+        let original = "fn load(path: &str) -> String {\n    std::fs::read_to_string(path).unwrap()\n}\n\nfn greet(name: &str) -> String {\n    format!(\"hi {name}\")\n}\n";
+
+        // Finding na linha 2, dentro de `load`
+        let finding = Finding::new(
+            crate::types::responses::Severity::Error,
+            "logic",
+            "unwrap on file read may panic",
+        )
+        .with_lines(vec![2]);
+        let result = create_test_result(Decision::Revise, 60, vec![finding]);
+        bank.judge("eval-1", original, "rust", &result, 1, 3)
+            .unwrap();
+
+        // Só `greet` mudou: a assinatura completa é outra, mas `load` continua igual
+        let edited = original.replace("format!(\"hi {name}\")", "format!(\"hi {}\", name)");
+        let matches = bank.retrieve(&edited, "rust");
+        let chunk_match = matches
+            .iter()
+            .find(|m| m.match_type == MatchType::Chunk)
+            .expect("pattern learned from the unchanged function");
+        assert_eq!(chunk_match.relevance, 0.85);
+        assert_eq!(chunk_match.pattern.issue_category, "logic");
+
+        // Editar a função onde o finding apareceu desfaz o match por trecho
+        let rewritten = original.replace(".unwrap()", ".unwrap_or_default()");
+        assert!(bank
+            .retrieve(&rewritten, "rust")
+            .iter()
+            .all(|m| m.match_type != MatchType::Chunk));
+    }
+
+    #[test]
+    fn test_retrieve_matches_renamed_variables_by_shape() {
+        let (mut bank, _dir) = create_test_bank();
+        let original = "fn total(items: &[u32]) -> u32 {\n    let mut sum = 0;\n    for item in items {\n        sum += item;\n    }\n    sum\n}";
+
+        let finding = Finding::new(
+            crate::types::responses::Severity::Warning,
+            "logic",
+            "sum may overflow",
+        );
+        let result = create_test_result(Decision::Revise, 60, vec![finding]);
+        bank.judge("eval-1", original, "rust", &result, 1, 3)
+            .unwrap();
+
+        // Comentários e espaços não mudam a assinatura exata
+        let commented = format!("// soma os itens\n{}", original.replace("    ", "\t"));
+        assert_eq!(
+            bank.retrieve(&commented, "rust")[0].match_type,
+            MatchType::Exact
+        );
+
+        let renamed = original
+            .replace("items", "values")
+            .replace("item", "v")
+            .replace("sum", "acc");
+        let matches = bank.retrieve(&renamed, "rust");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].match_type, MatchType::Shape);
+        assert_eq!(matches[0].relevance, 0.85);
+        assert_eq!(matches[0].pattern.issue_category, "logic");
+
+        // Outra estrutura não casa pela forma
+        let restructured = original.replace("sum += item;", "if item > 0 { sum += item; }");
+        assert!(bank.retrieve(&restructured, "rust").is_empty());
+    }
+
+    #[test]
+    fn test_migrate_marks_patterns_as_legacy() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("old.db");
+
+        // Banco criado antes das assinaturas por tokens
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE patterns (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    pattern_type TEXT NOT NULL,
+                    code_signature TEXT NOT NULL,
+                    language TEXT NOT NULL,
+                    issue_category TEXT NOT NULL,
+                    description TEXT NOT NULL,
+                    solution TEXT,
+                    success_count INTEGER DEFAULT 0,
+                    failure_count INTEGER DEFAULT 0,
+                    confidence REAL DEFAULT 0.5,
+                    last_seen TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    artifact_kind TEXT NOT NULL DEFAULT 'code',
+                    UNIQUE(code_signature, issue_category)
+                );
+                INSERT INTO patterns (pattern_type, code_signature, language, issue_category,
+                                      description, last_seen, created_at)
+                VALUES ('anti_pattern', 'old-sig', 'rust', 'logic', 'overflow', '', ''),
+                       ('anti_pattern', 'manual:abc', 'rust', 'security', 'manual', '', '');",
+            )
+            .unwrap();
+
+        let mut bank = ReasoningBank::new(&db_path).unwrap();
+        let patterns = bank.get_all_patterns().unwrap();
+        let legacy: Vec<&str> = patterns
+            .iter()
+            .filter(|p| p.is_legacy())
+            .map(|p| p.code_signature.as_str())
+            .collect();
+        assert_eq!(legacy, ["old-sig"]);
+        assert!(patterns.iter().all(|p| p.shape_signature.is_none()));
+        assert_eq!(bank.count_legacy_patterns().unwrap(), 1);
+
+        // Patterns novos usam a versão atual
+        let finding = Finding::new(
+            crate::types::responses::Severity::Warning,
+            "logic",
+            "overflow",
+        );
+        let result = create_test_result(Decision::Revise, 60, vec![finding]);
+        bank.judge("eval-1", "fn f() {}", "rust", &result, 1, 3)
+            .unwrap();
+        assert_eq!(bank.count_legacy_patterns().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_migrate_adds_artifact_kind() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("old.db");
+
+        // Banco criado antes da coluna artifact_kind
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE patterns (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    pattern_type TEXT NOT NULL,
+                    code_signature TEXT NOT NULL,
+                    language TEXT NOT NULL,
+                    issue_category TEXT NOT NULL,
+                    description TEXT NOT NULL,
+                    solution TEXT,
+                    success_count INTEGER DEFAULT 0,
+                    failure_count INTEGER DEFAULT 0,
+                    confidence REAL DEFAULT 0.5,
+                    last_seen TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    UNIQUE(code_signature, issue_category)
+                );
+                INSERT INTO patterns (pattern_type, code_signature, language, issue_category,
+                                      description, last_seen, created_at)
+                VALUES ('anti_pattern', 'sig', 'rust', 'logic', 'old', '', '');",
+            )
+            .unwrap();
+
+        let bank = ReasoningBank::new(&db_path).unwrap();
+        let patterns = bank.get_all_patterns().unwrap();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].artifact_kind, ArtifactKind::Code);
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    #[test]
+    fn test_postgres_backend_requires_feature() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let config = ReasoningConfig {
+            backend: StorageBackend::Postgres,
+            connection_string: Some("postgres://localhost/tetrad".to_string()),
+            ..Default::default()
+        };
+
+        let err = ReasoningBank::new_with_config(&db_path, &config)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("postgres"));
+        assert!(!db_path.exists());
+    }
+
     #[test]
     fn test_good_pattern_creation() {
         let (mut bank, _dir) = create_test_bank();
@@ -966,4 +1603,1020 @@ mod tests {
         // (patterns_merged é usize, então sempre >= 0)
         let _ = consolidation.patterns_merged;
     }
+
+    #[test]
+    fn test_find_divergent_detects_flip() {
+        let (mut bank, _dir) = create_test_bank();
+        let code = "fn flaky() { todo!() }";
+
+        let pass = create_test_result(Decision::Pass, 90, vec![]);
+        bank.judge("eval-1", code, "rust", &pass, 1, 3).unwrap();
+
+        // Mesma decisão não é instável
+        assert!(bank
+            .find_divergent(code, Decision::Pass, 3600)
+            .unwrap()
+            .is_none());
+
+        let (request_id, decision) = bank
+            .find_divergent(code, Decision::Block, 3600)
+            .unwrap()
+            .unwrap();
+        assert_eq!(request_id, "eval-1");
+        assert_eq!(decision, Decision::Pass);
+
+        // Outro código não interfere
+        assert!(bank
+            .find_divergent("fn other() {}", Decision::Block, 3600)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_find_divergent_respects_window() {
+        let (bank, _dir) = create_test_bank();
+        let code = "fn old() {}";
+        let old = (Utc::now() - chrono::Duration::hours(2)).to_rfc3339();
+
+        sqlite_conn(&bank)
+            .execute(
+                "INSERT INTO trajectories (request_id, code_hash, initial_score, final_score,
+                                           loops_to_consensus, was_successful, timestamp, decision)
+                 VALUES ('eval-old', ?, 50, 50, 1, 0, ?, 'block')",
+                params![PatternMatcher::compute_signature(code), old],
+            )
+            .unwrap();
+
+        assert!(bank
+            .find_divergent(code, Decision::Pass, 3600)
+            .unwrap()
+            .is_none());
+        assert!(bank
+            .find_divergent(code, Decision::Pass, 3 * 3600)
+            .unwrap()
+            .is_some());
+        // Janela 0 desabilita a detecção
+        assert!(bank
+            .find_divergent(code, Decision::Pass, 0)
+            .unwrap()
+            .is_none());
+    }
+
+    /// Grava uma trajetória com data arbitrária; devolve o id.
+    fn seed_trajectory(
+        bank: &ReasoningBank,
+        timestamp: &str,
+        decision: &str,
+        score: u8,
+        loops: u32,
+    ) -> i64 {
+        let conn = sqlite_conn(bank);
+        conn.execute(
+            "INSERT INTO trajectories (request_id, code_hash, initial_score, final_score,
+                                       loops_to_consensus, was_successful, timestamp, decision)
+             VALUES ('eval', 'hash', ?1, ?1, ?2, ?3, ?4, ?5)",
+            params![score, loops, decision == "pass", timestamp, decision],
+        )
+        .unwrap();
+        conn.last_insert_rowid()
+    }
+
+    #[test]
+    fn test_stats_by_period_buckets() {
+        let (bank, _dir) = create_test_bank();
+        // Domingo, último instante da semana de 23/02
+        seed_trajectory(&bank, "2026-03-01T23:59:59.999+00:00", "block", 30, 3);
+        // Segunda e domingo da semana de 02/03
+        let monday = seed_trajectory(&bank, "2026-03-02T00:00:00+00:00", "pass", 90, 1);
+        seed_trajectory(&bank, "2026-03-08T23:30:00.123456789+00:00", "block", 40, 2);
+        seed_trajectory(&bank, "2026-03-09T08:00:00+00:00", "pass", 80, 1);
+        for (executor, score) in [("Codex", 90), ("Gemini", 70)] {
+            sqlite_conn(&bank)
+                .execute(
+                    "INSERT INTO votes (trajectory_id, executor, vote, score)
+                     VALUES (?, ?, 'pass', ?)",
+                    params![monday, executor, score],
+                )
+                .unwrap();
+        }
+
+        let weeks = bank.stats_by_period(None, StatsBucket::Week).unwrap();
+        let periods: Vec<&str> = weeks.iter().map(|p| p.period.as_str()).collect();
+        assert_eq!(periods, ["2026-02-23", "2026-03-02", "2026-03-09"]);
+
+        assert_eq!(weeks[0].evaluations, 1);
+        assert_eq!(weeks[0].block_rate, 1.0);
+        assert_eq!(weeks[0].success_rate, 0.0);
+        assert_eq!(weeks[0].avg_loops, 3.0);
+        assert_eq!(weeks[0].avg_vote_score, None);
+
+        assert_eq!(weeks[1].evaluations, 2);
+        assert_eq!(weeks[1].block_rate, 0.5);
+        assert_eq!(weeks[1].success_rate, 0.5);
+        assert_eq!(weeks[1].avg_score, 65.0);
+        assert_eq!(weeks[1].avg_loops, 1.5);
+        assert_eq!(weeks[1].avg_vote_score, Some(80.0));
+
+        let days = bank.stats_by_period(None, StatsBucket::Day).unwrap();
+        let periods: Vec<&str> = days.iter().map(|p| p.period.as_str()).collect();
+        assert_eq!(
+            periods,
+            ["2026-03-01", "2026-03-02", "2026-03-08", "2026-03-09"]
+        );
+        assert!(days.iter().all(|day| day.evaluations == 1));
+    }
+
+    #[test]
+    fn test_stats_by_period_since_is_inclusive() {
+        let (bank, _dir) = create_test_bank();
+        seed_trajectory(&bank, "2026-03-01T23:59:59+00:00", "block", 30, 3);
+        seed_trajectory(&bank, "2026-03-02T00:00:00+00:00", "pass", 90, 1);
+        seed_trajectory(&bank, "2026-03-04T12:00:00+00:00", "pass", 85, 1);
+
+        let since = NaiveDate::from_ymd_opt(2026, 3, 2);
+        let weeks = bank.stats_by_period(since, StatsBucket::Week).unwrap();
+        assert_eq!(weeks.len(), 1);
+        assert_eq!(weeks[0].period, "2026-03-02");
+        assert_eq!(weeks[0].evaluations, 2);
+        assert_eq!(weeks[0].block_rate, 0.0);
+
+        // Um começo no meio da semana ainda rotula o período pela segunda-feira
+        let since = NaiveDate::from_ymd_opt(2026, 3, 3);
+        let weeks = bank.stats_by_period(since, StatsBucket::Week).unwrap();
+        assert_eq!(weeks[0].period, "2026-03-02");
+        assert_eq!(weeks[0].evaluations, 1);
+
+        let since = NaiveDate::from_ymd_opt(2026, 4, 1);
+        assert!(bank
+            .stats_by_period(since, StatsBucket::Day)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_flakiness_report_orders_by_flips() {
+        let (mut bank, _dir) = create_test_bank();
+        let pass = create_test_result(Decision::Pass, 90, vec![]);
+        let block = create_test_result(Decision::Block, 30, vec![]);
+
+        let sequence = [&pass, &block, &pass, &block];
+        for (i, result) in sequence.iter().enumerate() {
+            bank.judge(&format!("a-{}", i), "fn a() {}", "rust", result, 1, 3)
+                .unwrap();
+        }
+        bank.judge("b-0", "fn b() {}", "rust", &pass, 1, 3).unwrap();
+        bank.judge("b-1", "fn b() {}", "rust", &block, 1, 3)
+            .unwrap();
+        bank.judge("c-0", "fn c() {}", "rust", &pass, 1, 3).unwrap();
+        bank.judge("c-1", "fn c() {}", "rust", &pass, 1, 3).unwrap();
+
+        let report = bank.flakiness_report(10).unwrap();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].flips, 3);
+        assert_eq!(report[0].evaluations, 4);
+        assert_eq!(report[0].last_request_id, "a-3");
+        assert_eq!(report[1].flips, 1);
+
+        assert_eq!(bank.flakiness_report(1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_migrate_adds_trajectory_decision() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("old.db");
+
+        // Banco criado antes da coluna decision
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE trajectories (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    pattern_id INTEGER,
+                    request_id TEXT NOT NULL,
+                    code_hash TEXT NOT NULL,
+                    initial_score INTEGER,
+                    final_score INTEGER,
+                    loops_to_consensus INTEGER,
+                    was_successful BOOLEAN,
+                    timestamp TEXT NOT NULL
+                );",
+            )
+            .unwrap();
+
+        let mut bank = ReasoningBank::new(&db_path).unwrap();
+        let result = create_test_result(Decision::Pass, 90, vec![]);
+        bank.judge("eval-1", "fn x() {}", "rust", &result, 1, 3)
+            .unwrap();
+
+        assert!(bank
+            .find_divergent("fn x() {}", Decision::Revise, 3600)
+            .unwrap()
+            .is_some());
+    }
+
+    fn result_with_votes(decision: Decision, votes: &[(&str, Vote, u8)]) -> EvaluationResult {
+        ResultBuilder::new(decision, 80)
+            .with_votes(votes.iter().map(|&(executor, vote, score)| {
+                VoteBuilder::new(executor, vote, score)
+                    .reasoning(format!("{executor} says {vote}"))
+                    .issue(format!("issue from {executor}"))
+            }))
+            .build()
+    }
+
+    fn trajectory_id(bank: &ReasoningBank, request_id: &str) -> i64 {
+        sqlite_conn(bank)
+            .query_row(
+                "SELECT id FROM trajectories WHERE request_id = ?",
+                params![request_id],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_judge_records_votes() {
+        let (mut bank, _dir) = create_test_bank();
+        let result = result_with_votes(
+            Decision::Revise,
+            &[("Qwen", Vote::Pass, 90), ("Codex", Vote::Warn, 60)],
+        );
+        bank.judge("eval-1", "fn x() {}", "rust", &result, 1, 3)
+            .unwrap();
+
+        let votes = bank
+            .votes_for_trajectory(trajectory_id(&bank, "eval-1"))
+            .unwrap();
+        assert_eq!(votes.len(), 2);
+        assert_eq!(votes[0].executor, "Codex");
+        assert_eq!(votes[0].vote, Vote::Warn);
+        assert_eq!(votes[0].score, 60);
+        assert_eq!(votes[0].reasoning, "Codex says WARN");
+        assert_eq!(votes[0].issues, vec!["issue from Codex".to_string()]);
+        assert!(votes[0].suggestions.is_empty());
+        assert_eq!(votes[1].executor, "Qwen");
+
+        assert!(bank.votes_for_trajectory(999).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_executor_accuracy_stats() {
+        let (mut bank, _dir) = create_test_bank();
+        let evaluations = [
+            (
+                Decision::Pass,
+                [("Codex", Vote::Pass, 90), ("Gemini", Vote::Pass, 80)],
+            ),
+            (
+                Decision::Block,
+                [("Codex", Vote::Fail, 20), ("Gemini", Vote::Pass, 70)],
+            ),
+            (
+                Decision::Revise,
+                [("Codex", Vote::Warn, 60), ("Gemini", Vote::Fail, 30)],
+            ),
+        ];
+        for (i, (decision, votes)) in evaluations.iter().enumerate() {
+            let result = result_with_votes(*decision, votes);
+            bank.judge(
+                &format!("eval-{i}"),
+                &format!("fn f{i}() {{}}"),
+                "rust",
+                &result,
+                1,
+                3,
+            )
+            .unwrap();
+        }
+
+        let stats = bank.executor_accuracy_stats().unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let codex = &stats["Codex"];
+        assert_eq!(
+            (codex.votes, codex.pass, codex.warn, codex.fail),
+            (3, 1, 1, 1)
+        );
+        assert!((codex.mean_score - 170.0 / 3.0).abs() < 1e-9);
+        assert_eq!(codex.agreement_rate, 1.0);
+
+        let gemini = &stats["Gemini"];
+        assert_eq!(
+            (gemini.votes, gemini.pass, gemini.warn, gemini.fail),
+            (3, 2, 0, 1)
+        );
+        assert!((gemini.agreement_rate - 1.0 / 3.0).abs() < 1e-9);
+
+        assert_eq!(bank.distill().executor_stats, stats);
+    }
+
+    #[test]
+    fn test_prune_trajectories_removes_their_votes() {
+        let (mut bank, _dir) = create_bank_with(ReasoningConfig {
+            max_trajectories: 1,
+            ..Default::default()
+        });
+        let result = result_with_votes(Decision::Pass, &[("Codex", Vote::Pass, 90)]);
+        for i in 0..3 {
+            bank.judge(&format!("eval-{i}"), "fn f() {}", "rust", &result, 1, 3)
+                .unwrap();
+        }
+
+        assert_eq!(bank.prune_trajectories(false).unwrap(), 2);
+        let counts: HashMap<String, usize> = bank.table_counts().unwrap().into_iter().collect();
+        assert_eq!(counts["votes"], 1);
+        assert_eq!(bank.executor_accuracy_stats().unwrap()["Codex"].votes, 1);
+    }
+
+    #[test]
+    fn test_migrate_adds_votes_table() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("old.db");
+
+        // Banco criado antes da tabela votes, com uma trajetória já registrada
+        Connection::open(&db_path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE trajectories (
+                    id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    pattern_id INTEGER,
+                    request_id TEXT NOT NULL,
+                    code_hash TEXT NOT NULL,
+                    initial_score INTEGER,
+                    final_score INTEGER,
+                    loops_to_consensus INTEGER,
+                    was_successful BOOLEAN,
+                    timestamp TEXT NOT NULL,
+                    decision TEXT
+                );
+                INSERT INTO trajectories (request_id, code_hash, timestamp, decision)
+                VALUES ('eval-old', 'hash', '2024-01-01T00:00:00+00:00', 'pass');",
+            )
+            .unwrap();
+
+        let mut bank = ReasoningBank::new(&db_path).unwrap();
+        assert!(bank
+            .votes_for_trajectory(trajectory_id(&bank, "eval-old"))
+            .unwrap()
+            .is_empty());
+        assert!(bank.executor_accuracy_stats().unwrap().is_empty());
+
+        let result = result_with_votes(Decision::Pass, &[("Codex", Vote::Pass, 90)]);
+        bank.judge("eval-new", "fn x() {}", "rust", &result, 1, 3)
+            .unwrap();
+        assert_eq!(
+            bank.votes_for_trajectory(trajectory_id(&bank, "eval-new"))
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(bank.count_trajectories().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_opens_in_wal_mode() {
+        let (bank, _dir) = create_test_bank();
+
+        let mode: String = sqlite_conn(&bank)
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mode, "wal");
+
+        // synchronous=NORMAL é o valor 1
+        let synchronous: i64 = sqlite_conn(&bank)
+            .query_row("PRAGMA synchronous", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(synchronous, 1);
+    }
+
+    #[test]
+    fn test_wal_mode_can_be_disabled() {
+        let dir = tempdir().unwrap();
+        let config = ReasoningConfig {
+            wal_mode: false,
+            ..Default::default()
+        };
+        let bank = ReasoningBank::new_with_config(&dir.path().join("test.db"), &config).unwrap();
+
+        let mode: String = sqlite_conn(&bank)
+            .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+            .unwrap();
+        assert_ne!(mode, "wal");
+    }
+
+    #[test]
+    fn test_write_transaction_rolls_back_on_error() {
+        let (mut bank, _dir) = create_test_bank();
+        let request = EvaluationRequest::new("fn partial() {}", "rust");
+        let result = create_test_result(Decision::Pass, 90, vec![]);
+
+        // A trajetória e o GoodPattern são gravados, mas o erro desfaz tudo
+        let outcome: TetradResult<()> = bank.in_write_transaction(|bank| {
+            bank.record_judgment(&request, &result, 1, 3)?;
+            Err(crate::TetradError::config("boom"))
+        });
+
+        assert!(outcome.is_err());
+        assert_eq!(bank.count_trajectories().unwrap(), 0);
+        assert_eq!(bank.count_patterns().unwrap(), 0);
+    }
+
+    fn stored_text_lengths(bank: &ReasoningBank) -> (i64, i64) {
+        sqlite_conn(bank)
+            .query_row(
+                "SELECT MAX(length(description)), COALESCE(MAX(length(solution)), 0) FROM patterns",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_judge_normalizes_long_texts() {
+        let (mut bank, _dir) = create_test_bank();
+        let issue = format!("  Possible   SQL injection.\n\n{}", "Details ".repeat(200));
+        let finding = Finding::new(crate::types::responses::Severity::Error, "security", issue)
+            .with_suggestion("Use prepared statements. ".repeat(100));
+        let result = create_test_result(Decision::Block, 30, vec![finding]);
+
+        bank.judge("long-1", "fn q() {}", "rust", &result, 1, 3)
+            .unwrap();
+
+        let (description_len, solution_len) = stored_text_lengths(&bank);
+        assert!(
+            description_len <= 200,
+            "description has {}",
+            description_len
+        );
+        assert!(solution_len <= 500, "solution has {}", solution_len);
+
+        let pattern = &bank.get_all_patterns().unwrap()[0];
+        assert!(pattern
+            .description
+            .starts_with("Possible SQL injection. Details"));
+        assert!(pattern
+            .description
+            .ends_with(crate::reasoning::TRUNCATED_MARKER));
+    }
+
+    #[test]
+    fn test_equivalent_descriptions_keep_shortest() {
+        let (mut bank, _dir) = create_test_bank();
+        let judge = |bank: &mut ReasoningBank, id: &str, issue: &str| {
+            let finding = Finding::new(crate::types::responses::Severity::Error, "security", issue);
+            let result = create_test_result(Decision::Block, 30, vec![finding]);
+            bank.judge(id, "fn q() {}", "rust", &result, 1, 3).unwrap();
+        };
+
+        judge(
+            &mut bank,
+            "a",
+            "SQL Injection: the query concatenates user input!",
+        );
+        judge(
+            &mut bank,
+            "b",
+            "sql injection the query concatenates user input",
+        );
+        assert_eq!(
+            bank.get_all_patterns().unwrap()[0].description,
+            "sql injection the query concatenates user input"
+        );
+
+        // Descrição diferente (mesmo mais curta) não substitui a existente
+        judge(&mut bank, "c", "XSS");
+        let patterns = bank.get_all_patterns().unwrap();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(
+            patterns[0].description,
+            "sql injection the query concatenates user input"
+        );
+        assert_eq!(patterns[0].failure_count, 3);
+    }
+
+    #[test]
+    fn test_consolidate_normalizes_oversized_rows() {
+        let (mut bank, _dir) = create_test_bank();
+        let now = Utc::now().to_rfc3339();
+
+        // Linha gravada antes da normalização
+        sqlite_conn(&bank)
+            .execute(
+                "INSERT INTO patterns (pattern_type, code_signature, language, issue_category,
+                                       description, solution, success_count, failure_count,
+                                       confidence, last_seen, created_at)
+                 VALUES ('anti_pattern', 'sig', 'rust', 'logic', ?, ?, 0, 5, 0.0, ?, ?)",
+                params![
+                    "Off   by one. ".repeat(400),
+                    "Check bounds. ".repeat(400),
+                    &now,
+                    &now
+                ],
+            )
+            .unwrap();
+
+        let (description_before, solution_before) = stored_text_lengths(&bank);
+        assert!(description_before > 5000);
+        assert!(solution_before > 5000);
+
+        let consolidation = bank.consolidate().unwrap();
+        assert_eq!(consolidation.patterns_normalized, 1);
+
+        let (description_after, solution_after) = stored_text_lengths(&bank);
+        assert!(description_after <= 200);
+        assert!(solution_after <= 500);
+
+        // A limpeza só altera a linha uma vez
+        assert_eq!(bank.consolidate().unwrap().patterns_normalized, 0);
+    }
+
+    #[test]
+    fn test_list_patterns_filters_and_sorts() {
+        let (mut bank, _dir) = create_test_bank();
+        let finding = |category: &str, issue: &str| {
+            Finding::new(crate::types::responses::Severity::Error, category, issue)
+        };
+
+        let result = create_test_result(
+            Decision::Block,
+            30,
+            vec![
+                finding("security", "SQL injection"),
+                finding("logic", "Off by one"),
+            ],
+        );
+        bank.judge("list-1", "SELECT * FROM t", "sql", &result, 1, 3)
+            .unwrap();
+        bank.judge("list-2", "SELECT * FROM t", "sql", &result, 1, 3)
+            .unwrap();
+        bank.judge(
+            "list-3",
+            "fn ok() {}",
+            "rust",
+            &create_test_result(Decision::Pass, 95, vec![]),
+            1,
+            3,
+        )
+        .unwrap();
+
+        let all = bank.list_patterns(&PatternFilter::default()).unwrap();
+        assert_eq!(all.len(), 3);
+        // O GoodPattern tem a maior confiança
+        assert_eq!(all[0].pattern_type, PatternType::GoodPattern);
+
+        let by_usage = bank
+            .list_patterns(&PatternFilter {
+                sort: PatternSort::Usage,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(by_usage[0].failure_count, 2);
+
+        let security = bank
+            .list_patterns(&PatternFilter {
+                pattern_type: Some(PatternType::AntiPattern),
+                language: Some("SQL".to_string()),
+                category: Some("security".to_string()),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(security.len(), 1);
+        assert_eq!(security[0].description, "SQL injection");
+
+        let confident = bank
+            .list_patterns(&PatternFilter {
+                min_confidence: Some(0.9),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(confident.len(), 1);
+        assert_eq!(confident[0].pattern_type, PatternType::GoodPattern);
+
+        let limited = bank
+            .list_patterns(&PatternFilter {
+                limit: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+    }
+
+    #[test]
+    fn test_manual_pattern_roundtrip() {
+        let (mut bank, _dir) = create_test_bank();
+
+        let id = bank
+            .insert_manual_pattern(
+                "Python",
+                "security",
+                "  Never build   SQL with f-strings ",
+                Some("Use parameterized queries"),
+            )
+            .unwrap();
+
+        let pattern = bank.get_pattern(id).unwrap().unwrap();
+        assert_eq!(pattern.pattern_type, PatternType::AntiPattern);
+        assert_eq!(pattern.language, "python");
+        assert_eq!(pattern.description, "Never build SQL with f-strings");
+        assert_eq!(
+            pattern.solution.as_deref(),
+            Some("Use parameterized queries")
+        );
+        assert!(pattern.code_signature.starts_with("manual:"));
+
+        // Encontrado pelas buscas por keyword da categoria
+        let matches = bank.retrieve("db.query(f\"SELECT * FROM t WHERE id = {x}\")", "python");
+        assert!(matches.iter().any(|m| m.pattern.id == id));
+
+        // A mesma descrição na mesma categoria é rejeitada
+        let err = bank
+            .insert_manual_pattern(
+                "python",
+                "security",
+                "never build SQL with f-strings!",
+                None,
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{}", err);
+
+        assert!(bank
+            .insert_manual_pattern("python", "security", "   ", None)
+            .is_err());
+        assert!(bank.get_pattern(id + 100).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_delete_pattern_clears_trajectory_references() {
+        let (mut bank, _dir) = create_test_bank();
+        let id = bank
+            .insert_manual_pattern("rust", "logic", "Unchecked unwrap", None)
+            .unwrap();
+        sqlite_conn(&bank)
+            .execute(
+                "INSERT INTO trajectories (pattern_id, request_id, code_hash, timestamp)
+                 VALUES (?, 'req-1', 'hash', ?)",
+                params![id, Utc::now().to_rfc3339()],
+            )
+            .unwrap();
+
+        assert!(bank.delete_pattern(id).unwrap());
+        assert!(bank.get_pattern(id).unwrap().is_none());
+        assert!(!bank.delete_pattern(id).unwrap());
+
+        let pattern_id: Option<i64> = sqlite_conn(&bank)
+            .query_row(
+                "SELECT pattern_id FROM trajectories WHERE request_id = 'req-1'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(pattern_id, None);
+        assert_eq!(bank.count_trajectories().unwrap(), 1);
+    }
+
+    fn create_bank_with(config: ReasoningConfig) -> (ReasoningBank, tempfile::TempDir) {
+        let dir = tempdir().unwrap();
+        let bank = ReasoningBank::new_with_config(&dir.path().join("test.db"), &config).unwrap();
+        (bank, dir)
+    }
+
+    fn oldest_request_id(bank: &ReasoningBank) -> String {
+        sqlite_conn(bank)
+            .query_row(
+                "SELECT request_id FROM trajectories ORDER BY id LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_prune_trajectories_keeps_most_recent() {
+        let (mut bank, _dir) = create_bank_with(ReasoningConfig {
+            max_trajectories: 100,
+            ..Default::default()
+        });
+        let pass = create_test_result(Decision::Pass, 90, vec![]);
+        for i in 0..300 {
+            let code = format!("fn f{}() {{}}", i % 7);
+            bank.judge(&format!("eval-{}", i), &code, "rust", &pass, 1 + i % 3, 3)
+                .unwrap();
+        }
+        assert_eq!(bank.count_trajectories().unwrap(), 300);
+
+        assert_eq!(bank.prune_trajectories(false).unwrap(), 200);
+        assert_eq!(bank.count_trajectories().unwrap(), 100);
+        assert_eq!(oldest_request_id(&bank), "eval-200");
+
+        // Patterns guardam as próprias contagens e não são afetados
+        let knowledge = bank.distill();
+        assert_eq!(knowledge.total_trajectories, 100);
+        assert!(knowledge.total_patterns > 0);
+        assert!(knowledge.avg_loops_to_consensus > 0.0);
+
+        assert_eq!(bank.prune_trajectories(true).unwrap(), 0);
+        assert_eq!(bank.count_trajectories().unwrap(), 100);
+    }
+
+    #[test]
+    fn test_prune_trajectories_respects_retention() {
+        let (mut bank, _dir) = create_bank_with(ReasoningConfig {
+            max_trajectories: 0,
+            trajectory_retention_days: 30,
+            ..Default::default()
+        });
+        for (request_id, days) in [("eval-old", 45), ("eval-recent", 10)] {
+            sqlite_conn(&bank)
+                .execute(
+                    "INSERT INTO trajectories (request_id, code_hash, timestamp)
+                     VALUES (?, 'hash', ?)",
+                    params![
+                        request_id,
+                        (Utc::now() - chrono::Duration::days(days)).to_rfc3339()
+                    ],
+                )
+                .unwrap();
+        }
+        let pass = create_test_result(Decision::Pass, 90, vec![]);
+        bank.judge("eval-now", "fn now() {}", "rust", &pass, 1, 3)
+            .unwrap();
+
+        assert_eq!(bank.prune_trajectories(false).unwrap(), 1);
+        assert_eq!(bank.count_trajectories().unwrap(), 2);
+        assert_eq!(oldest_request_id(&bank), "eval-recent");
+    }
+
+    #[test]
+    fn test_good_patterns_aggregate_by_shape() {
+        let (mut bank, _dir) = create_test_bank();
+        let pass = create_test_result(Decision::Pass, 95, vec![]);
+
+        let first = bank
+            .judge(
+                "eval-1",
+                "fn total(a: i32) -> i32 { a + 1 }",
+                "rust",
+                &pass,
+                1,
+                3,
+            )
+            .unwrap();
+        assert_eq!(first.new_patterns_created, 1);
+
+        // Mesma estrutura com outros nomes e literais soma no mesmo pattern
+        let second = bank
+            .judge(
+                "eval-2",
+                "fn sum(b: i32) -> i32 { b + 2 }",
+                "rust",
+                &pass,
+                1,
+                3,
+            )
+            .unwrap();
+        assert_eq!(second.new_patterns_created, 0);
+        assert_eq!(second.patterns_updated, 1);
+
+        let patterns = bank.get_all_patterns().unwrap();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].success_count, 2);
+
+        // Outra linguagem não compartilha o pattern
+        bank.judge(
+            "eval-3",
+            "fn sum(b: i32) -> i32 { b + 2 }",
+            "go",
+            &pass,
+            1,
+            3,
+        )
+        .unwrap();
+        assert_eq!(bank.count_patterns().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_good_patterns_stay_bounded() {
+        let (mut bank, _dir) = create_bank_with(ReasoningConfig {
+            max_good_patterns_per_language: 50,
+            ..Default::default()
+        });
+        let pass = create_test_result(Decision::Pass, 95, vec![]);
+
+        // 1.000 aprovações de código com formas diferentes
+        for i in 0..1000 {
+            let code = format!("fn f(x: i32) -> i32 {{ {}x }}", "x + ".repeat(i));
+            bank.judge(&format!("eval-{}", i), &code, "rust", &pass, 1, 3)
+                .unwrap();
+        }
+
+        assert_eq!(bank.count_patterns().unwrap(), 50);
+        assert_eq!(bank.count_trajectories().unwrap(), 1000);
+
+        // As aprovações recolhidas continuam nas estatísticas da linguagem
+        let stats = bank.distill().language_stats;
+        let rust = &stats["rust"];
+        assert_eq!(rust.total_evaluations, 1000);
+        assert_eq!(rust.success_rate, 1.0);
+        assert_eq!(rust.avg_score, 100.0);
+
+        // Os mais recentes são mantidos
+        let counts: HashMap<String, usize> = bank.table_counts().unwrap().into_iter().collect();
+        assert_eq!(counts["good_pattern_summary"], 1);
+        let latest = PatternMatcher::compute_signature(&format!(
+            "fn f(x: i32) -> i32 {{ {}x }}",
+            "x + ".repeat(999)
+        ));
+        assert!(bank.pattern_exists(&latest, "success").unwrap());
+    }
+
+    #[test]
+    fn test_consolidate_collapses_stale_good_patterns() {
+        let (mut bank, _dir) = create_bank_with(ReasoningConfig {
+            good_pattern_retention_days: 30,
+            ..Default::default()
+        });
+        let pass = create_test_result(Decision::Pass, 95, vec![]);
+        let block = create_test_result(
+            Decision::Block,
+            30,
+            vec![Finding::new(
+                crate::types::responses::Severity::Error,
+                "logic",
+                "Unchecked unwrap",
+            )],
+        );
+
+        for i in 0..4 {
+            let code = format!("fn f() {{ {}1 }}", "1 + ".repeat(i));
+            bank.judge(&format!("eval-{}", i), &code, "rust", &pass, 1, 3)
+                .unwrap();
+        }
+        // Um GoodPattern reaproveitado e um anti-pattern nunca são recolhidos
+        bank.judge("eval-again", "fn f() { 1 }", "rust", &pass, 1, 3)
+            .unwrap();
+        bank.judge("eval-bad", "x.unwrap()", "rust", &block, 1, 3)
+            .unwrap();
+        // Patterns recentes não são recolhidos
+        assert_eq!(bank.consolidate().unwrap().good_patterns_collapsed, 0);
+        let before = bank.distill().language_stats["rust"].clone();
+
+        let stale = (Utc::now() - chrono::Duration::days(45)).to_rfc3339();
+        sqlite_conn(&bank)
+            .execute("UPDATE patterns SET last_seen = ?", params![stale])
+            .unwrap();
+
+        let consolidation = bank.consolidate().unwrap();
+        assert_eq!(consolidation.good_patterns_collapsed, 3);
+        assert_eq!(bank.count_patterns().unwrap(), 2);
+
+        let after = bank.distill().language_stats["rust"].clone();
+        assert_eq!(after.total_evaluations, before.total_evaluations);
+        assert!((after.success_rate - before.success_rate).abs() < 1e-9);
+        assert!((after.avg_score - before.avg_score).abs() < 1e-9);
+
+        // Nada mais a recolher
+        assert_eq!(bank.consolidate().unwrap().good_patterns_collapsed, 0);
+    }
+
+    #[test]
+    fn test_prune_trajectories_without_limits_keeps_everything() {
+        let (mut bank, _dir) = create_bank_with(ReasoningConfig {
+            max_trajectories: 0,
+            trajectory_retention_days: 0,
+            ..Default::default()
+        });
+        let pass = create_test_result(Decision::Pass, 90, vec![]);
+        for i in 0..20 {
+            bank.judge(&format!("eval-{}", i), "fn f() {}", "rust", &pass, 1, 3)
+                .unwrap();
+        }
+
+        assert_eq!(bank.prune_trajectories(true).unwrap(), 0);
+        assert_eq!(bank.count_trajectories().unwrap(), 20);
+    }
+
+    #[test]
+    fn test_consolidate_prunes_trajectories() {
+        let (mut bank, _dir) = create_bank_with(ReasoningConfig {
+            max_trajectories: 50,
+            ..Default::default()
+        });
+        let block = create_test_result(
+            Decision::Block,
+            30,
+            vec![Finding::new(
+                crate::types::responses::Severity::Error,
+                "logic",
+                "Unchecked unwrap",
+            )],
+        );
+        for i in 0..250 {
+            bank.judge(
+                &format!("eval-{}", i),
+                "fn f() { x.unwrap() }",
+                "rust",
+                &block,
+                1,
+                3,
+            )
+            .unwrap();
+        }
+
+        let consolidation = bank.consolidate().unwrap();
+        assert_eq!(consolidation.trajectories_pruned, 200);
+        assert_eq!(bank.count_trajectories().unwrap(), 50);
+
+        let counts: HashMap<String, usize> = bank.table_counts().unwrap().into_iter().collect();
+        assert_eq!(counts["trajectories"], 50);
+        assert_eq!(counts["patterns"], 1);
+        assert_eq!(bank.get_all_patterns().unwrap()[0].failure_count, 250);
+    }
+
+    /// Banco com 15 anti-patterns manuais; o de índice `i` tem `i + 1`
+    /// falhas e descrições longas o bastante para estourar o limite.
+    fn seed_antipatterns(bank: &mut ReasoningBank) {
+        for i in 0..15 {
+            let id = bank
+                .insert_manual_pattern(
+                    "rust",
+                    &format!("category_{:02}", i),
+                    &format!("Recurring issue {:02} {}", i, "detail ".repeat(20)),
+                    Some(&format!("Fix {:02} {}", i, "step ".repeat(20))),
+                )
+                .unwrap();
+            sqlite_conn(bank)
+                .execute(
+                    "UPDATE patterns SET pattern_type = 'anti_pattern', success_count = 0, failure_count = ?1 WHERE id = ?2",
+                    rusqlite::params![i + 1, id],
+                )
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_digest_orders_by_recurrence_and_respects_cap() {
+        let (mut bank, _dir) = create_test_bank();
+        seed_antipatterns(&mut bank);
+
+        let digest = bank.digest().unwrap();
+        assert!(digest.chars().count() <= crate::reasoning::DIGEST_MAX_CHARS);
+
+        let categories: Vec<&str> = digest
+            .lines()
+            .filter_map(|line| line.split("**").nth(1))
+            .collect();
+        assert!(!categories.is_empty());
+        // Os mais recorrentes primeiro; os que não cabem no limite ficam de fora
+        let expected: Vec<String> = (0..15)
+            .rev()
+            .map(|i| format!("category_{:02}", i))
+            .collect();
+        assert_eq!(categories, expected[..categories.len()]);
+        assert!(categories.len() < 10);
+
+        // Determinístico para o mesmo estado do banco
+        assert_eq!(bank.digest().unwrap(), digest);
+    }
+
+    #[test]
+    fn test_write_digest_skips_unchanged_file() {
+        let (mut bank, dir) = create_test_bank();
+        seed_antipatterns(&mut bank);
+        let path = dir.path().join("data/digest.md");
+
+        assert!(bank.write_digest(&path).unwrap());
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            bank.digest().unwrap()
+        );
+        assert!(!bank.write_digest(&path).unwrap());
+
+        // Uma mudança no ranking regrava o arquivo
+        sqlite_conn(&bank)
+            .execute(
+                "UPDATE patterns SET failure_count = 100 WHERE issue_category = 'category_00'",
+                [],
+            )
+            .unwrap();
+        assert!(bank.write_digest(&path).unwrap());
+        let digest = std::fs::read_to_string(&path).unwrap();
+        assert!(digest.contains("1. **category_00**"));
+    }
+
+    #[test]
+    fn test_consolidation_due() {
+        let (mut bank, _dir) = create_bank_with(ReasoningConfig {
+            consolidation_interval: 3,
+            ..Default::default()
+        });
+        assert!(!bank.consolidation_due());
+
+        let pass = create_test_result(Decision::Pass, 95, vec![]);
+        for i in 0..3 {
+            assert!(!bank.consolidation_due());
+            bank.judge(&format!("eval-{}", i), "fn f() {}", "rust", &pass, 1, 3)
+                .unwrap();
+        }
+        assert!(bank.consolidation_due());
+    }
 }
@@ -0,0 +1,155 @@
+//! Digest dos anti-patterns recorrentes do projeto.
+//!
+//! Um resumo curto (no máximo [`DIGEST_MAX_CHARS`] caracteres) dos
+//! anti-patterns mais frequentes, com a solução de cada um em uma linha.
+//! É regenerado a cada consolidação, gravado em `.tetrad/digest.md` para
+//! ser referenciado no CLAUDE.md e exposto como o recurso MCP
+//! `tetrad://digest`.
+
+use super::bank::Pattern;
+use super::patterns::PatternMatcher;
+
+/// Nome do arquivo do digest dentro do diretório de dados.
+pub const DIGEST_FILE_NAME: &str = "digest.md";
+
+/// Tamanho máximo do digest, em caracteres.
+pub const DIGEST_MAX_CHARS: usize = 1500;
+
+/// Quantos anti-patterns o digest considera.
+pub const DIGEST_MAX_PATTERNS: usize = 10;
+
+/// Limite de caracteres da descrição e da solução de cada item.
+const ITEM_TEXT_CHARS: usize = 120;
+
+const HEADER: &str = "# Recurring anti-patterns\n\n\
+Issues Tetrad's evaluators flagged most often in this project. Avoid them in new code.\n\n";
+
+/// Renderiza o digest dos anti-patterns.
+///
+/// A saída depende apenas dos patterns: eles são ordenados por número de
+/// avaliações, confiança e id, e nada variável (datas, contagens) entra no
+/// texto, então o arquivo só muda quando o ranking muda. Itens que
+/// estourariam [`DIGEST_MAX_CHARS`] são omitidos.
+pub fn render_digest(antipatterns: &[Pattern]) -> String {
+    let mut patterns: Vec<&Pattern> = antipatterns.iter().collect();
+    patterns.sort_by(|a, b| {
+        let uses = |p: &Pattern| p.success_count + p.failure_count;
+        uses(b)
+            .cmp(&uses(a))
+            .then(b.confidence.total_cmp(&a.confidence))
+            .then(a.id.cmp(&b.id))
+    });
+
+    let mut output = String::from(HEADER);
+    if patterns.is_empty() {
+        output.push_str("No recurring anti-patterns recorded yet.\n");
+        return output;
+    }
+
+    let mut used = output.chars().count();
+    for (i, pattern) in patterns.iter().take(DIGEST_MAX_PATTERNS).enumerate() {
+        let mut item = format!(
+            "{}. **{}** ({}): {}",
+            i + 1,
+            pattern.issue_category,
+            pattern.language,
+            PatternMatcher::normalize_text(&pattern.description, ITEM_TEXT_CHARS)
+        );
+        if let Some(solution) = pattern.solution.as_deref().filter(|s| !s.trim().is_empty()) {
+            item.push_str(" Fix: ");
+            item.push_str(&PatternMatcher::normalize_text(solution, ITEM_TEXT_CHARS));
+        }
+        item.push('\n');
+
+        let len = item.chars().count();
+        if used + len > DIGEST_MAX_CHARS {
+            break;
+        }
+        used += len;
+        output.push_str(&item);
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reasoning::{ArtifactKind, PatternType};
+    use chrono::Utc;
+
+    fn antipattern(id: i64, failures: i32, confidence: f64) -> Pattern {
+        Pattern {
+            id,
+            pattern_type: PatternType::AntiPattern,
+            code_signature: format!("sig-{}", id),
+            language: "rust".to_string(),
+            issue_category: format!("category_{}", id),
+            description: format!("Issue number {}", id),
+            solution: Some(format!("Fix number {}", id)),
+            success_count: 0,
+            failure_count: failures,
+            confidence,
+            last_seen: Utc::now(),
+            created_at: Utc::now(),
+            artifact_kind: ArtifactKind::Code,
+            shape_signature: None,
+            signature_version: 0,
+        }
+    }
+
+    #[test]
+    fn test_render_digest_empty() {
+        let digest = render_digest(&[]);
+        assert!(digest.starts_with("# Recurring anti-patterns"));
+        assert!(digest.contains("No recurring anti-patterns recorded yet."));
+    }
+
+    #[test]
+    fn test_render_digest_orders_and_ignores_input_order() {
+        let patterns = vec![
+            antipattern(1, 2, 0.5),
+            antipattern(2, 9, 0.5),
+            antipattern(3, 2, 0.9),
+            antipattern(4, 2, 0.5),
+        ];
+        let digest = render_digest(&patterns);
+
+        let lines: Vec<&str> = digest.lines().filter(|l| l.contains("**")).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "1. **category_2** (rust): Issue number 2 Fix: Fix number 2",
+                "2. **category_3** (rust): Issue number 3 Fix: Fix number 3",
+                "3. **category_1** (rust): Issue number 1 Fix: Fix number 1",
+                "4. **category_4** (rust): Issue number 4 Fix: Fix number 4",
+            ]
+        );
+
+        let mut reversed = patterns.clone();
+        reversed.reverse();
+        assert_eq!(render_digest(&reversed), digest);
+    }
+
+    #[test]
+    fn test_render_digest_respects_cap() {
+        let patterns: Vec<Pattern> = (1..=10)
+            .map(|id| {
+                let mut pattern = antipattern(id, 20 - id as i32, 0.8);
+                pattern.description = "word ".repeat(60);
+                pattern.solution = Some("fix ".repeat(60));
+                pattern
+            })
+            .collect();
+
+        let digest = render_digest(&patterns);
+        assert!(digest.chars().count() <= DIGEST_MAX_CHARS);
+        // Itens longos são cortados e os que não cabem ficam de fora
+        assert!(digest.contains("1. **category_1**"));
+        assert!(!digest.contains("10. **category_10**"));
+        assert!(digest
+            .lines()
+            .all(|l| !l.starts_with(char::is_numeric)
+                || l.contains(crate::reasoning::TRUNCATED_MARKER)));
+    }
+}
@@ -1,15 +1,91 @@
 //! Export/Import de patterns do ReasoningBank.
 //!
 //! Permite compartilhar conhecimento entre diferentes instalações do Tetrad.
+//! A exportação pode ser filtrada e gravada em JSON, JSON compactado com gzip
+//! ou Markdown; a importação reconhece o gzip pelo conteúdo, não pela extensão.
+//! Exportações JSON podem ser assinadas e a importação verifica a assinatura
+//! (veja [`super::signing`]).
 
+use std::borrow::Cow;
+use std::io::Read;
 use std::path::Path;
 
 use chrono::{DateTime, Utc};
+use ed25519_dalek::SigningKey;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
 
+use crate::types::build::BuildInfo;
+use crate::types::errors::TetradError;
+use crate::types::language::Language;
 use crate::TetradResult;
 
-use super::bank::{DistilledKnowledge, Pattern, ReasoningBank};
+use super::bank::{
+    DistilledKnowledge, Pattern, PatternFilter, PatternSort, PatternType, ReasoningBank,
+};
+use super::signing::{sign_export, PackSignature, SignaturePolicy, SignatureStatus};
+
+/// Assinatura (magic number) de arquivos gzip.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Tamanho máximo do JSON descompactado de um pacote `.json.gz`.
+pub const MAX_DECOMPRESSED_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Formato do arquivo de exportação.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// JSON formatado.
+    #[default]
+    Json,
+    /// JSON compactado com gzip.
+    JsonGz,
+    /// Relatório Markdown para leitura; não pode ser importado.
+    Markdown,
+}
+
+impl ExportFormat {
+    /// Extensão usual de arquivos neste formato.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::JsonGz => "json.gz",
+            ExportFormat::Markdown => "md",
+        }
+    }
+}
+
+/// Parâmetros de [`ReasoningBank::export`]; filtros `None` não filtram.
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// Formato do arquivo.
+    pub format: ExportFormat,
+    /// Linguagem (qualquer grafia; comparada pelo id canônico).
+    pub language: Option<String>,
+    /// Tipo do pattern.
+    pub pattern_type: Option<PatternType>,
+    /// Confiança mínima (0.0-1.0).
+    pub min_confidence: Option<f64>,
+    /// Chave que assina a exportação (só JSON e JSON compactado).
+    pub signing_key: Option<SigningKey>,
+}
+
+impl ExportOptions {
+    /// Se o pattern passa pelos filtros.
+    fn matches(&self, pattern: &Pattern) -> bool {
+        self.language
+            .as_ref()
+            .is_none_or(|l| Language::canonical(l) == pattern.language)
+            && self
+                .pattern_type
+                .as_ref()
+                .is_none_or(|t| *t == pattern.pattern_type)
+            && self
+                .min_confidence
+                .is_none_or(|min| pattern.confidence >= min)
+    }
+}
 
 /// Estrutura de exportação do ReasoningBank.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,10 +94,16 @@ pub struct ReasoningBankExport {
     pub version: String,
     /// Data/hora da exportação.
     pub exported_at: DateTime<Utc>,
+    /// Build do Tetrad que gerou a exportação (ausente em arquivos antigos).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build: Option<BuildInfo>,
     /// Conhecimento destilado.
     pub knowledge: DistilledKnowledge,
     /// Patterns exportados.
     pub patterns: Vec<Pattern>,
+    /// Assinatura do restante do arquivo (ausente em exportações não assinadas).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<PackSignature>,
 }
 
 /// Resultado de uma importação.
@@ -33,62 +115,139 @@ pub struct ImportResult {
     pub skipped: usize,
     /// Patterns mesclados (atualizados).
     pub merged: usize,
+    /// Resultado da verificação da assinatura do pacote.
+    pub signature: SignatureStatus,
 }
 
 impl ReasoningBank {
-    /// Exporta ReasoningBank para arquivo JSON.
-    pub fn export(&self, path: &Path) -> TetradResult<()> {
-        let knowledge = self.distill();
-        let patterns = self.get_all_patterns()?;
+    /// Exporta ReasoningBank para arquivo, retornando o número de patterns.
+    ///
+    /// Os filtros valem também para as listas de patterns do conhecimento
+    /// destilado, para que nada fora do filtro seja compartilhado.
+    ///
+    /// Com [`ExportOptions::signing_key`], a assinatura vai no campo
+    /// `signature` do JSON; exportações Markdown não podem ser assinadas.
+    pub fn export(&self, path: &Path, options: &ExportOptions) -> TetradResult<usize> {
+        if options.signing_key.is_some() && options.format == ExportFormat::Markdown {
+            return Err(TetradError::ReasoningBank(
+                "Markdown exports cannot be signed; use json or json-gz".to_string(),
+            ));
+        }
+
+        let mut knowledge = self.distill();
+        knowledge.top_antipatterns.retain(|p| options.matches(p));
+        knowledge.top_good_patterns.retain(|p| options.matches(p));
+        if let Some(language) = &options.language {
+            let language = Language::canonical(language);
+            knowledge.language_stats.retain(|l, _| l == language);
+        }
+
+        let patterns = self.list_patterns(&PatternFilter {
+            pattern_type: options.pattern_type.clone(),
+            language: options.language.clone(),
+            min_confidence: options.min_confidence,
+            sort: PatternSort::Usage,
+            ..Default::default()
+        })?;
 
         let export = ReasoningBankExport {
             version: "2.0".to_string(),
             exported_at: Utc::now(),
+            build: Some(BuildInfo::current()),
             knowledge,
             patterns,
+            signature: None,
         };
 
-        let json = serde_json::to_string_pretty(&export)?;
-        std::fs::write(path, json)?;
+        let mut json = serde_json::to_value(&export)?;
+        if let Some(key) = &options.signing_key {
+            sign_export(&mut json, key)?;
+        }
+
+        let bytes = match options.format {
+            ExportFormat::Json => serde_json::to_vec_pretty(&json)?,
+            ExportFormat::JsonGz => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                serde_json::to_writer(&mut encoder, &json)?;
+                encoder.finish()?
+            }
+            ExportFormat::Markdown => format_export(&export).into_bytes(),
+        };
+        std::fs::write(path, bytes)?;
 
         tracing::info!(
             path = %path.display(),
+            format = ?options.format,
             patterns = export.patterns.len(),
+            signed = options.signing_key.is_some(),
             "ReasoningBank exported"
         );
 
-        Ok(())
+        Ok(export.patterns.len())
     }
 
-    /// Importa patterns de arquivo JSON.
+    /// Importa patterns de arquivo JSON (opcionalmente compactado com gzip).
     pub fn import(&mut self, path: &Path) -> TetradResult<ImportResult> {
-        let json = std::fs::read_to_string(path)?;
-        let export: ReasoningBankExport = serde_json::from_str(&json)?;
-
-        let mut imported = 0;
-        let mut skipped = 0;
-        let mut merged = 0;
-
-        for pattern in export.patterns {
-            if self.pattern_exists(&pattern.code_signature, &pattern.issue_category)? {
-                // Pattern já existe - tenta mesclar
-                if self.merge_imported_pattern(&pattern)? {
-                    merged += 1;
+        let json = std::fs::read(path)?;
+        self.import_bytes(&json, &path.display().to_string())
+    }
+
+    /// Importa patterns de um pacote já carregado (arquivo ou download).
+    ///
+    /// Pacotes gzip são descompactados antes da leitura. `origin` identifica
+    /// o pacote nos logs. A assinatura não é exigida; veja
+    /// [`ReasoningBank::import_bytes_with_policy`].
+    pub fn import_bytes(&mut self, pack: &[u8], origin: &str) -> TetradResult<ImportResult> {
+        self.import_bytes_with_policy(pack, origin, &SignaturePolicy::default())
+    }
+
+    /// Importa um pacote verificando a assinatura segundo `policy`.
+    ///
+    /// A verificação acontece antes de qualquer escrita: um pacote recusado
+    /// pela política não deixa nada no banco.
+    pub fn import_bytes_with_policy(
+        &mut self,
+        pack: &[u8],
+        origin: &str,
+        policy: &SignaturePolicy,
+    ) -> TetradResult<ImportResult> {
+        let json = decompress(pack, origin)?;
+        let signature = policy.check(&json, origin)?;
+        let export: ReasoningBankExport = serde_json::from_slice(&json)?;
+
+        // Tudo ou nada: um erro no meio não deixa a importação pela metade
+        let (imported, skipped, merged) = self.in_write_transaction(|bank| {
+            let mut imported = 0;
+            let mut skipped = 0;
+            let mut merged = 0;
+
+            for mut pattern in export.patterns {
+                // Pacotes antigos ou de outras ferramentas usam grafias livres
+                pattern.language = Language::canonical(&pattern.language).to_string();
+
+                if bank.pattern_exists(&pattern.code_signature, &pattern.issue_category)? {
+                    // Pattern já existe - tenta mesclar
+                    if bank.merge_imported_pattern(&pattern)? {
+                        merged += 1;
+                    } else {
+                        skipped += 1;
+                    }
                 } else {
-                    skipped += 1;
+                    // Pattern novo - importa
+                    bank.insert_pattern(&pattern)?;
+                    imported += 1;
                 }
-            } else {
-                // Pattern novo - importa
-                self.insert_pattern(&pattern)?;
-                imported += 1;
             }
-        }
+
+            Ok((imported, skipped, merged))
+        })?;
 
         tracing::info!(
-            path = %path.display(),
+            source = origin,
             imported,
             skipped,
             merged,
+            signature = %signature,
             "ReasoningBank imported"
         );
 
@@ -96,76 +255,33 @@ impl ReasoningBank {
             imported,
             skipped,
             merged,
+            signature,
         })
     }
 
     /// Insere um pattern no banco.
     fn insert_pattern(&mut self, pattern: &Pattern) -> TetradResult<()> {
-        self.conn.execute(
-            "INSERT INTO patterns (pattern_type, code_signature, language, issue_category,
-                                   description, solution, success_count, failure_count,
-                                   confidence, last_seen, created_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            rusqlite::params![
-                pattern.pattern_type.to_string(),
-                pattern.code_signature,
-                pattern.language,
-                pattern.issue_category,
-                pattern.description,
-                pattern.solution,
-                pattern.success_count,
-                pattern.failure_count,
-                pattern.confidence,
-                pattern.last_seen.to_rfc3339(),
-                pattern.created_at.to_rfc3339()
-            ],
-        )?;
-
+        self.store.insert_pattern(pattern)?;
         Ok(())
     }
 
     /// Mescla um pattern importado com um existente.
     fn merge_imported_pattern(&mut self, pattern: &Pattern) -> TetradResult<bool> {
         // Só mescla se o pattern importado for mais recente ou tiver mais dados
-        let existing: Option<(i32, i32, String)> = self
-            .conn
-            .query_row(
-                "SELECT success_count, failure_count, last_seen
-                 FROM patterns
-                 WHERE code_signature = ? AND issue_category = ?",
-                rusqlite::params![pattern.code_signature, pattern.issue_category],
-                |row: &rusqlite::Row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
-            )
-            .ok();
+        let existing = self
+            .store
+            .find_pattern(&pattern.code_signature, &pattern.issue_category)?;
 
-        if let Some((existing_success, existing_failure, existing_last_seen)) = existing {
-            let existing_total = existing_success + existing_failure;
+        if let Some(existing) = existing {
+            let existing_total = existing.success_count + existing.failure_count;
             let imported_total = pattern.success_count + pattern.failure_count;
 
             // Mescla se o importado tiver mais dados ou for mais recente
-            let should_merge = imported_total > existing_total
-                || pattern.last_seen.to_rfc3339() > existing_last_seen;
+            let should_merge =
+                imported_total > existing_total || pattern.last_seen > existing.last_seen;
 
             if should_merge {
-                self.conn.execute(
-                    "UPDATE patterns
-                     SET success_count = success_count + ?,
-                         failure_count = failure_count + ?,
-                         last_seen = MAX(last_seen, ?),
-                         confidence = CAST(success_count + ? AS REAL) / (success_count + failure_count + ? + ?)
-                     WHERE code_signature = ? AND issue_category = ?",
-                    rusqlite::params![
-                        pattern.success_count,
-                        pattern.failure_count,
-                        pattern.last_seen.to_rfc3339(),
-                        pattern.success_count,
-                        pattern.success_count,
-                        pattern.failure_count,
-                        pattern.code_signature,
-                        pattern.issue_category
-                    ],
-                )?;
-
+                self.store.merge_counts(pattern)?;
                 return Ok(true);
             }
         }
@@ -174,6 +290,67 @@ impl ReasoningBank {
     }
 }
 
+/// Descompacta o pacote se ele for gzip; caso contrário, devolve-o intacto.
+fn decompress<'a>(pack: &'a [u8], origin: &str) -> TetradResult<Cow<'a, [u8]>> {
+    if !pack.starts_with(&GZIP_MAGIC) {
+        return Ok(Cow::Borrowed(pack));
+    }
+
+    let mut json = Vec::new();
+    GzDecoder::new(pack)
+        .take(MAX_DECOMPRESSED_BYTES + 1)
+        .read_to_end(&mut json)?;
+    if json.len() as u64 > MAX_DECOMPRESSED_BYTES {
+        return Err(TetradError::ReasoningBank(format!(
+            "{} expands to more than {} MB",
+            origin,
+            MAX_DECOMPRESSED_BYTES / (1024 * 1024)
+        )));
+    }
+    Ok(Cow::Owned(json))
+}
+
+/// Formata uma exportação como Markdown: o conhecimento destilado seguido
+/// de uma tabela com os patterns exportados.
+pub fn format_export(export: &ReasoningBankExport) -> String {
+    let mut output = format_knowledge(&export.knowledge);
+
+    output.push_str(&format!("## Patterns ({})\n\n", export.patterns.len()));
+    if export.patterns.is_empty() {
+        output.push_str("No patterns matched the export filters.\n");
+        return output;
+    }
+
+    output.push_str(
+        "| ID | Type | Language | Category | Confidence | Successes | Failures | Description | Solution |\n",
+    );
+    output.push_str("|---:|---|---|---|---:|---:|---:|---|---|\n");
+    for pattern in &export.patterns {
+        output.push_str(&format!(
+            "| {} | {} | {} | {} | {:.0}% | {} | {} | {} | {} |\n",
+            pattern.id,
+            pattern.pattern_type,
+            table_cell(&pattern.language),
+            table_cell(&pattern.issue_category),
+            pattern.confidence * 100.0,
+            pattern.success_count,
+            pattern.failure_count,
+            table_cell(&pattern.description),
+            table_cell(pattern.solution.as_deref().unwrap_or("")),
+        ));
+    }
+
+    output
+}
+
+/// Escapa texto para uma célula de tabela Markdown (uma linha, sem `|`).
+fn table_cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
 /// Formata conhecimento destilado para exibição.
 pub fn format_knowledge(knowledge: &DistilledKnowledge) -> String {
     let mut output = String::new();
@@ -250,12 +427,31 @@ pub fn format_knowledge(knowledge: &DistilledKnowledge) -> String {
         }
     }
 
+    // Executor Stats
+    if !knowledge.executor_stats.is_empty() {
+        output.push_str("## Executor Statistics\n\n");
+        let mut executors: Vec<_> = knowledge.executor_stats.iter().collect();
+        executors.sort_by(|a, b| a.0.cmp(b.0));
+        for (executor, stats) in executors {
+            output.push_str(&format!(
+                "### {}\n- Votes: {} (PASS {}, WARN {}, FAIL {})\n",
+                executor, stats.votes, stats.pass, stats.warn, stats.fail
+            ));
+            output.push_str(&format!(
+                "- Mean Score: {:.1}\n- Agreement with Decision: {:.0}%\n\n",
+                stats.mean_score,
+                stats.agreement_rate * 100.0
+            ));
+        }
+    }
+
     output
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::ResultBuilder;
     use tempfile::tempdir;
 
     fn create_test_bank() -> (ReasoningBank, tempfile::TempDir) {
@@ -270,7 +466,8 @@ mod tests {
         let (bank, dir) = create_test_bank();
         let export_path = dir.path().join("export.json");
 
-        bank.export(&export_path).unwrap();
+        bank.export(&export_path, &ExportOptions::default())
+            .unwrap();
 
         assert!(export_path.exists());
 
@@ -279,26 +476,36 @@ mod tests {
 
         assert_eq!(export.version, "2.0");
         assert!(export.patterns.is_empty());
+        assert_eq!(export.build, Some(BuildInfo::current()));
+    }
+
+    #[test]
+    fn test_import_export_without_build() {
+        let (mut bank, dir) = create_test_bank();
+        let import_path = dir.path().join("old.json");
+
+        // Exportações anteriores não tinham o campo build
+        let old_export = serde_json::json!({
+            "version": "2.0",
+            "exported_at": Utc::now(),
+            "knowledge": bank.distill(),
+            "patterns": []
+        });
+        std::fs::write(&import_path, old_export.to_string()).unwrap();
+
+        let result = bank.import(&import_path).unwrap();
+        assert_eq!(result.imported, 0);
     }
 
     #[test]
     fn test_export_import_roundtrip() {
-        use crate::types::responses::{Decision, Finding, Severity};
+        use crate::types::responses::{Finding, Severity};
 
         let (mut bank1, dir1) = create_test_bank();
 
         // Adiciona alguns patterns ao banco 1
         let finding = Finding::new(Severity::Warning, "security", "SQL injection");
-        let result = crate::types::responses::EvaluationResult {
-            request_id: "test".to_string(),
-            decision: Decision::Revise,
-            score: 60,
-            consensus_achieved: false,
-            votes: std::collections::HashMap::new(),
-            findings: vec![finding],
-            feedback: String::new(),
-            timestamp: Utc::now(),
-        };
+        let result = ResultBuilder::revise(60).with_finding(finding).build();
 
         bank1
             .judge("test-1", "SELECT * FROM users", "sql", &result, 3, 3)
@@ -306,7 +513,9 @@ mod tests {
 
         // Exporta
         let export_path = dir1.path().join("export.json");
-        bank1.export(&export_path).unwrap();
+        bank1
+            .export(&export_path, &ExportOptions::default())
+            .unwrap();
 
         // Cria novo banco e importa
         let (mut bank2, _dir2) = create_test_bank();
@@ -322,29 +531,21 @@ mod tests {
 
     #[test]
     fn test_import_skip_existing() {
-        use crate::types::responses::{Decision, Finding, Severity};
+        use crate::types::responses::{Finding, Severity};
 
         let (mut bank, dir) = create_test_bank();
 
         // Adiciona um pattern
         let finding = Finding::new(Severity::Warning, "security", "Test issue");
-        let result = crate::types::responses::EvaluationResult {
-            request_id: "test".to_string(),
-            decision: Decision::Revise,
-            score: 60,
-            consensus_achieved: false,
-            votes: std::collections::HashMap::new(),
-            findings: vec![finding],
-            feedback: String::new(),
-            timestamp: Utc::now(),
-        };
+        let result = ResultBuilder::revise(60).with_finding(finding).build();
 
         bank.judge("test-1", "test code", "rust", &result, 3, 3)
             .unwrap();
 
         // Exporta
         let export_path = dir.path().join("export.json");
-        bank.export(&export_path).unwrap();
+        bank.export(&export_path, &ExportOptions::default())
+            .unwrap();
 
         // Tenta importar de volta (deve fazer merge ou skip)
         let import_result = bank.import(&export_path).unwrap();
@@ -354,6 +555,260 @@ mod tests {
         assert!(import_result.skipped > 0 || import_result.merged > 0);
     }
 
+    /// Banco com um anti-pattern SQL, um Rust e um good pattern Rust.
+    fn create_mixed_bank() -> (ReasoningBank, tempfile::TempDir) {
+        use crate::types::responses::{EvaluationResult, Finding, Severity};
+
+        let (mut bank, dir) = create_test_bank();
+        let failing = |issue: &str| {
+            let mut result = EvaluationResult::failure("test", 30, "");
+            result.findings = vec![Finding::new(Severity::Error, "security", issue)];
+            result
+        };
+
+        bank.judge(
+            "mixed-1",
+            "SELECT * FROM t",
+            "sql",
+            &failing("SQL injection"),
+            1,
+            3,
+        )
+        .unwrap();
+        bank.judge(
+            "mixed-2",
+            "unsafe { x }",
+            "rust",
+            &failing("Unsound unsafe"),
+            1,
+            3,
+        )
+        .unwrap();
+        bank.judge(
+            "mixed-3",
+            "fn ok() {}",
+            "rust",
+            &EvaluationResult::success("test", 95, ""),
+            1,
+            3,
+        )
+        .unwrap();
+        (bank, dir)
+    }
+
+    #[test]
+    fn test_export_import_gzip_roundtrip() {
+        let (bank1, dir) = create_mixed_bank();
+        let export_path = dir.path().join("export.json.gz");
+
+        let exported = bank1
+            .export(
+                &export_path,
+                &ExportOptions {
+                    format: ExportFormat::JsonGz,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(exported, 3);
+
+        let bytes = std::fs::read(&export_path).unwrap();
+        assert!(bytes.starts_with(&GZIP_MAGIC));
+
+        let (mut bank2, _dir2) = create_test_bank();
+        let result = bank2.import(&export_path).unwrap();
+        assert_eq!(result.imported, 3);
+        assert_eq!(bank2.get_all_patterns().unwrap().len(), 3);
+
+        // O mesmo conteúdo, descompactado, continua importável
+        let json = decompress(&bytes, "test").unwrap();
+        let (mut bank3, _dir3) = create_test_bank();
+        assert_eq!(bank3.import_bytes(&json, "test").unwrap().imported, 3);
+    }
+
+    #[test]
+    fn test_import_rejects_corrupt_gzip() {
+        let (mut bank, _dir) = create_test_bank();
+        let pack = [0x1f, 0x8b, 0x08, 0x00, 0xff, 0xff];
+
+        assert!(bank.import_bytes(&pack, "corrupt").is_err());
+    }
+
+    #[test]
+    fn test_export_filters_exclude_patterns() {
+        let (bank, dir) = create_mixed_bank();
+        let export_path = dir.path().join("filtered.json");
+
+        let exported = bank
+            .export(
+                &export_path,
+                &ExportOptions {
+                    language: Some("RUST".to_string()),
+                    pattern_type: Some(PatternType::AntiPattern),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(exported, 1);
+
+        let content = std::fs::read_to_string(&export_path).unwrap();
+        let export: ReasoningBankExport = serde_json::from_str(&content).unwrap();
+        assert_eq!(export.patterns.len(), 1);
+        assert_eq!(export.patterns[0].language, "rust");
+        assert_eq!(export.patterns[0].pattern_type, PatternType::AntiPattern);
+
+        // O conhecimento destilado também não vaza o que ficou de fora
+        assert!(export
+            .knowledge
+            .top_antipatterns
+            .iter()
+            .all(|p| p.language == "rust"));
+        assert!(export.knowledge.top_good_patterns.is_empty());
+        assert!(!export.knowledge.language_stats.contains_key("sql"));
+
+        let confident = bank
+            .export(
+                &export_path,
+                &ExportOptions {
+                    min_confidence: Some(0.9),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(confident, 1);
+    }
+
+    #[test]
+    fn test_export_markdown() {
+        let (bank, dir) = create_mixed_bank();
+        let export_path = dir.path().join("export.md");
+
+        bank.export(
+            &export_path,
+            &ExportOptions {
+                format: ExportFormat::Markdown,
+                language: Some("sql".to_string()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let markdown = std::fs::read_to_string(&export_path).unwrap();
+        assert!(markdown.starts_with("# ReasoningBank Knowledge"));
+        assert!(markdown.contains("## Patterns (1)"));
+        assert!(markdown.contains("| anti_pattern | sql | security |"));
+        assert!(!markdown.contains("| rust |"));
+    }
+
+    /// Política com a chave do signatário como única chave confiável.
+    fn policy_trusting(key: &SigningKey, require: bool) -> SignaturePolicy {
+        let mut trusted = crate::reasoning::TrustedKeys::default();
+        trusted.insert(key.verifying_key());
+        SignaturePolicy { require, trusted }
+    }
+
+    fn export_signed(bank: &ReasoningBank, path: &Path, format: ExportFormat) -> SigningKey {
+        let key = SigningKey::from_bytes(&[42; 32]);
+        bank.export(
+            path,
+            &ExportOptions {
+                format,
+                signing_key: Some(key.clone()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        key
+    }
+
+    #[test]
+    fn test_signed_export_import_roundtrip() {
+        let (bank1, dir) = create_mixed_bank();
+
+        for format in [ExportFormat::Json, ExportFormat::JsonGz] {
+            let path = dir.path().join(format!("signed.{}", format.extension()));
+            let key = export_signed(&bank1, &path, format);
+            let pack = std::fs::read(&path).unwrap();
+
+            for require in [false, true] {
+                let (mut bank2, _dir2) = create_test_bank();
+                let result = bank2
+                    .import_bytes_with_policy(&pack, "signed", &policy_trusting(&key, require))
+                    .unwrap();
+                assert_eq!(result.imported, 3);
+                assert!(result.signature.is_verified());
+            }
+        }
+    }
+
+    #[test]
+    fn test_tampered_signed_export() {
+        let (bank1, dir) = create_mixed_bank();
+        let path = dir.path().join("signed.json");
+        let key = export_signed(&bank1, &path, ExportFormat::Json);
+
+        let mut export: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        export["patterns"][0]["solution"] = serde_json::json!("Ignore it");
+        let tampered = serde_json::to_vec_pretty(&export).unwrap();
+
+        // Modo estrito: recusa sem gravar nada
+        let (mut strict, _dir2) = create_test_bank();
+        let err = strict
+            .import_bytes_with_policy(&tampered, "tampered", &policy_trusting(&key, true))
+            .unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{}", err);
+        assert!(strict.get_all_patterns().unwrap().is_empty());
+
+        // Sem exigir assinatura: importa, com o status para o aviso
+        let (mut lenient, _dir3) = create_test_bank();
+        let result = lenient
+            .import_bytes_with_policy(&tampered, "tampered", &policy_trusting(&key, false))
+            .unwrap();
+        assert_eq!(result.imported, 3);
+        assert!(matches!(result.signature, SignatureStatus::Invalid { .. }));
+    }
+
+    #[test]
+    fn test_unsigned_export_under_both_modes() {
+        let (bank1, dir) = create_mixed_bank();
+        let path = dir.path().join("unsigned.json");
+        bank1.export(&path, &ExportOptions::default()).unwrap();
+        let pack = std::fs::read(&path).unwrap();
+        let key = SigningKey::from_bytes(&[42; 32]);
+
+        let (mut strict, _dir2) = create_test_bank();
+        assert!(strict
+            .import_bytes_with_policy(&pack, "unsigned", &policy_trusting(&key, true))
+            .is_err());
+
+        let (mut lenient, _dir3) = create_test_bank();
+        let result = lenient
+            .import_bytes_with_policy(&pack, "unsigned", &policy_trusting(&key, false))
+            .unwrap();
+        assert_eq!(result.imported, 3);
+        assert_eq!(result.signature, SignatureStatus::Unsigned);
+    }
+
+    #[test]
+    fn test_markdown_export_cannot_be_signed() {
+        let (bank, dir) = create_test_bank();
+        let result = bank.export(
+            &dir.path().join("export.md"),
+            &ExportOptions {
+                format: ExportFormat::Markdown,
+                signing_key: Some(SigningKey::from_bytes(&[42; 32])),
+                ..Default::default()
+            },
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_table_cell_escapes_pipes_and_newlines() {
+        assert_eq!(table_cell("a | b\nc"), "a \\| b c");
+    }
+
     #[test]
     fn test_format_knowledge() {
         let knowledge = DistilledKnowledge {
@@ -364,11 +819,24 @@ mod tests {
             avg_loops_to_consensus: 2.5,
             total_patterns: 10,
             total_trajectories: 50,
+            executor_stats: std::collections::HashMap::from([(
+                "Codex".to_string(),
+                crate::reasoning::ExecutorAccuracy {
+                    votes: 4,
+                    pass: 3,
+                    warn: 1,
+                    fail: 0,
+                    mean_score: 82.5,
+                    agreement_rate: 0.75,
+                },
+            )]),
         };
 
         let formatted = format_knowledge(&knowledge);
 
         assert!(formatted.contains("**Total Patterns:** 10"));
+        assert!(formatted.contains("- Votes: 4 (PASS 3, WARN 1, FAIL 0)"));
+        assert!(formatted.contains("- Agreement with Decision: 75%"));
         assert!(formatted.contains("**Total Trajectories:** 50"));
         assert!(formatted.contains("2.50"));
     }
@@ -0,0 +1,198 @@
+//! Influência dos patterns conhecidos na avaliação.
+//!
+//! Anti-patterns com confiança alta podem ser enviados como contexto aos
+//! executores e reduzir o score final. GoodPatterns nunca penalizam.
+//!
+//! O campo `confidence` de um pattern é a taxa de sucesso do código; para
+//! anti-patterns a confiança usada aqui é a taxa de falha.
+
+use crate::types::responses::{AppliedPattern, MatchedPattern, PatternAdjustment};
+
+use super::bank::{Pattern, PatternMatch, PatternType};
+
+/// Confiança de que um anti-pattern indica um problema real.
+fn antipattern_confidence(pattern: &Pattern) -> f64 {
+    let total = pattern.success_count + pattern.failure_count;
+    if total <= 0 {
+        return 0.0;
+    }
+    pattern.failure_count as f64 / total as f64
+}
+
+/// Filtra os anti-patterns com confiança mínima.
+pub fn known_antipatterns(matches: &[PatternMatch], min_confidence: f64) -> Vec<&PatternMatch> {
+    matches
+        .iter()
+        .filter(|m| m.pattern.pattern_type == PatternType::AntiPattern)
+        .filter(|m| antipattern_confidence(&m.pattern) >= min_confidence)
+        .collect()
+}
+
+/// Resume os patterns encontrados para guardar no resultado da avaliação,
+/// com as estatísticas que têm agora.
+pub fn matched_patterns(matches: &[PatternMatch]) -> Vec<MatchedPattern> {
+    matches
+        .iter()
+        .map(|m| MatchedPattern {
+            id: m.pattern.id,
+            pattern_type: m.pattern.pattern_type.to_string(),
+            issue_category: m.pattern.issue_category.clone(),
+            description: m.pattern.description.clone(),
+            match_type: m.match_type.to_string(),
+            relevance: m.relevance,
+            confidence: m.pattern.confidence,
+            success_count: m.pattern.success_count,
+            failure_count: m.pattern.failure_count,
+        })
+        .collect()
+}
+
+/// Monta a seção de contexto com os problemas já conhecidos.
+///
+/// Retorna `None` quando não há anti-patterns.
+pub fn prompt_context(antipatterns: &[&PatternMatch]) -> Option<String> {
+    if antipatterns.is_empty() {
+        return None;
+    }
+
+    let mut section = String::from("Problemas conhecidos de avaliações anteriores:\n");
+    for m in antipatterns {
+        section.push_str(&format!(
+            "- [{}] {} (confiança: {:.0}%)\n",
+            m.pattern.issue_category,
+            m.pattern.description,
+            antipattern_confidence(&m.pattern) * 100.0
+        ));
+        if let Some(solution) = &m.pattern.solution {
+            section.push_str(&format!("  Solução conhecida: {}\n", solution));
+        }
+    }
+    section.push_str("Verifique se estes problemas continuam presentes.");
+
+    Some(section)
+}
+
+/// Calcula a penalidade de score para os anti-patterns encontrados.
+///
+/// Cada pattern contribui com `relevance * confiança`; a soma é limitada a
+/// 1.0 e escalada para até `max_penalty` pontos.
+pub fn score_adjustment(
+    antipatterns: &[&PatternMatch],
+    original_score: u8,
+    max_penalty: u8,
+) -> Option<PatternAdjustment> {
+    if antipatterns.is_empty() || max_penalty == 0 {
+        return None;
+    }
+
+    let weight: f64 = antipatterns
+        .iter()
+        .map(|m| m.relevance * antipattern_confidence(&m.pattern))
+        .sum::<f64>()
+        .min(1.0);
+    let penalty = (weight * max_penalty as f64).round() as u8;
+    if penalty == 0 {
+        return None;
+    }
+
+    Some(PatternAdjustment {
+        original_score,
+        penalty,
+        patterns: antipatterns
+            .iter()
+            .map(|m| AppliedPattern {
+                issue_category: m.pattern.issue_category.clone(),
+                description: m.pattern.description.clone(),
+                confidence: antipattern_confidence(&m.pattern),
+            })
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reasoning::{ArtifactKind, MatchType, SIGNATURE_VERSION};
+    use chrono::Utc;
+
+    fn create_match(
+        pattern_type: PatternType,
+        success_count: i32,
+        failure_count: i32,
+        relevance: f64,
+    ) -> PatternMatch {
+        PatternMatch {
+            pattern: Pattern {
+                id: 1,
+                pattern_type,
+                code_signature: "sig".to_string(),
+                language: "rust".to_string(),
+                issue_category: "security".to_string(),
+                description: "SQL injection".to_string(),
+                solution: Some("Use prepared statements".to_string()),
+                success_count,
+                failure_count,
+                confidence: 0.5,
+                last_seen: Utc::now(),
+                created_at: Utc::now(),
+                artifact_kind: ArtifactKind::Code,
+                shape_signature: None,
+                signature_version: SIGNATURE_VERSION,
+            },
+            match_type: MatchType::Exact,
+            relevance,
+        }
+    }
+
+    #[test]
+    fn test_good_patterns_never_penalize() {
+        // Mesmo com falhas registradas, GoodPatterns não penalizam
+        let matches = vec![create_match(PatternType::GoodPattern, 0, 10, 1.0)];
+        let antipatterns = known_antipatterns(&matches, 0.5);
+
+        assert!(antipatterns.is_empty());
+        assert!(prompt_context(&antipatterns).is_none());
+        assert!(score_adjustment(&antipatterns, 90, 10).is_none());
+    }
+
+    #[test]
+    fn test_threshold_filters_low_confidence() {
+        let matches = vec![
+            create_match(PatternType::AntiPattern, 1, 9, 1.0),
+            create_match(PatternType::AntiPattern, 6, 4, 1.0),
+        ];
+
+        assert_eq!(known_antipatterns(&matches, 0.7).len(), 1);
+    }
+
+    #[test]
+    fn test_penalty_scales_with_confidence_and_is_capped() {
+        let weak = [create_match(PatternType::AntiPattern, 2, 8, 0.5)];
+        let weak_refs: Vec<_> = weak.iter().collect();
+        let adjustment = score_adjustment(&weak_refs, 90, 10).unwrap();
+        assert_eq!(adjustment.penalty, 4);
+        assert_eq!(adjustment.original_score, 90);
+        assert_eq!(adjustment.patterns[0].issue_category, "security");
+
+        let strong = [
+            create_match(PatternType::AntiPattern, 1, 9, 1.0),
+            create_match(PatternType::AntiPattern, 1, 9, 1.0),
+        ];
+        let strong_refs: Vec<_> = strong.iter().collect();
+        assert_eq!(score_adjustment(&strong_refs, 90, 10).unwrap().penalty, 10);
+
+        // Penalidade máxima 0 desliga o ajuste
+        assert!(score_adjustment(&strong_refs, 90, 0).is_none());
+    }
+
+    #[test]
+    fn test_prompt_context_lists_known_issues() {
+        let matches = vec![create_match(PatternType::AntiPattern, 1, 9, 1.0)];
+        let antipatterns = known_antipatterns(&matches, 0.7);
+        let context = prompt_context(&antipatterns).unwrap();
+
+        assert!(context.contains("Problemas conhecidos de avaliações anteriores"));
+        assert!(context.contains("[security] SQL injection (confiança: 90%)"));
+        assert!(context.contains("Use prepared statements"));
+    }
+}
@@ -0,0 +1,144 @@
+//! Contadores persistidos das avaliações do servidor MCP.
+//!
+//! O `MetricsHook` conta as avaliações desde que o servidor subiu; os mesmos
+//! contadores são somados no ReasoningBank, por tipo de avaliação, para que
+//! `tetrad metrics` mostre o acumulado entre execuções.
+
+use serde::Serialize;
+
+use crate::types::requests::EvaluationType;
+use crate::types::responses::Decision;
+use crate::TetradResult;
+
+use super::bank::ReasoningBank;
+
+/// Contadores acumulados de um tipo de avaliação.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct MetricCounters {
+    /// Tipo de avaliação (`code`, `plan`, `final_check`...).
+    pub evaluation_type: String,
+
+    /// Avaliações registradas.
+    pub evaluations: u64,
+
+    /// Avaliações com decisão PASS.
+    pub passes: u64,
+
+    /// Avaliações com decisão REVISE.
+    pub revises: u64,
+
+    /// Avaliações com decisão BLOCK.
+    pub blocks: u64,
+
+    /// Soma dos scores (para a média).
+    pub score_sum: u64,
+
+    /// Verificações finais respondidas com um certificado existente.
+    pub recertifications: u64,
+}
+
+impl MetricCounters {
+    /// Contadores de uma única avaliação.
+    pub fn evaluation(evaluation_type: EvaluationType, decision: Decision, score: u8) -> Self {
+        Self {
+            evaluation_type: evaluation_type.to_string(),
+            evaluations: 1,
+            passes: u64::from(decision == Decision::Pass),
+            revises: u64::from(decision == Decision::Revise),
+            blocks: u64::from(decision == Decision::Block),
+            score_sum: u64::from(score),
+            recertifications: 0,
+        }
+    }
+
+    /// Contadores de uma única re-certificação, contada como `final_check`.
+    pub fn recertification() -> Self {
+        Self {
+            evaluation_type: EvaluationType::FinalCheck.to_string(),
+            recertifications: 1,
+            ..Default::default()
+        }
+    }
+}
+
+impl ReasoningBank {
+    /// Soma uma avaliação aos contadores persistidos.
+    pub fn record_metrics(
+        &mut self,
+        evaluation_type: EvaluationType,
+        decision: Decision,
+        score: u8,
+    ) -> TetradResult<()> {
+        let delta = MetricCounters::evaluation(evaluation_type, decision, score);
+        self.in_write_transaction(|bank| bank.store.add_metrics(&delta))
+    }
+
+    /// Soma uma re-certificação aos contadores persistidos.
+    pub fn record_recertification(&mut self) -> TetradResult<()> {
+        let delta = MetricCounters::recertification();
+        self.in_write_transaction(|bank| bank.store.add_metrics(&delta))
+    }
+
+    /// Contadores persistidos, um por tipo de avaliação.
+    pub fn metric_counters(&self) -> TetradResult<Vec<MetricCounters>> {
+        self.store.metrics()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::ReasoningConfig;
+    use tempfile::TempDir;
+
+    fn bank(dir: &TempDir) -> ReasoningBank {
+        ReasoningBank::new_with_config(&dir.path().join("metrics.db"), &ReasoningConfig::default())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_metrics_accumulate_across_reopen() {
+        let dir = TempDir::new().unwrap();
+        {
+            let mut bank = bank(&dir);
+            bank.record_metrics(EvaluationType::Code, Decision::Pass, 90)
+                .unwrap();
+            bank.record_metrics(EvaluationType::Code, Decision::Block, 20)
+                .unwrap();
+            bank.record_metrics(EvaluationType::Plan, Decision::Revise, 65)
+                .unwrap();
+        }
+
+        // Os contadores sobrevivem ao processo
+        let mut bank = bank(&dir);
+        bank.record_recertification().unwrap();
+        let counters = bank.metric_counters().unwrap();
+
+        assert_eq!(
+            counters,
+            vec![
+                MetricCounters {
+                    evaluation_type: "code".to_string(),
+                    evaluations: 2,
+                    passes: 1,
+                    revises: 0,
+                    blocks: 1,
+                    score_sum: 110,
+                    recertifications: 0,
+                },
+                MetricCounters {
+                    evaluation_type: "final_check".to_string(),
+                    recertifications: 1,
+                    ..Default::default()
+                },
+                MetricCounters {
+                    evaluation_type: "plan".to_string(),
+                    evaluations: 1,
+                    revises: 1,
+                    score_sum: 65,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+}
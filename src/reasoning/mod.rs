@@ -5,17 +5,60 @@
 //!
 //! ## Componentes
 //!
-//! - **ReasoningBank**: Banco de dados SQLite que armazena patterns e trajetórias
+//! - **ReasoningBank**: Ciclo de aprendizado sobre patterns e trajetórias
+//! - **PatternStore**: Armazenamento dos patterns (SQLite local ou, com a
+//!   feature `postgres`, Postgres compartilhado)
 //! - **PatternMatcher**: Utilitários para matching e análise de código
-//! - **Export/Import**: Compartilhamento de conhecimento entre instalações
+//! - **Influence**: Uso dos anti-patterns conhecidos no prompt e no score
+//! - **Digest**: Resumo dos anti-patterns recorrentes, regenerado a cada consolidação
+//! - **Export/Import**: Compartilhamento de conhecimento entre instalações (JSON, gzip ou Markdown)
+//! - **Fetch**: Obtenção de pacotes de patterns de arquivos locais ou URLs
+//! - **Signing**: Assinatura ed25519 dos pacotes exportados e verificação na importação
+//! - **Usage**: Contabilidade mensal do orçamento de avaliações
+//! - **Metrics**: Contadores acumulados das avaliações do servidor MCP
 
 mod bank;
+mod digest;
 mod export;
+mod fetch;
+mod influence;
+mod metrics;
 mod patterns;
+#[cfg(feature = "postgres")]
+mod postgres;
+mod signing;
+mod sqlite;
+mod store;
+mod usage;
 
 pub use bank::{
-    ConsolidationResult, DistilledKnowledge, JudgmentResult, LanguageStats, MatchType, Pattern,
-    PatternMatch, PatternType, ReasoningBank,
+    ArtifactKind, ConsolidationResult, DistilledKnowledge, ExecutorAccuracy, FlakySignature,
+    JudgmentResult, LanguageStats, MatchType, Pattern, PatternFilter, PatternMatch, PatternSort,
+    PatternType, PeriodStats, ReasoningBank, StatsBucket,
 };
-pub use export::{format_knowledge, ImportResult, ReasoningBankExport};
-pub use patterns::PatternMatcher;
+pub use digest::{render_digest, DIGEST_FILE_NAME, DIGEST_MAX_CHARS, DIGEST_MAX_PATTERNS};
+pub use export::{
+    format_export, format_knowledge, ExportFormat, ExportOptions, ImportResult,
+    ReasoningBankExport, MAX_DECOMPRESSED_BYTES,
+};
+pub use fetch::{
+    fetch_pack, verify_sha256, FetchOptions, PackSource, DEFAULT_FETCH_TIMEOUT,
+    DEFAULT_MAX_PACK_BYTES,
+};
+pub(crate) use fetch::{is_http_url, post_json};
+pub use influence::{known_antipatterns, matched_patterns, prompt_context, score_adjustment};
+pub use metrics::MetricCounters;
+pub use patterns::{
+    ChunkSignature, PatternMatcher, MIN_DETECTION_CONFIDENCE, SIGNATURE_VERSION, TRUNCATED_MARKER,
+};
+pub use signing::{
+    fingerprint, generate_signing_key, load_signing_key, load_verifying_key, sign_export,
+    verify_pack, write_keypair, PackSignature, SignaturePolicy, SignatureStatus, TrustedKeys,
+    SIGNATURE_ALGORITHM, SIGNING_KEY_FILE_NAME, TRUSTED_KEYS_DIR_NAME, VERIFYING_KEY_FILE_NAME,
+};
+#[cfg(feature = "postgres")]
+pub use store::PostgresStore;
+pub use store::{
+    DecisionRecord, PatternStore, PeriodTally, SqliteStore, StoredVote, TrajectoryRecord, VoteTally,
+};
+pub use usage::{month_key, BudgetCharge, BudgetStatus, MonthlyUsage, UsageLimits};
@@ -2,23 +2,575 @@
 //!
 //! Este módulo fornece utilitários para:
 //! - Normalizar código (remover whitespace, comentários)
-//! - Computar assinaturas SHA256
+//! - Computar assinaturas SHA256 (do código inteiro e de cada função/bloco)
+//!   sobre os tokens do código, sem comentários nem conteúdo de strings
+//! - Computar a assinatura de forma, que ignora os nomes dos identificadores
 //! - Extrair keywords indicativas de patterns
+//! - Normalizar descrições e soluções vindas dos executores
+
+use std::collections::HashMap;
+use std::path::Path;
 
 use sha2::{Digest, Sha256};
 
+use crate::types::language::{Language, ANY, OTHER};
+
+/// Sufixo de descrições e soluções truncadas.
+pub const TRUNCATED_MARKER: &str = "… [truncated]";
+
+/// Palavras que iniciam uma declaração de topo (função, classe, bloco).
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "fn",
+    "def",
+    "function",
+    "func",
+    "class",
+    "impl",
+    "struct",
+    "enum",
+    "trait",
+    "interface",
+    "mod",
+];
+
+/// Modificadores que podem preceder uma declaração de topo.
+const DECLARATION_MODIFIERS: &[&str] = &[
+    "pub",
+    "pub(crate)",
+    "pub(super)",
+    "export",
+    "default",
+    "async",
+    "unsafe",
+    "const",
+    "extern",
+    "abstract",
+    "public",
+    "private",
+    "protected",
+    "static",
+    "final",
+];
+
+/// Versão do algoritmo de assinatura.
+///
+/// Patterns gravados com outra versão (0 = antes da tokenização) têm
+/// assinaturas que não batem mais com as atuais: só são encontrados por
+/// keyword.
+pub const SIGNATURE_VERSION: u32 = 1;
+
+/// Palavras reservadas mantidas na assinatura de forma; os demais
+/// identificadores viram marcadores pela ordem em que aparecem.
+const SHAPE_KEYWORDS: &[&str] = &[
+    "and",
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "def",
+    "default",
+    "defer",
+    "del",
+    "do",
+    "elif",
+    "else",
+    "enum",
+    "except",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "fn",
+    "for",
+    "from",
+    "func",
+    "function",
+    "go",
+    "if",
+    "impl",
+    "import",
+    "in",
+    "interface",
+    "is",
+    "lambda",
+    "let",
+    "loop",
+    "match",
+    "mod",
+    "mut",
+    "new",
+    "nil",
+    "none",
+    "not",
+    "null",
+    "or",
+    "package",
+    "pass",
+    "pub",
+    "raise",
+    "return",
+    "self",
+    "static",
+    "struct",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "unsafe",
+    "use",
+    "var",
+    "void",
+    "where",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Confiança mínima para aceitar a linguagem detectada; abaixo dela a
+/// detecção retorna "unknown".
+pub const MIN_DETECTION_CONFIDENCE: f64 = 0.4;
+
+/// Pontuação a partir da qual os tokens de uma linguagem dão confiança plena.
+const TOKEN_SATURATION: u32 = 8;
+
+/// Linhas a partir das quais um arquivo de configuração dá confiança plena.
+const CONFIG_SATURATION: u32 = 4;
+
+/// Tokens característicos de uma linguagem, com seus pesos.
+struct LanguageSignals {
+    language: &'static str,
+    /// Compara em minúsculas (linguagens que ignoram maiúsculas, como SQL).
+    case_insensitive: bool,
+    tokens: &'static [(&'static str, u32)],
+}
+
+/// Tabela de pontuação por linguagem.
+///
+/// Cada token presente no código soma seu peso uma vez; tokens comuns a
+/// várias linguagens têm peso baixo.
+const LANGUAGE_SIGNALS: &[LanguageSignals] = &[
+    LanguageSignals {
+        language: "rust",
+        case_insensitive: false,
+        tokens: &[
+            ("fn ", 2),
+            ("fn main(", 3),
+            ("pub fn ", 3),
+            ("let mut ", 3),
+            ("let ", 1),
+            ("impl ", 3),
+            ("&self", 3),
+            ("&str", 3),
+            ("use std::", 4),
+            ("#[derive", 4),
+            ("println!", 3),
+            ("Option<", 2),
+            ("Result<", 2),
+            ("::", 1),
+            ("match ", 1),
+            ("-> ", 1),
+        ],
+    },
+    LanguageSignals {
+        language: "python",
+        case_insensitive: false,
+        tokens: &[
+            ("def ", 3),
+            ("elif ", 3),
+            ("):\n", 2),
+            ("__init__", 4),
+            ("if __name__", 4),
+            ("self.", 2),
+            ("print(", 2),
+            ("import ", 1),
+            ("lambda ", 2),
+            ("None", 2),
+            ("True", 1),
+            ("False", 1),
+        ],
+    },
+    LanguageSignals {
+        language: "javascript",
+        case_insensitive: false,
+        tokens: &[
+            ("const ", 1),
+            ("let ", 1),
+            ("var ", 2),
+            ("function ", 2),
+            ("=>", 1),
+            ("===", 2),
+            ("!==", 2),
+            ("console.log", 3),
+            ("require(", 3),
+            ("module.exports", 4),
+            ("export default", 3),
+            ("document.", 3),
+            ("undefined", 2),
+        ],
+    },
+    // Só os tokens próprios; os de JavaScript somam via LANGUAGE_SUPERSETS
+    LanguageSignals {
+        language: "typescript",
+        case_insensitive: false,
+        tokens: &[
+            (": string", 3),
+            (": number", 3),
+            (": boolean", 3),
+            (": void", 3),
+            (": any", 3),
+            ("export interface ", 4),
+            ("export type ", 4),
+            ("interface ", 1),
+            ("readonly ", 2),
+            ("as const", 3),
+            ("?: ", 2),
+        ],
+    },
+    LanguageSignals {
+        language: "go",
+        case_insensitive: false,
+        tokens: &[
+            ("package ", 3),
+            ("func ", 3),
+            (":= ", 3),
+            ("fmt.", 3),
+            ("import (", 4),
+            ("err != nil", 4),
+            ("go func", 3),
+            ("defer ", 2),
+            ("chan ", 2),
+        ],
+    },
+    LanguageSignals {
+        language: "java",
+        case_insensitive: false,
+        tokens: &[
+            ("public class ", 3),
+            ("public static void main", 5),
+            ("System.out.", 4),
+            ("import java.", 5),
+            ("@Override", 3),
+            ("private ", 1),
+            ("String[]", 2),
+            ("extends ", 1),
+            ("void ", 1),
+        ],
+    },
+    LanguageSignals {
+        language: "c",
+        case_insensitive: false,
+        tokens: &[
+            ("#include <", 3),
+            ("int main(", 3),
+            ("printf(", 3),
+            ("malloc(", 3),
+            ("free(", 2),
+            ("sizeof(", 2),
+            ("#define ", 2),
+            ("NULL", 2),
+            ("char *", 2),
+            ("void ", 1),
+        ],
+    },
+    // Só os tokens próprios; os de C somam via LANGUAGE_SUPERSETS
+    LanguageSignals {
+        language: "cpp",
+        case_insensitive: false,
+        tokens: &[
+            ("std::", 4),
+            ("#include <iostream>", 5),
+            ("cout <<", 4),
+            ("template <", 4),
+            ("template<", 4),
+            ("nullptr", 4),
+            ("public:", 4),
+            ("private:", 3),
+            ("namespace ", 2),
+        ],
+    },
+    LanguageSignals {
+        language: "csharp",
+        case_insensitive: false,
+        tokens: &[
+            ("using System", 5),
+            ("Console.WriteLine", 5),
+            ("{ get; set; }", 5),
+            ("async Task", 4),
+            ("namespace ", 2),
+            ("public class ", 2),
+            ("var ", 1),
+        ],
+    },
+    LanguageSignals {
+        language: "kotlin",
+        case_insensitive: false,
+        tokens: &[
+            ("fun ", 3),
+            ("val ", 2),
+            ("var ", 1),
+            ("data class ", 5),
+            ("companion object", 5),
+            ("println(", 2),
+            ("when (", 2),
+        ],
+    },
+    LanguageSignals {
+        language: "swift",
+        case_insensitive: false,
+        tokens: &[
+            ("import Foundation", 5),
+            ("import UIKit", 5),
+            ("import SwiftUI", 5),
+            ("guard let ", 5),
+            ("if let ", 3),
+            ("func ", 2),
+            ("let ", 1),
+            ("var ", 1),
+            ("-> ", 1),
+        ],
+    },
+    LanguageSignals {
+        language: "ruby",
+        case_insensitive: false,
+        tokens: &[
+            ("puts ", 3),
+            ("require '", 3),
+            ("attr_accessor", 4),
+            ("do |", 4),
+            ("elsif ", 4),
+            ("end\n", 2),
+            ("def ", 1),
+            ("nil", 2),
+        ],
+    },
+    LanguageSignals {
+        language: "php",
+        case_insensitive: false,
+        tokens: &[("<?php", 8), ("$this->", 4), ("echo ", 2), ("function ", 1)],
+    },
+    LanguageSignals {
+        language: "shell",
+        case_insensitive: false,
+        tokens: &[
+            ("if [", 4),
+            ("; then", 4),
+            ("fi\n", 3),
+            ("esac", 4),
+            ("done", 2),
+            ("$(", 2),
+            ("${", 1),
+            ("echo ", 1),
+            ("export ", 1),
+        ],
+    },
+    LanguageSignals {
+        language: "sql",
+        case_insensitive: true,
+        tokens: &[
+            ("select ", 3),
+            (" from ", 1),
+            ("where ", 2),
+            ("insert into", 4),
+            ("create table", 5),
+            ("primary key", 4),
+            ("group by", 3),
+            ("order by", 2),
+            ("join ", 2),
+            ("values (", 2),
+        ],
+    },
+    LanguageSignals {
+        language: "dockerfile",
+        case_insensitive: false,
+        tokens: &[
+            ("FROM ", 2),
+            ("RUN ", 3),
+            ("COPY ", 3),
+            ("WORKDIR ", 4),
+            ("ENTRYPOINT ", 4),
+            ("EXPOSE ", 3),
+            ("CMD ", 2),
+            ("ENV ", 2),
+            ("ARG ", 2),
+        ],
+    },
+];
+
+/// Linguagens que estendem outra: quando algum token próprio aparece, os
+/// pontos da base somam para elas, e a base fica sem pontos.
+const LANGUAGE_SUPERSETS: &[(&str, &str)] = &[("typescript", "javascript"), ("cpp", "c")];
+
+/// Interpretadores de shebang e a linguagem correspondente.
+const SHEBANG_INTERPRETERS: &[(&str, &str)] = &[
+    ("python", "python"),
+    ("node", "javascript"),
+    ("deno", "typescript"),
+    ("ts-node", "typescript"),
+    ("bash", "shell"),
+    ("sh", "shell"),
+    ("zsh", "shell"),
+    ("ruby", "ruby"),
+    ("perl", "perl"),
+    ("php", "php"),
+];
+
+/// Nomes de arquivo sem extensão que identificam a linguagem.
+const FILE_NAMES: &[(&str, &str)] = &[
+    ("dockerfile", "dockerfile"),
+    ("containerfile", "dockerfile"),
+    ("makefile", "makefile"),
+    ("gnumakefile", "makefile"),
+];
+
+/// Assinatura de um trecho do código (função, classe ou bloco de topo).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkSignature {
+    /// Assinatura SHA256 do trecho normalizado.
+    pub signature: String,
+    /// Primeira linha do trecho (1-based).
+    pub start_line: u32,
+    /// Última linha do trecho (1-based, inclusiva).
+    pub end_line: u32,
+}
+
+impl ChunkSignature {
+    /// Distância em linhas até `line` (0 se o trecho a contém).
+    pub fn distance_to(&self, line: u32) -> u32 {
+        if line < self.start_line {
+            self.start_line - line
+        } else {
+            line.saturating_sub(self.end_line)
+        }
+    }
+}
+
 /// Utilitários para pattern matching.
 pub struct PatternMatcher;
 
 impl PatternMatcher {
-    /// Computa a assinatura SHA256 de um código normalizado.
+    /// Computa a assinatura SHA256 do código.
+    ///
+    /// A assinatura cobre a sequência de tokens: comentários e espaços não
+    /// contam, o conteúdo de strings é descartado e identificadores são
+    /// comparados sem diferenciar maiúsculas.
     pub fn compute_signature(code: &str) -> String {
-        let normalized = Self::normalize_code(code);
+        let canonical: Vec<String> = tokenize(code)
+            .into_iter()
+            .map(|token| match token {
+                CodeToken::Word(word) => word.to_lowercase(),
+                CodeToken::Number(number) => number.to_string(),
+                CodeToken::Literal => "\"\"".to_string(),
+                CodeToken::Symbol(c) => c.to_string(),
+            })
+            .collect();
+        Self::hash(&canonical.join(" "))
+    }
+
+    /// Computa a assinatura de forma do código.
+    ///
+    /// Mais grosseira que [`compute_signature`](Self::compute_signature):
+    /// mantém palavras reservadas e operadores, troca cada identificador por
+    /// um marcador posicional (`$0`, `$1`...) e números por `0`. Renomear
+    /// variáveis não muda a forma.
+    pub fn compute_shape_signature(code: &str) -> String {
+        let mut names: HashMap<String, usize> = HashMap::new();
+        let shape: Vec<String> = tokenize(code)
+            .into_iter()
+            .map(|token| match token {
+                CodeToken::Word(word) => {
+                    let word = word.to_lowercase();
+                    if SHAPE_KEYWORDS.contains(&word.as_str()) {
+                        word
+                    } else {
+                        let next = names.len();
+                        format!("${}", names.entry(word).or_insert(next))
+                    }
+                }
+                CodeToken::Number(_) => "0".to_string(),
+                CodeToken::Literal => "\"\"".to_string(),
+                CodeToken::Symbol(c) => c.to_string(),
+            })
+            .collect();
+        Self::hash(&shape.join(" "))
+    }
+
+    /// SHA256 em hexadecimal de um texto já normalizado.
+    pub fn hash(text: &str) -> String {
         let mut hasher = Sha256::new();
-        hasher.update(normalized.as_bytes());
+        hasher.update(text.as_bytes());
         hex::encode(hasher.finalize())
     }
 
+    /// Divide o código em trechos de topo e computa a assinatura de cada um.
+    ///
+    /// Um trecho começa em uma declaração sem indentação (`fn`, `def`,
+    /// `class`, `impl`...) e vai até a próxima. Código antes da primeira
+    /// declaração (imports) é ignorado. Com menos de dois trechos retorna
+    /// vazio: a assinatura do código inteiro já cobre o caso.
+    pub fn chunk_signatures(code: &str) -> Vec<ChunkSignature> {
+        let lines: Vec<&str> = code.lines().collect();
+        let starts: Vec<usize> = lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| Self::is_declaration(line))
+            .map(|(i, _)| i)
+            .collect();
+
+        if starts.len() < 2 {
+            return Vec::new();
+        }
+
+        starts
+            .iter()
+            .enumerate()
+            .filter_map(|(n, &start)| {
+                let end = starts.get(n + 1).copied().unwrap_or(lines.len());
+                let text = lines[start..end].join("\n");
+                if Self::normalize_code(&text).is_empty() {
+                    return None;
+                }
+                Some(ChunkSignature {
+                    signature: Self::compute_signature(&text),
+                    start_line: start as u32 + 1,
+                    end_line: end as u32,
+                })
+            })
+            .collect()
+    }
+
+    /// Trecho que contém `line`, ou o mais próximo dela.
+    pub fn nearest_chunk(chunks: &[ChunkSignature], line: u32) -> Option<&ChunkSignature> {
+        chunks.iter().min_by_key(|chunk| chunk.distance_to(line))
+    }
+
+    /// Se a linha inicia uma declaração de topo.
+    fn is_declaration(line: &str) -> bool {
+        if line.starts_with(char::is_whitespace) {
+            return false;
+        }
+
+        line.split_whitespace()
+            .find(|token| !DECLARATION_MODIFIERS.contains(token))
+            .map(|token| {
+                DECLARATION_KEYWORDS.iter().any(|keyword| {
+                    token == *keyword
+                        || token
+                            .strip_prefix(keyword)
+                            .is_some_and(|rest| rest.starts_with(['<', '(']))
+                })
+            })
+            .unwrap_or(false)
+    }
+
     /// Normaliza código removendo whitespace extra e comentários.
     pub fn normalize_code(code: &str) -> String {
         code.lines()
@@ -114,6 +666,43 @@ impl PatternMatcher {
         keywords
     }
 
+    /// Normaliza um texto de issue ou solução.
+    ///
+    /// Remove espaços nas pontas, colapsa whitespace interno e limita o texto
+    /// a `max_chars` caracteres. Textos cortados terminam em
+    /// [`TRUNCATED_MARKER`], de preferência após uma palavra completa.
+    pub fn normalize_text(text: &str, max_chars: usize) -> String {
+        let collapsed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if collapsed.chars().count() <= max_chars {
+            return collapsed;
+        }
+
+        let budget = max_chars.saturating_sub(TRUNCATED_MARKER.chars().count());
+        let cut: String = collapsed.chars().take(budget).collect();
+        let cut = match cut.rfind(' ') {
+            Some(pos) if pos > cut.len() / 2 => &cut[..pos],
+            _ => cut.as_str(),
+        };
+
+        format!("{}{}", cut.trim_end(), TRUNCATED_MARKER)
+    }
+
+    /// Chave para comparar descrições, ignorando caixa, pontuação e espaços.
+    ///
+    /// Descrições com a mesma chave dizem a mesma coisa.
+    pub fn text_key(text: &str) -> String {
+        text.split_whitespace()
+            .map(|word| {
+                word.chars()
+                    .filter(|c| c.is_alphanumeric())
+                    .flat_map(char::to_lowercase)
+                    .collect::<String>()
+            })
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     /// Calcula a similaridade entre dois códigos (0.0 - 1.0).
     pub fn similarity(code1: &str, code2: &str) -> f64 {
         let sig1 = Self::compute_signature(code1);
@@ -143,54 +732,229 @@ impl PatternMatcher {
     }
 
     /// Detecta a linguagem de programação do código.
+    ///
+    /// Atalho para [`Self::detect_language_with_confidence`] sem caminho.
     pub fn detect_language(code: &str) -> String {
-        let code_lower = code.to_lowercase();
+        Self::detect_language_with_confidence(code, None).0
+    }
 
-        // Rust
-        if code_lower.contains("fn ")
-            || code_lower.contains("let ")
-            || code_lower.contains("impl ")
-            || code_lower.contains("struct ")
-            || code_lower.contains("enum ")
+    /// Detecta a linguagem do código e a confiança da detecção (0.0 a 1.0).
+    ///
+    /// Em ordem: nome/extensão de `file_path`, shebang, JSON válido, formato
+    /// de configuração (TOML, INI, YAML) e, por fim, a tabela de tokens com
+    /// pesos. Abaixo de [`MIN_DETECTION_CONFIDENCE`] retorna "unknown".
+    pub fn detect_language_with_confidence(code: &str, file_path: Option<&str>) -> (String, f64) {
+        if let Some(language) =
+            file_path.and_then(|path| Self::language_from_file_name(Path::new(path)))
         {
-            return "rust".to_string();
+            return (language, 1.0);
+        }
+        if let Some(language) = Self::language_from_shebang(code) {
+            return (language.to_string(), 0.95);
         }
 
-        // Python
-        if code_lower.contains("def ")
-            || code_lower.contains("import ")
-            || code_lower.contains("class ")
-            || code_lower.contains("elif ")
+        let trimmed = code.trim();
+        if (trimmed.starts_with('{') || trimmed.starts_with('['))
+            && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
         {
-            return "python".to_string();
+            return ("json".to_string(), 0.95);
         }
 
-        // JavaScript/TypeScript
-        if code_lower.contains("const ")
-            || code_lower.contains("function ")
-            || code_lower.contains("=>")
-            || code_lower.contains("export ")
-        {
-            return "javascript".to_string();
+        let (language, confidence) =
+            Self::detect_config_format(code).unwrap_or_else(|| Self::score_tokens(code));
+        if confidence < MIN_DETECTION_CONFIDENCE {
+            return ("unknown".to_string(), confidence);
         }
+        (language.to_string(), confidence)
+    }
 
-        // Go
-        if code_lower.contains("func ")
-            || code_lower.contains("package ")
-            || code_lower.contains("go ")
-        {
-            return "go".to_string();
+    /// Infere a linguagem pelo nome do arquivo, aceitando também formatos
+    /// que não são código (Dockerfile, TOML, YAML...).
+    fn language_from_file_name(path: &Path) -> Option<String> {
+        if let Some(language) = Self::language_from_path(path) {
+            return Some(language);
         }
 
-        // Java
-        if code_lower.contains("public class")
-            || code_lower.contains("private ")
-            || code_lower.contains("static void main")
-        {
-            return "java".to_string();
+        let name = path.file_name()?.to_str()?.to_lowercase();
+        let stem = name.split('.').next().unwrap_or(&name);
+        if let Some((_, language)) = FILE_NAMES.iter().find(|(file, _)| *file == stem) {
+            return Some(language.to_string());
+        }
+
+        let ext = path.extension()?.to_str()?;
+        match Language::canonical(ext) {
+            OTHER | ANY => None,
+            language => Some(language.to_string()),
+        }
+    }
+
+    /// Linguagem indicada pelo shebang (`#!/usr/bin/env python3`).
+    fn language_from_shebang(code: &str) -> Option<&'static str> {
+        let line = code.lines().next()?.strip_prefix("#!")?;
+        let mut words = line.split_whitespace();
+        let mut program = words.next()?.rsplit('/').next()?;
+        if program == "env" {
+            program = words.find(|word| !word.starts_with('-'))?;
+        }
+        let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+
+        SHEBANG_INTERPRETERS
+            .iter()
+            .find(|(interpreter, _)| *interpreter == program)
+            .map(|(_, language)| *language)
+    }
+
+    /// Reconhece arquivos de configuração: toda linha é seção, par chave/valor
+    /// ou comentário.
+    ///
+    /// TOML e INI se distinguem pelos valores: TOML exige strings entre aspas
+    /// e tem arrays, tabelas inline e booleanos; INI aceita texto solto e
+    /// comentários com `;`.
+    fn detect_config_format(code: &str) -> Option<(&'static str, f64)> {
+        let (mut toml, mut ini, mut yaml) = (0u32, 0u32, 0u32);
+
+        for line in code.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with(';') {
+                ini += 2;
+            } else if line.starts_with("[[") && line.ends_with("]]") {
+                toml += 2;
+            } else if line.starts_with('[') && line.ends_with(']') {
+                toml += 1;
+                ini += 1;
+            } else if let Some((key, value)) = line.split_once('=') {
+                if !is_config_key(key.trim()) {
+                    return None;
+                }
+                match config_value_kind(value.trim()) {
+                    ConfigValue::Toml => toml += 1,
+                    ConfigValue::Bare => ini += 2,
+                    ConfigValue::Either => {
+                        toml += 1;
+                        ini += 1;
+                    }
+                }
+            } else if line == "---" || line.starts_with("- ") {
+                yaml += 1;
+            } else if let Some((key, _)) = line
+                .split_once(": ")
+                .or(line.strip_suffix(':').map(|key| (key, "")))
+            {
+                if !is_config_key(key) {
+                    return None;
+                }
+                yaml += 1;
+            } else {
+                return None;
+            }
+        }
+
+        // YAML não se mistura com pares `chave = valor`
+        if yaml > 0 && toml + ini > 0 {
+            return None;
+        }
+        let scores = [("toml", toml), ("ini", ini), ("yaml", yaml)];
+        let (language, best) = scores.iter().copied().max_by_key(|(_, score)| *score)?;
+        if best == 0 {
+            return None;
+        }
+        let runner_up = scores
+            .iter()
+            .filter(|(other, _)| *other != language)
+            .map(|(_, score)| *score)
+            .max()
+            .unwrap_or(0);
+        Some((language, confidence(best, runner_up, CONFIG_SATURATION)))
+    }
+
+    /// Pontua o código com [`LANGUAGE_SIGNALS`] e retorna a melhor linguagem.
+    fn score_tokens(code: &str) -> (&'static str, f64) {
+        let code_lower = code.to_lowercase();
+        let mut scores: Vec<(&'static str, u32)> = LANGUAGE_SIGNALS
+            .iter()
+            .map(|signals| {
+                let text = if signals.case_insensitive {
+                    code_lower.as_str()
+                } else {
+                    code
+                };
+                let score = signals
+                    .tokens
+                    .iter()
+                    .filter(|(token, _)| text.contains(token))
+                    .map(|(_, weight)| weight)
+                    .sum();
+                (signals.language, score)
+            })
+            .collect();
+
+        for &(superset, base) in LANGUAGE_SUPERSETS {
+            let score_of = |scores: &[(&str, u32)], language: &str| {
+                scores
+                    .iter()
+                    .find(|(l, _)| *l == language)
+                    .map_or(0, |(_, s)| *s)
+            };
+            let base_score = score_of(&scores, base);
+            if score_of(&scores, superset) > 0 {
+                for entry in scores.iter_mut() {
+                    if entry.0 == superset {
+                        entry.1 += base_score;
+                    } else if entry.0 == base {
+                        entry.1 = 0;
+                    }
+                }
+            }
         }
 
-        "unknown".to_string()
+        // Empates ficam com a linguagem que aparece primeiro na tabela
+        let (language, best) = scores
+            .iter()
+            .copied()
+            .rev()
+            .max_by_key(|(_, score)| *score)
+            .unwrap_or(("unknown", 0));
+        if best == 0 {
+            return ("unknown", 0.0);
+        }
+        let runner_up = scores
+            .iter()
+            .filter(|(other, _)| *other != language)
+            .map(|(_, score)| *score)
+            .max()
+            .unwrap_or(0);
+        (language, confidence(best, runner_up, TOKEN_SATURATION))
+    }
+
+    /// Infere a linguagem a partir da extensão do arquivo.
+    ///
+    /// Retorna `None` para extensões desconhecidas (arquivos que não são código).
+    pub fn language_from_path(path: &Path) -> Option<String> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+
+        let language = match ext.as_str() {
+            "rs" => "rust",
+            "py" | "pyi" => "python",
+            "js" | "jsx" | "mjs" | "cjs" => "javascript",
+            "ts" | "tsx" => "typescript",
+            "go" => "go",
+            "java" => "java",
+            "kt" | "kts" => "kotlin",
+            "c" | "h" => "c",
+            "cpp" | "cc" | "cxx" | "hpp" | "hh" => "cpp",
+            "cs" => "csharp",
+            "rb" => "ruby",
+            "php" => "php",
+            "swift" => "swift",
+            "scala" => "scala",
+            "sh" | "bash" | "zsh" => "shell",
+            "sql" => "sql",
+            _ => return None,
+        };
+
+        Some(language.to_string())
     }
 
     /// Categoriza o tipo de código.
@@ -235,6 +999,159 @@ impl PatternMatcher {
     }
 }
 
+/// Token do código usado nas assinaturas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CodeToken<'a> {
+    /// Identificador ou palavra reservada.
+    Word(&'a str),
+    /// Literal numérico.
+    Number(&'a str),
+    /// String ou caractere; o conteúdo é descartado.
+    Literal,
+    /// Operador ou pontuação.
+    Symbol(char),
+}
+
+type CodeChars<'a> = std::iter::Peekable<std::str::CharIndices<'a>>;
+
+/// Divide o código em tokens, sem comentários nem espaços.
+///
+/// Independe da linguagem: `//`, `/* */` e `#` abrem comentários (exceto
+/// `#[`, `#!` e `#"`, de atributos e raw strings do Rust); `"` e `` ` ``
+/// delimitam strings, e `'` só quando fecha na mesma linha (do contrário é
+/// um lifetime do Rust). Não precisa ser exato, só determinístico.
+fn tokenize(code: &str) -> Vec<CodeToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut chars = code.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, n)| n);
+        match c {
+            c if c.is_whitespace() => {}
+            '/' if next == Some('/') => skip_line(&mut chars),
+            '#' if !matches!(next, Some('[' | '!' | '"')) => skip_line(&mut chars),
+            '/' if next == Some('*') => {
+                chars.next();
+                let mut prev = ' ';
+                for (_, c) in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '"' | '`' => {
+                skip_literal(&mut chars, c, true);
+                tokens.push(CodeToken::Literal);
+            }
+            '\'' => {
+                let line = code[start + 1..].lines().next().unwrap_or("");
+                if closes_on_line(line) {
+                    skip_literal(&mut chars, '\'', false);
+                    tokens.push(CodeToken::Literal);
+                } else {
+                    tokens.push(CodeToken::Symbol('\''));
+                }
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some(&(i, n)) = chars.peek() {
+                    if !(n.is_alphanumeric() || n == '_') {
+                        break;
+                    }
+                    end = i + n.len_utf8();
+                    chars.next();
+                }
+                let word = &code[start..end];
+                tokens.push(if c.is_ascii_digit() {
+                    CodeToken::Number(word)
+                } else {
+                    CodeToken::Word(word)
+                });
+            }
+            c => tokens.push(CodeToken::Symbol(c)),
+        }
+    }
+
+    tokens
+}
+
+/// Avança até o fim da linha.
+fn skip_line(chars: &mut CodeChars<'_>) {
+    for (_, c) in chars.by_ref() {
+        if c == '\n' {
+            break;
+        }
+    }
+}
+
+/// Avança até a aspa que fecha o literal, respeitando escapes.
+fn skip_literal(chars: &mut CodeChars<'_>, quote: char, multiline: bool) {
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '\n' if !multiline => break,
+            c if c == quote => break,
+            _ => {}
+        }
+    }
+}
+
+/// Se a linha tem uma aspa simples não escapada.
+fn closes_on_line(line: &str) -> bool {
+    let mut escaped = false;
+    for c in line.chars() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\'' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Confiança de uma detecção: cresce com a pontuação até `saturation` e é
+/// proporcional à vantagem sobre a segunda colocada.
+fn confidence(best: u32, runner_up: u32, saturation: u32) -> f64 {
+    let strength = (best as f64 / saturation as f64).min(1.0);
+    let margin = best as f64 / (best + runner_up) as f64;
+    strength * margin
+}
+
+/// Tipo de valor numa linha `chave = valor`.
+enum ConfigValue {
+    /// Só é válido em TOML (string entre aspas, array, tabela, booleano).
+    Toml,
+    /// Texto solto, inválido em TOML.
+    Bare,
+    /// Válido nos dois formatos (números, datas).
+    Either,
+}
+
+fn config_value_kind(value: &str) -> ConfigValue {
+    if value.starts_with(['"', '\'', '[', '{']) || value == "true" || value == "false" {
+        ConfigValue::Toml
+    } else if value.parse::<f64>().is_ok()
+        || value
+            .chars()
+            .all(|c| c.is_ascii_digit() || "-:T.Z".contains(c))
+    {
+        ConfigValue::Either
+    } else {
+        ConfigValue::Bare
+    }
+}
+
+fn is_config_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '"'))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -250,10 +1167,136 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalize_text_collapses_whitespace() {
+        assert_eq!(
+            PatternMatcher::normalize_text("  SQL   injection\n\tin query  ", 200),
+            "SQL injection in query"
+        );
+    }
+
+    #[test]
+    fn test_normalize_text_truncates_at_word() {
+        let long = "word ".repeat(100);
+        let normalized = PatternMatcher::normalize_text(&long, 50);
+
+        assert!(normalized.chars().count() <= 50);
+        assert!(normalized.ends_with(TRUNCATED_MARKER));
+        assert!(normalized.starts_with("word word"));
+        assert!(!normalized.contains("wo…"));
+    }
+
+    #[test]
+    fn test_normalize_text_respects_char_boundaries() {
+        let long = "ação".repeat(30);
+        let normalized = PatternMatcher::normalize_text(&long, 20);
+        assert_eq!(normalized.chars().count(), 20);
+    }
+
+    #[test]
+    fn test_text_key_ignores_case_and_punctuation() {
+        assert_eq!(
+            PatternMatcher::text_key("SQL injection in query."),
+            PatternMatcher::text_key("  sql Injection  in query")
+        );
+        assert_ne!(
+            PatternMatcher::text_key("SQL injection"),
+            PatternMatcher::text_key("XSS injection")
+        );
+    }
+
+    #[test]
+    fn test_chunk_signatures_split_top_level_declarations() {
+        let code = "use std::io;\n\nfn first() {\n    let a = 1;\n}\n\npub fn second() {\n    let b = 2;\n}\n";
+        let chunks = PatternMatcher::chunk_signatures(code);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!((chunks[0].start_line, chunks[0].end_line), (3, 6));
+        assert_eq!((chunks[1].start_line, chunks[1].end_line), (7, 9));
+
+        // Editar uma função não muda a assinatura da outra
+        let edited = code.replace("let b = 2", "let b = 3");
+        let edited_chunks = PatternMatcher::chunk_signatures(&edited);
+        assert_eq!(chunks[0].signature, edited_chunks[0].signature);
+        assert_ne!(chunks[1].signature, edited_chunks[1].signature);
+
+        assert_eq!(
+            PatternMatcher::nearest_chunk(&chunks, 8)
+                .unwrap()
+                .start_line,
+            7
+        );
+        assert_eq!(
+            PatternMatcher::nearest_chunk(&chunks, 1)
+                .unwrap()
+                .start_line,
+            3
+        );
+    }
+
+    #[test]
+    fn test_chunk_signatures_single_declaration() {
+        assert!(PatternMatcher::chunk_signatures("fn main() {\n    run();\n}").is_empty());
+        assert!(PatternMatcher::chunk_signatures("let x = 1;").is_empty());
+    }
+
     #[test]
     fn test_compute_signature_different_code() {
         let code1 = "fn main() { println!(\"Hello\"); }";
-        let code2 = "fn main() { println!(\"World\"); }";
+        let code2 = "fn main() { eprintln!(\"Hello\"); }";
+
+        assert_ne!(
+            PatternMatcher::compute_signature(code1),
+            PatternMatcher::compute_signature(code2)
+        );
+    }
+
+    #[test]
+    fn test_compute_signature_ignores_comments_strings_and_layout() {
+        let code1 = "fn main() {\n    // saudação\n    println!(\"Hello\"); /* fim */\n}";
+        let code2 = "fn main() { println!('World'); } # outro comentário";
+
+        assert_eq!(
+            PatternMatcher::compute_signature(code1),
+            PatternMatcher::compute_signature(code2)
+        );
+        // Atributos do Rust não são comentários
+        assert_ne!(
+            PatternMatcher::compute_signature("#[test]\nfn a() {}"),
+            PatternMatcher::compute_signature("fn a() {}")
+        );
+    }
+
+    #[test]
+    fn test_rename_keeps_shape_signature() {
+        let original = "fn total(items: &[u32]) -> u32 {\n    let mut sum = 0;\n    for item in items {\n        sum += item;\n    }\n    sum\n}";
+        let renamed = "fn total(values: &[u32]) -> u32 {\n    let mut acc = 0;\n    for v in values {\n        acc += v;\n    }\n    acc\n}";
+        // Mesmos nomes em outra ordem de uso mudam a forma
+        let reordered = "fn total(items: &[u32]) -> u32 {\n    let mut sum = 0;\n    for item in items {\n        item += sum;\n    }\n    sum\n}";
+
+        assert_ne!(
+            PatternMatcher::compute_signature(original),
+            PatternMatcher::compute_signature(renamed)
+        );
+        assert_eq!(
+            PatternMatcher::compute_shape_signature(original),
+            PatternMatcher::compute_shape_signature(renamed)
+        );
+        assert_ne!(
+            PatternMatcher::compute_shape_signature(original),
+            PatternMatcher::compute_shape_signature(reordered)
+        );
+        // Identificadores sem diferenciar maiúsculas mantêm a assinatura exata
+        assert_eq!(
+            PatternMatcher::compute_signature("let Total = 1;"),
+            PatternMatcher::compute_signature("let total = 1;")
+        );
+    }
+
+    #[test]
+    fn test_lifetime_quote_does_not_hide_code() {
+        let code1 = "fn name(x: &'static str) -> &'static str { x }";
+        let code2 = "fn name(x: &'static str) -> &'static str { y }";
 
         assert_ne!(
             PatternMatcher::compute_signature(code1),
@@ -329,6 +1372,87 @@ mod tests {
         assert_eq!(PatternMatcher::detect_language(code), "javascript");
     }
 
+    #[test]
+    fn test_detect_language_samples() {
+        // (código, caminho, linguagem esperada)
+        let cases: &[(&str, Option<&str>, &str)] = &[
+            ("fn main() { let x = 5; }", None, "rust"),
+            ("use std::collections::HashMap;\n\npub fn count(words: &[&str]) -> HashMap<&str, usize> {\n    let mut counts = HashMap::new();\n    counts\n}", None, "rust"),
+            ("def main():\n    import os\n    print('hello')", None, "python"),
+            ("class User:\n    def __init__(self, name):\n        self.name = name\n", None, "python"),
+            ("const x = () => { console.log('hello'); }", None, "javascript"),
+            ("const express = require('express');\nmodule.exports = function (app) {};", None, "javascript"),
+            ("export interface User {\n  id: number;\n  name: string;\n}\n\nconst greet = (user: User): string => `hi ${user.name}`;", None, "typescript"),
+            ("function add(a: number, b: number): number {\n  return a + b;\n}", None, "typescript"),
+            ("package main\n\nimport \"fmt\"\n\nfunc main() {\n    msg := \"hi\"\n    fmt.Println(msg)\n}", None, "go"),
+            ("public class Main {\n    public static void main(String[] args) {\n        System.out.println(\"hi\");\n    }\n}", None, "java"),
+            ("#include <stdio.h>\n\nint main(void) {\n    printf(\"hi\\n\");\n    return 0;\n}", None, "c"),
+            ("#include <iostream>\n\nint main() {\n    std::cout << \"hi\" << std::endl;\n}", None, "cpp"),
+            ("using System;\n\nclass Program {\n    static void Main() {\n        Console.WriteLine(\"hi\");\n    }\n}", None, "csharp"),
+            ("data class User(val name: String)\n\nfun main() {\n    println(User(\"a\"))\n}", None, "kotlin"),
+            ("import Foundation\n\nfunc greet(_ name: String?) -> String {\n    guard let name = name else { return \"\" }\n    return name\n}", None, "swift"),
+            ("require 'json'\n\nclass User\n  attr_accessor :name\nend\n\n[1, 2].each do |n|\n  puts n\nend\n", None, "ruby"),
+            ("<?php\n\nclass User {\n    public function name() {\n        return $this->name;\n    }\n}", None, "php"),
+            ("#!/usr/bin/env bash\nset -e\necho done", None, "shell"),
+            ("#!/usr/bin/python3\nprint('hi')", None, "python"),
+            ("if [ -z \"$1\" ]; then\n  echo \"usage\"\n  exit 1\nfi\n", None, "shell"),
+            ("SELECT id, name FROM users WHERE active = 1 ORDER BY name;", None, "sql"),
+            ("create table users (id integer primary key, name text);", None, "sql"),
+            ("FROM rust:1.80\nWORKDIR /app\nCOPY . .\nRUN cargo build --release\nCMD [\"./app\"]", None, "dockerfile"),
+            ("[package]\nname = \"tetrad\"\nversion = \"0.1.0\"\n\n[dependencies]\nserde = { version = \"1\" }\n", None, "toml"),
+            ("[database]\nhost=localhost\nuser=admin\n; comment\nport=5432\n", None, "ini"),
+            ("name: ci\non: push\njobs:\n  build:\n    runs-on: ubuntu-latest\n", None, "yaml"),
+            ("{\"name\": \"tetrad\", \"version\": \"1.0\"}", None, "json"),
+            ("x = 1", Some("src/app.ts"), "typescript"),
+            ("anything", Some("docker/Dockerfile"), "dockerfile"),
+            ("key = value", Some("Cargo.toml"), "toml"),
+            ("This is a short note about the release.", None, "unknown"),
+            ("", None, "unknown"),
+        ];
+
+        for (code, path, expected) in cases {
+            let (language, confidence) =
+                PatternMatcher::detect_language_with_confidence(code, *path);
+            assert_eq!(
+                language, *expected,
+                "code: {code:?}, confidence: {confidence:.2}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_language_confidence() {
+        let (_, from_path) =
+            PatternMatcher::detect_language_with_confidence("x", Some("src/main.rs"));
+        assert_eq!(from_path, 1.0);
+
+        // Poucos sinais: melhor "unknown" do que um palpite
+        let (language, confidence) =
+            PatternMatcher::detect_language_with_confidence("let x = 1", None);
+        assert_eq!(language, "unknown");
+        assert!(confidence < MIN_DETECTION_CONFIDENCE);
+    }
+
+    #[test]
+    fn test_language_from_path() {
+        assert_eq!(
+            PatternMatcher::language_from_path(Path::new("src/main.rs")),
+            Some("rust".to_string())
+        );
+        assert_eq!(
+            PatternMatcher::language_from_path(Path::new("app/index.TSX")),
+            Some("typescript".to_string())
+        );
+        assert_eq!(
+            PatternMatcher::language_from_path(Path::new("README.md")),
+            None
+        );
+        assert_eq!(
+            PatternMatcher::language_from_path(Path::new("Makefile")),
+            None
+        );
+    }
+
     #[test]
     fn test_categorize_code_security() {
         let code = "execute_query(format!(\"SELECT * WHERE password = {}\", input));";
@@ -0,0 +1,1198 @@
+//! Backend Postgres do ReasoningBank (feature `postgres`).
+//!
+//! Permite que uma equipe compartilhe o mesmo banco de patterns. O esquema é
+//! equivalente ao do SQLite; datas continuam como texto RFC 3339 para que os
+//! dois backends ordenem e comparem da mesma forma.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use chrono::Utc;
+use postgres::{Client, NoTls, Row};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+use crate::types::language::Language;
+use crate::types::responses::Decision;
+use crate::TetradResult;
+
+use super::bank::{
+    ArtifactKind, LanguageStats, Pattern, PatternFilter, PatternSort, PatternType, StatsBucket,
+};
+use super::metrics::MetricCounters;
+use super::patterns::SIGNATURE_VERSION;
+use super::store::{
+    decision_from_str, decision_to_str, vote_from_str, vote_to_str, DecisionRecord, PatternStore,
+    PeriodTally, StoredVote, TrajectoryRecord, VoteTally, ONE_OFF_GOOD_PATTERN, TABLES,
+};
+use super::usage::{MonthlyUsage, UsageLimits};
+
+/// Chave do advisory lock que serializa as migrações entre instâncias.
+const MIGRATION_LOCK_KEY: i64 = 0x7e7a_d0b5;
+
+/// Patterns em um banco Postgres compartilhado.
+///
+/// O cliente `postgres` é síncrono e roda seu próprio runtime; por isso cada
+/// chamada é executada fora do runtime do servidor MCP (ver
+/// [`outside_runtime`]). As conexões não usam TLS.
+pub struct PostgresStore {
+    client: RefCell<Client>,
+}
+
+impl PostgresStore {
+    /// Conecta pela connection string (URL ou `key=value`) e aplica as
+    /// migrações.
+    pub fn connect(url: &str) -> TetradResult<Self> {
+        Self::from_config(url.parse()?)
+    }
+
+    /// Conecta com uma configuração já montada e aplica as migrações.
+    pub fn from_config(config: postgres::Config) -> TetradResult<Self> {
+        let client = outside_runtime(move || config.connect(NoTls))?;
+        let store = Self {
+            client: RefCell::new(client),
+        };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    /// Cria ou atualiza o esquema.
+    ///
+    /// Várias instâncias podem subir juntas contra o mesmo banco; o advisory
+    /// lock evita que disputem o `CREATE TABLE`.
+    fn migrate(&self) -> TetradResult<()> {
+        self.run(|client| {
+            let mut tx = client.transaction()?;
+            tx.execute("SELECT pg_advisory_xact_lock($1)", &[&MIGRATION_LOCK_KEY])?;
+            tx.batch_execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS patterns (
+                    id BIGSERIAL PRIMARY KEY,
+                    pattern_type TEXT NOT NULL,
+                    code_signature TEXT NOT NULL,
+                    language TEXT NOT NULL,
+                    issue_category TEXT NOT NULL,
+                    description TEXT NOT NULL,
+                    solution TEXT,
+                    success_count INTEGER NOT NULL DEFAULT 0,
+                    failure_count INTEGER NOT NULL DEFAULT 0,
+                    confidence DOUBLE PRECISION NOT NULL DEFAULT 0.5,
+                    last_seen TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    artifact_kind TEXT NOT NULL DEFAULT 'code',
+                    shape_signature TEXT,
+                    signature_version INTEGER NOT NULL DEFAULT 0,
+                    UNIQUE(code_signature, issue_category)
+                );
+
+                CREATE TABLE IF NOT EXISTS trajectories (
+                    id BIGSERIAL PRIMARY KEY,
+                    pattern_id BIGINT REFERENCES patterns(id),
+                    request_id TEXT NOT NULL,
+                    code_hash TEXT NOT NULL,
+                    initial_score INTEGER,
+                    final_score INTEGER,
+                    loops_to_consensus INTEGER,
+                    was_successful BOOLEAN,
+                    timestamp TEXT NOT NULL,
+                    decision TEXT
+                );
+
+                CREATE TABLE IF NOT EXISTS votes (
+                    id BIGSERIAL PRIMARY KEY,
+                    trajectory_id BIGINT NOT NULL REFERENCES trajectories(id) ON DELETE CASCADE,
+                    executor TEXT NOT NULL,
+                    vote TEXT NOT NULL,
+                    score INTEGER NOT NULL,
+                    reasoning TEXT NOT NULL DEFAULT '',
+                    issues TEXT NOT NULL DEFAULT '[]',
+                    suggestions TEXT NOT NULL DEFAULT '[]'
+                );
+
+                -- Colunas adicionadas depois da primeira versão do esquema
+                ALTER TABLE patterns ADD COLUMN IF NOT EXISTS
+                    artifact_kind TEXT NOT NULL DEFAULT 'code';
+                ALTER TABLE trajectories ADD COLUMN IF NOT EXISTS decision TEXT;
+                -- Patterns anteriores às assinaturas por tokens ficam legados
+                -- (versão 0, sem forma): o código original não é guardado
+                ALTER TABLE patterns ADD COLUMN IF NOT EXISTS shape_signature TEXT;
+                ALTER TABLE patterns ADD COLUMN IF NOT EXISTS
+                    signature_version INTEGER NOT NULL DEFAULT 0;
+
+                CREATE INDEX IF NOT EXISTS idx_patterns_signature ON patterns(code_signature);
+                CREATE INDEX IF NOT EXISTS idx_patterns_category ON patterns(issue_category);
+                CREATE INDEX IF NOT EXISTS idx_patterns_type ON patterns(pattern_type);
+                CREATE INDEX IF NOT EXISTS idx_patterns_artifact ON patterns(artifact_kind);
+                CREATE INDEX IF NOT EXISTS idx_patterns_shape ON patterns(shape_signature);
+                CREATE INDEX IF NOT EXISTS idx_trajectories_pattern ON trajectories(pattern_id);
+                CREATE INDEX IF NOT EXISTS idx_trajectories_hash ON trajectories(code_hash);
+                CREATE INDEX IF NOT EXISTS idx_votes_trajectory ON votes(trajectory_id);
+                CREATE INDEX IF NOT EXISTS idx_votes_executor ON votes(executor);
+
+                CREATE TABLE IF NOT EXISTS pattern_signatures (
+                    sub_signature TEXT NOT NULL,
+                    pattern_id BIGINT NOT NULL,
+                    PRIMARY KEY (sub_signature, pattern_id)
+                );
+                CREATE INDEX IF NOT EXISTS idx_pattern_signatures_pattern ON pattern_signatures(pattern_id);
+
+                CREATE TABLE IF NOT EXISTS usage (
+                    month TEXT PRIMARY KEY,
+                    evaluations BIGINT NOT NULL DEFAULT 0,
+                    estimated_cost DOUBLE PRECISION NOT NULL DEFAULT 0
+                );
+
+                CREATE TABLE IF NOT EXISTS good_pattern_summary (
+                    language TEXT PRIMARY KEY,
+                    success_count BIGINT NOT NULL DEFAULT 0,
+                    last_collapsed TEXT NOT NULL
+                );
+
+                CREATE TABLE IF NOT EXISTS metrics (
+                    evaluation_type TEXT PRIMARY KEY,
+                    evaluations BIGINT NOT NULL DEFAULT 0,
+                    passes BIGINT NOT NULL DEFAULT 0,
+                    revises BIGINT NOT NULL DEFAULT 0,
+                    blocks BIGINT NOT NULL DEFAULT 0,
+                    score_sum BIGINT NOT NULL DEFAULT 0,
+                    recertifications BIGINT NOT NULL DEFAULT 0
+                );
+            "#,
+            )?;
+
+            // Patterns manuais derivam a assinatura da descrição, que não mudou
+            tx.execute(
+                "UPDATE patterns SET signature_version = $1
+                 WHERE signature_version = 0 AND code_signature LIKE 'manual:%'",
+                &[&(SIGNATURE_VERSION as i32)],
+            )?;
+
+            // Linguagens gravadas antes da normalização ("Rust", "rs") viram ids canônicos
+            let languages: Vec<String> = tx
+                .query("SELECT DISTINCT language FROM patterns", &[])?
+                .iter()
+                .map(|row| row.get(0))
+                .collect();
+            for language in languages {
+                let canonical = Language::canonical(&language);
+                if canonical != language {
+                    tx.execute(
+                        "UPDATE patterns SET language = $1 WHERE language = $2",
+                        &[&canonical, &language],
+                    )?;
+                }
+            }
+
+            tx.commit()
+        })
+    }
+
+    /// Executa `f` com o cliente, fora do runtime Tokio.
+    fn run<T: Send>(
+        &self,
+        f: impl FnOnce(&mut Client) -> Result<T, postgres::Error> + Send,
+    ) -> TetradResult<T> {
+        let mut client = self.client.borrow_mut();
+        let client: &mut Client = &mut client;
+        Ok(outside_runtime(move || f(client))?)
+    }
+
+    fn query_patterns(
+        &self,
+        sql: &str,
+        params: &[&(dyn postgres::types::ToSql + Sync)],
+    ) -> TetradResult<Vec<Pattern>> {
+        let rows = self.run(|client| client.query(sql, params))?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| pattern_from_row(row).ok())
+            .collect())
+    }
+}
+
+/// Executa `f` fora do contexto do runtime Tokio atual.
+///
+/// O cliente síncrono faz `block_on` no próprio runtime, o que entra em
+/// pânico dentro de outro runtime. No runtime multi-thread o
+/// `block_in_place` libera a thread; no current-thread `f` roda em uma
+/// thread auxiliar.
+fn outside_runtime<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(f)
+        }
+        Ok(_) => std::thread::scope(|scope| {
+            scope
+                .spawn(f)
+                .join()
+                .expect("postgres worker thread panicked")
+        }),
+        Err(_) => f(),
+    }
+}
+
+impl PatternStore for PostgresStore {
+    fn backend(&self) -> &'static str {
+        "postgres"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn begin_write(&mut self) -> TetradResult<()> {
+        self.run(|client| client.batch_execute("BEGIN"))
+    }
+
+    fn commit(&mut self) -> TetradResult<()> {
+        self.run(|client| client.batch_execute("COMMIT"))
+    }
+
+    fn rollback(&mut self) {
+        let _ = self.run(|client| client.batch_execute("ROLLBACK"));
+    }
+
+    fn find_by_signature(&self, signature: &str, kind: ArtifactKind) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS} FROM patterns WHERE code_signature = $1 AND artifact_kind = $2"
+            ),
+            &[&signature, &kind.to_string()],
+        )
+    }
+
+    fn find_by_shape(&self, shape: &str, kind: ArtifactKind) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS} FROM patterns
+                 WHERE shape_signature = $1 AND artifact_kind = $2 AND signature_version = $3"
+            ),
+            &[&shape, &kind.to_string(), &(SIGNATURE_VERSION as i32)],
+        )
+    }
+
+    fn find_by_sub_signature(
+        &self,
+        sub_signature: &str,
+        kind: ArtifactKind,
+    ) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS} FROM patterns
+                 WHERE artifact_kind = $1
+                   AND id IN (SELECT pattern_id FROM pattern_signatures WHERE sub_signature = $2)"
+            ),
+            &[&kind.to_string(), &sub_signature],
+        )
+    }
+
+    fn find_by_keyword(
+        &self,
+        keyword: &str,
+        language: &str,
+        kind: ArtifactKind,
+    ) -> TetradResult<Vec<Pattern>> {
+        let keyword_pattern = format!("%{}%", keyword);
+
+        // ILIKE: o LIKE do SQLite não diferencia maiúsculas
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS}
+                 FROM patterns
+                 WHERE (language = $1 OR language = 'any')
+                   AND artifact_kind = $2
+                   AND (issue_category ILIKE $3 OR description ILIKE $3)
+                 ORDER BY confidence DESC
+                 LIMIT 10"
+            ),
+            &[&language, &kind.to_string(), &keyword_pattern],
+        )
+    }
+
+    fn insert_trajectory(&mut self, trajectory: &TrajectoryRecord<'_>) -> TetradResult<i64> {
+        let initial_score = trajectory.initial_score as i32;
+        let final_score = trajectory.final_score as i32;
+        let loops = trajectory.loops_to_consensus as i32;
+        let decision = decision_to_str(trajectory.decision);
+
+        let row = self.run(|client| {
+            client.query_one(
+                "INSERT INTO trajectories (pattern_id, request_id, code_hash, initial_score,
+                                           final_score, loops_to_consensus, was_successful,
+                                           timestamp, decision)
+                 VALUES (NULL, $1, $2, $3, $4, $5, $6, $7, $8)
+                 RETURNING id",
+                &[
+                    &trajectory.request_id,
+                    &trajectory.code_hash,
+                    &initial_score,
+                    &final_score,
+                    &loops,
+                    &trajectory.was_successful,
+                    &trajectory.timestamp,
+                    &decision,
+                ],
+            )
+        })?;
+        Ok(row.get(0))
+    }
+
+    fn insert_votes(&mut self, trajectory_id: i64, votes: &[StoredVote]) -> TetradResult<()> {
+        let rows = votes
+            .iter()
+            .map(|vote| {
+                Ok((
+                    vote_to_str(vote.vote),
+                    vote.score as i32,
+                    serde_json::to_string(&vote.issues)?,
+                    serde_json::to_string(&vote.suggestions)?,
+                ))
+            })
+            .collect::<TetradResult<Vec<_>>>()?;
+
+        self.run(|client| {
+            let stmt = client.prepare(
+                "INSERT INTO votes (trajectory_id, executor, vote, score, reasoning, issues,
+                                    suggestions)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            )?;
+            for (vote, (kind, score, issues, suggestions)) in votes.iter().zip(&rows) {
+                client.execute(
+                    &stmt,
+                    &[
+                        &trajectory_id,
+                        &vote.executor,
+                        kind,
+                        score,
+                        &vote.reasoning,
+                        issues,
+                        suggestions,
+                    ],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    fn votes_for_trajectory(&self, trajectory_id: i64) -> TetradResult<Vec<StoredVote>> {
+        let rows = self.run(|client| {
+            client.query(
+                "SELECT executor, vote, score, reasoning, issues, suggestions FROM votes
+                 WHERE trajectory_id = $1
+                 ORDER BY id",
+                &[&trajectory_id],
+            )
+        })?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                Some(StoredVote {
+                    executor: row.get(0),
+                    vote: vote_from_str(row.get(1))?,
+                    score: row.get::<_, i32>(2).clamp(0, 100) as u8,
+                    reasoning: row.get(3),
+                    issues: serde_json::from_str(row.get(4)).unwrap_or_default(),
+                    suggestions: serde_json::from_str(row.get(5)).unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    fn latest_divergent(
+        &self,
+        code_hash: &str,
+        since: &str,
+        decision: Decision,
+    ) -> TetradResult<Option<(String, Decision)>> {
+        let decision = decision_to_str(decision);
+        let row = self.run(|client| {
+            client.query_opt(
+                "SELECT request_id, decision FROM trajectories
+                 WHERE code_hash = $1 AND timestamp >= $2 AND decision IS NOT NULL AND decision <> $3
+                 ORDER BY id DESC LIMIT 1",
+                &[&code_hash, &since, &decision],
+            )
+        })?;
+
+        Ok(row.and_then(|row| {
+            let request_id: String = row.get(0);
+            let decision: String = row.get(1);
+            decision_from_str(&decision).map(|decision| (request_id, decision))
+        }))
+    }
+
+    fn decision_history(&self) -> TetradResult<Vec<DecisionRecord>> {
+        let rows = self.run(|client| {
+            client.query(
+                "SELECT code_hash, request_id, decision, timestamp FROM trajectories
+                 WHERE decision IS NOT NULL
+                 ORDER BY code_hash, id",
+                &[],
+            )
+        })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DecisionRecord {
+                code_hash: row.get(0),
+                request_id: row.get(1),
+                decision: row.get(2),
+                timestamp: row.get(3),
+            })
+            .collect())
+    }
+
+    fn record_outcome(
+        &mut self,
+        signature: &str,
+        category: &str,
+        was_successful: bool,
+        now: &str,
+    ) -> TetradResult<bool> {
+        let (success, failure): (i32, i32) = if was_successful { (1, 0) } else { (0, 1) };
+        let updated = self.run(|client| {
+            client.execute(
+                "UPDATE patterns
+                 SET success_count = success_count + $1,
+                     failure_count = failure_count + $2,
+                     last_seen = $3,
+                     confidence = CAST(success_count + $1 AS DOUBLE PRECISION)
+                                  / (success_count + failure_count + 1)
+                 WHERE code_signature = $4 AND issue_category = $5",
+                &[&success, &failure, &now, &signature, &category],
+            )
+        })?;
+
+        Ok(updated > 0)
+    }
+
+    fn record_good_outcome(&mut self, signature: &str, now: &str) -> TetradResult<bool> {
+        let updated = self.run(|client| {
+            client.execute(
+                "UPDATE patterns
+                 SET success_count = success_count + 1,
+                     pattern_type = 'good_pattern',
+                     last_seen = $1,
+                     confidence = CAST(success_count + 1 AS DOUBLE PRECISION)
+                                  / (success_count + failure_count + 1)
+                 WHERE code_signature = $2 AND issue_category = 'success'",
+                &[&now, &signature],
+            )
+        })?;
+
+        Ok(updated > 0)
+    }
+
+    fn record_good_shape(
+        &mut self,
+        shape: &str,
+        language: &str,
+        kind: ArtifactKind,
+        now: &str,
+    ) -> TetradResult<bool> {
+        let kind = kind.to_string();
+        let updated = self.run(|client| {
+            client.execute(
+                "UPDATE patterns
+                 SET success_count = success_count + 1,
+                     last_seen = $1,
+                     confidence = CAST(success_count + 1 AS DOUBLE PRECISION)
+                                  / (success_count + failure_count + 1)
+                 WHERE id = (SELECT id FROM patterns
+                             WHERE shape_signature = $2 AND language = $3 AND artifact_kind = $4
+                               AND issue_category = 'success' AND pattern_type = 'good_pattern'
+                             ORDER BY id LIMIT 1)",
+                &[&now, &shape, &language, &kind],
+            )
+        })?;
+
+        Ok(updated > 0)
+    }
+
+    fn insert_pattern(&mut self, pattern: &Pattern) -> TetradResult<i64> {
+        let pattern_type = pattern.pattern_type.to_string();
+        let last_seen = pattern.last_seen.to_rfc3339();
+        let created_at = pattern.created_at.to_rfc3339();
+        let artifact_kind = pattern.artifact_kind.to_string();
+        let signature_version = pattern.signature_version as i32;
+
+        let row = self.run(|client| {
+            client.query_one(
+                "INSERT INTO patterns (pattern_type, code_signature, language, issue_category,
+                                       description, solution, success_count, failure_count,
+                                       confidence, last_seen, created_at, artifact_kind,
+                                       shape_signature, signature_version)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                 RETURNING id",
+                &[
+                    &pattern_type,
+                    &pattern.code_signature,
+                    &pattern.language,
+                    &pattern.issue_category,
+                    &pattern.description,
+                    &pattern.solution,
+                    &pattern.success_count,
+                    &pattern.failure_count,
+                    &pattern.confidence,
+                    &last_seen,
+                    &created_at,
+                    &artifact_kind,
+                    &pattern.shape_signature,
+                    &signature_version,
+                ],
+            )
+        })?;
+
+        Ok(row.get(0))
+    }
+
+    fn find_pattern(&self, signature: &str, category: &str) -> TetradResult<Option<Pattern>> {
+        let sql = format!(
+            "SELECT {PATTERN_COLUMNS} FROM patterns
+             WHERE code_signature = $1 AND issue_category = $2"
+        );
+        let row = self.run(|client| client.query_opt(&sql, &[&signature, &category]))?;
+
+        Ok(row.map(|row| pattern_from_row(&row)).transpose()?)
+    }
+
+    fn link_sub_signature(&mut self, sub_signature: &str, pattern_id: i64) -> TetradResult<()> {
+        self.run(|client| {
+            client.execute(
+                "INSERT INTO pattern_signatures (sub_signature, pattern_id) VALUES ($1, $2)
+                 ON CONFLICT DO NOTHING",
+                &[&sub_signature, &pattern_id],
+            )
+        })?;
+        Ok(())
+    }
+
+    fn update_description(
+        &mut self,
+        signature: &str,
+        category: &str,
+        description: &str,
+    ) -> TetradResult<()> {
+        self.run(|client| {
+            client.execute(
+                "UPDATE patterns SET description = $1
+                 WHERE code_signature = $2 AND issue_category = $3",
+                &[&description, &signature, &category],
+            )
+        })?;
+        Ok(())
+    }
+
+    fn merge_counts(&mut self, pattern: &Pattern) -> TetradResult<()> {
+        let last_seen = pattern.last_seen.to_rfc3339();
+
+        self.run(|client| {
+            client.execute(
+                "UPDATE patterns
+                 SET success_count = success_count + $1,
+                     failure_count = failure_count + $2,
+                     last_seen = GREATEST(last_seen, $3),
+                     confidence = CAST(success_count + $1 AS DOUBLE PRECISION)
+                                  / (success_count + failure_count + $1 + $2)
+                 WHERE code_signature = $4 AND issue_category = $5",
+                &[
+                    &pattern.success_count,
+                    &pattern.failure_count,
+                    &last_seen,
+                    &pattern.code_signature,
+                    &pattern.issue_category,
+                ],
+            )
+        })?;
+        Ok(())
+    }
+
+    fn top_patterns(&self, pattern_type: PatternType, limit: usize) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS}
+                 FROM patterns
+                 WHERE pattern_type = $1
+                 ORDER BY (success_count + failure_count) DESC, confidence DESC, id
+                 LIMIT $2"
+            ),
+            &[&pattern_type.to_string(), &(limit as i64)],
+        )
+    }
+
+    fn problematic_categories(&self) -> TetradResult<HashMap<String, usize>> {
+        let rows = self.run(|client| {
+            client.query(
+                "SELECT issue_category, COUNT(*) AS count
+                 FROM patterns
+                 WHERE pattern_type = 'anti_pattern'
+                 GROUP BY issue_category
+                 ORDER BY count DESC",
+                &[],
+            )
+        })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get(0), row.get::<_, i64>(1) as usize))
+            .collect())
+    }
+
+    fn language_stats(&self) -> TetradResult<HashMap<String, LanguageStats>> {
+        let rows = self.run(|client| {
+            // GoodPatterns recolhidos contam como patterns de confiança 1.0
+            client.query(
+                "SELECT language,
+                        SUM(total)::BIGINT,
+                        (SUM(good) / SUM(total))::DOUBLE PRECISION,
+                        (SUM(score) / SUM(total))::DOUBLE PRECISION
+                 FROM (
+                     SELECT language,
+                            COUNT(*)::DOUBLE PRECISION AS total,
+                            SUM(CASE WHEN pattern_type = 'good_pattern' THEN 1.0 ELSE 0.0 END)::DOUBLE PRECISION AS good,
+                            SUM(confidence * 100) AS score
+                     FROM patterns
+                     GROUP BY language
+                     UNION ALL
+                     SELECT language,
+                            success_count::DOUBLE PRECISION,
+                            success_count::DOUBLE PRECISION,
+                            success_count * 100.0::DOUBLE PRECISION
+                     FROM good_pattern_summary
+                     WHERE success_count > 0
+                 ) AS stats
+                 GROUP BY language",
+                &[],
+            )
+        })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get(0),
+                    LanguageStats {
+                        total_evaluations: row.get::<_, i64>(1) as usize,
+                        success_rate: row.get(2),
+                        avg_score: row.get(3),
+                    },
+                )
+            })
+            .collect())
+    }
+
+    fn average_loops_to_consensus(&self) -> TetradResult<f64> {
+        let row = self.run(|client| {
+            client.query_one(
+                "SELECT AVG(loops_to_consensus)::DOUBLE PRECISION FROM trajectories
+                 WHERE was_successful",
+                &[],
+            )
+        })?;
+
+        Ok(row.get::<_, Option<f64>>(0).unwrap_or(0.0))
+    }
+
+    fn count_patterns(&self) -> TetradResult<usize> {
+        let row = self.run(|client| client.query_one("SELECT COUNT(*) FROM patterns", &[]))?;
+        Ok(row.get::<_, i64>(0) as usize)
+    }
+
+    fn count_trajectories(&self) -> TetradResult<usize> {
+        let row = self.run(|client| client.query_one("SELECT COUNT(*) FROM trajectories", &[]))?;
+        Ok(row.get::<_, i64>(0) as usize)
+    }
+
+    fn vote_tallies(&self) -> TetradResult<Vec<VoteTally>> {
+        let rows = self.run(|client| {
+            client.query(
+                "SELECT v.executor, v.vote, t.decision, COUNT(*), SUM(v.score)
+                 FROM votes v JOIN trajectories t ON t.id = v.trajectory_id
+                 GROUP BY v.executor, v.vote, t.decision",
+                &[],
+            )
+        })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| VoteTally {
+                executor: row.get(0),
+                vote: row.get(1),
+                decision: row.get(2),
+                count: row.get::<_, i64>(3) as usize,
+                score_sum: row.get(4),
+            })
+            .collect())
+    }
+
+    fn period_tallies(
+        &self,
+        since: Option<&str>,
+        bucket: StatsBucket,
+    ) -> TetradResult<Vec<PeriodTally>> {
+        // Timestamps RFC 3339 em UTC: os 10 primeiros caracteres são a data.
+        // date_trunc('week') começa a semana na segunda-feira
+        let period = match bucket {
+            StatsBucket::Day => "substr(t.timestamp, 1, 10)",
+            StatsBucket::Week => {
+                "to_char(date_trunc('week', substr(t.timestamp, 1, 10)::DATE), 'YYYY-MM-DD')"
+            }
+        };
+        let rows = self.run(|client| {
+            client.query(
+                &format!(
+                    "SELECT {period} AS period, COUNT(*),
+                            SUM(CASE WHEN t.was_successful THEN 1 ELSE 0 END)::BIGINT,
+                            SUM(CASE WHEN t.decision = 'block' THEN 1 ELSE 0 END)::BIGINT,
+                            COALESCE(SUM(t.final_score), 0)::BIGINT,
+                            COALESCE(SUM(t.loops_to_consensus), 0)::BIGINT,
+                            COALESCE(SUM(v.votes), 0)::BIGINT,
+                            COALESCE(SUM(v.score_sum), 0)::BIGINT
+                     FROM trajectories t
+                     LEFT JOIN (SELECT trajectory_id, COUNT(*) AS votes, SUM(score) AS score_sum
+                                FROM votes GROUP BY trajectory_id) v ON v.trajectory_id = t.id
+                     WHERE $1::TEXT IS NULL OR t.timestamp >= $1
+                     GROUP BY period
+                     ORDER BY period"
+                ),
+                &[&since],
+            )
+        })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| PeriodTally {
+                period: row.get(0),
+                evaluations: row.get::<_, i64>(1) as usize,
+                successes: row.get::<_, i64>(2) as usize,
+                blocks: row.get::<_, i64>(3) as usize,
+                score_sum: row.get(4),
+                loops_sum: row.get(5),
+                votes: row.get::<_, i64>(6) as usize,
+                vote_score_sum: row.get(7),
+            })
+            .collect())
+    }
+
+    fn count_legacy_patterns(&self) -> TetradResult<usize> {
+        let row = self.run(|client| {
+            client.query_one(
+                "SELECT COUNT(*) FROM patterns WHERE signature_version <> $1",
+                &[&(SIGNATURE_VERSION as i32)],
+            )
+        })?;
+        Ok(row.get::<_, i64>(0) as usize)
+    }
+
+    fn pattern_texts(&self) -> TetradResult<Vec<(i64, String, Option<String>)>> {
+        let rows =
+            self.run(|client| client.query("SELECT id, description, solution FROM patterns", &[]))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get(0), row.get(1), row.get(2)))
+            .collect())
+    }
+
+    fn update_texts(
+        &mut self,
+        id: i64,
+        description: &str,
+        solution: Option<&str>,
+    ) -> TetradResult<()> {
+        self.run(|client| {
+            client.execute(
+                "UPDATE patterns SET description = $1, solution = $2 WHERE id = $3",
+                &[&description, &solution, &id],
+            )
+        })?;
+        Ok(())
+    }
+
+    fn merge_duplicates(&mut self) -> TetradResult<usize> {
+        // A constraint UNIQUE já impede duplicatas; mantido por paridade
+        // com o SQLite, cujos bancos antigos podem tê-las
+        let merged = self.run(|client| {
+            let duplicates = client.query(
+                "SELECT p1.id, p2.id
+                 FROM patterns p1
+                 JOIN patterns p2 ON p1.code_signature = p2.code_signature
+                                  AND p1.issue_category = p2.issue_category
+                                  AND p1.id < p2.id",
+                &[],
+            )?;
+
+            for row in &duplicates {
+                let keep_id: i64 = row.get(0);
+                let remove_id: i64 = row.get(1);
+                client.execute(
+                    "UPDATE patterns
+                     SET success_count = success_count + (SELECT success_count FROM patterns WHERE id = $1),
+                         failure_count = failure_count + (SELECT failure_count FROM patterns WHERE id = $1)
+                     WHERE id = $2",
+                    &[&remove_id, &keep_id],
+                )?;
+                client.execute("DELETE FROM patterns WHERE id = $1", &[&remove_id])?;
+            }
+
+            Ok(duplicates.len())
+        })?;
+
+        Ok(merged)
+    }
+
+    fn prune_low_quality(&mut self) -> TetradResult<usize> {
+        let cutoff = (Utc::now() - chrono::Duration::days(30))
+            .format("%Y-%m-%dT%H:%M:%S+00:00")
+            .to_string();
+
+        let pruned = self.run(|client| {
+            client.execute(
+                "DELETE FROM patterns
+                 WHERE confidence < 0.3
+                   AND (success_count + failure_count) < 3
+                   AND created_at < $1",
+                &[&cutoff],
+            )
+        })?;
+
+        Ok(pruned as usize)
+    }
+
+    fn reinforce_high_value(&mut self) -> TetradResult<usize> {
+        let reinforced = self.run(|client| {
+            client.execute(
+                "UPDATE patterns
+                 SET confidence = LEAST(confidence * 1.05, 1.0)
+                 WHERE (success_count + failure_count) > 10
+                   AND confidence > 0.7",
+                &[],
+            )
+        })?;
+
+        Ok(reinforced as usize)
+    }
+
+    fn recalculate_confidences(&mut self) -> TetradResult<()> {
+        self.run(|client| {
+            client.execute(
+                "UPDATE patterns
+                 SET confidence = CASE
+                     WHEN (success_count + failure_count) = 0 THEN 0.5
+                     ELSE CAST(success_count AS DOUBLE PRECISION) / (success_count + failure_count)
+                 END,
+                 pattern_type = CASE
+                     WHEN CAST(success_count AS DOUBLE PRECISION) / (success_count + failure_count + 0.001) > 0.8 THEN 'good_pattern'
+                     WHEN CAST(failure_count AS DOUBLE PRECISION) / (success_count + failure_count + 0.001) > 0.8 THEN 'anti_pattern'
+                     ELSE 'ambiguous'
+                 END",
+                &[],
+            )
+        })?;
+
+        Ok(())
+    }
+
+    fn collapse_good_patterns(
+        &mut self,
+        keep: Option<usize>,
+        before: Option<&str>,
+    ) -> TetradResult<usize> {
+        if keep.is_none() && before.is_none() {
+            return Ok(0);
+        }
+
+        let keep = keep.map(|keep| keep as i64);
+        let now = Utc::now().to_rfc3339();
+        let collapsed = self.run(|client| {
+            // Posição 1 = visto mais recentemente na linguagem
+            let candidates = client.query(
+                &format!(
+                    "SELECT id, language FROM (
+                         SELECT id, language, last_seen,
+                                ROW_NUMBER() OVER (
+                                    PARTITION BY language ORDER BY last_seen DESC, id DESC
+                                ) AS position
+                         FROM patterns
+                         WHERE {ONE_OFF_GOOD_PATTERN}
+                     ) AS ranked
+                     WHERE ($1::TEXT IS NOT NULL AND last_seen < $1)
+                        OR ($2::BIGINT IS NOT NULL AND position > $2)"
+                ),
+                &[&before, &keep],
+            )?;
+            let ids: Vec<i64> = candidates.iter().map(|row| row.get(0)).collect();
+
+            let mut per_language: HashMap<String, i64> = HashMap::new();
+            for row in &candidates {
+                *per_language.entry(row.get(1)).or_default() += 1;
+            }
+            for (language, count) in &per_language {
+                client.execute(
+                    "INSERT INTO good_pattern_summary (language, success_count, last_collapsed)
+                     VALUES ($1, $2, $3)
+                     ON CONFLICT (language) DO UPDATE SET
+                         success_count = good_pattern_summary.success_count
+                                         + EXCLUDED.success_count,
+                         last_collapsed = EXCLUDED.last_collapsed",
+                    &[language, count, &now],
+                )?;
+            }
+
+            client.execute(
+                "UPDATE trajectories SET pattern_id = NULL WHERE pattern_id = ANY($1)",
+                &[&ids],
+            )?;
+            client.execute(
+                "DELETE FROM pattern_signatures WHERE pattern_id = ANY($1)",
+                &[&ids],
+            )?;
+            client.execute("DELETE FROM patterns WHERE id = ANY($1)", &[&ids])
+        })?;
+
+        Ok(collapsed as usize)
+    }
+
+    fn prune_trajectories(
+        &mut self,
+        keep: Option<usize>,
+        before: Option<&str>,
+    ) -> TetradResult<usize> {
+        let keep = keep.map(|keep| keep as i64);
+        let pruned = self.run(|client| {
+            let mut pruned = 0;
+            if let Some(before) = before {
+                pruned +=
+                    client.execute("DELETE FROM trajectories WHERE timestamp < $1", &[&before])?;
+            }
+            if let Some(keep) = keep {
+                pruned += client.execute(
+                    "DELETE FROM trajectories
+                     WHERE id NOT IN (SELECT id FROM trajectories ORDER BY id DESC LIMIT $1)",
+                    &[&keep],
+                )?;
+            }
+            Ok(pruned)
+        })?;
+
+        Ok(pruned as usize)
+    }
+
+    fn all_patterns(&self) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS}
+                 FROM patterns
+                 ORDER BY (success_count + failure_count) DESC"
+            ),
+            &[],
+        )
+    }
+
+    fn list_patterns(&self, filter: &PatternFilter) -> TetradResult<Vec<Pattern>> {
+        let order = match filter.sort {
+            PatternSort::Confidence => "confidence DESC, (success_count + failure_count) DESC",
+            PatternSort::Usage => "(success_count + failure_count) DESC, confidence DESC",
+        };
+        let sql = format!(
+            "SELECT {PATTERN_COLUMNS} FROM patterns
+             WHERE ($1::TEXT IS NULL OR pattern_type = $1)
+               AND ($2::TEXT IS NULL OR lower(language) = lower($2))
+               AND ($3::TEXT IS NULL OR lower(issue_category) = lower($3))
+               AND ($4::DOUBLE PRECISION IS NULL OR confidence >= $4)
+             ORDER BY {order}, id
+             LIMIT $5"
+        );
+        let pattern_type = filter.pattern_type.as_ref().map(|t| t.to_string());
+        // LIMIT NULL no Postgres significa sem limite
+        let limit = filter.limit.map(|l| l as i64);
+
+        self.query_patterns(
+            &sql,
+            &[
+                &pattern_type,
+                &filter.language,
+                &filter.category,
+                &filter.min_confidence,
+                &limit,
+            ],
+        )
+    }
+
+    fn get_pattern(&self, id: i64) -> TetradResult<Option<Pattern>> {
+        let sql = format!("SELECT {PATTERN_COLUMNS} FROM patterns WHERE id = $1");
+        let row = self.run(|client| client.query_opt(&sql, &[&id]))?;
+
+        Ok(row.map(|row| pattern_from_row(&row)).transpose()?)
+    }
+
+    fn delete_pattern(&mut self, id: i64) -> TetradResult<bool> {
+        let deleted = self.run(|client| {
+            client.execute(
+                "UPDATE trajectories SET pattern_id = NULL WHERE pattern_id = $1",
+                &[&id],
+            )?;
+            client.execute(
+                "DELETE FROM pattern_signatures WHERE pattern_id = $1",
+                &[&id],
+            )?;
+            client.execute("DELETE FROM patterns WHERE id = $1", &[&id])
+        })?;
+
+        Ok(deleted > 0)
+    }
+    fn vacuum(&mut self) -> TetradResult<()> {
+        self.run(|client| client.batch_execute("VACUUM trajectories"))?;
+        Ok(())
+    }
+
+    fn table_counts(&self) -> TetradResult<Vec<(String, usize)>> {
+        TABLES
+            .iter()
+            .map(|table| {
+                let sql = format!("SELECT COUNT(*) FROM {table}");
+                let row = self.run(|client| client.query_one(&sql, &[]))?;
+                Ok((table.to_string(), row.get::<_, i64>(0) as usize))
+            })
+            .collect()
+    }
+
+    fn add_usage(
+        &mut self,
+        month: &str,
+        evaluations: u64,
+        cost: f64,
+        limits: &UsageLimits,
+    ) -> TetradResult<Option<MonthlyUsage>> {
+        let evaluations = evaluations as i64;
+        let max_evaluations = limits.max_evaluations.map(|max| max as i64);
+        let updated = self.run(|client| {
+            client.execute(
+                "INSERT INTO usage (month) VALUES ($1) ON CONFLICT (month) DO NOTHING",
+                &[&month],
+            )?;
+            client.execute(
+                "UPDATE usage SET evaluations = evaluations + $1, estimated_cost = estimated_cost + $2
+                 WHERE month = $3
+                   AND ($4::BIGINT IS NULL OR evaluations + $1 <= $4::BIGINT)
+                   AND ($5::DOUBLE PRECISION IS NULL OR estimated_cost + $2 <= $5::DOUBLE PRECISION)",
+                &[&evaluations, &cost, &month, &max_evaluations, &limits.max_cost],
+            )
+        })?;
+
+        if updated == 0 {
+            return Ok(None);
+        }
+        self.usage(month).map(Some)
+    }
+
+    fn usage(&self, month: &str) -> TetradResult<MonthlyUsage> {
+        let row = self.run(|client| {
+            client.query_opt(
+                "SELECT evaluations, estimated_cost FROM usage WHERE month = $1",
+                &[&month],
+            )
+        })?;
+        let (evaluations, estimated_cost) = match row {
+            Some(row) => (row.try_get::<_, i64>(0)?, row.try_get::<_, f64>(1)?),
+            None => (0, 0.0),
+        };
+
+        Ok(MonthlyUsage {
+            month: month.to_string(),
+            evaluations: evaluations as u64,
+            estimated_cost,
+        })
+    }
+
+    fn add_metrics(&mut self, delta: &MetricCounters) -> TetradResult<()> {
+        let values = [
+            delta.evaluations as i64,
+            delta.passes as i64,
+            delta.revises as i64,
+            delta.blocks as i64,
+            delta.score_sum as i64,
+            delta.recertifications as i64,
+        ];
+        self.run(|client| {
+            client.execute(
+                "INSERT INTO metrics
+                     (evaluation_type, evaluations, passes, revises, blocks, score_sum, recertifications)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7)
+                 ON CONFLICT (evaluation_type) DO UPDATE SET
+                     evaluations = metrics.evaluations + EXCLUDED.evaluations,
+                     passes = metrics.passes + EXCLUDED.passes,
+                     revises = metrics.revises + EXCLUDED.revises,
+                     blocks = metrics.blocks + EXCLUDED.blocks,
+                     score_sum = metrics.score_sum + EXCLUDED.score_sum,
+                     recertifications = metrics.recertifications + EXCLUDED.recertifications",
+                &[
+                    &delta.evaluation_type,
+                    &values[0],
+                    &values[1],
+                    &values[2],
+                    &values[3],
+                    &values[4],
+                    &values[5],
+                ],
+            )
+        })?;
+        Ok(())
+    }
+
+    fn metrics(&self) -> TetradResult<Vec<MetricCounters>> {
+        let rows = self.run(|client| {
+            client.query(
+                "SELECT evaluation_type, evaluations, passes, revises, blocks, score_sum, recertifications
+                 FROM metrics
+                 ORDER BY evaluation_type",
+                &[],
+            )
+        })?;
+        rows.iter()
+            .map(|row| {
+                Ok(MetricCounters {
+                    evaluation_type: row.try_get(0)?,
+                    evaluations: row.try_get::<_, i64>(1)? as u64,
+                    passes: row.try_get::<_, i64>(2)? as u64,
+                    revises: row.try_get::<_, i64>(3)? as u64,
+                    blocks: row.try_get::<_, i64>(4)? as u64,
+                    score_sum: row.try_get::<_, i64>(5)? as u64,
+                    recertifications: row.try_get::<_, i64>(6)? as u64,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Colunas lidas por [`pattern_from_row`], na ordem esperada.
+const PATTERN_COLUMNS: &str = "id, pattern_type, code_signature, language, issue_category,
+    description, solution, success_count, failure_count, confidence,
+    last_seen, created_at, artifact_kind, shape_signature, signature_version";
+
+fn pattern_from_row(row: &Row) -> Result<Pattern, postgres::Error> {
+    Ok(Pattern {
+        id: row.try_get(0)?,
+        pattern_type: PatternType::from_str(row.try_get(1)?),
+        code_signature: row.try_get(2)?,
+        language: row.try_get(3)?,
+        issue_category: row.try_get(4)?,
+        description: row.try_get(5)?,
+        solution: row.try_get(6)?,
+        success_count: row.try_get(7)?,
+        failure_count: row.try_get(8)?,
+        confidence: row.try_get(9)?,
+        last_seen: row
+            .try_get::<_, &str>(10)?
+            .parse()
+            .unwrap_or_else(|_| Utc::now()),
+        created_at: row
+            .try_get::<_, &str>(11)?
+            .parse()
+            .unwrap_or_else(|_| Utc::now()),
+        artifact_kind: ArtifactKind::from_str(row.try_get(12)?),
+        shape_signature: row.try_get(13)?,
+        signature_version: row.try_get::<_, i32>(14)? as u32,
+    })
+}
@@ -0,0 +1,427 @@
+//! Assinatura de pacotes de patterns.
+//!
+//! Pacotes distribuídos entre equipes precisam acusar adulteração.
+//! `tetrad export --sign-key` assina a exportação com ed25519 e embute no
+//! próprio JSON a assinatura e a impressão digital da chave pública;
+//! `tetrad import` procura essa chave entre as confiáveis e verifica a
+//! assinatura antes de gravar qualquer pattern.
+//!
+//! A mensagem assinada é o JSON compacto da exportação sem o campo
+//! `signature`, com as chaves na ordem em que aparecem no arquivo. Quem
+//! verifica só remove o campo e serializa de novo, sem depender da ordem
+//! dos mapas de quem assinou.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::types::errors::TetradError;
+use crate::TetradResult;
+
+/// Algoritmo gravado em [`PackSignature::algorithm`].
+pub const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+/// Nome do arquivo da chave privada gerada por `tetrad keygen`.
+pub const SIGNING_KEY_FILE_NAME: &str = "signing.key";
+
+/// Nome do arquivo da chave pública gerada por `tetrad keygen`.
+pub const VERIFYING_KEY_FILE_NAME: &str = "signing.pub";
+
+/// Diretório padrão das chaves confiáveis, dentro do diretório de dados.
+pub const TRUSTED_KEYS_DIR_NAME: &str = "trusted-keys";
+
+/// Campo da exportação que guarda a assinatura.
+const SIGNATURE_FIELD: &str = "signature";
+
+/// Assinatura destacada embutida em uma exportação.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackSignature {
+    /// Algoritmo da assinatura (sempre [`SIGNATURE_ALGORITHM`]).
+    pub algorithm: String,
+    /// Impressão digital da chave pública que assinou.
+    pub key_fingerprint: String,
+    /// Assinatura, em hex.
+    pub signature: String,
+}
+
+/// Resultado da verificação de um pacote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// O pacote não tem assinatura.
+    Unsigned,
+    /// Assinatura válida de uma chave confiável.
+    Verified {
+        /// Impressão digital da chave.
+        fingerprint: String,
+    },
+    /// Assinado por uma chave que não está entre as confiáveis.
+    UnknownKey {
+        /// Impressão digital da chave.
+        fingerprint: String,
+    },
+    /// A assinatura não confere: o conteúdo foi alterado depois de assinado.
+    Invalid {
+        /// Impressão digital declarada no pacote.
+        fingerprint: String,
+    },
+}
+
+impl SignatureStatus {
+    /// Se a assinatura foi verificada.
+    pub fn is_verified(&self) -> bool {
+        matches!(self, SignatureStatus::Verified { .. })
+    }
+}
+
+impl fmt::Display for SignatureStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignatureStatus::Unsigned => write!(f, "pack is not signed"),
+            SignatureStatus::Verified { fingerprint } => {
+                write!(f, "valid signature from trusted key {}", fingerprint)
+            }
+            SignatureStatus::UnknownKey { fingerprint } => {
+                write!(f, "signed by untrusted key {}", fingerprint)
+            }
+            SignatureStatus::Invalid { fingerprint } => {
+                write!(f, "signature from key {} does not match", fingerprint)
+            }
+        }
+    }
+}
+
+/// Chaves públicas confiáveis, indexadas pela impressão digital.
+#[derive(Debug, Clone, Default)]
+pub struct TrustedKeys {
+    keys: HashMap<String, VerifyingKey>,
+}
+
+impl TrustedKeys {
+    /// Lê todos os arquivos `*.pub` de `dir`; outros arquivos são ignorados.
+    ///
+    /// Um arquivo `.pub` ilegível é um erro: uma chave confiável ignorada em
+    /// silêncio faria pacotes legítimos serem recusados sem explicação.
+    pub fn load_dir(dir: &Path) -> TetradResult<Self> {
+        let mut trusted = Self::default();
+        let entries = std::fs::read_dir(dir).map_err(|e| {
+            TetradError::ReasoningBank(format!(
+                "Could not read trusted keys from {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        for entry in entries {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "pub") {
+                trusted.insert(load_verifying_key(&path)?);
+            }
+        }
+        Ok(trusted)
+    }
+
+    /// Adiciona uma chave.
+    pub fn insert(&mut self, key: VerifyingKey) {
+        self.keys.insert(fingerprint(&key), key);
+    }
+
+    /// Número de chaves.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Se não há nenhuma chave.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Chave com a impressão digital dada.
+    pub fn get(&self, fingerprint: &str) -> Option<&VerifyingKey> {
+        self.keys.get(fingerprint)
+    }
+}
+
+/// O que fazer com pacotes sem assinatura válida de uma chave confiável.
+#[derive(Debug, Clone, Default)]
+pub struct SignaturePolicy {
+    /// Recusa o pacote; sem isso, só registra um aviso.
+    pub require: bool,
+    /// Chaves aceitas.
+    pub trusted: TrustedKeys,
+}
+
+impl SignaturePolicy {
+    /// Verifica o JSON (já descompactado) de um pacote.
+    ///
+    /// Retorna o resultado da verificação, ou erro se a política exige
+    /// assinatura e o pacote não tem uma válida. `origin` identifica o
+    /// pacote nas mensagens.
+    pub fn check(&self, json: &[u8], origin: &str) -> TetradResult<SignatureStatus> {
+        let status = verify_pack(json, &self.trusted)?;
+        if status.is_verified() {
+            return Ok(status);
+        }
+        if self.require {
+            return Err(TetradError::ReasoningBank(format!(
+                "{}: {}; refusing to import",
+                origin, status
+            )));
+        }
+        tracing::warn!(
+            source = origin,
+            status = %status,
+            "Importing pack without a trusted signature"
+        );
+        Ok(status)
+    }
+}
+
+/// Impressão digital de uma chave pública: os primeiros 16 bytes do SHA-256
+/// da chave, em hex.
+pub fn fingerprint(key: &VerifyingKey) -> String {
+    hex::encode(&Sha256::digest(key.as_bytes())[..16])
+}
+
+/// Gera uma chave privada nova a partir do gerador aleatório do sistema.
+pub fn generate_signing_key() -> TetradResult<SigningKey> {
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed).map_err(|e| {
+        TetradError::Other(format!("Could not generate a random signing key: {}", e))
+    })?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Grava o par de chaves em `dir` ([`SIGNING_KEY_FILE_NAME`] e
+/// [`VERIFYING_KEY_FILE_NAME`]) e retorna os dois caminhos.
+///
+/// Um par existente só é substituído com `force`. A chave privada fica
+/// legível apenas pelo dono.
+pub fn write_keypair(
+    dir: &Path,
+    key: &SigningKey,
+    force: bool,
+) -> TetradResult<(PathBuf, PathBuf)> {
+    let private = dir.join(SIGNING_KEY_FILE_NAME);
+    let public = dir.join(VERIFYING_KEY_FILE_NAME);
+    if !force && (private.exists() || public.exists()) {
+        return Err(TetradError::Other(format!(
+            "{} already exists; use --force to replace it",
+            private.display()
+        )));
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        // Criado já restrito: a chave nunca fica legível por outros usuários
+        options.mode(0o600);
+    }
+    let mut file = options.open(&private)?;
+    writeln!(file, "{}", hex::encode(key.to_bytes()))?;
+    #[cfg(unix)]
+    {
+        // Um arquivo substituído com --force mantém as permissões antigas
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&private, std::fs::Permissions::from_mode(0o600))?;
+    }
+
+    std::fs::write(
+        &public,
+        format!("{}\n", hex::encode(key.verifying_key().as_bytes())),
+    )?;
+    Ok((private, public))
+}
+
+/// Lê uma chave privada gravada por [`write_keypair`].
+pub fn load_signing_key(path: &Path) -> TetradResult<SigningKey> {
+    Ok(SigningKey::from_bytes(&read_key_bytes(path)?))
+}
+
+/// Lê uma chave pública gravada por [`write_keypair`].
+pub fn load_verifying_key(path: &Path) -> TetradResult<VerifyingKey> {
+    VerifyingKey::from_bytes(&read_key_bytes(path)?).map_err(|e| {
+        TetradError::ReasoningBank(format!("Invalid public key {}: {}", path.display(), e))
+    })
+}
+
+/// Lê os 32 bytes em hex de um arquivo de chave.
+fn read_key_bytes(path: &Path) -> TetradResult<[u8; 32]> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        TetradError::ReasoningBank(format!("Could not read key {}: {}", path.display(), e))
+    })?;
+    hex::decode(content.trim())
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| {
+            TetradError::ReasoningBank(format!(
+                "{} is not a key written by tetrad keygen",
+                path.display()
+            ))
+        })
+}
+
+/// Assina uma exportação já convertida em JSON, gravando a assinatura no
+/// campo `signature` (uma assinatura anterior é substituída).
+pub fn sign_export(export: &mut Value, key: &SigningKey) -> TetradResult<()> {
+    let message = signed_message(export)?;
+    let signature = PackSignature {
+        algorithm: SIGNATURE_ALGORITHM.to_string(),
+        key_fingerprint: fingerprint(&key.verifying_key()),
+        signature: hex::encode(key.sign(&message).to_bytes()),
+    };
+    export_object(export)?.insert(
+        SIGNATURE_FIELD.to_string(),
+        serde_json::to_value(signature)?,
+    );
+    Ok(())
+}
+
+/// Verifica a assinatura do JSON de uma exportação contra as chaves confiáveis.
+pub fn verify_pack(json: &[u8], trusted: &TrustedKeys) -> TetradResult<SignatureStatus> {
+    let mut export: Value = serde_json::from_slice(json)?;
+    let Some(field) = export_object(&mut export)?.get(SIGNATURE_FIELD).cloned() else {
+        return Ok(SignatureStatus::Unsigned);
+    };
+
+    let Ok(signature) = serde_json::from_value::<PackSignature>(field) else {
+        return Ok(SignatureStatus::Invalid {
+            fingerprint: String::new(),
+        });
+    };
+    let fingerprint = signature.key_fingerprint;
+    let Some(key) = trusted.get(&fingerprint) else {
+        return Ok(SignatureStatus::UnknownKey { fingerprint });
+    };
+
+    let bytes = hex::decode(&signature.signature)
+        .ok()
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok());
+    let valid = signature.algorithm == SIGNATURE_ALGORITHM
+        && bytes.is_some_and(|bytes| {
+            let message = signed_message(&export);
+            message.is_ok_and(|message| {
+                key.verify_strict(&message, &Signature::from_bytes(&bytes))
+                    .is_ok()
+            })
+        });
+
+    Ok(if valid {
+        SignatureStatus::Verified { fingerprint }
+    } else {
+        SignatureStatus::Invalid { fingerprint }
+    })
+}
+
+/// Mensagem assinada: o JSON compacto da exportação sem a assinatura.
+fn signed_message(export: &Value) -> TetradResult<Vec<u8>> {
+    let mut unsigned = export.clone();
+    // shift_remove preserva a ordem das demais chaves
+    export_object(&mut unsigned)?.shift_remove(SIGNATURE_FIELD);
+    Ok(serde_json::to_vec(&unsigned)?)
+}
+
+fn export_object(export: &mut Value) -> TetradResult<&mut serde_json::Map<String, Value>> {
+    export
+        .as_object_mut()
+        .ok_or_else(|| TetradError::ReasoningBank("Pattern pack is not a JSON object".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(byte: u8) -> SigningKey {
+        SigningKey::from_bytes(&[byte; 32])
+    }
+
+    fn trusting(key: &SigningKey) -> TrustedKeys {
+        let mut trusted = TrustedKeys::default();
+        trusted.insert(key.verifying_key());
+        trusted
+    }
+
+    fn signed_pack(key: &SigningKey) -> Vec<u8> {
+        let mut export = serde_json::json!({
+            "version": "2.0",
+            "patterns": [{"id": 1, "confidence": 0.7333333333333333, "language": "rust"}],
+            "knowledge": {"language_stats": {"sql": 1, "rust": 2}}
+        });
+        sign_export(&mut export, key).unwrap();
+        serde_json::to_vec_pretty(&export).unwrap()
+    }
+
+    #[test]
+    fn test_sign_and_verify() {
+        let signer = key(7);
+        let pack = signed_pack(&signer);
+
+        assert_eq!(
+            verify_pack(&pack, &trusting(&signer)).unwrap(),
+            SignatureStatus::Verified {
+                fingerprint: fingerprint(&signer.verifying_key())
+            }
+        );
+        assert!(matches!(
+            verify_pack(&pack, &trusting(&key(8))).unwrap(),
+            SignatureStatus::UnknownKey { .. }
+        ));
+    }
+
+    #[test]
+    fn test_tampered_pack_is_invalid() {
+        let signer = key(7);
+        let pack = String::from_utf8(signed_pack(&signer)).unwrap();
+        let tampered = pack.replace("\"rust\": 2", "\"rust\": 3");
+        assert_ne!(tampered, pack);
+
+        assert!(matches!(
+            verify_pack(tampered.as_bytes(), &trusting(&signer)).unwrap(),
+            SignatureStatus::Invalid { .. }
+        ));
+    }
+
+    #[test]
+    fn test_policy_refuses_only_when_required() {
+        let signer = key(7);
+        let unsigned = br#"{"version": "2.0", "patterns": []}"#;
+
+        let warn = SignaturePolicy::default();
+        assert_eq!(
+            warn.check(unsigned, "unsigned").unwrap(),
+            SignatureStatus::Unsigned
+        );
+
+        let strict = SignaturePolicy {
+            require: true,
+            trusted: trusting(&signer),
+        };
+        assert!(strict.check(unsigned, "unsigned").is_err());
+        assert!(strict.check(&signed_pack(&signer), "signed").is_ok());
+        assert!(strict.check(&signed_pack(&key(8)), "other").is_err());
+    }
+
+    #[test]
+    fn test_keypair_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let signer = generate_signing_key().unwrap();
+
+        let (private, public) = write_keypair(dir.path(), &signer, false).unwrap();
+        assert_eq!(load_signing_key(&private).unwrap(), signer);
+        assert_eq!(load_verifying_key(&public).unwrap(), signer.verifying_key());
+        assert!(write_keypair(dir.path(), &signer, false).is_err());
+
+        // A chave privada não entra no conjunto de chaves confiáveis
+        let trusted = TrustedKeys::load_dir(dir.path()).unwrap();
+        assert_eq!(trusted.len(), 1);
+        assert!(trusted.get(&fingerprint(&signer.verifying_key())).is_some());
+    }
+}
@@ -0,0 +1,1264 @@
+//! Backend SQLite do ReasoningBank (padrão).
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::types::config::ReasoningConfig;
+use crate::types::language::Language;
+use crate::types::responses::Decision;
+use crate::TetradResult;
+
+use super::bank::{
+    ArtifactKind, LanguageStats, Pattern, PatternFilter, PatternSort, PatternType, StatsBucket,
+};
+use super::metrics::MetricCounters;
+use super::patterns::SIGNATURE_VERSION;
+use super::store::{
+    decision_from_str, decision_to_str, vote_from_str, vote_to_str, DecisionRecord, PatternStore,
+    PeriodTally, StoredVote, TrajectoryRecord, VoteTally, ONE_OFF_GOOD_PATTERN, TABLES,
+};
+use super::usage::{MonthlyUsage, UsageLimits};
+
+/// Patterns em um arquivo SQLite local.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    /// Cria ou abre o banco em `db_path`.
+    ///
+    /// O banco é compartilhado entre o servidor MCP e a CLI: em modo WAL
+    /// leitores não bloqueiam escritores, e o busy_timeout faz cada processo
+    /// esperar pelo outro em vez de falhar com "database is locked".
+    pub fn open(db_path: &Path, config: &ReasoningConfig) -> TetradResult<Self> {
+        // Create parent directory if it doesn't exist
+        if let Some(parent) = db_path.parent() {
+            if !parent.as_os_str().is_empty() && !parent.exists() {
+                std::fs::create_dir_all(parent)?;
+            }
+        }
+
+        let conn = Connection::open(db_path)?;
+
+        // O timeout vem antes dos demais pragmas, que também disputam o lock
+        conn.busy_timeout(Duration::from_millis(config.busy_timeout_ms))?;
+        if config.wal_mode {
+            conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| {
+                row.get::<_, String>(0)
+            })?;
+            conn.pragma_update(None, "synchronous", "NORMAL")?;
+        }
+
+        // Cria as tabelas se não existirem
+        conn.execute_batch(
+            r#"
+            CREATE TABLE IF NOT EXISTS patterns (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                pattern_type TEXT NOT NULL,
+                code_signature TEXT NOT NULL,
+                language TEXT NOT NULL,
+                issue_category TEXT NOT NULL,
+                description TEXT NOT NULL,
+                solution TEXT,
+                success_count INTEGER DEFAULT 0,
+                failure_count INTEGER DEFAULT 0,
+                confidence REAL DEFAULT 0.5,
+                last_seen TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                artifact_kind TEXT NOT NULL DEFAULT 'code',
+                shape_signature TEXT,
+                signature_version INTEGER NOT NULL DEFAULT 0,
+                UNIQUE(code_signature, issue_category)
+            );
+
+            CREATE TABLE IF NOT EXISTS trajectories (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                pattern_id INTEGER REFERENCES patterns(id),
+                request_id TEXT NOT NULL,
+                code_hash TEXT NOT NULL,
+                initial_score INTEGER,
+                final_score INTEGER,
+                loops_to_consensus INTEGER,
+                was_successful BOOLEAN,
+                timestamp TEXT NOT NULL,
+                decision TEXT
+            );
+
+            CREATE TABLE IF NOT EXISTS votes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                trajectory_id INTEGER NOT NULL REFERENCES trajectories(id) ON DELETE CASCADE,
+                executor TEXT NOT NULL,
+                vote TEXT NOT NULL,
+                score INTEGER NOT NULL,
+                reasoning TEXT NOT NULL DEFAULT '',
+                issues TEXT NOT NULL DEFAULT '[]',
+                suggestions TEXT NOT NULL DEFAULT '[]'
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_patterns_signature ON patterns(code_signature);
+            CREATE INDEX IF NOT EXISTS idx_patterns_category ON patterns(issue_category);
+            CREATE INDEX IF NOT EXISTS idx_patterns_type ON patterns(pattern_type);
+            CREATE INDEX IF NOT EXISTS idx_trajectories_pattern ON trajectories(pattern_id);
+            CREATE INDEX IF NOT EXISTS idx_votes_trajectory ON votes(trajectory_id);
+            CREATE INDEX IF NOT EXISTS idx_votes_executor ON votes(executor);
+
+            CREATE TABLE IF NOT EXISTS pattern_signatures (
+                sub_signature TEXT NOT NULL,
+                pattern_id INTEGER NOT NULL,
+                PRIMARY KEY (sub_signature, pattern_id)
+            );
+            CREATE INDEX IF NOT EXISTS idx_pattern_signatures_pattern ON pattern_signatures(pattern_id);
+
+            CREATE TABLE IF NOT EXISTS usage (
+                month TEXT PRIMARY KEY,
+                evaluations INTEGER NOT NULL DEFAULT 0,
+                estimated_cost REAL NOT NULL DEFAULT 0
+            );
+
+            CREATE TABLE IF NOT EXISTS good_pattern_summary (
+                language TEXT PRIMARY KEY,
+                success_count INTEGER NOT NULL DEFAULT 0,
+                last_collapsed TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS metrics (
+                evaluation_type TEXT PRIMARY KEY,
+                evaluations INTEGER NOT NULL DEFAULT 0,
+                passes INTEGER NOT NULL DEFAULT 0,
+                revises INTEGER NOT NULL DEFAULT 0,
+                blocks INTEGER NOT NULL DEFAULT 0,
+                score_sum INTEGER NOT NULL DEFAULT 0,
+                recertifications INTEGER NOT NULL DEFAULT 0
+            );
+        "#,
+        )?;
+
+        Self::migrate(&conn)?;
+
+        Ok(Self { conn })
+    }
+
+    /// Atualiza bancos criados por versões anteriores.
+    fn migrate(conn: &Connection) -> TetradResult<()> {
+        // artifact_kind foi adicionado depois; patterns antigos são de código
+        if conn
+            .prepare("SELECT artifact_kind FROM patterns LIMIT 0")
+            .is_err()
+        {
+            conn.execute(
+                "ALTER TABLE patterns ADD COLUMN artifact_kind TEXT NOT NULL DEFAULT 'code'",
+                [],
+            )?;
+        }
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_patterns_artifact ON patterns(artifact_kind)",
+            [],
+        )?;
+
+        // As assinaturas passaram a ser calculadas sobre tokens. O código
+        // original não é guardado, então patterns antigos não podem ser
+        // recalculados: ficam marcados como legados (versão 0, sem forma)
+        if conn
+            .prepare("SELECT shape_signature FROM patterns LIMIT 0")
+            .is_err()
+        {
+            conn.execute_batch(
+                "ALTER TABLE patterns ADD COLUMN shape_signature TEXT;
+                 ALTER TABLE patterns ADD COLUMN signature_version INTEGER NOT NULL DEFAULT 0;",
+            )?;
+        }
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_patterns_shape ON patterns(shape_signature)",
+            [],
+        )?;
+
+        // Patterns manuais derivam a assinatura da descrição, que não mudou
+        conn.execute(
+            "UPDATE patterns SET signature_version = ?
+             WHERE signature_version = 0 AND code_signature LIKE 'manual:%'",
+            params![SIGNATURE_VERSION],
+        )?;
+
+        // decision também; trajetórias antigas ficam sem decisão (NULL)
+        if conn
+            .prepare("SELECT decision FROM trajectories LIMIT 0")
+            .is_err()
+        {
+            conn.execute("ALTER TABLE trajectories ADD COLUMN decision TEXT", [])?;
+        }
+
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_trajectories_hash ON trajectories(code_hash)",
+            [],
+        )?;
+
+        // Linguagens gravadas antes da normalização ("Rust", "rs") viram ids canônicos
+        let languages: Vec<String> = conn
+            .prepare("SELECT DISTINCT language FROM patterns")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        for language in languages {
+            let canonical = Language::canonical(&language);
+            if canonical != language {
+                conn.execute(
+                    "UPDATE patterns SET language = ?1 WHERE language = ?2",
+                    params![canonical, language],
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Conexão subjacente.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    fn query_patterns(
+        &self,
+        sql: &str,
+        params: impl rusqlite::Params,
+    ) -> TetradResult<Vec<Pattern>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let patterns = stmt
+            .query_map(params, pattern_from_row)?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(patterns)
+    }
+}
+
+impl PatternStore for SqliteStore {
+    fn backend(&self) -> &'static str {
+        "sqlite"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    /// `BEGIN IMMEDIATE` reserva a escrita logo no início, então o
+    /// busy_timeout cobre a disputa com outros processos.
+    fn begin_write(&mut self) -> TetradResult<()> {
+        self.conn.execute_batch("BEGIN IMMEDIATE")?;
+        Ok(())
+    }
+
+    fn commit(&mut self) -> TetradResult<()> {
+        self.conn.execute_batch("COMMIT")?;
+        Ok(())
+    }
+
+    fn rollback(&mut self) {
+        let _ = self.conn.execute_batch("ROLLBACK");
+    }
+
+    fn find_by_signature(&self, signature: &str, kind: ArtifactKind) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS} FROM patterns WHERE code_signature = ? AND artifact_kind = ?"
+            ),
+            params![signature, kind.to_string()],
+        )
+    }
+
+    fn find_by_shape(&self, shape: &str, kind: ArtifactKind) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS} FROM patterns
+                 WHERE shape_signature = ? AND artifact_kind = ? AND signature_version = ?"
+            ),
+            params![shape, kind.to_string(), SIGNATURE_VERSION],
+        )
+    }
+
+    fn find_by_sub_signature(
+        &self,
+        sub_signature: &str,
+        kind: ArtifactKind,
+    ) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS} FROM patterns
+                 WHERE artifact_kind = ?
+                   AND id IN (SELECT pattern_id FROM pattern_signatures WHERE sub_signature = ?)"
+            ),
+            params![kind.to_string(), sub_signature],
+        )
+    }
+
+    fn find_by_keyword(
+        &self,
+        keyword: &str,
+        language: &str,
+        kind: ArtifactKind,
+    ) -> TetradResult<Vec<Pattern>> {
+        let keyword_pattern = format!("%{}%", keyword);
+
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS}
+                 FROM patterns
+                 WHERE (language = ? OR language = 'any')
+                   AND artifact_kind = ?
+                   AND (issue_category LIKE ? OR description LIKE ?)
+                 ORDER BY confidence DESC
+                 LIMIT 10"
+            ),
+            params![
+                language,
+                kind.to_string(),
+                &keyword_pattern,
+                &keyword_pattern
+            ],
+        )
+    }
+
+    fn insert_trajectory(&mut self, trajectory: &TrajectoryRecord<'_>) -> TetradResult<i64> {
+        self.conn.execute(
+            "INSERT INTO trajectories (pattern_id, request_id, code_hash, initial_score,
+                                       final_score, loops_to_consensus, was_successful, timestamp,
+                                       decision)
+             VALUES (NULL, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                trajectory.request_id,
+                trajectory.code_hash,
+                trajectory.initial_score as i32,
+                trajectory.final_score as i32,
+                trajectory.loops_to_consensus as i32,
+                trajectory.was_successful,
+                trajectory.timestamp,
+                decision_to_str(trajectory.decision)
+            ],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    fn insert_votes(&mut self, trajectory_id: i64, votes: &[StoredVote]) -> TetradResult<()> {
+        let mut stmt = self.conn.prepare(
+            "INSERT INTO votes (trajectory_id, executor, vote, score, reasoning, issues,
+                                suggestions)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )?;
+        for vote in votes {
+            stmt.execute(params![
+                trajectory_id,
+                vote.executor,
+                vote_to_str(vote.vote),
+                vote.score as i32,
+                vote.reasoning,
+                serde_json::to_string(&vote.issues)?,
+                serde_json::to_string(&vote.suggestions)?,
+            ])?;
+        }
+        Ok(())
+    }
+
+    fn votes_for_trajectory(&self, trajectory_id: i64) -> TetradResult<Vec<StoredVote>> {
+        let rows = self
+            .conn
+            .prepare(
+                "SELECT executor, vote, score, reasoning, issues, suggestions FROM votes
+                 WHERE trajectory_id = ?
+                 ORDER BY id",
+            )?
+            .query_map(params![trajectory_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i32>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(executor, vote, score, reasoning, issues, suggestions)| {
+                Some(StoredVote {
+                    executor,
+                    vote: vote_from_str(&vote)?,
+                    score: score.clamp(0, 100) as u8,
+                    reasoning,
+                    issues: serde_json::from_str(&issues).unwrap_or_default(),
+                    suggestions: serde_json::from_str(&suggestions).unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
+    fn latest_divergent(
+        &self,
+        code_hash: &str,
+        since: &str,
+        decision: Decision,
+    ) -> TetradResult<Option<(String, Decision)>> {
+        let previous: Option<(String, String)> = self
+            .conn
+            .query_row(
+                "SELECT request_id, decision FROM trajectories
+                 WHERE code_hash = ? AND timestamp >= ? AND decision IS NOT NULL AND decision != ?
+                 ORDER BY id DESC LIMIT 1",
+                params![code_hash, since, decision_to_str(decision)],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        Ok(previous.and_then(|(request_id, decision)| {
+            decision_from_str(&decision).map(|decision| (request_id, decision))
+        }))
+    }
+
+    fn decision_history(&self) -> TetradResult<Vec<DecisionRecord>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT code_hash, request_id, decision, timestamp FROM trajectories
+             WHERE decision IS NOT NULL
+             ORDER BY code_hash, id",
+        )?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok(DecisionRecord {
+                    code_hash: row.get(0)?,
+                    request_id: row.get(1)?,
+                    decision: row.get(2)?,
+                    timestamp: row.get(3)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(rows)
+    }
+
+    fn record_outcome(
+        &mut self,
+        signature: &str,
+        category: &str,
+        was_successful: bool,
+        now: &str,
+    ) -> TetradResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE patterns
+             SET success_count = success_count + ?,
+                 failure_count = failure_count + ?,
+                 last_seen = ?,
+                 confidence = CAST(success_count + ? AS REAL) / (success_count + failure_count + 1)
+             WHERE code_signature = ? AND issue_category = ?",
+            params![
+                if was_successful { 1 } else { 0 },
+                if was_successful { 0 } else { 1 },
+                now,
+                if was_successful { 1 } else { 0 },
+                signature,
+                category
+            ],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    fn record_good_outcome(&mut self, signature: &str, now: &str) -> TetradResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE patterns
+             SET success_count = success_count + 1,
+                 pattern_type = 'good_pattern',
+                 last_seen = ?,
+                 confidence = CAST(success_count + 1 AS REAL) / (success_count + failure_count + 1)
+             WHERE code_signature = ? AND issue_category = 'success'",
+            params![now, signature],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    fn record_good_shape(
+        &mut self,
+        shape: &str,
+        language: &str,
+        kind: ArtifactKind,
+        now: &str,
+    ) -> TetradResult<bool> {
+        let updated = self.conn.execute(
+            "UPDATE patterns
+             SET success_count = success_count + 1,
+                 last_seen = ?1,
+                 confidence = CAST(success_count + 1 AS REAL) / (success_count + failure_count + 1)
+             WHERE id = (SELECT id FROM patterns
+                         WHERE shape_signature = ?2 AND language = ?3 AND artifact_kind = ?4
+                           AND issue_category = 'success' AND pattern_type = 'good_pattern'
+                         ORDER BY id LIMIT 1)",
+            params![now, shape, language, kind.to_string()],
+        )?;
+
+        Ok(updated > 0)
+    }
+
+    fn insert_pattern(&mut self, pattern: &Pattern) -> TetradResult<i64> {
+        self.conn.execute(
+            "INSERT INTO patterns (pattern_type, code_signature, language, issue_category,
+                                   description, solution, success_count, failure_count,
+                                   confidence, last_seen, created_at, artifact_kind,
+                                   shape_signature, signature_version)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            params![
+                pattern.pattern_type.to_string(),
+                pattern.code_signature,
+                pattern.language,
+                pattern.issue_category,
+                pattern.description,
+                pattern.solution,
+                pattern.success_count,
+                pattern.failure_count,
+                pattern.confidence,
+                pattern.last_seen.to_rfc3339(),
+                pattern.created_at.to_rfc3339(),
+                pattern.artifact_kind.to_string(),
+                pattern.shape_signature,
+                pattern.signature_version
+            ],
+        )?;
+
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    fn find_pattern(&self, signature: &str, category: &str) -> TetradResult<Option<Pattern>> {
+        let pattern = self
+            .conn
+            .query_row(
+                &format!(
+                    "SELECT {PATTERN_COLUMNS} FROM patterns
+                     WHERE code_signature = ? AND issue_category = ?"
+                ),
+                params![signature, category],
+                pattern_from_row,
+            )
+            .optional()?;
+
+        Ok(pattern)
+    }
+
+    fn link_sub_signature(&mut self, sub_signature: &str, pattern_id: i64) -> TetradResult<()> {
+        self.conn.execute(
+            "INSERT OR IGNORE INTO pattern_signatures (sub_signature, pattern_id) VALUES (?, ?)",
+            params![sub_signature, pattern_id],
+        )?;
+        Ok(())
+    }
+
+    fn update_description(
+        &mut self,
+        signature: &str,
+        category: &str,
+        description: &str,
+    ) -> TetradResult<()> {
+        self.conn.execute(
+            "UPDATE patterns SET description = ?
+             WHERE code_signature = ? AND issue_category = ?",
+            params![description, signature, category],
+        )?;
+        Ok(())
+    }
+
+    fn merge_counts(&mut self, pattern: &Pattern) -> TetradResult<()> {
+        self.conn.execute(
+            "UPDATE patterns
+             SET success_count = success_count + ?,
+                 failure_count = failure_count + ?,
+                 last_seen = MAX(last_seen, ?),
+                 confidence = CAST(success_count + ? AS REAL) / (success_count + failure_count + ? + ?)
+             WHERE code_signature = ? AND issue_category = ?",
+            params![
+                pattern.success_count,
+                pattern.failure_count,
+                pattern.last_seen.to_rfc3339(),
+                pattern.success_count,
+                pattern.success_count,
+                pattern.failure_count,
+                pattern.code_signature,
+                pattern.issue_category
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn top_patterns(&self, pattern_type: PatternType, limit: usize) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS}
+                 FROM patterns
+                 WHERE pattern_type = ?
+                 ORDER BY (success_count + failure_count) DESC, confidence DESC, id
+                 LIMIT ?"
+            ),
+            params![pattern_type.to_string(), limit as i32],
+        )
+    }
+
+    fn problematic_categories(&self) -> TetradResult<HashMap<String, usize>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT issue_category, COUNT(*) as count
+             FROM patterns
+             WHERE pattern_type = 'anti_pattern'
+             GROUP BY issue_category
+             ORDER BY count DESC",
+        )?;
+
+        let categories: HashMap<String, usize> = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, usize>(1)?))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(categories)
+    }
+
+    fn language_stats(&self) -> TetradResult<HashMap<String, LanguageStats>> {
+        // GoodPatterns recolhidos contam como patterns de confiança 1.0
+        let mut stmt = self.conn.prepare(
+            "SELECT language,
+                    SUM(total),
+                    SUM(good) / SUM(total) as success_rate,
+                    SUM(score) / SUM(total) as avg_score
+             FROM (
+                 SELECT language,
+                        COUNT(*) as total,
+                        SUM(CASE WHEN pattern_type = 'good_pattern' THEN 1.0 ELSE 0.0 END) as good,
+                        SUM(confidence * 100) as score
+                 FROM patterns
+                 GROUP BY language
+                 UNION ALL
+                 SELECT language, success_count, success_count * 1.0, success_count * 100.0
+                 FROM good_pattern_summary
+                 WHERE success_count > 0
+             )
+             GROUP BY language",
+        )?;
+
+        let stats: HashMap<String, LanguageStats> = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    LanguageStats {
+                        total_evaluations: row.get::<_, usize>(1)?,
+                        success_rate: row.get::<_, f64>(2)?,
+                        avg_score: row.get::<_, f64>(3)?,
+                    },
+                ))
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(stats)
+    }
+
+    fn average_loops_to_consensus(&self) -> TetradResult<f64> {
+        let avg: f64 = self
+            .conn
+            .query_row(
+                "SELECT AVG(loops_to_consensus) FROM trajectories WHERE was_successful = 1",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap_or(0.0);
+
+        Ok(avg)
+    }
+
+    fn count_patterns(&self) -> TetradResult<usize> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM patterns", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    fn count_trajectories(&self) -> TetradResult<usize> {
+        let count: i64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM trajectories", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    fn vote_tallies(&self) -> TetradResult<Vec<VoteTally>> {
+        let rows = self
+            .conn
+            .prepare(
+                "SELECT v.executor, v.vote, t.decision, COUNT(*), SUM(v.score)
+                 FROM votes v JOIN trajectories t ON t.id = v.trajectory_id
+                 GROUP BY v.executor, v.vote, t.decision",
+            )?
+            .query_map([], |row| {
+                Ok(VoteTally {
+                    executor: row.get(0)?,
+                    vote: row.get(1)?,
+                    decision: row.get(2)?,
+                    count: row.get::<_, i64>(3)? as usize,
+                    score_sum: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(rows)
+    }
+
+    fn period_tallies(
+        &self,
+        since: Option<&str>,
+        bucket: StatsBucket,
+    ) -> TetradResult<Vec<PeriodTally>> {
+        // Timestamps RFC 3339 em UTC: os 10 primeiros caracteres são a data.
+        // A semana começa na segunda-feira: recua 6 dias e avança até ela
+        let period = match bucket {
+            StatsBucket::Day => "substr(t.timestamp, 1, 10)",
+            StatsBucket::Week => "date(substr(t.timestamp, 1, 10), '-6 days', 'weekday 1')",
+        };
+        let rows = self
+            .conn
+            .prepare(&format!(
+                "SELECT {period} AS period, COUNT(*),
+                        SUM(CASE WHEN t.was_successful THEN 1 ELSE 0 END),
+                        SUM(CASE WHEN t.decision = 'block' THEN 1 ELSE 0 END),
+                        COALESCE(SUM(t.final_score), 0),
+                        COALESCE(SUM(t.loops_to_consensus), 0),
+                        COALESCE(SUM(v.votes), 0),
+                        COALESCE(SUM(v.score_sum), 0)
+                 FROM trajectories t
+                 LEFT JOIN (SELECT trajectory_id, COUNT(*) AS votes, SUM(score) AS score_sum
+                            FROM votes GROUP BY trajectory_id) v ON v.trajectory_id = t.id
+                 WHERE ?1 IS NULL OR t.timestamp >= ?1
+                 GROUP BY period
+                 ORDER BY period"
+            ))?
+            .query_map(params![since], |row| {
+                Ok(PeriodTally {
+                    period: row.get(0)?,
+                    evaluations: row.get::<_, i64>(1)? as usize,
+                    successes: row.get::<_, i64>(2)? as usize,
+                    blocks: row.get::<_, i64>(3)? as usize,
+                    score_sum: row.get(4)?,
+                    loops_sum: row.get(5)?,
+                    votes: row.get::<_, i64>(6)? as usize,
+                    vote_score_sum: row.get(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        Ok(rows)
+    }
+
+    fn count_legacy_patterns(&self) -> TetradResult<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM patterns WHERE signature_version <> ?",
+            params![SIGNATURE_VERSION],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    fn pattern_texts(&self) -> TetradResult<Vec<(i64, String, Option<String>)>> {
+        let rows = self
+            .conn
+            .prepare("SELECT id, description, solution FROM patterns")?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(rows)
+    }
+
+    fn update_texts(
+        &mut self,
+        id: i64,
+        description: &str,
+        solution: Option<&str>,
+    ) -> TetradResult<()> {
+        self.conn.execute(
+            "UPDATE patterns SET description = ?, solution = ? WHERE id = ?",
+            params![description, solution, id],
+        )?;
+        Ok(())
+    }
+
+    fn merge_duplicates(&mut self) -> TetradResult<usize> {
+        let mut merged = 0;
+
+        // Por enquanto, merge apenas duplicatas exatas
+        let duplicates: Vec<(i64, i64)> = self
+            .conn
+            .prepare(
+                "SELECT p1.id, p2.id
+                 FROM patterns p1
+                 JOIN patterns p2 ON p1.code_signature = p2.code_signature
+                                  AND p1.issue_category = p2.issue_category
+                                  AND p1.id < p2.id",
+            )?
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        for (keep_id, remove_id) in duplicates {
+            // Soma os counts do pattern removido ao mantido
+            self.conn.execute(
+                "UPDATE patterns
+                 SET success_count = success_count + (SELECT success_count FROM patterns WHERE id = ?),
+                     failure_count = failure_count + (SELECT failure_count FROM patterns WHERE id = ?)
+                 WHERE id = ?",
+                params![remove_id, remove_id, keep_id],
+            )?;
+
+            // Remove o duplicado
+            self.conn
+                .execute("DELETE FROM patterns WHERE id = ?", params![remove_id])?;
+            merged += 1;
+        }
+
+        Ok(merged)
+    }
+
+    fn prune_low_quality(&mut self) -> TetradResult<usize> {
+        // Nota: created_at está em formato RFC3339 (ex: 2024-01-15T10:30:00+00:00),
+        // então usamos strftime para gerar comparação compatível
+        let pruned = self.conn.execute(
+            "DELETE FROM patterns
+             WHERE confidence < 0.3
+               AND (success_count + failure_count) < 3
+               AND created_at < strftime('%Y-%m-%dT%H:%M:%S+00:00', datetime('now', '-30 days'))",
+            [],
+        )?;
+
+        Ok(pruned)
+    }
+
+    fn reinforce_high_value(&mut self) -> TetradResult<usize> {
+        let reinforced = self.conn.execute(
+            "UPDATE patterns
+             SET confidence = MIN(confidence * 1.05, 1.0)
+             WHERE (success_count + failure_count) > 10
+               AND confidence > 0.7",
+            [],
+        )?;
+
+        Ok(reinforced)
+    }
+
+    fn recalculate_confidences(&mut self) -> TetradResult<()> {
+        self.conn.execute(
+            "UPDATE patterns
+             SET confidence = CASE
+                 WHEN (success_count + failure_count) = 0 THEN 0.5
+                 ELSE CAST(success_count AS REAL) / (success_count + failure_count)
+             END,
+             pattern_type = CASE
+                 WHEN CAST(success_count AS REAL) / (success_count + failure_count + 0.001) > 0.8 THEN 'good_pattern'
+                 WHEN CAST(failure_count AS REAL) / (success_count + failure_count + 0.001) > 0.8 THEN 'anti_pattern'
+                 ELSE 'ambiguous'
+             END",
+            [],
+        )?;
+
+        Ok(())
+    }
+
+    fn collapse_good_patterns(
+        &mut self,
+        keep: Option<usize>,
+        before: Option<&str>,
+    ) -> TetradResult<usize> {
+        if keep.is_none() && before.is_none() {
+            return Ok(0);
+        }
+
+        // Posição 1 = visto mais recentemente na linguagem
+        let candidates: Vec<(i64, String)> = self
+            .conn
+            .prepare(&format!(
+                "SELECT id, language FROM (
+                     SELECT id, language, last_seen,
+                            ROW_NUMBER() OVER (
+                                PARTITION BY language ORDER BY last_seen DESC, id DESC
+                            ) AS position
+                     FROM patterns
+                     WHERE {ONE_OFF_GOOD_PATTERN}
+                 )
+                 WHERE (?1 IS NOT NULL AND last_seen < ?1)
+                    OR (?2 IS NOT NULL AND position > ?2)"
+            ))?
+            .query_map(params![before, keep.map(|k| k as i64)], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut per_language: HashMap<&str, i64> = HashMap::new();
+        for (_, language) in &candidates {
+            *per_language.entry(language.as_str()).or_default() += 1;
+        }
+        let now = Utc::now().to_rfc3339();
+        for (language, count) in per_language {
+            self.conn.execute(
+                "INSERT INTO good_pattern_summary (language, success_count, last_collapsed)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT(language) DO UPDATE SET
+                     success_count = success_count + excluded.success_count,
+                     last_collapsed = excluded.last_collapsed",
+                params![language, count, now],
+            )?;
+        }
+        for (id, _) in &candidates {
+            self.delete_pattern(*id)?;
+        }
+
+        Ok(candidates.len())
+    }
+
+    fn prune_trajectories(
+        &mut self,
+        keep: Option<usize>,
+        before: Option<&str>,
+    ) -> TetradResult<usize> {
+        let mut pruned = 0;
+        if let Some(before) = before {
+            pruned += self.conn.execute(
+                "DELETE FROM trajectories WHERE timestamp < ?",
+                params![before],
+            )?;
+        }
+        if let Some(keep) = keep {
+            // ids crescem com o registro: os maiores são os mais recentes
+            pruned += self.conn.execute(
+                "DELETE FROM trajectories
+                 WHERE id NOT IN (SELECT id FROM trajectories ORDER BY id DESC LIMIT ?)",
+                params![keep as i64],
+            )?;
+        }
+        // Sem PRAGMA foreign_keys o ON DELETE CASCADE não vale no SQLite
+        if pruned > 0 {
+            self.conn.execute(
+                "DELETE FROM votes WHERE trajectory_id NOT IN (SELECT id FROM trajectories)",
+                [],
+            )?;
+        }
+
+        Ok(pruned)
+    }
+
+    fn all_patterns(&self) -> TetradResult<Vec<Pattern>> {
+        self.query_patterns(
+            &format!(
+                "SELECT {PATTERN_COLUMNS}
+                 FROM patterns
+                 ORDER BY (success_count + failure_count) DESC"
+            ),
+            [],
+        )
+    }
+
+    fn list_patterns(&self, filter: &PatternFilter) -> TetradResult<Vec<Pattern>> {
+        let order = match filter.sort {
+            PatternSort::Confidence => "confidence DESC, (success_count + failure_count) DESC",
+            PatternSort::Usage => "(success_count + failure_count) DESC, confidence DESC",
+        };
+        let sql = format!(
+            "SELECT {PATTERN_COLUMNS} FROM patterns
+             WHERE (?1 IS NULL OR pattern_type = ?1)
+               AND (?2 IS NULL OR lower(language) = lower(?2))
+               AND (?3 IS NULL OR lower(issue_category) = lower(?3))
+               AND (?4 IS NULL OR confidence >= ?4)
+             ORDER BY {order}, id
+             LIMIT ?5"
+        );
+        // LIMIT -1 no SQLite significa sem limite
+        let limit = filter.limit.map_or(-1, |l| l as i64);
+
+        self.query_patterns(
+            &sql,
+            params![
+                filter.pattern_type.as_ref().map(|t| t.to_string()),
+                filter.language,
+                filter.category,
+                filter.min_confidence,
+                limit
+            ],
+        )
+    }
+
+    fn get_pattern(&self, id: i64) -> TetradResult<Option<Pattern>> {
+        let pattern = self
+            .conn
+            .query_row(
+                &format!("SELECT {PATTERN_COLUMNS} FROM patterns WHERE id = ?"),
+                params![id],
+                pattern_from_row,
+            )
+            .optional()?;
+
+        Ok(pattern)
+    }
+
+    fn delete_pattern(&mut self, id: i64) -> TetradResult<bool> {
+        self.conn.execute(
+            "UPDATE trajectories SET pattern_id = NULL WHERE pattern_id = ?",
+            params![id],
+        )?;
+        self.conn.execute(
+            "DELETE FROM pattern_signatures WHERE pattern_id = ?",
+            params![id],
+        )?;
+        let deleted = self
+            .conn
+            .execute("DELETE FROM patterns WHERE id = ?", params![id])?;
+        Ok(deleted > 0)
+    }
+
+    fn checkpoint(&mut self) -> TetradResult<()> {
+        self.conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        Ok(())
+    }
+
+    fn vacuum(&mut self) -> TetradResult<()> {
+        self.conn.execute_batch("VACUUM")?;
+        // Em modo WAL o arquivo reescrito fica no journal até o checkpoint
+        self.checkpoint()
+    }
+
+    fn table_counts(&self) -> TetradResult<Vec<(String, usize)>> {
+        TABLES
+            .iter()
+            .map(|table| {
+                let count: i64 =
+                    self.conn
+                        .query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+                            row.get(0)
+                        })?;
+                Ok((table.to_string(), count as usize))
+            })
+            .collect()
+    }
+    fn add_usage(
+        &mut self,
+        month: &str,
+        evaluations: u64,
+        cost: f64,
+        limits: &UsageLimits,
+    ) -> TetradResult<Option<MonthlyUsage>> {
+        self.conn.execute(
+            "INSERT INTO usage (month) VALUES (?) ON CONFLICT(month) DO NOTHING",
+            params![month],
+        )?;
+        let updated = self.conn.execute(
+            "UPDATE usage SET evaluations = evaluations + ?1, estimated_cost = estimated_cost + ?2
+             WHERE month = ?3
+               AND (?4 IS NULL OR evaluations + ?1 <= ?4)
+               AND (?5 IS NULL OR estimated_cost + ?2 <= ?5)",
+            params![
+                evaluations as i64,
+                cost,
+                month,
+                limits.max_evaluations.map(|max| max as i64),
+                limits.max_cost
+            ],
+        )?;
+
+        if updated == 0 {
+            return Ok(None);
+        }
+        self.usage(month).map(Some)
+    }
+
+    fn usage(&self, month: &str) -> TetradResult<MonthlyUsage> {
+        let usage = self
+            .conn
+            .query_row(
+                "SELECT evaluations, estimated_cost FROM usage WHERE month = ?",
+                params![month],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, f64>(1)?)),
+            )
+            .optional()?
+            .unwrap_or_default();
+
+        Ok(MonthlyUsage {
+            month: month.to_string(),
+            evaluations: usage.0 as u64,
+            estimated_cost: usage.1,
+        })
+    }
+
+    fn add_metrics(&mut self, delta: &MetricCounters) -> TetradResult<()> {
+        self.conn.execute(
+            "INSERT INTO metrics
+                 (evaluation_type, evaluations, passes, revises, blocks, score_sum, recertifications)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+             ON CONFLICT(evaluation_type) DO UPDATE SET
+                 evaluations = evaluations + excluded.evaluations,
+                 passes = passes + excluded.passes,
+                 revises = revises + excluded.revises,
+                 blocks = blocks + excluded.blocks,
+                 score_sum = score_sum + excluded.score_sum,
+                 recertifications = recertifications + excluded.recertifications",
+            params![
+                delta.evaluation_type,
+                delta.evaluations as i64,
+                delta.passes as i64,
+                delta.revises as i64,
+                delta.blocks as i64,
+                delta.score_sum as i64,
+                delta.recertifications as i64
+            ],
+        )?;
+        Ok(())
+    }
+
+    fn metrics(&self) -> TetradResult<Vec<MetricCounters>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT evaluation_type, evaluations, passes, revises, blocks, score_sum, recertifications
+             FROM metrics
+             ORDER BY evaluation_type",
+        )?;
+        let counters = stmt
+            .query_map([], |row| {
+                Ok(MetricCounters {
+                    evaluation_type: row.get(0)?,
+                    evaluations: row.get::<_, i64>(1)? as u64,
+                    passes: row.get::<_, i64>(2)? as u64,
+                    revises: row.get::<_, i64>(3)? as u64,
+                    blocks: row.get::<_, i64>(4)? as u64,
+                    score_sum: row.get::<_, i64>(5)? as u64,
+                    recertifications: row.get::<_, i64>(6)? as u64,
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+        Ok(counters)
+    }
+}
+
+/// Colunas lidas por [`pattern_from_row`], na ordem esperada.
+const PATTERN_COLUMNS: &str = "id, pattern_type, code_signature, language, issue_category,
+    description, solution, success_count, failure_count, confidence,
+    last_seen, created_at, artifact_kind, shape_signature, signature_version";
+
+fn pattern_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Pattern> {
+    Ok(Pattern {
+        id: row.get(0)?,
+        pattern_type: PatternType::from_str(&row.get::<_, String>(1)?),
+        code_signature: row.get(2)?,
+        language: row.get(3)?,
+        issue_category: row.get(4)?,
+        description: row.get(5)?,
+        solution: row.get(6)?,
+        success_count: row.get(7)?,
+        failure_count: row.get(8)?,
+        confidence: row.get(9)?,
+        last_seen: row
+            .get::<_, String>(10)?
+            .parse()
+            .unwrap_or_else(|_| Utc::now()),
+        created_at: row
+            .get::<_, String>(11)?
+            .parse()
+            .unwrap_or_else(|_| Utc::now()),
+        artifact_kind: ArtifactKind::from_str(&row.get::<_, String>(12)?),
+        shape_signature: row.get(13)?,
+        signature_version: row.get(14)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_insert_and_find_pattern() {
+        let dir = tempdir().unwrap();
+        let mut store =
+            SqliteStore::open(&dir.path().join("test.db"), &ReasoningConfig::default()).unwrap();
+        let now = Utc::now();
+        let pattern = Pattern {
+            id: 0,
+            pattern_type: PatternType::AntiPattern,
+            code_signature: "sig".to_string(),
+            language: "rust".to_string(),
+            issue_category: "logic".to_string(),
+            description: "Unchecked unwrap".to_string(),
+            solution: None,
+            success_count: 0,
+            failure_count: 2,
+            confidence: 0.0,
+            last_seen: now,
+            created_at: now,
+            artifact_kind: ArtifactKind::Code,
+            shape_signature: Some("shape".to_string()),
+            signature_version: SIGNATURE_VERSION,
+        };
+
+        let id = store.insert_pattern(&pattern).unwrap();
+        let found = store.find_pattern("sig", "logic").unwrap().unwrap();
+        assert_eq!(found.id, id);
+        assert_eq!(found.failure_count, 2);
+        assert!(store.find_pattern("sig", "security").unwrap().is_none());
+
+        assert!(store
+            .record_outcome("sig", "logic", true, &now.to_rfc3339())
+            .unwrap());
+        assert!(!store
+            .record_outcome("other", "logic", true, &now.to_rfc3339())
+            .unwrap());
+        assert_eq!(store.get_pattern(id).unwrap().unwrap().success_count, 1);
+    }
+
+    #[test]
+    fn test_migrate_normalizes_languages() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let config = ReasoningConfig::default();
+
+        {
+            let store = SqliteStore::open(&path, &config).unwrap();
+            for (signature, language) in [
+                ("a", "Rust"),
+                ("b", "rs"),
+                ("c", "rust"),
+                ("d", "C++"),
+                ("e", "COBOL"),
+                ("f", "any"),
+            ] {
+                store
+                    .connection()
+                    .execute(
+                        "INSERT INTO patterns (pattern_type, code_signature, language,
+                         issue_category, description, last_seen, created_at)
+                         VALUES ('anti_pattern', ?1, ?2, 'logic', 'x', '', '')",
+                        params![signature, language],
+                    )
+                    .unwrap();
+            }
+        }
+
+        let store = SqliteStore::open(&path, &config).unwrap();
+        let languages: Vec<(String, String)> = store
+            .connection()
+            .prepare("SELECT code_signature, language FROM patterns ORDER BY code_signature")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let languages: Vec<&str> = languages.iter().map(|(_, l)| l.as_str()).collect();
+        assert_eq!(languages, ["rust", "rust", "rust", "cpp", "other", "any"]);
+
+        let stats = store.language_stats().unwrap();
+        assert_eq!(stats.len(), 4);
+    }
+}
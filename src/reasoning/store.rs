@@ -0,0 +1,399 @@
+//! Armazenamento de patterns e trajetórias do ReasoningBank.
+//!
+//! O [`ReasoningBank`](super::ReasoningBank) concentra a lógica do ciclo
+//! RETRIEVE → JUDGE → DISTILL → CONSOLIDATE; toda a persistência passa pelo
+//! trait [`PatternStore`]. O backend padrão é o [`SqliteStore`], um arquivo
+//! local por máquina; com a feature `postgres`, o [`PostgresStore`] permite
+//! um banco compartilhado pela equipe.
+
+use std::any::Any;
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::responses::{Decision, Vote};
+use crate::TetradResult;
+
+use super::bank::{ArtifactKind, LanguageStats, Pattern, PatternFilter, PatternType, StatsBucket};
+use super::metrics::MetricCounters;
+use super::usage::{MonthlyUsage, UsageLimits};
+
+#[cfg(feature = "postgres")]
+pub use super::postgres::PostgresStore;
+pub use super::sqlite::SqliteStore;
+
+/// Uma avaliação registrada na fase JUDGE.
+#[derive(Debug, Clone)]
+pub struct TrajectoryRecord<'a> {
+    pub request_id: &'a str,
+    /// Assinatura do código avaliado.
+    pub code_hash: &'a str,
+    pub initial_score: u8,
+    pub final_score: u8,
+    pub loops_to_consensus: u32,
+    pub was_successful: bool,
+    pub decision: Decision,
+    /// Momento da avaliação (RFC 3339).
+    pub timestamp: &'a str,
+}
+
+/// Decisão de uma trajetória, usada no relatório de flakiness.
+#[derive(Debug, Clone)]
+pub struct DecisionRecord {
+    pub code_hash: String,
+    pub request_id: String,
+    pub decision: String,
+    /// Momento da avaliação (RFC 3339).
+    pub timestamp: String,
+}
+
+/// Voto individual de um executor, guardado com a trajetória.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredVote {
+    pub executor: String,
+    pub vote: Vote,
+    pub score: u8,
+    pub reasoning: String,
+    pub issues: Vec<String>,
+    pub suggestions: Vec<String>,
+}
+
+/// Votos agrupados por executor, voto e decisão final da trajetória.
+#[derive(Debug, Clone)]
+pub struct VoteTally {
+    pub executor: String,
+    pub vote: String,
+    /// Decisão final; `None` em trajetórias antigas, sem decisão.
+    pub decision: Option<String>,
+    pub count: usize,
+    /// Soma dos scores dos votos do grupo.
+    pub score_sum: i64,
+}
+
+/// Trajetórias de um período, somadas para as tendências de `tetrad stats`.
+#[derive(Debug, Clone)]
+pub struct PeriodTally {
+    /// Primeiro dia do período (`YYYY-MM-DD`).
+    pub period: String,
+    pub evaluations: usize,
+    /// Avaliações marcadas como bem-sucedidas.
+    pub successes: usize,
+    /// Avaliações com decisão BLOCK.
+    pub blocks: usize,
+    /// Soma dos scores finais.
+    pub score_sum: i64,
+    /// Soma dos loops até o consenso.
+    pub loops_sum: i64,
+    /// Votos guardados das trajetórias do período.
+    pub votes: usize,
+    /// Soma dos scores desses votos.
+    pub vote_score_sum: i64,
+}
+
+/// Persistência de patterns e trajetórias.
+///
+/// As operações de escrita executadas entre [`begin_write`](Self::begin_write)
+/// e [`commit`](Self::commit) devem ser atômicas: o banco chama
+/// [`rollback`](Self::rollback) se qualquer uma falhar. Datas são gravadas
+/// como texto RFC 3339, então comparações de texto seguem a ordem temporal.
+pub trait PatternStore: Send {
+    /// Nome do backend ("sqlite", "postgres"), usado em logs.
+    fn backend(&self) -> &'static str;
+
+    /// Acesso ao tipo concreto (testes e diagnósticos específicos do backend).
+    fn as_any(&self) -> &dyn Any;
+
+    // ─── Transações ──────────────────────────────────────────────────────────
+
+    /// Inicia uma transação de escrita.
+    fn begin_write(&mut self) -> TetradResult<()>;
+
+    /// Confirma a transação aberta por [`begin_write`](Self::begin_write).
+    fn commit(&mut self) -> TetradResult<()>;
+
+    /// Desfaz a transação aberta; erros são ignorados.
+    fn rollback(&mut self);
+
+    // ─── RETRIEVE ────────────────────────────────────────────────────────────
+
+    /// Patterns com a assinatura exata.
+    fn find_by_signature(&self, signature: &str, kind: ArtifactKind) -> TetradResult<Vec<Pattern>>;
+
+    /// Patterns com a assinatura de forma.
+    fn find_by_shape(&self, shape: &str, kind: ArtifactKind) -> TetradResult<Vec<Pattern>>;
+
+    /// Até 10 patterns da linguagem (ou `any`) cuja categoria ou descrição
+    /// contém `keyword`, por confiança.
+    fn find_by_keyword(
+        &self,
+        keyword: &str,
+        language: &str,
+        kind: ArtifactKind,
+    ) -> TetradResult<Vec<Pattern>>;
+
+    /// Patterns ligados à assinatura de um trecho (função ou bloco).
+    fn find_by_sub_signature(
+        &self,
+        sub_signature: &str,
+        kind: ArtifactKind,
+    ) -> TetradResult<Vec<Pattern>>;
+
+    // ─── JUDGE ───────────────────────────────────────────────────────────────
+
+    /// Registra uma trajetória e retorna seu id.
+    fn insert_trajectory(&mut self, trajectory: &TrajectoryRecord<'_>) -> TetradResult<i64>;
+
+    /// Registra os votos individuais de uma trajetória.
+    fn insert_votes(&mut self, trajectory_id: i64, votes: &[StoredVote]) -> TetradResult<()>;
+
+    /// Votos de uma trajetória, em ordem de registro.
+    fn votes_for_trajectory(&self, trajectory_id: i64) -> TetradResult<Vec<StoredVote>>;
+
+    /// Trajetória mais recente do código desde `since` com decisão diferente
+    /// de `decision`: retorna o `request_id` e a decisão.
+    fn latest_divergent(
+        &self,
+        code_hash: &str,
+        since: &str,
+        decision: Decision,
+    ) -> TetradResult<Option<(String, Decision)>>;
+
+    /// Trajetórias com decisão, ordenadas por código e ordem de registro.
+    fn decision_history(&self) -> TetradResult<Vec<DecisionRecord>>;
+
+    /// Soma um sucesso ou uma falha ao pattern e recalcula sua confiança.
+    ///
+    /// Retorna `false` se o pattern não existir.
+    fn record_outcome(
+        &mut self,
+        signature: &str,
+        category: &str,
+        was_successful: bool,
+        now: &str,
+    ) -> TetradResult<bool>;
+
+    /// Soma um sucesso ao pattern de categoria `success` e o marca como
+    /// GoodPattern. Retorna `false` se o pattern não existir.
+    fn record_good_outcome(&mut self, signature: &str, now: &str) -> TetradResult<bool>;
+
+    /// Soma um sucesso ao GoodPattern com a mesma forma, linguagem e tipo de
+    /// artefato. Retorna `false` se não houver um.
+    fn record_good_shape(
+        &mut self,
+        shape: &str,
+        language: &str,
+        kind: ArtifactKind,
+        now: &str,
+    ) -> TetradResult<bool>;
+
+    /// Insere um pattern (o `id` é ignorado) e retorna o id gerado.
+    fn insert_pattern(&mut self, pattern: &Pattern) -> TetradResult<i64>;
+
+    /// Busca o pattern pela chave única (assinatura, categoria).
+    fn find_pattern(&self, signature: &str, category: &str) -> TetradResult<Option<Pattern>>;
+
+    /// Liga o pattern à assinatura de um trecho; ligações repetidas são
+    /// ignoradas.
+    fn link_sub_signature(&mut self, sub_signature: &str, pattern_id: i64) -> TetradResult<()>;
+
+    /// Troca a descrição do pattern.
+    fn update_description(
+        &mut self,
+        signature: &str,
+        category: &str,
+        description: &str,
+    ) -> TetradResult<()>;
+
+    /// Soma as contagens de um pattern importado ao existente.
+    fn merge_counts(&mut self, pattern: &Pattern) -> TetradResult<()>;
+
+    // ─── DISTILL ─────────────────────────────────────────────────────────────
+
+    /// Patterns do tipo mais avaliados, depois por confiança.
+    fn top_patterns(&self, pattern_type: PatternType, limit: usize) -> TetradResult<Vec<Pattern>>;
+
+    /// Número de anti-patterns por categoria.
+    fn problematic_categories(&self) -> TetradResult<HashMap<String, usize>>;
+
+    /// Estatísticas dos patterns por linguagem.
+    fn language_stats(&self) -> TetradResult<HashMap<String, LanguageStats>>;
+
+    /// Média de loops até o consenso nas trajetórias bem-sucedidas.
+    fn average_loops_to_consensus(&self) -> TetradResult<f64>;
+
+    fn count_patterns(&self) -> TetradResult<usize>;
+
+    fn count_trajectories(&self) -> TetradResult<usize>;
+
+    /// Votos guardados, agrupados por executor, voto e decisão final.
+    fn vote_tallies(&self) -> TetradResult<Vec<VoteTally>>;
+
+    /// Trajetórias a partir do dia `since` (`YYYY-MM-DD`), agrupadas por
+    /// `bucket` e ordenadas pelo período.
+    fn period_tallies(
+        &self,
+        since: Option<&str>,
+        bucket: StatsBucket,
+    ) -> TetradResult<Vec<PeriodTally>>;
+
+    /// Patterns com assinaturas de uma versão anterior do algoritmo.
+    fn count_legacy_patterns(&self) -> TetradResult<usize>;
+
+    // ─── CONSOLIDATE ─────────────────────────────────────────────────────────
+
+    /// Id, descrição e solução de todos os patterns.
+    fn pattern_texts(&self) -> TetradResult<Vec<(i64, String, Option<String>)>>;
+
+    /// Troca a descrição e a solução do pattern.
+    fn update_texts(
+        &mut self,
+        id: i64,
+        description: &str,
+        solution: Option<&str>,
+    ) -> TetradResult<()>;
+
+    /// Mescla patterns com a mesma assinatura e categoria, somando as
+    /// contagens no mais antigo. Retorna quantos foram removidos.
+    fn merge_duplicates(&mut self) -> TetradResult<usize>;
+
+    /// Remove patterns com confiança < 0.3, menos de 3 avaliações e criados
+    /// há mais de 30 dias.
+    fn prune_low_quality(&mut self) -> TetradResult<usize>;
+
+    /// Aumenta em 5% (até 1.0) a confiança de patterns com mais de 10
+    /// avaliações e confiança > 0.7.
+    fn reinforce_high_value(&mut self) -> TetradResult<usize>;
+
+    /// Recalcula confiança e tipo de todos os patterns a partir das contagens.
+    fn recalculate_confidences(&mut self) -> TetradResult<()>;
+
+    /// Recolhe GoodPatterns avulsos (um sucesso, nenhuma falha) nos
+    /// contadores por linguagem de `good_pattern_summary`: os vistos pela
+    /// última vez antes de `before` (se informado) e, em cada linguagem,
+    /// todos menos os `keep` mais recentes (se informado). Retorna quantos
+    /// foram recolhidos.
+    fn collapse_good_patterns(
+        &mut self,
+        keep: Option<usize>,
+        before: Option<&str>,
+    ) -> TetradResult<usize>;
+
+    /// Remove as trajetórias registradas antes de `before` (se informado) e,
+    /// das restantes, todas menos as `keep` mais recentes (se informado),
+    /// junto com seus votos. Retorna quantas trajetórias foram removidas.
+    fn prune_trajectories(
+        &mut self,
+        keep: Option<usize>,
+        before: Option<&str>,
+    ) -> TetradResult<usize>;
+
+    // ─── Consultas e gestão ──────────────────────────────────────────────────
+
+    /// Todos os patterns, mais avaliados primeiro.
+    fn all_patterns(&self) -> TetradResult<Vec<Pattern>>;
+
+    /// Patterns que atendem ao filtro.
+    fn list_patterns(&self, filter: &PatternFilter) -> TetradResult<Vec<Pattern>>;
+
+    fn get_pattern(&self, id: i64) -> TetradResult<Option<Pattern>>;
+
+    /// Remove o pattern e suas assinaturas de trecho, e solta as trajetórias
+    /// que apontavam para ele.
+    ///
+    /// Retorna `false` se o pattern não existir.
+    fn delete_pattern(&mut self, id: i64) -> TetradResult<bool>;
+
+    /// Grava no arquivo principal o que ainda está só no journal (WAL), para
+    /// que um encerramento abrupto do processo não dependa da recuperação.
+    fn checkpoint(&mut self) -> TetradResult<()> {
+        Ok(())
+    }
+
+    /// Devolve ao sistema o espaço liberado por remoções. Não pode rodar
+    /// dentro de uma transação.
+    fn vacuum(&mut self) -> TetradResult<()> {
+        Ok(())
+    }
+
+    /// Número de linhas de cada tabela, por nome de tabela.
+    fn table_counts(&self) -> TetradResult<Vec<(String, usize)>>;
+
+    // ─── Uso ─────────────────────────────────────────────────────────────────
+
+    /// Soma avaliações e custo ao uso de `month` (`YYYY-MM`).
+    ///
+    /// A soma só acontece se o total couber em `limits`; caso contrário nada
+    /// muda e o retorno é `None`. A verificação e a soma são uma única
+    /// instrução, então escritores concorrentes não ultrapassam o limite.
+    fn add_usage(
+        &mut self,
+        month: &str,
+        evaluations: u64,
+        cost: f64,
+        limits: &UsageLimits,
+    ) -> TetradResult<Option<MonthlyUsage>>;
+
+    /// Uso acumulado em `month` (zerado se não houver registro).
+    fn usage(&self, month: &str) -> TetradResult<MonthlyUsage>;
+
+    // ─── Métricas ────────────────────────────────────────────────────────────
+
+    /// Soma `delta` aos contadores de `delta.evaluation_type`.
+    fn add_metrics(&mut self, delta: &MetricCounters) -> TetradResult<()>;
+
+    /// Contadores de todos os tipos de avaliação, ordenados pelo tipo.
+    fn metrics(&self) -> TetradResult<Vec<MetricCounters>>;
+}
+
+/// Tabelas do esquema, na ordem em que [`PatternStore::table_counts`] as
+/// informa.
+pub(super) const TABLES: [&str; 7] = [
+    "patterns",
+    "trajectories",
+    "votes",
+    "pattern_signatures",
+    "usage",
+    "good_pattern_summary",
+    "metrics",
+];
+
+/// Filtro SQL dos GoodPatterns avulsos, que [`PatternStore::collapse_good_patterns`]
+/// recolhe.
+pub(super) const ONE_OFF_GOOD_PATTERN: &str = "pattern_type = 'good_pattern'
+       AND issue_category = 'success'
+       AND success_count = 1
+       AND failure_count = 0";
+
+pub(super) fn decision_to_str(decision: Decision) -> &'static str {
+    match decision {
+        Decision::Pass => "pass",
+        Decision::Revise => "revise",
+        Decision::Block => "block",
+    }
+}
+
+pub(super) fn decision_from_str(value: &str) -> Option<Decision> {
+    match value {
+        "pass" => Some(Decision::Pass),
+        "revise" => Some(Decision::Revise),
+        "block" => Some(Decision::Block),
+        _ => None,
+    }
+}
+
+pub(super) fn vote_to_str(vote: Vote) -> &'static str {
+    match vote {
+        Vote::Pass => "pass",
+        Vote::Warn => "warn",
+        Vote::Fail => "fail",
+    }
+}
+
+pub(super) fn vote_from_str(value: &str) -> Option<Vote> {
+    match value {
+        "pass" => Some(Vote::Pass),
+        "warn" => Some(Vote::Warn),
+        "fail" => Some(Vote::Fail),
+        _ => None,
+    }
+}
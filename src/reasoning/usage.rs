@@ -0,0 +1,370 @@
+//! Contabilidade mensal de avaliações para o orçamento (`[general.budget]`).
+//!
+//! Cada avaliação soma 1 ao contador do mês (UTC, chave `YYYY-MM`) e o custo
+//! estimado das chamadas aos executores. O uso fica no ReasoningBank, então
+//! o servidor MCP e a CLI dividem o mesmo orçamento. A soma é condicional e
+//! atômica no banco: avaliações concorrentes nunca ultrapassam o limite.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::types::config::{BudgetAction, BudgetConfig};
+use crate::TetradResult;
+
+use super::bank::ReasoningBank;
+
+/// Uso acumulado em um mês.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MonthlyUsage {
+    /// Mês (`YYYY-MM`, UTC).
+    pub month: String,
+
+    /// Avaliações contabilizadas.
+    pub evaluations: u64,
+
+    /// Custo estimado acumulado.
+    pub estimated_cost: f64,
+}
+
+/// Limites aplicados a uma soma de uso (`None` = sem limite).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageLimits {
+    /// Máximo de avaliações no mês.
+    pub max_evaluations: Option<u64>,
+
+    /// Custo estimado máximo no mês.
+    pub max_cost: Option<f64>,
+}
+
+impl UsageLimits {
+    /// Limites configurados no orçamento.
+    pub fn from_budget(budget: &BudgetConfig) -> Self {
+        Self {
+            max_evaluations: budget.max_evaluations_per_month,
+            max_cost: budget.max_estimated_cost,
+        }
+    }
+}
+
+/// Situação do orçamento no mês, exibida em `tetrad_status` e `tetrad stats`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BudgetStatus {
+    /// Uso acumulado no mês.
+    #[serde(flatten)]
+    pub usage: MonthlyUsage,
+
+    /// Limite de avaliações configurado.
+    pub max_evaluations: Option<u64>,
+
+    /// Limite de custo configurado.
+    pub max_estimated_cost: Option<f64>,
+
+    /// Avaliações restantes no mês.
+    pub remaining_evaluations: Option<u64>,
+
+    /// Custo restante no mês.
+    pub remaining_cost: Option<f64>,
+
+    /// Se algum limite foi atingido.
+    pub exhausted: bool,
+}
+
+impl BudgetStatus {
+    /// Compara o uso com os limites do orçamento.
+    pub fn new(usage: MonthlyUsage, budget: &BudgetConfig) -> Self {
+        let remaining_evaluations = budget
+            .max_evaluations_per_month
+            .map(|max| max.saturating_sub(usage.evaluations));
+        let remaining_cost = budget
+            .max_estimated_cost
+            .map(|max| (max - usage.estimated_cost).max(0.0));
+        let exhausted =
+            remaining_evaluations == Some(0) || remaining_cost.is_some_and(|cost| cost <= 0.0);
+
+        Self {
+            usage,
+            max_evaluations: budget.max_evaluations_per_month,
+            max_estimated_cost: budget.max_estimated_cost,
+            remaining_evaluations,
+            remaining_cost,
+            exhausted,
+        }
+    }
+
+    /// Descrição curta do uso, para mensagens de erro e logs.
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(max) = self.max_evaluations {
+            parts.push(format!("{}/{} evaluations", self.usage.evaluations, max));
+        }
+        if let Some(max) = self.max_estimated_cost {
+            parts.push(format!(
+                "{:.2}/{:.2} estimated cost",
+                self.usage.estimated_cost, max
+            ));
+        }
+        format!("{} in {}", parts.join(", "), self.usage.month)
+    }
+}
+
+/// Resultado de contabilizar uma avaliação.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetCharge {
+    /// Contabilizada dentro do orçamento.
+    Charged(BudgetStatus),
+
+    /// Contabilizada além do orçamento (ação `warn` ou `--override-budget`).
+    OverBudget(BudgetStatus),
+
+    /// Recusada: o orçamento não comporta a avaliação; nada foi somado.
+    Refused(BudgetStatus),
+}
+
+/// Chave do mês de `now` (`YYYY-MM`, UTC).
+pub fn month_key(now: DateTime<Utc>) -> String {
+    now.format("%Y-%m").to_string()
+}
+
+impl ReasoningBank {
+    /// Situação do orçamento no mês de `now`.
+    pub fn budget_status(
+        &self,
+        budget: &BudgetConfig,
+        now: DateTime<Utc>,
+    ) -> TetradResult<BudgetStatus> {
+        let usage = self.store.usage(&month_key(now))?;
+        Ok(BudgetStatus::new(usage, budget))
+    }
+
+    /// Contabiliza `evaluations` avaliações de custo total `cost` no mês de `now`.
+    ///
+    /// Com a ação `warn`, ou `require_override` e `override_budget`, a soma é
+    /// sempre feita; caso contrário ela só acontece se couber no orçamento.
+    pub fn charge_usage(
+        &mut self,
+        budget: &BudgetConfig,
+        evaluations: u64,
+        cost: f64,
+        override_budget: bool,
+        now: DateTime<Utc>,
+    ) -> TetradResult<BudgetCharge> {
+        let month = month_key(now);
+        let enforced = budget.is_limited()
+            && match budget.action {
+                BudgetAction::Warn => false,
+                BudgetAction::RequireOverride => !override_budget,
+                BudgetAction::Refuse => true,
+            };
+        let limits = if enforced {
+            UsageLimits::from_budget(budget)
+        } else {
+            UsageLimits::default()
+        };
+
+        let added = self.in_write_transaction(|bank| {
+            bank.store.add_usage(&month, evaluations, cost, &limits)
+        })?;
+
+        Ok(match added {
+            None => {
+                let usage = self.store.usage(&month)?;
+                BudgetCharge::Refused(BudgetStatus::new(usage, budget))
+            }
+            Some(usage) => {
+                let status = BudgetStatus::new(usage, budget);
+                let over = UsageLimits::from_budget(budget);
+                let exceeded = over
+                    .max_evaluations
+                    .is_some_and(|max| status.usage.evaluations > max)
+                    || over
+                        .max_cost
+                        .is_some_and(|max| status.usage.estimated_cost > max);
+                if exceeded {
+                    BudgetCharge::OverBudget(status)
+                } else {
+                    BudgetCharge::Charged(status)
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::ReasoningConfig;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn bank(dir: &TempDir) -> ReasoningBank {
+        ReasoningBank::new_with_config(&dir.path().join("usage.db"), &ReasoningConfig::default())
+            .unwrap()
+    }
+
+    fn budget(max_evaluations: u64, action: BudgetAction) -> BudgetConfig {
+        BudgetConfig {
+            max_evaluations_per_month: Some(max_evaluations),
+            action,
+            ..Default::default()
+        }
+    }
+
+    fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_month_key() {
+        assert_eq!(month_key(at(2026, 3, 31, 23)), "2026-03");
+        assert_eq!(month_key(at(2026, 4, 1, 0)), "2026-04");
+    }
+
+    #[test]
+    fn test_refuse_stops_at_limit() {
+        let dir = TempDir::new().unwrap();
+        let mut bank = bank(&dir);
+        let budget = budget(2, BudgetAction::Refuse);
+        let now = at(2026, 3, 10, 12);
+
+        assert!(matches!(
+            bank.charge_usage(&budget, 1, 0.0, false, now).unwrap(),
+            BudgetCharge::Charged(_)
+        ));
+        let BudgetCharge::Charged(status) = bank.charge_usage(&budget, 1, 0.0, false, now).unwrap()
+        else {
+            panic!("second evaluation should fit the budget");
+        };
+        assert!(status.exhausted);
+        assert_eq!(status.remaining_evaluations, Some(0));
+
+        // A terceira é recusada e não soma nada
+        let BudgetCharge::Refused(status) = bank.charge_usage(&budget, 1, 0.0, false, now).unwrap()
+        else {
+            panic!("third evaluation should be refused");
+        };
+        assert_eq!(status.usage.evaluations, 2);
+        assert_eq!(
+            bank.budget_status(&budget, now).unwrap().usage.evaluations,
+            2
+        );
+    }
+
+    #[test]
+    fn test_month_rollover_resets_budget() {
+        let dir = TempDir::new().unwrap();
+        let mut bank = bank(&dir);
+        let budget = budget(1, BudgetAction::Refuse);
+
+        let march = at(2026, 3, 31, 23);
+        assert!(matches!(
+            bank.charge_usage(&budget, 1, 0.0, false, march).unwrap(),
+            BudgetCharge::Charged(_)
+        ));
+        assert!(matches!(
+            bank.charge_usage(&budget, 1, 0.0, false, march).unwrap(),
+            BudgetCharge::Refused(_)
+        ));
+
+        // Um novo mês começa do zero; março continua registrado
+        let april = at(2026, 4, 1, 0);
+        let BudgetCharge::Charged(status) =
+            bank.charge_usage(&budget, 1, 0.0, false, april).unwrap()
+        else {
+            panic!("april should have a fresh budget");
+        };
+        assert_eq!(status.usage.month, "2026-04");
+        assert_eq!(status.usage.evaluations, 1);
+        assert_eq!(
+            bank.budget_status(&budget, march)
+                .unwrap()
+                .usage
+                .evaluations,
+            1
+        );
+    }
+
+    #[test]
+    fn test_warn_and_override_keep_counting() {
+        let dir = TempDir::new().unwrap();
+        let mut bank = bank(&dir);
+        let now = at(2026, 3, 10, 12);
+
+        let warn = budget(1, BudgetAction::Warn);
+        bank.charge_usage(&warn, 1, 0.0, false, now).unwrap();
+        let BudgetCharge::OverBudget(status) =
+            bank.charge_usage(&warn, 1, 0.0, false, now).unwrap()
+        else {
+            panic!("warn should count the evaluation over budget");
+        };
+        assert_eq!(status.usage.evaluations, 2);
+        assert!(status.exhausted);
+
+        let require = budget(1, BudgetAction::RequireOverride);
+        assert!(matches!(
+            bank.charge_usage(&require, 1, 0.0, false, now).unwrap(),
+            BudgetCharge::Refused(_)
+        ));
+        assert!(matches!(
+            bank.charge_usage(&require, 1, 0.0, true, now).unwrap(),
+            BudgetCharge::OverBudget(_)
+        ));
+    }
+
+    #[test]
+    fn test_cost_limit() {
+        let dir = TempDir::new().unwrap();
+        let mut bank = bank(&dir);
+        let budget = BudgetConfig {
+            max_estimated_cost: Some(1.0),
+            ..Default::default()
+        };
+        let now = at(2026, 3, 10, 12);
+
+        assert!(matches!(
+            bank.charge_usage(&budget, 1, 0.6, false, now).unwrap(),
+            BudgetCharge::Charged(_)
+        ));
+        let BudgetCharge::Refused(status) = bank.charge_usage(&budget, 1, 0.6, false, now).unwrap()
+        else {
+            panic!("cost over the limit should be refused");
+        };
+        assert!((status.usage.estimated_cost - 0.6).abs() < 1e-9);
+        assert!((status.remaining_cost.unwrap() - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_concurrent_charges_never_exceed_limit() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("usage.db");
+        let budget = budget(10, BudgetAction::Refuse);
+        let now = at(2026, 3, 10, 12);
+
+        // Cada thread abre sua própria conexão, como processos separados
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let path = path.clone();
+                let budget = budget.clone();
+                std::thread::spawn(move || {
+                    let mut bank =
+                        ReasoningBank::new_with_config(&path, &ReasoningConfig::default()).unwrap();
+                    (0..5)
+                        .filter(|_| {
+                            matches!(
+                                bank.charge_usage(&budget, 1, 0.0, false, now).unwrap(),
+                                BudgetCharge::Charged(_)
+                            )
+                        })
+                        .count()
+                })
+            })
+            .collect();
+
+        let charged: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+        assert_eq!(charged, 10);
+        let bank = bank(&dir);
+        assert_eq!(
+            bank.budget_status(&budget, now).unwrap().usage.evaluations,
+            10
+        );
+    }
+}
@@ -0,0 +1,263 @@
+//! Builders para testes (feature `test-util`).
+//!
+//! [`EvaluationResult`] tem dezenas de campos e cada campo novo quebrava os
+//! literais espalhados pelos testes. Os builders partem de valores padrão
+//! estáveis e só pedem o que o teste quer verificar, então a evolução do
+//! esquema fica concentrada aqui.
+//!
+//! Os testes do próprio crate usam este módulo; quem embute o Tetrad pode
+//! usá-lo nos seus ativando a feature:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! tetrad = { version = "0.1", features = ["test-util"] }
+//! ```
+//!
+//! ```
+//! use tetrad::test_support::{RequestBuilder, ResultBuilder, VoteBuilder};
+//! use tetrad::types::responses::{Decision, Finding, Severity};
+//!
+//! let request = RequestBuilder::new().code("x = eval(input())").language("python").build();
+//! let result = ResultBuilder::pass(85)
+//!     .with_vote(VoteBuilder::pass("Codex", 90))
+//!     .with_vote(VoteBuilder::warn("Gemini", 70).issue("eval on user input"))
+//!     .with_finding(Finding::new(Severity::Warning, "security", "eval on user input"))
+//!     .build();
+//!
+//! assert_eq!(request.language.as_str(), "python");
+//! assert_eq!(result.decision, Decision::Pass);
+//! assert_eq!(result.votes.len(), 2);
+//! ```
+
+use std::collections::HashMap;
+
+use crate::types::language::Language;
+use crate::types::requests::{EvaluationRequest, EvaluationType};
+use crate::types::responses::{Decision, EvaluationResult, Finding, ModelVote, Vote};
+
+/// `request_id` padrão dos builders.
+pub const TEST_REQUEST_ID: &str = "test-123";
+
+/// Código padrão do [`RequestBuilder`].
+pub const TEST_CODE: &str = "fn main() {}";
+
+/// Constrói um [`EvaluationResult`].
+///
+/// Padrões: `request_id` [`TEST_REQUEST_ID`], feedback `"Test feedback"`,
+/// consenso só em Pass, sem votos nem findings.
+#[derive(Debug, Clone)]
+pub struct ResultBuilder {
+    result: EvaluationResult,
+}
+
+impl ResultBuilder {
+    /// Resultado com a decisão e o score dados.
+    pub fn new(decision: Decision, score: u8) -> Self {
+        let mut result = EvaluationResult::success(TEST_REQUEST_ID, score, "Test feedback");
+        result.decision = decision;
+        result.consensus_achieved = decision == Decision::Pass;
+        Self { result }
+    }
+
+    pub fn pass(score: u8) -> Self {
+        Self::new(Decision::Pass, score)
+    }
+
+    pub fn revise(score: u8) -> Self {
+        Self::new(Decision::Revise, score)
+    }
+
+    pub fn block(score: u8) -> Self {
+        Self::new(Decision::Block, score)
+    }
+
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.result.request_id = request_id.into();
+        self
+    }
+
+    pub fn consensus(mut self, achieved: bool) -> Self {
+        self.result.consensus_achieved = achieved;
+        self
+    }
+
+    pub fn confidence(mut self, confidence: f64) -> Self {
+        self.result.confidence = confidence;
+        self
+    }
+
+    pub fn feedback(mut self, feedback: impl Into<String>) -> Self {
+        self.result.feedback = feedback.into();
+        self
+    }
+
+    /// Acrescenta um voto, indexado pelo nome do executor.
+    pub fn with_vote(mut self, vote: impl Into<ModelVote>) -> Self {
+        let vote = vote.into();
+        self.result.votes.insert(vote.executor.clone(), vote);
+        self
+    }
+
+    pub fn with_votes(self, votes: impl IntoIterator<Item = impl Into<ModelVote>>) -> Self {
+        votes.into_iter().fold(self, Self::with_vote)
+    }
+
+    pub fn with_finding(mut self, finding: Finding) -> Self {
+        self.result.findings.push(finding);
+        self
+    }
+
+    pub fn with_findings(mut self, findings: impl IntoIterator<Item = Finding>) -> Self {
+        self.result.findings.extend(findings);
+        self
+    }
+
+    /// Marca os executores como sem voto real (resultado degradado).
+    pub fn degraded<S: Into<String>>(mut self, executors: impl IntoIterator<Item = S>) -> Self {
+        self.result.degraded = true;
+        self.result.degraded_executors = executors.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn build(self) -> EvaluationResult {
+        self.result
+    }
+}
+
+impl From<ResultBuilder> for EvaluationResult {
+    fn from(builder: ResultBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Constrói um [`ModelVote`].
+#[derive(Debug, Clone)]
+pub struct VoteBuilder {
+    vote: ModelVote,
+}
+
+impl VoteBuilder {
+    pub fn new(executor: impl Into<String>, vote: Vote, score: u8) -> Self {
+        Self {
+            vote: ModelVote::new(executor, vote, score),
+        }
+    }
+
+    pub fn pass(executor: impl Into<String>, score: u8) -> Self {
+        Self::new(executor, Vote::Pass, score)
+    }
+
+    pub fn warn(executor: impl Into<String>, score: u8) -> Self {
+        Self::new(executor, Vote::Warn, score)
+    }
+
+    pub fn fail(executor: impl Into<String>, score: u8) -> Self {
+        Self::new(executor, Vote::Fail, score)
+    }
+
+    pub fn reasoning(mut self, reasoning: impl Into<String>) -> Self {
+        self.vote.reasoning = reasoning.into();
+        self
+    }
+
+    pub fn issue(mut self, issue: impl Into<String>) -> Self {
+        self.vote.issues.push(issue.into());
+        self
+    }
+
+    /// Acrescenta um issue com as linhas afetadas.
+    pub fn issue_at(mut self, issue: impl Into<String>, lines: Vec<u32>) -> Self {
+        let issue = issue.into();
+        self.vote.lines.insert(issue.clone(), lines);
+        self.vote.issues.push(issue);
+        self
+    }
+
+    pub fn suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.vote.suggestions.push(suggestion.into());
+        self
+    }
+
+    pub fn build(self) -> ModelVote {
+        self.vote
+    }
+}
+
+impl From<VoteBuilder> for ModelVote {
+    fn from(builder: VoteBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Votos simples (executor, voto, score), indexados pelo nome do executor.
+pub fn vote_map(votes: &[(&str, Vote, u8)]) -> HashMap<String, ModelVote> {
+    votes
+        .iter()
+        .map(|&(executor, vote, score)| {
+            (executor.to_string(), ModelVote::new(executor, vote, score))
+        })
+        .collect()
+}
+
+/// Constrói um [`EvaluationRequest`].
+///
+/// Padrões: [`TEST_CODE`] em Rust, tipo `code` e `request_id`
+/// [`TEST_REQUEST_ID`] (fixo, ao contrário de [`EvaluationRequest::new`]).
+#[derive(Debug, Clone)]
+pub struct RequestBuilder {
+    request: EvaluationRequest,
+}
+
+impl RequestBuilder {
+    pub fn new() -> Self {
+        let mut request = EvaluationRequest::new(TEST_CODE, "rust");
+        request.request_id = TEST_REQUEST_ID.to_string();
+        Self { request }
+    }
+
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.request.code = code.into();
+        self
+    }
+
+    pub fn language(mut self, language: impl Into<Language>) -> Self {
+        self.request.language = language.into();
+        self
+    }
+
+    pub fn evaluation_type(mut self, evaluation_type: EvaluationType) -> Self {
+        self.request.evaluation_type = evaluation_type;
+        self
+    }
+
+    pub fn request_id(mut self, request_id: impl Into<String>) -> Self {
+        self.request.request_id = request_id.into();
+        self
+    }
+
+    pub fn file_path(mut self, path: impl Into<String>) -> Self {
+        self.request.file_path = Some(path.into());
+        self
+    }
+
+    pub fn context(mut self, context: impl Into<String>) -> Self {
+        self.request.context = Some(context.into());
+        self
+    }
+
+    pub fn build(self) -> EvaluationRequest {
+        self.request
+    }
+}
+
+impl Default for RequestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<RequestBuilder> for EvaluationRequest {
+    fn from(builder: RequestBuilder) -> Self {
+        builder.build()
+    }
+}
@@ -0,0 +1,76 @@
+//! Build metadata embedded at compile time.
+
+use serde::{Deserialize, Serialize};
+
+/// Identifies the binary that produced a result.
+///
+/// Values are captured by `build.rs`; fields fall back to `"unknown"` when the
+/// information was not available at build time (e.g. building outside git).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// Crate version.
+    pub version: String,
+
+    /// Short git commit hash, suffixed with `-dirty` for uncommitted changes.
+    pub git_commit: String,
+
+    /// Build date (UTC, `YYYY-MM-DD`).
+    pub build_date: String,
+
+    /// Output of `rustc --version`.
+    pub rustc: String,
+
+    /// Enabled cargo features.
+    pub features: Vec<String>,
+
+    /// Target triple.
+    pub target: String,
+}
+
+impl BuildInfo {
+    /// Returns the metadata of the running binary.
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: env!("TETRAD_GIT_COMMIT").to_string(),
+            build_date: env!("TETRAD_BUILD_DATE").to_string(),
+            rustc: env!("TETRAD_RUSTC_VERSION").to_string(),
+            features: env!("TETRAD_FEATURES")
+                .split(',')
+                .filter(|feature| !feature.is_empty())
+                .map(str::to_string)
+                .collect(),
+            target: env!("TETRAD_TARGET").to_string(),
+        }
+    }
+}
+
+impl Default for BuildInfo {
+    fn default() -> Self {
+        Self::current()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_current_build_info_is_populated() {
+        let build = BuildInfo::current();
+
+        assert_eq!(build.version, env!("CARGO_PKG_VERSION"));
+        assert!(!build.git_commit.is_empty());
+        assert_eq!(build.build_date.len(), "YYYY-MM-DD".len());
+        assert!(build.rustc.starts_with("rustc") || build.rustc == "unknown");
+        assert!(!build.target.is_empty());
+    }
+
+    #[test]
+    fn test_build_info_serialization() {
+        let json = serde_json::to_value(BuildInfo::current()).unwrap();
+
+        assert!(json["git_commit"].is_string());
+        assert!(json["features"].is_array());
+    }
+}
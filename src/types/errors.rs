@@ -11,9 +11,31 @@ pub enum TetradError {
     #[cfg(feature = "sqlite")]
     #[error("SQLite error: {0}")]
     Sqlite(#[from] rusqlite::Error),
+    #[cfg(feature = "postgres")]
+    #[error("Postgres error: {0}")]
+    Postgres(#[from] postgres::Error),
     #[error("Configuration error: {0}")]
     Config(String),
 
+    /// Configuration file that does not parse or fails [`Config::validate`];
+    /// one entry per problem, each starting with the field path.
+    ///
+    /// [`Config::validate`]: crate::types::config::Config::validate
+    #[error("Invalid configuration: {}", .0.join("; "))]
+    InvalidConfig(Vec<String>),
+
+    /// Configuration file that defines commands and has not been approved;
+    /// `surface` lists the commands it would run.
+    #[error(
+        "{path} {reason} and defines commands ({}); review it and run again with --trust",
+        .surface.join("; ")
+    )]
+    UntrustedConfig {
+        path: String,
+        reason: String,
+        surface: Vec<String>,
+    },
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -41,9 +63,21 @@ pub enum TetradError {
     #[error("ReasoningBank error: {0}")]
     ReasoningBank(String),
 
+    #[error("Monthly budget exhausted: {0}")]
+    BudgetExceeded(String),
+
     #[error("MCP server error: {0}")]
     McpServer(String),
 
+    #[error("Server busy: {0}")]
+    ServerBusy(String),
+
+    /// Code larger than `general.max_code_bytes` (size, limit).
+    #[error(
+        "Code is {0} bytes, over general.max_code_bytes ({1}); review it in smaller parts or enable general.chunking"
+    )]
+    CodeTooLarge(usize, usize),
+
     #[error("Configuration not found at: {0}")]
     ConfigNotFound(String),
 
@@ -0,0 +1,258 @@
+//! Identificadores de linguagem do Tetrad.
+//!
+//! Linguagens chegam dos clientes MCP e da CLI em várias grafias ("Rust",
+//! "rs", "c++", "C#"). [`Language`] as converte uma única vez, na borda, para
+//! um id canônico em minúsculas, usado nas chaves do cache, nas linhas do
+//! ReasoningBank e na seleção de templates de prompt.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Id de linguagens fora da tabela.
+pub const OTHER: &str = "other";
+
+/// Id de patterns que valem para qualquer linguagem.
+pub const ANY: &str = "any";
+
+/// Linguagens conhecidas: id canônico e aliases aceitos.
+///
+/// Dialetos de SQL e HCL têm ids próprios, agrupados em famílias por
+/// [`language_family`](crate::executors::language_family).
+const LANGUAGES: &[(&str, &[&str])] = &[
+    ("rust", &["rs"]),
+    ("python", &["py", "pyi", "python3", "py3"]),
+    (
+        "javascript",
+        &["js", "jsx", "mjs", "cjs", "node", "nodejs", "ecmascript"],
+    ),
+    ("typescript", &["ts", "tsx", "mts", "cts"]),
+    ("go", &["golang"]),
+    ("java", &[]),
+    ("kotlin", &["kt", "kts"]),
+    ("scala", &["sc"]),
+    ("c", &["h"]),
+    (
+        "cpp",
+        &["c++", "cxx", "cc", "hpp", "hh", "hxx", "cplusplus"],
+    ),
+    ("csharp", &["c#", "cs", "dotnet"]),
+    ("fsharp", &["f#", "fs"]),
+    ("objective-c", &["objc", "objectivec", "m"]),
+    ("swift", &[]),
+    ("ruby", &["rb"]),
+    ("php", &[]),
+    ("perl", &["pl"]),
+    ("lua", &[]),
+    ("r", &[]),
+    ("dart", &[]),
+    ("elixir", &["ex", "exs"]),
+    ("erlang", &["erl"]),
+    ("haskell", &["hs"]),
+    ("ocaml", &["ml"]),
+    ("clojure", &["clj", "cljs"]),
+    ("zig", &[]),
+    ("nim", &[]),
+    ("julia", &["jl"]),
+    ("solidity", &["sol"]),
+    ("shell", &["sh", "bash", "zsh", "fish", "ksh"]),
+    ("powershell", &["ps1", "pwsh"]),
+    ("sql", &[]),
+    ("postgresql", &["postgres", "psql", "pgsql"]),
+    ("plpgsql", &["pl/pgsql"]),
+    ("mysql", &["mariadb"]),
+    ("sqlite", &["sqlite3"]),
+    ("tsql", &["t-sql", "mssql"]),
+    ("terraform", &["tf"]),
+    ("hcl", &[]),
+    ("yaml", &["yml"]),
+    ("json", &[]),
+    ("toml", &[]),
+    ("ini", &["cfg"]),
+    ("xml", &[]),
+    ("html", &["htm"]),
+    ("css", &["scss", "sass", "less"]),
+    ("markdown", &["md"]),
+    ("text", &["txt", "plaintext", "plain"]),
+    ("dockerfile", &["docker"]),
+    ("makefile", &["make"]),
+    ("protobuf", &["proto"]),
+    ("graphql", &["gql"]),
+    ("vue", &[]),
+    ("svelte", &[]),
+    (ANY, &["*", "all"]),
+];
+
+/// Linguagem de um artefato avaliado.
+///
+/// Linguagens fora da tabela viram [`OTHER`], mas a grafia original é
+/// mantida para exibição (`Display`) e para os prompts.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Language {
+    id: &'static str,
+    /// Grafia original de linguagens desconhecidas.
+    original: Option<String>,
+}
+
+impl Language {
+    /// Converte uma grafia qualquer (id, alias, maiúsculas) na linguagem.
+    pub fn parse(value: &str) -> Self {
+        let value = value.trim();
+        match Self::lookup(value) {
+            Some(id) => Self { id, original: None },
+            None => Self {
+                id: OTHER,
+                original: (!value.is_empty()).then(|| value.to_string()),
+            },
+        }
+    }
+
+    /// Id canônico de uma grafia qualquer (`other` para as desconhecidas).
+    pub fn canonical(value: &str) -> &'static str {
+        Self::lookup(value.trim()).unwrap_or(OTHER)
+    }
+
+    /// Id canônico em minúsculas.
+    pub fn as_str(&self) -> &'static str {
+        self.id
+    }
+
+    /// Nome para exibição e prompts: o id canônico, ou a grafia original de
+    /// uma linguagem desconhecida.
+    pub fn name(&self) -> &str {
+        self.original.as_deref().unwrap_or(self.id)
+    }
+
+    /// Se a linguagem está na tabela.
+    pub fn is_known(&self) -> bool {
+        self.id != OTHER
+    }
+
+    /// Grafia original de uma linguagem desconhecida.
+    pub fn original(&self) -> Option<&str> {
+        self.original.as_deref()
+    }
+
+    fn lookup(value: &str) -> Option<&'static str> {
+        let value = value.to_lowercase();
+        LANGUAGES
+            .iter()
+            .find(|(id, aliases)| *id == value || aliases.contains(&value.as_str()))
+            .map(|(id, _)| *id)
+    }
+}
+
+impl std::fmt::Display for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<&str> for Language {
+    fn from(value: &str) -> Self {
+        Self::parse(value)
+    }
+}
+
+impl From<&String> for Language {
+    fn from(value: &String) -> Self {
+        Self::parse(value)
+    }
+}
+
+impl From<String> for Language {
+    fn from(value: String) -> Self {
+        Self::parse(&value)
+    }
+}
+
+impl Serialize for Language {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id)
+    }
+}
+
+impl<'de> Deserialize<'de> for Language {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Ok(Self::parse(&value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aliases() {
+        for (input, expected) in [
+            ("Rust", "rust"),
+            ("rs", "rust"),
+            ("  RUST ", "rust"),
+            ("c++", "cpp"),
+            ("C++", "cpp"),
+            ("hpp", "cpp"),
+            ("C#", "csharp"),
+            ("cs", "csharp"),
+            ("py", "python"),
+            ("Python3", "python"),
+            ("js", "javascript"),
+            ("TSX", "typescript"),
+            ("golang", "go"),
+            ("bash", "shell"),
+            ("yml", "yaml"),
+            ("Postgres", "postgresql"),
+            ("tf", "terraform"),
+            ("md", "markdown"),
+            ("*", "any"),
+        ] {
+            assert_eq!(Language::parse(input).as_str(), expected, "{}", input);
+            assert_eq!(Language::canonical(input), expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_canonical_ids_are_unique() {
+        let mut seen = std::collections::HashSet::new();
+        for (id, aliases) in LANGUAGES {
+            assert_eq!(*id, id.to_lowercase());
+            assert!(seen.insert(*id), "duplicate id {}", id);
+            for alias in *aliases {
+                assert_eq!(*alias, alias.to_lowercase());
+                assert!(seen.insert(*alias), "duplicate alias {}", alias);
+            }
+        }
+        assert!(!seen.contains(OTHER));
+    }
+
+    #[test]
+    fn test_unknown_language_keeps_original() {
+        let language = Language::parse("COBOL");
+        assert_eq!(language.as_str(), "other");
+        assert!(!language.is_known());
+        assert_eq!(language.original(), Some("COBOL"));
+        assert_eq!(language.to_string(), "COBOL");
+
+        // Desconhecidas diferentes têm o mesmo id, mas não são iguais
+        assert_ne!(language, Language::parse("fortran"));
+        assert_eq!(Language::canonical("fortran"), "other");
+
+        let empty = Language::parse("  ");
+        assert_eq!(empty.as_str(), "other");
+        assert_eq!(empty.to_string(), "other");
+    }
+
+    #[test]
+    fn test_known_language_displays_canonical_id() {
+        let language = Language::parse("C++");
+        assert!(language.is_known());
+        assert_eq!(language.original(), None);
+        assert_eq!(language.to_string(), "cpp");
+        assert_eq!(language, Language::parse("cxx"));
+    }
+
+    #[test]
+    fn test_serde_uses_canonical_id() {
+        let language: Language = serde_json::from_str("\"RS\"").unwrap();
+        assert_eq!(language.as_str(), "rust");
+        assert_eq!(serde_json::to_string(&language).unwrap(), "\"rust\"");
+    }
+}
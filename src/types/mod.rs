@@ -1,6 +1,9 @@
 //! Tipos compartilhados do Tetrad.
 
+pub mod build;
 pub mod config;
 pub mod errors;
+pub mod language;
 pub mod requests;
 pub mod responses;
+pub mod trust;
@@ -1,7 +1,16 @@
 //! Tipos de requisição do Tetrad.
 
+use std::borrow::Cow;
+
 use serde::{Deserialize, Serialize};
 
+use super::language::Language;
+use crate::{TetradError, TetradResult};
+
+/// Menor espaço (em bytes) que cada arquivo relacionado pode receber ao
+/// ajustar o contexto ao orçamento; abaixo disso a requisição é recusada.
+pub const MIN_RELATED_FILE_BYTES: usize = 256;
+
 /// Requisição de avaliação de código.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EvaluationRequest {
@@ -11,8 +20,8 @@ pub struct EvaluationRequest {
     /// Código a ser avaliado.
     pub code: String,
 
-    /// Linguagem do código.
-    pub language: String,
+    /// Linguagem do código (id canônico; veja [`Language`]).
+    pub language: Language,
 
     /// Tipo de avaliação.
     pub evaluation_type: EvaluationType,
@@ -22,11 +31,41 @@ pub struct EvaluationRequest {
 
     /// Arquivo de origem (se aplicável).
     pub file_path: Option<String>,
+
+    /// Código relacionado à documentação revisada (se aplicável).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub related_code: Option<String>,
+
+    /// Tipo da documentação revisada (se aplicável).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc_type: Option<DocType>,
+
+    /// Arquivos do projeto enviados como contexto somente leitura (tipos,
+    /// funções chamadas). Não entram na assinatura do ReasoningBank.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_files: Vec<RelatedFile>,
+
+    /// Executores que podem votar nesta requisição; `None` usa todos os
+    /// habilitados na configuração.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executors: Option<Vec<String>>,
+}
+
+/// Arquivo do projeto enviado como contexto de uma revisão.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelatedFile {
+    /// Caminho do arquivo, exibido no cabeçalho do trecho no prompt.
+    pub path: String,
+
+    /// Conteúdo do arquivo.
+    pub content: String,
 }
 
 impl EvaluationRequest {
     /// Cria uma nova requisição de avaliação.
-    pub fn new(code: impl Into<String>, language: impl Into<String>) -> Self {
+    ///
+    /// A linguagem é normalizada aqui (`"Rust"`, `"rs"` → `rust`).
+    pub fn new(code: impl Into<String>, language: impl Into<Language>) -> Self {
         Self {
             request_id: uuid::Uuid::new_v4().to_string(),
             code: code.into(),
@@ -34,6 +73,10 @@ impl EvaluationRequest {
             evaluation_type: EvaluationType::Code,
             context: None,
             file_path: None,
+            related_code: None,
+            doc_type: None,
+            related_files: Vec::new(),
+            executors: None,
         }
     }
 
@@ -54,11 +97,159 @@ impl EvaluationRequest {
         self.file_path = Some(path.into());
         self
     }
+
+    /// Define o código relacionado à documentação.
+    pub fn with_related_code(mut self, code: impl Into<String>) -> Self {
+        self.related_code = Some(code.into());
+        self
+    }
+
+    /// Define o tipo da documentação revisada.
+    pub fn with_doc_type(mut self, doc_type: DocType) -> Self {
+        self.doc_type = Some(doc_type);
+        self
+    }
+
+    /// Define os arquivos relacionados enviados como contexto.
+    pub fn with_related_files(mut self, files: Vec<RelatedFile>) -> Self {
+        self.related_files = files;
+        self
+    }
+
+    /// Restringe os executores que votam nesta requisição.
+    pub fn with_executors(mut self, executors: Vec<String>) -> Self {
+        self.executors = Some(executors);
+        self
+    }
+
+    /// Conteúdo usado na chave do cache.
+    ///
+    /// Revisões de documentação incluem o código relacionado: a mesma
+    /// documentação pode estar correta para uma versão do código e errada
+    /// para outra. Pelo mesmo motivo, os arquivos relacionados e o tipo da
+    /// documentação (que muda os critérios do prompt) também entram.
+    pub fn cache_content(&self) -> Cow<'_, str> {
+        if self.related_code.is_none() && self.related_files.is_empty() && self.doc_type.is_none() {
+            return Cow::Borrowed(&self.code);
+        }
+
+        let mut content = self.code.clone();
+        if let Some(doc_type) = self.doc_type {
+            content.push_str(&format!("\n[doc_type {}]", doc_type));
+        }
+        if let Some(code) = &self.related_code {
+            content.push('\n');
+            content.push_str(code);
+        }
+        for file in &self.related_files {
+            content.push_str(&format!("\n[related {}]\n{}", file.path, file.content));
+        }
+        Cow::Owned(content)
+    }
+
+    /// Conteúdo usado para calcular a assinatura no ReasoningBank.
+    ///
+    /// Para diffs considera apenas as linhas adicionadas, de forma que o
+    /// contexto inalterado do arquivo não influencie os patterns.
+    pub fn signature_source(&self) -> Cow<'_, str> {
+        match self.evaluation_type {
+            EvaluationType::Diff => Cow::Owned(added_lines(&self.code)),
+            _ => Cow::Borrowed(&self.code),
+        }
+    }
+}
+
+/// Ajusta os arquivos relacionados a `budget` bytes de conteúdo.
+///
+/// Se o total couber, nada muda. Caso contrário o orçamento é dividido entre
+/// os arquivos: os que cabem na sua parte entram inteiros e a sobra vai para
+/// os maiores, que são cortados no fim de uma linha com um aviso. O caminho e
+/// o início de cada arquivo, onde ficam imports e definições de tipos, são
+/// sempre mantidos. Se a parte de cada arquivo ficar abaixo de
+/// [`MIN_RELATED_FILE_BYTES`], retorna erro em vez de truncar demais.
+pub fn fit_related_files(
+    mut files: Vec<RelatedFile>,
+    budget: usize,
+) -> TetradResult<Vec<RelatedFile>> {
+    let total: usize = files.iter().map(|f| f.content.len()).sum();
+    if total <= budget {
+        return Ok(files);
+    }
+    if files.len() * MIN_RELATED_FILE_BYTES > budget {
+        return Err(TetradError::other(format!(
+            "related_files: {} files totalling {} bytes do not fit in general.max_context_bytes \
+             ({} bytes); send fewer or smaller files",
+            files.len(),
+            total,
+            budget
+        )));
+    }
+
+    // Do menor para o maior, cada arquivo recebe uma parte igual do que resta
+    let mut order: Vec<usize> = (0..files.len()).collect();
+    order.sort_by_key(|&i| files[i].content.len());
+
+    let mut remaining = budget;
+    for (n, &i) in order.iter().enumerate() {
+        let share = remaining / (order.len() - n);
+        let content = &mut files[i].content;
+        remaining -= content.len().min(share);
+        if content.len() > share {
+            truncate_at_line(content, share);
+        }
+    }
+    Ok(files)
+}
+
+/// Corta `content` em até `limit` bytes, no fim de uma linha quando possível.
+fn truncate_at_line(content: &mut String, limit: usize) {
+    let mut cut = limit;
+    while !content.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    if let Some(newline) = content[..cut].rfind('\n') {
+        cut = newline;
+    }
+
+    let omitted = content.len() - cut;
+    content.truncate(cut);
+    content.push_str(&format!("\n… [truncated {} bytes]", omitted));
+}
+
+/// Extrai as linhas adicionadas de um diff unificado, sem o prefixo `+`.
+pub fn added_lines(diff: &str) -> String {
+    diff.lines()
+        .filter(|line| line.starts_with('+') && !line.starts_with("+++ "))
+        .map(|line| &line[1..])
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Lista os arquivos (lado novo) de um diff unificado, na ordem em que aparecem.
+///
+/// Arquivos removidos (`+++ /dev/null`) são ignorados.
+pub fn diff_files(diff: &str) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for path in diff.lines().filter_map(|line| line.strip_prefix("+++ ")) {
+        // Descarta timestamps após tab e o prefixo "b/" do git
+        let path = path.split('\t').next().unwrap_or(path).trim();
+        if path == "/dev/null" {
+            continue;
+        }
+        let path = path.strip_prefix("b/").unwrap_or(path);
+        if !files.iter().any(|f| f == path) {
+            files.push(path.to_string());
+        }
+    }
+    files
 }
 
 /// Tipo de avaliação.
+///
+/// Novos tipos podem ser adicionados sem quebrar compatibilidade.
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum EvaluationType {
     /// Avaliação de plano/design.
     Plan,
@@ -68,6 +259,11 @@ pub enum EvaluationType {
     Tests,
     /// Verificação final.
     FinalCheck,
+    /// Revisão de documentação (docstrings, README).
+    #[serde(alias = "docs")]
+    Documentation,
+    /// Revisão de um diff unificado (apenas as linhas alteradas).
+    Diff,
 }
 
 impl std::fmt::Display for EvaluationType {
@@ -77,6 +273,138 @@ impl std::fmt::Display for EvaluationType {
             EvaluationType::Code => write!(f, "code"),
             EvaluationType::Tests => write!(f, "tests"),
             EvaluationType::FinalCheck => write!(f, "final_check"),
+            EvaluationType::Documentation => write!(f, "documentation"),
+            EvaluationType::Diff => write!(f, "diff"),
+        }
+    }
+}
+
+/// Tipo de documentação revisada por `tetrad_review_docs`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DocType {
+    /// README ou guia de uso do projeto.
+    Readme,
+    /// Referência de API (docstrings, rustdoc, javadoc).
+    Api,
+    /// Changelog ou notas de versão.
+    Changelog,
+}
+
+impl std::fmt::Display for DocType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocType::Readme => write!(f, "readme"),
+            DocType::Api => write!(f, "api"),
+            DocType::Changelog => write!(f, "changelog"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,3 +1,3 @@\n fn main() {\n-    old();\n+    new();\n }\n";
+
+    #[test]
+    fn test_added_lines() {
+        assert_eq!(added_lines(DIFF), "    new();");
+        assert_eq!(added_lines("no diff here"), "");
+    }
+
+    #[test]
+    fn test_diff_files() {
+        assert_eq!(diff_files(DIFF), vec!["src/lib.rs"]);
+
+        let multi = format!(
+            "{}--- a/src/old.rs\n+++ /dev/null\n@@ -1 +0,0 @@\n-gone();\n\
+             --- /dev/null\n+++ b/src/new.rs\t2024-01-01\n@@ -0,0 +1 @@\n+born();\n",
+            DIFF
+        );
+        assert_eq!(diff_files(&multi), vec!["src/lib.rs", "src/new.rs"]);
+        assert!(diff_files("no diff here").is_empty());
+    }
+
+    #[test]
+    fn test_signature_source() {
+        let diff = EvaluationRequest::new(DIFF, "rust").with_type(EvaluationType::Diff);
+        assert_eq!(diff.signature_source(), "    new();");
+
+        let code = EvaluationRequest::new("fn main() {}", "rust");
+        assert_eq!(code.signature_source(), "fn main() {}");
+    }
+
+    fn related(path: &str, content: &str) -> RelatedFile {
+        RelatedFile {
+            path: path.to_string(),
+            content: content.to_string(),
         }
     }
+
+    #[test]
+    fn test_related_files_change_cache_key_but_not_signature() {
+        let plain = EvaluationRequest::new("fn area(s: &Shape) -> f64 { s.w * s.h }", "rust");
+        let with_types = plain.clone().with_related_files(vec![related(
+            "src/shape.rs",
+            "pub struct Shape { w: f64, h: f64 }",
+        )]);
+        let other_types = plain
+            .clone()
+            .with_related_files(vec![related("src/shape.rs", "pub struct Shape { r: f64 }")]);
+
+        assert_ne!(plain.cache_content(), with_types.cache_content());
+        assert_ne!(with_types.cache_content(), other_types.cache_content());
+        assert_eq!(plain.signature_source(), with_types.signature_source());
+    }
+
+    #[test]
+    fn test_doc_type_changes_cache_key() {
+        let docs =
+            EvaluationRequest::new("# Usage", "markdown").with_type(EvaluationType::Documentation);
+        let readme = docs.clone().with_doc_type(DocType::Readme);
+        let changelog = docs.clone().with_doc_type(DocType::Changelog);
+
+        assert_ne!(docs.cache_content(), readme.cache_content());
+        assert_ne!(readme.cache_content(), changelog.cache_content());
+        assert_eq!(docs.signature_source(), readme.signature_source());
+    }
+
+    #[test]
+    fn test_doc_type_serde() {
+        let doc_type: DocType = serde_json::from_str("\"changelog\"").unwrap();
+        assert_eq!(doc_type, DocType::Changelog);
+        assert!(serde_json::from_str::<DocType>("\"blog\"").is_err());
+
+        let eval_type: EvaluationType = serde_json::from_str("\"docs\"").unwrap();
+        assert_eq!(eval_type, EvaluationType::Documentation);
+    }
+
+    #[test]
+    fn test_fit_related_files_keeps_small_files_whole() {
+        let line = "x".repeat(99);
+        let large = format!("// header\n{}", format!("{}\n", line).repeat(20));
+        let files = vec![related("a.rs", "struct A;"), related("b.rs", &large)];
+
+        let fitted = fit_related_files(files.clone(), 4096).unwrap();
+        assert_eq!(fitted, files);
+
+        let fitted = fit_related_files(files, 600).unwrap();
+        assert_eq!(fitted[0].content, "struct A;");
+        assert!(fitted[1].content.starts_with("// header\n"));
+        assert!(fitted[1].content.contains("… [truncated"));
+        let kept = fitted[1].content.split("\n…").next().unwrap();
+        assert!(kept.len() <= 600 - "struct A;".len());
+        assert!(kept.ends_with(&line));
+    }
+
+    #[test]
+    fn test_fit_related_files_rejects_beyond_limit() {
+        let files: Vec<RelatedFile> = (0..5)
+            .map(|i| related(&format!("f{}.rs", i), &"y".repeat(1000)))
+            .collect();
+
+        let err = fit_related_files(files, 1000).unwrap_err();
+        assert!(err.to_string().contains("general.max_context_bytes"));
+    }
 }
@@ -2,6 +2,9 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::types::config::StrictnessTrace;
 
 /// Resultado de uma avaliação.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +21,12 @@ pub struct EvaluationResult {
     /// Se consenso foi alcançado.
     pub consensus_achieved: bool,
 
+    /// Confiança do consenso (0.0-1.0): unanimidade dos votos, folga do
+    /// score sobre `min_score` e consenso. Resultados gravados antes deste
+    /// campo são lidos com 0.0.
+    #[serde(default)]
+    pub confidence: f64,
+
     /// Votos de cada executor.
     pub votes: HashMap<String, ModelVote>,
 
@@ -29,6 +38,288 @@ pub struct EvaluationResult {
 
     /// Timestamp da avaliação.
     pub timestamp: chrono::DateTime<chrono::Utc>,
+
+    /// Avaliação recente do mesmo código que chegou a outra decisão.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flaky_with: Option<String>,
+
+    /// Ajuste de score aplicado por anti-patterns do ReasoningBank.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern_adjustment: Option<PatternAdjustment>,
+
+    /// Se o prazo total da avaliação (`general.timeout_secs`) esgotou antes
+    /// de todos os votos chegarem.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub budget_exceeded: bool,
+
+    /// Executores interrompidos pelo prazo; contam como votos neutros.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cut_off_executors: Vec<String>,
+
+    /// Se algum voto foi substituído por um voto neutro (executor com falha
+    /// ou interrompido) ou se votaram menos executores que os habilitados.
+    ///
+    /// O veredito tem baixa confiança e não fica no cache como um normal.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+
+    /// Executores sem voto real nesta avaliação.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub degraded_executors: Vec<String>,
+
+    /// Executores cancelados porque a decisão já estava definida
+    /// (`consensus.early_exit`); o score considera só os votos recebidos.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub short_circuited_executors: Vec<String>,
+
+    /// Prazo de cada chamada, em segundos, escolhido pelo timeout adaptativo
+    /// (`general.adaptive_timeouts`); vazio com os prazos fixos.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub executor_timeouts: HashMap<String, u64>,
+
+    /// Executores consultados quando a requisição restringiu quem vota.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executor_selection: Option<ExecutorSelection>,
+
+    /// Motivo informado pelo hook que pulou a avaliação.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_reason: Option<String>,
+
+    /// Regra de consenso efetivamente aplicada aos votos (ex: "strong",
+    /// ou "single-evaluator" quando a regra adaptativa reduziu a votação).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_rule: Option<String>,
+
+    /// Se um Pass foi rebaixado para Revise por ficar abaixo de
+    /// `consensus.min_confidence`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub low_confidence: bool,
+
+    /// Origem das configurações cobertas por `consensus.strictness`
+    /// (dial ou valor explícito), quando o dial está configurado.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strictness: Option<StrictnessTrace>,
+
+    /// Condição da regra de consenso que produziu a decisão.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<RuleExplanation>,
+
+    /// Patterns do ReasoningBank encontrados para o código avaliado.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub matched_patterns: Vec<MatchedPattern>,
+
+    /// Se o resultado foi entregue a partir do cache de avaliações (na
+    /// última vez em que este `request_id` foi respondido).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub from_cache: bool,
+
+    /// Resultado de cada trecho quando o código passou de
+    /// `general.max_code_bytes` e foi avaliado em partes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub chunks: Vec<ChunkOutcome>,
+
+    /// Loop de refinamento da revisão: 1 na primeira, e um a mais a cada
+    /// revisão que indica a anterior em `previous_request_id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refinement_loop: Option<u32>,
+
+    /// Se a revisão passou de `consensus.max_loops` e foi bloqueada.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub loop_limit_exceeded: bool,
+
+    /// Feedback principal ainda não renderizado.
+    ///
+    /// Enquanto pendente, `feedback` contém apenas as notas acrescentadas
+    /// depois da agregação. Chame [`EvaluationResult::materialize_feedback`]
+    /// antes de exibir ou serializar o resultado.
+    #[serde(skip)]
+    pub pending_feedback: Option<PendingFeedback>,
+}
+
+/// Executores de uma avaliação restrita pela requisição.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutorSelection {
+    /// Executores que receberam a requisição.
+    pub consulted: Vec<String>,
+
+    /// Executores habilitados deixados de fora pela requisição.
+    pub skipped_by_request: Vec<String>,
+
+    /// Executores desabilitados na configuração.
+    pub disabled_in_config: Vec<String>,
+}
+
+/// Por que a regra de consenso chegou à sua decisão.
+///
+/// Produzida por `ConsensusRule::explain`; `condition` é um identificador
+/// estável para ferramentas e `detail` a mesma condição em texto, com os
+/// valores que a satisfizeram.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleExplanation {
+    /// Regra aplicada (ex: "strong", ou "single-evaluator" na regra adaptativa).
+    pub rule: String,
+
+    /// Decisão da regra, antes do gate de severidade e dos ajustes posteriores.
+    pub decision: Decision,
+
+    /// Condição que decidiu (ex: `all_pass`, `any_fail`, `majority_fail`,
+    /// `insufficient_votes`, `block_expression`).
+    pub condition: String,
+
+    /// A condição em texto.
+    pub detail: String,
+
+    /// Executores cujos votos acionaram a condição, em ordem alfabética.
+    #[serde(default)]
+    pub drivers: Vec<String>,
+
+    /// Votos considerados.
+    pub votes: usize,
+
+    /// Votos PASS.
+    pub pass_count: usize,
+
+    /// Votos WARN.
+    pub warn_count: usize,
+
+    /// Votos FAIL.
+    pub fail_count: usize,
+
+    /// Score médio dos votos.
+    pub avg_score: u8,
+
+    /// Score mínimo exigido.
+    pub min_score: u8,
+
+    /// Votos mínimos exigidos pela regra.
+    pub min_required: usize,
+
+    /// Gate de severidade (`consensus.block_on_severity`) que mudou a
+    /// decisão da regra, se mudou.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity_gate: Option<String>,
+}
+
+impl RuleExplanation {
+    /// Cria a explicação de `rule` para os votos, ainda sem decisão
+    /// (Revise, condição vazia).
+    pub fn new(
+        rule: impl Into<String>,
+        votes: &HashMap<String, ModelVote>,
+        min_score: u8,
+        min_required: usize,
+    ) -> Self {
+        let count = |vote: Vote| votes.values().filter(|v| v.vote == vote).count();
+        let total: u32 = votes.values().map(|v| v.score as u32).sum();
+        Self {
+            rule: rule.into(),
+            decision: Decision::Revise,
+            condition: String::new(),
+            detail: String::new(),
+            drivers: Vec::new(),
+            votes: votes.len(),
+            pass_count: count(Vote::Pass),
+            warn_count: count(Vote::Warn),
+            fail_count: count(Vote::Fail),
+            avg_score: total.checked_div(votes.len() as u32).unwrap_or(0) as u8,
+            min_score,
+            min_required,
+            severity_gate: None,
+        }
+    }
+
+    /// Define a decisão e a condição que a produziu.
+    pub fn decided(
+        mut self,
+        decision: Decision,
+        condition: impl Into<String>,
+        detail: impl Into<String>,
+    ) -> Self {
+        self.decision = decision;
+        self.condition = condition.into();
+        self.detail = detail.into();
+        self
+    }
+
+    /// Define os executores que acionaram a condição.
+    pub fn driven_by<'a>(mut self, executors: impl IntoIterator<Item = &'a String>) -> Self {
+        self.drivers = executors.into_iter().cloned().collect();
+        self.drivers.sort();
+        self
+    }
+}
+
+/// Pattern do ReasoningBank encontrado para o código de uma avaliação, com
+/// as estatísticas que tinha naquele momento.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchedPattern {
+    /// ID do pattern no ReasoningBank.
+    pub id: i64,
+
+    /// Tipo (`anti_pattern`, `good_pattern`, `ambiguous`).
+    pub pattern_type: String,
+
+    /// Categoria do issue.
+    pub issue_category: String,
+
+    /// Descrição do pattern.
+    pub description: String,
+
+    /// Como o código casou com o pattern (`exact`, `shape`, `chunk`, `keyword`).
+    pub match_type: String,
+
+    /// Relevância do match (0.0-1.0).
+    pub relevance: f64,
+
+    /// Confiança do pattern (0.0-1.0).
+    pub confidence: f64,
+
+    /// Avaliações em que o pattern se confirmou.
+    pub success_count: i32,
+
+    /// Avaliações em que o pattern não se confirmou.
+    pub failure_count: i32,
+}
+
+/// Resultado de um trecho de uma avaliação em partes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkOutcome {
+    /// Número do trecho, a partir de 1.
+    pub chunk: usize,
+
+    /// Primeira linha do trecho no código original, a partir de 1 (inclui
+    /// as linhas repetidas do trecho anterior).
+    pub start_line: usize,
+
+    /// Última linha do trecho no código original.
+    pub end_line: usize,
+
+    /// Decisão do trecho.
+    pub decision: Decision,
+
+    /// Score do trecho.
+    pub score: u8,
+
+    /// ID da avaliação que produziu o resultado do trecho (a original, se
+    /// veio do cache).
+    pub request_id: String,
+}
+
+/// Renderização adiada do feedback principal de um resultado.
+#[derive(Clone)]
+pub struct PendingFeedback(Arc<dyn Fn(&EvaluationResult) -> String + Send + Sync>);
+
+impl PendingFeedback {
+    /// Cria a renderização adiada a partir de uma função.
+    pub fn new(render: impl Fn(&EvaluationResult) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(render))
+    }
+}
+
+impl std::fmt::Debug for PendingFeedback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PendingFeedback")
+    }
 }
 
 impl EvaluationResult {
@@ -39,10 +330,31 @@ impl EvaluationResult {
             decision: Decision::Pass,
             score,
             consensus_achieved: true,
+            confidence: 0.0,
             votes: HashMap::new(),
             findings: Vec::new(),
             feedback: feedback.into(),
             timestamp: chrono::Utc::now(),
+            flaky_with: None,
+            pattern_adjustment: None,
+            budget_exceeded: false,
+            cut_off_executors: Vec::new(),
+            degraded: false,
+            degraded_executors: Vec::new(),
+            short_circuited_executors: Vec::new(),
+            executor_timeouts: HashMap::new(),
+            executor_selection: None,
+            skip_reason: None,
+            effective_rule: None,
+            strictness: None,
+            low_confidence: false,
+            explanation: None,
+            matched_patterns: Vec::new(),
+            from_cache: false,
+            chunks: Vec::new(),
+            refinement_loop: None,
+            loop_limit_exceeded: false,
+            pending_feedback: None,
         }
     }
 
@@ -53,12 +365,150 @@ impl EvaluationResult {
             decision: Decision::Block,
             score,
             consensus_achieved: false,
+            confidence: 0.0,
             votes: HashMap::new(),
             findings: Vec::new(),
             feedback: feedback.into(),
             timestamp: chrono::Utc::now(),
+            flaky_with: None,
+            pattern_adjustment: None,
+            budget_exceeded: false,
+            cut_off_executors: Vec::new(),
+            degraded: false,
+            degraded_executors: Vec::new(),
+            short_circuited_executors: Vec::new(),
+            executor_timeouts: HashMap::new(),
+            executor_selection: None,
+            skip_reason: None,
+            effective_rule: None,
+            strictness: None,
+            low_confidence: false,
+            explanation: None,
+            matched_patterns: Vec::new(),
+            from_cache: false,
+            chunks: Vec::new(),
+            refinement_loop: None,
+            loop_limit_exceeded: false,
+            pending_feedback: None,
+        }
+    }
+
+    /// Renderiza o feedback pendente, se houver, antes das notas já acrescentadas.
+    ///
+    /// O feedback reflete o estado do resultado neste momento.
+    pub fn materialize_feedback(&mut self) {
+        if let Some(pending) = self.pending_feedback.take() {
+            let body = (pending.0)(self);
+            self.feedback.insert_str(0, &body);
         }
     }
+
+    /// Retorna o resultado com o feedback renderizado.
+    #[must_use]
+    pub fn with_materialized_feedback(mut self) -> Self {
+        self.materialize_feedback();
+        self
+    }
+
+    /// Cria o resultado de uma avaliação pulada por hook.
+    ///
+    /// Nenhum executor votou: o score reflete apenas a decisão escolhida
+    /// pelo hook, e só um Pass conta como consenso.
+    pub fn skipped(
+        request_id: impl Into<String>,
+        reason: impl Into<String>,
+        decision: Decision,
+    ) -> Self {
+        let reason = reason.into();
+        let score = match decision {
+            Decision::Pass => 100,
+            Decision::Revise => 50,
+            Decision::Block => 0,
+        };
+        let mut result = Self::success(
+            request_id,
+            score,
+            format!("## Avaliação Pulada\n\n{}\n", reason),
+        );
+        result.decision = decision;
+        result.consensus_achieved = decision == Decision::Pass;
+        result.skip_reason = Some(reason);
+        result
+    }
+
+    /// Marca o resultado como instável em relação a uma avaliação anterior.
+    ///
+    /// O mesmo código recebeu `previous_decision` em `previous_request_id`;
+    /// a divergência vem do não determinismo dos modelos, não do código.
+    pub fn mark_flaky(&mut self, previous_request_id: &str, previous_decision: Decision) {
+        self.flaky_with = Some(previous_request_id.to_string());
+        self.feedback.push_str(&format!(
+            "\n### Resultado Instável\n\n\
+             O mesmo código recebeu {} na avaliação `{}`, mas agora recebeu {}. \
+             Reavalie para confirmar ou use uma regra de consenso mais estrita (golden).\n",
+            previous_decision, previous_request_id, self.decision
+        ));
+    }
+
+    /// Marca que o prazo da avaliação esgotou e `cut_off` ficou sem votar.
+    pub fn mark_budget_exceeded(&mut self, cut_off: Vec<String>, budget_secs: u64) {
+        self.feedback.push_str(&format!(
+            "\n### Prazo Esgotado\n\n\
+             A avaliação atingiu o limite de {}s antes de {} responder; \
+             o resultado usa apenas os votos recebidos até então.\n",
+            budget_secs,
+            cut_off.join(", ")
+        ));
+        self.budget_exceeded = true;
+        self.cut_off_executors = cut_off;
+    }
+
+    /// Marca o resultado como degradado: `executors` não deram voto real.
+    pub fn mark_degraded(&mut self, executors: Vec<String>) {
+        self.feedback.push_str(&format!(
+            "\n### Resultado Degradado\n\n\
+             {} não {} voto real; o resultado tem baixa confiança.\n",
+            executors.join(", "),
+            if executors.len() == 1 { "deu" } else { "deram" }
+        ));
+        self.degraded = true;
+        self.degraded_executors = executors;
+    }
+
+    /// Bloqueia o resultado por ter passado de `max_loops` loops de
+    /// refinamento: o ciclo de revisão e correção não está convergindo.
+    pub fn mark_loop_limit_exceeded(&mut self, refinement_loop: u32, max_loops: u8) {
+        self.feedback.push_str(&format!(
+            "\n### Limite de Loops Excedido\n\n\
+             Esta é a revisão {} do mesmo ciclo, acima do limite de {} \
+             (`consensus.max_loops`); a decisão passou de {} para BLOCK. \
+             Reveja a abordagem antes de enviar outra correção.\n",
+            refinement_loop, max_loops, self.decision
+        ));
+        self.decision = Decision::Block;
+        self.consensus_achieved = false;
+        self.loop_limit_exceeded = true;
+    }
+
+    /// Marca que a decisão foi definida antes de `executors` votarem, com
+    /// `seats` assentos na votação.
+    pub fn mark_short_circuited(&mut self, executors: Vec<String>, seats: usize) {
+        let waited = if executors.len() == 1 {
+            "foi aguardado"
+        } else {
+            "foram aguardados"
+        };
+        self.feedback.push_str(&format!(
+            "\n### Decisão Antecipada\n\n\
+             A decisão já estava definida quando {} de {} executores tinham respondido; \
+             {} não {}. O score considera apenas os votos recebidos.\n",
+            seats.saturating_sub(executors.len()),
+            seats,
+            executors.join(", "),
+            waited
+        ));
+        self.short_circuited_executors = executors;
+    }
 }
 
 /// Decisão final da avaliação.
@@ -103,6 +553,35 @@ pub struct ModelVote {
 
     /// Sugestões de melhoria.
     pub suggestions: Vec<String>,
+
+    /// Correções sugeridas, indexadas pelo texto do issue.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub fixes: HashMap<String, SuggestedFix>,
+
+    /// Linhas afetadas, indexadas pelo texto do issue.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub lines: HashMap<String, Vec<u32>>,
+
+    /// Sugestão específica de cada issue, indexada pelo texto do issue.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub issue_suggestions: HashMap<String, String>,
+
+    /// Arquivo de cada issue em diffs com vários arquivos, indexado pelo texto do issue.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub issue_files: HashMap<String, String>,
+
+    /// Como a resposta foi interpretada, quando não veio no JSON esperado.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_quality: Option<ParseQuality>,
+
+    /// Idioma detectado na resposta em texto livre.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_language: Option<ResponseLanguage>,
+
+    /// Se o voto é o neutro (WARN 50) posto no lugar de um executor que
+    /// falhou, estourou o prazo ou foi cortado, e não uma resposta real.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub synthetic: bool,
 }
 
 impl ModelVote {
@@ -115,6 +594,21 @@ impl ModelVote {
             reasoning: String::new(),
             issues: Vec::new(),
             suggestions: Vec::new(),
+            fixes: HashMap::new(),
+            lines: HashMap::new(),
+            issue_suggestions: HashMap::new(),
+            issue_files: HashMap::new(),
+            parse_quality: None,
+            response_language: None,
+            synthetic: false,
+        }
+    }
+
+    /// Voto neutro para um assento sem voto próprio.
+    pub fn synthetic(executor: impl Into<String>) -> Self {
+        Self {
+            synthetic: true,
+            ..Self::new(executor, Vote::Warn, 50)
         }
     }
 
@@ -135,6 +629,93 @@ impl ModelVote {
         self.suggestions = suggestions;
         self
     }
+
+    /// Adiciona correções sugeridas.
+    pub fn with_fixes(mut self, fixes: HashMap<String, SuggestedFix>) -> Self {
+        self.fixes = fixes;
+        self
+    }
+
+    /// Adiciona as linhas afetadas por cada issue.
+    pub fn with_lines(mut self, lines: HashMap<String, Vec<u32>>) -> Self {
+        self.lines = lines;
+        self
+    }
+
+    /// Adiciona a sugestão específica de cada issue.
+    pub fn with_issue_suggestions(mut self, suggestions: HashMap<String, String>) -> Self {
+        self.issue_suggestions = suggestions;
+        self
+    }
+
+    /// Adiciona o arquivo de cada issue.
+    pub fn with_issue_files(mut self, files: HashMap<String, String>) -> Self {
+        self.issue_files = files;
+        self
+    }
+
+    /// Marca o voto como inferido de texto livre no idioma `language`.
+    pub fn with_text_language(mut self, language: ResponseLanguage) -> Self {
+        self.parse_quality = Some(if language.is_supported() {
+            ParseQuality::Text
+        } else {
+            ParseQuality::UnsupportedLanguage
+        });
+        self.response_language = Some(language);
+        self
+    }
+}
+
+/// Como a resposta de um executor foi interpretada.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParseQuality {
+    /// Voto inferido por palavras-chave do texto.
+    Text,
+    /// Texto num idioma sem palavras-chave: voto neutro (WARN).
+    UnsupportedLanguage,
+}
+
+/// Idioma de uma resposta em texto livre.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ResponseLanguage {
+    /// Português.
+    #[serde(rename = "pt")]
+    Portuguese,
+    /// Inglês.
+    #[serde(rename = "en")]
+    English,
+    /// Espanhol.
+    #[serde(rename = "es")]
+    Spanish,
+    /// Chinês.
+    #[serde(rename = "zh")]
+    Chinese,
+    /// Sem sinal suficiente; analisado como português e inglês.
+    #[serde(rename = "unknown")]
+    Unknown,
+    /// Idioma sem palavras-chave (japonês, russo, francês...).
+    #[serde(rename = "unsupported")]
+    Unsupported,
+}
+
+impl ResponseLanguage {
+    /// Código do idioma (`pt`, `en`, `es`, `zh`, `unknown`, `unsupported`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            ResponseLanguage::Portuguese => "pt",
+            ResponseLanguage::English => "en",
+            ResponseLanguage::Spanish => "es",
+            ResponseLanguage::Chinese => "zh",
+            ResponseLanguage::Unknown => "unknown",
+            ResponseLanguage::Unsupported => "unsupported",
+        }
+    }
+
+    /// Se há palavras-chave para analisar respostas neste idioma.
+    pub fn is_supported(&self) -> bool {
+        *self != ResponseLanguage::Unsupported
+    }
 }
 
 /// Voto individual.
@@ -149,6 +730,18 @@ pub enum Vote {
     Fail,
 }
 
+impl Vote {
+    /// Se o voto aponta na mesma direção da decisão final.
+    pub fn agrees_with(self, decision: Decision) -> bool {
+        matches!(
+            (self, decision),
+            (Vote::Pass, Decision::Pass)
+                | (Vote::Warn, Decision::Revise)
+                | (Vote::Fail, Decision::Block)
+        )
+    }
+}
+
 impl std::fmt::Display for Vote {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -187,6 +780,22 @@ pub struct Finding {
     /// Força do consenso (forte, moderado, fraco).
     #[serde(default)]
     pub consensus_strength: String,
+
+    /// Correção sugerida com mais apoio entre os executores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<SuggestedFix>,
+
+    /// Correções conflitantes propostas por outros executores.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternative_fixes: Vec<SuggestedFix>,
+
+    /// Arquivo afetado, em requests com vários arquivos.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+
+    /// Trecho em que o finding foi encontrado, nas avaliações em partes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunk: Option<usize>,
 }
 
 impl Finding {
@@ -200,6 +809,10 @@ impl Finding {
             suggestion: None,
             source: String::new(),
             consensus_strength: String::new(),
+            fix: None,
+            alternative_fixes: Vec::new(),
+            file: None,
+            chunk: None,
         }
     }
 
@@ -226,6 +839,141 @@ impl Finding {
         self.consensus_strength = strength.into();
         self
     }
+
+    /// Adiciona correção sugerida.
+    pub fn with_fix(mut self, fix: SuggestedFix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// Adiciona o arquivo afetado.
+    pub fn with_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+
+    /// Retorna todas as correções (principal e alternativas).
+    pub fn fixes(&self) -> impl Iterator<Item = &SuggestedFix> {
+        self.fix.iter().chain(self.alternative_fixes.iter())
+    }
+}
+
+/// Findings de um mesmo arquivo, em requests com vários arquivos.
+#[derive(Debug, Clone, Serialize)]
+pub struct FileFindings {
+    /// Arquivo afetado; `None` agrupa os findings sem arquivo.
+    pub file: Option<String>,
+
+    /// Mini-veredito do arquivo: a pior severidade entre seus findings.
+    pub verdict: Severity,
+
+    /// Findings do arquivo, na ordem do array principal.
+    pub findings: Vec<Finding>,
+}
+
+impl FileFindings {
+    /// Agrupa findings por arquivo.
+    ///
+    /// Retorna vazio quando nenhum finding tem arquivo (requests de um só
+    /// arquivo). Arquivos vêm em ordem alfabética; findings sem arquivo, por
+    /// último.
+    pub fn group(findings: &[Finding]) -> Vec<FileFindings> {
+        if findings.iter().all(|f| f.file.is_none()) {
+            return Vec::new();
+        }
+
+        let mut groups: std::collections::BTreeMap<Option<&str>, Vec<Finding>> =
+            std::collections::BTreeMap::new();
+        for finding in findings {
+            groups
+                .entry(finding.file.as_deref())
+                .or_default()
+                .push(finding.clone());
+        }
+
+        let mut grouped: Vec<FileFindings> = groups
+            .into_iter()
+            .map(|(file, findings)| FileFindings {
+                file: file.map(str::to_string),
+                verdict: findings
+                    .iter()
+                    .map(|f| f.severity)
+                    .max()
+                    .unwrap_or(Severity::Info),
+                findings,
+            })
+            .collect();
+        // None ordena antes de Some no BTreeMap; findings sem arquivo vão para o fim
+        let unfiled = usize::from(grouped[0].file.is_none());
+        grouped.rotate_left(unfiled);
+        grouped
+    }
+}
+
+/// Correção aplicável sugerida por um executor.
+///
+/// O Tetrad apenas transporta correções; nunca as aplica no código.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SuggestedFix {
+    /// Primeira linha substituída (1-based, inclusiva).
+    pub start_line: u32,
+
+    /// Última linha substituída (inclusiva).
+    pub end_line: u32,
+
+    /// Código que substitui o intervalo de linhas.
+    pub replacement: String,
+
+    /// Executores que propuseram esta correção.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
+}
+
+impl SuggestedFix {
+    /// Cria uma nova correção.
+    pub fn new(start_line: u32, end_line: u32, replacement: impl Into<String>) -> Self {
+        Self {
+            start_line,
+            end_line,
+            replacement: replacement.into(),
+            sources: Vec::new(),
+        }
+    }
+
+    /// Verifica se duas correções propõem a mesma alteração (ignorando fontes).
+    pub fn same_change(&self, other: &SuggestedFix) -> bool {
+        self.start_line == other.start_line
+            && self.end_line == other.end_line
+            && self.replacement.trim_end() == other.replacement.trim_end()
+    }
+}
+
+/// Ajuste de score motivado por anti-patterns conhecidos.
+///
+/// Fica no resultado para que o usuário veja por que o score caiu.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PatternAdjustment {
+    /// Score dos avaliadores antes do ajuste.
+    pub original_score: u8,
+
+    /// Pontos subtraídos do score.
+    pub penalty: u8,
+
+    /// Anti-patterns que motivaram o ajuste.
+    pub patterns: Vec<AppliedPattern>,
+}
+
+/// Anti-pattern considerado em um ajuste de score.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AppliedPattern {
+    /// Categoria do issue.
+    pub issue_category: String,
+
+    /// Descrição do issue.
+    pub description: String,
+
+    /// Confiança do pattern (0.0-1.0).
+    pub confidence: f64,
 }
 
 /// Severidade de um finding.
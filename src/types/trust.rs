@@ -0,0 +1,314 @@
+//! Trust store for project configuration files.
+//!
+//! `tetrad.toml` is discovered from the current directory, so a cloned
+//! repository can ship one that runs arbitrary commands as executors. A
+//! configuration that defines such commands is only used once the user has
+//! approved it; approvals are kept by content hash in the user data
+//! directory, never in the project, and a modified file needs a new approval.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::executors::MOCK_COMMAND;
+use crate::types::config::{Config, ExecutorConfig, ExecutorsConfig, DATA_DIR_ENV};
+use crate::{TetradError, TetradResult};
+
+/// File holding the approvals, inside the user data directory.
+pub const TRUST_FILE_NAME: &str = "trusted.json";
+
+/// Approved configuration files.
+#[derive(Debug)]
+pub struct TrustStore {
+    path: PathBuf,
+    entries: BTreeMap<PathBuf, TrustEntry>,
+}
+
+/// Approval of one configuration file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrustEntry {
+    /// SHA-256 of the file contents when it was approved.
+    pub sha256: String,
+
+    /// When the file was approved.
+    pub approved_at: DateTime<Utc>,
+}
+
+/// Whether a configuration file may be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustStatus {
+    /// Approved with these exact contents.
+    Trusted,
+
+    /// Never approved.
+    Unknown,
+
+    /// Approved, but the contents changed since.
+    Modified,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct TrustFile {
+    #[serde(default)]
+    configs: BTreeMap<PathBuf, TrustEntry>,
+}
+
+impl TrustStore {
+    /// Default location: `trusted.json` in `$TETRAD_DATA_DIR`, or in the
+    /// platform data directory (`~/.local/share/tetrad` on Linux).
+    ///
+    /// Unlike [`Config::data_dir`], this never points inside a project.
+    pub fn default_path() -> Option<PathBuf> {
+        match std::env::var_os(DATA_DIR_ENV).filter(|dir| !dir.is_empty()) {
+            Some(dir) => Some(PathBuf::from(dir).join(TRUST_FILE_NAME)),
+            None => dirs::data_dir().map(|dir| dir.join("tetrad").join(TRUST_FILE_NAME)),
+        }
+    }
+
+    /// Opens the store at [`TrustStore::default_path`].
+    pub fn open_default() -> TetradResult<Self> {
+        let path = Self::default_path()
+            .ok_or_else(|| TetradError::config("no user data directory for the trust store"))?;
+        Self::open(path)
+    }
+
+    /// Opens the store at `path`; a missing file is an empty store.
+    pub fn open(path: impl Into<PathBuf>) -> TetradResult<Self> {
+        let path = path.into();
+        let file: TrustFile = match std::fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                TetradError::config(format!("invalid trust store {}: {}", path.display(), e))
+            })?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => TrustFile::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path,
+            entries: file.configs,
+        })
+    }
+
+    /// Location of the store.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether `config_path` was approved with `contents`.
+    pub fn status(&self, config_path: &Path, contents: &[u8]) -> TrustStatus {
+        match self.entries.get(&store_key(config_path)) {
+            Some(entry) if entry.sha256 == content_hash(contents) => TrustStatus::Trusted,
+            Some(_) => TrustStatus::Modified,
+            None => TrustStatus::Unknown,
+        }
+    }
+
+    /// Approves `config_path` with `contents`, replacing any earlier approval.
+    pub fn approve(&mut self, config_path: &Path, contents: &[u8]) {
+        self.entries.insert(
+            store_key(config_path),
+            TrustEntry {
+                sha256: content_hash(contents),
+                approved_at: Utc::now(),
+            },
+        );
+    }
+
+    /// Removes the approval of `config_path`; returns whether there was one.
+    pub fn revoke(&mut self, config_path: &Path) -> bool {
+        self.entries.remove(&store_key(config_path)).is_some()
+    }
+
+    /// Approved files, by path.
+    pub fn entries(&self) -> impl Iterator<Item = (&Path, &TrustEntry)> {
+        self.entries
+            .iter()
+            .map(|(path, entry)| (path.as_path(), entry))
+    }
+
+    /// Writes the store, creating its directory if needed.
+    pub fn save(&self) -> TetradResult<()> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = TrustFile {
+            configs: self.entries.clone(),
+        };
+        std::fs::write(&self.path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+}
+
+/// Commands a configuration would run: enabled built-in executors whose
+/// command or arguments differ from the defaults, and enabled custom
+/// executors. Empty when the configuration only runs the stock CLIs.
+/// Executors with `command = "mock"` run nothing and are left out.
+pub fn executable_surface(config: &Config) -> Vec<String> {
+    let defaults = ExecutorsConfig::default();
+    let builtins = [
+        ("codex", &config.executors.codex, &defaults.codex),
+        ("gemini", &config.executors.gemini, &defaults.gemini),
+        ("qwen", &config.executors.qwen, &defaults.qwen),
+    ];
+
+    let mut surface: Vec<String> = builtins
+        .into_iter()
+        .filter(|(_, executor, default)| {
+            runs_command(executor)
+                && (executor.command != default.command || executor.args != default.args)
+        })
+        .map(|(name, executor, _)| format!("executors.{}: {}", name, command_line(executor)))
+        .collect();
+    surface.extend(
+        config
+            .executors
+            .custom
+            .iter()
+            .filter(|custom| runs_command(&custom.executor))
+            .map(|custom| {
+                format!(
+                    "executors.custom \"{}\": {}",
+                    custom.name,
+                    command_line(&custom.executor)
+                )
+            }),
+    );
+    surface
+}
+
+fn runs_command(executor: &ExecutorConfig) -> bool {
+    executor.enabled && executor.command != MOCK_COMMAND
+}
+
+fn command_line(executor: &ExecutorConfig) -> String {
+    std::iter::once(executor.command.as_str())
+        .chain(executor.args.iter().map(String::as_str))
+        .map(|part| {
+            if part.is_empty() || part.contains(char::is_whitespace) {
+                format!("{:?}", part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Approvals are keyed by canonical path, so `./tetrad.toml` and the
+/// absolute path share one entry.
+fn store_key(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn content_hash(contents: &[u8]) -> String {
+    hex::encode(Sha256::digest(contents))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::CustomExecutorConfig;
+
+    fn store(dir: &tempfile::TempDir) -> TrustStore {
+        TrustStore::open(dir.path().join("data").join(TRUST_FILE_NAME)).unwrap()
+    }
+
+    fn config_file(dir: &tempfile::TempDir) -> PathBuf {
+        let path = dir.path().join("tetrad.toml");
+        std::fs::write(&path, "").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_first_load_is_unknown_until_approved() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = config_file(&dir);
+        let mut store = store(&dir);
+
+        assert_eq!(store.status(&path, b"contents"), TrustStatus::Unknown);
+        store.approve(&path, b"contents");
+        assert_eq!(store.status(&path, b"contents"), TrustStatus::Trusted);
+    }
+
+    #[test]
+    fn test_modified_contents_need_a_new_approval() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = config_file(&dir);
+        let mut store = store(&dir);
+        store.approve(&path, b"command = \"codex\"");
+
+        assert_eq!(
+            store.status(&path, b"command = \"./evil.sh\""),
+            TrustStatus::Modified
+        );
+        store.approve(&path, b"command = \"./evil.sh\"");
+        assert_eq!(
+            store.status(&path, b"command = \"./evil.sh\""),
+            TrustStatus::Trusted
+        );
+    }
+
+    #[test]
+    fn test_save_open_and_revoke() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = config_file(&dir);
+        let mut store = store(&dir);
+        store.approve(&path, b"contents");
+        store.save().unwrap();
+
+        let mut reopened = TrustStore::open(store.path()).unwrap();
+        assert_eq!(reopened.entries().count(), 1);
+        assert_eq!(reopened.status(&path, b"contents"), TrustStatus::Trusted);
+
+        assert!(reopened.revoke(&path));
+        assert!(!reopened.revoke(&path));
+        assert_eq!(reopened.status(&path, b"contents"), TrustStatus::Unknown);
+    }
+
+    #[test]
+    fn test_open_rejects_corrupt_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(TRUST_FILE_NAME);
+        std::fs::write(&path, "not json").unwrap();
+
+        assert!(TrustStore::open(&path).is_err());
+    }
+
+    #[test]
+    fn test_default_executors_have_no_surface() {
+        let mut config = Config::default();
+        assert!(executable_surface(&config).is_empty());
+
+        // A disabled executor never runs
+        config.executors.codex.enabled = false;
+        config.executors.codex.command = "./evil.sh".to_string();
+        assert!(executable_surface(&config).is_empty());
+
+        // Neither does the deterministic mock
+        crate::executors::use_mock_executors(&mut config.executors);
+        config.executors.codex.enabled = true;
+        assert!(executable_surface(&config).is_empty());
+    }
+
+    #[test]
+    fn test_surface_lists_changed_and_custom_commands() {
+        let mut config = Config::default();
+        config.executors.gemini.args = vec!["--yolo".to_string()];
+        config.executors.custom.push(CustomExecutorConfig {
+            name: "Local".to_string(),
+            executor: ExecutorConfig::new("bash", &["-c", "echo ok"]),
+            specialization: String::new(),
+            output_format: Default::default(),
+        });
+
+        assert_eq!(
+            executable_surface(&config),
+            vec![
+                "executors.gemini: gemini --yolo".to_string(),
+                "executors.custom \"Local\": bash -c \"echo ok\"".to_string(),
+            ]
+        );
+    }
+}
@@ -14,6 +14,7 @@ fn create_config(rule: ConsensusRuleConfig, min_score: u8, max_loops: u8) -> Con
         default_rule: rule,
         min_score,
         max_loops,
+        ..Default::default()
     }
 }
 
@@ -443,3 +444,307 @@ mod finding_tests {
         assert_eq!(finding.consensus_strength, "strong");
     }
 }
+
+// Testes de severity gating
+
+mod severity_gate_tests {
+    use super::*;
+    use tetrad::types::config::SeverityGate;
+
+    fn unanimous_pass_with_issue(issue: &str) -> HashMap<String, ModelVote> {
+        let mut codex = ModelVote::new("codex", Vote::Pass, 90);
+        codex.issues.push(issue.to_string());
+
+        vec![
+            ("codex".to_string(), codex),
+            create_vote("gemini", Vote::Pass, 92),
+            create_vote("qwen", Vote::Pass, 88),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn test_default_gate_is_critical() {
+        let config: ConsensusConfig = toml::from_str("min_score = 70").unwrap();
+        assert_eq!(config.block_on_severity, SeverityGate::Critical);
+    }
+
+    #[test]
+    fn test_gate_parses_from_toml() {
+        let config: ConsensusConfig = toml::from_str(r#"block_on_severity = "off""#).unwrap();
+        assert_eq!(config.block_on_severity, SeverityGate::Off);
+        assert_eq!(config.block_on_severity.min_severity(), None);
+
+        let config: ConsensusConfig = toml::from_str(r#"block_on_severity = "error""#).unwrap();
+        assert_eq!(
+            config.block_on_severity.min_severity(),
+            Some(Severity::Error)
+        );
+    }
+
+    #[test]
+    fn test_engine_blocks_unanimous_pass_with_critical_finding() {
+        let engine = ConsensusEngine::new(create_config(ConsensusRuleConfig::Golden, 70, 3));
+
+        let result = engine.evaluate(
+            unanimous_pass_with_issue("Security: secret logged in plain text"),
+            "gate-critical",
+        );
+
+        assert_eq!(result.decision, Decision::Block);
+        assert!(result.consensus_achieved);
+        assert!(result.feedback.contains("severity gating"));
+    }
+
+    #[test]
+    fn test_engine_gate_off_keeps_vote_decision() {
+        let config = ConsensusConfig {
+            block_on_severity: SeverityGate::Off,
+            ..create_config(ConsensusRuleConfig::Golden, 70, 3)
+        };
+        let engine = ConsensusEngine::new(config);
+
+        let result = engine.evaluate(
+            unanimous_pass_with_issue("Security: secret logged in plain text"),
+            "gate-off",
+        );
+
+        assert_eq!(result.decision, Decision::Pass);
+        assert!(result.consensus_achieved);
+        assert!(!result.feedback.contains("severity gating"));
+    }
+}
+
+// Testes do consenso adaptativo
+mod adaptive_tests {
+    use super::*;
+    use tetrad::consensus::Turnout;
+
+    const RULES: [ConsensusRuleConfig; 3] = [
+        ConsensusRuleConfig::Golden,
+        ConsensusRuleConfig::Strong,
+        ConsensusRuleConfig::Weak,
+    ];
+
+    fn votes(votes: &[(&str, Vote, u8)]) -> HashMap<String, ModelVote> {
+        votes
+            .iter()
+            .map(|(name, vote, score)| create_vote(name, *vote, *score))
+            .collect()
+    }
+
+    #[test]
+    fn test_one_voter_follows_its_vote() {
+        for rule in RULES {
+            let engine = ConsensusEngine::new(create_config(rule, 70, 3));
+            for (vote, score, expected) in [
+                (Vote::Pass, 90, Decision::Pass),
+                (Vote::Pass, 50, Decision::Revise),
+                (Vote::Warn, 90, Decision::Revise),
+                (Vote::Fail, 20, Decision::Block),
+            ] {
+                let result = engine.evaluate(votes(&[("codex", vote, score)]), "one");
+                assert_eq!(result.decision, expected, "{:?} {:?}", rule, vote);
+                assert_eq!(result.effective_rule.as_deref(), Some("single-evaluator"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_two_voters_must_agree() {
+        for rule in RULES {
+            let engine = ConsensusEngine::new(create_config(rule, 70, 3));
+            for (first, second, expected) in [
+                (Vote::Pass, Vote::Pass, Decision::Pass),
+                (Vote::Pass, Vote::Warn, Decision::Revise),
+                (Vote::Pass, Vote::Fail, Decision::Revise),
+                (Vote::Fail, Vote::Fail, Decision::Block),
+            ] {
+                let result = engine.evaluate(
+                    votes(&[("codex", first, 85), ("gemini", second, 85)]),
+                    "two",
+                );
+                assert_eq!(
+                    result.decision, expected,
+                    "{:?} {:?}/{:?}",
+                    rule, first, second
+                );
+                assert_eq!(result.effective_rule.as_deref(), Some("dual-evaluator"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_three_voters_use_configured_rule() {
+        for (rule, expected) in [
+            (ConsensusRuleConfig::Golden, Decision::Block),
+            (ConsensusRuleConfig::Strong, Decision::Revise),
+            (ConsensusRuleConfig::Weak, Decision::Pass),
+        ] {
+            let engine = ConsensusEngine::new(create_config(rule, 70, 3));
+            let result = engine.evaluate(
+                votes(&[
+                    ("codex", Vote::Pass, 85),
+                    ("gemini", Vote::Pass, 88),
+                    ("qwen", Vote::Fail, 40),
+                ]),
+                "three",
+            );
+            assert_eq!(result.decision, expected, "{:?}", rule);
+            assert_eq!(result.effective_rule.as_deref(), Some(engine.rule_name()));
+        }
+    }
+
+    #[test]
+    fn test_three_of_five_seats_scale_configured_rule() {
+        let engine =
+            ConsensusEngine::with_voters(create_config(ConsensusRuleConfig::Strong, 70, 3), 5);
+        let all_pass = votes(&[
+            ("codex", Vote::Pass, 85),
+            ("gemini", Vote::Pass, 88),
+            ("qwen", Vote::Pass, 82),
+        ]);
+
+        let result = engine.evaluate_with_turnout(all_pass, "three-of-five", &Turnout::new(5));
+        assert_eq!(result.decision, Decision::Pass);
+        assert_eq!(result.effective_rule.as_deref(), Some("strong"));
+    }
+}
+
+// Testes de consenso ponderado pelo `weight` dos executores
+mod weighted_tests {
+    use super::*;
+    use tetrad::consensus::VoteWeights;
+
+    fn votes(votes: &[(&str, Vote, u8)]) -> HashMap<String, ModelVote> {
+        votes
+            .iter()
+            .map(|(name, vote, score)| create_vote(name, *vote, *score))
+            .collect()
+    }
+
+    fn weights(codex: u8, gemini: u8, qwen: u8) -> VoteWeights {
+        VoteWeights::new([("codex", codex), ("gemini", gemini), ("qwen", qwen)])
+    }
+
+    #[test]
+    fn test_strong_rule_weighted_average() {
+        let config = create_config(ConsensusRuleConfig::Strong, 70, 3);
+        let all_pass = votes(&[
+            ("codex", Vote::Pass, 90),
+            ("gemini", Vote::Pass, 55),
+            ("qwen", Vote::Pass, 55),
+        ]);
+
+        // Média simples 66: abaixo do mínimo
+        let unweighted = ConsensusEngine::new(config.clone()).evaluate(all_pass.clone(), "plain");
+        assert_eq!(unweighted.decision, Decision::Revise);
+        assert_eq!(unweighted.score, 66);
+
+        // Codex com peso 5: (90*5 + 55 + 55) / 7 = 80
+        let weighted = ConsensusEngine::new(config)
+            .with_weights(weights(5, 1, 1))
+            .evaluate(all_pass, "weighted");
+        assert_eq!(weighted.decision, Decision::Pass);
+        assert_eq!(weighted.score, 80);
+    }
+
+    #[test]
+    fn test_strong_rule_weight_cannot_skip_unanimity() {
+        let engine = ConsensusEngine::new(create_config(ConsensusRuleConfig::Strong, 70, 3))
+            .with_weights(weights(10, 1, 1));
+        let result = engine.evaluate(
+            votes(&[
+                ("codex", Vote::Pass, 95),
+                ("gemini", Vote::Pass, 80),
+                ("qwen", Vote::Fail, 30),
+            ]),
+            "strong",
+        );
+        assert_ne!(result.decision, Decision::Pass);
+    }
+
+    #[test]
+    fn test_weak_rule_heavy_executor_majority() {
+        let config = create_config(ConsensusRuleConfig::Weak, 70, 3);
+        let split = votes(&[
+            ("codex", Vote::Pass, 90),
+            ("gemini", Vote::Warn, 60),
+            ("qwen", Vote::Fail, 30),
+        ]);
+
+        // Sem pesos não há maioria
+        let unweighted = ConsensusEngine::new(config.clone()).evaluate(split.clone(), "plain");
+        assert_eq!(unweighted.decision, Decision::Revise);
+
+        // O PASS do Codex tem 5 de 7 do peso
+        let weighted = ConsensusEngine::new(config.clone())
+            .with_weights(weights(5, 1, 1))
+            .evaluate(split.clone(), "weighted");
+        assert_eq!(weighted.decision, Decision::Pass);
+
+        // Com o peso no FAIL do Qwen, bloqueia
+        let weighted = ConsensusEngine::new(config)
+            .with_weights(weights(1, 1, 5))
+            .evaluate(split, "weighted-fail");
+        assert_eq!(weighted.decision, Decision::Block);
+    }
+
+    #[test]
+    fn test_golden_rule_heavy_executor_cannot_pass_alone() {
+        let engine = ConsensusEngine::new(create_config(ConsensusRuleConfig::Golden, 70, 3))
+            .with_weights(weights(10, 1, 1));
+        let result = engine.evaluate(
+            votes(&[
+                ("codex", Vote::Pass, 98),
+                ("gemini", Vote::Warn, 65),
+                ("qwen", Vote::Warn, 65),
+            ]),
+            "golden",
+        );
+        assert_eq!(result.decision, Decision::Revise);
+    }
+
+    #[test]
+    fn test_equal_weights_match_unweighted() {
+        let cases = [
+            votes(&[
+                ("codex", Vote::Pass, 85),
+                ("gemini", Vote::Pass, 72),
+                ("qwen", Vote::Fail, 40),
+            ]),
+            votes(&[
+                ("codex", Vote::Pass, 90),
+                ("gemini", Vote::Warn, 60),
+                ("qwen", Vote::Fail, 30),
+            ]),
+            votes(&[
+                ("codex", Vote::Fail, 20),
+                ("gemini", Vote::Fail, 35),
+                ("qwen", Vote::Pass, 80),
+            ]),
+            votes(&[
+                ("codex", Vote::Pass, 71),
+                ("gemini", Vote::Pass, 69),
+                ("qwen", Vote::Pass, 70),
+            ]),
+        ];
+        for rule in [
+            ConsensusRuleConfig::Golden,
+            ConsensusRuleConfig::Strong,
+            ConsensusRuleConfig::Weak,
+        ] {
+            let config = create_config(rule, 70, 3);
+            let plain = ConsensusEngine::new(config.clone());
+            let weighted = ConsensusEngine::new(config).with_weights(weights(3, 3, 3));
+            for votes in &cases {
+                let expected = plain.evaluate(votes.clone(), "plain");
+                let actual = weighted.evaluate(votes.clone(), "weighted");
+                assert_eq!(actual.decision, expected.decision, "{:?}", rule);
+                assert_eq!(actual.score, expected.score, "{:?}", rule);
+                assert_eq!(actual.explanation, expected.explanation, "{:?}", rule);
+            }
+        }
+    }
+}
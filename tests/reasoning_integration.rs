@@ -239,3 +239,159 @@ mod utility_tests {
         );
     }
 }
+
+// Testes de detecção de resultados instáveis
+mod flakiness_tests {
+    use super::*;
+
+    #[test]
+    fn test_conflicting_results_are_flagged() {
+        let (_temp_dir, db_path) = temp_db_path();
+        let mut bank = ReasoningBank::new(&db_path).expect("Failed to create bank");
+        let code = "fn maybe() { risky() }";
+
+        let passed = EvaluationResult::success("eval-pass", 90, "ok");
+        bank.judge("eval-pass", code, "rust", &passed, 1, 3)
+            .unwrap();
+
+        let mut blocked = EvaluationResult::failure("eval-block", 40, "issues");
+        let (previous_id, previous_decision) = bank
+            .find_divergent(code, blocked.decision, 3600)
+            .unwrap()
+            .expect("Divergent result should be detected");
+        blocked.mark_flaky(&previous_id, previous_decision);
+
+        assert_eq!(blocked.flaky_with.as_deref(), Some("eval-pass"));
+        assert!(blocked.feedback.contains("Resultado Instável"));
+        assert!(blocked.feedback.contains("`eval-pass`"));
+
+        let json = serde_json::to_value(&blocked).unwrap();
+        assert_eq!(json["flaky_with"], "eval-pass");
+    }
+
+    #[test]
+    fn test_flakiness_report_lists_flip_prone_code() {
+        let (_temp_dir, db_path) = temp_db_path();
+        let mut bank = ReasoningBank::new(&db_path).expect("Failed to create bank");
+
+        let passed = EvaluationResult::success("p", 90, "ok");
+        let blocked = EvaluationResult::failure("b", 40, "issues");
+        bank.judge("req-1", "fn a() {}", "rust", &passed, 1, 3)
+            .unwrap();
+        bank.judge("req-2", "fn a() {}", "rust", &blocked, 1, 3)
+            .unwrap();
+        bank.judge("req-3", "fn stable() {}", "rust", &passed, 1, 3)
+            .unwrap();
+
+        let report = bank.flakiness_report(10).unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].flips, 1);
+        assert_eq!(report[0].last_request_id, "req-2");
+    }
+}
+
+// Testes da influência dos patterns na avaliação
+mod influence_tests {
+    use super::*;
+    use tetrad::reasoning::{known_antipatterns, prompt_context, score_adjustment};
+    use tetrad::types::responses::{Finding, Severity};
+
+    #[test]
+    fn test_repeated_failures_penalize_score() {
+        let (_temp_dir, db_path) = temp_db_path();
+        let mut bank = ReasoningBank::new(&db_path).expect("Failed to create bank");
+        let code = "fn query(id: &str) { db.execute(format!(\"SELECT {}\", id)) }";
+
+        let mut failed = EvaluationResult::failure("fail-1", 30, "SQL injection");
+        failed.findings.push(Finding::new(
+            Severity::Critical,
+            "security",
+            "SQL injection via format!",
+        ));
+        bank.judge("fail-1", code, "rust", &failed, 1, 3).unwrap();
+        bank.judge("fail-2", code, "rust", &failed, 1, 3).unwrap();
+
+        let matches = bank.retrieve(code, "rust");
+        let antipatterns = known_antipatterns(&matches, 0.7);
+        assert!(!antipatterns.is_empty());
+        assert!(prompt_context(&antipatterns)
+            .unwrap()
+            .contains("SQL injection via format!"));
+
+        let adjustment = score_adjustment(&antipatterns, 85, 10).unwrap();
+        assert_eq!(adjustment.original_score, 85);
+        assert_eq!(adjustment.penalty, 10);
+    }
+
+    #[test]
+    fn test_successful_code_is_not_penalized() {
+        let (_temp_dir, db_path) = temp_db_path();
+        let mut bank = ReasoningBank::new(&db_path).expect("Failed to create bank");
+        let code = "fn add(a: i32, b: i32) -> i32 { a + b }";
+
+        let mut passed = sample_result();
+        passed.findings.push(Finding::new(
+            Severity::Info,
+            "style",
+            "Consider documenting add",
+        ));
+        bank.judge("pass-1", code, "rust", &passed, 1, 3).unwrap();
+
+        let matches = bank.retrieve(code, "rust");
+        assert!(known_antipatterns(&matches, 0.7).is_empty());
+    }
+}
+
+// Testes de concorrência (servidor MCP e CLI no mesmo banco)
+mod concurrency_tests {
+    use super::*;
+
+    #[test]
+    fn test_two_banks_interleave_judge_calls() {
+        let (_temp_dir, db_path) = temp_db_path();
+        // Cria o schema antes de abrir as conexões concorrentes
+        drop(ReasoningBank::new(&db_path).expect("Failed to create bank"));
+
+        let handles: Vec<_> = (0..2)
+            .map(|worker| {
+                let db_path = db_path.clone();
+                std::thread::spawn(move || {
+                    let mut bank = ReasoningBank::new(&db_path).expect("Failed to open bank");
+                    for i in 0..25 {
+                        let mut result =
+                            EvaluationResult::failure(format!("w{}-{}", worker, i), 40, "issues");
+                        result.findings.push(tetrad::types::responses::Finding::new(
+                            tetrad::types::responses::Severity::Error,
+                            "logic",
+                            "shared issue",
+                        ));
+                        bank.judge(
+                            &format!("w{}-{}", worker, i),
+                            "fn shared() {}",
+                            "rust",
+                            &result,
+                            1,
+                            3,
+                        )
+                        .expect("judge failed under contention");
+
+                        // Leituras concorrentes, como `tetrad history`
+                        let _ = bank.distill();
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("worker panicked");
+        }
+
+        let bank = ReasoningBank::new(&db_path).unwrap();
+        assert_eq!(bank.count_trajectories().unwrap(), 50);
+
+        // Nenhuma contagem perdida: o pattern compartilhado viu as 50 falhas
+        let patterns = bank.get_all_patterns().unwrap();
+        assert_eq!(patterns.len(), 1);
+        assert_eq!(patterns[0].failure_count, 50);
+    }
+}